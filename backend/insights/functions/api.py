@@ -638,6 +638,8 @@ def update_device_profile(hardware_id: str):
     - soil_type: Soil type (potting_mix, coco_coir, peat, soil, hydroponic)
     - pot_size: Pot size in liters (positive number)
     - expected_interval_sec: Expected time between readings in seconds
+    - temp_offset_c, humidity_offset_pct, soil_moisture_offset_pct: Calibration
+      offsets the data plane adds to sensor values at ingestion (numbers)
 
     Preserves learned patterns when updating.
 
@@ -648,6 +650,7 @@ def update_device_profile(hardware_id: str):
     Requirements: 15.1, 15.3-15.6
     """
     from aws_lambda_powertools.event_handler.exceptions import BadRequestError
+    import math
     import sys
     import os
     import time
@@ -719,6 +722,16 @@ def update_device_profile(hardware_id: str):
             except (ValueError, TypeError):
                 raise BadRequestError("expected_interval_sec must be a positive integer")
 
+        # Validate calibration offsets if provided
+        calibration = {}
+        for name in ("temp_offset_c", "humidity_offset_pct", "soil_moisture_offset_pct"):
+            value = body.get(name)
+            if value is None:
+                continue
+            if isinstance(value, bool) or not isinstance(value, (int, float)) or not math.isfinite(value):
+                raise BadRequestError(f"{name} must be a number")
+            calibration[name] = float(value)
+
         # Fetch existing profile to preserve learned patterns
         response = device_profiles_table.get_item(
             Key={'hardware_id': hardware_id}
@@ -742,6 +755,8 @@ def update_device_profile(hardware_id: str):
             existing_profile.pot_size_liters = pot_size
         if expected_interval_sec is not None:
             existing_profile.expected_interval_sec = expected_interval_sec
+        for name, value in calibration.items():
+            setattr(existing_profile, name, value)
 
         # Update timestamp
         existing_profile.updated_at_ms = int(time.time() * 1000)
@@ -279,6 +279,10 @@ class DeviceProfile:
     baseline_moisture_range: Optional[Dict[str, float]] = None  # {"min": x, "max": y}
     typical_watering_interval_sec: Optional[int] = None
     last_watering_events: List[int] = field(default_factory=list)  # List of timestamp_ms
+    # Calibration offsets added to sensor values at ingestion by the data plane
+    temp_offset_c: Optional[float] = None
+    humidity_offset_pct: Optional[float] = None
+    soil_moisture_offset_pct: Optional[float] = None
     updated_at_ms: Optional[int] = None
 
     def to_dynamodb_item(self) -> Dict[str, Any]:
@@ -299,6 +303,12 @@ class DeviceProfile:
             item["typical_watering_interval_sec"] = self.typical_watering_interval_sec
         if self.last_watering_events:
             item["last_watering_events"] = self.last_watering_events
+        if self.temp_offset_c is not None:
+            item["temp_offset_c"] = self.temp_offset_c
+        if self.humidity_offset_pct is not None:
+            item["humidity_offset_pct"] = self.humidity_offset_pct
+        if self.soil_moisture_offset_pct is not None:
+            item["soil_moisture_offset_pct"] = self.soil_moisture_offset_pct
         if self.updated_at_ms is not None:
             item["updated_at_ms"] = self.updated_at_ms
         return item
@@ -315,6 +325,9 @@ class DeviceProfile:
             baseline_moisture_range=item.get("baseline_moisture_range"),
             typical_watering_interval_sec=item.get("typical_watering_interval_sec"),
             last_watering_events=item.get("last_watering_events", []),
+            temp_offset_c=item.get("temp_offset_c"),
+            humidity_offset_pct=item.get("humidity_offset_pct"),
+            soil_moisture_offset_pct=item.get("soil_moisture_offset_pct"),
             updated_at_ms=item.get("updated_at_ms")
         )
 
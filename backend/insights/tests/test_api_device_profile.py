@@ -225,3 +225,43 @@ class TestUpdateDeviceProfile:
         assert body['profile']['soil_type'] == 'potting_mix'  # unchanged
         assert body['profile']['pot_size_liters'] == 5.0  # unchanged
         assert body['profile']['expected_interval_sec'] == 300  # unchanged
+
+    @patch('functions.api.device_profiles_table')
+    def test_update_profile_calibration_offsets(self, mock_table, put_profile_event, sample_profile):
+        """Test calibration offsets are stored and kept by later updates."""
+        mock_table.get_item.return_value = {'Item': sample_profile}
+        mock_table.put_item.return_value = {}
+        put_profile_event['body'] = json.dumps({
+            "soil_moisture_offset_pct": -5,
+            "temp_offset_c": 0.5
+        })
+
+        from functions.api import app
+        response = app.resolve(put_profile_event, Mock())
+
+        assert response['statusCode'] == 200
+        body = json.loads(response['body'])
+        assert body['profile']['soil_moisture_offset_pct'] == -5.0
+        assert body['profile']['temp_offset_c'] == 0.5
+        assert 'humidity_offset_pct' not in body['profile']
+
+        # A later update of another field keeps the stored offsets
+        mock_table.get_item.return_value = {'Item': body['profile']}
+        put_profile_event['body'] = json.dumps({"plant_type": "monstera"})
+        response = app.resolve(put_profile_event, Mock())
+
+        body = json.loads(response['body'])
+        assert body['profile']['soil_moisture_offset_pct'] == -5.0
+
+    def test_update_profile_invalid_calibration_offset(self, put_profile_event):
+        """Test a non-numeric calibration offset returns 400."""
+        put_profile_event['body'] = json.dumps({
+            "humidity_offset_pct": "high"
+        })
+
+        from functions.api import app
+        response = app.resolve(put_profile_event, Mock())
+
+        assert response['statusCode'] == 400
+        body = json.loads(response['body'])
+        assert 'humidity_offset_pct must be a number' in body['message']
@@ -1,11 +1,15 @@
 use aws_sdk_dynamodb::Client as DynamoDbClient;
 use chrono::DateTime;
-use sha2::{Digest, Sha256};
+use std::future::Future;
 
-use crate::error::AuthError;
-use crate::repo::api_keys::{get_api_key_by_hash, update_last_used};
+use crate::config::ApiKeyHashScheme;
+use crate::error::{AuthError, DatabaseError};
+use crate::repo::api_keys::{get_api_key_by_hash, update_api_key_hash, update_last_used};
+use esp32_backend::shared::api_key_hash::{argon2_digest, sha256_digest};
+use esp32_backend::shared::best_effort::BestEffortQueue;
 use esp32_backend::shared::domain::ApiKey;
-use esp32_backend::shared::time::Clock;
+use esp32_backend::shared::time::{Clock, FixedClock};
+use esp32_backend::shared::validators::is_api_key_format;
 
 /// Hash an API key using SHA-256 with a pepper from environment variable
 ///
@@ -33,40 +37,253 @@ use esp32_backend::shared::time::Clock;
 pub fn hash_api_key(key: &str) -> Result<String, AuthError> {
     // Load pepper from environment variable
     let pepper = std::env::var("API_KEY_PEPPER").map_err(|_| AuthError::ConfigError)?;
+    Ok(sha256_digest(&pepper, key))
+}
+
+/// Hash an API key using Argon2id keyed with the pepper
+///
+/// Keys are looked up by hash through a GSI, so the hash must be deterministic:
+/// the salt is derived from the pepper rather than generated per key. Raw keys
+/// are 256-bit random values, so the salt only has to keep hashes specific to
+/// this deployment; Argon2's memory-hard cost is what makes a leaked table
+/// expensive to brute-force.
+///
+/// # Returns
+/// * `Result<String, AuthError>` - The hex-encoded 32-byte Argon2id output (untagged)
+///
+/// # Errors
+/// * `AuthError::ConfigError` - If API_KEY_PEPPER environment variable is not set
+pub fn hash_api_key_argon2(key: &str) -> Result<String, AuthError> {
+    let pepper = std::env::var("API_KEY_PEPPER").map_err(|_| AuthError::ConfigError)?;
+    argon2_digest(&pepper, key).map_err(|_| AuthError::ConfigError)
+}
+
+/// Hash an API key with `scheme` and tag it for storage (`sha256$...`/`argon2$...`)
+pub fn hash_api_key_with_scheme(key: &str, scheme: ApiKeyHashScheme) -> Result<String, AuthError> {
+    let pepper = std::env::var("API_KEY_PEPPER").map_err(|_| AuthError::ConfigError)?;
+    tagged_hash(&pepper, key, scheme)
+}
+
+/// `hash_api_key_with_scheme` with an explicit pepper
+fn tagged_hash(pepper: &str, key: &str, scheme: ApiKeyHashScheme) -> Result<String, AuthError> {
+    let digest = match scheme {
+        ApiKeyHashScheme::Sha256 => sha256_digest(pepper, key),
+        ApiKeyHashScheme::Argon2 => {
+            argon2_digest(pepper, key).map_err(|_| AuthError::ConfigError)?
+        }
+    };
+
+    Ok(format!("{}${}", scheme.tag(), digest))
+}
+
+/// Peppers API keys are hashed with
+///
+/// During a pepper rotation `API_KEY_PEPPER` holds the new pepper and
+/// `API_KEY_PEPPER_PREVIOUS` the old one, so keys hashed with the old pepper
+/// keep validating until they are re-hashed. `API_KEY_PEPPER_VERSION`
+/// (default 1) numbers the current pepper and is stored as `pepper_version`
+/// with every hash written, so keys still on an older pepper can be found
+/// before the previous pepper is removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Peppers {
+    pub current: String,
+    pub previous: Option<String>,
+    pub version: u32,
+}
 
-    // Create SHA-256 hasher
-    let mut hasher = Sha256::new();
+impl Peppers {
+    /// Read the peppers from the environment
+    ///
+    /// # Errors
+    /// * `AuthError::ConfigError` - API_KEY_PEPPER is not set, or
+    ///   API_KEY_PEPPER_VERSION is not a positive integer
+    pub fn from_env() -> Result<Self, AuthError> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, AuthError> {
+        let current = lookup("API_KEY_PEPPER").ok_or(AuthError::ConfigError)?;
+        let previous = lookup("API_KEY_PEPPER_PREVIOUS").filter(|p| !p.trim().is_empty());
+        let version = match lookup("API_KEY_PEPPER_VERSION") {
+            Some(value) => value
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or(AuthError::ConfigError)?,
+            None => 1,
+        };
+
+        Ok(Self {
+            current,
+            previous,
+            version,
+        })
+    }
+
+    /// Peppers to try, current first; `true` marks the previous pepper
+    fn lookup_order(&self) -> impl Iterator<Item = (&str, bool)> {
+        std::iter::once((self.current.as_str(), false))
+            .chain(self.previous.as_deref().map(|previous| (previous, true)))
+    }
+}
+
+/// Scheme a stored `api_key_hash` was written with
+///
+/// Hashes stored before scheme tags existed have no `$` and are SHA-256.
+/// Returns `None` for a tag this build does not know.
+pub fn stored_hash_scheme(stored_hash: &str) -> Option<ApiKeyHashScheme> {
+    match stored_hash.split_once('$') {
+        Some((tag, _)) => ApiKeyHashScheme::from_tag(tag),
+        None => Some(ApiKeyHashScheme::Sha256),
+    }
+}
+
+/// Stored-hash forms a key is looked up by, in the order they are tried
+///
+/// `None` is a legacy untagged SHA-256 hash. The SHA-256 forms are cheap and
+/// come first. Hashes are only ever upgraded to the configured scheme, so an
+/// `argon2$` hash can only exist once `API_KEY_HASH_SCHEME=argon2` is set; the
+/// Argon2 form, which costs a memory-hard hash, is only tried then. A key that
+/// matches no record therefore costs two queries and no Argon2 hash under the
+/// default `sha256` scheme.
+fn lookup_order(configured: ApiKeyHashScheme) -> &'static [Option<ApiKeyHashScheme>] {
+    match configured {
+        ApiKeyHashScheme::Sha256 => &[None, Some(ApiKeyHashScheme::Sha256)],
+        ApiKeyHashScheme::Argon2 => &[
+            None,
+            Some(ApiKeyHashScheme::Sha256),
+            Some(ApiKeyHashScheme::Argon2),
+        ],
+    }
+}
 
-    // Hash: pepper || key
-    // This ensures that even if the database is leaked, attackers cannot
-    // verify API keys without knowing the pepper
-    hasher.update(pepper.as_bytes());
-    hasher.update(key.as_bytes());
+/// The `api_key_hash` value a key would be stored under in one lookup form
+fn lookup_hash(
+    pepper: &str,
+    key: &str,
+    form: Option<ApiKeyHashScheme>,
+) -> Result<String, AuthError> {
+    match form {
+        None => Ok(sha256_digest(pepper, key)),
+        Some(scheme) => tagged_hash(pepper, key, scheme),
+    }
+}
 
-    // Return hex-encoded hash
-    Ok(hex::encode(hasher.finalize()))
+/// An API key record and whether it was found under the previous pepper
+#[derive(Debug)]
+struct FoundKey {
+    record: ApiKey,
+    matched_previous_pepper: bool,
+}
+
+/// Every pepper and stored-hash form a key is looked up under, in order
+///
+/// The SHA-256 forms are tried under each pepper, current first, before any
+/// Argon2 form, so a key not yet re-hashed off the previous pepper costs no
+/// Argon2 hash. `true` marks the previous pepper. A key that matches no record
+/// costs:
+///
+/// | `API_KEY_HASH_SCHEME` | No rotation | During a rotation |
+/// |---|---|---|
+/// | `sha256` | 2 queries | 4 queries |
+/// | `argon2` | 3 queries, 1 Argon2id hash | 6 queries, 2 Argon2id hashes |
+fn lookup_plan(
+    peppers: &Peppers,
+    configured: ApiKeyHashScheme,
+) -> Vec<(&str, bool, Option<ApiKeyHashScheme>)> {
+    let mut plan = Vec::new();
+    for argon2 in [false, true] {
+        for (pepper, is_previous) in peppers.lookup_order() {
+            for &form in lookup_order(configured) {
+                if (form == Some(ApiKeyHashScheme::Argon2)) == argon2 {
+                    plan.push((pepper, is_previous, form));
+                }
+            }
+        }
+    }
+
+    plan
+}
+
+/// Find an API key record by trying each pepper and stored-hash form in
+/// `lookup_plan` order
+///
+/// `lookup` fetches the record stored under one hash. The search stops at the
+/// first record found.
+async fn find_api_key<F, Fut>(
+    api_key: &str,
+    hash_scheme: ApiKeyHashScheme,
+    peppers: &Peppers,
+    lookup: F,
+) -> Result<FoundKey, AuthError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Option<ApiKey>, DatabaseError>>,
+{
+    for (pepper, is_previous, form) in lookup_plan(peppers, hash_scheme) {
+        let key_hash = lookup_hash(pepper, api_key, form)?;
+
+        let api_key_record = lookup(key_hash).await.map_err(|_| AuthError::InvalidKey)?;
+
+        if let Some(record) = api_key_record {
+            return Ok(FoundKey {
+                record,
+                matched_previous_pepper: is_previous,
+            });
+        }
+    }
+
+    Err(AuthError::InvalidKey)
+}
+
+/// Whether a stored hash should be re-hashed under the configured scheme
+fn needs_rehash(stored_hash: &str, configured: ApiKeyHashScheme) -> bool {
+    stored_hash_scheme(stored_hash).is_some_and(|scheme| scheme < configured)
+}
+
+/// Check that a raw API key has the shape of an issued key
+///
+/// Issued keys are 64 hex characters. Anything else cannot match a stored
+/// hash, so it is rejected before hashing or querying DynamoDB.
+///
+/// # Errors
+/// * `AuthError::MalformedKey` - Empty, wrong length, or not hex
+pub fn validate_api_key_format(key: &str) -> Result<(), AuthError> {
+    if is_api_key_format(key) {
+        Ok(())
+    } else {
+        Err(AuthError::MalformedKey)
+    }
 }
 
 /// Validate an API key against the DynamoDB api_keys table
 ///
 /// This function:
-/// 1. Hashes the incoming API key
-/// 2. Queries the api_keys table GSI_hash by api_key_hash
-/// 3. Checks if the key is active (is_active=true)
-/// 4. Updates last_used_at if needed (throttled to 5-minute intervals)
+/// 1. Rejects keys that are not 64 hex characters (see `validate_api_key_format`)
+/// 2. Hashes the incoming API key with each pepper (see `Peppers`) and stored-hash form (see `lookup_plan`)
+/// 3. Queries the api_keys table GSI_hash by api_key_hash until a record matches
+/// 4. Checks if the key is active (is_active=true)
+/// 5. Queues a last_used_at update if needed (throttled to `last_used_throttle_minutes`)
+/// 6. Queues a re-hash with the current pepper if the record matched the
+///    previous pepper or its hash scheme is older than `hash_scheme`
 ///
 /// # Arguments
 /// * `client` - DynamoDB client
 /// * `table_name` - Name of the api_keys table
 /// * `api_key` - Raw API key from X-API-Key header
 /// * `clock` - Clock implementation for timestamp generation
+/// * `last_used_throttle_minutes` - Minimum age of last_used_at before it is rewritten
+/// * `hash_scheme` - Scheme stored hashes are upgraded to
+/// * `writes` - Queue for the best-effort last_used_at and re-hash updates
 ///
 /// # Returns
 /// * `Ok(ApiKey)` - Valid and active API key record
 /// * `Err(AuthError)` - Invalid, inactive, or not found
 ///
 /// # Errors
-/// * `AuthError::ConfigError` - API_KEY_PEPPER not set
+/// * `AuthError::MalformedKey` - Key is not a 64-character hex string
+/// * `AuthError::ConfigError` - API_KEY_PEPPER not set or API_KEY_PEPPER_VERSION invalid
 /// * `AuthError::InvalidKey` - Key not found in database
 /// * `AuthError::KeyRevoked` - Key exists but is_active=false
 pub async fn validate_api_key(
@@ -74,64 +291,98 @@ pub async fn validate_api_key(
     table_name: &str,
     api_key: &str,
     clock: &dyn Clock,
+    last_used_throttle_minutes: i64,
+    hash_scheme: ApiKeyHashScheme,
+    writes: &BestEffortQueue,
 ) -> Result<ApiKey, AuthError> {
-    // Hash the incoming API key
-    let key_hash = hash_api_key(api_key)?;
-
-    // Query the api_keys table by hash
-    let api_key_record = get_api_key_by_hash(client, table_name, &key_hash)
-        .await
-        .map_err(|_| AuthError::InvalidKey)?;
+    validate_api_key_format(api_key)?;
 
-    // Check if key was found
-    let api_key_record = api_key_record.ok_or(AuthError::InvalidKey)?;
+    // Look the key up under each pepper and stored-hash form
+    let peppers = Peppers::from_env()?;
+    let found = find_api_key(api_key, hash_scheme, &peppers, |key_hash| async move {
+        get_api_key_by_hash(client, table_name, &key_hash).await
+    })
+    .await?;
+    let api_key_record = found.record;
 
     // Check if key is active
     if !api_key_record.is_active {
         return Err(AuthError::KeyRevoked);
     }
 
-    // Update last_used_at if needed (throttled to 5-minute intervals)
-    if should_update_last_used(&api_key_record.last_used_at, clock) {
-        // Ignore errors from update - validation succeeded, update is best-effort
-        let _ = update_last_used(client, table_name, &api_key_record.key_id, clock).await;
+    // Update last_used_at if needed (throttled to reduce write costs)
+    if should_update_last_used(
+        &api_key_record.last_used_at,
+        clock,
+        last_used_throttle_minutes,
+    ) {
+        // Ignore errors from update - validation succeeded, update is best-effort.
+        // Snapshot the request time so the write records when the key was used,
+        // not when the queued write happens to run.
+        let client = client.clone();
+        let table_name = table_name.to_string();
+        let key_id = api_key_record.key_id.clone();
+        let used_at = FixedClock::from_epoch_seconds(clock.now_epoch_seconds());
+        writes.submit(async move {
+            let _ = update_last_used(&client, &table_name, &key_id, &used_at).await;
+        });
+    }
+
+    // Upgrade an older stored hash now that the raw key is known. Best-effort:
+    // if it fails the key keeps validating and is upgraded on a later request.
+    if found.matched_previous_pepper || needs_rehash(&api_key_record.api_key_hash, hash_scheme) {
+        let new_hash = tagged_hash(&peppers.current, api_key, hash_scheme)?;
+        let client = client.clone();
+        let table_name = table_name.to_string();
+        let key_id = api_key_record.key_id.clone();
+        let old_hash = api_key_record.api_key_hash.clone();
+        let pepper_version = peppers.version;
+        writes.submit(async move {
+            let _ = update_api_key_hash(
+                &client,
+                &table_name,
+                &key_id,
+                &old_hash,
+                &new_hash,
+                pepper_version,
+            )
+            .await;
+        });
     }
 
     Ok(api_key_record)
 }
 
-/// Determine if last_used_at should be updated based on 5-minute throttling
+/// Determine if last_used_at should be updated based on the throttle window
 ///
 /// Returns true if:
 /// - last_used_at is None (never used before)
-/// - last_used_at is older than 5 minutes
+/// - last_used_at is at least `throttle_minutes` old
 ///
 /// # Arguments
 /// * `last_used_at` - Optional RFC3339 timestamp of last use
 /// * `clock` - Clock implementation for getting current time
+/// * `throttle_minutes` - Minimum age in minutes before an update is written
 ///
 /// # Returns
 /// * `bool` - true if update is needed, false otherwise
-fn should_update_last_used(last_used_at: &Option<String>, clock: &dyn Clock) -> bool {
+fn should_update_last_used(
+    last_used_at: &Option<String>,
+    clock: &dyn Clock,
+    throttle_minutes: i64,
+) -> bool {
     match last_used_at {
         None => true, // Never used before, should update
         Some(ts) => {
             // Parse the last_used_at timestamp
-            let last_used = match DateTime::parse_from_rfc3339(ts) {
-                Ok(dt) => dt.with_timezone(&chrono::Utc),
+            let last_used_ms = match DateTime::parse_from_rfc3339(ts) {
+                Ok(dt) => dt.timestamp_millis(),
                 Err(_) => return true, // Invalid timestamp, update it
             };
 
-            // Get current time
-            let now_str = clock.now_rfc3339();
-            let now = match DateTime::parse_from_rfc3339(&now_str) {
-                Ok(dt) => dt.with_timezone(&chrono::Utc),
-                Err(_) => return true, // Can't parse current time, update anyway
-            };
-
-            // Check if more than 5 minutes have passed
-            let duration = now.signed_duration_since(last_used);
-            duration.num_minutes() >= 5
+            // Check if the throttle window has passed
+            let elapsed_minutes = (clock.now_epoch_ms() - last_used_ms) / 60_000;
+            elapsed_minutes >= throttle_minutes
         }
     }
 }
@@ -141,6 +392,7 @@ mod tests {
     use super::*;
     use esp32_backend::shared::domain::ApiKey;
     use esp32_backend::shared::time::FixedClock;
+    use sha2::{Digest, Sha256};
 
     // Helper to set up test environment with unique pepper
     fn setup_test_pepper(pepper: &str) {
@@ -274,6 +526,323 @@ mod tests {
         assert_eq!(hash, expected_hash);
     }
 
+    #[test]
+    fn test_hash_api_key_with_scheme_tags_hash() {
+        setup_test_pepper("test-pepper-secret-tagged");
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+
+        let sha256 = hash_api_key_with_scheme(key, ApiKeyHashScheme::Sha256).unwrap();
+        let argon2 = hash_api_key_with_scheme(key, ApiKeyHashScheme::Argon2).unwrap();
+
+        assert!(sha256.starts_with("sha256$"));
+        assert_eq!(sha256.len(), "sha256$".len() + 64);
+        assert!(argon2.starts_with("argon2$"));
+        assert_eq!(argon2.len(), "argon2$".len() + 64);
+        assert_ne!(sha256["sha256$".len()..], argon2["argon2$".len()..]);
+    }
+
+    #[test]
+    fn test_stored_hash_scheme_dispatch() {
+        let digest = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+
+        // Legacy hashes have no tag and are SHA-256
+        assert_eq!(stored_hash_scheme(digest), Some(ApiKeyHashScheme::Sha256));
+        assert_eq!(
+            stored_hash_scheme(&format!("sha256${}", digest)),
+            Some(ApiKeyHashScheme::Sha256)
+        );
+        assert_eq!(
+            stored_hash_scheme(&format!("argon2${}", digest)),
+            Some(ApiKeyHashScheme::Argon2)
+        );
+        assert_eq!(stored_hash_scheme(&format!("scrypt${}", digest)), None);
+    }
+
+    #[test]
+    fn test_needs_rehash_only_for_older_schemes() {
+        let digest = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        let legacy = digest.to_string();
+        let sha256 = format!("sha256${}", digest);
+        let argon2 = format!("argon2${}", digest);
+
+        assert!(needs_rehash(&legacy, ApiKeyHashScheme::Argon2));
+        assert!(needs_rehash(&sha256, ApiKeyHashScheme::Argon2));
+        assert!(!needs_rehash(&argon2, ApiKeyHashScheme::Argon2));
+
+        // The default scheme never re-hashes, including a newer stored hash
+        assert!(!needs_rehash(&legacy, ApiKeyHashScheme::Sha256));
+        assert!(!needs_rehash(&argon2, ApiKeyHashScheme::Sha256));
+        assert!(!needs_rehash(
+            &format!("scrypt${}", digest),
+            ApiKeyHashScheme::Argon2
+        ));
+    }
+
+    #[test]
+    fn test_lookup_order_tries_sha256_forms_first() {
+        assert_eq!(
+            lookup_order(ApiKeyHashScheme::Sha256),
+            &[None, Some(ApiKeyHashScheme::Sha256)]
+        );
+        assert_eq!(
+            lookup_order(ApiKeyHashScheme::Argon2),
+            &[
+                None,
+                Some(ApiKeyHashScheme::Sha256),
+                Some(ApiKeyHashScheme::Argon2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lookup_hash_verifies_stored_hashes() {
+        setup_test_pepper("test-pepper-secret-lookup");
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        let other_key = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
+        let pepper = "test-pepper-secret-lookup";
+
+        // A legacy untagged hash is matched by the untagged form
+        let legacy = hash_api_key(key).unwrap();
+        assert_eq!(lookup_hash(pepper, key, None).unwrap(), legacy);
+        assert_ne!(lookup_hash(pepper, other_key, None).unwrap(), legacy);
+
+        // A tagged hash is matched by its own scheme's form only
+        let argon2 = hash_api_key_with_scheme(key, ApiKeyHashScheme::Argon2).unwrap();
+        assert_eq!(
+            lookup_hash(pepper, key, Some(ApiKeyHashScheme::Argon2)).unwrap(),
+            argon2
+        );
+        assert_ne!(
+            lookup_hash(pepper, key, Some(ApiKeyHashScheme::Sha256)).unwrap(),
+            argon2
+        );
+    }
+
+    // ============================================================================
+    // Pepper Rotation Tests
+    // ============================================================================
+
+    fn peppers_from(pairs: &[(&str, &str)]) -> Result<Peppers, AuthError> {
+        Peppers::from_lookup(|name| {
+            pairs
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    fn rotating_peppers() -> Peppers {
+        Peppers {
+            current: "pepper-new".to_string(),
+            previous: Some("pepper-old".to_string()),
+            version: 2,
+        }
+    }
+
+    fn stored_key(api_key_hash: &str) -> ApiKey {
+        ApiKey {
+            key_id: "test-key-id-123".to_string(),
+            api_key_hash: api_key_hash.to_string(),
+            created_at: "2024-01-15T10:00:00Z".to_string(),
+            last_used_at: None,
+            is_active: true,
+            description: None,
+            scopes: vec![],
+            rate_limit_per_minute: None,
+        }
+    }
+
+    /// Find a key in a table holding a single record
+    async fn find_in(
+        api_key: &str,
+        peppers: &Peppers,
+        record: &ApiKey,
+    ) -> Result<FoundKey, AuthError> {
+        find_api_key(api_key, ApiKeyHashScheme::Sha256, peppers, |key_hash| {
+            let found = (key_hash == record.api_key_hash).then(|| record.clone());
+            async move { Ok(found) }
+        })
+        .await
+    }
+
+    /// The hashes looked up for a key that matches no record, in order
+    async fn lookups_for_unknown_key(
+        hash_scheme: ApiKeyHashScheme,
+        peppers: &Peppers,
+    ) -> Vec<String> {
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        let lookups = std::sync::Mutex::new(Vec::new());
+
+        let result = find_api_key(key, hash_scheme, peppers, |key_hash| {
+            lookups.lock().unwrap().push(key_hash);
+            async { Ok(None) }
+        })
+        .await;
+        assert!(matches!(result, Err(AuthError::InvalidKey)));
+
+        lookups.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_skips_argon2_under_sha256_scheme() {
+        let peppers = Peppers {
+            previous: None,
+            ..rotating_peppers()
+        };
+
+        // Legacy and sha256$ forms only: two queries, no Argon2 hash
+        let lookups = lookups_for_unknown_key(ApiKeyHashScheme::Sha256, &peppers).await;
+        assert_eq!(lookups.len(), 2);
+        assert!(lookups.iter().all(|hash| !hash.starts_with("argon2$")));
+
+        // Under argon2 the Argon2 form is tried once, after the SHA-256 forms
+        let lookups = lookups_for_unknown_key(ApiKeyHashScheme::Argon2, &peppers).await;
+        assert_eq!(lookups.len(), 3);
+        assert!(lookups[2].starts_with("argon2$"));
+    }
+
+    #[test]
+    fn test_peppers_from_lookup() {
+        let peppers = peppers_from(&[("API_KEY_PEPPER", "pepper-new")]).unwrap();
+        assert_eq!(peppers.previous, None);
+        assert_eq!(peppers.version, 1);
+
+        let peppers = peppers_from(&[
+            ("API_KEY_PEPPER", "pepper-new"),
+            ("API_KEY_PEPPER_PREVIOUS", "pepper-old"),
+            ("API_KEY_PEPPER_VERSION", "2"),
+        ])
+        .unwrap();
+        assert_eq!(peppers, rotating_peppers());
+
+        // A blank previous pepper means no rotation in progress
+        let peppers = peppers_from(&[
+            ("API_KEY_PEPPER", "pepper-new"),
+            ("API_KEY_PEPPER_PREVIOUS", " "),
+        ])
+        .unwrap();
+        assert_eq!(peppers.previous, None);
+
+        assert!(matches!(peppers_from(&[]), Err(AuthError::ConfigError)));
+        for invalid in ["0", "-1", "v2"] {
+            assert!(matches!(
+                peppers_from(&[
+                    ("API_KEY_PEPPER", "pepper-new"),
+                    ("API_KEY_PEPPER_VERSION", invalid)
+                ]),
+                Err(AuthError::ConfigError)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_previous_pepper_key_validates_and_is_rehashed() {
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        let peppers = rotating_peppers();
+
+        // Stored before the rotation as a legacy hash under the old pepper
+        let record = stored_key(&sha256_digest("pepper-old", key));
+        let found = find_in(key, &peppers, &record).await.unwrap();
+        assert_eq!(found.record.key_id, "test-key-id-123");
+        assert!(found.matched_previous_pepper);
+
+        // The re-hash moves it to the current pepper, where it is found first
+        let rehashed =
+            stored_key(&tagged_hash(&peppers.current, key, ApiKeyHashScheme::Sha256).unwrap());
+        assert_ne!(rehashed.api_key_hash, record.api_key_hash);
+        let found = find_in(key, &peppers, &rehashed).await.unwrap();
+        assert!(!found.matched_previous_pepper);
+    }
+
+    #[tokio::test]
+    async fn test_key_matching_neither_pepper_is_rejected() {
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        let record = stored_key(&sha256_digest("pepper-unknown", key));
+
+        let result = find_in(key, &rotating_peppers(), &record).await;
+        assert!(matches!(result, Err(AuthError::InvalidKey)));
+
+        // Once the previous pepper is removed, old-pepper keys stop matching
+        let record = stored_key(&sha256_digest("pepper-old", key));
+        let peppers = Peppers {
+            previous: None,
+            ..rotating_peppers()
+        };
+        let result = find_in(key, &peppers, &record).await;
+        assert!(matches!(result, Err(AuthError::InvalidKey)));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_during_rotation_tries_sha256_forms_first() {
+        let peppers = rotating_peppers();
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+
+        // Both peppers' SHA-256 forms, current first: four queries, no Argon2 hash
+        let lookups = lookups_for_unknown_key(ApiKeyHashScheme::Sha256, &peppers).await;
+        assert_eq!(
+            lookups,
+            vec![
+                sha256_digest("pepper-new", key),
+                format!("sha256${}", sha256_digest("pepper-new", key)),
+                sha256_digest("pepper-old", key),
+                format!("sha256${}", sha256_digest("pepper-old", key)),
+            ]
+        );
+
+        // Under argon2 the Argon2 forms follow every SHA-256 form
+        let lookups = lookups_for_unknown_key(ApiKeyHashScheme::Argon2, &peppers).await;
+        assert_eq!(lookups.len(), 6);
+        assert!(lookups[..4].iter().all(|hash| !hash.starts_with("argon2$")));
+        assert!(lookups[4..].iter().all(|hash| hash.starts_with("argon2$")));
+    }
+
+    // ============================================================================
+    // Key Format Tests
+    // ============================================================================
+
+    #[test]
+    fn test_validate_api_key_format_empty() {
+        assert!(matches!(
+            validate_api_key_format(""),
+            Err(AuthError::MalformedKey)
+        ));
+    }
+
+    #[test]
+    fn test_validate_api_key_format_wrong_length() {
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        for malformed in [&key[..63], &format!("{}0", key), "test-key-123"] {
+            assert!(matches!(
+                validate_api_key_format(malformed),
+                Err(AuthError::MalformedKey)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_api_key_format_non_hex() {
+        use esp32_backend::shared::validators::API_KEY_HEX_LEN;
+
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542dg";
+        assert_eq!(key.len(), API_KEY_HEX_LEN);
+        assert!(matches!(
+            validate_api_key_format(key),
+            Err(AuthError::MalformedKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_well_formed_unknown_key_is_invalid_after_lookup() {
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        assert!(validate_api_key_format(key).is_ok());
+        assert!(validate_api_key_format(&key.to_uppercase()).is_ok());
+
+        let record = stored_key(&sha256_digest("pepper-unknown", key));
+        let result = find_in(key, &rotating_peppers(), &record).await;
+        assert!(matches!(result, Err(AuthError::InvalidKey)));
+    }
+
     // ============================================================================
     // Last Used Throttling Tests
     // ============================================================================
@@ -284,7 +853,7 @@ mod tests {
         let last_used_at = None;
 
         // Should update if never used before
-        assert!(should_update_last_used(&last_used_at, &clock));
+        assert!(should_update_last_used(&last_used_at, &clock, 5));
     }
 
     #[test]
@@ -294,7 +863,7 @@ mod tests {
         let last_used_at = Some("2024-01-15T10:28:00Z".to_string());
 
         // Should NOT update if used within last 5 minutes
-        assert!(!should_update_last_used(&last_used_at, &clock));
+        assert!(!should_update_last_used(&last_used_at, &clock, 5));
     }
 
     #[test]
@@ -304,7 +873,7 @@ mod tests {
         let last_used_at = Some("2024-01-15T10:20:00Z".to_string());
 
         // Should update if used more than 5 minutes ago
-        assert!(should_update_last_used(&last_used_at, &clock));
+        assert!(should_update_last_used(&last_used_at, &clock, 5));
     }
 
     #[test]
@@ -314,7 +883,24 @@ mod tests {
         let last_used_at = Some("2024-01-15T10:25:00Z".to_string());
 
         // Should update if exactly 5 minutes (>= 5 minutes)
-        assert!(should_update_last_used(&last_used_at, &clock));
+        assert!(should_update_last_used(&last_used_at, &clock, 5));
+    }
+
+    #[test]
+    fn test_should_update_last_used_sixty_minute_window() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+
+        // Last used 30 minutes ago - inside a 60-minute window
+        let last_used_at = Some("2024-01-15T10:00:00Z".to_string());
+        assert!(!should_update_last_used(&last_used_at, &clock, 60));
+
+        // Last used 61 minutes ago - outside a 60-minute window
+        let last_used_at = Some("2024-01-15T09:29:00Z".to_string());
+        assert!(should_update_last_used(&last_used_at, &clock, 60));
+
+        // Exactly 60 minutes keeps the >= semantics
+        let last_used_at = Some("2024-01-15T09:30:00Z".to_string());
+        assert!(should_update_last_used(&last_used_at, &clock, 60));
     }
 
     #[test]
@@ -324,7 +910,7 @@ mod tests {
         let last_used_at = Some("invalid-timestamp".to_string());
 
         // Should update if timestamp is invalid (to fix it)
-        assert!(should_update_last_used(&last_used_at, &clock));
+        assert!(should_update_last_used(&last_used_at, &clock, 5));
     }
 
     // ============================================================================
@@ -341,6 +927,8 @@ mod tests {
             last_used_at: Some("2024-01-15T10:20:00Z".to_string()),
             is_active: true,
             description: Some("Test API key".to_string()),
+            scopes: vec![],
+            rate_limit_per_minute: None,
         };
 
         // Active key should pass the is_active check
@@ -357,6 +945,8 @@ mod tests {
             last_used_at: Some("2024-01-15T10:20:00Z".to_string()),
             is_active: false, // Key is revoked
             description: Some("Revoked test API key".to_string()),
+            scopes: vec![],
+            rate_limit_per_minute: None,
         };
 
         // Revoked key should fail the is_active check
@@ -444,14 +1034,16 @@ mod tests {
             last_used_at: Some("2024-01-15T10:20:00Z".to_string()),
             is_active: true,
             description: Some("Test API key".to_string()),
+            scopes: vec![],
+            rate_limit_per_minute: None,
         };
 
         // With recent clock (2 minutes after last_used_at), should NOT update
-        let should_update_recent = should_update_last_used(&api_key.last_used_at, &clock_recent);
+        let should_update_recent = should_update_last_used(&api_key.last_used_at, &clock_recent, 5);
         assert!(!should_update_recent);
 
         // With old clock (10 minutes after last_used_at), should update
-        let should_update_old = should_update_last_used(&api_key.last_used_at, &clock_old);
+        let should_update_old = should_update_last_used(&api_key.last_used_at, &clock_old, 5);
         assert!(should_update_old);
     }
 
@@ -475,6 +1067,8 @@ mod tests {
             last_used_at: Some("2024-01-15T10:20:00Z".to_string()),
             is_active: true,
             description: Some("Test key".to_string()),
+            scopes: vec![],
+            rate_limit_per_minute: None,
         });
 
         // Step 3: Check if key was found
@@ -492,7 +1086,7 @@ mod tests {
 
         // Step 5: Check if last_used_at should be updated
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
-        let should_update = should_update_last_used(&api_key.last_used_at, &clock);
+        let should_update = should_update_last_used(&api_key.last_used_at, &clock, 5);
         assert!(should_update); // 10 minutes have passed, should update
     }
 
@@ -516,6 +1110,8 @@ mod tests {
             last_used_at: Some("2024-01-15T10:20:00Z".to_string()),
             is_active: false, // Revoked
             description: Some("Revoked key".to_string()),
+            scopes: vec![],
+            rate_limit_per_minute: None,
         });
 
         // Step 3: Check if key was found
@@ -1,6 +1,76 @@
 use aws_sdk_dynamodb::Client as DynamoDbClient;
+use esp32_backend::shared::validators::DEFAULT_MAX_BODY_BYTES;
 use std::time::Duration;
 
+/// Default minimum age (minutes) of last_used_at before it is rewritten
+pub const DEFAULT_LAST_USED_THROTTLE_MINUTES: i64 = 5;
+
+/// Default window (seconds) in which a same-boot re-registration skips the device update
+pub const DEFAULT_REGISTER_RETRY_WINDOW_SECONDS: i64 = 300;
+
+/// Default days a stored reading is kept before DynamoDB TTL expires it
+pub const DEFAULT_READINGS_TTL_DAYS: i64 = 90;
+
+/// Default ingest requests an API key may make per minute when rate limiting is enabled
+pub const DEFAULT_INGEST_RATE_LIMIT_PER_MINUTE: u32 = 120;
+
+/// Environment variables the data plane cannot start without
+///
+/// `API_KEY_PEPPER` is read again when API keys are hashed; it is listed here
+/// so a missing pepper fails at cold start instead of on the first request.
+pub const REQUIRED_ENV_VARS: [&str; 5] = [
+    "DEVICES_TABLE",
+    "API_KEYS_TABLE",
+    "PROCESSED_BATCHES_TABLE",
+    "DEVICE_READINGS_TABLE",
+    "API_KEY_PEPPER",
+];
+
+/// How ingestion treats a batch that regresses behind the last stored reading
+/// for the same boot (see `TIME_REGRESSION_MODE`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeRegressionMode {
+    /// No check (no extra read per batch)
+    #[default]
+    Off,
+    /// Store the batch and report a warning in the response
+    Warn,
+    /// Reject the request with 400
+    Reject,
+}
+
+/// How API keys are hashed when they are looked up and upgraded (see `API_KEY_HASH_SCHEME`)
+///
+/// Variants are ordered oldest first, so a stored hash whose scheme compares
+/// less than the configured one is due for an upgrade.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiKeyHashScheme {
+    /// SHA-256 over pepper || key
+    #[default]
+    Sha256,
+    /// Argon2id keyed with the pepper
+    Argon2,
+}
+
+impl ApiKeyHashScheme {
+    /// Tag written before `$` in a stored `api_key_hash`
+    pub fn tag(self) -> &'static str {
+        match self {
+            ApiKeyHashScheme::Sha256 => "sha256",
+            ApiKeyHashScheme::Argon2 => "argon2",
+        }
+    }
+
+    /// Parse a scheme tag (`sha256` or `argon2`)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "sha256" => Some(ApiKeyHashScheme::Sha256),
+            "argon2" => Some(ApiKeyHashScheme::Argon2),
+            _ => None,
+        }
+    }
+}
+
 /// Configuration for the Data Plane API
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -14,11 +84,47 @@ pub struct Config {
     pub processed_batches_table: String,
     /// Device readings table name
     pub device_readings_table: String,
+    /// Minimum minutes between last_used_at writes for an API key
+    pub last_used_throttle_minutes: i64,
+    /// Check for batches that move backwards in time within a boot
+    pub time_regression_mode: TimeRegressionMode,
+    /// Warn when a sensor repeats one value this many times in a batch (None disables)
+    pub stuck_sensor_min_run: Option<usize>,
+    /// Largest request body accepted before JSON parsing, in bytes
+    pub max_body_bytes: usize,
+    /// Accept sensor names and feature keys outside the known set at registration
+    pub allow_unknown_capabilities: bool,
+    /// Seconds after a registration in which a retry from the same boot skips the update (0 disables)
+    pub register_retry_window_seconds: i64,
+    /// Scheme API keys are upgraded to after a successful validation
+    pub api_key_hash_scheme: ApiKeyHashScheme,
+    /// Days after ingestion a reading expires (0 keeps readings forever)
+    pub readings_ttl_days: i64,
+    /// Let a reading replace one already stored at the same ts_batch instead of
+    /// reporting it as a duplicate
+    pub allow_reading_overwrite: bool,
+    /// Rollups table holding the per-key request counters (None disables rate limiting)
+    pub rollups_table: Option<String>,
+    /// Ingest requests per minute for keys without their own limit (0 leaves them unlimited)
+    pub ingest_rate_limit_per_minute: u32,
+    /// Device profiles table holding calibration offsets (None stores readings uncorrected)
+    pub device_profiles_table: Option<String>,
+    /// Reject readings closer than this many milliseconds to the device's previous one (0 disables)
+    pub min_reading_interval_ms: i64,
 }
 
 impl Config {
     /// Create a new Config instance from environment variables
+    ///
+    /// Every variable in `REQUIRED_ENV_VARS` is checked before anything else
+    /// is loaded, and all missing names are reported in one error. Call once
+    /// at cold start.
     pub async fn from_env() -> Result<Self, ConfigError> {
+        // The pepper is only checked here; the auth module reads it when hashing
+        let required = read_required_vars(REQUIRED_ENV_VARS, |name| std::env::var(name).ok())?;
+        let [devices_table, api_keys_table, processed_batches_table, device_readings_table, _] =
+            required;
+
         // Load AWS configuration with behavior version
         let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
             .load()
@@ -36,26 +142,91 @@ impl Config {
 
         let dynamodb_client = DynamoDbClient::from_conf(dynamodb_config);
 
-        // Load table names from environment variables
-        let devices_table = std::env::var("DEVICES_TABLE")
-            .map_err(|_| ConfigError::MissingEnvVar("DEVICES_TABLE".to_string()))?;
+        let last_used_throttle_minutes = match std::env::var("LAST_USED_THROTTLE_MINUTES") {
+            Ok(value) => parse_throttle_minutes(&value)?,
+            Err(_) => DEFAULT_LAST_USED_THROTTLE_MINUTES,
+        };
 
-        let api_keys_table = std::env::var("API_KEYS_TABLE")
-            .map_err(|_| ConfigError::MissingEnvVar("API_KEYS_TABLE".to_string()))?;
+        let time_regression_mode = match std::env::var("TIME_REGRESSION_MODE") {
+            Ok(value) => parse_time_regression_mode(&value)?,
+            Err(_) => TimeRegressionMode::default(),
+        };
 
-        let processed_batches_table = std::env::var("PROCESSED_BATCHES_TABLE")
-            .map_err(|_| ConfigError::MissingEnvVar("PROCESSED_BATCHES_TABLE".to_string()))?;
+        let stuck_sensor_min_run = match std::env::var("STUCK_SENSOR_MIN_RUN") {
+            Ok(value) => Some(parse_stuck_sensor_min_run(&value)?),
+            Err(_) => None,
+        };
 
-        let device_readings_table = std::env::var("DEVICE_READINGS_TABLE")
-            .map_err(|_| ConfigError::MissingEnvVar("DEVICE_READINGS_TABLE".to_string()))?;
+        let max_body_bytes = match std::env::var("MAX_BODY_BYTES") {
+            Ok(value) => parse_max_body_bytes(&value)?,
+            Err(_) => DEFAULT_MAX_BODY_BYTES,
+        };
 
-        Ok(Config {
-            dynamodb_client,
-            devices_table,
-            api_keys_table,
-            processed_batches_table,
-            device_readings_table,
-        })
+        let allow_unknown_capabilities = std::env::var("ALLOW_UNKNOWN_CAPABILITIES")
+            .map(|v| parse_flag(&v))
+            .unwrap_or(false);
+
+        let register_retry_window_seconds = match std::env::var("REGISTER_RETRY_WINDOW_SECONDS") {
+            Ok(value) => parse_register_retry_window_seconds(&value)?,
+            Err(_) => DEFAULT_REGISTER_RETRY_WINDOW_SECONDS,
+        };
+
+        let api_key_hash_scheme = match std::env::var("API_KEY_HASH_SCHEME") {
+            Ok(value) => parse_api_key_hash_scheme(&value)?,
+            Err(_) => ApiKeyHashScheme::default(),
+        };
+
+        let readings_ttl_days = match std::env::var("READINGS_TTL_DAYS") {
+            Ok(value) => parse_readings_ttl_days(&value)?,
+            Err(_) => DEFAULT_READINGS_TTL_DAYS,
+        };
+
+        let allow_reading_overwrite = std::env::var("ALLOW_READING_OVERWRITE")
+            .map(|v| parse_flag(&v))
+            .unwrap_or(false);
+
+        let rollups_table = std::env::var("ROLLUPS_TABLE")
+            .ok()
+            .filter(|table| !table.trim().is_empty());
+
+        let ingest_rate_limit_per_minute = match std::env::var("INGEST_RATE_LIMIT_PER_MINUTE") {
+            Ok(value) => parse_ingest_rate_limit_per_minute(&value)?,
+            Err(_) => DEFAULT_INGEST_RATE_LIMIT_PER_MINUTE,
+        };
+
+        let device_profiles_table = std::env::var("DEVICE_PROFILES_TABLE")
+            .ok()
+            .filter(|table| !table.trim().is_empty());
+
+        let min_reading_interval_ms = match std::env::var("MIN_READING_INTERVAL_MS") {
+            Ok(value) => parse_min_reading_interval_ms(&value)?,
+            Err(_) => 0,
+        };
+
+        ConfigBuilder::new()
+            .devices_table(devices_table)
+            .api_keys_table(api_keys_table)
+            .processed_batches_table(processed_batches_table)
+            .device_readings_table(device_readings_table)
+            .last_used_throttle_minutes(last_used_throttle_minutes)
+            .time_regression_mode(time_regression_mode)
+            .stuck_sensor_min_run(stuck_sensor_min_run)
+            .max_body_bytes(max_body_bytes)
+            .allow_unknown_capabilities(allow_unknown_capabilities)
+            .register_retry_window_seconds(register_retry_window_seconds)
+            .api_key_hash_scheme(api_key_hash_scheme)
+            .readings_ttl_days(readings_ttl_days)
+            .allow_reading_overwrite(allow_reading_overwrite)
+            .rollups_table(rollups_table)
+            .ingest_rate_limit_per_minute(ingest_rate_limit_per_minute)
+            .device_profiles_table(device_profiles_table)
+            .min_reading_interval_ms(min_reading_interval_ms)
+            .build(dynamodb_client)
+    }
+
+    /// Seconds a reading is retained after ingestion, or `None` when readings never expire
+    pub fn readings_retention_seconds(&self) -> Option<i64> {
+        (self.readings_ttl_days > 0).then(|| self.readings_ttl_days * 24 * 3600)
     }
 
     /// Create a test configuration with custom table names
@@ -68,44 +239,355 @@ impl Config {
         processed_batches_table: String,
         device_readings_table: String,
     ) -> Self {
-        use aws_sdk_dynamodb::config::{Credentials, Region};
-
-        // Create test credentials
-        let credentials =
-            Credentials::new("test_access_key", "test_secret_key", None, None, "test");
-
-        // Create DynamoDB client pointing to local endpoint
-        let dynamodb_config = aws_sdk_dynamodb::config::Builder::new()
-            .behavior_version(aws_sdk_dynamodb::config::BehaviorVersion::latest())
-            .region(Region::new("us-east-1"))
-            .credentials_provider(credentials)
-            .endpoint_url(endpoint_url)
-            .timeout_config(
-                aws_sdk_dynamodb::config::timeout::TimeoutConfig::builder()
-                    .operation_timeout(Duration::from_secs(10))
-                    .operation_attempt_timeout(Duration::from_secs(5))
-                    .build(),
-            )
-            .build();
+        ConfigBuilder::new()
+            .devices_table(devices_table)
+            .api_keys_table(api_keys_table)
+            .processed_batches_table(processed_batches_table)
+            .device_readings_table(device_readings_table)
+            .build(local_dynamodb_client(endpoint_url))
+            .expect("test configuration sets every required value")
+    }
+}
 
-        let dynamodb_client = DynamoDbClient::from_conf(dynamodb_config);
+/// Builds a `Config` from named values instead of positional arguments
+///
+/// Table names are required; `build` reports every one that is unset or
+/// blank by its environment variable name. Everything else starts at the
+/// same default `from_env` uses when its variable is unset.
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+    devices_table: Option<String>,
+    api_keys_table: Option<String>,
+    processed_batches_table: Option<String>,
+    device_readings_table: Option<String>,
+    last_used_throttle_minutes: i64,
+    time_regression_mode: TimeRegressionMode,
+    stuck_sensor_min_run: Option<usize>,
+    max_body_bytes: usize,
+    allow_unknown_capabilities: bool,
+    register_retry_window_seconds: i64,
+    api_key_hash_scheme: ApiKeyHashScheme,
+    readings_ttl_days: i64,
+    allow_reading_overwrite: bool,
+    rollups_table: Option<String>,
+    ingest_rate_limit_per_minute: u32,
+    device_profiles_table: Option<String>,
+    min_reading_interval_ms: i64,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            devices_table: None,
+            api_keys_table: None,
+            processed_batches_table: None,
+            device_readings_table: None,
+            last_used_throttle_minutes: DEFAULT_LAST_USED_THROTTLE_MINUTES,
+            time_regression_mode: TimeRegressionMode::default(),
+            stuck_sensor_min_run: None,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            allow_unknown_capabilities: false,
+            register_retry_window_seconds: DEFAULT_REGISTER_RETRY_WINDOW_SECONDS,
+            api_key_hash_scheme: ApiKeyHashScheme::default(),
+            readings_ttl_days: DEFAULT_READINGS_TTL_DAYS,
+            allow_reading_overwrite: false,
+            rollups_table: None,
+            ingest_rate_limit_per_minute: DEFAULT_INGEST_RATE_LIMIT_PER_MINUTE,
+            device_profiles_table: None,
+            min_reading_interval_ms: 0,
+        }
+    }
+}
 
-        Config {
+impl ConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn devices_table(mut self, table: impl Into<String>) -> Self {
+        self.devices_table = Some(table.into());
+        self
+    }
+
+    pub fn api_keys_table(mut self, table: impl Into<String>) -> Self {
+        self.api_keys_table = Some(table.into());
+        self
+    }
+
+    pub fn processed_batches_table(mut self, table: impl Into<String>) -> Self {
+        self.processed_batches_table = Some(table.into());
+        self
+    }
+
+    pub fn device_readings_table(mut self, table: impl Into<String>) -> Self {
+        self.device_readings_table = Some(table.into());
+        self
+    }
+
+    pub fn last_used_throttle_minutes(mut self, minutes: i64) -> Self {
+        self.last_used_throttle_minutes = minutes;
+        self
+    }
+
+    pub fn time_regression_mode(mut self, mode: TimeRegressionMode) -> Self {
+        self.time_regression_mode = mode;
+        self
+    }
+
+    /// Run length that triggers a stuck-sensor warning (None disables the check)
+    pub fn stuck_sensor_min_run(mut self, min_run: Option<usize>) -> Self {
+        self.stuck_sensor_min_run = min_run;
+        self
+    }
+
+    pub fn max_body_bytes(mut self, bytes: usize) -> Self {
+        self.max_body_bytes = bytes;
+        self
+    }
+
+    pub fn allow_unknown_capabilities(mut self, allow: bool) -> Self {
+        self.allow_unknown_capabilities = allow;
+        self
+    }
+
+    pub fn register_retry_window_seconds(mut self, seconds: i64) -> Self {
+        self.register_retry_window_seconds = seconds;
+        self
+    }
+
+    pub fn api_key_hash_scheme(mut self, scheme: ApiKeyHashScheme) -> Self {
+        self.api_key_hash_scheme = scheme;
+        self
+    }
+
+    pub fn readings_ttl_days(mut self, days: i64) -> Self {
+        self.readings_ttl_days = days;
+        self
+    }
+
+    pub fn allow_reading_overwrite(mut self, allow: bool) -> Self {
+        self.allow_reading_overwrite = allow;
+        self
+    }
+
+    /// Rollups table for per-key request counters (None disables rate limiting)
+    pub fn rollups_table(mut self, table: Option<String>) -> Self {
+        self.rollups_table = table;
+        self
+    }
+
+    pub fn ingest_rate_limit_per_minute(mut self, limit: u32) -> Self {
+        self.ingest_rate_limit_per_minute = limit;
+        self
+    }
+
+    /// Device profiles table for calibration offsets (None stores readings uncorrected)
+    pub fn device_profiles_table(mut self, table: Option<String>) -> Self {
+        self.device_profiles_table = table;
+        self
+    }
+
+    pub fn min_reading_interval_ms(mut self, interval_ms: i64) -> Self {
+        self.min_reading_interval_ms = interval_ms;
+        self
+    }
+
+    /// Build the configuration, or list every required table that is missing
+    ///
+    /// Missing tables are reported by environment variable name, in
+    /// `REQUIRED_ENV_VARS` order.
+    pub fn build(self, dynamodb_client: DynamoDbClient) -> Result<Config, ConfigError> {
+        let mut missing = Vec::new();
+        let mut require = |name: &'static str, value: Option<String>| match value {
+            Some(value) if !value.trim().is_empty() => value,
+            _ => {
+                missing.push(name);
+                String::new()
+            }
+        };
+
+        let devices_table = require("DEVICES_TABLE", self.devices_table);
+        let api_keys_table = require("API_KEYS_TABLE", self.api_keys_table);
+        let processed_batches_table =
+            require("PROCESSED_BATCHES_TABLE", self.processed_batches_table);
+        let device_readings_table = require("DEVICE_READINGS_TABLE", self.device_readings_table);
+
+        if !missing.is_empty() {
+            return Err(ConfigError::MissingEnvVar(missing.join(", ")));
+        }
+
+        Ok(Config {
             dynamodb_client,
             devices_table,
             api_keys_table,
             processed_batches_table,
             device_readings_table,
+            last_used_throttle_minutes: self.last_used_throttle_minutes,
+            time_regression_mode: self.time_regression_mode,
+            stuck_sensor_min_run: self.stuck_sensor_min_run,
+            max_body_bytes: self.max_body_bytes,
+            allow_unknown_capabilities: self.allow_unknown_capabilities,
+            register_retry_window_seconds: self.register_retry_window_seconds,
+            api_key_hash_scheme: self.api_key_hash_scheme,
+            readings_ttl_days: self.readings_ttl_days,
+            allow_reading_overwrite: self.allow_reading_overwrite,
+            rollups_table: self.rollups_table,
+            ingest_rate_limit_per_minute: self.ingest_rate_limit_per_minute,
+            device_profiles_table: self.device_profiles_table,
+            min_reading_interval_ms: self.min_reading_interval_ms,
+        })
+    }
+}
+
+/// DynamoDB client for DynamoDB Local with static test credentials
+#[cfg(test)]
+fn local_dynamodb_client(endpoint_url: &str) -> DynamoDbClient {
+    use aws_sdk_dynamodb::config::{Credentials, Region};
+
+    // Create test credentials
+    let credentials = Credentials::new("test_access_key", "test_secret_key", None, None, "test");
+
+    // Create DynamoDB client pointing to local endpoint
+    let dynamodb_config = aws_sdk_dynamodb::config::Builder::new()
+        .behavior_version(aws_sdk_dynamodb::config::BehaviorVersion::latest())
+        .region(Region::new("us-east-1"))
+        .credentials_provider(credentials)
+        .endpoint_url(endpoint_url)
+        .timeout_config(
+            aws_sdk_dynamodb::config::timeout::TimeoutConfig::builder()
+                .operation_timeout(Duration::from_secs(10))
+                .operation_attempt_timeout(Duration::from_secs(5))
+                .build(),
+        )
+        .build();
+
+    DynamoDbClient::from_conf(dynamodb_config)
+}
+
+/// Read each required variable, collecting every missing name
+///
+/// Unset and blank values both count as missing. The error lists the names
+/// in `names` order, comma-separated.
+fn read_required_vars<const N: usize>(
+    names: [&str; N],
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<[String; N], ConfigError> {
+    let mut missing = Vec::new();
+    let values = names.map(|name| match lookup(name) {
+        Some(value) if !value.trim().is_empty() => value,
+        _ => {
+            missing.push(name);
+            String::new()
         }
+    });
+
+    if missing.is_empty() {
+        Ok(values)
+    } else {
+        Err(ConfigError::MissingEnvVar(missing.join(", ")))
     }
 }
 
+/// Parse LAST_USED_THROTTLE_MINUTES (a non-negative whole number of minutes)
+fn parse_throttle_minutes(value: &str) -> Result<i64, ConfigError> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|minutes| *minutes >= 0)
+        .ok_or_else(|| ConfigError::InvalidEnvVar("LAST_USED_THROTTLE_MINUTES".to_string()))
+}
+
+/// Parse TIME_REGRESSION_MODE (`off`, `warn`, or `reject`)
+fn parse_time_regression_mode(value: &str) -> Result<TimeRegressionMode, ConfigError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "off" => Ok(TimeRegressionMode::Off),
+        "warn" => Ok(TimeRegressionMode::Warn),
+        "reject" => Ok(TimeRegressionMode::Reject),
+        _ => Err(ConfigError::InvalidEnvVar(
+            "TIME_REGRESSION_MODE".to_string(),
+        )),
+    }
+}
+
+/// Parse STUCK_SENSOR_MIN_RUN (a whole number of readings, at least 2)
+fn parse_stuck_sensor_min_run(value: &str) -> Result<usize, ConfigError> {
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|min_run| *min_run >= 2)
+        .ok_or_else(|| ConfigError::InvalidEnvVar("STUCK_SENSOR_MIN_RUN".to_string()))
+}
+
+/// Parse MAX_BODY_BYTES (a positive whole number of bytes)
+fn parse_max_body_bytes(value: &str) -> Result<usize, ConfigError> {
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|bytes| *bytes > 0)
+        .ok_or_else(|| ConfigError::InvalidEnvVar("MAX_BODY_BYTES".to_string()))
+}
+
+/// Parse REGISTER_RETRY_WINDOW_SECONDS (a non-negative whole number of seconds)
+fn parse_register_retry_window_seconds(value: &str) -> Result<i64, ConfigError> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|seconds| *seconds >= 0)
+        .ok_or_else(|| ConfigError::InvalidEnvVar("REGISTER_RETRY_WINDOW_SECONDS".to_string()))
+}
+
+/// Parse API_KEY_HASH_SCHEME (`sha256` or `argon2`)
+fn parse_api_key_hash_scheme(value: &str) -> Result<ApiKeyHashScheme, ConfigError> {
+    ApiKeyHashScheme::from_tag(value.trim().to_ascii_lowercase().as_str())
+        .ok_or_else(|| ConfigError::InvalidEnvVar("API_KEY_HASH_SCHEME".to_string()))
+}
+
+/// Parse READINGS_TTL_DAYS (a non-negative whole number of days; 0 disables expiry)
+fn parse_readings_ttl_days(value: &str) -> Result<i64, ConfigError> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|days| *days >= 0)
+        .ok_or_else(|| ConfigError::InvalidEnvVar("READINGS_TTL_DAYS".to_string()))
+}
+
+/// Parse INGEST_RATE_LIMIT_PER_MINUTE (a non-negative whole number of requests; 0 disables)
+fn parse_ingest_rate_limit_per_minute(value: &str) -> Result<u32, ConfigError> {
+    value
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| ConfigError::InvalidEnvVar("INGEST_RATE_LIMIT_PER_MINUTE".to_string()))
+}
+
+/// Parse MIN_READING_INTERVAL_MS (a non-negative whole number of milliseconds; 0 disables)
+fn parse_min_reading_interval_ms(value: &str) -> Result<i64, ConfigError> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|ms| *ms >= 0)
+        .ok_or_else(|| ConfigError::InvalidEnvVar("MIN_READING_INTERVAL_MS".to_string()))
+}
+
+/// Interpret a boolean environment flag (`true`/`1`, case-insensitive)
+fn parse_flag(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "true" | "1")
+}
+
 /// Configuration errors
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
-    #[error("Missing required environment variable: {0}")]
+    #[error("Missing required environment variables: {0}")]
     MissingEnvVar(String),
 
+    #[error("Invalid value for environment variable: {0}")]
+    InvalidEnvVar(String),
+
     #[error("AWS configuration error: {0}")]
     AwsConfig(String),
 }
@@ -121,18 +603,23 @@ mod tests {
         let saved_api_keys = std::env::var("API_KEYS_TABLE").ok();
         let saved_batches = std::env::var("PROCESSED_BATCHES_TABLE").ok();
         let saved_readings = std::env::var("DEVICE_READINGS_TABLE").ok();
+        let saved_pepper = std::env::var("API_KEY_PEPPER").ok();
 
         // Clear environment variables to test error handling
         std::env::remove_var("DEVICES_TABLE");
         std::env::remove_var("API_KEYS_TABLE");
         std::env::remove_var("PROCESSED_BATCHES_TABLE");
         std::env::remove_var("DEVICE_READINGS_TABLE");
+        std::env::remove_var("API_KEY_PEPPER");
 
         let result = Config::from_env().await;
         assert!(result.is_err());
 
         if let Err(ConfigError::MissingEnvVar(var)) = result {
-            assert_eq!(var, "DEVICES_TABLE");
+            assert_eq!(
+                var,
+                "DEVICES_TABLE, API_KEYS_TABLE, PROCESSED_BATCHES_TABLE, DEVICE_READINGS_TABLE, API_KEY_PEPPER"
+            );
         } else {
             panic!("Expected MissingEnvVar error");
         }
@@ -150,6 +637,9 @@ mod tests {
         if let Some(val) = saved_readings {
             std::env::set_var("DEVICE_READINGS_TABLE", val);
         }
+        if let Some(val) = saved_pepper {
+            std::env::set_var("API_KEY_PEPPER", val);
+        }
     }
 
     #[tokio::test]
@@ -159,6 +649,7 @@ mod tests {
         std::env::set_var("API_KEYS_TABLE", "test-api-keys");
         std::env::set_var("PROCESSED_BATCHES_TABLE", "test-processed-batches");
         std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
+        std::env::set_var("API_KEY_PEPPER", "test-pepper");
 
         let result = Config::from_env().await;
 
@@ -185,6 +676,48 @@ mod tests {
         std::env::remove_var("API_KEYS_TABLE");
         std::env::remove_var("PROCESSED_BATCHES_TABLE");
         std::env::remove_var("DEVICE_READINGS_TABLE");
+        std::env::remove_var("API_KEY_PEPPER");
+    }
+
+    fn complete_env(name: &str) -> Option<String> {
+        Some(format!("value-of-{}", name))
+    }
+
+    #[test]
+    fn test_read_required_vars_complete_env() {
+        let values = read_required_vars(REQUIRED_ENV_VARS, complete_env).unwrap();
+        assert_eq!(values[0], "value-of-DEVICES_TABLE");
+        assert_eq!(values[4], "value-of-API_KEY_PEPPER");
+    }
+
+    #[test]
+    fn test_read_required_vars_missing_pepper() {
+        let lookup = |name: &str| {
+            (name != "API_KEY_PEPPER")
+                .then(|| complete_env(name))
+                .flatten()
+        };
+        let err = read_required_vars(REQUIRED_ENV_VARS, lookup).unwrap_err();
+
+        assert!(matches!(err, ConfigError::MissingEnvVar(ref var) if var == "API_KEY_PEPPER"));
+        assert_eq!(
+            err.to_string(),
+            "Missing required environment variables: API_KEY_PEPPER"
+        );
+    }
+
+    #[test]
+    fn test_read_required_vars_blank_counts_as_missing() {
+        let lookup = |name: &str| match name {
+            "API_KEYS_TABLE" => Some("  ".to_string()),
+            "DEVICE_READINGS_TABLE" => None,
+            _ => complete_env(name),
+        };
+
+        assert!(matches!(
+            read_required_vars(REQUIRED_ENV_VARS, lookup),
+            Err(ConfigError::MissingEnvVar(ref var)) if var == "API_KEYS_TABLE, DEVICE_READINGS_TABLE"
+        ));
     }
 
     #[tokio::test]
@@ -202,5 +735,222 @@ mod tests {
         assert_eq!(config.api_keys_table, "test-api-keys");
         assert_eq!(config.processed_batches_table, "test-processed-batches");
         assert_eq!(config.device_readings_table, "test-device-readings");
+        assert_eq!(
+            config.last_used_throttle_minutes,
+            DEFAULT_LAST_USED_THROTTLE_MINUTES
+        );
+        assert_eq!(config.time_regression_mode, TimeRegressionMode::Off);
+        assert_eq!(config.stuck_sensor_min_run, None);
+        assert_eq!(config.api_key_hash_scheme, ApiKeyHashScheme::Sha256);
+        assert_eq!(config.readings_ttl_days, DEFAULT_READINGS_TTL_DAYS);
+        assert_eq!(config.readings_retention_seconds(), Some(90 * 24 * 3600));
+        assert!(!config.allow_reading_overwrite);
+    }
+
+    fn complete_builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+            .devices_table("test-devices")
+            .api_keys_table("test-api-keys")
+            .processed_batches_table("test-processed-batches")
+            .device_readings_table("test-device-readings")
+    }
+
+    #[tokio::test]
+    async fn test_builder_fully_populated_succeeds() {
+        let config = complete_builder()
+            .time_regression_mode(TimeRegressionMode::Reject)
+            .device_profiles_table(Some("test-device-profiles".to_string()))
+            .min_reading_interval_ms(5_000)
+            .build(local_dynamodb_client("http://localhost:8000"))
+            .unwrap();
+
+        assert_eq!(config.devices_table, "test-devices");
+        assert_eq!(config.processed_batches_table, "test-processed-batches");
+        assert_eq!(config.time_regression_mode, TimeRegressionMode::Reject);
+        assert_eq!(
+            config.device_profiles_table.as_deref(),
+            Some("test-device-profiles")
+        );
+        assert_eq!(config.min_reading_interval_ms, 5_000);
+        assert_eq!(config.max_body_bytes, DEFAULT_MAX_BODY_BYTES);
+        assert_eq!(
+            config.ingest_rate_limit_per_minute,
+            DEFAULT_INGEST_RATE_LIMIT_PER_MINUTE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builder_lists_every_missing_table() {
+        let err = ConfigBuilder::new()
+            .api_keys_table("test-api-keys")
+            .processed_batches_table("  ")
+            .build(local_dynamodb_client("http://localhost:8000"))
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ConfigError::MissingEnvVar(ref var)
+                if var == "DEVICES_TABLE, PROCESSED_BATCHES_TABLE, DEVICE_READINGS_TABLE"
+        ));
+    }
+
+    #[test]
+    fn test_parse_throttle_minutes() {
+        assert_eq!(parse_throttle_minutes("60").unwrap(), 60);
+        assert_eq!(parse_throttle_minutes(" 1 ").unwrap(), 1);
+        assert_eq!(parse_throttle_minutes("0").unwrap(), 0);
+
+        for invalid in ["-5", "abc", "", "1.5"] {
+            assert!(matches!(
+                parse_throttle_minutes(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "LAST_USED_THROTTLE_MINUTES"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_time_regression_mode() {
+        assert_eq!(
+            parse_time_regression_mode("off").unwrap(),
+            TimeRegressionMode::Off
+        );
+        assert_eq!(
+            parse_time_regression_mode(" Warn ").unwrap(),
+            TimeRegressionMode::Warn
+        );
+        assert_eq!(
+            parse_time_regression_mode("reject").unwrap(),
+            TimeRegressionMode::Reject
+        );
+
+        assert!(matches!(
+            parse_time_regression_mode("strict"),
+            Err(ConfigError::InvalidEnvVar(ref var)) if var == "TIME_REGRESSION_MODE"
+        ));
+    }
+
+    #[test]
+    fn test_parse_stuck_sensor_min_run() {
+        assert_eq!(parse_stuck_sensor_min_run("10").unwrap(), 10);
+        assert_eq!(parse_stuck_sensor_min_run(" 2 ").unwrap(), 2);
+
+        for invalid in ["1", "0", "-3", "many"] {
+            assert!(matches!(
+                parse_stuck_sensor_min_run(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "STUCK_SENSOR_MIN_RUN"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_max_body_bytes() {
+        assert_eq!(parse_max_body_bytes("65536").unwrap(), 65_536);
+
+        for invalid in ["0", "-1", "256KB"] {
+            assert!(matches!(
+                parse_max_body_bytes(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "MAX_BODY_BYTES"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_register_retry_window_seconds() {
+        assert_eq!(parse_register_retry_window_seconds("0").unwrap(), 0);
+        assert_eq!(parse_register_retry_window_seconds(" 120 ").unwrap(), 120);
+
+        for invalid in ["-1", "5m", ""] {
+            assert!(matches!(
+                parse_register_retry_window_seconds(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "REGISTER_RETRY_WINDOW_SECONDS"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_readings_ttl_days() {
+        assert_eq!(parse_readings_ttl_days("0").unwrap(), 0);
+        assert_eq!(parse_readings_ttl_days(" 30 ").unwrap(), 30);
+
+        for invalid in ["-1", "90d", ""] {
+            assert!(matches!(
+                parse_readings_ttl_days(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "READINGS_TTL_DAYS"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_ingest_rate_limit_per_minute() {
+        assert_eq!(parse_ingest_rate_limit_per_minute("0").unwrap(), 0);
+        assert_eq!(parse_ingest_rate_limit_per_minute(" 600 ").unwrap(), 600);
+
+        for invalid in ["-1", "60/min", ""] {
+            assert!(matches!(
+                parse_ingest_rate_limit_per_minute(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "INGEST_RATE_LIMIT_PER_MINUTE"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_min_reading_interval_ms() {
+        assert_eq!(parse_min_reading_interval_ms("0").unwrap(), 0);
+        assert_eq!(parse_min_reading_interval_ms(" 5000 ").unwrap(), 5000);
+
+        for invalid in ["-1", "5s", ""] {
+            assert!(matches!(
+                parse_min_reading_interval_ms(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "MIN_READING_INTERVAL_MS"
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_readings_retention_seconds_disabled_at_zero() {
+        let mut config = Config::for_test(
+            "http://localhost:8000",
+            "test-devices".to_string(),
+            "test-api-keys".to_string(),
+            "test-processed-batches".to_string(),
+            "test-device-readings".to_string(),
+        )
+        .await;
+
+        config.readings_ttl_days = 0;
+        assert_eq!(config.readings_retention_seconds(), None);
+
+        config.readings_ttl_days = 1;
+        assert_eq!(config.readings_retention_seconds(), Some(86_400));
+    }
+
+    #[test]
+    fn test_parse_api_key_hash_scheme() {
+        assert_eq!(
+            parse_api_key_hash_scheme("sha256").unwrap(),
+            ApiKeyHashScheme::Sha256
+        );
+        assert_eq!(
+            parse_api_key_hash_scheme(" Argon2 ").unwrap(),
+            ApiKeyHashScheme::Argon2
+        );
+        assert!(ApiKeyHashScheme::Sha256 < ApiKeyHashScheme::Argon2);
+
+        for invalid in ["bcrypt", ""] {
+            assert!(matches!(
+                parse_api_key_hash_scheme(invalid),
+                Err(ConfigError::InvalidEnvVar(ref var)) if var == "API_KEY_HASH_SCHEME"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_flag() {
+        assert!(parse_flag("true"));
+        assert!(parse_flag("TRUE"));
+        assert!(parse_flag("1"));
+        assert!(!parse_flag("false"));
+        assert!(!parse_flag("0"));
+        assert!(!parse_flag(""));
     }
 }
@@ -13,6 +13,9 @@ pub mod auth;
 #[path = "control/crypto.rs"]
 pub mod crypto;
 
+#[path = "control/response.rs"]
+mod response;
+
 #[path = "control/router.rs"]
 mod router;
 
@@ -27,15 +30,10 @@ use lambda_http::{run, service_fn, Error, Request};
 
 async fn function_handler(
     event: Request,
+    config: &config::ControlConfig,
 ) -> Result<lambda_http::Response<lambda_http::Body>, Error> {
-    // Load configuration from environment
-    let config = config::ControlConfig::from_env().await.map_err(|e| {
-        tracing::error!("Failed to load configuration: {}", e);
-        Error::from(format!("Configuration error: {}", e))
-    })?;
-
     // Route the request using the router
-    router::route_request(event, &config).await
+    router::route_request(event, config).await
 }
 
 #[tokio::main]
@@ -46,7 +44,14 @@ async fn main() -> Result<(), Error> {
         .without_time()
         .init();
 
-    run(service_fn(function_handler)).await
+    // Load and validate configuration once per cold start
+    let config = config::ControlConfig::from_env().await.map_err(|e| {
+        tracing::error!("Failed to load configuration: {}", e);
+        Error::from(format!("Configuration error: {}", e))
+    })?;
+    let config = &config;
+
+    run(service_fn(move |event| function_handler(event, config))).await
 }
 
 #[cfg(test)]
@@ -66,18 +71,25 @@ mod tests {
         request
     }
 
+    async fn test_config() -> config::ControlConfig {
+        config::ControlConfig::for_test(
+            "http://localhost:8000",
+            "test-devices".to_string(),
+            "test-api-keys".to_string(),
+            "test-device-readings".to_string(),
+            "test-admin-token".to_string(),
+            "*".to_string(),
+        )
+        .await
+    }
+
     #[tokio::test]
     async fn test_control_plane_health_endpoint() {
-        // Set up required environment variables
-        std::env::set_var("DEVICES_TABLE", "test-devices");
-        std::env::set_var("API_KEYS_TABLE", "test-api-keys");
-        std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
-        std::env::set_var("ADMIN_TOKEN", "test-admin-token");
-        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+        let config = test_config().await;
 
         let request = create_test_request(Method::GET, "/health");
 
-        let response = function_handler(request).await;
+        let response = function_handler(request, &config).await;
 
         assert!(response.is_ok());
         let resp = response.unwrap();
@@ -89,27 +101,15 @@ mod tests {
         };
         assert!(body.contains("healthy"));
         assert!(body.contains("control-plane-api"));
-
-        // Clean up
-        std::env::remove_var("DEVICES_TABLE");
-        std::env::remove_var("API_KEYS_TABLE");
-        std::env::remove_var("DEVICE_READINGS_TABLE");
-        std::env::remove_var("ADMIN_TOKEN");
-        std::env::remove_var("API_KEY_PEPPER");
     }
 
     #[tokio::test]
     async fn test_control_plane_cors_preflight() {
-        // Set up required environment variables
-        std::env::set_var("DEVICES_TABLE", "test-devices");
-        std::env::set_var("API_KEYS_TABLE", "test-api-keys");
-        std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
-        std::env::set_var("ADMIN_TOKEN", "test-admin-token");
-        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+        let config = test_config().await;
 
         let request = create_test_request(Method::OPTIONS, "/api-keys");
 
-        let response = function_handler(request).await;
+        let response = function_handler(request, &config).await;
 
         assert!(response.is_ok());
         let resp = response.unwrap();
@@ -119,27 +119,15 @@ mod tests {
         assert!(headers.contains_key("access-control-allow-origin"));
         assert!(headers.contains_key("access-control-allow-methods"));
         assert!(headers.contains_key("access-control-allow-headers"));
-
-        // Clean up
-        std::env::remove_var("DEVICES_TABLE");
-        std::env::remove_var("API_KEYS_TABLE");
-        std::env::remove_var("DEVICE_READINGS_TABLE");
-        std::env::remove_var("ADMIN_TOKEN");
-        std::env::remove_var("API_KEY_PEPPER");
     }
 
     #[tokio::test]
     async fn test_control_plane_unknown_route() {
-        // Set up required environment variables
-        std::env::set_var("DEVICES_TABLE", "test-devices");
-        std::env::set_var("API_KEYS_TABLE", "test-api-keys");
-        std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
-        std::env::set_var("ADMIN_TOKEN", "test-admin-token");
-        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+        let config = test_config().await;
 
         let request = create_test_request(Method::GET, "/unknown");
 
-        let response = function_handler(request).await;
+        let response = function_handler(request, &config).await;
 
         assert!(response.is_ok());
         let resp = response.unwrap();
@@ -147,52 +135,28 @@ mod tests {
 
         let headers = resp.headers();
         assert!(headers.contains_key("access-control-allow-origin"));
-
-        // Clean up
-        std::env::remove_var("DEVICES_TABLE");
-        std::env::remove_var("API_KEYS_TABLE");
-        std::env::remove_var("DEVICE_READINGS_TABLE");
-        std::env::remove_var("ADMIN_TOKEN");
-        std::env::remove_var("API_KEY_PEPPER");
     }
 
     #[tokio::test]
     async fn test_control_plane_trailing_slash_normalization() {
-        // Set up required environment variables
-        std::env::set_var("DEVICES_TABLE", "test-devices");
-        std::env::set_var("API_KEYS_TABLE", "test-api-keys");
-        std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
-        std::env::set_var("ADMIN_TOKEN", "test-admin-token");
-        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+        let config = test_config().await;
 
         let request1 = create_test_request(Method::GET, "/health");
         let request2 = create_test_request(Method::GET, "/health/");
 
-        let response1 = function_handler(request1).await;
-        let response2 = function_handler(request2).await;
+        let response1 = function_handler(request1, &config).await;
+        let response2 = function_handler(request2, &config).await;
 
         assert!(response1.is_ok());
         assert!(response2.is_ok());
 
         assert_eq!(response1.unwrap().status(), 200);
         assert_eq!(response2.unwrap().status(), 200);
-
-        // Clean up
-        std::env::remove_var("DEVICES_TABLE");
-        std::env::remove_var("API_KEYS_TABLE");
-        std::env::remove_var("DEVICE_READINGS_TABLE");
-        std::env::remove_var("ADMIN_TOKEN");
-        std::env::remove_var("API_KEY_PEPPER");
     }
 
     #[tokio::test]
     async fn test_control_plane_not_implemented_endpoints() {
-        // Set up required environment variables
-        std::env::set_var("DEVICES_TABLE", "test-devices");
-        std::env::set_var("API_KEYS_TABLE", "test-api-keys");
-        std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
-        std::env::set_var("ADMIN_TOKEN", "test-admin-token");
-        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+        let config = test_config().await;
 
         let endpoints = vec![
             // POST /api-keys, GET /api-keys, DELETE /api-keys/{key_id}, and GET /devices are now implemented
@@ -204,7 +168,7 @@ mod tests {
         for (method, uri) in endpoints {
             let request = create_test_request(method.clone(), uri);
 
-            let response = function_handler(request).await;
+            let response = function_handler(request, &config).await;
 
             assert!(response.is_ok(), "Failed for {} {}", method, uri);
             let resp = response.unwrap();
@@ -213,12 +177,5 @@ mod tests {
             let headers = resp.headers();
             assert!(headers.contains_key("access-control-allow-origin"));
         }
-
-        // Clean up
-        std::env::remove_var("DEVICES_TABLE");
-        std::env::remove_var("API_KEYS_TABLE");
-        std::env::remove_var("DEVICE_READINGS_TABLE");
-        std::env::remove_var("ADMIN_TOKEN");
-        std::env::remove_var("API_KEY_PEPPER");
     }
 }
@@ -47,28 +47,28 @@ pub fn validate_bearer_token(event: &Request) -> Result<(), AuthError> {
 /// Performs constant-time string comparison to prevent timing attacks
 ///
 /// # Arguments
-/// * `a` - First string to compare
-/// * `b` - Second string to compare
+/// * `provided` - Token supplied by the caller
+/// * `expected` - Secret token to compare against
 ///
 /// # Returns
 /// * `true` if strings are equal, `false` otherwise
 ///
 /// # Security Note
-/// This function always compares all bytes to prevent timing-based attacks
-/// that could leak information about the expected token
-fn constant_time_compare(a: &str, b: &str) -> bool {
-    let a_bytes = a.as_bytes();
-    let b_bytes = b.as_bytes();
-
-    // If lengths differ, still compare to avoid timing leak
-    if a_bytes.len() != b_bytes.len() {
-        return false;
-    }
-
-    // XOR all bytes and accumulate result
-    let mut result = 0u8;
-    for (a_byte, b_byte) in a_bytes.iter().zip(b_bytes.iter()) {
-        result |= a_byte ^ b_byte;
+/// The loop always runs over every byte of `expected`, whatever the length
+/// of `provided`, and a length mismatch is folded into the result rather
+/// than returned early, so timing leaks neither where the first differing
+/// byte is nor whether the lengths match.
+fn constant_time_compare(provided: &str, expected: &str) -> bool {
+    let provided_bytes = provided.as_bytes();
+    let expected_bytes = expected.as_bytes();
+
+    // Non-zero when the lengths differ
+    let mut result = provided_bytes.len() ^ expected_bytes.len();
+
+    // XOR all bytes and accumulate result; missing bytes compare as zero
+    for (i, expected_byte) in expected_bytes.iter().enumerate() {
+        let provided_byte = provided_bytes.get(i).copied().unwrap_or(0);
+        result |= usize::from(provided_byte ^ expected_byte);
     }
 
     result == 0
@@ -199,6 +199,32 @@ mod tests {
         std::env::remove_var("ADMIN_TOKEN");
     }
 
+    #[test]
+    fn test_validate_bearer_token_wrong_token_same_length() {
+        std::env::set_var("ADMIN_TOKEN", "test-token");
+
+        let request = create_test_request(Method::GET, "/api-keys", Some("Bearer test-tokex"));
+
+        let result = validate_bearer_token(&request);
+        assert!(matches!(result.unwrap_err(), AuthError::InvalidToken));
+
+        std::env::remove_var("ADMIN_TOKEN");
+    }
+
+    #[test]
+    fn test_validate_bearer_token_wrong_token_different_length() {
+        std::env::set_var("ADMIN_TOKEN", "test-token");
+
+        for token in ["Bearer test", "Bearer test-token-extended"] {
+            let request = create_test_request(Method::GET, "/api-keys", Some(token));
+
+            let result = validate_bearer_token(&request);
+            assert!(matches!(result.unwrap_err(), AuthError::InvalidToken));
+        }
+
+        std::env::remove_var("ADMIN_TOKEN");
+    }
+
     #[test]
     fn test_constant_time_compare_equal() {
         assert!(constant_time_compare("test", "test"));
@@ -222,6 +248,15 @@ mod tests {
         assert!(!constant_time_compare("short", "longer"));
         assert!(!constant_time_compare("longer", "short"));
         assert!(!constant_time_compare("", "nonempty"));
+        assert!(!constant_time_compare("nonempty", ""));
+    }
+
+    #[test]
+    fn test_constant_time_compare_zero_padding_does_not_match() {
+        // Missing bytes compare as zero, so the length check must still fail these
+        assert!(!constant_time_compare("test", "test\0"));
+        assert!(!constant_time_compare("test\0", "test"));
+        assert!(!constant_time_compare("\0", ""));
     }
 
     #[test]
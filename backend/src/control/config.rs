@@ -1,6 +1,33 @@
 use aws_sdk_dynamodb::Client as DynamoDbClient;
+use esp32_backend::shared::validators::{
+    DEFAULT_MAX_BODY_BYTES, DEFAULT_PAGE_LIMIT, MAX_API_KEYS_PAGE_LIMIT, MAX_DEVICES_PAGE_LIMIT,
+    MAX_READINGS_PAGE_LIMIT,
+};
 use std::time::Duration;
 
+/// Default maximum span of a range query, in days
+pub const DEFAULT_MAX_QUERY_RANGE_DAYS: i64 = 31;
+
+/// Default number of readings the stats endpoint reads before summarizing what it has
+pub const DEFAULT_STATS_ROW_BUDGET: usize = 50_000;
+
+/// Environment variables the control plane cannot start without
+///
+/// `API_KEY_PEPPER` is read again when API keys are hashed; it is listed here
+/// so a missing pepper fails at cold start instead of on the first key created.
+pub const REQUIRED_ENV_VARS: [&str; 10] = [
+    "DEVICES_TABLE",
+    "API_KEYS_TABLE",
+    "DEVICE_READINGS_TABLE",
+    "PLANT_EVENTS_TABLE",
+    "STATUS_TRANSITIONS_TABLE",
+    "DEVICE_STATUS_TABLE",
+    "NOTES_TABLE",
+    "PLANT_AGGREGATES_TABLE",
+    "ADMIN_TOKEN",
+    "API_KEY_PEPPER",
+];
+
 /// Configuration for the Control Plane API
 #[derive(Debug, Clone)]
 pub struct ControlConfig {
@@ -12,15 +39,54 @@ pub struct ControlConfig {
     pub api_keys_table: String,
     /// Device readings table name
     pub device_readings_table: String,
+    /// Plant events table name
+    pub plant_events_table: String,
+    /// Device status transitions table name
+    pub status_transitions_table: String,
+    /// Device status (health summary) table name
+    pub device_status_table: String,
+    /// Device notes table name
+    pub notes_table: String,
+    /// Plant aggregates (hourly/daily/weekly sensor stats) table name
+    pub aggregates_table: String,
     /// Admin token for Bearer authentication
     pub admin_token: String,
     /// CORS allowed origin
     pub cors_allowed_origin: String,
+    /// Whether debug endpoints (e.g. `/debug/cursor`) are served
+    pub debug_endpoints_enabled: bool,
+    /// Maximum span (to - from) of range queries that read every page, in days
+    pub max_query_range_days: i64,
+    /// Most readings GET /devices/{hardware_id}/readings/stats reads before returning a partial summary
+    pub stats_row_budget: usize,
+    /// Largest request body accepted before JSON parsing, in bytes
+    pub max_body_bytes: usize,
+    /// Page size of GET /devices when `limit` is absent
+    pub devices_page_default: i32,
+    /// Largest page size of GET /devices
+    pub devices_page_max: i32,
+    /// Page size of GET /api-keys when `limit` is absent
+    pub api_keys_page_default: i32,
+    /// Largest page size of GET /api-keys
+    pub api_keys_page_max: i32,
+    /// Page size of GET /devices/{hardware_id}/readings when `limit` is absent
+    pub readings_page_default: i32,
+    /// Largest page size of GET /devices/{hardware_id}/readings
+    pub readings_page_max: i32,
 }
 
 impl ControlConfig {
     /// Create a new ControlConfig instance from environment variables
+    ///
+    /// Every variable in `REQUIRED_ENV_VARS` is checked before anything else
+    /// is loaded, and all missing names are reported in one error. Call once
+    /// at cold start.
     pub async fn from_env() -> Result<Self, ControlConfigError> {
+        // The pepper is only checked here; the crypto module reads it when hashing
+        let required = read_required_vars(REQUIRED_ENV_VARS, |name| std::env::var(name).ok())?;
+        let [devices_table, api_keys_table, device_readings_table, plant_events_table, status_transitions_table, device_status_table, notes_table, aggregates_table, admin_token, _] =
+            required;
+
         // Load AWS configuration with behavior version
         let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
             .load()
@@ -38,30 +104,75 @@ impl ControlConfig {
 
         let dynamodb_client = DynamoDbClient::from_conf(dynamodb_config);
 
-        // Load table names from environment variables
-        let devices_table = std::env::var("DEVICES_TABLE")
-            .map_err(|_| ControlConfigError::MissingEnvVar("DEVICES_TABLE".to_string()))?;
-
-        let api_keys_table = std::env::var("API_KEYS_TABLE")
-            .map_err(|_| ControlConfigError::MissingEnvVar("API_KEYS_TABLE".to_string()))?;
-
-        let device_readings_table = std::env::var("DEVICE_READINGS_TABLE")
-            .map_err(|_| ControlConfigError::MissingEnvVar("DEVICE_READINGS_TABLE".to_string()))?;
-
-        let admin_token = std::env::var("ADMIN_TOKEN")
-            .map_err(|_| ControlConfigError::MissingEnvVar("ADMIN_TOKEN".to_string()))?;
-
         let cors_allowed_origin =
             std::env::var("CORS_ALLOWED_ORIGIN").unwrap_or_else(|_| "*".to_string());
 
-        Ok(ControlConfig {
-            dynamodb_client,
-            devices_table,
-            api_keys_table,
-            device_readings_table,
-            admin_token,
-            cors_allowed_origin,
-        })
+        let debug_endpoints_enabled = std::env::var("DEBUG_ENDPOINTS_ENABLED")
+            .map(|v| parse_flag(&v))
+            .unwrap_or(false);
+
+        let max_query_range_days = match std::env::var("MAX_QUERY_RANGE_DAYS") {
+            Ok(value) => parse_max_query_range_days(&value)?,
+            Err(_) => DEFAULT_MAX_QUERY_RANGE_DAYS,
+        };
+
+        let stats_row_budget = match std::env::var("STATS_ROW_BUDGET") {
+            Ok(value) => parse_stats_row_budget(&value)?,
+            Err(_) => DEFAULT_STATS_ROW_BUDGET,
+        };
+
+        let max_body_bytes = match std::env::var("MAX_BODY_BYTES") {
+            Ok(value) => parse_max_body_bytes(&value)?,
+            Err(_) => DEFAULT_MAX_BODY_BYTES,
+        };
+
+        let env = |name: &str| std::env::var(name).ok();
+        let (devices_page_default, devices_page_max) = read_page_limits(
+            env,
+            "DEVICES_PAGE_DEFAULT",
+            "DEVICES_PAGE_MAX",
+            DEFAULT_PAGE_LIMIT,
+            MAX_DEVICES_PAGE_LIMIT,
+        )?;
+        let (api_keys_page_default, api_keys_page_max) = read_page_limits(
+            env,
+            "API_KEYS_PAGE_DEFAULT",
+            "API_KEYS_PAGE_MAX",
+            DEFAULT_PAGE_LIMIT,
+            MAX_API_KEYS_PAGE_LIMIT,
+        )?;
+        let (readings_page_default, readings_page_max) = read_page_limits(
+            env,
+            "READINGS_PAGE_DEFAULT",
+            "READINGS_PAGE_MAX",
+            DEFAULT_PAGE_LIMIT,
+            MAX_READINGS_PAGE_LIMIT,
+        )?;
+
+        ControlConfigBuilder::new()
+            .devices_table(devices_table)
+            .api_keys_table(api_keys_table)
+            .device_readings_table(device_readings_table)
+            .plant_events_table(plant_events_table)
+            .status_transitions_table(status_transitions_table)
+            .device_status_table(device_status_table)
+            .notes_table(notes_table)
+            .aggregates_table(aggregates_table)
+            .admin_token(admin_token)
+            .cors_allowed_origin(cors_allowed_origin)
+            .debug_endpoints_enabled(debug_endpoints_enabled)
+            .max_query_range_days(max_query_range_days)
+            .stats_row_budget(stats_row_budget)
+            .max_body_bytes(max_body_bytes)
+            .devices_page_limits(devices_page_default, devices_page_max)
+            .api_keys_page_limits(api_keys_page_default, api_keys_page_max)
+            .readings_page_limits(readings_page_default, readings_page_max)
+            .build(dynamodb_client)
+    }
+
+    /// Maximum span of a range query in epoch milliseconds
+    pub fn max_query_range_ms(&self) -> i64 {
+        self.max_query_range_days * 24 * 60 * 60 * 1000
     }
 
     /// Create a test configuration with custom values
@@ -75,45 +186,347 @@ impl ControlConfig {
         admin_token: String,
         cors_allowed_origin: String,
     ) -> Self {
-        use aws_sdk_dynamodb::config::{Credentials, Region};
-
-        // Create test credentials
-        let credentials =
-            Credentials::new("test_access_key", "test_secret_key", None, None, "test");
-
-        // Create DynamoDB client pointing to local endpoint
-        let dynamodb_config = aws_sdk_dynamodb::config::Builder::new()
-            .behavior_version(aws_sdk_dynamodb::config::BehaviorVersion::latest())
-            .region(Region::new("us-east-1"))
-            .credentials_provider(credentials)
-            .endpoint_url(endpoint_url)
-            .timeout_config(
-                aws_sdk_dynamodb::config::timeout::TimeoutConfig::builder()
-                    .operation_timeout(Duration::from_secs(10))
-                    .operation_attempt_timeout(Duration::from_secs(5))
-                    .build(),
-            )
-            .build();
+        ControlConfigBuilder::new()
+            .devices_table(devices_table)
+            .api_keys_table(api_keys_table)
+            .device_readings_table(device_readings_table)
+            .plant_events_table("test-plant-events")
+            .status_transitions_table("test-status-transitions")
+            .device_status_table("test-device-status")
+            .notes_table("test-notes")
+            .aggregates_table("test-plant-aggregates")
+            .admin_token(admin_token)
+            .cors_allowed_origin(cors_allowed_origin)
+            .build(local_dynamodb_client(endpoint_url))
+            .expect("test configuration sets every required value")
+    }
+}
 
-        let dynamodb_client = DynamoDbClient::from_conf(dynamodb_config);
+/// Builds a `ControlConfig` from named values instead of positional arguments
+///
+/// Table names and the admin token are required; `build` reports every one
+/// that is unset or blank by its environment variable name. Everything else
+/// starts at the same default `from_env` uses when its variable is unset.
+#[derive(Debug, Clone)]
+pub struct ControlConfigBuilder {
+    devices_table: Option<String>,
+    api_keys_table: Option<String>,
+    device_readings_table: Option<String>,
+    plant_events_table: Option<String>,
+    status_transitions_table: Option<String>,
+    device_status_table: Option<String>,
+    notes_table: Option<String>,
+    aggregates_table: Option<String>,
+    admin_token: Option<String>,
+    cors_allowed_origin: String,
+    debug_endpoints_enabled: bool,
+    max_query_range_days: i64,
+    stats_row_budget: usize,
+    max_body_bytes: usize,
+    devices_page_limits: (i32, i32),
+    api_keys_page_limits: (i32, i32),
+    readings_page_limits: (i32, i32),
+}
+
+impl Default for ControlConfigBuilder {
+    fn default() -> Self {
+        Self {
+            devices_table: None,
+            api_keys_table: None,
+            device_readings_table: None,
+            plant_events_table: None,
+            status_transitions_table: None,
+            device_status_table: None,
+            notes_table: None,
+            aggregates_table: None,
+            admin_token: None,
+            cors_allowed_origin: "*".to_string(),
+            debug_endpoints_enabled: false,
+            max_query_range_days: DEFAULT_MAX_QUERY_RANGE_DAYS,
+            stats_row_budget: DEFAULT_STATS_ROW_BUDGET,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            devices_page_limits: (DEFAULT_PAGE_LIMIT, MAX_DEVICES_PAGE_LIMIT),
+            api_keys_page_limits: (DEFAULT_PAGE_LIMIT, MAX_API_KEYS_PAGE_LIMIT),
+            readings_page_limits: (DEFAULT_PAGE_LIMIT, MAX_READINGS_PAGE_LIMIT),
+        }
+    }
+}
 
-        ControlConfig {
+impl ControlConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn devices_table(mut self, table: impl Into<String>) -> Self {
+        self.devices_table = Some(table.into());
+        self
+    }
+
+    pub fn api_keys_table(mut self, table: impl Into<String>) -> Self {
+        self.api_keys_table = Some(table.into());
+        self
+    }
+
+    pub fn device_readings_table(mut self, table: impl Into<String>) -> Self {
+        self.device_readings_table = Some(table.into());
+        self
+    }
+
+    pub fn plant_events_table(mut self, table: impl Into<String>) -> Self {
+        self.plant_events_table = Some(table.into());
+        self
+    }
+
+    pub fn status_transitions_table(mut self, table: impl Into<String>) -> Self {
+        self.status_transitions_table = Some(table.into());
+        self
+    }
+
+    pub fn device_status_table(mut self, table: impl Into<String>) -> Self {
+        self.device_status_table = Some(table.into());
+        self
+    }
+
+    pub fn notes_table(mut self, table: impl Into<String>) -> Self {
+        self.notes_table = Some(table.into());
+        self
+    }
+
+    pub fn aggregates_table(mut self, table: impl Into<String>) -> Self {
+        self.aggregates_table = Some(table.into());
+        self
+    }
+
+    pub fn admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(token.into());
+        self
+    }
+
+    pub fn cors_allowed_origin(mut self, origin: impl Into<String>) -> Self {
+        self.cors_allowed_origin = origin.into();
+        self
+    }
+
+    pub fn debug_endpoints_enabled(mut self, enabled: bool) -> Self {
+        self.debug_endpoints_enabled = enabled;
+        self
+    }
+
+    pub fn max_query_range_days(mut self, days: i64) -> Self {
+        self.max_query_range_days = days;
+        self
+    }
+
+    pub fn stats_row_budget(mut self, rows: usize) -> Self {
+        self.stats_row_budget = rows;
+        self
+    }
+
+    pub fn max_body_bytes(mut self, bytes: usize) -> Self {
+        self.max_body_bytes = bytes;
+        self
+    }
+
+    /// Default and maximum page size of GET /devices
+    pub fn devices_page_limits(mut self, default: i32, max: i32) -> Self {
+        self.devices_page_limits = (default, max);
+        self
+    }
+
+    /// Default and maximum page size of GET /api-keys
+    pub fn api_keys_page_limits(mut self, default: i32, max: i32) -> Self {
+        self.api_keys_page_limits = (default, max);
+        self
+    }
+
+    /// Default and maximum page size of GET /devices/{hardware_id}/readings
+    pub fn readings_page_limits(mut self, default: i32, max: i32) -> Self {
+        self.readings_page_limits = (default, max);
+        self
+    }
+
+    /// Build the configuration, or list every required value that is missing
+    ///
+    /// Missing values are reported by environment variable name, in
+    /// `REQUIRED_ENV_VARS` order.
+    pub fn build(
+        self,
+        dynamodb_client: DynamoDbClient,
+    ) -> Result<ControlConfig, ControlConfigError> {
+        let mut missing = Vec::new();
+        let mut require = |name: &'static str, value: Option<String>| match value {
+            Some(value) if !value.trim().is_empty() => value,
+            _ => {
+                missing.push(name);
+                String::new()
+            }
+        };
+
+        let devices_table = require("DEVICES_TABLE", self.devices_table);
+        let api_keys_table = require("API_KEYS_TABLE", self.api_keys_table);
+        let device_readings_table = require("DEVICE_READINGS_TABLE", self.device_readings_table);
+        let plant_events_table = require("PLANT_EVENTS_TABLE", self.plant_events_table);
+        let status_transitions_table =
+            require("STATUS_TRANSITIONS_TABLE", self.status_transitions_table);
+        let device_status_table = require("DEVICE_STATUS_TABLE", self.device_status_table);
+        let notes_table = require("NOTES_TABLE", self.notes_table);
+        let aggregates_table = require("PLANT_AGGREGATES_TABLE", self.aggregates_table);
+        let admin_token = require("ADMIN_TOKEN", self.admin_token);
+
+        if !missing.is_empty() {
+            return Err(ControlConfigError::MissingEnvVar(missing.join(", ")));
+        }
+
+        Ok(ControlConfig {
             dynamodb_client,
             devices_table,
             api_keys_table,
             device_readings_table,
+            plant_events_table,
+            status_transitions_table,
+            device_status_table,
+            notes_table,
+            aggregates_table,
             admin_token,
-            cors_allowed_origin,
+            cors_allowed_origin: self.cors_allowed_origin,
+            debug_endpoints_enabled: self.debug_endpoints_enabled,
+            max_query_range_days: self.max_query_range_days,
+            stats_row_budget: self.stats_row_budget,
+            max_body_bytes: self.max_body_bytes,
+            devices_page_default: self.devices_page_limits.0,
+            devices_page_max: self.devices_page_limits.1,
+            api_keys_page_default: self.api_keys_page_limits.0,
+            api_keys_page_max: self.api_keys_page_limits.1,
+            readings_page_default: self.readings_page_limits.0,
+            readings_page_max: self.readings_page_limits.1,
+        })
+    }
+}
+
+/// DynamoDB client for DynamoDB Local with static test credentials
+#[cfg(test)]
+fn local_dynamodb_client(endpoint_url: &str) -> DynamoDbClient {
+    use aws_sdk_dynamodb::config::{Credentials, Region};
+
+    // Create test credentials
+    let credentials = Credentials::new("test_access_key", "test_secret_key", None, None, "test");
+
+    // Create DynamoDB client pointing to local endpoint
+    let dynamodb_config = aws_sdk_dynamodb::config::Builder::new()
+        .behavior_version(aws_sdk_dynamodb::config::BehaviorVersion::latest())
+        .region(Region::new("us-east-1"))
+        .credentials_provider(credentials)
+        .endpoint_url(endpoint_url)
+        .timeout_config(
+            aws_sdk_dynamodb::config::timeout::TimeoutConfig::builder()
+                .operation_timeout(Duration::from_secs(10))
+                .operation_attempt_timeout(Duration::from_secs(5))
+                .build(),
+        )
+        .build();
+
+    DynamoDbClient::from_conf(dynamodb_config)
+}
+
+/// Read each required variable, collecting every missing name
+///
+/// Unset and blank values both count as missing. The error lists the names
+/// in `names` order, comma-separated.
+fn read_required_vars<const N: usize>(
+    names: [&str; N],
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<[String; N], ControlConfigError> {
+    let mut missing = Vec::new();
+    let values = names.map(|name| match lookup(name) {
+        Some(value) if !value.trim().is_empty() => value,
+        _ => {
+            missing.push(name);
+            String::new()
         }
+    });
+
+    if missing.is_empty() {
+        Ok(values)
+    } else {
+        Err(ControlConfigError::MissingEnvVar(missing.join(", ")))
+    }
+}
+
+/// Parse MAX_QUERY_RANGE_DAYS (must be a positive integer)
+fn parse_max_query_range_days(value: &str) -> Result<i64, ControlConfigError> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|days| *days > 0)
+        .ok_or_else(|| ControlConfigError::InvalidEnvVar("MAX_QUERY_RANGE_DAYS".to_string()))
+}
+
+/// Parse STATS_ROW_BUDGET (a positive number of readings)
+fn parse_stats_row_budget(value: &str) -> Result<usize, ControlConfigError> {
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|rows| *rows > 0)
+        .ok_or_else(|| ControlConfigError::InvalidEnvVar("STATS_ROW_BUDGET".to_string()))
+}
+
+/// Parse MAX_BODY_BYTES (a positive whole number of bytes)
+fn parse_max_body_bytes(value: &str) -> Result<usize, ControlConfigError> {
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|bytes| *bytes > 0)
+        .ok_or_else(|| ControlConfigError::InvalidEnvVar("MAX_BODY_BYTES".to_string()))
+}
+
+/// Read a list endpoint's default and maximum page size
+///
+/// Unset variables keep `default` and `max`. Set values must be positive
+/// integers, and the resulting default may not exceed the resulting max.
+fn read_page_limits(
+    lookup: impl Fn(&str) -> Option<String>,
+    default_var: &str,
+    max_var: &str,
+    default: i32,
+    max: i32,
+) -> Result<(i32, i32), ControlConfigError> {
+    let read = |name: &str, fallback: i32| match lookup(name) {
+        Some(value) => value
+            .trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|limit| *limit > 0)
+            .ok_or_else(|| ControlConfigError::InvalidEnvVar(name.to_string())),
+        None => Ok(fallback),
+    };
+
+    let page_default = read(default_var, default)?;
+    let page_max = read(max_var, max)?;
+
+    if page_default > page_max {
+        return Err(ControlConfigError::InvalidEnvVar(default_var.to_string()));
     }
+
+    Ok((page_default, page_max))
+}
+
+/// Interpret a boolean environment flag (`true`/`1`, case-insensitive)
+fn parse_flag(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "true" | "1")
 }
 
 /// Configuration errors for Control Plane
 #[derive(Debug, thiserror::Error)]
 pub enum ControlConfigError {
-    #[error("Missing required environment variable: {0}")]
+    #[error("Missing required environment variables: {0}")]
     MissingEnvVar(String),
 
+    #[error("Invalid value for environment variable: {0}")]
+    InvalidEnvVar(String),
+
     #[error("AWS configuration error: {0}")]
     AwsConfig(String),
 }
@@ -130,19 +543,26 @@ mod tests {
         let saved_readings = std::env::var("DEVICE_READINGS_TABLE").ok();
         let saved_admin_token = std::env::var("ADMIN_TOKEN").ok();
         let saved_cors = std::env::var("CORS_ALLOWED_ORIGIN").ok();
+        let saved_pepper = std::env::var("API_KEY_PEPPER").ok();
 
         // Clear environment variables to test error handling
         std::env::remove_var("DEVICES_TABLE");
         std::env::remove_var("API_KEYS_TABLE");
         std::env::remove_var("DEVICE_READINGS_TABLE");
+        std::env::remove_var("PLANT_EVENTS_TABLE");
+        std::env::remove_var("STATUS_TRANSITIONS_TABLE");
+        std::env::remove_var("DEVICE_STATUS_TABLE");
+        std::env::remove_var("NOTES_TABLE");
+        std::env::remove_var("PLANT_AGGREGATES_TABLE");
         std::env::remove_var("ADMIN_TOKEN");
         std::env::remove_var("CORS_ALLOWED_ORIGIN");
+        std::env::remove_var("API_KEY_PEPPER");
 
         let result = ControlConfig::from_env().await;
         assert!(result.is_err());
 
         if let Err(ControlConfigError::MissingEnvVar(var)) = result {
-            assert_eq!(var, "DEVICES_TABLE");
+            assert_eq!(var, REQUIRED_ENV_VARS.join(", "));
         } else {
             panic!("Expected MissingEnvVar error");
         }
@@ -163,6 +583,9 @@ mod tests {
         if let Some(val) = saved_cors {
             std::env::set_var("CORS_ALLOWED_ORIGIN", val);
         }
+        if let Some(val) = saved_pepper {
+            std::env::set_var("API_KEY_PEPPER", val);
+        }
     }
 
     #[tokio::test]
@@ -171,7 +594,13 @@ mod tests {
         std::env::set_var("DEVICES_TABLE", "test-devices");
         std::env::set_var("API_KEYS_TABLE", "test-api-keys");
         std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
+        std::env::set_var("PLANT_EVENTS_TABLE", "test-plant-events");
+        std::env::set_var("STATUS_TRANSITIONS_TABLE", "test-status-transitions");
+        std::env::set_var("DEVICE_STATUS_TABLE", "test-device-status");
+        std::env::set_var("NOTES_TABLE", "test-notes");
+        std::env::set_var("PLANT_AGGREGATES_TABLE", "test-plant-aggregates");
         std::env::set_var("ADMIN_TOKEN", "test-admin-token");
+        std::env::set_var("API_KEY_PEPPER", "test-pepper");
         std::env::set_var("CORS_ALLOWED_ORIGIN", "https://example.com");
 
         let result = ControlConfig::from_env().await;
@@ -183,6 +612,11 @@ mod tests {
                 assert_eq!(config.devices_table, "test-devices");
                 assert_eq!(config.api_keys_table, "test-api-keys");
                 assert_eq!(config.device_readings_table, "test-device-readings");
+                assert_eq!(config.plant_events_table, "test-plant-events");
+                assert_eq!(config.status_transitions_table, "test-status-transitions");
+                assert_eq!(config.device_status_table, "test-device-status");
+                assert_eq!(config.notes_table, "test-notes");
+                assert_eq!(config.aggregates_table, "test-plant-aggregates");
                 assert_eq!(config.admin_token, "test-admin-token");
                 assert_eq!(config.cors_allowed_origin, "https://example.com");
             }
@@ -199,8 +633,14 @@ mod tests {
         std::env::remove_var("DEVICES_TABLE");
         std::env::remove_var("API_KEYS_TABLE");
         std::env::remove_var("DEVICE_READINGS_TABLE");
+        std::env::remove_var("PLANT_EVENTS_TABLE");
+        std::env::remove_var("STATUS_TRANSITIONS_TABLE");
+        std::env::remove_var("DEVICE_STATUS_TABLE");
+        std::env::remove_var("NOTES_TABLE");
+        std::env::remove_var("PLANT_AGGREGATES_TABLE");
         std::env::remove_var("ADMIN_TOKEN");
         std::env::remove_var("CORS_ALLOWED_ORIGIN");
+        std::env::remove_var("API_KEY_PEPPER");
     }
 
     #[tokio::test]
@@ -209,7 +649,13 @@ mod tests {
         std::env::set_var("DEVICES_TABLE", "test-devices");
         std::env::set_var("API_KEYS_TABLE", "test-api-keys");
         std::env::set_var("DEVICE_READINGS_TABLE", "test-device-readings");
+        std::env::set_var("PLANT_EVENTS_TABLE", "test-plant-events");
+        std::env::set_var("STATUS_TRANSITIONS_TABLE", "test-status-transitions");
+        std::env::set_var("DEVICE_STATUS_TABLE", "test-device-status");
+        std::env::set_var("NOTES_TABLE", "test-notes");
+        std::env::set_var("PLANT_AGGREGATES_TABLE", "test-plant-aggregates");
         std::env::set_var("ADMIN_TOKEN", "test-admin-token");
+        std::env::set_var("API_KEY_PEPPER", "test-pepper");
         std::env::remove_var("CORS_ALLOWED_ORIGIN");
 
         let result = ControlConfig::from_env().await;
@@ -233,7 +679,44 @@ mod tests {
         std::env::remove_var("DEVICES_TABLE");
         std::env::remove_var("API_KEYS_TABLE");
         std::env::remove_var("DEVICE_READINGS_TABLE");
+        std::env::remove_var("PLANT_EVENTS_TABLE");
+        std::env::remove_var("STATUS_TRANSITIONS_TABLE");
+        std::env::remove_var("DEVICE_STATUS_TABLE");
+        std::env::remove_var("NOTES_TABLE");
+        std::env::remove_var("PLANT_AGGREGATES_TABLE");
         std::env::remove_var("ADMIN_TOKEN");
+        std::env::remove_var("API_KEY_PEPPER");
+    }
+
+    fn complete_env(name: &str) -> Option<String> {
+        Some(format!("value-of-{}", name))
+    }
+
+    #[test]
+    fn test_read_required_vars_complete_env() {
+        let values = read_required_vars(REQUIRED_ENV_VARS, complete_env).unwrap();
+        assert_eq!(values[6], "value-of-NOTES_TABLE");
+        assert_eq!(values[7], "value-of-PLANT_AGGREGATES_TABLE");
+        assert_eq!(values[8], "value-of-ADMIN_TOKEN");
+        assert_eq!(values[9], "value-of-API_KEY_PEPPER");
+    }
+
+    #[test]
+    fn test_read_required_vars_missing_pepper() {
+        let lookup = |name: &str| {
+            (name != "API_KEY_PEPPER")
+                .then(|| complete_env(name))
+                .flatten()
+        };
+        let err = read_required_vars(REQUIRED_ENV_VARS, lookup).unwrap_err();
+
+        assert!(
+            matches!(err, ControlConfigError::MissingEnvVar(ref var) if var == "API_KEY_PEPPER")
+        );
+        assert_eq!(
+            err.to_string(),
+            "Missing required environment variables: API_KEY_PEPPER"
+        );
     }
 
     #[tokio::test]
@@ -253,5 +736,183 @@ mod tests {
         assert_eq!(config.device_readings_table, "test-device-readings");
         assert_eq!(config.admin_token, "test-admin-token");
         assert_eq!(config.cors_allowed_origin, "https://example.com");
+        assert!(!config.debug_endpoints_enabled);
+        assert_eq!(config.max_query_range_days, DEFAULT_MAX_QUERY_RANGE_DAYS);
+        assert_eq!(config.max_query_range_ms(), 31 * 86_400_000);
+        assert_eq!(config.stats_row_budget, DEFAULT_STATS_ROW_BUDGET);
+        assert_eq!(config.devices_page_default, 50);
+        assert_eq!(config.devices_page_max, 100);
+        assert_eq!(config.api_keys_page_max, 100);
+        assert_eq!(config.readings_page_max, 1000);
+    }
+
+    fn complete_builder() -> ControlConfigBuilder {
+        ControlConfigBuilder::new()
+            .devices_table("test-devices")
+            .api_keys_table("test-api-keys")
+            .device_readings_table("test-device-readings")
+            .plant_events_table("test-plant-events")
+            .status_transitions_table("test-status-transitions")
+            .device_status_table("test-device-status")
+            .notes_table("test-notes")
+            .aggregates_table("test-plant-aggregates")
+            .admin_token("test-admin-token")
+    }
+
+    #[tokio::test]
+    async fn test_builder_fully_populated_succeeds() {
+        let config = complete_builder()
+            .cors_allowed_origin("https://example.com")
+            .readings_page_limits(100, 500)
+            .build(local_dynamodb_client("http://localhost:8000"))
+            .unwrap();
+
+        assert_eq!(config.devices_table, "test-devices");
+        assert_eq!(config.api_keys_table, "test-api-keys");
+        assert_eq!(config.aggregates_table, "test-plant-aggregates");
+        assert_eq!(config.admin_token, "test-admin-token");
+        assert_eq!(config.cors_allowed_origin, "https://example.com");
+        assert_eq!(
+            (config.readings_page_default, config.readings_page_max),
+            (100, 500)
+        );
+        assert_eq!(config.devices_page_max, MAX_DEVICES_PAGE_LIMIT);
+        assert_eq!(config.max_query_range_days, DEFAULT_MAX_QUERY_RANGE_DAYS);
+    }
+
+    #[tokio::test]
+    async fn test_builder_reports_missing_admin_token() {
+        let builder = ControlConfigBuilder {
+            admin_token: None,
+            ..complete_builder()
+        };
+        let err = builder
+            .build(local_dynamodb_client("http://localhost:8000"))
+            .unwrap_err();
+
+        assert!(matches!(err, ControlConfigError::MissingEnvVar(ref var) if var == "ADMIN_TOKEN"));
+        assert_eq!(
+            err.to_string(),
+            "Missing required environment variables: ADMIN_TOKEN"
+        );
+
+        // A blank token is as good as none
+        let err = complete_builder()
+            .admin_token("  ")
+            .build(local_dynamodb_client("http://localhost:8000"))
+            .unwrap_err();
+        assert!(matches!(err, ControlConfigError::MissingEnvVar(ref var) if var == "ADMIN_TOKEN"));
+    }
+
+    #[tokio::test]
+    async fn test_builder_lists_every_missing_value() {
+        let err = ControlConfigBuilder::new()
+            .build(local_dynamodb_client("http://localhost:8000"))
+            .unwrap_err();
+
+        // Every required variable except the pepper, which isn't part of the config
+        let expected = REQUIRED_ENV_VARS[..REQUIRED_ENV_VARS.len() - 1].join(", ");
+        assert!(matches!(err, ControlConfigError::MissingEnvVar(ref var) if *var == expected));
+    }
+
+    fn page_limits(pairs: &[(&str, &str)]) -> Result<(i32, i32), ControlConfigError> {
+        let lookup = |name: &str| {
+            pairs
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        };
+        read_page_limits(
+            lookup,
+            "DEVICES_PAGE_DEFAULT",
+            "DEVICES_PAGE_MAX",
+            DEFAULT_PAGE_LIMIT,
+            MAX_DEVICES_PAGE_LIMIT,
+        )
+    }
+
+    #[test]
+    fn test_read_page_limits_defaults() {
+        assert_eq!(page_limits(&[]).unwrap(), (50, 100));
+    }
+
+    #[test]
+    fn test_read_page_limits_overrides_change_clamp() {
+        use esp32_backend::shared::validators::clamp_limit;
+
+        let (default, max) =
+            page_limits(&[("DEVICES_PAGE_DEFAULT", "20"), ("DEVICES_PAGE_MAX", "250")]).unwrap();
+        assert_eq!((default, max), (20, 250));
+        assert_eq!(clamp_limit(None, default, max), 20);
+        assert_eq!(clamp_limit(Some(200), default, max), 200);
+        assert_eq!(clamp_limit(Some(500), default, max), 250);
+
+        // Lowering only the max still caps requests at the new value
+        let (default, max) = page_limits(&[("DEVICES_PAGE_MAX", "60")]).unwrap();
+        assert_eq!(clamp_limit(Some(100), default, max), 60);
+    }
+
+    #[test]
+    fn test_read_page_limits_invalid() {
+        for invalid in ["0", "-1", "abc", ""] {
+            assert!(matches!(
+                page_limits(&[("DEVICES_PAGE_MAX", invalid)]),
+                Err(ControlConfigError::InvalidEnvVar(ref var)) if var == "DEVICES_PAGE_MAX"
+            ));
+        }
+
+        // A default above the max is rejected rather than silently clamped
+        assert!(matches!(
+            page_limits(&[("DEVICES_PAGE_DEFAULT", "200")]),
+            Err(ControlConfigError::InvalidEnvVar(ref var)) if var == "DEVICES_PAGE_DEFAULT"
+        ));
+    }
+
+    #[test]
+    fn test_parse_max_query_range_days() {
+        assert_eq!(parse_max_query_range_days("90").unwrap(), 90);
+        assert_eq!(parse_max_query_range_days(" 7 ").unwrap(), 7);
+
+        for invalid in ["0", "-1", "abc", ""] {
+            assert!(matches!(
+                parse_max_query_range_days(invalid),
+                Err(ControlConfigError::InvalidEnvVar(ref var)) if var == "MAX_QUERY_RANGE_DAYS"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_stats_row_budget() {
+        assert_eq!(parse_stats_row_budget("20000").unwrap(), 20_000);
+
+        for invalid in ["0", "-1", "abc", ""] {
+            assert!(matches!(
+                parse_stats_row_budget(invalid),
+                Err(ControlConfigError::InvalidEnvVar(ref var)) if var == "STATS_ROW_BUDGET"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_max_body_bytes() {
+        assert_eq!(parse_max_body_bytes("1048576").unwrap(), 1_048_576);
+
+        for invalid in ["0", "-1", "1MB", ""] {
+            assert!(matches!(
+                parse_max_body_bytes(invalid),
+                Err(ControlConfigError::InvalidEnvVar(ref var)) if var == "MAX_BODY_BYTES"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_flag() {
+        assert!(parse_flag("true"));
+        assert!(parse_flag("TRUE"));
+        assert!(parse_flag("1"));
+        assert!(!parse_flag("false"));
+        assert!(!parse_flag("0"));
+        assert!(!parse_flag(""));
+        assert!(!parse_flag("yes"));
     }
 }
@@ -25,8 +25,12 @@ pub fn add_cors_headers(mut response: Response<Body>) -> Response<Body> {
     );
     headers.insert(
         "Access-Control-Allow-Headers",
-        "Content-Type, Authorization, X-API-Key".parse().unwrap(),
+        "Content-Type, Authorization, X-API-Key, If-None-Match, If-Match"
+            .parse()
+            .unwrap(),
     );
+    // Let browser dashboards read the ETag for conditional requests
+    headers.insert("Access-Control-Expose-Headers", "ETag".parse().unwrap());
     headers.insert("Access-Control-Max-Age", "3600".parse().unwrap());
 
     response
@@ -75,7 +79,11 @@ mod tests {
         );
         assert_eq!(
             headers.get("Access-Control-Allow-Headers").unwrap(),
-            "Content-Type, Authorization, X-API-Key"
+            "Content-Type, Authorization, X-API-Key, If-None-Match, If-Match"
+        );
+        assert_eq!(
+            headers.get("Access-Control-Expose-Headers").unwrap(),
+            "ETag"
         );
         assert_eq!(headers.get("Access-Control-Max-Age").unwrap(), "3600");
 
@@ -118,7 +126,7 @@ mod tests {
         );
         assert_eq!(
             headers.get("Access-Control-Allow-Headers").unwrap(),
-            "Content-Type, Authorization, X-API-Key"
+            "Content-Type, Authorization, X-API-Key, If-None-Match, If-Match"
         );
         assert_eq!(headers.get("Access-Control-Max-Age").unwrap(), "3600");
 
@@ -150,7 +158,7 @@ mod tests {
         );
         assert_eq!(
             headers.get("Access-Control-Allow-Headers").unwrap(),
-            "Content-Type, Authorization, X-API-Key"
+            "Content-Type, Authorization, X-API-Key, If-None-Match, If-Match"
         );
         assert_eq!(headers.get("Access-Control-Max-Age").unwrap(), "3600");
 
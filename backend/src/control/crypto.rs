@@ -1,11 +1,13 @@
 use rand::Rng;
-use sha2::{Digest, Sha256};
 
 use crate::error::AuthError;
+use esp32_backend::shared::api_key_hash::{argon2_digest, sha256_digest};
+use esp32_backend::shared::validators::API_KEY_BYTES;
 
 /// Generate a cryptographically secure random API key
 ///
-/// Generates 32 random bytes and encodes them as a 64-character hexadecimal string.
+/// Generates `API_KEY_BYTES` (32) random bytes and encodes them as a
+/// 64-character lowercase hexadecimal string (`API_KEY_HEX_LEN`).
 /// Uses the system's cryptographically secure random number generator.
 ///
 /// # Returns
@@ -20,8 +22,17 @@ use crate::error::AuthError;
 /// assert!(api_key.chars().all(|c| c.is_ascii_hexdigit()));
 /// ```
 pub fn generate_api_key() -> String {
+    generate_api_key_with_len(API_KEY_BYTES)
+}
+
+/// Generate a random key of `bytes` random bytes, hex-encoded
+///
+/// The result is `2 * bytes` lowercase hexadecimal characters. Only keys of
+/// `API_KEY_BYTES` pass the data plane's format check; other lengths are for
+/// secrets that are never presented as an API key.
+pub fn generate_api_key_with_len(bytes: usize) -> String {
     let mut rng = rand::thread_rng();
-    let bytes: Vec<u8> = (0..32).map(|_| rng.gen()).collect();
+    let bytes: Vec<u8> = (0..bytes).map(|_| rng.gen()).collect();
     hex::encode(bytes)
 }
 
@@ -51,18 +62,62 @@ pub fn generate_api_key() -> String {
 pub fn hash_api_key(key: &str) -> Result<String, AuthError> {
     // Load pepper from environment variable
     let pepper = std::env::var("API_KEY_PEPPER").map_err(|_| AuthError::ConfigError)?;
+    Ok(sha256_digest(&pepper, key))
+}
+
+/// Every `api_key_hash` value a key may be stored under
+///
+/// New keys are stored as untagged SHA-256; the data plane upgrades them to
+/// `sha256$...` or `argon2$...` on use according to its `API_KEY_HASH_SCHEME`,
+/// so a lookup has to try each form. Both planes hash through
+/// `esp32_backend::shared::api_key_hash`, so the forms always match. While `API_KEY_PEPPER_PREVIOUS` is set, the forms
+/// under the previous pepper follow those under the current one.
+pub fn stored_hash_candidates(key: &str) -> Result<Vec<String>, AuthError> {
+    let pepper = std::env::var("API_KEY_PEPPER").map_err(|_| AuthError::ConfigError)?;
+    let mut candidates = pepper_candidates(&pepper, key)?.to_vec();
 
-    // Create SHA-256 hasher
-    let mut hasher = Sha256::new();
+    if let Some(previous) = previous_pepper() {
+        candidates.extend(pepper_candidates(&previous, key)?);
+    }
+
+    Ok(candidates)
+}
+
+/// The stored-hash forms of a key under one pepper
+fn pepper_candidates(pepper: &str, key: &str) -> Result<[String; 3], AuthError> {
+    let sha256 = sha256_digest(pepper, key);
+    let argon2 = argon2_digest(pepper, key).map_err(|_| AuthError::ConfigError)?;
+
+    Ok([
+        sha256.clone(),
+        format!("sha256${}", sha256),
+        format!("argon2${}", argon2),
+    ])
+}
 
-    // Hash: pepper || key
-    // This ensures that even if the database is leaked, attackers cannot
-    // verify API keys without knowing the pepper
-    hasher.update(pepper.as_bytes());
-    hasher.update(key.as_bytes());
+/// The pepper being rotated away from, if a rotation is in progress
+fn previous_pepper() -> Option<String> {
+    std::env::var("API_KEY_PEPPER_PREVIOUS")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+}
 
-    // Return hex-encoded hash
-    Ok(hex::encode(hasher.finalize()))
+/// Version of the current pepper, stored as `pepper_version` with new hashes
+///
+/// Read from `API_KEY_PEPPER_VERSION`; defaults to 1 when unset.
+///
+/// # Errors
+/// * `AuthError::ConfigError` - API_KEY_PEPPER_VERSION is not a positive integer
+pub fn pepper_version() -> Result<u32, AuthError> {
+    match std::env::var("API_KEY_PEPPER_VERSION") {
+        Ok(value) => value
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|v| *v > 0)
+            .ok_or(AuthError::ConfigError),
+        Err(_) => Ok(1),
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +164,29 @@ mod tests {
         assert_ne!(key1, key3, "Generated keys should be unique");
     }
 
+    #[test]
+    fn test_generate_api_key_passes_format_check() {
+        use esp32_backend::shared::validators::{is_api_key_format, API_KEY_HEX_LEN};
+
+        let key = generate_api_key();
+        assert_eq!(key.len(), API_KEY_HEX_LEN);
+        assert!(is_api_key_format(&key));
+        assert_eq!(key, key.to_lowercase(), "API key should be lowercase hex");
+    }
+
+    #[test]
+    fn test_generate_api_key_with_len() {
+        for bytes in [0, 1, 16, 48] {
+            let key = generate_api_key_with_len(bytes);
+            assert_eq!(key.len(), bytes * 2);
+            assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
+        }
+
+        assert!(!esp32_backend::shared::validators::is_api_key_format(
+            &generate_api_key_with_len(16)
+        ));
+    }
+
     #[test]
     fn test_hash_api_key_consistency() {
         let _lock = TEST_MUTEX.lock().unwrap();
@@ -260,4 +338,59 @@ mod tests {
         let hash2 = hash_api_key(&api_key).unwrap();
         assert_eq!(hash, hash2);
     }
+
+    #[test]
+    fn test_stored_hash_candidates_cover_each_form() {
+        let _lock = TEST_MUTEX.lock().unwrap();
+        setup_test_pepper("test-pepper-secret-candidates");
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+
+        std::env::remove_var("API_KEY_PEPPER_PREVIOUS");
+        let legacy = hash_api_key(key).unwrap();
+        let candidates = stored_hash_candidates(key).unwrap();
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0], legacy);
+        assert_eq!(candidates[1], format!("sha256${}", legacy));
+        assert!(candidates[2].starts_with("argon2$"));
+        assert_eq!(candidates[2].len(), "argon2$".len() + 64);
+    }
+
+    #[test]
+    fn test_stored_hash_candidates_include_previous_pepper() {
+        let _lock = TEST_MUTEX.lock().unwrap();
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+
+        // Hashed before the rotation
+        setup_test_pepper("test-pepper-secret-old");
+        let old_hash = hash_api_key(key).unwrap();
+
+        setup_test_pepper("test-pepper-secret-new");
+        std::env::set_var("API_KEY_PEPPER_PREVIOUS", "test-pepper-secret-old");
+        let candidates = stored_hash_candidates(key).unwrap();
+        std::env::remove_var("API_KEY_PEPPER_PREVIOUS");
+
+        // Current pepper's forms come first
+        assert_eq!(candidates.len(), 6);
+        assert_eq!(candidates[0], hash_api_key(key).unwrap());
+        assert_eq!(candidates[3], old_hash);
+    }
+
+    #[test]
+    fn test_pepper_version() {
+        let _lock = TEST_MUTEX.lock().unwrap();
+
+        std::env::remove_var("API_KEY_PEPPER_VERSION");
+        assert_eq!(pepper_version().unwrap(), 1);
+
+        std::env::set_var("API_KEY_PEPPER_VERSION", "3");
+        assert_eq!(pepper_version().unwrap(), 3);
+
+        for invalid in ["0", "two"] {
+            std::env::set_var("API_KEY_PEPPER_VERSION", invalid);
+            assert!(matches!(pepper_version(), Err(AuthError::ConfigError)));
+        }
+
+        std::env::remove_var("API_KEY_PEPPER_VERSION");
+    }
 }
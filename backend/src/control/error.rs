@@ -2,6 +2,7 @@ use lambda_http::{Body, Response};
 use thiserror::Error;
 
 use esp32_backend::shared::error::{error_codes, ErrorResponse};
+use esp32_backend::shared::query_params::QueryParamError;
 
 /// Main error type for the Control Plane API
 #[derive(Debug, Error)]
@@ -15,6 +16,9 @@ pub enum ApiError {
     #[error("Not found error: {0}")]
     NotFound(#[from] NotFoundError),
 
+    #[error("Precondition failed: resource has changed")]
+    PreconditionFailed,
+
     #[error("Database error: {0}")]
     Database(#[from] DatabaseError),
 
@@ -62,8 +66,8 @@ pub enum ValidationError {
     #[error("Invalid request body: {0}")]
     InvalidBody(String),
 
-    #[error("Invalid cursor format")]
-    InvalidCursor,
+    #[error("Invalid cursor format: {0}")]
+    InvalidCursor(String),
 
     #[error("Invalid pagination parameters")]
     InvalidPagination,
@@ -78,6 +82,9 @@ pub enum NotFoundError {
     #[error("Device exists but has no readings")]
     NoReadings,
 
+    #[error("Reading not found")]
+    ReadingNotFound,
+
     #[error("API key not found")]
     ApiKeyNotFound,
 
@@ -161,10 +168,10 @@ impl ApiError {
             ApiError::Validation(ValidationError::InvalidBody(msg)) => {
                 (400, error_codes::INVALID_FORMAT, msg.clone())
             }
-            ApiError::Validation(ValidationError::InvalidCursor) => (
+            ApiError::Validation(ValidationError::InvalidCursor(detail)) => (
                 400,
                 error_codes::INVALID_FORMAT,
-                String::from("Invalid cursor format"),
+                format!("Invalid cursor format: {}", detail),
             ),
             ApiError::Validation(ValidationError::InvalidPagination) => (
                 400,
@@ -183,6 +190,11 @@ impl ApiError {
                 error_codes::NO_READINGS,
                 String::from("Device exists but has no readings"),
             ),
+            ApiError::NotFound(NotFoundError::ReadingNotFound) => (
+                404,
+                error_codes::READING_NOT_FOUND,
+                String::from("Reading not found"),
+            ),
             ApiError::NotFound(NotFoundError::ApiKeyNotFound) => (
                 404,
                 error_codes::API_KEY_NOT_FOUND,
@@ -194,6 +206,13 @@ impl ApiError {
                 String::from("Resource not found"),
             ),
 
+            // Precondition errors
+            ApiError::PreconditionFailed => (
+                412,
+                error_codes::PRECONDITION_FAILED,
+                String::from("Resource has changed since it was read"),
+            ),
+
             // Database errors
             ApiError::Database(_) => (
                 500,
@@ -230,6 +249,21 @@ impl ApiError {
     }
 }
 
+impl From<QueryParamError> for ValidationError {
+    fn from(err: QueryParamError) -> Self {
+        match err {
+            QueryParamError::Missing(name) => ValidationError::MissingField(name),
+            QueryParamError::InvalidFormat(name) => ValidationError::InvalidFormat(name),
+        }
+    }
+}
+
+impl From<QueryParamError> for ApiError {
+    fn from(err: QueryParamError) -> Self {
+        ApiError::Validation(err.into())
+    }
+}
+
 impl From<aws_sdk_dynamodb::Error> for DatabaseError {
     fn from(err: aws_sdk_dynamodb::Error) -> Self {
         DatabaseError::DynamoDb(err.to_string())
@@ -321,6 +355,38 @@ mod tests {
         assert!(body.contains("req-101"));
     }
 
+    #[test]
+    fn test_reading_not_found_error_to_http_response() {
+        let error = ApiError::NotFound(NotFoundError::ReadingNotFound);
+        let response = error.to_http_response("req-102");
+
+        assert_eq!(response.status(), 404);
+
+        let body = match response.body() {
+            Body::Text(text) => text.clone(),
+            _ => panic!("Expected text body"),
+        };
+
+        assert!(body.contains("READING_NOT_FOUND"));
+        assert!(body.contains("req-102"));
+    }
+
+    #[test]
+    fn test_precondition_failed_error_to_http_response() {
+        let error = ApiError::PreconditionFailed;
+        let response = error.to_http_response("req-412");
+
+        assert_eq!(response.status(), 412);
+
+        let body = match response.body() {
+            Body::Text(text) => text.clone(),
+            _ => panic!("Expected text body"),
+        };
+
+        assert!(body.contains("PRECONDITION_FAILED"));
+        assert!(body.contains("req-412"));
+    }
+
     #[test]
     fn test_database_error_to_http_response() {
         let error = ApiError::Database(DatabaseError::DynamoDb(String::from("Connection failed")));
@@ -397,4 +463,20 @@ mod tests {
 
         assert!(body.contains(error_codes::DEVICE_NOT_FOUND));
     }
+
+    #[test]
+    fn test_query_param_error_mapping() {
+        let error: ApiError = QueryParamError::Missing(String::from("from")).into();
+        assert!(matches!(
+            error,
+            ApiError::Validation(ValidationError::MissingField(ref name)) if name == "from"
+        ));
+
+        let error: ApiError = QueryParamError::InvalidFormat(String::from("to")).into();
+        assert!(matches!(
+            error,
+            ApiError::Validation(ValidationError::InvalidFormat(ref name)) if name == "to"
+        ));
+        assert_eq!(error.to_http_response("req-test").status(), 400);
+    }
 }
@@ -0,0 +1,448 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use crate::handlers::parse_json_body;
+use crate::handlers::reading_stats::validate_range;
+use esp32_backend::shared::domain::{Reading, SENSOR_STATUS_OK};
+use esp32_backend::shared::plant_insights::{quality_weight, Aggregate, SensorStats, WindowType};
+use esp32_backend::shared::query_params::SortOrder;
+use esp32_backend::shared::time::{Clock, SystemClock};
+use esp32_backend::shared::validators::MAX_READINGS_PAGE_LIMIT;
+
+/// Request payload for recomputing a device's aggregates
+#[derive(Debug, Deserialize)]
+pub struct RecomputeRequest {
+    /// Start of range (epoch milliseconds, inclusive)
+    pub from: i64,
+    /// End of range (epoch milliseconds, inclusive)
+    pub to: i64,
+    /// Window size to recompute (`hourly`, `daily` or `weekly`)
+    pub window: WindowType,
+}
+
+/// Response payload for an aggregate recompute
+#[derive(Debug, Serialize)]
+pub struct RecomputeResponse {
+    pub hardware_id: String,
+    pub window: WindowType,
+    /// Start of the first window recomputed (epoch milliseconds)
+    pub from: i64,
+    /// End of the last window recomputed (epoch milliseconds, inclusive)
+    pub to: i64,
+    /// Number of readings read from the range
+    pub readings_read: usize,
+    /// Number of aggregates written
+    pub windows_recomputed: usize,
+}
+
+/// Widen `[from_ms, to_ms]` to whole windows after checking it against the cap
+///
+/// Only the requested span counts toward `max_range_ms`, so the aligned range
+/// may be up to one window wider on each side.
+pub fn resolve_recompute_range(
+    from_ms: i64,
+    to_ms: i64,
+    window: WindowType,
+    max_range_ms: i64,
+) -> Result<(i64, i64), ApiError> {
+    validate_range(from_ms, to_ms, max_range_ms)?;
+
+    let (start, _) = window.window_bounds(from_ms);
+    let (_, end) = window.window_bounds(to_ms);
+    Ok((start, end - 1))
+}
+
+/// Group readings by the start of the window containing each one
+pub fn group_into_windows(
+    readings: Vec<Reading>,
+    window: WindowType,
+) -> BTreeMap<i64, Vec<Reading>> {
+    let mut windows: BTreeMap<i64, Vec<Reading>> = BTreeMap::new();
+    for reading in readings {
+        let (start, _) = window.window_bounds(reading.timestamp_ms);
+        windows.entry(start).or_default().push(reading);
+    }
+    windows
+}
+
+/// Build the aggregate for one window from its readings
+///
+/// Every reading counts toward each sensor's `total_count`; a value only
+/// counts as valid when its sensor reported `ok`, matching the aggregator.
+/// Averages weight each value by its reading's quality score.
+/// Temperature is the BME280 air temperature. The window is complete once
+/// it has ended at `computed_at_ms`.
+pub fn compute_aggregate(
+    hardware_id: &str,
+    window: WindowType,
+    window_start_ms: i64,
+    readings: &[Reading],
+    computed_at_ms: i64,
+) -> Aggregate {
+    let stats = |select: fn(&Reading) -> Option<f64>, status: fn(&Reading) -> &str| {
+        let mut stats = SensorStats::default();
+        for reading in readings {
+            stats.record_weighted(
+                select(reading).filter(|_| status(reading) == SENSOR_STATUS_OK),
+                quality_weight(reading.quality),
+            );
+        }
+        stats
+    };
+
+    let (window_start_ms, window_end_ms) = window.window_bounds(window_start_ms);
+
+    Aggregate {
+        device_window: Aggregate::device_window_key(hardware_id, window),
+        hardware_id: hardware_id.to_string(),
+        window_type: window,
+        window_start_ms,
+        window_end_ms,
+        temperature_stats: Some(stats(
+            |r| r.sensors.bme280_temp_c,
+            |r| &r.sensor_status.bme280,
+        )),
+        humidity_stats: Some(stats(
+            |r| r.sensors.humidity_pct,
+            |r| &r.sensor_status.bme280,
+        )),
+        pressure_stats: Some(stats(
+            |r| r.sensors.pressure_hpa,
+            |r| &r.sensor_status.bme280,
+        )),
+        soil_moisture_stats: Some(stats(
+            |r| r.sensors.soil_moisture_pct,
+            |r| &r.sensor_status.soil_moisture,
+        )),
+        computed_at_ms,
+        is_complete: window_end_ms <= computed_at_ms,
+        ttl: None,
+    }
+}
+
+/// Handler for POST /admin/devices/{hardware_id}/recompute endpoint
+///
+/// Rebuilds a device's aggregates from its stored readings, e.g. after a
+/// backfill or an aggregator bug. The range is widened to whole windows,
+/// every reading in it is read, and one aggregate is written per window that
+/// holds readings; windows without readings are left untouched. The
+/// requested span is capped by `MAX_QUERY_RANGE_DAYS`.
+///
+/// # Returns
+/// * HTTP 200 with the number of windows recomputed
+/// * HTTP 400 if the body is invalid or the range exceeds the cap
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn recompute_aggregates(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing aggregate recompute request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let request: RecomputeRequest = parse_json_body(&event, config, &request_id)?;
+
+    let (from_ms, to_ms) = resolve_recompute_range(
+        request.from,
+        request.to,
+        request.window,
+        config.max_query_range_ms(),
+    )?;
+
+    // Check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let mut readings = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = crate::repo::readings::query_readings(
+            &config.dynamodb_client,
+            &config.device_readings_table,
+            hardware_id,
+            from_ms,
+            to_ms,
+            Some(MAX_READINGS_PAGE_LIMIT),
+            cursor,
+            SortOrder::Asc,
+        )
+        .await?;
+
+        readings.extend(page.readings);
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let readings_read = readings.len();
+    let computed_at_ms = SystemClock::new().now_epoch_ms();
+    let windows = group_into_windows(readings, request.window);
+
+    for (window_start_ms, window_readings) in &windows {
+        let aggregate = compute_aggregate(
+            hardware_id,
+            request.window,
+            *window_start_ms,
+            window_readings,
+            computed_at_ms,
+        );
+        crate::repo::aggregates::put_aggregate(
+            &config.dynamodb_client,
+            &config.aggregates_table,
+            &aggregate,
+        )
+        .await?;
+    }
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        window = %request.window,
+        readings_read = readings_read,
+        windows_recomputed = windows.len(),
+        "Recomputed aggregates"
+    );
+
+    let response = RecomputeResponse {
+        hardware_id: hardware_id.to_string(),
+        window: request.window,
+        from: from_ms,
+        to: to_ms,
+        readings_read,
+        windows_recomputed: windows.len(),
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::domain::{SensorStatus, SensorValues};
+    use std::collections::HashMap;
+
+    const HOUR_MS: i64 = 3_600_000;
+    // 2024-01-01T00:00:00Z, a Monday
+    const JAN_1_MS: i64 = 1704067200000;
+
+    fn reading(timestamp_ms: i64, temp: Option<f64>, bme280_status: &str) -> Reading {
+        Reading {
+            batch_id: "batch1".to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: temp,
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: Some(40.0),
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: SensorStatus {
+                bme280: bme280_status.to_string(),
+                ds18b20: "ok".to_string(),
+                soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Two readings in the first hour, none in the second, three in the third
+    fn known_readings() -> Vec<Reading> {
+        vec![
+            reading(JAN_1_MS, Some(20.0), "ok"),
+            reading(JAN_1_MS + HOUR_MS - 1, Some(22.0), "ok"),
+            reading(JAN_1_MS + 2 * HOUR_MS, Some(18.0), "ok"),
+            reading(JAN_1_MS + 2 * HOUR_MS + 60_000, Some(85.0), "error"),
+            reading(JAN_1_MS + 3 * HOUR_MS - 1, Some(19.0), "ok"),
+        ]
+    }
+
+    #[test]
+    fn test_group_into_windows_hourly() {
+        let windows = group_into_windows(known_readings(), WindowType::Hourly);
+
+        let starts: Vec<i64> = windows.keys().copied().collect();
+        assert_eq!(starts, vec![JAN_1_MS, JAN_1_MS + 2 * HOUR_MS]);
+        assert_eq!(windows[&JAN_1_MS].len(), 2);
+        assert_eq!(windows[&(JAN_1_MS + 2 * HOUR_MS)].len(), 3);
+    }
+
+    #[test]
+    fn test_group_into_windows_daily_and_weekly() {
+        let daily = group_into_windows(known_readings(), WindowType::Daily);
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily[&JAN_1_MS].len(), 5);
+
+        let weekly = group_into_windows(known_readings(), WindowType::Weekly);
+        assert_eq!(weekly.keys().copied().collect::<Vec<_>>(), vec![JAN_1_MS]);
+    }
+
+    #[test]
+    fn test_compute_aggregate_counts_only_ok_values() {
+        let windows = group_into_windows(known_readings(), WindowType::Hourly);
+        let start = JAN_1_MS + 2 * HOUR_MS;
+        let aggregate = compute_aggregate(
+            "AA:BB:CC:DD:EE:FF",
+            WindowType::Hourly,
+            start,
+            &windows[&start],
+            JAN_1_MS + 4 * HOUR_MS,
+        );
+
+        assert_eq!(aggregate.device_window, "AA:BB:CC:DD:EE:FF#hourly");
+        assert_eq!(aggregate.window_start_ms, start);
+        assert_eq!(aggregate.window_end_ms, start + HOUR_MS);
+        assert!(aggregate.is_complete);
+
+        let temperature = aggregate.temperature_stats.unwrap();
+        assert_eq!(temperature.total_count, 3);
+        assert_eq!(temperature.valid_count, 2);
+        assert_eq!(temperature.max, 19.0);
+        assert!((temperature.avg - 18.5).abs() < 1e-9);
+
+        let humidity = aggregate.humidity_stats.unwrap();
+        assert_eq!(humidity.total_count, 3);
+        assert_eq!(humidity.valid_count, 0);
+
+        assert_eq!(aggregate.soil_moisture_stats.unwrap().valid_count, 3);
+    }
+
+    #[test]
+    fn test_compute_aggregate_low_quality_outlier_moves_mean_less() {
+        let mut outlier = reading(JAN_1_MS + 120_000, Some(40.0), "ok");
+        let readings = vec![
+            reading(JAN_1_MS, Some(20.0), "ok"),
+            reading(JAN_1_MS + 60_000, Some(20.0), "ok"),
+            outlier.clone(),
+        ];
+        let unweighted = compute_aggregate(
+            "AA:BB:CC:DD:EE:FF",
+            WindowType::Hourly,
+            JAN_1_MS,
+            &readings,
+            JAN_1_MS + HOUR_MS,
+        );
+
+        outlier.quality = Some(0.1);
+        let readings = vec![readings[0].clone(), readings[1].clone(), outlier];
+        let weighted = compute_aggregate(
+            "AA:BB:CC:DD:EE:FF",
+            WindowType::Hourly,
+            JAN_1_MS,
+            &readings,
+            JAN_1_MS + HOUR_MS,
+        );
+
+        let unweighted = unweighted.temperature_stats.unwrap();
+        let weighted = weighted.temperature_stats.unwrap();
+        assert!((unweighted.avg - 80.0 / 3.0).abs() < 1e-9);
+        // (20 + 20 + 0.1 * 40) / 2.1
+        assert!((weighted.avg - 44.0 / 2.1).abs() < 1e-9);
+        // The outlier still counts toward the range and spread
+        assert_eq!(weighted.max, 40.0);
+        assert_eq!(weighted.valid_count, 3);
+        assert_eq!(weighted.stddev, unweighted.stddev);
+    }
+
+    #[test]
+    fn test_compute_aggregate_open_window_is_incomplete() {
+        let readings = vec![reading(JAN_1_MS + 60_000, Some(20.0), "ok")];
+        let aggregate = compute_aggregate(
+            "AA:BB:CC:DD:EE:FF",
+            WindowType::Hourly,
+            JAN_1_MS,
+            &readings,
+            JAN_1_MS + 120_000,
+        );
+
+        assert!(!aggregate.is_complete);
+    }
+
+    #[test]
+    fn test_resolve_recompute_range_aligns_to_windows() {
+        let range = resolve_recompute_range(
+            JAN_1_MS + 90 * 60_000,
+            JAN_1_MS + 150 * 60_000,
+            WindowType::Hourly,
+            31 * 86_400_000,
+        )
+        .unwrap();
+
+        assert_eq!(range, (JAN_1_MS + HOUR_MS, JAN_1_MS + 3 * HOUR_MS - 1));
+    }
+
+    #[test]
+    fn test_resolve_recompute_range_rejects_over_cap() {
+        let max_range_ms = 31 * 86_400_000;
+        let result = resolve_recompute_range(
+            JAN_1_MS,
+            JAN_1_MS + max_range_ms + 1,
+            WindowType::Daily,
+            max_range_ms,
+        );
+        assert!(matches!(
+            result,
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field))) if field == "to"
+        ));
+
+        assert!(resolve_recompute_range(
+            JAN_1_MS,
+            JAN_1_MS + max_range_ms,
+            WindowType::Daily,
+            max_range_ms
+        )
+        .is_ok());
+        assert!(
+            resolve_recompute_range(JAN_1_MS + 1, JAN_1_MS, WindowType::Daily, max_range_ms)
+                .is_err()
+        );
+    }
+}
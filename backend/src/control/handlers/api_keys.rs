@@ -1,19 +1,37 @@
 use lambda_http::{Body, Request, RequestExt, Response};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
 use tracing::{error, info};
 
 use crate::auth::validate_bearer_token;
 use crate::config::ControlConfig;
-use crate::crypto::{generate_api_key, hash_api_key};
-use crate::error::ApiError;
+use crate::crypto::{generate_api_key, hash_api_key, pepper_version, stored_hash_candidates};
+use crate::error::{ApiError, DatabaseError, ValidationError};
+use crate::handlers::{parse_json_body, PageMetadata};
+use crate::repo::api_keys::ApiKeyCreatedRange;
+use esp32_backend::shared::concurrency::bounded_map;
+use esp32_backend::shared::domain::ApiKey;
 use esp32_backend::shared::id_generator::{IdGenerator, RandomIdGenerator};
 use esp32_backend::shared::time::{Clock, SystemClock};
+use esp32_backend::shared::validators::{
+    parse_and_clamp_limit, MAX_API_KEY_DESCRIPTION_LEN, MAX_REVOKE_BATCH_KEY_IDS,
+};
+
+/// Revocations in flight at once for POST /api-keys/revoke-batch
+const REVOKE_BATCH_CONCURRENCY: usize = 10;
 
 /// Request payload for creating a new API key
 #[derive(Debug, Deserialize)]
 pub struct CreateApiKeyRequest {
-    /// Optional description for the API key
+    /// Optional description for the API key (at most 256 characters, no control characters)
     pub description: Option<String>,
+    /// Scopes granted to the key (e.g. "data:write")
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Ingest requests allowed per minute; 0 exempts the key, omitted uses the deployment-wide limit
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
 }
 
 /// Response payload for API key creation
@@ -42,6 +60,11 @@ pub struct ApiKeyListItem {
     pub is_active: bool,
     /// Optional description for the API key
     pub description: Option<String>,
+    /// Scopes granted to the key
+    pub scopes: Vec<String>,
+    /// Per-key ingest limit, omitted when the deployment-wide limit applies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_minute: Option<u32>,
 }
 
 /// Response payload for API key listing
@@ -49,11 +72,53 @@ pub struct ApiKeyListItem {
 pub struct ListApiKeysResponse {
     /// List of API keys
     pub api_keys: Vec<ApiKeyListItem>,
+    /// Page size, item count, and whether more pages follow
+    pub page: PageMetadata,
     /// Optional pageToken for pagination
     #[serde(rename = "pageToken", skip_serializing_if = "Option::is_none")]
     pub page_token: Option<String>,
 }
 
+/// Request payload for verifying an API key
+#[derive(Debug, Deserialize)]
+pub struct VerifyApiKeyRequest {
+    /// The raw API key value to check
+    pub api_key: String,
+}
+
+/// Response payload for API key verification
+#[derive(Debug, Serialize, PartialEq)]
+pub struct VerifyApiKeyResponse {
+    /// Whether the key matches a stored API key
+    pub valid: bool,
+    /// Whether the key can currently be used (false for unknown keys)
+    pub is_active: bool,
+    /// key_id of the matching key (null for unknown keys)
+    pub key_id: Option<String>,
+    /// RFC3339 expiry of the key; keys do not expire, so this is always null
+    pub expires_at: Option<String>,
+}
+
+impl VerifyApiKeyResponse {
+    /// Build the response from the hash lookup result
+    pub fn from_lookup(api_key: Option<&ApiKey>) -> Self {
+        match api_key {
+            Some(api_key) => Self {
+                valid: true,
+                is_active: api_key.is_active,
+                key_id: Some(api_key.key_id.clone()),
+                expires_at: None,
+            },
+            None => Self {
+                valid: false,
+                is_active: false,
+                key_id: None,
+                expires_at: None,
+            },
+        }
+    }
+}
+
 /// Response payload for API key revocation
 #[derive(Debug, Serialize)]
 pub struct RevokeApiKeyResponse {
@@ -63,6 +128,17 @@ pub struct RevokeApiKeyResponse {
     pub key_id: String,
 }
 
+/// Response payload for API key rotation
+#[derive(Debug, Serialize)]
+pub struct RotateApiKeyResponse {
+    /// The unchanged key_id
+    pub key_id: String,
+    /// The new raw API key value (only shown once)
+    pub api_key: String,
+    /// Warning message to save the key
+    pub message: String,
+}
+
 /// Handler for POST /api-keys endpoint
 pub async fn create_api_key(
     event: Request,
@@ -79,28 +155,13 @@ pub async fn create_api_key(
     validate_bearer_token(&event)?;
 
     // Parse request body
-    let body = match event.body() {
-        Body::Text(text) => text,
-        Body::Binary(bytes) => std::str::from_utf8(bytes).map_err(|e| {
-            error!(request_id = %request_id, error = %e, "Failed to parse request body as UTF-8");
-            ApiError::Validation(crate::error::ValidationError::InvalidBody(
-                "Request body must be valid UTF-8".to_string(),
-            ))
-        })?,
-        Body::Empty => "{}",
-    };
-
-    let request: CreateApiKeyRequest = serde_json::from_str(body).map_err(|e| {
-        error!(request_id = %request_id, error = %e, "Failed to deserialize request body");
-        ApiError::Validation(crate::error::ValidationError::InvalidBody(format!(
-            "Invalid JSON: {}",
-            e
-        )))
-    })?;
+    let request: CreateApiKeyRequest = parse_json_body(&event, config, &request_id)?;
+    let description = normalize_description(request.description)?;
 
     info!(
         request_id = %request_id,
-        has_description = request.description.is_some(),
+        has_description = description.is_some(),
+        scopes = ?request.scopes,
         "Parsed create API key request"
     );
 
@@ -113,6 +174,7 @@ pub async fn create_api_key(
 
     // Hash the API key
     let api_key_hash = hash_api_key(&api_key)?;
+    let pepper_version = pepper_version()?;
     info!(
         request_id = %request_id,
         "Hashed API key"
@@ -139,8 +201,11 @@ pub async fn create_api_key(
         &config.api_keys_table,
         &key_id,
         &api_key_hash,
+        pepper_version,
         &created_at,
-        request.description,
+        description,
+        &request.scopes,
+        request.rate_limit_per_minute,
     )
     .await?;
 
@@ -177,6 +242,14 @@ pub async fn create_api_key(
 }
 
 /// Handler for GET /api-keys endpoint
+///
+/// # Query Parameters
+/// * `limit` - Maximum number of keys to evaluate (default 50, max 100)
+/// * `pageToken` - Optional pagination token from previous response
+/// * `scope` - Optional scope filter (e.g. `data:write`); filtered pages may be
+///   sparse, so keep following `pageToken` until it is absent
+/// * `createdAfter` / `createdBefore` - Optional inclusive RFC3339 bounds on
+///   `created_at`
 pub async fn list_api_keys(
     event: Request,
     config: &ControlConfig,
@@ -194,19 +267,28 @@ pub async fn list_api_keys(
     // Parse query parameters
     let query_params = event.query_string_parameters();
 
-    let limit: i32 = query_params
-        .first("limit")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(50); // Default limit
-
-    let limit = limit.min(100).max(1); // Clamp between 1 and 100
+    let limit = parse_and_clamp_limit(
+        query_params.first("limit"),
+        config.api_keys_page_default,
+        config.api_keys_page_max,
+    );
 
     let page_token = query_params.first("pageToken").map(|s| s.to_string());
 
+    let scope = query_params.first("scope");
+
+    let created = parse_created_range(
+        query_params.first("createdAfter"),
+        query_params.first("createdBefore"),
+    )?;
+
     info!(
         request_id = %request_id,
         limit = limit,
         has_page_token = page_token.is_some(),
+        scope = ?scope,
+        created_after = ?created.created_after,
+        created_before = ?created.created_before,
         "Parsed query parameters"
     );
 
@@ -216,6 +298,8 @@ pub async fn list_api_keys(
         &config.api_keys_table,
         limit,
         page_token,
+        scope,
+        &created,
     )
     .await?;
 
@@ -235,11 +319,14 @@ pub async fn list_api_keys(
             last_used_at: key.last_used_at,
             is_active: key.is_active,
             description: key.description,
+            scopes: key.scopes,
+            rate_limit_per_minute: key.rate_limit_per_minute,
         })
         .collect();
 
     // Build response
     let response = ListApiKeysResponse {
+        page: PageMetadata::new(limit, api_key_items.len(), page_token.as_ref()),
         api_keys: api_key_items,
         page_token,
     };
@@ -261,6 +348,57 @@ pub async fn list_api_keys(
         .unwrap())
 }
 
+/// Trim an API key description and reject oversized or unprintable ones
+///
+/// A description that is blank after trimming is dropped. Control
+/// characters (newlines, NUL, etc.) are rejected rather than stripped.
+fn normalize_description(description: Option<String>) -> Result<Option<String>, ApiError> {
+    let Some(description) = description else {
+        return Ok(None);
+    };
+
+    let trimmed = description.trim();
+    if trimmed.chars().count() > MAX_API_KEY_DESCRIPTION_LEN
+        || trimmed.chars().any(char::is_control)
+    {
+        return Err(ValidationError::InvalidFormat("description".to_string()).into());
+    }
+
+    Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
+}
+
+/// Parse the `createdAfter` / `createdBefore` query parameters
+///
+/// Bounds are normalized to UTC in the same RFC3339 form as the stored
+/// `created_at`, so the sort-key comparison is lexicographically correct.
+fn parse_created_range(
+    created_after: Option<&str>,
+    created_before: Option<&str>,
+) -> Result<ApiKeyCreatedRange, ApiError> {
+    fn normalize(value: Option<&str>, field: &str) -> Result<Option<String>, ApiError> {
+        value
+            .map(|v| {
+                chrono::DateTime::parse_from_rfc3339(v)
+                    .map(|dt| dt.with_timezone(&chrono::Utc).to_rfc3339())
+                    .map_err(|_| ValidationError::InvalidFormat(field.to_string()).into())
+            })
+            .transpose()
+    }
+
+    let range = ApiKeyCreatedRange {
+        created_after: normalize(created_after, "createdAfter")?,
+        created_before: normalize(created_before, "createdBefore")?,
+    };
+
+    if let (Some(after), Some(before)) = (&range.created_after, &range.created_before) {
+        if after > before {
+            return Err(ValidationError::InvalidValue(String::from("createdAfter")).into());
+        }
+    }
+
+    Ok(range)
+}
+
 /// Handler for DELETE /api-keys/{key_id} endpoint
 pub async fn revoke_api_key(
     event: Request,
@@ -286,7 +424,14 @@ pub async fn revoke_api_key(
 
     // Call revoke_api_key() to set is_active=false
     crate::repo::api_keys::revoke_api_key(&config.dynamodb_client, &config.api_keys_table, key_id)
-        .await?;
+        .await
+        .map_err(|e| match e {
+            DatabaseError::ConditionalCheckFailed => {
+                info!(request_id = %request_id, key_id = %key_id, "API key not found");
+                ApiError::NotFound(crate::error::NotFoundError::ApiKeyNotFound)
+            }
+            e => ApiError::Database(e),
+        })?;
 
     info!(
         request_id = %request_id,
@@ -317,6 +462,270 @@ pub async fn revoke_api_key(
         .unwrap())
 }
 
+/// Request payload for POST /api-keys/revoke-batch
+#[derive(Debug, Deserialize)]
+pub struct RevokeApiKeysBatchRequest {
+    pub key_ids: Vec<String>,
+}
+
+/// Outcome of revoking one key in a batch
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RevokeBatchResult {
+    pub key_id: String,
+    /// True when the key is now revoked (including keys that already were),
+    /// false when no API key exists with this key_id
+    pub revoked: bool,
+}
+
+/// Response payload for POST /api-keys/revoke-batch
+#[derive(Debug, Serialize)]
+pub struct RevokeApiKeysBatchResponse {
+    /// One result per distinct key_id, in request order
+    pub results: Vec<RevokeBatchResult>,
+}
+
+/// Handler for POST /api-keys/revoke-batch endpoint
+///
+/// Revokes up to 100 API keys in one call, `REVOKE_BATCH_CONCURRENCY` at a
+/// time. Duplicate IDs are collapsed. An unknown key_id is reported as
+/// `revoked: false` instead of failing the request; revoking is idempotent,
+/// so a request that fails on a database error can be retried as is.
+///
+/// # Request Body
+/// * `key_ids` - API key IDs to revoke (1 to 100 distinct IDs)
+///
+/// # Returns
+/// * HTTP 200 with `{ key_id, revoked }` per key
+/// * HTTP 400 if the body is invalid, empty, or lists more than 100 IDs
+/// * HTTP 401 if Bearer token is invalid
+pub async fn revoke_api_keys_batch(
+    event: Request,
+    config: &ControlConfig,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        "Processing batch revoke API keys request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let request: RevokeApiKeysBatchRequest = parse_json_body(&event, config, &request_id)?;
+    let key_ids = normalize_key_ids(request.key_ids)?;
+
+    info!(
+        request_id = %request_id,
+        count = key_ids.len(),
+        "Revoking API keys"
+    );
+
+    let results = revoke_each(key_ids, |key_id| async move {
+        crate::repo::api_keys::revoke_api_key(
+            &config.dynamodb_client,
+            &config.api_keys_table,
+            &key_id,
+        )
+        .await
+    })
+    .await?;
+
+    info!(
+        request_id = %request_id,
+        revoked = results.iter().filter(|r| r.revoked).count(),
+        unknown = results.iter().filter(|r| !r.revoked).count(),
+        "Batch revoke complete"
+    );
+
+    let response_body =
+        serde_json::to_string(&RevokeApiKeysBatchResponse { results }).map_err(|e| {
+            error!(request_id = %request_id, error = %e, "Failed to serialize response");
+            ApiError::Internal(format!("Failed to serialize response: {}", e))
+        })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Dedupe key IDs (keeping first occurrence order) and enforce the cap
+fn normalize_key_ids(key_ids: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let mut seen = HashSet::new();
+    let unique: Vec<String> = key_ids
+        .into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .collect();
+
+    if unique.is_empty() || unique.len() > MAX_REVOKE_BATCH_KEY_IDS {
+        return Err(ValidationError::InvalidValue(String::from("key_ids")).into());
+    }
+
+    Ok(unique)
+}
+
+/// Run `revoke` for every key ID, `REVOKE_BATCH_CONCURRENCY` at a time
+///
+/// A `ConditionalCheckFailed` (unknown key) becomes `revoked: false`; any
+/// other database error fails the batch once every call has finished.
+async fn revoke_each<F, Fut>(
+    key_ids: Vec<String>,
+    revoke: F,
+) -> Result<Vec<RevokeBatchResult>, DatabaseError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<(), DatabaseError>>,
+{
+    let outcomes = bounded_map(key_ids.clone(), REVOKE_BATCH_CONCURRENCY, revoke).await;
+
+    key_ids
+        .into_iter()
+        .zip(outcomes)
+        .map(|(key_id, outcome)| {
+            let revoked = match outcome {
+                Ok(()) => true,
+                Err(DatabaseError::ConditionalCheckFailed) => false,
+                Err(e) => return Err(e),
+            };
+            Ok(RevokeBatchResult { key_id, revoked })
+        })
+        .collect()
+}
+
+/// Handler for POST /api-keys/{key_id}/rotate endpoint
+///
+/// Generates a new raw key for an existing key_id and replaces the stored hash,
+/// so devices can be reconfigured without changing key_id. The previous raw
+/// key stops working immediately.
+///
+/// # Returns
+/// * HTTP 200 with the new raw key (shown once) and the same key_id
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if no API key exists with this key_id
+pub async fn rotate_api_key(
+    event: Request,
+    config: &ControlConfig,
+    key_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        key_id = %key_id,
+        "Processing rotate API key request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    // Generate and hash the replacement key
+    let api_key = generate_api_key();
+    let api_key_hash = hash_api_key(&api_key)?;
+
+    crate::repo::api_keys::rotate_key(
+        &config.dynamodb_client,
+        &config.api_keys_table,
+        key_id,
+        &api_key_hash,
+        pepper_version()?,
+    )
+    .await
+    .map_err(|e| match e {
+        crate::error::DatabaseError::ConditionalCheckFailed => {
+            info!(request_id = %request_id, key_id = %key_id, "API key not found");
+            ApiError::NotFound(crate::error::NotFoundError::ApiKeyNotFound)
+        }
+        e => ApiError::Database(e),
+    })?;
+
+    info!(
+        request_id = %request_id,
+        key_id = %key_id,
+        "API key rotated successfully"
+    );
+
+    let response = RotateApiKeyResponse {
+        key_id: key_id.to_string(),
+        api_key,
+        message: "API key rotated successfully. Save this key - it will not be shown again."
+            .to_string(),
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Handler for POST /api-keys/verify endpoint
+///
+/// Hashes the submitted key and looks it up without updating `last_used_at`,
+/// so integration tooling can check a key before putting it on a device.
+///
+/// # Returns
+/// * HTTP 200 with `valid`, `is_active`, `key_id` and `expires_at`
+///   (`valid=false` for unknown keys)
+/// * HTTP 400 if the body is missing `api_key` or is too large
+/// * HTTP 401 if Bearer token is invalid
+pub async fn verify_api_key(
+    event: Request,
+    config: &ControlConfig,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        "Processing verify API key request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let request: VerifyApiKeyRequest = parse_json_body(&event, config, &request_id)?;
+
+    // The data plane may have upgraded the stored hash, so try every form
+    let mut api_key = None;
+    for api_key_hash in stored_hash_candidates(&request.api_key)? {
+        api_key = crate::repo::api_keys::get_api_key_by_hash(
+            &config.dynamodb_client,
+            &config.api_keys_table,
+            &api_key_hash,
+        )
+        .await?;
+        if api_key.is_some() {
+            break;
+        }
+    }
+
+    let response = VerifyApiKeyResponse::from_lookup(api_key.as_ref());
+
+    info!(
+        request_id = %request_id,
+        valid = response.valid,
+        is_active = response.is_active,
+        "Verified API key"
+    );
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,6 +763,53 @@ mod tests {
         let json = r#"{}"#;
         let request: CreateApiKeyRequest = serde_json::from_str(json).unwrap();
         assert_eq!(request.description, None);
+        assert!(request.scopes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_api_key_request_deserialization_with_scopes() {
+        let json = r#"{"description":"Ingest key","scopes":["data:write"]}"#;
+        let request: CreateApiKeyRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.scopes, vec!["data:write".to_string()]);
+        assert_eq!(request.rate_limit_per_minute, None);
+    }
+
+    #[tokio::test]
+    async fn test_create_api_key_request_deserialization_with_rate_limit() {
+        let json = r#"{"description":"Busy gateway","rate_limit_per_minute":600}"#;
+        let request: CreateApiKeyRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.rate_limit_per_minute, Some(600));
+    }
+
+    #[test]
+    fn test_normalize_description_accepts_and_trims() {
+        assert_eq!(
+            normalize_description(Some("  Greenhouse cluster  ".to_string())).unwrap(),
+            Some("Greenhouse cluster".to_string())
+        );
+        assert_eq!(
+            normalize_description(Some("é".repeat(MAX_API_KEY_DESCRIPTION_LEN))).unwrap(),
+            Some("é".repeat(MAX_API_KEY_DESCRIPTION_LEN))
+        );
+        assert_eq!(normalize_description(None).unwrap(), None);
+        assert_eq!(
+            normalize_description(Some("   ".to_string())).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize_description_rejects_long_and_control_chars() {
+        for description in [
+            "x".repeat(MAX_API_KEY_DESCRIPTION_LEN + 1),
+            "line one\nline two".to_string(),
+            "null\0byte".to_string(),
+        ] {
+            assert!(matches!(
+                normalize_description(Some(description)),
+                Err(ApiError::Validation(ValidationError::InvalidFormat(ref f))) if f == "description"
+            ));
+        }
     }
 
     #[tokio::test]
@@ -489,6 +945,125 @@ mod tests {
         std::env::remove_var("API_KEY_PEPPER");
     }
 
+    #[tokio::test]
+    async fn test_create_api_key_oversized_body() {
+        // Set up environment
+        std::env::set_var("ADMIN_TOKEN", "test-token");
+        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+
+        let mut config = ControlConfig::for_test(
+            "http://localhost:8000",
+            "test-devices".to_string(),
+            "test-api-keys".to_string(),
+            "test-device-readings".to_string(),
+            "test-admin-token".to_string(),
+            "*".to_string(),
+        )
+        .await;
+        config.max_body_bytes = 64;
+
+        let body = format!(r#"{{"description": "{}"}}"#, "x".repeat(64));
+        let request =
+            create_test_request(Method::POST, "/api-keys", &body, Some("Bearer test-token"));
+
+        match create_api_key(request, &config).await {
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidBody(msg))) => {
+                assert!(msg.starts_with("payload too large"));
+            }
+            other => panic!("Expected InvalidBody error, got: {:?}", other),
+        }
+
+        // Clean up
+        std::env::remove_var("ADMIN_TOKEN");
+        std::env::remove_var("API_KEY_PEPPER");
+    }
+
+    fn stored_key(is_active: bool) -> ApiKey {
+        ApiKey {
+            key_id: "a1b2c3d4-e5f6-7890-abcd-ef1234567890".to_string(),
+            api_key_hash: "hash".to_string(),
+            created_at: "2024-01-15T10:30:00Z".to_string(),
+            last_used_at: None,
+            is_active,
+            description: None,
+            scopes: vec!["data:write".to_string()],
+            rate_limit_per_minute: None,
+        }
+    }
+
+    #[test]
+    fn test_verify_api_key_valid_active_key() {
+        let response = VerifyApiKeyResponse::from_lookup(Some(&stored_key(true)));
+
+        assert_eq!(
+            response,
+            VerifyApiKeyResponse {
+                valid: true,
+                is_active: true,
+                key_id: Some("a1b2c3d4-e5f6-7890-abcd-ef1234567890".to_string()),
+                expires_at: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_api_key_revoked_key() {
+        let response = VerifyApiKeyResponse::from_lookup(Some(&stored_key(false)));
+
+        assert!(response.valid);
+        assert!(!response.is_active);
+        assert_eq!(
+            response.key_id.as_deref(),
+            Some("a1b2c3d4-e5f6-7890-abcd-ef1234567890")
+        );
+    }
+
+    #[test]
+    fn test_verify_api_key_unknown_key() {
+        let response = VerifyApiKeyResponse::from_lookup(None);
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            json,
+            r#"{"valid":false,"is_active":false,"key_id":null,"expires_at":null}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_api_key_missing_api_key_field() {
+        // Set up environment
+        std::env::set_var("ADMIN_TOKEN", "test-token");
+        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+
+        let config = ControlConfig::for_test(
+            "http://localhost:8000",
+            "test-devices".to_string(),
+            "test-api-keys".to_string(),
+            "test-device-readings".to_string(),
+            "test-admin-token".to_string(),
+            "*".to_string(),
+        )
+        .await;
+
+        let request = create_test_request(
+            Method::POST,
+            "/api-keys/verify",
+            "{}",
+            Some("Bearer test-token"),
+        );
+
+        match verify_api_key(request, &config).await {
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidBody(msg))) => {
+                assert!(msg.contains("api_key"));
+            }
+            other => panic!("Expected InvalidBody error, got: {:?}", other),
+        }
+
+        // Clean up
+        std::env::remove_var("ADMIN_TOKEN");
+        std::env::remove_var("API_KEY_PEPPER");
+    }
+
     #[tokio::test]
     async fn test_list_api_keys_response_serialization() {
         let response = ListApiKeysResponse {
@@ -499,6 +1074,8 @@ mod tests {
                     last_used_at: Some("2024-01-15T14:22:00Z".to_string()),
                     is_active: true,
                     description: Some("Test key 1".to_string()),
+                    scopes: vec!["data:write".to_string()],
+                    rate_limit_per_minute: Some(600),
                 },
                 ApiKeyListItem {
                     key_id: "key-2".to_string(),
@@ -506,8 +1083,11 @@ mod tests {
                     last_used_at: None,
                     is_active: false,
                     description: None,
+                    scopes: vec![],
+                    rate_limit_per_minute: None,
                 },
             ],
+            page: PageMetadata::new(2, 2, Some(&"base64pagetoken".to_string())),
             page_token: Some("base64pagetoken".to_string()),
         };
 
@@ -517,9 +1097,69 @@ mod tests {
         assert!(json.contains("key-2"));
         assert!(json.contains("nextPageToken"));
         assert!(json.contains("base64pagetoken"));
+        assert!(json.contains("\"scopes\":[\"data:write\"]"));
+        assert_eq!(json.matches("rate_limit_per_minute").count(), 1);
 
         // Verify api_key_hash is NOT in the response
         assert!(!json.contains("api_key_hash"));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["page"],
+            serde_json::json!({"limit": 2, "returned": 2, "has_more": true})
+        );
+    }
+
+    #[test]
+    fn test_list_api_keys_response_last_page() {
+        let response = ListApiKeysResponse {
+            api_keys: vec![],
+            page: PageMetadata::new(50, 0, None),
+            page_token: None,
+        };
+
+        let value = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            value["page"],
+            serde_json::json!({"limit": 50, "returned": 0, "has_more": false})
+        );
+        assert!(value.get("pageToken").is_none());
+    }
+
+    #[test]
+    fn test_parse_created_range() {
+        let range = parse_created_range(None, None).unwrap();
+        assert_eq!(range, ApiKeyCreatedRange::default());
+
+        let range = parse_created_range(
+            Some("2024-01-15T12:00:00+02:00"),
+            Some("2024-02-01T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(
+            range.created_after.as_deref(),
+            Some("2024-01-15T10:00:00+00:00")
+        );
+        assert_eq!(
+            range.created_before.as_deref(),
+            Some("2024-02-01T00:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_created_range_invalid() {
+        assert!(matches!(
+            parse_created_range(Some("yesterday"), None),
+            Err(ApiError::Validation(ValidationError::InvalidFormat(ref f))) if f == "createdAfter"
+        ));
+        assert!(matches!(
+            parse_created_range(None, Some("2024-01-15")),
+            Err(ApiError::Validation(ValidationError::InvalidFormat(ref f))) if f == "createdBefore"
+        ));
+        assert!(matches!(
+            parse_created_range(Some("2024-02-01T00:00:00Z"), Some("2024-01-01T00:00:00Z")),
+            Err(ApiError::Validation(ValidationError::InvalidValue(_)))
+        ));
     }
 
     #[tokio::test]
@@ -672,4 +1312,156 @@ mod tests {
         std::env::remove_var("ADMIN_TOKEN");
         std::env::remove_var("API_KEY_PEPPER");
     }
+
+    #[tokio::test]
+    async fn test_rotate_api_key_response_serialization() {
+        let response = RotateApiKeyResponse {
+            key_id: "a1b2c3d4-e5f6-7890-abcd-ef1234567890".to_string(),
+            api_key: "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8".to_string(),
+            message: "API key rotated successfully. Save this key - it will not be shown again."
+                .to_string(),
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["key_id"], "a1b2c3d4-e5f6-7890-abcd-ef1234567890");
+        assert_eq!(
+            json["api_key"],
+            "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8"
+        );
+        assert!(json["message"].as_str().unwrap().contains("rotated"));
+        assert_eq!(json.as_object().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_api_key_missing_auth_header() {
+        std::env::set_var("ADMIN_TOKEN", "test-token");
+        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+
+        let config = ControlConfig::for_test(
+            "http://localhost:8000",
+            "test-devices".to_string(),
+            "test-api-keys".to_string(),
+            "test-device-readings".to_string(),
+            "test-admin-token".to_string(),
+            "*".to_string(),
+        )
+        .await;
+
+        let request = create_test_request(Method::POST, "/api-keys/test-key-id/rotate", "", None);
+
+        let result = rotate_api_key(request, &config, "test-key-id").await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Auth(crate::error::AuthError::MissingToken))
+        ));
+
+        std::env::remove_var("ADMIN_TOKEN");
+        std::env::remove_var("API_KEY_PEPPER");
+    }
+
+    #[test]
+    fn test_normalize_key_ids_dedupes_and_caps() {
+        let key_ids = vec!["a".to_string(), "b".to_string(), "a".to_string()];
+        assert_eq!(normalize_key_ids(key_ids).unwrap(), vec!["a", "b"]);
+
+        let at_cap: Vec<String> = (0..MAX_REVOKE_BATCH_KEY_IDS)
+            .map(|i| format!("key-{}", i))
+            .collect();
+        assert_eq!(normalize_key_ids(at_cap).unwrap().len(), 100);
+
+        let over_cap: Vec<String> = (0..=MAX_REVOKE_BATCH_KEY_IDS)
+            .map(|i| format!("key-{}", i))
+            .collect();
+        assert!(matches!(
+            normalize_key_ids(over_cap),
+            Err(ApiError::Validation(ValidationError::InvalidValue(ref f))) if f == "key_ids"
+        ));
+        assert!(normalize_key_ids(vec![]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_each_reports_existing_and_unknown_keys() {
+        let key_ids = vec![
+            "known-1".to_string(),
+            "unknown".to_string(),
+            "already-revoked".to_string(),
+        ];
+
+        let results = revoke_each(key_ids, |key_id| async move {
+            if key_id == "unknown" {
+                Err(DatabaseError::ConditionalCheckFailed)
+            } else {
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                RevokeBatchResult {
+                    key_id: "known-1".to_string(),
+                    revoked: true,
+                },
+                RevokeBatchResult {
+                    key_id: "unknown".to_string(),
+                    revoked: false,
+                },
+                RevokeBatchResult {
+                    key_id: "already-revoked".to_string(),
+                    revoked: true,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revoke_each_fails_on_database_error() {
+        let result = revoke_each(
+            vec!["a".to_string(), "b".to_string()],
+            |key_id| async move {
+                if key_id == "b" {
+                    Err(DatabaseError::DynamoDb("throttled".to_string()))
+                } else {
+                    Ok(())
+                }
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(DatabaseError::DynamoDb(_))));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_api_keys_batch_missing_auth_header() {
+        std::env::set_var("ADMIN_TOKEN", "test-token");
+        std::env::set_var("API_KEY_PEPPER", "test-pepper");
+
+        let config = ControlConfig::for_test(
+            "http://localhost:8000",
+            "test-devices".to_string(),
+            "test-api-keys".to_string(),
+            "test-device-readings".to_string(),
+            "test-admin-token".to_string(),
+            "*".to_string(),
+        )
+        .await;
+
+        let request = create_test_request(
+            Method::POST,
+            "/api-keys/revoke-batch",
+            r#"{"key_ids":["a"]}"#,
+            None,
+        );
+
+        let result = revoke_api_keys_batch(request, &config).await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Auth(crate::error::AuthError::MissingToken))
+        ));
+
+        std::env::remove_var("ADMIN_TOKEN");
+        std::env::remove_var("API_KEY_PEPPER");
+    }
 }
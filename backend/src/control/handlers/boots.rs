@@ -0,0 +1,276 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use std::collections::HashMap;
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use crate::handlers::latest_readings::to_response_item;
+use crate::handlers::readings::ReadingResponseItem;
+use esp32_backend::shared::domain::Reading;
+use esp32_backend::shared::query_params::parse_clamped_limit;
+use esp32_backend::shared::validators::{
+    DEFAULT_BOOTS_LIMIT, MAX_BOOTS_LIMIT, MAX_READINGS_PAGE_LIMIT,
+};
+
+/// Readings from one boot session
+#[derive(Debug)]
+pub struct BootGroup<'a> {
+    /// Non-empty; every reading shares the same boot_id
+    pub readings: Vec<&'a Reading>,
+}
+
+impl<'a> BootGroup<'a> {
+    /// Final reading of the boot (latest timestamp; ties go to the higher batch_id)
+    pub fn latest(&self) -> &'a Reading {
+        self.readings
+            .iter()
+            .copied()
+            .max_by(|a, b| {
+                a.timestamp_ms
+                    .cmp(&b.timestamp_ms)
+                    .then_with(|| a.batch_id.cmp(&b.batch_id))
+            })
+            .expect("boot group is never empty")
+    }
+}
+
+/// Group readings by boot_id, most recently active boot first
+///
+/// Boots are ordered by their latest reading, so a boot whose readings
+/// arrived out of order still sorts by when it last reported.
+pub fn group_boots(readings: &[Reading]) -> Vec<BootGroup<'_>> {
+    let mut groups: Vec<BootGroup<'_>> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+
+    for reading in readings {
+        let slot = *index.entry(&reading.boot_id).or_insert_with(|| {
+            groups.push(BootGroup {
+                readings: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[slot].readings.push(reading);
+    }
+
+    groups.sort_by_key(|group| std::cmp::Reverse(group.latest().timestamp_ms));
+    groups
+}
+
+/// Final reading of each of the `limit` most recent boots, newest boot first
+pub fn latest_reading_per_boot(readings: &[Reading], limit: usize) -> Vec<&Reading> {
+    group_boots(readings)
+        .iter()
+        .take(limit)
+        .map(BootGroup::latest)
+        .collect()
+}
+
+/// Response payload for the latest reading of each recent boot
+#[derive(Debug, Serialize)]
+pub struct BootLatestReadingsResponse {
+    pub hardware_id: String,
+    /// One entry per boot, most recent boot first
+    pub boots: Vec<ReadingResponseItem>,
+}
+
+/// Handler for GET /devices/{hardware_id}/boots/latest-readings endpoint
+///
+/// Returns the final reading of each of the most recent boot sessions, so
+/// firmware QA can check how each boot ended. Boots are found among the
+/// newest `MAX_READINGS_PAGE_LIMIT` readings; older boots are not reported.
+///
+/// # Query Parameters
+/// * `limit` - Maximum number of boots to return (default 10, max 50)
+///
+/// # Returns
+/// * HTTP 200 with one reading per boot (empty when the device has no readings)
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn get_boot_latest_readings(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing boot latest readings request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let limit = parse_clamped_limit(
+        &event.query_string_parameters(),
+        DEFAULT_BOOTS_LIMIT,
+        MAX_BOOTS_LIMIT,
+    );
+
+    // First, check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let readings = crate::repo::readings::get_recent_readings(
+        &config.dynamodb_client,
+        &config.device_readings_table,
+        hardware_id,
+        MAX_READINGS_PAGE_LIMIT,
+    )
+    .await?;
+
+    let boots: Vec<ReadingResponseItem> = latest_reading_per_boot(&readings, limit as usize)
+        .into_iter()
+        .cloned()
+        .map(to_response_item)
+        .collect();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        scanned = readings.len(),
+        boots = boots.len(),
+        "Retrieved latest reading per boot"
+    );
+
+    let response = BootLatestReadingsResponse {
+        hardware_id: hardware_id.to_string(),
+        boots,
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::domain::{SensorStatus, SensorValues};
+
+    const BOOT_A: &str = "7c9e6679-7425-40de-944b-e07fc1f90ae7";
+    const BOOT_B: &str = "9b2f3c1e-4d5a-4e6b-8c7d-0e1f2a3b4c5d";
+
+    fn reading(boot_id: &str, timestamp_ms: i64) -> Reading {
+        Reading {
+            batch_id: format!("batch-{}", timestamp_ms),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms,
+            boot_id: boot_id.to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: Some(21.0),
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: None,
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: SensorStatus {
+                bme280: "ok".to_string(),
+                ds18b20: "ok".to_string(),
+                soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_two_boots_return_their_final_readings() {
+        // Newest first, as the repo returns them
+        let readings = vec![
+            reading(BOOT_B, 5000),
+            reading(BOOT_B, 4000),
+            reading(BOOT_A, 3000),
+            reading(BOOT_A, 2000),
+            reading(BOOT_A, 1000),
+        ];
+
+        let latest = latest_reading_per_boot(&readings, 10);
+
+        assert_eq!(latest.len(), 2);
+        assert_eq!(
+            (latest[0].boot_id.as_str(), latest[0].timestamp_ms),
+            (BOOT_B, 5000)
+        );
+        assert_eq!(
+            (latest[1].boot_id.as_str(), latest[1].timestamp_ms),
+            (BOOT_A, 3000)
+        );
+    }
+
+    #[test]
+    fn test_limit_caps_boot_count() {
+        let readings: Vec<Reading> = (0..5)
+            .map(|i| {
+                let boot_id = format!("00000000-0000-4000-8000-00000000000{}", i);
+                reading(&boot_id, 1000 * (i + 1))
+            })
+            .collect();
+
+        let latest = latest_reading_per_boot(&readings, 3);
+
+        let timestamps: Vec<i64> = latest.iter().map(|r| r.timestamp_ms).collect();
+        assert_eq!(timestamps, vec![5000, 4000, 3000]);
+    }
+
+    #[test]
+    fn test_group_boots_orders_by_latest_reading() {
+        // A buffered reading from BOOT_A arrives after BOOT_B started
+        let readings = vec![
+            reading(BOOT_B, 4000),
+            reading(BOOT_A, 6000),
+            reading(BOOT_A, 1000),
+        ];
+
+        let groups = group_boots(&readings);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].latest().boot_id, BOOT_A);
+        assert_eq!(groups[0].readings.len(), 2);
+        assert_eq!(groups[0].latest().timestamp_ms, 6000);
+        assert_eq!(groups[1].latest().boot_id, BOOT_B);
+    }
+
+    #[test]
+    fn test_latest_reading_per_boot_empty() {
+        assert!(latest_reading_per_boot(&[], 10).is_empty());
+
+        let response = BootLatestReadingsResponse {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            boots: vec![],
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"boots\":[]"));
+    }
+}
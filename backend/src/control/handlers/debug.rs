@@ -0,0 +1,448 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use esp32_backend::shared::cursor::{
+    decode_api_key_page_token, decode_device_page_token, decode_events_page_token,
+    decode_global_ingest_time_page_token, decode_ingest_time_page_token,
+    decode_insights_page_token, decode_readings_page_token, page_token_version,
+    GlobalIngestShardPosition,
+};
+
+/// Kind of pagination cursor accepted by the debug endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorType {
+    Devices,
+    Readings,
+    /// A device's readings by ingest time (`since_ingest` queries)
+    IngestTime,
+    /// Fleet-wide readings by ingest time (GET /admin/readings/recent)
+    GlobalIngestTime,
+    ApiKeys,
+    Events,
+    Insights,
+}
+
+impl CursorType {
+    /// Parse the `type` query parameter value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "devices" => Some(CursorType::Devices),
+            "readings" => Some(CursorType::Readings),
+            "ingest_time" => Some(CursorType::IngestTime),
+            "global_ingest_time" => Some(CursorType::GlobalIngestTime),
+            "api_keys" => Some(CursorType::ApiKeys),
+            "events" => Some(CursorType::Events),
+            "insights" => Some(CursorType::Insights),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CursorType::Devices => "devices",
+            CursorType::Readings => "readings",
+            CursorType::IngestTime => "ingest_time",
+            CursorType::GlobalIngestTime => "global_ingest_time",
+            CursorType::ApiKeys => "api_keys",
+            CursorType::Events => "events",
+            CursorType::Insights => "insights",
+        }
+    }
+}
+
+/// Decoded contents of a pagination cursor
+///
+/// Only the fields carried by the given cursor type are populated. `version`
+/// is the payload version the token was written with. `issued_at` is always
+/// present and is null for cursors that do not carry it.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct CursorPreviewResponse {
+    #[serde(rename = "type")]
+    pub cursor_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts_batch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gsi1sk: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_time_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shards: Option<Vec<ShardPreview>>,
+    pub version: Option<u8>,
+    pub issued_at: Option<String>,
+}
+
+impl CursorPreviewResponse {
+    fn empty(cursor_type: CursorType) -> Self {
+        Self {
+            cursor_type: cursor_type.as_str(),
+            hardware_id: None,
+            ts_batch: None,
+            gsi1sk: None,
+            key_id: None,
+            start_time_ms: None,
+            ingest_time_ms: None,
+            timestamp_ms: None,
+            shards: None,
+            version: None,
+            issued_at: None,
+        }
+    }
+}
+
+/// Where a fleet-wide ingest-time cursor stands in one index shard
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ShardPreview {
+    /// `start`, `after` or `done`
+    pub state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts_batch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_time_ms: Option<i64>,
+}
+
+impl From<GlobalIngestShardPosition> for ShardPreview {
+    fn from(position: GlobalIngestShardPosition) -> Self {
+        let state = |state| ShardPreview {
+            state,
+            hardware_id: None,
+            ts_batch: None,
+            ingest_time_ms: None,
+        };
+
+        match position {
+            GlobalIngestShardPosition::Start => state("start"),
+            GlobalIngestShardPosition::After(after) => ShardPreview {
+                hardware_id: Some(after.hardware_id),
+                ts_batch: Some(after.ts_batch),
+                ingest_time_ms: Some(after.ingest_time_ms),
+                ..state("after")
+            },
+            GlobalIngestShardPosition::Done => state("done"),
+        }
+    }
+}
+
+/// Handler for GET /debug/cursor endpoint
+///
+/// Decodes a pagination cursor with the typed decoder for its endpoint so
+/// support can verify what a client is sending. Only served when
+/// `DEBUG_ENDPOINTS_ENABLED` is set; otherwise the route does not exist.
+///
+/// # Query Parameters
+/// * `token` - The opaque cursor to decode
+/// * `type` - Cursor kind: `devices`, `readings`, `ingest_time`,
+///   `global_ingest_time`, `api_keys`, `events`, or `insights`
+///
+/// # Returns
+/// * HTTP 200 with the decoded cursor fields
+/// * HTTP 400 if `token` or `type` is missing/invalid, or the token does not decode
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if debug endpoints are disabled
+pub async fn preview_cursor(
+    event: Request,
+    config: &ControlConfig,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    if !config.debug_endpoints_enabled {
+        warn!(request_id = %request_id, "Debug endpoint requested while disabled");
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::ResourceNotFound,
+        ));
+    }
+
+    info!(request_id = %request_id, "Processing cursor preview request");
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let query_params = event.query_string_parameters();
+
+    let token = query_params
+        .first("token")
+        .ok_or_else(|| crate::error::ValidationError::MissingField(String::from("token")))?;
+
+    let cursor_type = parse_cursor_type(query_params.first("type"))?;
+
+    let preview = decode_cursor_preview(token, cursor_type)?;
+
+    let response_body = serde_json::to_string(&preview).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Parse the required `type` query parameter into a CursorType
+fn parse_cursor_type(value: Option<&str>) -> Result<CursorType, ApiError> {
+    let value =
+        value.ok_or_else(|| crate::error::ValidationError::MissingField(String::from("type")))?;
+
+    CursorType::parse(value)
+        .ok_or_else(|| crate::error::ValidationError::InvalidValue(String::from("type")).into())
+}
+
+/// Decode a cursor with the typed decoder for `cursor_type`
+pub fn decode_cursor_preview(
+    token: &str,
+    cursor_type: CursorType,
+) -> Result<CursorPreviewResponse, ApiError> {
+    let invalid = |e: esp32_backend::shared::cursor::CursorError| -> ApiError {
+        crate::error::ValidationError::InvalidCursor(format!(
+            "not a {} cursor ({})",
+            cursor_type.as_str(),
+            e
+        ))
+        .into()
+    };
+
+    let mut preview = CursorPreviewResponse::empty(cursor_type);
+
+    match cursor_type {
+        CursorType::Devices => {
+            let decoded = decode_device_page_token(token).map_err(invalid)?;
+            preview.hardware_id = Some(decoded.hardware_id);
+            preview.gsi1sk = Some(decoded.gsi1sk);
+        }
+        CursorType::Readings => {
+            let decoded = decode_readings_page_token(token).map_err(invalid)?;
+            preview.hardware_id = Some(decoded.hardware_id);
+            preview.ts_batch = Some(decoded.ts_batch);
+        }
+        CursorType::IngestTime => {
+            let decoded = decode_ingest_time_page_token(token).map_err(invalid)?;
+            preview.hardware_id = Some(decoded.hardware_id);
+            preview.ts_batch = Some(decoded.ts_batch);
+            preview.ingest_time_ms = Some(decoded.ingest_time_ms);
+        }
+        CursorType::GlobalIngestTime => {
+            let decoded = decode_global_ingest_time_page_token(token).map_err(invalid)?;
+            preview.shards = Some(decoded.shards.into_iter().map(Into::into).collect());
+        }
+        CursorType::ApiKeys => {
+            let decoded = decode_api_key_page_token(token).map_err(invalid)?;
+            preview.key_id = Some(decoded.key_id);
+            preview.gsi1sk = Some(decoded.gsi1sk);
+        }
+        CursorType::Events => {
+            let decoded = decode_events_page_token(token).map_err(invalid)?;
+            preview.hardware_id = Some(decoded.hardware_id);
+            preview.start_time_ms = Some(decoded.start_time_ms);
+        }
+        CursorType::Insights => {
+            let decoded = decode_insights_page_token(token).map_err(invalid)?;
+            preview.hardware_id = Some(decoded.hardware_id);
+            preview.timestamp_ms = Some(decoded.timestamp_ms);
+        }
+    }
+
+    // The token decoded above, so its version byte is readable
+    preview.version = Some(page_token_version(token).map_err(invalid)?);
+
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::cursor::{
+        encode_api_key_page_token, encode_device_page_token, encode_events_page_token,
+        encode_global_ingest_time_page_token, encode_ingest_time_page_token,
+        encode_insights_page_token, encode_readings_page_token, IngestTimePageToken,
+        CURSOR_VERSION,
+    };
+
+    #[test]
+    fn test_cursor_type_parse() {
+        assert_eq!(CursorType::parse("readings"), Some(CursorType::Readings));
+        assert_eq!(CursorType::parse("devices"), Some(CursorType::Devices));
+        assert_eq!(CursorType::parse("api_keys"), Some(CursorType::ApiKeys));
+        assert_eq!(CursorType::parse("events"), Some(CursorType::Events));
+        assert_eq!(
+            CursorType::parse("ingest_time"),
+            Some(CursorType::IngestTime)
+        );
+        assert_eq!(
+            CursorType::parse("global_ingest_time"),
+            Some(CursorType::GlobalIngestTime)
+        );
+        assert_eq!(CursorType::parse("insights"), Some(CursorType::Insights));
+        assert_eq!(CursorType::parse("Readings"), None);
+
+        assert!(matches!(
+            parse_cursor_type(None),
+            Err(ApiError::Validation(crate::error::ValidationError::MissingField(ref f))) if f == "type"
+        ));
+        assert!(matches!(
+            parse_cursor_type(Some("batches")),
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidValue(ref f))) if f == "type"
+        ));
+    }
+
+    #[test]
+    fn test_decode_readings_cursor() {
+        let token =
+            encode_readings_page_token("AA:BB:CC:DD:EE:FF", "1704067800000#batch_1").unwrap();
+        let preview = decode_cursor_preview(&token, CursorType::Readings).unwrap();
+
+        assert_eq!(preview.cursor_type, "readings");
+        assert_eq!(preview.hardware_id.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+        assert_eq!(preview.ts_batch.as_deref(), Some("1704067800000#batch_1"));
+        assert_eq!(preview.version, Some(CURSOR_VERSION));
+        assert_eq!(preview.issued_at, None);
+
+        let json = serde_json::to_string(&preview).unwrap();
+        assert!(json.contains("\"type\":\"readings\""));
+        assert!(json.contains("\"version\":2"));
+        assert!(json.contains("\"issued_at\":null"));
+        assert!(!json.contains("gsi1sk"));
+    }
+
+    #[test]
+    fn test_decode_devices_cursor() {
+        let token = encode_device_page_token("AA:BB:CC:DD:EE:FF", "2024-01-15T14:22:00Z").unwrap();
+        let preview = decode_cursor_preview(&token, CursorType::Devices).unwrap();
+
+        assert_eq!(preview.hardware_id.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+        assert_eq!(preview.gsi1sk.as_deref(), Some("2024-01-15T14:22:00Z"));
+        assert_eq!(preview.ts_batch, None);
+    }
+
+    #[test]
+    fn test_decode_api_keys_cursor() {
+        let token = encode_api_key_page_token(
+            "a1b2c3d4-e5f6-7890-abcd-ef1234567890",
+            "2024-01-15T10:30:00Z",
+        )
+        .unwrap();
+        let preview = decode_cursor_preview(&token, CursorType::ApiKeys).unwrap();
+
+        assert_eq!(
+            preview.key_id.as_deref(),
+            Some("a1b2c3d4-e5f6-7890-abcd-ef1234567890")
+        );
+        assert_eq!(preview.gsi1sk.as_deref(), Some("2024-01-15T10:30:00Z"));
+        assert_eq!(preview.hardware_id, None);
+    }
+
+    #[test]
+    fn test_decode_events_cursor() {
+        let token = encode_events_page_token("AA:BB:CC:DD:EE:FF", 1704067800000).unwrap();
+        let preview = decode_cursor_preview(&token, CursorType::Events).unwrap();
+
+        assert_eq!(preview.hardware_id.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+        assert_eq!(preview.start_time_ms, Some(1704067800000));
+    }
+
+    #[test]
+    fn test_decode_legacy_cursor_reports_version_1() {
+        // Issued before the version byte existed: bare JSON
+        use base64::{engine::general_purpose, Engine as _};
+        let token = general_purpose::STANDARD
+            .encode(br#"{"hardware_id":"AA:BB:CC:DD:EE:FF","ts_batch":"1704067800000#b"}"#);
+        let preview = decode_cursor_preview(&token, CursorType::Readings).unwrap();
+
+        assert_eq!(preview.version, Some(1));
+    }
+
+    #[test]
+    fn test_decode_ingest_time_cursor() {
+        let token =
+            encode_ingest_time_page_token("AA:BB:CC:DD:EE:FF", "1704060000000#b", 1704067805000)
+                .unwrap();
+        let preview = decode_cursor_preview(&token, CursorType::IngestTime).unwrap();
+
+        assert_eq!(preview.cursor_type, "ingest_time");
+        assert_eq!(preview.hardware_id.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+        assert_eq!(preview.ts_batch.as_deref(), Some("1704060000000#b"));
+        assert_eq!(preview.ingest_time_ms, Some(1704067805000));
+        assert_eq!(preview.version, Some(CURSOR_VERSION));
+    }
+
+    #[test]
+    fn test_decode_global_ingest_time_cursor() {
+        let token = encode_global_ingest_time_page_token(&[
+            GlobalIngestShardPosition::Start,
+            GlobalIngestShardPosition::After(IngestTimePageToken {
+                hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+                ts_batch: "1704060000000#b".to_string(),
+                ingest_time_ms: 1704067805000,
+            }),
+            GlobalIngestShardPosition::Done,
+        ])
+        .unwrap();
+        let preview = decode_cursor_preview(&token, CursorType::GlobalIngestTime).unwrap();
+
+        let shards = preview.shards.unwrap();
+        assert_eq!(
+            shards.iter().map(|s| s.state).collect::<Vec<_>>(),
+            vec!["start", "after", "done"]
+        );
+        assert_eq!(shards[1].hardware_id.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+        assert_eq!(shards[1].ingest_time_ms, Some(1704067805000));
+        assert_eq!(shards[0].hardware_id, None);
+        assert_eq!(preview.hardware_id, None);
+        assert_eq!(preview.version, Some(CURSOR_VERSION));
+    }
+
+    #[test]
+    fn test_decode_insights_cursor() {
+        let token = encode_insights_page_token("AA:BB:CC:DD:EE:FF", 1704067800000).unwrap();
+        let preview = decode_cursor_preview(&token, CursorType::Insights).unwrap();
+
+        assert_eq!(preview.cursor_type, "insights");
+        assert_eq!(preview.hardware_id.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+        assert_eq!(preview.timestamp_ms, Some(1704067800000));
+        assert_eq!(preview.start_time_ms, None);
+        assert_eq!(preview.version, Some(CURSOR_VERSION));
+
+        // Same fields as an events cursor, but a different endpoint
+        assert!(decode_cursor_preview(&token, CursorType::Events).is_err());
+    }
+
+    #[test]
+    fn test_malformed_cursor_returns_400() {
+        let err = decode_cursor_preview("not-valid-base64!@#", CursorType::Readings).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Validation(crate::error::ValidationError::InvalidCursor(_))
+        ));
+
+        let response = err.to_http_response("test-request-id");
+        assert_eq!(response.status(), 400);
+
+        let body = match response.body() {
+            Body::Text(text) => text.clone(),
+            _ => panic!("Expected text body"),
+        };
+        assert!(body.contains("Invalid cursor format: not a readings cursor"));
+    }
+
+    #[test]
+    fn test_cursor_of_wrong_type_returns_400() {
+        // A devices cursor lacks the ts_batch field a readings cursor requires
+        let token = encode_device_page_token("AA:BB:CC:DD:EE:FF", "2024-01-15T14:22:00Z").unwrap();
+        let err = decode_cursor_preview(&token, CursorType::Readings).unwrap_err();
+
+        assert_eq!(err.to_http_response("test-request-id").status(), 400);
+    }
+}
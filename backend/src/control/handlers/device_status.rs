@@ -0,0 +1,210 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use tracing::{error, info};
+
+use super::fleet_health::DeviceHealthInput;
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use crate::repo::device_status::DeviceStatusFields;
+use esp32_backend::shared::plant_insights::HealthCategory;
+use esp32_backend::shared::time::{Clock, SystemClock};
+
+/// Response payload for GET /devices/{hardware_id}/status
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DeviceStatusResponse {
+    pub hardware_id: String,
+    /// Health category as of now
+    pub health_category: HealthCategory,
+    pub last_seen_event_time_ms: Option<i64>,
+    pub last_seen_ingest_time_ms: Option<i64>,
+    /// Ingest time minus event time of the latest reading (negative when the device clock is ahead)
+    pub ingest_event_skew_seconds: Option<i64>,
+    /// True when the latest skew exceeded the status updater's SKEW_WARN_THRESHOLD_SEC
+    pub clock_skew_warning: bool,
+    pub pipeline_lag_seconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error_at_ms: Option<i64>,
+    pub updated_at_ms: Option<i64>,
+}
+
+impl DeviceStatusResponse {
+    /// Build the response from the device's status item, if one exists
+    ///
+    /// Without a status item the health category is judged by the registry
+    /// `last_seen_at`, as in GET /fleet/health, and every status field is empty.
+    pub fn new(
+        hardware_id: &str,
+        last_seen_at: String,
+        status: Option<DeviceStatusFields>,
+        now_ms: i64,
+    ) -> Self {
+        let health_category = DeviceHealthInput {
+            last_seen_at,
+            status: status.as_ref().map(|s| s.health.clone()),
+        }
+        .health_category(now_ms);
+
+        let status = status.as_ref();
+        DeviceStatusResponse {
+            hardware_id: hardware_id.to_string(),
+            health_category,
+            last_seen_event_time_ms: status.and_then(|s| s.last_seen_event_time_ms),
+            last_seen_ingest_time_ms: status.and_then(|s| s.health.last_seen_ingest_time_ms),
+            ingest_event_skew_seconds: status.and_then(|s| s.ingest_event_skew_seconds),
+            clock_skew_warning: status.is_some_and(|s| s.clock_skew_warning),
+            pipeline_lag_seconds: status.and_then(|s| s.pipeline_lag_seconds),
+            last_error_at_ms: status.and_then(|s| s.health.last_error_at_ms),
+            updated_at_ms: status.and_then(|s| s.updated_at_ms),
+        }
+    }
+}
+
+/// Handler for GET /devices/{hardware_id}/status endpoint
+///
+/// Returns the device's current health category along with the clock and
+/// pipeline fields kept by the status updater, so dashboards can flag devices
+/// whose clocks drift (`clock_skew_warning`).
+///
+/// # Returns
+/// * HTTP 200 with the device status
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn get_device_status(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing device status request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    // First, check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    let Some(device) = device else {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    };
+
+    let status = crate::repo::device_status::get_device_status(
+        &config.dynamodb_client,
+        &config.device_status_table,
+        hardware_id,
+    )
+    .await?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        has_status = status.is_some(),
+        "Retrieved device status"
+    );
+
+    let response = DeviceStatusResponse::new(
+        hardware_id,
+        device.last_seen_at,
+        status,
+        SystemClock::new().now_epoch_ms(),
+    );
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::device_status::DeviceHealthFields;
+
+    /// 2024-01-01T12:00:00Z
+    const NOW_MS: i64 = 1704110400000;
+
+    fn status(skew_seconds: i64, clock_skew_warning: bool) -> DeviceStatusFields {
+        DeviceStatusFields {
+            health: DeviceHealthFields {
+                last_seen_ingest_time_ms: Some(NOW_MS - 60_000),
+                last_error_at_ms: None,
+            },
+            last_seen_event_time_ms: Some(NOW_MS - 60_000 - skew_seconds * 1000),
+            ingest_event_skew_seconds: Some(skew_seconds),
+            clock_skew_warning,
+            pipeline_lag_seconds: Some(skew_seconds + 60),
+            updated_at_ms: Some(NOW_MS - 59_000),
+        }
+    }
+
+    #[test]
+    fn test_device_status_response_surfaces_clock_skew_warning() {
+        let response = DeviceStatusResponse::new(
+            "AA:BB:CC:DD:EE:FF",
+            "2024-01-01T11:59:00Z".to_string(),
+            Some(status(-900, true)),
+            NOW_MS,
+        );
+
+        assert_eq!(response.health_category, HealthCategory::Healthy);
+        assert_eq!(response.ingest_event_skew_seconds, Some(-900));
+        assert!(response.clock_skew_warning);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["clock_skew_warning"], true);
+        assert_eq!(json["ingest_event_skew_seconds"], -900);
+        assert_eq!(json["health_category"], "healthy");
+        assert!(json.get("last_error_at_ms").is_none());
+    }
+
+    #[test]
+    fn test_device_status_response_no_warning() {
+        let response = DeviceStatusResponse::new(
+            "AA:BB:CC:DD:EE:FF",
+            "2024-01-01T11:59:00Z".to_string(),
+            Some(status(30, false)),
+            NOW_MS,
+        );
+
+        assert!(!response.clock_skew_warning);
+        assert_eq!(response.pipeline_lag_seconds, Some(90));
+    }
+
+    #[test]
+    fn test_device_status_response_without_status_item() {
+        let response = DeviceStatusResponse::new(
+            "AA:BB:CC:DD:EE:FF",
+            "2024-01-01T08:00:00Z".to_string(),
+            None,
+            NOW_MS,
+        );
+
+        assert_eq!(response.health_category, HealthCategory::Stale);
+        assert!(!response.clock_skew_warning);
+        assert_eq!(response.ingest_event_skew_seconds, None);
+        assert_eq!(response.last_seen_ingest_time_ms, None);
+    }
+}
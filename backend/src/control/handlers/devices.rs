@@ -5,6 +5,12 @@ use tracing::{error, info};
 use crate::auth::validate_bearer_token;
 use crate::config::ControlConfig;
 use crate::error::ApiError;
+use crate::handlers::{PageMetadata, PageMetadataCamel};
+use crate::repo::devices::DeviceListFilter;
+use crate::response::{CamelCaseMirror, KeyCase};
+use esp32_backend::shared::query_params::{
+    parse_clamped_limit, parse_optional, parse_optional_string,
+};
 
 /// Response item for device listing
 #[derive(Debug, Serialize)]
@@ -21,6 +27,45 @@ pub struct DeviceListItem {
     pub first_registered_at: String,
     /// RFC3339 timestamp when last seen
     pub last_seen_at: String,
+    /// Epoch ms of the oldest stored reading
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_reading_ms: Option<i64>,
+    /// Epoch ms of the newest stored reading
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_reading_ms: Option<i64>,
+}
+
+/// `DeviceListItem` with camelCase field names, for `?case=camel`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceListItemCamel<'a> {
+    pub hardware_id: &'a str,
+    pub confirmation_id: &'a str,
+    pub friendly_name: Option<&'a str>,
+    pub firmware_version: &'a str,
+    pub first_registered_at: &'a str,
+    pub last_seen_at: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_reading_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_reading_ms: Option<i64>,
+}
+
+impl CamelCaseMirror for DeviceListItem {
+    type Camel<'a> = DeviceListItemCamel<'a>;
+
+    fn camel(&self) -> DeviceListItemCamel<'_> {
+        DeviceListItemCamel {
+            hardware_id: &self.hardware_id,
+            confirmation_id: &self.confirmation_id,
+            friendly_name: self.friendly_name.as_deref(),
+            firmware_version: &self.firmware_version,
+            first_registered_at: &self.first_registered_at,
+            last_seen_at: &self.last_seen_at,
+            first_reading_ms: self.first_reading_ms,
+            last_reading_ms: self.last_reading_ms,
+        }
+    }
 }
 
 /// Response payload for device listing
@@ -28,10 +73,76 @@ pub struct DeviceListItem {
 pub struct ListDevicesResponse {
     /// List of devices
     pub devices: Vec<DeviceListItem>,
+    /// Page size, item count, and whether more pages follow
+    pub page: PageMetadata,
     /// Optional cursor for pagination
     pub next_cursor: Option<String>,
 }
 
+/// `ListDevicesResponse` with camelCase field names, for `?case=camel`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDevicesResponseCamel<'a> {
+    pub devices: Vec<DeviceListItemCamel<'a>>,
+    pub page: PageMetadataCamel,
+    pub next_cursor: Option<&'a str>,
+}
+
+impl CamelCaseMirror for ListDevicesResponse {
+    type Camel<'a> = ListDevicesResponseCamel<'a>;
+
+    fn camel(&self) -> ListDevicesResponseCamel<'_> {
+        ListDevicesResponseCamel {
+            devices: self.devices.iter().map(DeviceListItem::camel).collect(),
+            page: self.page.camel(),
+            next_cursor: self.next_cursor.as_deref(),
+        }
+    }
+}
+
+/// Device detail with camelCase field names, for `?case=camel`
+///
+/// `capabilities` is passed through as is: its field names are single words
+/// and its feature names are data.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceCamel<'a> {
+    pub hardware_id: &'a str,
+    pub confirmation_id: &'a str,
+    pub friendly_name: Option<&'a str>,
+    pub firmware_version: &'a str,
+    pub capabilities: &'a esp32_backend::shared::domain::Capabilities,
+    pub first_registered_at: &'a str,
+    pub last_seen_at: &'a str,
+    pub last_boot_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_reading_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_reading_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readings_this_boot: Option<i64>,
+}
+
+impl CamelCaseMirror for esp32_backend::shared::domain::Device {
+    type Camel<'a> = DeviceCamel<'a>;
+
+    fn camel(&self) -> DeviceCamel<'_> {
+        DeviceCamel {
+            hardware_id: &self.hardware_id,
+            confirmation_id: &self.confirmation_id,
+            friendly_name: self.friendly_name.as_deref(),
+            firmware_version: &self.firmware_version,
+            capabilities: &self.capabilities,
+            first_registered_at: &self.first_registered_at,
+            last_seen_at: &self.last_seen_at,
+            last_boot_id: &self.last_boot_id,
+            first_reading_ms: self.first_reading_ms,
+            last_reading_ms: self.last_reading_ms,
+            readings_this_boot: self.readings_this_boot,
+        }
+    }
+}
+
 /// Handler for GET /devices endpoint
 ///
 /// Lists all registered devices with pagination, sorted by last_seen_at descending.
@@ -39,6 +150,11 @@ pub struct ListDevicesResponse {
 /// # Query Parameters
 /// * `limit` - Maximum number of devices to return (default 50, max 100)
 /// * `cursor` - Optional pagination cursor from previous response
+/// * `firmware_version` - Optional exact firmware version, or a prefix ending in `*`
+/// * `min_last_seen` - Optional RFC3339 timestamp; only devices seen at or after it
+///
+/// `firmware_version` is applied after `limit`, so a page may hold fewer
+/// devices than `limit` while `next_cursor` is still set.
 ///
 /// # Returns
 /// * HTTP 200 with device list and optional next_cursor
@@ -61,19 +177,27 @@ pub async fn list_devices(
     // Parse query parameters
     let query_params = event.query_string_parameters();
 
-    let limit: i32 = query_params
-        .first("limit")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(50); // Default limit
+    let limit = parse_clamped_limit(
+        &query_params,
+        config.devices_page_default,
+        config.devices_page_max,
+    );
 
-    let limit = limit.min(100).max(1); // Clamp between 1 and 100
+    let cursor = parse_optional_string(&query_params, "cursor");
 
-    let cursor = query_params.first("cursor").map(|s| s.to_string());
+    let case = crate::response::parse_key_case(query_params.first(crate::response::CASE_PARAM))?;
+
+    let filter = parse_device_list_filter(
+        query_params.first("firmware_version"),
+        query_params.first("min_last_seen"),
+    )?;
 
     info!(
         request_id = %request_id,
         limit = limit,
         has_cursor = cursor.is_some(),
+        firmware_version = ?filter.firmware_version,
+        min_last_seen = ?filter.min_last_seen,
         "Parsed query parameters"
     );
 
@@ -83,6 +207,7 @@ pub async fn list_devices(
         &config.devices_table,
         Some(limit),
         cursor,
+        &filter,
     )
     .await?;
 
@@ -104,16 +229,19 @@ pub async fn list_devices(
             firmware_version: device.firmware_version,
             first_registered_at: device.first_registered_at,
             last_seen_at: device.last_seen_at,
+            first_reading_ms: device.first_reading_ms,
+            last_reading_ms: device.last_reading_ms,
         })
         .collect();
 
     // Build response
     let response = ListDevicesResponse {
+        page: PageMetadata::new(limit, device_items.len(), result.page_token.as_ref()),
         devices: device_items,
         next_cursor: result.page_token,
     };
 
-    let response_body = serde_json::to_string(&response).map_err(|e| {
+    let response_body = crate::response::to_json_string(&response, case).map_err(|e| {
         error!(request_id = %request_id, error = %e, "Failed to serialize response");
         ApiError::Internal(format!("Failed to serialize response: {}", e))
     })?;
@@ -130,9 +258,45 @@ pub async fn list_devices(
         .unwrap())
 }
 
+/// Parse the optional device list filters
+///
+/// `min_last_seen` is normalized to UTC in the same RFC3339 form as the stored
+/// `last_seen_at`, so the sort-key comparison is lexicographically correct.
+fn parse_device_list_filter(
+    firmware_version: Option<&str>,
+    min_last_seen: Option<&str>,
+) -> Result<DeviceListFilter, ApiError> {
+    let firmware_version = match firmware_version {
+        Some(v) if v.trim_end_matches('*').is_empty() => {
+            return Err(crate::error::ValidationError::InvalidValue(String::from(
+                "firmware_version",
+            ))
+            .into());
+        }
+        Some(v) => Some(v.to_string()),
+        None => None,
+    };
+
+    let min_last_seen = min_last_seen
+        .map(|v| {
+            chrono::DateTime::parse_from_rfc3339(v)
+                .map(|dt| dt.with_timezone(&chrono::Utc).to_rfc3339())
+                .map_err(|_| {
+                    crate::error::ValidationError::InvalidFormat(String::from("min_last_seen"))
+                })
+        })
+        .transpose()?;
+
+    Ok(DeviceListFilter {
+        firmware_version,
+        min_last_seen,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use esp32_backend::shared::validators::{DEFAULT_PAGE_LIMIT, MAX_DEVICES_PAGE_LIMIT};
     use lambda_http::http::Method;
     use lambda_http::Context;
 
@@ -159,6 +323,8 @@ mod tests {
             firmware_version: String::from("1.0.16"),
             first_registered_at: String::from("2024-01-15T10:30:00Z"),
             last_seen_at: String::from("2024-01-15T14:22:00Z"),
+            first_reading_ms: None,
+            last_reading_ms: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -183,6 +349,8 @@ mod tests {
             firmware_version: String::from("1.0.16"),
             first_registered_at: String::from("2024-01-15T10:30:00Z"),
             last_seen_at: String::from("2024-01-15T14:22:00Z"),
+            first_reading_ms: None,
+            last_reading_ms: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -201,6 +369,8 @@ mod tests {
                     firmware_version: String::from("1.0.16"),
                     first_registered_at: String::from("2024-01-15T10:30:00Z"),
                     last_seen_at: String::from("2024-01-15T14:22:00Z"),
+                    first_reading_ms: None,
+                    last_reading_ms: None,
                 },
                 DeviceListItem {
                     hardware_id: String::from("11:22:33:44:55:66"),
@@ -209,8 +379,11 @@ mod tests {
                     firmware_version: String::from("1.0.15"),
                     first_registered_at: String::from("2024-01-14T10:30:00Z"),
                     last_seen_at: String::from("2024-01-14T14:22:00Z"),
+                    first_reading_ms: None,
+                    last_reading_ms: None,
                 },
             ],
+            page: PageMetadata::new(2, 2, Some(&String::from("base64cursor"))),
             next_cursor: Some(String::from("base64cursor")),
         };
 
@@ -220,18 +393,31 @@ mod tests {
         assert!(json.contains("11:22:33:44:55:66"));
         assert!(json.contains("next_cursor"));
         assert!(json.contains("base64cursor"));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["page"],
+            serde_json::json!({"limit": 2, "returned": 2, "has_more": true})
+        );
     }
 
     #[tokio::test]
     async fn test_list_devices_response_serialization_no_cursor() {
         let response = ListDevicesResponse {
             devices: vec![],
+            page: PageMetadata::new(50, 0, None),
             next_cursor: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("devices"));
         assert!(json.contains("next_cursor"));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["page"],
+            serde_json::json!({"limit": 50, "returned": 0, "has_more": false})
+        );
     }
 
     #[tokio::test]
@@ -295,17 +481,46 @@ mod tests {
         // Clean up
         std::env::remove_var("ADMIN_TOKEN");
     }
+
+    #[test]
+    fn test_parse_device_list_filter() {
+        let filter = parse_device_list_filter(None, None).unwrap();
+        assert_eq!(filter, DeviceListFilter::default());
+
+        let filter =
+            parse_device_list_filter(Some("1.0.*"), Some("2024-01-15T12:00:00+02:00")).unwrap();
+        assert_eq!(filter.firmware_version.as_deref(), Some("1.0.*"));
+        assert_eq!(
+            filter.min_last_seen.as_deref(),
+            Some("2024-01-15T10:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_device_list_filter_invalid() {
+        assert!(parse_device_list_filter(None, Some("yesterday")).is_err());
+        assert!(parse_device_list_filter(Some("*"), None).is_err());
+        assert!(parse_device_list_filter(Some(""), None).is_err());
+    }
 }
 
 /// Handler for GET /devices/{hardware_id} endpoint
 ///
 /// Retrieves complete device record including capabilities.
 ///
+/// Responses carry a weak `ETag`; a request whose `If-None-Match` matches the
+/// current device state gets HTTP 304 with an empty body.
+///
 /// # Path Parameters
 /// * `hardware_id` - MAC address of the device
 ///
+/// # Query Parameters
+/// * `summary` - When `true`, return only the device list fields (no
+///   capabilities, no ETag) read through a projection
+///
 /// # Returns
-/// * HTTP 200 with complete device record
+/// * HTTP 200 with complete device record, or its summary
+/// * HTTP 304 if `If-None-Match` matches the current ETag
 /// * HTTP 401 if Bearer token is invalid
 /// * HTTP 404 if device not found
 pub async fn get_device_detail(
@@ -324,12 +539,21 @@ pub async fn get_device_detail(
     // Validate Bearer token
     validate_bearer_token(&event)?;
 
+    let query_params = event.query_string_parameters();
+    let summary = parse_optional::<bool>(&query_params, "summary")?.unwrap_or(false);
+    let case = crate::response::parse_key_case(query_params.first(crate::response::CASE_PARAM))?;
+
     info!(
         request_id = %request_id,
         hardware_id = %hardware_id,
+        summary = summary,
         "Querying device from DynamoDB"
     );
 
+    if summary {
+        return get_device_summary_response(config, hardware_id, case, &request_id).await;
+    }
+
     // Query device by partition key
     let device = crate::repo::devices::get_device(
         &config.dynamodb_client,
@@ -354,8 +578,12 @@ pub async fn get_device_detail(
         "Retrieved device from DynamoDB"
     );
 
-    // Serialize complete device record (including capabilities)
-    let response_body = serde_json::to_string(&device).map_err(|e| {
+    let if_none_match = event
+        .headers()
+        .get("if-none-match")
+        .and_then(|v| v.to_str().ok());
+
+    let response = device_detail_response(&device, if_none_match, case).map_err(|e| {
         error!(request_id = %request_id, error = %e, "Failed to serialize device");
         ApiError::Internal(format!("Failed to serialize device: {}", e))
     })?;
@@ -363,7 +591,44 @@ pub async fn get_device_detail(
     info!(
         request_id = %request_id,
         hardware_id = %hardware_id,
-        "Returning successful device detail response"
+        status = response.status().as_u16(),
+        "Returning device detail response"
+    );
+
+    Ok(response)
+}
+
+/// Fetch a device's lightweight fields and serialize them as a `DeviceListItem`
+async fn get_device_summary_response(
+    config: &ControlConfig,
+    hardware_id: &str,
+    case: KeyCase,
+    request_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let summary = crate::repo::devices::get_device_summary(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?
+    .ok_or_else(|| {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        ApiError::NotFound(crate::error::NotFoundError::DeviceNotFound)
+    })?;
+
+    let response_body = crate::response::to_json_string(&summary, case).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize device summary");
+        ApiError::Internal(format!("Failed to serialize device summary: {}", e))
+    })?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Returning device summary response"
     );
 
     Ok(Response::builder()
@@ -373,6 +638,77 @@ pub async fn get_device_detail(
         .unwrap())
 }
 
+/// Compute the ETag for a device detail response
+///
+/// `friendly_name`, `last_reading_ms` and `readings_this_boot` are included
+/// because renaming a device or ingesting readings does not touch
+/// `last_seen_at`.
+pub fn device_etag(device: &esp32_backend::shared::domain::Device) -> String {
+    let last_reading_ms = device
+        .last_reading_ms
+        .map(|ms| ms.to_string())
+        .unwrap_or_default();
+    let readings_this_boot = device
+        .readings_this_boot
+        .map(|count| count.to_string())
+        .unwrap_or_default();
+
+    esp32_backend::shared::etag::weak_etag(&[
+        &device.last_seen_at,
+        &device.firmware_version,
+        device.friendly_name.as_deref().unwrap_or(""),
+        &last_reading_ms,
+        &readings_this_boot,
+    ])
+}
+
+/// Build the device detail response, or a 304 if `If-None-Match` matches
+fn device_detail_response(
+    device: &esp32_backend::shared::domain::Device,
+    if_none_match: Option<&str>,
+    case: KeyCase,
+) -> Result<Response<Body>, serde_json::Error> {
+    let etag = device_etag(device);
+
+    if if_none_match
+        .is_some_and(|value| esp32_backend::shared::etag::if_none_match_matches(value, &etag))
+    {
+        return Ok(Response::builder()
+            .status(304)
+            .header("etag", etag)
+            .body(Body::Empty)
+            .unwrap());
+    }
+
+    // Serialize complete device record (including capabilities)
+    let response_body = crate::response::to_json_string(device, case)?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .header("etag", etag)
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Check an optional `If-Match` header against the device's current ETag
+///
+/// A missing header always passes, so clients that don't send one keep the
+/// last-write-wins behaviour.
+fn check_if_match(
+    if_match: Option<&str>,
+    device: &esp32_backend::shared::domain::Device,
+) -> Result<(), ApiError> {
+    match if_match {
+        Some(value)
+            if !esp32_backend::shared::etag::if_match_matches(value, &device_etag(device)) =>
+        {
+            Err(ApiError::PreconditionFailed)
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Request payload for updating device friendly_name
 #[derive(Debug, serde::Deserialize)]
 pub struct UpdateFriendlyNameRequest {
@@ -400,6 +736,10 @@ pub struct UpdateFriendlyNameResponse {
 /// # Path Parameters
 /// * `hardware_id` - MAC address of the device
 ///
+/// # Headers
+/// * `If-Match` - Optional ETag from `GET /devices/{hardware_id}`; the update
+///   only applies if the device has not changed since
+///
 /// # Request Body
 /// * `friendly_name` - New friendly name (or null to remove)
 ///
@@ -408,6 +748,7 @@ pub struct UpdateFriendlyNameResponse {
 /// * HTTP 400 if friendly_name validation fails
 /// * HTTP 401 if Bearer token is invalid
 /// * HTTP 404 if device not found
+/// * HTTP 412 if `If-Match` does not match the device's current ETag
 pub async fn update_device_friendly_name(
     event: Request,
     config: &ControlConfig,
@@ -472,7 +813,7 @@ pub async fn update_device_friendly_name(
     )
     .await?;
 
-    if device.is_none() {
+    let Some(device) = device else {
         info!(
             request_id = %request_id,
             hardware_id = %hardware_id,
@@ -481,22 +822,48 @@ pub async fn update_device_friendly_name(
         return Err(ApiError::NotFound(
             crate::error::NotFoundError::DeviceNotFound,
         ));
-    }
+    };
+
+    let if_match = event
+        .headers()
+        .get("if-match")
+        .and_then(|v| v.to_str().ok());
+
+    check_if_match(if_match, &device).inspect_err(|_| {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "If-Match does not match current device ETag"
+        );
+    })?;
 
     info!(
         request_id = %request_id,
         hardware_id = %hardware_id,
+        has_if_match = if_match.is_some(),
         "Updating friendly_name in DynamoDB"
     );
 
-    // Update friendly_name
+    // Update friendly_name, failing if the device changed since it was read
     crate::repo::devices::update_friendly_name(
         &config.dynamodb_client,
         &config.devices_table,
         hardware_id,
         request.friendly_name.as_deref(),
+        if_match.map(|_| &device),
     )
-    .await?;
+    .await
+    .map_err(|e| match e {
+        crate::error::DatabaseError::ConditionalCheckFailed => {
+            info!(
+                request_id = %request_id,
+                hardware_id = %hardware_id,
+                "Device changed before friendly_name update"
+            );
+            ApiError::PreconditionFailed
+        }
+        e => ApiError::Database(e),
+    })?;
 
     info!(
         request_id = %request_id,
@@ -620,6 +987,7 @@ mod device_detail_tests {
 mod integration_style_tests {
     use super::*;
     use esp32_backend::shared::domain::{Capabilities, Device};
+    use esp32_backend::shared::time::FixedClock;
     use lambda_http::http::Method;
     use lambda_http::Context;
     use std::collections::HashMap;
@@ -652,50 +1020,56 @@ mod integration_style_tests {
         features.insert("tft_display".to_string(), true);
         features.insert("offline_buffering".to_string(), true);
 
-        Device {
-            hardware_id: hardware_id.to_string(),
-            confirmation_id: confirmation_id.to_string(),
-            friendly_name: friendly_name.map(|s| s.to_string()),
-            firmware_version: "1.0.16".to_string(),
-            capabilities: Capabilities {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+
+        Device::builder()
+            .hardware_id(hardware_id)
+            .confirmation_id(confirmation_id)
+            .friendly_name(friendly_name.map(|s| s.to_string()))
+            .firmware_version("1.0.16")
+            .capabilities(Capabilities {
                 sensors: vec!["bme280".to_string(), "ds18b20".to_string()],
                 features,
-            },
-            first_registered_at: "2024-01-15T10:30:00Z".to_string(),
-            last_seen_at: last_seen_at.to_string(),
-            last_boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
-        }
+            })
+            .first_registered_at("2024-01-15T10:30:00Z")
+            .last_seen_at(last_seen_at)
+            .last_boot_id("7c9e6679-7425-40de-944b-e07fc1f90ae7")
+            .build(&clock)
+            .unwrap()
     }
 
     #[tokio::test]
     async fn test_list_devices_query_param_parsing() {
+        let parse = |raw: Option<&str>| {
+            let params: HashMap<String, String> = raw
+                .map(|limit| (String::from("limit"), limit.to_string()))
+                .into_iter()
+                .collect();
+            parse_clamped_limit(&params.into(), DEFAULT_PAGE_LIMIT, MAX_DEVICES_PAGE_LIMIT)
+        };
+
         // Test default limit logic
-        let limit: i32 = None.unwrap_or(50);
-        assert_eq!(limit, 50);
+        assert_eq!(parse(None), 50);
 
         // Test custom limit logic
-        let limit: i32 = Some(25).unwrap_or(50);
-        assert_eq!(limit, 25);
+        assert_eq!(parse(Some("25")), 25);
 
         // Test limit clamping (max 100)
-        let limit = 150_i32.min(100).max(1);
-        assert_eq!(limit, 100);
+        assert_eq!(parse(Some("150")), 100);
 
         // Test limit clamping (min 1)
-        let limit = 0_i32.min(100).max(1);
-        assert_eq!(limit, 1);
+        assert_eq!(parse(Some("0")), 1);
 
         // Test negative limit clamping
-        let limit = (-5_i32).min(100).max(1);
-        assert_eq!(limit, 1);
+        assert_eq!(parse(Some("-5")), 1);
     }
 
     #[tokio::test]
     async fn test_list_devices_cursor_parsing() {
-        use esp32_backend::shared::cursor::encode_device_cursor;
+        use esp32_backend::shared::cursor::encode_device_page_token;
 
         // Test cursor encoding
-        let cursor = encode_device_cursor("AA:BB:CC:DD:EE:FF", "2024-01-15T14:22:00Z").unwrap();
+        let cursor = encode_device_page_token("AA:BB:CC:DD:EE:FF", "2024-01-15T14:22:00Z").unwrap();
 
         // Verify cursor is not empty and is base64
         assert!(!cursor.is_empty());
@@ -704,8 +1078,8 @@ mod integration_style_tests {
             .all(|c| c.is_alphanumeric() || c == '+' || c == '/' || c == '='));
 
         // Test cursor decoding
-        use esp32_backend::shared::cursor::decode_device_cursor;
-        let decoded = decode_device_cursor(&cursor).unwrap();
+        use esp32_backend::shared::cursor::decode_device_page_token;
+        let decoded = decode_device_page_token(&cursor).unwrap();
         assert_eq!(decoded.hardware_id, "AA:BB:CC:DD:EE:FF");
         assert_eq!(decoded.gsi1sk, "2024-01-15T14:22:00Z");
     }
@@ -726,6 +1100,8 @@ mod integration_style_tests {
             firmware_version: device.firmware_version.clone(),
             first_registered_at: device.first_registered_at.clone(),
             last_seen_at: device.last_seen_at.clone(),
+            first_reading_ms: device.first_reading_ms,
+            last_reading_ms: device.last_reading_ms,
         };
 
         // Verify capabilities are excluded from list item
@@ -738,7 +1114,7 @@ mod integration_style_tests {
 
     #[tokio::test]
     async fn test_device_list_response_with_pagination() {
-        use esp32_backend::shared::cursor::encode_device_cursor;
+        use esp32_backend::shared::cursor::encode_device_page_token;
 
         let devices = vec![
             create_test_device(
@@ -764,12 +1140,15 @@ mod integration_style_tests {
                 firmware_version: device.firmware_version,
                 first_registered_at: device.first_registered_at,
                 last_seen_at: device.last_seen_at,
+                first_reading_ms: device.first_reading_ms,
+                last_reading_ms: device.last_reading_ms,
             })
             .collect();
 
-        let cursor = encode_device_cursor("11:22:33:44:55:66", "2024-01-15T13:00:00Z").unwrap();
+        let cursor = encode_device_page_token("11:22:33:44:55:66", "2024-01-15T13:00:00Z").unwrap();
 
         let response = ListDevicesResponse {
+            page: PageMetadata::new(2, device_items.len(), Some(&cursor)),
             devices: device_items,
             next_cursor: Some(cursor.clone()),
         };
@@ -798,10 +1177,13 @@ mod integration_style_tests {
                 firmware_version: device.firmware_version,
                 first_registered_at: device.first_registered_at,
                 last_seen_at: device.last_seen_at,
+                first_reading_ms: device.first_reading_ms,
+                last_reading_ms: device.last_reading_ms,
             })
             .collect();
 
         let response = ListDevicesResponse {
+            page: PageMetadata::new(50, device_items.len(), None),
             devices: device_items,
             next_cursor: None,
         };
@@ -814,13 +1196,13 @@ mod integration_style_tests {
 
     #[tokio::test]
     async fn test_cursor_encoding_decoding_roundtrip() {
-        use esp32_backend::shared::cursor::{decode_device_cursor, encode_device_cursor};
+        use esp32_backend::shared::cursor::{decode_device_page_token, encode_device_page_token};
 
         let hardware_id = "AA:BB:CC:DD:EE:FF";
         let gsi1sk = "2024-01-15T14:22:00Z";
 
         // Encode cursor
-        let encoded = encode_device_cursor(hardware_id, gsi1sk).unwrap();
+        let encoded = encode_device_page_token(hardware_id, gsi1sk).unwrap();
 
         // Verify it's base64
         assert!(!encoded.is_empty());
@@ -829,7 +1211,7 @@ mod integration_style_tests {
             .all(|c| c.is_alphanumeric() || c == '+' || c == '/' || c == '='));
 
         // Decode cursor
-        let decoded = decode_device_cursor(&encoded).unwrap();
+        let decoded = decode_device_page_token(&encoded).unwrap();
 
         // Verify roundtrip
         assert_eq!(decoded.hardware_id, hardware_id);
@@ -838,9 +1220,9 @@ mod integration_style_tests {
 
     #[tokio::test]
     async fn test_cursor_decoding_invalid_base64() {
-        use esp32_backend::shared::cursor::decode_device_cursor;
+        use esp32_backend::shared::cursor::decode_device_page_token;
 
-        let result = decode_device_cursor("not-valid-base64!@#$%");
+        let result = decode_device_page_token("not-valid-base64!@#$%");
         assert!(result.is_err());
     }
 
@@ -868,6 +1250,198 @@ mod integration_style_tests {
         assert!(json.contains("last_boot_id"));
     }
 
+    #[test]
+    fn test_device_etag_stable_for_identical_state() {
+        let a = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+        let b = a.clone();
+
+        assert_eq!(device_etag(&a), device_etag(&b));
+    }
+
+    #[test]
+    fn test_check_if_match_accepts_current_etag() {
+        let device = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+
+        assert!(check_if_match(Some(&device_etag(&device)), &device).is_ok());
+        assert!(check_if_match(Some("*"), &device).is_ok());
+        assert!(check_if_match(None, &device).is_ok());
+    }
+
+    #[test]
+    fn test_check_if_match_rejects_stale_etag() {
+        let read = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+        let stale_etag = device_etag(&read);
+
+        // Another client renamed the device after it was read
+        let mut current = read.clone();
+        current.friendly_name = Some(String::from("renamed"));
+
+        let error = check_if_match(Some(&stale_etag), &current).unwrap_err();
+        assert!(matches!(error, ApiError::PreconditionFailed));
+        assert_eq!(error.to_http_response("req-412").status(), 412);
+    }
+
+    #[test]
+    fn test_device_etag_changes_with_last_reading_ms() {
+        let mut a = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+        a.last_reading_ms = Some(1705328520000);
+        let mut b = a.clone();
+        b.last_reading_ms = Some(1705328820000);
+
+        assert_ne!(device_etag(&a), device_etag(&b));
+    }
+
+    #[test]
+    fn test_device_etag_changes_with_readings_this_boot() {
+        let a = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+        let mut b = a.clone();
+        b.readings_this_boot = Some(12);
+
+        assert_ne!(device_etag(&a), device_etag(&b));
+    }
+
+    #[test]
+    fn test_device_etag_changes_with_last_seen_at() {
+        let a = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+        let b = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:27:00Z",
+        );
+
+        assert_ne!(device_etag(&a), device_etag(&b));
+    }
+
+    #[test]
+    fn test_device_detail_response_sets_etag() {
+        let device = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+
+        let response = device_detail_response(&device, None, KeyCase::Snake).unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get("etag").unwrap().to_str().unwrap(),
+            device_etag(&device)
+        );
+
+        // Stale tag still returns the full body
+        let response =
+            device_detail_response(&device, Some("W/\"stale\""), KeyCase::Snake).unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    #[test]
+    fn test_device_detail_response_not_modified() {
+        let device = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+        let etag = device_etag(&device);
+
+        let response = device_detail_response(&device, Some(&etag), KeyCase::Snake).unwrap();
+        assert_eq!(response.status(), 304);
+        assert_eq!(
+            response.headers().get("etag").unwrap().to_str().unwrap(),
+            etag
+        );
+        assert!(matches!(response.body(), Body::Empty));
+    }
+
+    #[test]
+    fn test_device_detail_camel_case_keeps_feature_names() {
+        let device = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            Some("test-device"),
+            "2024-01-15T14:22:00Z",
+        );
+
+        let response = device_detail_response(&device, None, KeyCase::Camel).unwrap();
+        let body = match response.body() {
+            Body::Text(text) => serde_json::from_str::<serde_json::Value>(text).unwrap(),
+            _ => panic!("Expected text body"),
+        };
+
+        assert_eq!(body["firstRegisteredAt"], "2024-01-15T10:30:00Z");
+        assert_eq!(body["lastBootId"], "7c9e6679-7425-40de-944b-e07fc1f90ae7");
+        assert!(body.get("first_registered_at").is_none());
+        // Feature names are data, not field names
+        assert_eq!(body["capabilities"]["features"]["tft_display"], true);
+        assert!(body["capabilities"]["features"].get("tftDisplay").is_none());
+    }
+
+    #[test]
+    fn test_list_devices_response_case() {
+        let device = create_test_device(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            None,
+            "2024-01-15T14:22:00Z",
+        );
+        let response = ListDevicesResponse {
+            devices: vec![DeviceListItem {
+                hardware_id: device.hardware_id,
+                confirmation_id: device.confirmation_id,
+                friendly_name: device.friendly_name,
+                firmware_version: device.firmware_version,
+                first_registered_at: device.first_registered_at,
+                last_seen_at: device.last_seen_at,
+                first_reading_ms: Some(1705314600000),
+                last_reading_ms: None,
+            }],
+            page: PageMetadata::new(50, 1, None),
+            next_cursor: None,
+        };
+
+        let snake = crate::response::to_json_string(&response, KeyCase::Snake).unwrap();
+        assert!(snake.contains(r#""first_registered_at":"2024-01-15T10:30:00Z""#));
+        assert!(snake.contains(r#""has_more":false"#));
+
+        let camel = crate::response::to_json_string(&response, KeyCase::Camel).unwrap();
+        assert!(camel.contains(r#""firstRegisteredAt":"2024-01-15T10:30:00Z""#));
+        assert!(camel.contains(r#""firstReadingMs":1705314600000"#));
+        assert!(camel.contains(r#""hasMore":false"#));
+        assert!(camel.contains(r#""nextCursor":null"#));
+        assert!(!camel.contains("first_registered_at"));
+    }
+
     #[tokio::test]
     async fn test_device_not_found_error_type() {
         use crate::error::NotFoundError;
@@ -887,6 +1461,7 @@ mod integration_style_tests {
     async fn test_list_devices_empty_result() {
         let response = ListDevicesResponse {
             devices: vec![],
+            page: PageMetadata::new(50, 0, None),
             next_cursor: None,
         };
 
@@ -953,6 +1528,8 @@ mod integration_style_tests {
             firmware_version: device.firmware_version,
             first_registered_at: device.first_registered_at,
             last_seen_at: device.last_seen_at,
+            first_reading_ms: device.first_reading_ms,
+            last_reading_ms: device.last_reading_ms,
         };
 
         let json = serde_json::to_string(&list_item).unwrap();
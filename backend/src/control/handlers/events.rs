@@ -0,0 +1,197 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use esp32_backend::shared::plant_insights::{Event, EventType};
+use esp32_backend::shared::query_params::{
+    parse_clamped_limit, parse_optional_string, parse_required_timestamp,
+};
+use esp32_backend::shared::validators::{DEFAULT_PAGE_LIMIT, MAX_EVENTS_PAGE_LIMIT};
+
+/// Response payload for events query
+#[derive(Debug, Serialize)]
+pub struct QueryEventsResponse {
+    /// List of detected events (newest first)
+    pub events: Vec<Event>,
+    /// Optional cursor for pagination
+    pub next_cursor: Option<String>,
+}
+
+/// Handler for GET /devices/{hardware_id}/events endpoint
+///
+/// Queries detected plant events for a device within a time range with pagination.
+///
+/// # Query Parameters
+/// * `from` - Start of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `to` - End of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `type` - Optional event type filter (e.g. `Watering_Event`)
+/// * `limit` - Maximum number of events to evaluate (default 50, max 1000)
+/// * `cursor` - Optional pagination cursor from previous response
+///
+/// # Returns
+/// * HTTP 200 with events list and optional next_cursor
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 400 if query parameters are invalid (including unknown `type`)
+/// * HTTP 404 if device doesn't exist
+pub async fn query_events(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing query events request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    // Parse query parameters
+    let query_params = event.query_string_parameters();
+
+    // Parse from timestamp (required)
+    let from_ms = parse_required_timestamp(&query_params, "from")?;
+
+    // Parse to timestamp (required)
+    let to_ms = parse_required_timestamp(&query_params, "to")?;
+
+    // Parse event type filter (optional, must be a known event type)
+    let event_type = parse_event_type(query_params.first("type"))?;
+
+    let limit = parse_clamped_limit(&query_params, DEFAULT_PAGE_LIMIT, MAX_EVENTS_PAGE_LIMIT);
+
+    let cursor = parse_optional_string(&query_params, "cursor");
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        from_ms = from_ms,
+        to_ms = to_ms,
+        event_type = ?event_type,
+        limit = limit,
+        has_cursor = cursor.is_some(),
+        "Parsed query parameters"
+    );
+
+    // First, check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let result = crate::repo::events::query_events(
+        &config.dynamodb_client,
+        &config.plant_events_table,
+        hardware_id,
+        from_ms,
+        to_ms,
+        event_type,
+        Some(limit),
+        cursor,
+    )
+    .await?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        count = result.events.len(),
+        has_next_cursor = result.next_cursor.is_some(),
+        "Retrieved events from DynamoDB"
+    );
+
+    let response = QueryEventsResponse {
+        events: result.events,
+        next_cursor: result.next_cursor,
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Parse the optional `type` query parameter into an EventType
+fn parse_event_type(value: Option<&str>) -> Result<Option<EventType>, ApiError> {
+    match value {
+        None => Ok(None),
+        Some(s) => EventType::parse(s).map(Some).ok_or_else(|| {
+            crate::error::ValidationError::InvalidValue(String::from("type")).into()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parse_event_type_valid() {
+        assert_eq!(parse_event_type(None).unwrap(), None);
+        assert_eq!(
+            parse_event_type(Some("Watering_Event")).unwrap(),
+            Some(EventType::WateringEvent)
+        );
+        assert_eq!(
+            parse_event_type(Some("Humidity_Anomaly")).unwrap(),
+            Some(EventType::HumidityAnomaly)
+        );
+    }
+
+    #[test]
+    fn test_parse_event_type_invalid_returns_400() {
+        let err = parse_event_type(Some("Flood")).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field)) if field == "type"
+        ));
+
+        let response = err.to_http_response("test-request-id");
+        assert_eq!(response.status(), 400);
+    }
+
+    #[test]
+    fn test_query_events_response_serialization() {
+        let response = QueryEventsResponse {
+            events: vec![Event {
+                hardware_id: String::from("AA:BB:CC:DD:EE:FF"),
+                start_time_ms: 1704067200000,
+                end_time_ms: 1704067800000,
+                event_type: EventType::WateringEvent,
+                sensor_values: HashMap::new(),
+                detection_metadata: HashMap::new(),
+                created_at_ms: 1704067900000,
+            }],
+            next_cursor: None,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"event_type\":\"Watering_Event\""));
+        assert!(json.contains("next_cursor"));
+    }
+}
@@ -0,0 +1,373 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use std::future::Future;
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::{ApiError, DatabaseError};
+use crate::repo::device_status::DeviceHealthFields;
+use crate::repo::devices::DeviceListFilter;
+use esp32_backend::shared::concurrency::{bounded_map, DEFAULT_FANOUT_CONCURRENCY};
+use esp32_backend::shared::domain::Device;
+use esp32_backend::shared::plant_insights::HealthCategory;
+use esp32_backend::shared::query_params::parse_clamped_limit;
+use esp32_backend::shared::status_transition::derive_health_category;
+use esp32_backend::shared::time::{Clock, SystemClock};
+use esp32_backend::shared::validators::{
+    DEFAULT_FLEET_HEALTH_MAX_DEVICES, MAX_FLEET_HEALTH_MAX_DEVICES,
+};
+
+/// Largest page size accepted by `list_devices`
+const DEVICE_PAGE_LIMIT: usize = 1000;
+
+/// Health inputs for one device
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceHealthInput {
+    /// RFC3339 `last_seen_at` from the device registry
+    pub last_seen_at: String,
+    /// Status item from the device status table, if one has been written
+    pub status: Option<DeviceHealthFields>,
+}
+
+impl DeviceHealthInput {
+    /// Health category of the device
+    ///
+    /// Uses the status item when present. A device without one (the status
+    /// updater has not seen a reading yet) is judged by its registry
+    /// `last_seen_at`; an unparseable timestamp counts as missing.
+    pub fn health_category(&self, now_ms: i64) -> HealthCategory {
+        match &self.status {
+            Some(status) => derive_health_category(
+                status.last_seen_ingest_time_ms,
+                status.last_error_at_ms,
+                now_ms,
+            ),
+            None => {
+                let last_seen_ms = chrono::DateTime::parse_from_rfc3339(&self.last_seen_at)
+                    .ok()
+                    .map(|dt| dt.timestamp_millis());
+                derive_health_category(last_seen_ms, None, now_ms)
+            }
+        }
+    }
+}
+
+/// Number of devices in each health category
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+pub struct FleetHealthCounts {
+    pub healthy: u32,
+    pub stale: u32,
+    pub missing: u32,
+    pub failing: u32,
+    /// Sum of the four categories
+    pub total: u32,
+}
+
+impl FleetHealthCounts {
+    /// Count devices by health category as of the clock's current time
+    pub fn from_devices(devices: &[DeviceHealthInput], clock: &dyn Clock) -> Self {
+        let now_ms = clock.now_epoch_ms();
+
+        let mut counts = Self::default();
+        for device in devices {
+            match device.health_category(now_ms) {
+                HealthCategory::Healthy => counts.healthy += 1,
+                HealthCategory::Stale => counts.stale += 1,
+                HealthCategory::Missing => counts.missing += 1,
+                HealthCategory::Failing => counts.failing += 1,
+            }
+            counts.total += 1;
+        }
+        counts
+    }
+}
+
+/// Response payload for GET /fleet/health
+#[derive(Debug, Serialize)]
+pub struct FleetHealthResponse {
+    #[serde(flatten)]
+    pub counts: FleetHealthCounts,
+    /// True when `max_devices` was reached before the last page of devices
+    pub truncated: bool,
+}
+
+/// Handler for GET /fleet/health endpoint
+///
+/// Pages through every registered device, looks up each device's status
+/// item (at most `DEFAULT_FANOUT_CONCURRENCY` at a time), and counts devices
+/// per health category.
+///
+/// # Query Parameters
+/// * `max_devices` - Maximum number of devices to evaluate (default 500, max 2000)
+///
+/// # Returns
+/// * HTTP 200 with per-category counts and a truncation flag
+/// * HTTP 401 if Bearer token is invalid
+pub async fn get_fleet_health(
+    event: Request,
+    config: &ControlConfig,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(request_id = %request_id, "Processing fleet health request");
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let max_devices = parse_clamped_limit(
+        &event.query_string_parameters(),
+        DEFAULT_FLEET_HEALTH_MAX_DEVICES,
+        MAX_FLEET_HEALTH_MAX_DEVICES,
+    ) as usize;
+
+    let (devices, truncated) = list_devices_capped(config, max_devices).await?;
+
+    info!(
+        request_id = %request_id,
+        count = devices.len(),
+        truncated = truncated,
+        "Looking up device status for fleet health"
+    );
+
+    let inputs = lookup_health(devices, |hardware_id| async move {
+        crate::repo::device_status::get_device_health(
+            &config.dynamodb_client,
+            &config.device_status_table,
+            &hardware_id,
+        )
+        .await
+    })
+    .await?;
+
+    let response = FleetHealthResponse {
+        counts: FleetHealthCounts::from_devices(&inputs, &SystemClock::new()),
+        truncated,
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Read up to `max_devices` devices, following page tokens
+///
+/// Returns the devices and whether more pages remained when the cap was hit.
+async fn list_devices_capped(
+    config: &ControlConfig,
+    max_devices: usize,
+) -> Result<(Vec<Device>, bool), DatabaseError> {
+    let mut devices = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let remaining = (max_devices - devices.len()).min(DEVICE_PAGE_LIMIT);
+        let page = crate::repo::devices::list_devices(
+            &config.dynamodb_client,
+            &config.devices_table,
+            Some(remaining as i32),
+            page_token,
+            &DeviceListFilter::default(),
+        )
+        .await?;
+
+        devices.extend(page.devices);
+
+        match page.page_token {
+            Some(_) if devices.len() >= max_devices => return Ok((devices, true)),
+            Some(token) => page_token = Some(token),
+            None => return Ok((devices, false)),
+        }
+    }
+}
+
+/// Look up the status of every device, `DEFAULT_FANOUT_CONCURRENCY` at a time
+async fn lookup_health<F, Fut>(
+    devices: Vec<Device>,
+    lookup: F,
+) -> Result<Vec<DeviceHealthInput>, DatabaseError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Option<DeviceHealthFields>, DatabaseError>>,
+{
+    bounded_map(devices, DEFAULT_FANOUT_CONCURRENCY, |device| {
+        let status = lookup(device.hardware_id);
+        async move {
+            status.await.map(|status| DeviceHealthInput {
+                last_seen_at: device.last_seen_at,
+                status,
+            })
+        }
+    })
+    .await
+    .into_iter()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::domain::Capabilities;
+    use esp32_backend::shared::time::FixedClock;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const HOUR_MS: i64 = 3_600_000;
+
+    /// 2024-01-01T12:00:00Z
+    fn clock() -> FixedClock {
+        FixedClock::from_rfc3339("2024-01-01T12:00:00Z").unwrap()
+    }
+
+    fn now_ms() -> i64 {
+        clock().now_epoch_ms()
+    }
+
+    fn with_status(seen_hours_ago: i64, error_hours_ago: Option<i64>) -> DeviceHealthInput {
+        DeviceHealthInput {
+            last_seen_at: "2024-01-01T11:00:00Z".to_string(),
+            status: Some(DeviceHealthFields {
+                last_seen_ingest_time_ms: Some(now_ms() - seen_hours_ago * HOUR_MS),
+                last_error_at_ms: error_hours_ago.map(|h| now_ms() - h * HOUR_MS),
+            }),
+        }
+    }
+
+    fn without_status(last_seen_at: &str) -> DeviceHealthInput {
+        DeviceHealthInput {
+            last_seen_at: last_seen_at.to_string(),
+            status: None,
+        }
+    }
+
+    fn device(hardware_id: &str) -> Device {
+        Device {
+            hardware_id: hardware_id.to_string(),
+            confirmation_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+            friendly_name: None,
+            firmware_version: "1.0.16".to_string(),
+            capabilities: Capabilities {
+                sensors: vec![],
+                features: HashMap::new(),
+            },
+            first_registered_at: "2024-01-01T00:00:00Z".to_string(),
+            last_seen_at: "2024-01-01T11:30:00Z".to_string(),
+            last_boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_mix_of_statuses() {
+        let devices = vec![
+            with_status(1, None),
+            with_status(0, Some(48)),
+            with_status(4, None),
+            with_status(10, None),
+            with_status(1, Some(3)),
+        ];
+
+        let counts = FleetHealthCounts::from_devices(&devices, &clock());
+
+        assert_eq!(
+            counts,
+            FleetHealthCounts {
+                healthy: 2,
+                stale: 1,
+                missing: 1,
+                failing: 1,
+                total: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_counts_infer_from_last_seen_without_status() {
+        let devices = vec![
+            without_status("2024-01-01T11:00:00Z"),
+            without_status("2024-01-01T08:00:00+00:00"),
+            without_status("2023-12-31T00:00:00Z"),
+            without_status("not-a-timestamp"),
+        ];
+
+        let counts = FleetHealthCounts::from_devices(&devices, &clock());
+
+        assert_eq!(counts.healthy, 1);
+        assert_eq!(counts.stale, 1);
+        assert_eq!(counts.missing, 2);
+        assert_eq!(counts.failing, 0);
+        assert_eq!(counts.total, 4);
+    }
+
+    #[test]
+    fn test_counts_follow_clock() {
+        let devices = vec![with_status(1, None)];
+
+        let mut later = clock();
+        later.advance_seconds(3 * 3600);
+
+        assert_eq!(
+            FleetHealthCounts::from_devices(&devices, &clock()).healthy,
+            1
+        );
+        assert_eq!(FleetHealthCounts::from_devices(&devices, &later).stale, 1);
+    }
+
+    #[test]
+    fn test_fleet_health_response_serialization() {
+        let response = FleetHealthResponse {
+            counts: FleetHealthCounts::from_devices(&[with_status(1, None)], &clock()),
+            truncated: false,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            json,
+            r#"{"healthy":1,"stale":0,"missing":0,"failing":0,"total":1,"truncated":false}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_health_bounds_concurrency() {
+        let devices: Vec<Device> = (0..35)
+            .map(|i| device(&format!("AA:BB:CC:DD:EE:{:02X}", i)))
+            .collect();
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let inputs = lookup_health(devices, |_| async {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(None)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(inputs.len(), 35);
+        assert!(inputs
+            .iter()
+            .all(|i| i.last_seen_at == "2024-01-01T11:30:00Z"));
+        assert_eq!(peak.load(Ordering::SeqCst), DEFAULT_FANOUT_CONCURRENCY);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_health_propagates_database_errors() {
+        let result = lookup_health(vec![device("AA:BB:CC:DD:EE:01")], |_| async {
+            Err(DatabaseError::DynamoDb(String::from("throttled")))
+        })
+        .await;
+
+        assert!(result.is_err());
+    }
+}
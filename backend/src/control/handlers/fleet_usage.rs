@@ -0,0 +1,245 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::{ApiError, DatabaseError};
+use crate::handlers::reading_stats::validate_range;
+use crate::repo::devices::DeviceListFilter;
+use esp32_backend::shared::concurrency::{bounded_map, DEFAULT_FANOUT_CONCURRENCY};
+use esp32_backend::shared::query_params::{
+    parse_clamped_limit, parse_optional_string, parse_required_timestamp,
+};
+use esp32_backend::shared::validators::{DEFAULT_PAGE_LIMIT, MAX_FLEET_USAGE_PAGE_LIMIT};
+
+/// Per-device reading counts for one page of devices
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct FleetUsage {
+    /// Reading count per hardware_id
+    pub devices: BTreeMap<String, i64>,
+    /// Sum of `devices` counts
+    pub total: i64,
+}
+
+impl FleetUsage {
+    /// Aggregate per-device counts into a map and total
+    pub fn from_counts(counts: Vec<(String, i64)>) -> Self {
+        let mut usage = Self::default();
+        for (hardware_id, count) in counts {
+            usage.total += count;
+            *usage.devices.entry(hardware_id).or_insert(0) += count;
+        }
+        usage
+    }
+}
+
+/// Response payload for GET /fleet/usage
+#[derive(Debug, Serialize)]
+pub struct FleetUsageResponse {
+    /// Start of range (epoch milliseconds or RFC3339, inclusive)
+    pub from: i64,
+    /// End of range (epoch milliseconds or RFC3339, inclusive)
+    pub to: i64,
+    #[serde(flatten)]
+    pub usage: FleetUsage,
+    /// Cursor for the next page of devices
+    pub next_cursor: Option<String>,
+}
+
+/// Handler for GET /fleet/usage endpoint
+///
+/// Returns the number of readings each device stored in a time range, for
+/// cost attribution. Devices are paged in the same order as GET /devices;
+/// `total` covers only the devices on the returned page.
+///
+/// # Query Parameters
+/// * `from` - Start of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `to` - End of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `limit` - Maximum number of devices to count (default 50, max 50)
+/// * `cursor` - Optional pagination cursor from previous response
+///
+/// # Returns
+/// * HTTP 200 with per-device counts, page total, and optional next_cursor
+/// * HTTP 400 if parameters are invalid or the range exceeds the cap
+/// * HTTP 401 if Bearer token is invalid
+pub async fn get_fleet_usage(
+    event: Request,
+    config: &ControlConfig,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(request_id = %request_id, "Processing fleet usage request");
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let query_params = event.query_string_parameters();
+
+    // Parse from timestamp (required)
+    let from_ms = parse_required_timestamp(&query_params, "from")?;
+
+    // Parse to timestamp (required)
+    let to_ms = parse_required_timestamp(&query_params, "to")?;
+
+    validate_range(from_ms, to_ms, config.max_query_range_ms())?;
+
+    let limit = parse_clamped_limit(
+        &query_params,
+        DEFAULT_PAGE_LIMIT,
+        MAX_FLEET_USAGE_PAGE_LIMIT,
+    );
+
+    let cursor = parse_optional_string(&query_params, "cursor");
+
+    let page = crate::repo::devices::list_devices(
+        &config.dynamodb_client,
+        &config.devices_table,
+        Some(limit),
+        cursor,
+        &DeviceListFilter::default(),
+    )
+    .await?;
+
+    let hardware_ids: Vec<String> = page
+        .devices
+        .into_iter()
+        .map(|device| device.hardware_id)
+        .collect();
+
+    info!(
+        request_id = %request_id,
+        count = hardware_ids.len(),
+        "Counting readings per device"
+    );
+
+    let counts = count_for_each(&hardware_ids, |hardware_id| async move {
+        crate::repo::readings::count_readings(
+            &config.dynamodb_client,
+            &config.device_readings_table,
+            &hardware_id,
+            from_ms,
+            to_ms,
+        )
+        .await
+    })
+    .await?;
+
+    let response = FleetUsageResponse {
+        from: from_ms,
+        to: to_ms,
+        usage: FleetUsage::from_counts(counts),
+        next_cursor: page.page_token,
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Run `count` for every hardware ID, `DEFAULT_FANOUT_CONCURRENCY` at a time,
+/// pairing each ID with its count
+async fn count_for_each<F, Fut>(
+    hardware_ids: &[String],
+    count: F,
+) -> Result<Vec<(String, i64)>, DatabaseError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<i64, DatabaseError>>,
+{
+    let results = bounded_map(
+        hardware_ids.iter().cloned(),
+        DEFAULT_FANOUT_CONCURRENCY,
+        count,
+    )
+    .await;
+
+    hardware_ids
+        .iter()
+        .cloned()
+        .zip(results)
+        .map(|(hardware_id, result)| result.map(|count| (hardware_id, count)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fleet_usage_from_counts() {
+        let usage = FleetUsage::from_counts(vec![
+            (String::from("AA:BB:CC:DD:EE:01"), 120),
+            (String::from("AA:BB:CC:DD:EE:02"), 0),
+            (String::from("AA:BB:CC:DD:EE:03"), 45),
+        ]);
+
+        assert_eq!(usage.total, 165);
+        assert_eq!(usage.devices.len(), 3);
+        assert_eq!(usage.devices["AA:BB:CC:DD:EE:01"], 120);
+        assert_eq!(usage.devices["AA:BB:CC:DD:EE:02"], 0);
+        assert_eq!(usage.devices["AA:BB:CC:DD:EE:03"], 45);
+    }
+
+    #[test]
+    fn test_fleet_usage_from_counts_empty() {
+        assert_eq!(FleetUsage::from_counts(Vec::new()), FleetUsage::default());
+    }
+
+    #[tokio::test]
+    async fn test_count_for_each_with_fixed_counts() {
+        let hardware_ids = vec![
+            String::from("AA:BB:CC:DD:EE:01"),
+            String::from("AA:BB:CC:DD:EE:02"),
+        ];
+        let fixed: BTreeMap<&str, i64> =
+            [("AA:BB:CC:DD:EE:01", 10), ("AA:BB:CC:DD:EE:02", 32)].into();
+
+        let counts = count_for_each(&hardware_ids, |hardware_id| {
+            let count = fixed[hardware_id.as_str()];
+            async move { Ok(count) }
+        })
+        .await
+        .unwrap();
+
+        let usage = FleetUsage::from_counts(counts);
+        assert_eq!(usage.total, 42);
+        assert_eq!(usage.devices["AA:BB:CC:DD:EE:02"], 32);
+    }
+
+    #[tokio::test]
+    async fn test_count_for_each_propagates_database_errors() {
+        let hardware_ids = vec![String::from("AA:BB:CC:DD:EE:01")];
+
+        let result = count_for_each(&hardware_ids, |_| async {
+            Err(DatabaseError::DynamoDb(String::from("throttled")))
+        })
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fleet_usage_response_serialization() {
+        let response = FleetUsageResponse {
+            from: 1000,
+            to: 2000,
+            usage: FleetUsage::from_counts(vec![(String::from("AA:BB:CC:DD:EE:01"), 7)]),
+            next_cursor: None,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"devices\":{\"AA:BB:CC:DD:EE:01\":7}"));
+        assert!(json.contains("\"total\":7"));
+        assert!(json.contains("\"next_cursor\":null"));
+    }
+}
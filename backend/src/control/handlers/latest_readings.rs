@@ -0,0 +1,261 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::{ApiError, DatabaseError};
+use crate::handlers::readings::ReadingResponseItem;
+use esp32_backend::shared::concurrency::{bounded_map, DEFAULT_FANOUT_CONCURRENCY};
+use esp32_backend::shared::domain::Reading;
+use esp32_backend::shared::validators::MAX_LATEST_BATCH_HARDWARE_IDS;
+
+/// Request payload for POST /devices/latest
+#[derive(Debug, Deserialize)]
+pub struct BatchLatestReadingsRequest {
+    pub hardware_ids: Vec<String>,
+}
+
+/// Response payload for POST /devices/latest
+#[derive(Debug, Serialize)]
+pub struct BatchLatestReadingsResponse {
+    /// Latest reading per requested hardware_id (null when the device is
+    /// unknown or has no readings)
+    pub readings: BTreeMap<String, Option<ReadingResponseItem>>,
+}
+
+/// Handler for POST /devices/latest endpoint
+///
+/// Returns the latest reading for up to 50 devices in one call. Lookups run
+/// concurrently, `DEFAULT_FANOUT_CONCURRENCY` at a time; duplicate IDs are
+/// collapsed and unknown devices map to null rather than failing the request.
+///
+/// # Request Body
+/// * `hardware_ids` - Device MAC addresses (1 to 50 distinct IDs)
+///
+/// # Returns
+/// * HTTP 200 with a map of hardware_id to latest reading (or null)
+/// * HTTP 400 if the body is invalid, empty, or lists more than 50 IDs
+/// * HTTP 401 if Bearer token is invalid
+pub async fn get_latest_readings_batch(
+    event: Request,
+    config: &ControlConfig,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(request_id = %request_id, "Processing batch latest readings request");
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let body = match event.body() {
+        Body::Text(text) => text,
+        Body::Binary(bytes) => std::str::from_utf8(bytes).map_err(|e| {
+            error!(request_id = %request_id, error = %e, "Failed to parse request body as UTF-8");
+            ApiError::Validation(crate::error::ValidationError::InvalidBody(
+                "Request body must be valid UTF-8".to_string(),
+            ))
+        })?,
+        Body::Empty => {
+            return Err(
+                crate::error::ValidationError::MissingField(String::from("hardware_ids")).into(),
+            )
+        }
+    };
+
+    let request: BatchLatestReadingsRequest = serde_json::from_str(body).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to deserialize request body");
+        ApiError::Validation(crate::error::ValidationError::InvalidBody(format!(
+            "Invalid JSON: {}",
+            e
+        )))
+    })?;
+
+    let hardware_ids = normalize_hardware_ids(request.hardware_ids)?;
+
+    info!(
+        request_id = %request_id,
+        count = hardware_ids.len(),
+        "Fetching latest readings"
+    );
+
+    let results = fetch_latest_for_each(&hardware_ids, |hardware_id| async move {
+        crate::repo::readings::get_latest_reading(
+            &config.dynamodb_client,
+            &config.device_readings_table,
+            &hardware_id,
+        )
+        .await
+    })
+    .await?;
+
+    let response = BatchLatestReadingsResponse {
+        readings: results
+            .into_iter()
+            .map(|(hardware_id, reading)| (hardware_id, reading.map(to_response_item)))
+            .collect(),
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Dedupe hardware IDs (keeping first occurrence order) and enforce the cap
+fn normalize_hardware_ids(hardware_ids: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let mut seen = HashSet::new();
+    let unique: Vec<String> = hardware_ids
+        .into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .collect();
+
+    if unique.is_empty() || unique.len() > MAX_LATEST_BATCH_HARDWARE_IDS {
+        return Err(
+            crate::error::ValidationError::InvalidValue(String::from("hardware_ids")).into(),
+        );
+    }
+
+    Ok(unique)
+}
+
+/// Run `fetch` for every hardware ID, `DEFAULT_FANOUT_CONCURRENCY` at a time
+///
+/// Results are returned in the same order as `hardware_ids`, regardless of
+/// the order in which the lookups complete.
+async fn fetch_latest_for_each<F, Fut, T>(
+    hardware_ids: &[String],
+    fetch: F,
+) -> Result<Vec<(String, Option<T>)>, DatabaseError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Option<T>, DatabaseError>>,
+{
+    let results = bounded_map(
+        hardware_ids.iter().cloned(),
+        DEFAULT_FANOUT_CONCURRENCY,
+        fetch,
+    )
+    .await;
+
+    hardware_ids
+        .iter()
+        .cloned()
+        .zip(results)
+        .map(|(hardware_id, result)| result.map(|value| (hardware_id, value)))
+        .collect()
+}
+
+pub(crate) fn to_response_item(reading: Reading) -> ReadingResponseItem {
+    ReadingResponseItem {
+        timestamp_ms: reading.timestamp_ms,
+        batch_id: reading.batch_id,
+        boot_id: reading.boot_id,
+        firmware_version: reading.firmware_version,
+        friendly_name: reading.friendly_name,
+        sensors: reading.sensors.into(),
+        status_summary: reading.sensor_status.summary(),
+        sensor_status: reading.sensor_status,
+        was_buffered: reading.was_buffered,
+        quality: reading.quality,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn ids(n: usize) -> Vec<String> {
+        (0..n)
+            .map(|i| format!("AA:BB:CC:DD:EE:{:02X}", i))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_preserves_request_order() {
+        let hardware_ids = ids(5);
+
+        // Earlier IDs finish last, so completion order is reversed
+        let results = fetch_latest_for_each(&hardware_ids, |hardware_id| async move {
+            let index = u64::from_str_radix(&hardware_id[15..], 16).unwrap();
+            tokio::time::sleep(Duration::from_millis((5 - index) * 10)).await;
+            Ok((index % 2 == 0).then_some(index))
+        })
+        .await
+        .unwrap();
+
+        let returned_ids: Vec<&String> = results.iter().map(|(id, _)| id).collect();
+        assert_eq!(returned_ids, hardware_ids.iter().collect::<Vec<_>>());
+        assert_eq!(
+            results.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec![Some(0), None, Some(2), None, Some(4)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_propagates_database_errors() {
+        let result = fetch_latest_for_each(&ids(3), |hardware_id| async move {
+            if hardware_id.ends_with("01") {
+                Err(DatabaseError::DynamoDb("throttled".to_string()))
+            } else {
+                Ok(Some(()))
+            }
+        })
+        .await;
+
+        assert!(matches!(result, Err(DatabaseError::DynamoDb(_))));
+    }
+
+    #[test]
+    fn test_normalize_hardware_ids_dedupes_in_order() {
+        let input = vec![
+            "AA:BB:CC:DD:EE:02".to_string(),
+            "AA:BB:CC:DD:EE:01".to_string(),
+            "AA:BB:CC:DD:EE:02".to_string(),
+        ];
+
+        assert_eq!(
+            normalize_hardware_ids(input).unwrap(),
+            vec!["AA:BB:CC:DD:EE:02", "AA:BB:CC:DD:EE:01"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_hardware_ids_cap() {
+        assert_eq!(normalize_hardware_ids(ids(50)).unwrap().len(), 50);
+
+        let err = normalize_hardware_ids(ids(51)).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field)) if field == "hardware_ids"
+        ));
+
+        // Duplicates do not count against the cap
+        let mut with_duplicates = ids(50);
+        with_duplicates.extend(ids(10));
+        assert_eq!(normalize_hardware_ids(with_duplicates).unwrap().len(), 50);
+    }
+
+    #[test]
+    fn test_normalize_hardware_ids_rejects_empty() {
+        assert!(normalize_hardware_ids(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_batch_latest_response_serializes_null_for_missing() {
+        let mut readings = BTreeMap::new();
+        readings.insert("AA:BB:CC:DD:EE:FF".to_string(), None);
+
+        let json = serde_json::to_string(&BatchLatestReadingsResponse { readings }).unwrap();
+        assert_eq!(json, r#"{"readings":{"AA:BB:CC:DD:EE:FF":null}}"#);
+    }
+}
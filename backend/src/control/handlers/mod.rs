@@ -1,3 +1,107 @@
+use lambda_http::{Body, Request};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::error;
+
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use crate::response::CamelCaseMirror;
+use esp32_backend::shared::validators::validate_body_size;
+
+pub mod aggregate_recompute;
 pub mod api_keys;
+pub mod boots;
+pub mod debug;
+pub mod device_status;
 pub mod devices;
+pub mod events;
+pub mod fleet_health;
+pub mod fleet_usage;
+pub mod latest_readings;
+pub mod notes;
+pub mod reading_export;
+pub mod reading_stats;
 pub mod readings;
+pub mod recent_readings;
+pub mod status_history;
+
+/// Pagination summary included in list responses
+///
+/// `has_more` is true exactly when the response carries a cursor for the
+/// next page. A page may hold fewer than `limit` items and still have more
+/// (e.g. when a filter is applied after the query).
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PageMetadata {
+    /// Page size the request was served with, after clamping
+    pub limit: i32,
+    /// Number of items in this page
+    pub returned: usize,
+    /// Whether another page is available
+    pub has_more: bool,
+}
+
+/// `PageMetadata` with camelCase field names, for `?case=camel`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageMetadataCamel {
+    pub limit: i32,
+    pub returned: usize,
+    pub has_more: bool,
+}
+
+impl CamelCaseMirror for PageMetadata {
+    type Camel<'a> = PageMetadataCamel;
+
+    fn camel(&self) -> PageMetadataCamel {
+        PageMetadataCamel {
+            limit: self.limit,
+            returned: self.returned,
+            has_more: self.has_more,
+        }
+    }
+}
+
+impl PageMetadata {
+    pub fn new(limit: i32, returned: usize, next_cursor: Option<&String>) -> Self {
+        Self {
+            limit,
+            returned,
+            has_more: next_cursor.is_some(),
+        }
+    }
+}
+
+/// Parse a JSON request body after checking its size
+///
+/// An empty body is parsed as `{}`.
+pub(crate) fn parse_json_body<T: DeserializeOwned>(
+    event: &Request,
+    config: &ControlConfig,
+    request_id: &str,
+) -> Result<T, ApiError> {
+    let body = match event.body() {
+        Body::Text(text) => text,
+        Body::Binary(bytes) => std::str::from_utf8(bytes).map_err(|e| {
+            error!(request_id = %request_id, error = %e, "Failed to parse request body as UTF-8");
+            ApiError::Validation(crate::error::ValidationError::InvalidBody(
+                "Request body must be valid UTF-8".to_string(),
+            ))
+        })?,
+        Body::Empty => "{}",
+    };
+
+    let content_length = event
+        .headers()
+        .get("content-length")
+        .and_then(|v| v.to_str().ok());
+    validate_body_size(content_length, body.len(), config.max_body_bytes)
+        .map_err(|e| ApiError::Validation(crate::error::ValidationError::InvalidBody(e.message)))?;
+
+    serde_json::from_str(body).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to deserialize request body");
+        ApiError::Validation(crate::error::ValidationError::InvalidBody(format!(
+            "Invalid JSON: {}",
+            e
+        )))
+    })
+}
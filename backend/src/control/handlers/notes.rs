@@ -0,0 +1,275 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use crate::handlers::parse_json_body;
+use crate::handlers::reading_stats::validate_range;
+use esp32_backend::shared::domain::Note;
+use esp32_backend::shared::query_params::parse_required_timestamp;
+use esp32_backend::shared::validators::{validate_epoch_millis, validate_note_text};
+
+/// Request payload for creating a device note
+#[derive(Debug, Deserialize)]
+pub struct CreateNoteRequest {
+    /// Epoch ms the note refers to (same bounds as reading timestamps)
+    pub timestamp_ms: i64,
+    /// Note text (at most 1000 characters)
+    pub text: String,
+}
+
+/// Response payload for listing device notes
+#[derive(Debug, Serialize)]
+pub struct ListNotesResponse {
+    /// Notes in the requested range (oldest first)
+    pub notes: Vec<Note>,
+}
+
+/// Handler for POST /devices/{hardware_id}/notes endpoint
+///
+/// Attaches an operator note (e.g. "repotted plant here") to a device at a
+/// point in time so it can be correlated with sensor changes.
+///
+/// # Returns
+/// * HTTP 201 with the stored note
+/// * HTTP 400 if the text is empty or too long, or the timestamp is out of range
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn create_note(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing create note request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let request: CreateNoteRequest = parse_json_body(&event, config, &request_id)?;
+
+    validate_create_note_request(&request)?;
+
+    // Check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let note = crate::repo::notes::create_note(
+        &config.dynamodb_client,
+        &config.notes_table,
+        hardware_id,
+        request.timestamp_ms,
+        &request.text,
+    )
+    .await?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        timestamp_ms = note.timestamp_ms,
+        "Created note"
+    );
+
+    let response_body = serde_json::to_string(&note).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(201)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Handler for GET /devices/{hardware_id}/notes endpoint
+///
+/// # Query Parameters
+/// * `from` - Start of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `to` - End of time range (epoch milliseconds or RFC3339, inclusive)
+///
+/// # Returns
+/// * HTTP 200 with every note in the range (oldest first)
+/// * HTTP 400 if the range is missing, inverted, or wider than `MAX_QUERY_RANGE_DAYS`
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn list_notes(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing list notes request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let query_params = event.query_string_parameters();
+
+    // Parse from timestamp (required)
+    let from_ms = parse_required_timestamp(&query_params, "from")?;
+
+    // Parse to timestamp (required)
+    let to_ms = parse_required_timestamp(&query_params, "to")?;
+
+    validate_range(from_ms, to_ms, config.max_query_range_ms())?;
+
+    // Check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let notes = crate::repo::notes::list_notes(
+        &config.dynamodb_client,
+        &config.notes_table,
+        hardware_id,
+        from_ms,
+        to_ms,
+    )
+    .await?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        count = notes.len(),
+        "Retrieved notes from DynamoDB"
+    );
+
+    let response_body = serde_json::to_string(&ListNotesResponse { notes }).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Validate note text length and timestamp bounds
+fn validate_create_note_request(request: &CreateNoteRequest) -> Result<(), ApiError> {
+    validate_note_text(&request.text)
+        .map_err(|e| crate::error::ValidationError::InvalidValue(format!("text: {}", e.message)))?;
+
+    validate_epoch_millis(request.timestamp_ms).map_err(|e| {
+        crate::error::ValidationError::InvalidValue(format!("timestamp_ms: {}", e.message))
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::validators::MAX_NOTE_TEXT_LEN;
+
+    fn request(timestamp_ms: i64, text: &str) -> CreateNoteRequest {
+        CreateNoteRequest {
+            timestamp_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_create_note_request_valid() {
+        assert!(
+            validate_create_note_request(&request(1704067200000, "repotted plant here")).is_ok()
+        );
+        assert!(validate_create_note_request(&request(
+            1704067200000,
+            &"a".repeat(MAX_NOTE_TEXT_LEN)
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_create_note_request_text_too_long() {
+        let err = validate_create_note_request(&request(
+            1704067200000,
+            &"a".repeat(MAX_NOTE_TEXT_LEN + 1),
+        ))
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ApiError::Validation(crate::error::ValidationError::InvalidValue(ref msg)) if msg.starts_with("text:")
+        ));
+        assert_eq!(err.to_http_response("test-request-id").status(), 400);
+    }
+
+    #[test]
+    fn test_validate_create_note_request_timestamp_out_of_bounds() {
+        // Before year 2000, same bound as reading timestamps
+        let err = validate_create_note_request(&request(1000, "note")).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Validation(crate::error::ValidationError::InvalidValue(ref msg)) if msg.starts_with("timestamp_ms:")
+        ));
+    }
+
+    #[test]
+    fn test_create_note_request_requires_text() {
+        let result: Result<CreateNoteRequest, _> =
+            serde_json::from_str(r#"{"timestamp_ms": 1704067200000}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_list_notes_response_serialization() {
+        let response = ListNotesResponse {
+            notes: vec![Note {
+                hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+                timestamp_ms: 1704067200000,
+                text: "repotted plant here".to_string(),
+            }],
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            json,
+            r#"{"notes":[{"hardware_id":"AA:BB:CC:DD:EE:FF","timestamp_ms":1704067200000,"text":"repotted plant here"}]}"#
+        );
+    }
+}
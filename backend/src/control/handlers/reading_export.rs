@@ -0,0 +1,286 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use tracing::{error, info};
+
+use super::readings::{to_unit_response_item, ReadingResponseItem};
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use esp32_backend::shared::cursor::encode_readings_page_token;
+use esp32_backend::shared::domain::{build_ts_batch, Reading};
+use esp32_backend::shared::query_params::{
+    parse_optional_i32, parse_optional_string, parse_required_timestamp, SortOrder,
+};
+use esp32_backend::shared::units::UnitSystem;
+use esp32_backend::shared::validators::{clamp_limit, MAX_READINGS_PAGE_LIMIT};
+
+/// Most rows one export response may hold
+///
+/// The whole body is built in memory and returned in one response, which
+/// Lambda caps at 6 MB; 10,000 readings stays well under it.
+pub const MAX_EXPORT_ROWS: i32 = 10_000;
+
+/// Last line of an export that stopped at `max_rows`
+///
+/// Pass `next_cursor` back as `cursor` to continue where the export stopped.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ExportTrailer {
+    pub truncated: bool,
+    /// Number of readings written before the trailer
+    pub row_count: usize,
+    pub next_cursor: Option<String>,
+}
+
+/// Render readings as newline-delimited JSON, one object per line
+///
+/// There is no wrapping array; every line, including the optional trailer,
+/// is a complete JSON object ending in `\n`.
+pub fn format_ndjson(
+    items: &[ReadingResponseItem],
+    trailer: Option<&ExportTrailer>,
+) -> Result<String, serde_json::Error> {
+    let mut body = String::new();
+
+    for item in items {
+        body.push_str(&serde_json::to_string(item)?);
+        body.push('\n');
+    }
+
+    if let Some(trailer) = trailer {
+        body.push_str(&serde_json::to_string(trailer)?);
+        body.push('\n');
+    }
+
+    Ok(body)
+}
+
+/// Drop readings past `max_rows`, returning whether any were dropped
+pub fn truncate_to_cap(readings: &mut Vec<Reading>, max_rows: usize) -> bool {
+    let truncated = readings.len() > max_rows;
+    readings.truncate(max_rows);
+    truncated
+}
+
+/// Handler for GET /devices/{hardware_id}/readings/export endpoint
+///
+/// Pages through every reading in the range, oldest first, and returns them
+/// as NDJSON (`application/x-ndjson`) in metric units. Reads one row past
+/// `max_rows` to tell whether the range holds more; if it does, the body ends
+/// with an `ExportTrailer` line carrying a cursor to resume from.
+///
+/// # Query Parameters
+/// * `from` - Start of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `to` - End of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `max_rows` - Readings per response (default and max 10,000)
+/// * `cursor` - `next_cursor` from a truncated export's trailer
+///
+/// # Returns
+/// * HTTP 200 with one reading per line
+/// * HTTP 400 if parameters are invalid
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn export_readings(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing reading export request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let query_params = event.query_string_parameters();
+
+    // Parse from timestamp (required)
+    let from_ms = parse_required_timestamp(&query_params, "from")?;
+
+    // Parse to timestamp (required)
+    let to_ms = parse_required_timestamp(&query_params, "to")?;
+
+    let max_rows = clamp_limit(
+        parse_optional_i32(&query_params, "max_rows")?,
+        MAX_EXPORT_ROWS,
+        MAX_EXPORT_ROWS,
+    ) as usize;
+
+    let mut cursor = parse_optional_string(&query_params, "cursor");
+
+    // First, check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    // Page until the range is exhausted or one row past the cap has been read
+    let mut readings = Vec::new();
+    loop {
+        let remaining = (max_rows + 1 - readings.len()) as i32;
+        let page = crate::repo::readings::query_readings(
+            &config.dynamodb_client,
+            &config.device_readings_table,
+            hardware_id,
+            from_ms,
+            to_ms,
+            Some(remaining.min(MAX_READINGS_PAGE_LIMIT)),
+            cursor,
+            SortOrder::Asc,
+        )
+        .await?;
+
+        readings.extend(page.readings);
+
+        match page.next_cursor {
+            Some(next) if readings.len() <= max_rows => cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    let truncated = truncate_to_cap(&mut readings, max_rows);
+
+    // Resume after the last reading written
+    let trailer = truncated.then(|| ExportTrailer {
+        truncated: true,
+        row_count: readings.len(),
+        next_cursor: readings.last().and_then(|last| {
+            let ts_batch = build_ts_batch(last.timestamp_ms, last.seq, &last.batch_id);
+            encode_readings_page_token(hardware_id, &ts_batch).ok()
+        }),
+    });
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        count = readings.len(),
+        truncated = truncated,
+        "Exporting readings"
+    );
+
+    let items: Vec<ReadingResponseItem> = readings
+        .into_iter()
+        .map(|reading| to_unit_response_item(reading, UnitSystem::Metric))
+        .collect();
+
+    let response_body = format_ndjson(&items, trailer.as_ref()).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize export");
+        ApiError::Internal(format!("Failed to serialize export: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/x-ndjson")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::domain::{SensorStatus, SensorValues};
+    use std::collections::HashMap;
+
+    fn reading(timestamp_ms: i64) -> Reading {
+        Reading {
+            batch_id: format!("batch-{}", timestamp_ms),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: Some(22.5),
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: Some(41.0),
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: SensorStatus {
+                bme280: "ok".to_string(),
+                ds18b20: "missing".to_string(),
+                soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn items(readings: Vec<Reading>) -> Vec<ReadingResponseItem> {
+        readings
+            .into_iter()
+            .map(|r| to_unit_response_item(r, UnitSystem::Metric))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_ndjson_one_object_per_line() {
+        let body = format_ndjson(&items(vec![reading(1000), reading(2000)]), None).unwrap();
+
+        assert!(!body.starts_with('['));
+        assert!(body.ends_with('\n'));
+
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, expected_ts) in lines.iter().zip([1000, 2000]) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(value.is_object());
+            assert_eq!(value["timestamp_ms"], expected_ts);
+            assert_eq!(value["sensors"]["soil_moisture_pct"], 41.0);
+        }
+    }
+
+    #[test]
+    fn test_format_ndjson_empty_export() {
+        assert_eq!(format_ndjson(&[], None).unwrap(), "");
+    }
+
+    #[test]
+    fn test_format_ndjson_trailer_is_last_line() {
+        let trailer = ExportTrailer {
+            truncated: true,
+            row_count: 1,
+            next_cursor: Some("cursor".to_string()),
+        };
+        let body = format_ndjson(&items(vec![reading(1000)]), Some(&trailer)).unwrap();
+
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            r#"{"truncated":true,"row_count":1,"next_cursor":"cursor"}"#
+        );
+    }
+
+    #[test]
+    fn test_truncate_to_cap() {
+        let mut readings: Vec<Reading> = (1..=4).map(|i| reading(i * 1000)).collect();
+        assert!(truncate_to_cap(&mut readings, 3));
+        assert_eq!(readings.len(), 3);
+        assert_eq!(readings.last().unwrap().timestamp_ms, 3000);
+
+        // Exactly at the cap is not truncated
+        assert!(!truncate_to_cap(&mut readings, 3));
+        assert_eq!(readings.len(), 3);
+    }
+}
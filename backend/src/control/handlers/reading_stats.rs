@@ -0,0 +1,374 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use tracing::{error, info};
+
+use super::reading_export::truncate_to_cap;
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use esp32_backend::shared::domain::Reading;
+use esp32_backend::shared::plant_insights::{compute_percentiles, SensorStats};
+use esp32_backend::shared::query_params::{parse_required_timestamp, SortOrder};
+use esp32_backend::shared::validators::MAX_READINGS_PAGE_LIMIT;
+
+/// Status value marking a sensor reading as usable
+const SENSOR_STATUS_OK: &str = "ok";
+
+/// Numeric summary of one sensor over the requested range
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SensorSummary {
+    /// Number of valid values summarized
+    pub count: i64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Population standard deviation
+    pub stddev: f64,
+    pub p50: f64,
+    pub p95: f64,
+}
+
+impl SensorSummary {
+    /// Summarize a set of values, or `None` when there are none
+    pub fn from_values(mut values: Vec<f64>) -> Option<Self> {
+        let mut stats = SensorStats::default();
+        for value in &values {
+            stats.record(Some(*value));
+        }
+
+        if stats.valid_count == 0 {
+            return None;
+        }
+
+        values.retain(|v| v.is_finite());
+        values.sort_by(|a, b| a.total_cmp(b));
+        let percentiles = compute_percentiles(&values, &[50.0, 95.0]);
+
+        Some(Self {
+            count: stats.valid_count,
+            min: stats.min,
+            max: stats.max,
+            mean: stats.avg,
+            stddev: stats.stddev,
+            p50: percentiles[0],
+            p95: percentiles[1],
+        })
+    }
+}
+
+/// Per-sensor summaries; sensors with no valid values are omitted
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct SensorSummaries {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bme280_temp_c: Option<SensorSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ds18b20_temp_c: Option<SensorSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub humidity_pct: Option<SensorSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure_hpa: Option<SensorSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soil_moisture_pct: Option<SensorSummary>,
+}
+
+/// Response payload for reading stats query
+#[derive(Debug, Serialize)]
+pub struct ReadingStatsResponse {
+    pub hardware_id: String,
+    /// Start of range (epoch milliseconds or RFC3339, inclusive)
+    pub from: i64,
+    /// End of range (epoch milliseconds or RFC3339, inclusive)
+    pub to: i64,
+    /// Number of readings summarized
+    pub reading_count: usize,
+    /// True when the range held more readings than the row budget; only the
+    /// newest `reading_count` readings were summarized
+    pub partial: bool,
+    pub sensors: SensorSummaries,
+}
+
+/// Summarize each sensor across readings
+///
+/// A value is only included when its sensor reported `ok` for that reading
+/// (BME280 covers temperature, humidity, and pressure), matching the
+/// aggregator.
+pub fn summarize_readings(readings: &[Reading]) -> SensorSummaries {
+    let collect = |select: fn(&Reading) -> Option<f64>, status: fn(&Reading) -> &str| {
+        readings
+            .iter()
+            .filter(|r| status(r) == SENSOR_STATUS_OK)
+            .filter_map(select)
+            .collect::<Vec<f64>>()
+    };
+
+    SensorSummaries {
+        bme280_temp_c: SensorSummary::from_values(collect(
+            |r| r.sensors.bme280_temp_c,
+            |r| &r.sensor_status.bme280,
+        )),
+        ds18b20_temp_c: SensorSummary::from_values(collect(
+            |r| r.sensors.ds18b20_temp_c,
+            |r| &r.sensor_status.ds18b20,
+        )),
+        humidity_pct: SensorSummary::from_values(collect(
+            |r| r.sensors.humidity_pct,
+            |r| &r.sensor_status.bme280,
+        )),
+        pressure_hpa: SensorSummary::from_values(collect(
+            |r| r.sensors.pressure_hpa,
+            |r| &r.sensor_status.bme280,
+        )),
+        soil_moisture_pct: SensorSummary::from_values(collect(
+            |r| r.sensors.soil_moisture_pct,
+            |r| &r.sensor_status.soil_moisture,
+        )),
+    }
+}
+
+/// Handler for GET /devices/{hardware_id}/readings/stats endpoint
+///
+/// Returns count, min, max, mean, stddev, p50, and p95 per sensor over a time
+/// range. Pages are read newest first until the range is exhausted or the
+/// config's `stats_row_budget` is reached, in which case the response is
+/// marked `partial`. The range itself is capped by `MAX_QUERY_RANGE_DAYS`.
+///
+/// # Query Parameters
+/// * `from` - Start of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `to` - End of time range (epoch milliseconds or RFC3339, inclusive)
+///
+/// # Returns
+/// * HTTP 200 with per-sensor summaries (sensors without data are omitted)
+/// * HTTP 400 if parameters are invalid or the range exceeds the cap
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn get_reading_stats(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing reading stats request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let query_params = event.query_string_parameters();
+
+    // Parse from timestamp (required)
+    let from_ms = parse_required_timestamp(&query_params, "from")?;
+
+    // Parse to timestamp (required)
+    let to_ms = parse_required_timestamp(&query_params, "to")?;
+
+    validate_range(from_ms, to_ms, config.max_query_range_ms())?;
+
+    // First, check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    // Page until the range is exhausted or one row past the budget has been read
+    let row_budget = config.stats_row_budget;
+    let mut readings = Vec::new();
+    let mut cursor = None;
+    loop {
+        let remaining = (row_budget + 1 - readings.len()).min(MAX_READINGS_PAGE_LIMIT as usize);
+        let page = crate::repo::readings::query_readings(
+            &config.dynamodb_client,
+            &config.device_readings_table,
+            hardware_id,
+            from_ms,
+            to_ms,
+            Some(remaining as i32),
+            cursor,
+            SortOrder::Desc,
+        )
+        .await?;
+
+        readings.extend(page.readings);
+
+        match page.next_cursor {
+            Some(next) if readings.len() <= row_budget => cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    let partial = truncate_to_cap(&mut readings, row_budget);
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        count = readings.len(),
+        partial = partial,
+        "Retrieved readings for stats"
+    );
+
+    let response = ReadingStatsResponse {
+        hardware_id: hardware_id.to_string(),
+        from: from_ms,
+        to: to_ms,
+        reading_count: readings.len(),
+        partial,
+        sensors: summarize_readings(&readings),
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Reject inverted ranges and ranges wider than `max_range_ms`
+pub(crate) fn validate_range(from_ms: i64, to_ms: i64, max_range_ms: i64) -> Result<(), ApiError> {
+    if from_ms > to_ms || to_ms - from_ms > max_range_ms {
+        return Err(crate::error::ValidationError::InvalidValue(String::from("to")).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::domain::{SensorStatus, SensorValues};
+    use std::collections::HashMap;
+
+    fn reading(temp: Option<f64>, soil: Option<f64>, bme280_status: &str) -> Reading {
+        Reading {
+            batch_id: "batch1".to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms: 1704067800000,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: temp,
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: soil,
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: SensorStatus {
+                bme280: bme280_status.to_string(),
+                ds18b20: "ok".to_string(),
+                soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_readings_known_dataset() {
+        let readings: Vec<Reading> = (1..=10)
+            .map(|v| reading(Some(v as f64), Some(40.0), "ok"))
+            .collect();
+
+        let summaries = summarize_readings(&readings);
+        let temp = summaries.bme280_temp_c.unwrap();
+
+        assert_eq!(temp.count, 10);
+        assert_eq!(temp.min, 1.0);
+        assert_eq!(temp.max, 10.0);
+        assert!((temp.mean - 5.5).abs() < 1e-9);
+        assert!((temp.stddev - 8.25_f64.sqrt()).abs() < 1e-9);
+        assert!((temp.p50 - 5.5).abs() < 1e-9);
+        assert!((temp.p95 - 9.55).abs() < 1e-9);
+
+        let soil = summaries.soil_moisture_pct.unwrap();
+        assert_eq!(soil.stddev, 0.0);
+        assert_eq!(soil.p95, 40.0);
+
+        assert_eq!(summaries.humidity_pct, None);
+        assert_eq!(summaries.ds18b20_temp_c, None);
+    }
+
+    #[test]
+    fn test_summarize_readings_skips_sensor_errors() {
+        let readings = vec![
+            reading(Some(20.0), None, "ok"),
+            reading(Some(85.0), None, "error"),
+        ];
+
+        let temp = summarize_readings(&readings).bme280_temp_c.unwrap();
+        assert_eq!(temp.count, 1);
+        assert_eq!(temp.max, 20.0);
+    }
+
+    #[test]
+    fn test_summarize_readings_empty_range() {
+        let summaries = summarize_readings(&[]);
+        assert_eq!(summaries, SensorSummaries::default());
+
+        let response = ReadingStatsResponse {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            from: 1000,
+            to: 2000,
+            reading_count: 0,
+            partial: false,
+            sensors: summaries,
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"reading_count\":0"));
+        assert!(json.contains("\"partial\":false"));
+        assert!(json.contains("\"sensors\":{}"));
+    }
+
+    #[test]
+    fn test_row_budget_summarizes_newest_readings() {
+        // Newest first, as the handler reads them; one row past a budget of 4
+        let mut readings: Vec<Reading> = [9.0, 7.0, 3.0, 5.0, 100.0]
+            .iter()
+            .map(|t| reading(Some(*t), None, "ok"))
+            .collect();
+
+        assert!(truncate_to_cap(&mut readings, 4));
+
+        let temp = summarize_readings(&readings).bme280_temp_c.unwrap();
+        assert_eq!(temp.count, 4);
+        assert_eq!(temp.min, 3.0);
+        assert_eq!(temp.max, 9.0);
+        assert!((temp.mean - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_validate_range() {
+        let day_ms = 86_400_000;
+        assert!(validate_range(0, 31 * day_ms, 31 * day_ms).is_ok());
+        assert!(validate_range(1000, 1000, day_ms).is_ok());
+
+        let err = validate_range(0, 31 * day_ms + 1, 31 * day_ms).unwrap_err();
+        assert_eq!(err.to_http_response("test-request-id").status(), 400);
+
+        assert!(validate_range(2000, 1000, day_ms).is_err());
+    }
+}
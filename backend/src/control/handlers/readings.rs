@@ -1,10 +1,23 @@
 use lambda_http::{Body, Request, RequestExt, Response};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use tracing::{error, info};
 
 use crate::auth::validate_bearer_token;
 use crate::config::ControlConfig;
 use crate::error::ApiError;
+use crate::handlers::{PageMetadata, PageMetadataCamel};
+use crate::response::{CamelCaseMirror, KeyCase};
+use esp32_backend::shared::domain::{
+    Reading, SensorStatusSummary, MAX_READING_SEQ, SENSOR_STATUS_OK,
+};
+use esp32_backend::shared::query_params::{
+    parse_clamped_limit, parse_optional, parse_optional_string, parse_required_timestamp,
+    parse_sort_order,
+};
+use esp32_backend::shared::units::{
+    convert_sensor_values, ResponseSensorValues, UnitSystem, UnitsMetadata,
+};
 
 /// Response item for readings query (excludes internal fields)
 #[derive(Debug, Serialize)]
@@ -20,9 +33,78 @@ pub struct ReadingResponseItem {
     /// Optional friendly name snapshot
     pub friendly_name: Option<String>,
     /// Sensor values
-    pub sensors: esp32_backend::shared::domain::SensorValues,
+    pub sensors: ResponseSensorValues,
     /// Sensor status
     pub sensor_status: esp32_backend::shared::domain::SensorStatus,
+    /// Overall sensor health derived from `sensor_status`
+    pub status_summary: SensorStatusSummary,
+    /// True if the reading was buffered on the device and delivered late
+    pub was_buffered: bool,
+    /// Device-reported quality score (0.0 to 1.0), if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
+}
+
+/// `ReadingResponseItem` with camelCase field names, for `?case=camel`
+///
+/// `sensors` and `sensor_status` are passed through as is, since their keys
+/// are sensor names (the same keys `format=series` returns).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingResponseItemCamel<'a> {
+    pub timestamp_ms: i64,
+    pub batch_id: &'a str,
+    pub boot_id: &'a str,
+    pub firmware_version: &'a str,
+    pub friendly_name: Option<&'a str>,
+    pub sensors: &'a ResponseSensorValues,
+    pub sensor_status: &'a esp32_backend::shared::domain::SensorStatus,
+    pub status_summary: SensorStatusSummary,
+    pub was_buffered: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
+}
+
+impl CamelCaseMirror for ReadingResponseItem {
+    type Camel<'a> = ReadingResponseItemCamel<'a>;
+
+    fn camel(&self) -> ReadingResponseItemCamel<'_> {
+        ReadingResponseItemCamel {
+            timestamp_ms: self.timestamp_ms,
+            batch_id: &self.batch_id,
+            boot_id: &self.boot_id,
+            firmware_version: &self.firmware_version,
+            friendly_name: self.friendly_name.as_deref(),
+            sensors: &self.sensors,
+            sensor_status: &self.sensor_status,
+            status_summary: self.status_summary,
+            was_buffered: self.was_buffered,
+            quality: self.quality,
+        }
+    }
+}
+
+/// `UnitsMetadata` with camelCase field names, for `?case=camel`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitsMetadataCamel {
+    pub temperature: &'static str,
+    pub pressure: &'static str,
+    pub humidity: &'static str,
+    pub soil_moisture: &'static str,
+}
+
+impl CamelCaseMirror for UnitsMetadata {
+    type Camel<'a> = UnitsMetadataCamel;
+
+    fn camel(&self) -> UnitsMetadataCamel {
+        UnitsMetadataCamel {
+            temperature: self.temperature,
+            pressure: self.pressure,
+            humidity: self.humidity,
+            soil_moisture: self.soil_moisture,
+        }
+    }
 }
 
 /// Response payload for readings query
@@ -30,24 +112,176 @@ pub struct ReadingResponseItem {
 pub struct QueryReadingsResponse {
     /// List of readings
     pub readings: Vec<ReadingResponseItem>,
+    /// Units of the sensor values in `readings`
+    pub units: UnitsMetadata,
+    /// Page size, item count, and whether more pages follow
+    pub page: PageMetadata,
     /// Optional cursor for pagination
     pub next_cursor: Option<String>,
 }
 
+/// `QueryReadingsResponse` with camelCase field names, for `?case=camel`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryReadingsResponseCamel<'a> {
+    pub readings: Vec<ReadingResponseItemCamel<'a>>,
+    pub units: UnitsMetadataCamel,
+    pub page: PageMetadataCamel,
+    pub next_cursor: Option<&'a str>,
+}
+
+impl CamelCaseMirror for QueryReadingsResponse {
+    type Camel<'a> = QueryReadingsResponseCamel<'a>;
+
+    fn camel(&self) -> QueryReadingsResponseCamel<'_> {
+        QueryReadingsResponseCamel {
+            readings: self
+                .readings
+                .iter()
+                .map(ReadingResponseItem::camel)
+                .collect(),
+            units: self.units.camel(),
+            page: self.page.camel(),
+            next_cursor: self.next_cursor.as_deref(),
+        }
+    }
+}
+
+/// One sensor value at a point in time, as returned by `format=series`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeriesPoint {
+    /// Epoch milliseconds UTC of the reading
+    pub t: i64,
+    /// Sensor value in the requested units
+    pub v: f64,
+}
+
+/// Response payload for readings query with `format=series`
+#[derive(Debug, Serialize)]
+pub struct SeriesReadingsResponse {
+    /// Points per sensor key, in the same order as the readings page
+    pub series: BTreeMap<String, Vec<SeriesPoint>>,
+    /// Units of the values in `series`
+    pub units: UnitsMetadata,
+    /// Page size, reading count, and whether more pages follow
+    pub page: PageMetadata,
+    /// Optional cursor for pagination
+    pub next_cursor: Option<String>,
+}
+
+/// `SeriesReadingsResponse` with camelCase field names, for `?case=camel`
+///
+/// The `series` keys are sensor names and keep their case.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesReadingsResponseCamel<'a> {
+    pub series: &'a BTreeMap<String, Vec<SeriesPoint>>,
+    pub units: UnitsMetadataCamel,
+    pub page: PageMetadataCamel,
+    pub next_cursor: Option<&'a str>,
+}
+
+impl CamelCaseMirror for SeriesReadingsResponse {
+    type Camel<'a> = SeriesReadingsResponseCamel<'a>;
+
+    fn camel(&self) -> SeriesReadingsResponseCamel<'_> {
+        SeriesReadingsResponseCamel {
+            series: &self.series,
+            units: self.units.camel(),
+            page: self.page.camel(),
+            next_cursor: self.next_cursor.as_deref(),
+        }
+    }
+}
+
+/// Shape of the readings query response body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadingsFormat {
+    /// One record per reading (default)
+    #[default]
+    Records,
+    /// One array of `{t, v}` points per sensor, for charting
+    Series,
+}
+
+/// Response payload for readings query with `since_ingest`
+#[derive(Debug, Serialize)]
+pub struct IngestTimeReadingsResponse {
+    /// Readings in ascending ingest order
+    pub readings: Vec<ReadingResponseItem>,
+    /// Units of the sensor values in `readings`
+    pub units: UnitsMetadata,
+    /// Ingest time of the last reading returned; pass as `since_ingest` on the next poll
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_ingest_time_ms: Option<i64>,
+    /// Optional cursor for pagination
+    pub next_cursor: Option<String>,
+}
+
+/// `IngestTimeReadingsResponse` with camelCase field names, for `?case=camel`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngestTimeReadingsResponseCamel<'a> {
+    pub readings: Vec<ReadingResponseItemCamel<'a>>,
+    pub units: UnitsMetadataCamel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_ingest_time_ms: Option<i64>,
+    pub next_cursor: Option<&'a str>,
+}
+
+impl CamelCaseMirror for IngestTimeReadingsResponse {
+    type Camel<'a> = IngestTimeReadingsResponseCamel<'a>;
+
+    fn camel(&self) -> IngestTimeReadingsResponseCamel<'_> {
+        IngestTimeReadingsResponseCamel {
+            readings: self
+                .readings
+                .iter()
+                .map(ReadingResponseItem::camel)
+                .collect(),
+            units: self.units.camel(),
+            last_ingest_time_ms: self.last_ingest_time_ms,
+            next_cursor: self.next_cursor.as_deref(),
+        }
+    }
+}
+
+/// Response payload for readings query with `count_only=true`
+#[derive(Debug, Serialize)]
+pub struct CountReadingsResponse {
+    /// Number of readings in the time range
+    pub count: i64,
+}
+
 /// Handler for GET /devices/{hardware_id}/readings endpoint
 ///
 /// Queries sensor readings for a device within a time range with pagination.
 ///
 /// # Query Parameters
-/// * `from` - Start of time range (epoch milliseconds, inclusive)
-/// * `to` - End of time range (epoch milliseconds, inclusive)
+/// * `from` - Start of time range (epoch milliseconds or RFC3339, inclusive)
+/// * `to` - End of time range (epoch milliseconds or RFC3339, inclusive)
 /// * `limit` - Maximum number of readings to return (default 50, max 1000)
 /// * `cursor` - Optional pagination cursor from previous response
+/// * `order` - `desc` (default, newest first) or `asc` (oldest first); keep
+///   the same order when following `next_cursor`
+/// * `units` - Optional unit system for sensor values (`metric` default, or `imperial`)
+/// * `count_only` - When `true`, return only `{ "count": N }` for the range
+///   (cannot be combined with `cursor`)
+/// * `format` - `records` (default) or `series`, which returns one array of
+///   `{t, v}` points per sensor instead of one record per reading (cannot be
+///   combined with `count_only`)
+/// * `since_ingest` - Return readings ingested at or after this epoch-ms time,
+///   in ascending ingest order, instead of a `from`/`to` range (cannot be
+///   combined with `from`, `to`, `order` or `count_only`)
+/// * `unbounded` - When `true`, skip the `MAX_QUERY_RANGE_DAYS` limit on
+///   the `from`/`to` span (a guard against accidental scans, not an access
+///   control)
 ///
 /// # Returns
-/// * HTTP 200 with readings list and optional next_cursor
+/// * HTTP 200 with readings list and optional next_cursor, or the count
 /// * HTTP 401 if Bearer token is invalid
-/// * HTTP 400 if query parameters are invalid
+/// * HTTP 400 if query parameters are invalid (including unknown `units` or
+///   `format`, or a range wider than `MAX_QUERY_RANGE_DAYS`)
 /// * HTTP 404 if device doesn't exist
 pub async fn query_readings(
     event: Request,
@@ -68,32 +302,60 @@ pub async fn query_readings(
     // Parse query parameters
     let query_params = event.query_string_parameters();
 
+    let since_ingest: Option<i64> = parse_optional(&query_params, "since_ingest")?;
+    if let Some(since_ingest_ms) = since_ingest {
+        return query_readings_since_ingest(
+            &event,
+            config,
+            hardware_id,
+            since_ingest_ms,
+            &request_id,
+        )
+        .await;
+    }
+
     // Parse from timestamp (required)
-    let from_ms: i64 = query_params
-        .first("from")
-        .ok_or_else(|| crate::error::ValidationError::MissingField(String::from("from")))?
-        .parse()
-        .map_err(|_| crate::error::ValidationError::InvalidFormat(String::from("from")))?;
+    let from_ms = parse_required_timestamp(&query_params, "from")?;
 
     // Parse to timestamp (required)
-    let to_ms: i64 = query_params
-        .first("to")
-        .ok_or_else(|| crate::error::ValidationError::MissingField(String::from("to")))?
-        .parse()
-        .map_err(|_| crate::error::ValidationError::InvalidFormat(String::from("to")))?;
+    let to_ms = parse_required_timestamp(&query_params, "to")?;
+
+    // The range limit is advisory, not an access control: every caller here
+    // holds the admin token, which can already read any device's full history,
+    // so it only guards against accidental multi-year scans
+    let unbounded = parse_optional::<bool>(&query_params, "unbounded")?.unwrap_or(false);
+    check_readings_range(from_ms, to_ms, config.max_query_range_ms(), unbounded)?;
+
+    // Parse limit (optional, default 50, max 1000 unless overridden in config)
+    let limit = parse_clamped_limit(
+        &query_params,
+        config.readings_page_default,
+        config.readings_page_max,
+    );
+
+    let cursor = parse_optional_string(&query_params, "cursor");
 
-    // Parse limit (optional, default 50, max 1000)
-    let limit: Option<i32> = query_params.first("limit").and_then(|s| s.parse().ok());
+    let order = parse_sort_order(&query_params)?;
 
-    let cursor = query_params.first("cursor").map(|s| s.to_string());
+    let units = parse_unit_system(query_params.first("units"))?;
+
+    let count_only = parse_count_only(query_params.first("count_only"), cursor.is_some())?;
+
+    let format = parse_readings_format(query_params.first("format"), count_only)?;
+
+    let case = crate::response::parse_key_case(query_params.first(crate::response::CASE_PARAM))?;
 
     info!(
         request_id = %request_id,
         hardware_id = %hardware_id,
         from_ms = from_ms,
         to_ms = to_ms,
-        limit = ?limit,
+        limit = limit,
         has_cursor = cursor.is_some(),
+        order = ?order,
+        units = ?units,
+        count_only = count_only,
+        format = ?format,
         "Parsed query parameters"
     );
 
@@ -116,6 +378,36 @@ pub async fn query_readings(
         ));
     }
 
+    if count_only {
+        let count = crate::repo::readings::count_readings(
+            &config.dynamodb_client,
+            &config.device_readings_table,
+            hardware_id,
+            from_ms,
+            to_ms,
+        )
+        .await?;
+
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            count = count,
+            "Returning readings count"
+        );
+
+        let response_body =
+            serde_json::to_string(&CountReadingsResponse { count }).map_err(|e| {
+                error!(request_id = %request_id, error = %e, "Failed to serialize response");
+                ApiError::Internal(format!("Failed to serialize response: {}", e))
+            })?;
+
+        return Ok(Response::builder()
+            .status(200)
+            .header("content-type", "application/json")
+            .body(Body::from(response_body))
+            .unwrap());
+    }
+
     info!(
         request_id = %request_id,
         hardware_id = %hardware_id,
@@ -129,8 +421,9 @@ pub async fn query_readings(
         hardware_id,
         from_ms,
         to_ms,
-        limit,
+        Some(limit),
         cursor,
+        order,
     )
     .await?;
 
@@ -142,28 +435,34 @@ pub async fn query_readings(
         "Retrieved readings from DynamoDB"
     );
 
-    // Convert to response items
-    let reading_items: Vec<ReadingResponseItem> = result
-        .readings
-        .into_iter()
-        .map(|reading| ReadingResponseItem {
-            timestamp_ms: reading.timestamp_ms,
-            batch_id: reading.batch_id,
-            boot_id: reading.boot_id,
-            firmware_version: reading.firmware_version,
-            friendly_name: reading.friendly_name,
-            sensors: reading.sensors,
-            sensor_status: reading.sensor_status,
-        })
-        .collect();
-
-    // Build response
-    let response = QueryReadingsResponse {
-        readings: reading_items,
-        next_cursor: result.next_cursor,
-    };
-
-    let response_body = serde_json::to_string(&response).map_err(|e| {
+    let page = PageMetadata::new(limit, result.readings.len(), result.next_cursor.as_ref());
+
+    // Build response in the requested format
+    let response_body = match format {
+        ReadingsFormat::Records => crate::response::to_json_string(
+            &QueryReadingsResponse {
+                readings: result
+                    .readings
+                    .into_iter()
+                    .map(|reading| to_unit_response_item(reading, units))
+                    .collect(),
+                units: UnitsMetadata::for_system(units),
+                page,
+                next_cursor: result.next_cursor,
+            },
+            case,
+        ),
+        ReadingsFormat::Series => crate::response::to_json_string(
+            &SeriesReadingsResponse {
+                series: transpose_to_series(&result.readings, units),
+                units: UnitsMetadata::for_system(units),
+                page,
+                next_cursor: result.next_cursor,
+            },
+            case,
+        ),
+    }
+    .map_err(|e| {
         error!(request_id = %request_id, error = %e, "Failed to serialize response");
         ApiError::Internal(format!("Failed to serialize response: {}", e))
     })?;
@@ -181,11 +480,253 @@ pub async fn query_readings(
         .unwrap())
 }
 
+/// Serve GET /devices/{hardware_id}/readings?since_ingest= from the ingest-time index
+///
+/// The bearer token has already been validated by `query_readings`.
+async fn query_readings_since_ingest(
+    event: &Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+    since_ingest_ms: i64,
+    request_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let query_params = event.query_string_parameters();
+
+    // since_ingest replaces the event-time range and its ordering
+    if ["from", "to", "order", "count_only", "format"]
+        .iter()
+        .any(|name| query_params.first(name).is_some())
+    {
+        return Err(
+            crate::error::ValidationError::InvalidValue(String::from("since_ingest")).into(),
+        );
+    }
+
+    let limit = parse_clamped_limit(
+        &query_params,
+        config.readings_page_default,
+        config.readings_page_max,
+    );
+    let cursor = parse_optional_string(&query_params, "cursor");
+    let units = parse_unit_system(query_params.first("units"))?;
+    let case = crate::response::parse_key_case(query_params.first(crate::response::CASE_PARAM))?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        since_ingest_ms = since_ingest_ms,
+        limit = limit,
+        has_cursor = cursor.is_some(),
+        "Querying readings by ingest time"
+    );
+
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let result = crate::repo::readings::query_by_ingest_time(
+        &config.dynamodb_client,
+        &config.device_readings_table,
+        hardware_id,
+        since_ingest_ms,
+        limit,
+        cursor,
+    )
+    .await?;
+
+    let response = IngestTimeReadingsResponse {
+        readings: result
+            .readings
+            .into_iter()
+            .map(|reading| to_unit_response_item(reading, units))
+            .collect(),
+        units: UnitsMetadata::for_system(units),
+        last_ingest_time_ms: result.last_ingest_time_ms,
+        next_cursor: result.next_cursor,
+    };
+
+    let response_body = crate::response::to_json_string(&response, case).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+/// Convert a stored reading to a response item in the requested units
+pub(crate) fn to_unit_response_item(
+    reading: esp32_backend::shared::domain::Reading,
+    units: UnitSystem,
+) -> ReadingResponseItem {
+    ReadingResponseItem {
+        timestamp_ms: reading.timestamp_ms,
+        batch_id: reading.batch_id,
+        boot_id: reading.boot_id,
+        firmware_version: reading.firmware_version,
+        friendly_name: reading.friendly_name,
+        sensors: convert_sensor_values(&reading.sensors, units),
+        status_summary: reading.sensor_status.summary(),
+        sensor_status: reading.sensor_status,
+        was_buffered: reading.was_buffered,
+        quality: reading.quality,
+    }
+}
+
+/// Parse the optional `units` query parameter (defaults to metric)
+fn parse_unit_system(value: Option<&str>) -> Result<UnitSystem, ApiError> {
+    match value {
+        None => Ok(UnitSystem::default()),
+        Some(s) => UnitSystem::parse(s).ok_or_else(|| {
+            crate::error::ValidationError::InvalidValue(String::from("units")).into()
+        }),
+    }
+}
+
+/// Transpose readings into one series of `{t, v}` points per sensor key
+///
+/// A point is left out when the reading has no value for the sensor or the
+/// sensor's status isn't ok. BME280 status covers temperature, humidity and
+/// pressure; an extra sensor (e.g. `sht31_temp_c`) uses the status named by
+/// the part of its key before the first `_` (e.g. `sht31`), and is kept when
+/// the reading reports no such status.
+pub(crate) fn transpose_to_series(
+    readings: &[Reading],
+    units: UnitSystem,
+) -> BTreeMap<String, Vec<SeriesPoint>> {
+    let mut series: BTreeMap<String, Vec<SeriesPoint>> = BTreeMap::new();
+
+    for reading in readings {
+        let status = &reading.sensor_status;
+        let is_ok = |value: &str| value == SENSOR_STATUS_OK;
+        let values = convert_sensor_values(&reading.sensors, units);
+
+        let typed = [
+            ("bme280_temp_c", values.bme280_temp_c, is_ok(&status.bme280)),
+            ("bme280_temp_f", values.bme280_temp_f, is_ok(&status.bme280)),
+            (
+                "ds18b20_temp_c",
+                values.ds18b20_temp_c,
+                is_ok(&status.ds18b20),
+            ),
+            (
+                "ds18b20_temp_f",
+                values.ds18b20_temp_f,
+                is_ok(&status.ds18b20),
+            ),
+            ("humidity_pct", values.humidity_pct, is_ok(&status.bme280)),
+            ("pressure_hpa", values.pressure_hpa, is_ok(&status.bme280)),
+            ("pressure_inhg", values.pressure_inhg, is_ok(&status.bme280)),
+            (
+                "soil_moisture_pct",
+                values.soil_moisture_pct,
+                is_ok(&status.soil_moisture),
+            ),
+        ];
+        for (key, value, ok) in typed {
+            if let Some(v) = value.filter(|_| ok) {
+                series
+                    .entry(key.to_string())
+                    .or_default()
+                    .push(SeriesPoint {
+                        t: reading.timestamp_ms,
+                        v,
+                    });
+            }
+        }
+
+        for (key, v) in values.extra_sensors {
+            let sensor = key.split('_').next().unwrap_or(&key);
+            if status.extra_status.get(sensor).is_none_or(|s| is_ok(s)) {
+                series.entry(key).or_default().push(SeriesPoint {
+                    t: reading.timestamp_ms,
+                    v,
+                });
+            }
+        }
+    }
+
+    series
+}
+
+/// Parse the optional `format` query parameter (defaults to records)
+///
+/// A count has no readings to shape, so `series` cannot be combined with
+/// `count_only`.
+fn parse_readings_format(
+    value: Option<&str>,
+    count_only: bool,
+) -> Result<ReadingsFormat, ApiError> {
+    let format = match value {
+        None | Some("records") => ReadingsFormat::Records,
+        Some("series") => ReadingsFormat::Series,
+        Some(_) => {
+            return Err(crate::error::ValidationError::InvalidValue(String::from("format")).into())
+        }
+    };
+
+    if format == ReadingsFormat::Series && count_only {
+        return Err(crate::error::ValidationError::InvalidValue(String::from("format")).into());
+    }
+
+    Ok(format)
+}
+
+/// Reject a `from`/`to` span wider than `max_range_ms` unless `unbounded`
+fn check_readings_range(
+    from_ms: i64,
+    to_ms: i64,
+    max_range_ms: i64,
+    unbounded: bool,
+) -> Result<(), ApiError> {
+    if !unbounded && to_ms.saturating_sub(from_ms) > max_range_ms {
+        return Err(
+            crate::error::ValidationError::InvalidFormat(String::from("range too large")).into(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse the optional `count_only` query parameter (defaults to false)
+///
+/// A count covers the whole range, so it cannot be combined with `cursor`.
+fn parse_count_only(value: Option<&str>, has_cursor: bool) -> Result<bool, ApiError> {
+    let count_only = match value {
+        None | Some("false") => false,
+        Some("true") => true,
+        Some(_) => {
+            return Err(
+                crate::error::ValidationError::InvalidValue(String::from("count_only")).into(),
+            )
+        }
+    };
+
+    if count_only && has_cursor {
+        return Err(crate::error::ValidationError::InvalidValue(String::from("cursor")).into());
+    }
+
+    Ok(count_only)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use lambda_http::http::Method;
     use lambda_http::Context;
+    use std::collections::HashMap;
 
     fn create_test_request(method: Method, uri: &str, auth_header: Option<&str>) -> Request {
         let mut builder = lambda_http::http::Request::builder()
@@ -217,12 +758,18 @@ mod tests {
                 humidity_pct: Some(45.2),
                 pressure_hpa: Some(1013.25),
                 soil_moisture_pct: Some(62.3),
-            },
+                extra_sensors: HashMap::new(),
+            }
+            .into(),
             sensor_status: SensorStatus {
                 bme280: String::from("ok"),
                 ds18b20: String::from("ok"),
                 soil_moisture: String::from("ok"),
+                extra_status: HashMap::new(),
             },
+            status_summary: SensorStatusSummary::Ok,
+            was_buffered: false,
+            quality: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -233,6 +780,7 @@ mod tests {
         assert!(json.contains("firmware_version"));
         assert!(json.contains("sensors"));
         assert!(json.contains("sensor_status"));
+        assert!(json.contains(r#""status_summary":"ok""#));
 
         // Verify hardware_id is NOT in the response (it's in the URL path)
         assert!(!json.contains("hardware_id"));
@@ -255,13 +803,21 @@ mod tests {
                     humidity_pct: Some(45.2),
                     pressure_hpa: Some(1013.25),
                     soil_moisture_pct: None,
-                },
+                    extra_sensors: HashMap::new(),
+                }
+                .into(),
                 sensor_status: SensorStatus {
                     bme280: String::from("ok"),
                     ds18b20: String::from("error"),
                     soil_moisture: String::from("ok"),
+                    extra_status: HashMap::new(),
                 },
+                status_summary: SensorStatusSummary::Degraded,
+                was_buffered: false,
+                quality: None,
             }],
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            page: PageMetadata::new(1, 1, Some(&String::from("base64cursor"))),
             next_cursor: Some(String::from("base64cursor")),
         };
 
@@ -270,12 +826,98 @@ mod tests {
         assert!(json.contains("batch_1"));
         assert!(json.contains("next_cursor"));
         assert!(json.contains("base64cursor"));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["page"],
+            serde_json::json!({"limit": 1, "returned": 1, "has_more": true})
+        );
+    }
+
+    #[test]
+    fn test_query_readings_response_camel_case() {
+        use esp32_backend::shared::domain::{SensorStatus, SensorValues};
+
+        let response = QueryReadingsResponse {
+            readings: vec![ReadingResponseItem {
+                timestamp_ms: 1704067800000,
+                batch_id: String::from("batch_1"),
+                boot_id: String::from("7c9e6679-7425-40de-944b-e07fc1f90ae7"),
+                firmware_version: String::from("1.0.16"),
+                friendly_name: None,
+                sensors: SensorValues {
+                    bme280_temp_c: Some(22.5),
+                    ds18b20_temp_c: None,
+                    humidity_pct: None,
+                    pressure_hpa: None,
+                    soil_moisture_pct: None,
+                    extra_sensors: HashMap::from([(String::from("sht31_temp_c"), 21.9)]),
+                }
+                .into(),
+                sensor_status: SensorStatus {
+                    bme280: String::from("ok"),
+                    ds18b20: String::from("ok"),
+                    soil_moisture: String::from("ok"),
+                    extra_status: HashMap::new(),
+                },
+                status_summary: SensorStatusSummary::Ok,
+                was_buffered: true,
+                quality: None,
+            }],
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            page: PageMetadata::new(1, 1, None),
+            next_cursor: None,
+        };
+
+        let json = crate::response::to_json_string(&response, KeyCase::Camel).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let reading = &value["readings"][0];
+        assert_eq!(reading["timestampMs"], 1704067800000_i64);
+        assert_eq!(reading["wasBuffered"], true);
+        assert_eq!(reading["statusSummary"], "ok");
+        assert!(reading.get("timestamp_ms").is_none());
+        // Sensor names are data and keep their case
+        assert_eq!(reading["sensors"]["bme280_temp_c"], 22.5);
+        assert_eq!(reading["sensors"]["sht31_temp_c"], 21.9);
+        assert_eq!(reading["sensorStatus"]["soil_moisture"], "ok");
+        assert_eq!(value["units"]["soilMoisture"], "%");
+        assert_eq!(value["page"]["hasMore"], false);
+        assert!(value.get("nextCursor").is_some());
+
+        // snake_case stays the default
+        let json = crate::response::to_json_string(&response, KeyCase::default()).unwrap();
+        assert!(json.contains(r#""timestamp_ms":1704067800000"#));
+    }
+
+    #[test]
+    fn test_series_readings_response_camel_case_keeps_sensor_keys() {
+        let response = SeriesReadingsResponse {
+            series: BTreeMap::from([(
+                String::from("soil_moisture_pct"),
+                vec![SeriesPoint {
+                    t: 1704067800000,
+                    v: 41.0,
+                }],
+            )]),
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            page: PageMetadata::new(1, 1, None),
+            next_cursor: None,
+        };
+
+        let json = crate::response::to_json_string(&response, KeyCase::Camel).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["series"]["soil_moisture_pct"][0]["v"], 41.0);
+        assert_eq!(value["page"]["hasMore"], false);
     }
 
     #[tokio::test]
     async fn test_query_readings_response_no_cursor() {
         let response = QueryReadingsResponse {
             readings: vec![],
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            page: PageMetadata::new(50, 0, None),
             next_cursor: None,
         };
 
@@ -283,6 +925,36 @@ mod tests {
         assert!(json.contains("readings"));
         assert!(json.contains("next_cursor"));
         assert!(json.contains("null")); // next_cursor should be null
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["page"],
+            serde_json::json!({"limit": 50, "returned": 0, "has_more": false})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ingest_time_readings_response_serialization() {
+        let response = IngestTimeReadingsResponse {
+            readings: vec![],
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            last_ingest_time_ms: Some(1704067805000),
+            next_cursor: Some(String::from("ingestcursor")),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"last_ingest_time_ms\":1704067805000"));
+        assert!(json.contains("\"next_cursor\":\"ingestcursor\""));
+
+        // Nothing new since the last poll
+        let empty = IngestTimeReadingsResponse {
+            readings: vec![],
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            last_ingest_time_ms: None,
+            next_cursor: None,
+        };
+        let json = serde_json::to_string(&empty).unwrap();
+        assert!(!json.contains("last_ingest_time_ms"));
     }
 
     #[tokio::test]
@@ -378,12 +1050,18 @@ mod tests {
                 humidity_pct: None,
                 pressure_hpa: None,
                 soil_moisture_pct: None,
-            },
+                extra_sensors: HashMap::new(),
+            }
+            .into(),
             sensor_status: SensorStatus {
                 bme280: String::from("ok"),
                 ds18b20: String::from("error"),
                 soil_moisture: String::from("ok"),
+                extra_status: HashMap::new(),
             },
+            status_summary: SensorStatusSummary::Degraded,
+            was_buffered: false,
+            quality: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -409,6 +1087,207 @@ mod tests {
         assert_eq!(padded, "0000000001000#");
     }
 
+    #[test]
+    fn test_parse_unit_system() {
+        assert_eq!(parse_unit_system(None).unwrap(), UnitSystem::Metric);
+        assert_eq!(
+            parse_unit_system(Some("imperial")).unwrap(),
+            UnitSystem::Imperial
+        );
+
+        let err = parse_unit_system(Some("kelvin")).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field)) if field == "units"
+        ));
+        assert_eq!(err.to_http_response("test-request-id").status(), 400);
+    }
+
+    #[test]
+    fn test_parse_count_only() {
+        assert!(!parse_count_only(None, false).unwrap());
+        assert!(!parse_count_only(Some("false"), true).unwrap());
+        assert!(parse_count_only(Some("true"), false).unwrap());
+
+        assert!(matches!(
+            parse_count_only(Some("yes"), false),
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field))) if field == "count_only"
+        ));
+        assert!(matches!(
+            parse_count_only(Some("true"), true),
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field))) if field == "cursor"
+        ));
+    }
+
+    #[test]
+    fn test_check_readings_range() {
+        const DAY_MS: i64 = 86_400_000;
+        let max_range_ms = 31 * DAY_MS;
+        let from_ms = 1704067200000;
+
+        // In range, including exactly the limit
+        assert!(check_readings_range(from_ms, from_ms + DAY_MS, max_range_ms, false).is_ok());
+        assert!(check_readings_range(from_ms, from_ms + max_range_ms, max_range_ms, false).is_ok());
+
+        // Over range
+        assert!(matches!(
+            check_readings_range(0, from_ms, max_range_ms, false),
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidFormat(ref msg))) if msg == "range too large"
+        ));
+
+        // unbounded=true skips the limit
+        assert!(check_readings_range(0, from_ms, max_range_ms, true).is_ok());
+    }
+
+    fn series_reading(
+        timestamp_ms: i64,
+        bme280_temp_c: Option<f64>,
+        bme280_status: &str,
+    ) -> Reading {
+        use esp32_backend::shared::domain::{SensorStatus, SensorValues};
+
+        Reading {
+            hardware_id: String::from("AA:BB:CC:DD:EE:FF"),
+            timestamp_ms,
+            batch_id: String::from("batch_1"),
+            boot_id: String::from("7c9e6679-7425-40de-944b-e07fc1f90ae7"),
+            firmware_version: String::from("1.0.16"),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c,
+                ds18b20_temp_c: None,
+                humidity_pct: Some(45.0),
+                pressure_hpa: None,
+                soil_moisture_pct: Some(40.0),
+                extra_sensors: HashMap::from([(String::from("sht31_temp_c"), 21.0)]),
+            },
+            sensor_status: SensorStatus {
+                bme280: String::from(bme280_status),
+                ds18b20: String::from("error"),
+                soil_moisture: String::from("ok"),
+                extra_status: HashMap::from([(String::from("sht31"), String::from("ok"))]),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_transpose_to_series_drops_absent_and_non_ok_points() {
+        let mut failed_extra = series_reading(3000, Some(23.0), "ok");
+        failed_extra
+            .sensor_status
+            .extra_status
+            .insert(String::from("sht31"), String::from("error"));
+        let readings = vec![
+            series_reading(1000, Some(22.0), "ok"),
+            series_reading(2000, Some(99.0), "error"),
+            failed_extra,
+            series_reading(4000, None, "ok"),
+        ];
+
+        let series = transpose_to_series(&readings, UnitSystem::Metric);
+
+        // ds18b20 reported an error and pressure was never read
+        assert!(!series.contains_key("ds18b20_temp_c"));
+        assert!(!series.contains_key("pressure_hpa"));
+        assert_eq!(
+            series["bme280_temp_c"],
+            vec![
+                SeriesPoint { t: 1000, v: 22.0 },
+                SeriesPoint { t: 3000, v: 23.0 }
+            ]
+        );
+        assert_eq!(
+            series["sht31_temp_c"]
+                .iter()
+                .map(|p| p.t)
+                .collect::<Vec<_>>(),
+            vec![1000, 2000, 4000]
+        );
+    }
+
+    #[test]
+    fn test_transpose_to_series_preserves_timestamps_per_series() {
+        let readings = vec![
+            series_reading(1000, Some(20.0), "ok"),
+            series_reading(2000, Some(25.0), "error"),
+            series_reading(3000, Some(30.0), "ok"),
+        ];
+
+        let series = transpose_to_series(&readings, UnitSystem::Imperial);
+
+        assert!(!series.contains_key("bme280_temp_c"));
+        assert_eq!(
+            series["bme280_temp_f"],
+            vec![
+                SeriesPoint { t: 1000, v: 68.0 },
+                SeriesPoint { t: 3000, v: 86.0 }
+            ]
+        );
+        assert_eq!(
+            series["humidity_pct"]
+                .iter()
+                .map(|p| p.t)
+                .collect::<Vec<_>>(),
+            vec![1000, 3000]
+        );
+        assert_eq!(
+            series["soil_moisture_pct"]
+                .iter()
+                .map(|p| p.t)
+                .collect::<Vec<_>>(),
+            vec![1000, 2000, 3000]
+        );
+    }
+
+    #[test]
+    fn test_parse_readings_format() {
+        assert_eq!(
+            parse_readings_format(None, false).unwrap(),
+            ReadingsFormat::Records
+        );
+        assert_eq!(
+            parse_readings_format(Some("records"), true).unwrap(),
+            ReadingsFormat::Records
+        );
+        assert_eq!(
+            parse_readings_format(Some("series"), false).unwrap(),
+            ReadingsFormat::Series
+        );
+
+        assert!(matches!(
+            parse_readings_format(Some("csv"), false),
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field))) if field == "format"
+        ));
+        assert!(matches!(
+            parse_readings_format(Some("series"), true),
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field))) if field == "format"
+        ));
+    }
+
+    #[test]
+    fn test_count_readings_response_serialization() {
+        let json = serde_json::to_string(&CountReadingsResponse { count: 2500 }).unwrap();
+        assert_eq!(json, r#"{"count":2500}"#);
+    }
+
+    #[test]
+    fn test_query_readings_response_units_metadata() {
+        let response = QueryReadingsResponse {
+            readings: vec![],
+            units: UnitsMetadata::for_system(UnitSystem::Imperial),
+            page: PageMetadata::new(50, 0, None),
+            next_cursor: None,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"temperature\":\"F\""));
+        assert!(json.contains("\"pressure\":\"inHg\""));
+    }
+
     #[tokio::test]
     async fn test_latest_reading_response_serialization() {
         use esp32_backend::shared::domain::{SensorStatus, SensorValues};
@@ -425,12 +1304,19 @@ mod tests {
                 humidity_pct: Some(45.2),
                 pressure_hpa: Some(1013.25),
                 soil_moisture_pct: Some(62.3),
-            },
+                extra_sensors: HashMap::new(),
+            }
+            .into(),
             sensor_status: SensorStatus {
                 bme280: String::from("ok"),
                 ds18b20: String::from("ok"),
                 soil_moisture: String::from("ok"),
+                extra_status: HashMap::new(),
             },
+            status_summary: SensorStatusSummary::Ok,
+            was_buffered: false,
+            quality: None,
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -463,12 +1349,19 @@ mod tests {
                 humidity_pct: None,
                 pressure_hpa: None,
                 soil_moisture_pct: None,
-            },
+                extra_sensors: HashMap::new(),
+            }
+            .into(),
             sensor_status: SensorStatus {
                 bme280: String::from("ok"),
                 ds18b20: String::from("error"),
                 soil_moisture: String::from("ok"),
+                extra_status: HashMap::new(),
             },
+            status_summary: SensorStatusSummary::Degraded,
+            was_buffered: false,
+            quality: None,
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -506,6 +1399,40 @@ mod tests {
         // Clean up
         std::env::remove_var("ADMIN_TOKEN");
     }
+
+    #[tokio::test]
+    async fn test_get_reading_missing_auth_header() {
+        // Set up environment
+        std::env::set_var("ADMIN_TOKEN", "test-token");
+
+        let config = ControlConfig::for_test(
+            "http://localhost:8000",
+            String::from("test-devices"),
+            String::from("test-api-keys"),
+            String::from("test-device-readings"),
+            String::from("test-admin-token"),
+            String::from("*"),
+        )
+        .await;
+
+        let request = create_test_request(
+            Method::GET,
+            "/devices/AA:BB:CC:DD:EE:FF/readings/batch_123?ts=1704067800000",
+            None,
+        );
+
+        let result = get_reading(request, &config, "AA:BB:CC:DD:EE:FF", "batch_123").await;
+
+        match result.unwrap_err() {
+            ApiError::Auth(crate::error::AuthError::MissingToken) => {
+                // Expected error
+            }
+            e => panic!("Expected MissingToken error, got: {:?}", e),
+        }
+
+        // Clean up
+        std::env::remove_var("ADMIN_TOKEN");
+    }
     // Note: Testing invalid token with environment variables is unreliable in parallel test execution
     // This is better tested in integration tests where environment is controlled
     // The auth module has comprehensive tests for token validation logic
@@ -623,6 +1550,8 @@ mod tests {
         // Test that QueryReadingsResponse serializes correctly with empty readings
         let response = QueryReadingsResponse {
             readings: vec![],
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            page: PageMetadata::new(50, 0, None),
             next_cursor: None,
         };
 
@@ -633,6 +1562,8 @@ mod tests {
         // Test with cursor
         let response_with_cursor = QueryReadingsResponse {
             readings: vec![],
+            units: UnitsMetadata::for_system(UnitSystem::Metric),
+            page: PageMetadata::new(50, 0, Some(&"cursor123".to_string())),
             next_cursor: Some("cursor123".to_string()),
         };
 
@@ -704,15 +1635,27 @@ pub struct LatestReadingResponse {
     /// Optional friendly name snapshot
     pub friendly_name: Option<String>,
     /// Sensor values
-    pub sensors: esp32_backend::shared::domain::SensorValues,
+    pub sensors: ResponseSensorValues,
     /// Sensor status
     pub sensor_status: esp32_backend::shared::domain::SensorStatus,
+    /// Overall sensor health derived from `sensor_status`
+    pub status_summary: SensorStatusSummary,
+    /// True if the reading was buffered on the device and delivered late
+    pub was_buffered: bool,
+    /// Device-reported quality score (0.0 to 1.0), if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
+    /// Units of the sensor values in `sensors`
+    pub units: UnitsMetadata,
 }
 
 /// Handler for GET /devices/{hardware_id}/latest endpoint
 ///
 /// Retrieves the most recent reading for a device.
 ///
+/// # Query Parameters
+/// * `units` - Optional unit system for sensor values (`metric` default, or `imperial`)
+///
 /// # Returns
 /// * HTTP 200 with the latest reading
 /// * HTTP 401 if Bearer token is invalid
@@ -734,6 +1677,8 @@ pub async fn get_latest_reading(
     // Validate Bearer token
     validate_bearer_token(&event)?;
 
+    let units = parse_unit_system(event.query_string_parameters().first("units"))?;
+
     // First, check if device exists
     let device = crate::repo::devices::get_device(
         &config.dynamodb_client,
@@ -783,8 +1728,12 @@ pub async fn get_latest_reading(
                 boot_id: reading.boot_id,
                 firmware_version: reading.firmware_version,
                 friendly_name: reading.friendly_name,
-                sensors: reading.sensors,
+                sensors: convert_sensor_values(&reading.sensors, units),
+                status_summary: reading.sensor_status.summary(),
                 sensor_status: reading.sensor_status,
+                was_buffered: reading.was_buffered,
+                quality: reading.quality,
+                units: UnitsMetadata::for_system(units),
             };
 
             let response_body = serde_json::to_string(&response).map_err(|e| {
@@ -814,3 +1763,116 @@ pub async fn get_latest_reading(
         }
     }
 }
+
+/// Response payload for a single reading lookup
+#[derive(Debug, Serialize)]
+pub struct ReadingDetailResponse {
+    #[serde(flatten)]
+    pub reading: ReadingResponseItem,
+    /// Units of the sensor values in `sensors`
+    pub units: UnitsMetadata,
+}
+
+/// Handler for GET /devices/{hardware_id}/readings/{batch_id} endpoint
+///
+/// Retrieves one reading by its batch_id and timestamp, which together with
+/// the optional device sequence make up the reading's sort key.
+///
+/// # Query Parameters
+/// * `ts` - Required reading timestamp (epoch ms or RFC3339)
+/// * `seq` - Optional device-assigned sequence, for readings sent with one
+/// * `units` - Optional unit system for sensor values (`metric` default, or `imperial`)
+///
+/// # Returns
+/// * HTTP 200 with the reading
+/// * HTTP 400 if `ts` is missing or `ts`/`seq` is malformed
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 with DEVICE_NOT_FOUND if device doesn't exist
+/// * HTTP 404 with READING_NOT_FOUND if no reading has that key
+pub async fn get_reading(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+    batch_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        batch_id = %batch_id,
+        "Processing get reading request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let query_params = event.query_string_parameters();
+    let timestamp_ms = parse_required_timestamp(&query_params, "ts")?;
+    let seq: Option<u32> = parse_optional(&query_params, "seq")?;
+    if seq.is_some_and(|seq| seq > MAX_READING_SEQ) {
+        return Err(crate::error::ValidationError::InvalidValue(String::from("seq")).into());
+    }
+    let units = parse_unit_system(query_params.first("units"))?;
+
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let reading = crate::repo::readings::get_reading(
+        &config.dynamodb_client,
+        &config.device_readings_table,
+        hardware_id,
+        timestamp_ms,
+        seq,
+        batch_id,
+    )
+    .await?
+    .ok_or_else(|| {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            batch_id = %batch_id,
+            timestamp_ms = timestamp_ms,
+            "Reading not found"
+        );
+        ApiError::NotFound(crate::error::NotFoundError::ReadingNotFound)
+    })?;
+
+    let response = ReadingDetailResponse {
+        reading: to_unit_response_item(reading, units),
+        units: UnitsMetadata::for_system(units),
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        batch_id = %batch_id,
+        "Returning successful reading response"
+    );
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
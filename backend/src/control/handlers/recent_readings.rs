@@ -0,0 +1,183 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use tracing::{error, info};
+
+use super::readings::{to_unit_response_item, ReadingResponseItem};
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use esp32_backend::shared::query_params::{
+    parse_clamped_limit, parse_optional_string, parse_optional_timestamp,
+};
+use esp32_backend::shared::time::{Clock, SystemClock};
+use esp32_backend::shared::units::UnitSystem;
+
+/// How far back `since` reaches when it is omitted (5 minutes)
+pub const DEFAULT_RECENT_WINDOW_MS: i64 = 5 * 60 * 1000;
+
+/// One reading in a fleet-wide listing, tagged with its device
+#[derive(Debug, Serialize)]
+pub struct RecentReadingItem {
+    pub hardware_id: String,
+    #[serde(flatten)]
+    pub reading: ReadingResponseItem,
+}
+
+/// Response payload for GET /admin/readings/recent
+#[derive(Debug, Serialize)]
+pub struct RecentReadingsResponse {
+    /// Readings from every device, in ascending ingest order
+    pub readings: Vec<RecentReadingItem>,
+    /// Ingest time of the last reading returned; pass as `since` on the next poll
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_ingest_time_ms: Option<i64>,
+    /// Optional cursor for pagination
+    pub next_cursor: Option<String>,
+}
+
+/// Start of the listing: `since` if given, otherwise `DEFAULT_RECENT_WINDOW_MS` before now
+fn resolve_since(since: Option<i64>, now_ms: i64) -> i64 {
+    since.unwrap_or(now_ms - DEFAULT_RECENT_WINDOW_MS)
+}
+
+/// Handler for GET /admin/readings/recent endpoint
+///
+/// Lists readings ingested across the whole fleet since a point in time, for
+/// support debugging (e.g. everything a firmware rollout sent in the last few
+/// minutes). Reads the `GlobalIngestTimeIndex`; sensor values are metric.
+///
+/// # Query Parameters
+/// * `since` - Earliest ingest time (epoch milliseconds or RFC3339, inclusive; default 5 minutes ago)
+/// * `limit` - Readings per page (same default and maximum as device readings)
+/// * `cursor` - Pagination cursor from a previous response
+///
+/// # Returns
+/// * HTTP 200 with readings in ascending ingest order
+/// * HTTP 400 if parameters or the cursor are invalid
+/// * HTTP 401 if Bearer token is invalid
+pub async fn list_recent_readings(
+    event: Request,
+    config: &ControlConfig,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(request_id = %request_id, "Processing recent readings request");
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let query_params = event.query_string_parameters();
+
+    let since_ingest_ms = resolve_since(
+        parse_optional_timestamp(&query_params, "since")?,
+        SystemClock::new().now_epoch_ms(),
+    );
+    let limit = parse_clamped_limit(
+        &query_params,
+        config.readings_page_default,
+        config.readings_page_max,
+    );
+    let cursor = parse_optional_string(&query_params, "cursor");
+
+    info!(
+        request_id = %request_id,
+        since_ingest_ms = since_ingest_ms,
+        limit = limit,
+        has_cursor = cursor.is_some(),
+        "Querying fleet-wide readings by ingest time"
+    );
+
+    let result = crate::repo::readings::query_recent_global(
+        &config.dynamodb_client,
+        &config.device_readings_table,
+        since_ingest_ms,
+        limit,
+        cursor,
+    )
+    .await?;
+
+    let response = RecentReadingsResponse {
+        readings: result
+            .readings
+            .into_iter()
+            .map(|reading| RecentReadingItem {
+                hardware_id: reading.hardware_id.clone(),
+                reading: to_unit_response_item(reading, UnitSystem::Metric),
+            })
+            .collect(),
+        last_ingest_time_ms: result.last_ingest_time_ms,
+        next_cursor: result.next_cursor,
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::domain::{Reading, SensorStatus, SensorValues};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_resolve_since() {
+        let now_ms = 1704067800000;
+        assert_eq!(resolve_since(None, now_ms), now_ms - 300_000);
+        assert_eq!(resolve_since(Some(1704060000000), now_ms), 1704060000000);
+    }
+
+    #[test]
+    fn test_recent_reading_item_carries_hardware_id() {
+        let reading = Reading {
+            batch_id: "batch-1".to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms: 1704067800000,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: Some(22.5),
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: None,
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: SensorStatus {
+                bme280: "ok".to_string(),
+                ds18b20: "missing".to_string(),
+                soil_moisture: "missing".to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        };
+
+        let response = RecentReadingsResponse {
+            readings: vec![RecentReadingItem {
+                hardware_id: reading.hardware_id.clone(),
+                reading: to_unit_response_item(reading, UnitSystem::Metric),
+            }],
+            last_ingest_time_ms: Some(1704067805000),
+            next_cursor: None,
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&response).unwrap();
+        let item = &json["readings"][0];
+        assert_eq!(item["hardware_id"], "AA:BB:CC:DD:EE:FF");
+        assert_eq!(item["batch_id"], "batch-1");
+        assert_eq!(item["sensors"]["bme280_temp_c"], 22.5);
+        assert_eq!(json["last_ingest_time_ms"], 1704067805000i64);
+        assert!(json["next_cursor"].is_null());
+    }
+}
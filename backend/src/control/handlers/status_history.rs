@@ -0,0 +1,134 @@
+use lambda_http::{Body, Request, RequestExt, Response};
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::auth::validate_bearer_token;
+use crate::config::ControlConfig;
+use crate::error::ApiError;
+use esp32_backend::shared::status_transition::StatusTransition;
+use esp32_backend::shared::validators::{
+    parse_and_clamp_limit, DEFAULT_PAGE_LIMIT, MAX_STATUS_HISTORY_PAGE_LIMIT,
+};
+
+/// Response payload for status history query
+#[derive(Debug, Serialize)]
+pub struct StatusHistoryResponse {
+    pub hardware_id: String,
+    /// Health category transitions, newest first
+    pub transitions: Vec<StatusTransition>,
+}
+
+/// Handler for GET /devices/{hardware_id}/status/history endpoint
+///
+/// Returns the device's recorded health category transitions. Transitions are
+/// appended by the scheduled status evaluator whenever a device's health
+/// category changes.
+///
+/// # Query Parameters
+/// * `limit` - Maximum number of transitions to return (default 50, max 100)
+///
+/// # Returns
+/// * HTTP 200 with transitions (newest first)
+/// * HTTP 401 if Bearer token is invalid
+/// * HTTP 404 if device doesn't exist
+pub async fn get_status_history(
+    event: Request,
+    config: &ControlConfig,
+    hardware_id: &str,
+) -> Result<Response<Body>, ApiError> {
+    let request_id = event.lambda_context().request_id.clone();
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        "Processing status history request"
+    );
+
+    // Validate Bearer token
+    validate_bearer_token(&event)?;
+
+    let limit = parse_and_clamp_limit(
+        event.query_string_parameters().first("limit"),
+        DEFAULT_PAGE_LIMIT,
+        MAX_STATUS_HISTORY_PAGE_LIMIT,
+    );
+
+    // First, check if device exists
+    let device = crate::repo::devices::get_device(
+        &config.dynamodb_client,
+        &config.devices_table,
+        hardware_id,
+    )
+    .await?;
+
+    if device.is_none() {
+        info!(
+            request_id = %request_id,
+            hardware_id = %hardware_id,
+            "Device not found"
+        );
+        return Err(ApiError::NotFound(
+            crate::error::NotFoundError::DeviceNotFound,
+        ));
+    }
+
+    let transitions = crate::repo::status_transitions::list_transitions(
+        &config.dynamodb_client,
+        &config.status_transitions_table,
+        hardware_id,
+        limit,
+    )
+    .await?;
+
+    info!(
+        request_id = %request_id,
+        hardware_id = %hardware_id,
+        count = transitions.len(),
+        "Retrieved status transitions"
+    );
+
+    let response = StatusHistoryResponse {
+        hardware_id: hardware_id.to_string(),
+        transitions,
+    };
+
+    let response_body = serde_json::to_string(&response).map_err(|e| {
+        error!(request_id = %request_id, error = %e, "Failed to serialize response");
+        ApiError::Internal(format!("Failed to serialize response: {}", e))
+    })?;
+
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::from(response_body))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::plant_insights::HealthCategory;
+
+    #[test]
+    fn test_status_history_response_serialization() {
+        let response = StatusHistoryResponse {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            transitions: vec![
+                StatusTransition {
+                    from: Some(HealthCategory::Healthy),
+                    to: HealthCategory::Missing,
+                    at_ms: 1704096000000,
+                },
+                StatusTransition {
+                    from: None,
+                    to: HealthCategory::Healthy,
+                    at_ms: 1704067200000,
+                },
+            ],
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains(r#""from":"healthy","to":"missing","at_ms":1704096000000"#));
+        assert!(json.contains(r#""from":null,"to":"healthy""#));
+    }
+}
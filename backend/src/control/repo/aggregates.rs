@@ -0,0 +1,201 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::shared::plant_insights::{Aggregate, SensorStats};
+
+/// Store an aggregate, replacing any existing item for the same window
+///
+/// Items are keyed by device_window (partition key) and window_start_ms
+/// (sort key), matching the items written by the insights aggregator.
+pub async fn put_aggregate(
+    client: &DynamoDbClient,
+    table_name: &str,
+    aggregate: &Aggregate,
+) -> Result<(), DatabaseError> {
+    client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(aggregate_to_item(aggregate)))
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    Ok(())
+}
+
+/// Convert an Aggregate to a DynamoDB item
+///
+/// Sensors without stats are left out of the item.
+fn aggregate_to_item(aggregate: &Aggregate) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert(
+        "device_window".to_string(),
+        AttributeValue::S(aggregate.device_window.clone()),
+    );
+    item.insert(
+        "window_start_ms".to_string(),
+        AttributeValue::N(aggregate.window_start_ms.to_string()),
+    );
+    item.insert(
+        "window_end_ms".to_string(),
+        AttributeValue::N(aggregate.window_end_ms.to_string()),
+    );
+    item.insert(
+        "window_type".to_string(),
+        AttributeValue::S(aggregate.window_type.as_str().to_string()),
+    );
+    item.insert(
+        "hardware_id".to_string(),
+        AttributeValue::S(aggregate.hardware_id.clone()),
+    );
+    item.insert(
+        "is_complete".to_string(),
+        AttributeValue::Bool(aggregate.is_complete),
+    );
+    item.insert(
+        "computed_at_ms".to_string(),
+        AttributeValue::N(aggregate.computed_at_ms.to_string()),
+    );
+
+    let sensors = [
+        ("temperature_stats", &aggregate.temperature_stats),
+        ("humidity_stats", &aggregate.humidity_stats),
+        ("pressure_stats", &aggregate.pressure_stats),
+        ("soil_moisture_stats", &aggregate.soil_moisture_stats),
+    ];
+    for (field, stats) in sensors {
+        if let Some(stats) = stats {
+            item.insert(field.to_string(), sensor_stats_to_attribute(stats));
+        }
+    }
+
+    if let Some(ttl) = aggregate.ttl {
+        item.insert("ttl".to_string(), AttributeValue::N(ttl.to_string()));
+    }
+
+    item
+}
+
+/// Convert SensorStats to the map stored per sensor
+///
+/// Counts and accumulators are always present, plus the quality-weight
+/// accumulators once values have been weighted; min/max/avg/stddev are only
+/// written when the window had a valid value, as the aggregator does.
+fn sensor_stats_to_attribute(stats: &SensorStats) -> AttributeValue {
+    let mut map = HashMap::from([
+        (
+            "valid_count".to_string(),
+            AttributeValue::N(stats.valid_count.to_string()),
+        ),
+        (
+            "total_count".to_string(),
+            AttributeValue::N(stats.total_count.to_string()),
+        ),
+        ("sum".to_string(), AttributeValue::N(stats.sum.to_string())),
+        (
+            "sumsq".to_string(),
+            AttributeValue::N(stats.sumsq.to_string()),
+        ),
+    ]);
+
+    if let Some(weight_sum) = stats.weight_sum {
+        map.insert(
+            "weight_sum".to_string(),
+            AttributeValue::N(weight_sum.to_string()),
+        );
+        map.insert(
+            "weighted_sum".to_string(),
+            AttributeValue::N(stats.weighted_sum.to_string()),
+        );
+    }
+
+    if stats.valid_count > 0 {
+        map.insert("min".to_string(), AttributeValue::N(stats.min.to_string()));
+        map.insert("max".to_string(), AttributeValue::N(stats.max.to_string()));
+        map.insert("avg".to_string(), AttributeValue::N(stats.avg.to_string()));
+        map.insert(
+            "stddev".to_string(),
+            AttributeValue::N(stats.stddev.to_string()),
+        );
+    }
+
+    AttributeValue::M(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::plant_insights::WindowType;
+
+    fn hourly_aggregate() -> Aggregate {
+        let mut temperature = SensorStats::default();
+        temperature.record(Some(20.0));
+        temperature.record(Some(22.0));
+
+        let mut soil_moisture = SensorStats::default();
+        soil_moisture.record(None);
+        soil_moisture.record(None);
+
+        Aggregate {
+            device_window: Aggregate::device_window_key("AA:BB:CC:DD:EE:FF", WindowType::Hourly),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            window_type: WindowType::Hourly,
+            window_start_ms: 1704067200000,
+            window_end_ms: 1704070800000,
+            temperature_stats: Some(temperature),
+            humidity_stats: None,
+            pressure_stats: None,
+            soil_moisture_stats: Some(soil_moisture),
+            computed_at_ms: 1704074400000,
+            is_complete: true,
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_to_item_keys_and_attributes() {
+        let item = aggregate_to_item(&hourly_aggregate());
+
+        assert_eq!(
+            item.get("device_window").unwrap().as_s().unwrap(),
+            "AA:BB:CC:DD:EE:FF#hourly"
+        );
+        assert_eq!(
+            item.get("window_start_ms").unwrap().as_n().unwrap(),
+            "1704067200000"
+        );
+        assert_eq!(item.get("window_type").unwrap().as_s().unwrap(), "hourly");
+        assert_eq!(item.get("is_complete"), Some(&AttributeValue::Bool(true)));
+        assert!(!item.contains_key("humidity_stats"));
+        assert!(!item.contains_key("ttl"));
+
+        let temperature = item.get("temperature_stats").unwrap().as_m().unwrap();
+        assert_eq!(temperature.get("valid_count").unwrap().as_n().unwrap(), "2");
+        assert_eq!(temperature.get("avg").unwrap().as_n().unwrap(), "21");
+        assert_eq!(temperature.get("sumsq").unwrap().as_n().unwrap(), "884");
+        assert_eq!(temperature.get("weight_sum").unwrap().as_n().unwrap(), "2");
+        assert_eq!(
+            temperature.get("weighted_sum").unwrap().as_n().unwrap(),
+            "42"
+        );
+    }
+
+    #[test]
+    fn test_sensor_stats_without_valid_values_omits_derived_fields() {
+        let item = aggregate_to_item(&hourly_aggregate());
+        let soil_moisture = item.get("soil_moisture_stats").unwrap().as_m().unwrap();
+
+        assert_eq!(
+            soil_moisture.get("valid_count").unwrap().as_n().unwrap(),
+            "0"
+        );
+        assert_eq!(
+            soil_moisture.get("total_count").unwrap().as_n().unwrap(),
+            "2"
+        );
+        assert!(!soil_moisture.contains_key("min"));
+        assert!(!soil_moisture.contains_key("stddev"));
+    }
+}
@@ -0,0 +1,159 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+
+/// Health-relevant fields of a device status item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceHealthFields {
+    pub last_seen_ingest_time_ms: Option<i64>,
+    pub last_error_at_ms: Option<i64>,
+}
+
+/// Clock and pipeline fields of a device status item, for GET /devices/{hardware_id}/status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceStatusFields {
+    pub health: DeviceHealthFields,
+    pub last_seen_event_time_ms: Option<i64>,
+    pub ingest_event_skew_seconds: Option<i64>,
+    /// Absent on items written before the skew threshold existed
+    pub clock_skew_warning: bool,
+    pub pipeline_lag_seconds: Option<i64>,
+    pub updated_at_ms: Option<i64>,
+}
+
+/// Get the health-relevant fields of a device's status item
+///
+/// # Returns
+/// * `Ok(Some(DeviceHealthFields))` - The device has a status item
+/// * `Ok(None)` - The status updater has not written one yet
+pub async fn get_device_health(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+) -> Result<Option<DeviceHealthFields>, DatabaseError> {
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .projection_expression("last_seen_ingest_time_ms, last_error_at_ms")
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    Ok(result.item.as_ref().map(item_to_health_fields))
+}
+
+/// Get the status fields shown by GET /devices/{hardware_id}/status
+///
+/// # Returns
+/// * `Ok(Some(DeviceStatusFields))` - The device has a status item
+/// * `Ok(None)` - The status updater has not written one yet
+pub async fn get_device_status(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+) -> Result<Option<DeviceStatusFields>, DatabaseError> {
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .projection_expression(
+            "last_seen_ingest_time_ms, last_error_at_ms, last_seen_event_time_ms, \
+             ingest_event_skew_seconds, clock_skew_warning, pipeline_lag_seconds, updated_at_ms",
+        )
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    Ok(result.item.as_ref().map(item_to_status_fields))
+}
+
+/// Read a numeric attribute as i64
+fn number(item: &HashMap<String, AttributeValue>, name: &str) -> Option<i64> {
+    item.get(name)
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+}
+
+/// Convert a device status item to its health-relevant fields
+fn item_to_health_fields(item: &HashMap<String, AttributeValue>) -> DeviceHealthFields {
+    DeviceHealthFields {
+        last_seen_ingest_time_ms: number(item, "last_seen_ingest_time_ms"),
+        last_error_at_ms: number(item, "last_error_at_ms"),
+    }
+}
+
+/// Convert a device status item to the fields shown by the status endpoint
+fn item_to_status_fields(item: &HashMap<String, AttributeValue>) -> DeviceStatusFields {
+    DeviceStatusFields {
+        health: item_to_health_fields(item),
+        last_seen_event_time_ms: number(item, "last_seen_event_time_ms"),
+        ingest_event_skew_seconds: number(item, "ingest_event_skew_seconds"),
+        clock_skew_warning: item
+            .get("clock_skew_warning")
+            .and_then(|v| v.as_bool().ok())
+            .copied()
+            .unwrap_or(false),
+        pipeline_lag_seconds: number(item, "pipeline_lag_seconds"),
+        updated_at_ms: number(item, "updated_at_ms"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_to_health_fields() {
+        let mut item = HashMap::new();
+        item.insert(
+            "last_seen_ingest_time_ms".to_string(),
+            AttributeValue::N("1704067200000".to_string()),
+        );
+        item.insert(
+            "last_error_at_ms".to_string(),
+            AttributeValue::N("1704060000000".to_string()),
+        );
+
+        assert_eq!(
+            item_to_health_fields(&item),
+            DeviceHealthFields {
+                last_seen_ingest_time_ms: Some(1704067200000),
+                last_error_at_ms: Some(1704060000000),
+            }
+        );
+    }
+
+    #[test]
+    fn test_item_to_status_fields() {
+        let mut item = HashMap::new();
+        item.insert(
+            "last_seen_ingest_time_ms".to_string(),
+            AttributeValue::N("1704067200000".to_string()),
+        );
+        item.insert(
+            "ingest_event_skew_seconds".to_string(),
+            AttributeValue::N("-600".to_string()),
+        );
+        item.insert("clock_skew_warning".to_string(), AttributeValue::Bool(true));
+
+        let fields = item_to_status_fields(&item);
+        assert_eq!(fields.health.last_seen_ingest_time_ms, Some(1704067200000));
+        assert_eq!(fields.ingest_event_skew_seconds, Some(-600));
+        assert!(fields.clock_skew_warning);
+        assert_eq!(fields.pipeline_lag_seconds, None);
+
+        // Items written before the warning existed read as no warning
+        item.remove("clock_skew_warning");
+        assert!(!item_to_status_fields(&item).clock_skew_warning);
+    }
+
+    #[test]
+    fn test_item_to_health_fields_missing_attributes() {
+        let fields = item_to_health_fields(&HashMap::new());
+        assert_eq!(fields.last_seen_ingest_time_ms, None);
+        assert_eq!(fields.last_error_at_ms, None);
+    }
+}
@@ -1,8 +1,10 @@
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
 use aws_sdk_dynamodb::types::AttributeValue;
 use aws_sdk_dynamodb::Client as DynamoDbClient;
 use std::collections::HashMap;
 
 use crate::error::DatabaseError;
+use crate::handlers::devices::DeviceListItem;
 use esp32_backend::shared::domain::{Capabilities, Device};
 
 /// Get a device by hardware_id from the devices table
@@ -41,6 +43,41 @@ pub async fn get_device(
     }
 }
 
+/// Attributes read for a device summary; capabilities are left out
+pub const DEVICE_SUMMARY_PROJECTION: &str =
+    "hardware_id, confirmation_id, friendly_name, firmware_version, first_registered_at, last_seen_at";
+
+/// Get the lightweight fields of a device by hardware_id
+///
+/// Uses GetItem with `DEVICE_SUMMARY_PROJECTION`, so the capabilities map is
+/// never read. Suited to dashboards polling many devices.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the devices table
+/// * `hardware_id` - MAC address of the device (partition key)
+///
+/// # Returns
+/// * `Ok(Some(DeviceListItem))` - Device found
+/// * `Ok(None)` - Device not found
+/// * `Err(DatabaseError)` - DynamoDB error occurred
+pub async fn get_device_summary(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+) -> Result<Option<DeviceListItem>, DatabaseError> {
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .projection_expression(DEVICE_SUMMARY_PROJECTION)
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result.item.as_ref().map(item_to_device_summary).transpose()
+}
+
 /// Create a new device record in the devices table
 ///
 /// Uses PutItem to create a new device record with GSI attributes.
@@ -168,9 +205,96 @@ pub async fn update_device_timestamps(
     Ok(())
 }
 
+/// Parameters for a friendly_name update
+#[derive(Debug, Clone)]
+pub struct FriendlyNameUpdateParams {
+    pub update_expression: String,
+    pub condition_expression: Option<String>,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the update that sets or removes friendly_name
+///
+/// When `expected` is given, the update is conditioned on the device still
+/// holding the values its ETag was computed from (`last_seen_at`,
+/// `firmware_version`, `friendly_name`, `last_reading_ms` and
+/// `readings_this_boot`), so a device that changed after it was read fails
+/// the write instead of being overwritten.
+pub fn build_friendly_name_update_params(
+    friendly_name: Option<&str>,
+    expected: Option<&Device>,
+) -> FriendlyNameUpdateParams {
+    let mut expression_attribute_values = HashMap::new();
+
+    let update_expression = match friendly_name {
+        Some(name) => {
+            expression_attribute_values
+                .insert(":name".to_string(), AttributeValue::S(name.to_string()));
+            "SET friendly_name = :name"
+        }
+        None => "REMOVE friendly_name",
+    };
+
+    let condition_expression = expected.map(|device| {
+        let optional = [
+            (
+                "friendly_name",
+                device
+                    .friendly_name
+                    .as_ref()
+                    .map(|name| AttributeValue::S(name.clone())),
+            ),
+            (
+                "last_reading_ms",
+                device
+                    .last_reading_ms
+                    .map(|ms| AttributeValue::N(ms.to_string())),
+            ),
+            (
+                "readings_this_boot",
+                device
+                    .readings_this_boot
+                    .map(|count| AttributeValue::N(count.to_string())),
+            ),
+        ];
+
+        let mut conditions = vec![
+            "last_seen_at = :expected_last_seen_at".to_string(),
+            "firmware_version = :expected_firmware_version".to_string(),
+        ];
+        expression_attribute_values.insert(
+            ":expected_last_seen_at".to_string(),
+            AttributeValue::S(device.last_seen_at.clone()),
+        );
+        expression_attribute_values.insert(
+            ":expected_firmware_version".to_string(),
+            AttributeValue::S(device.firmware_version.clone()),
+        );
+
+        for (attribute, value) in optional {
+            match value {
+                Some(value) => {
+                    let placeholder = format!(":expected_{}", attribute);
+                    conditions.push(format!("{} = {}", attribute, placeholder));
+                    expression_attribute_values.insert(placeholder, value);
+                }
+                None => conditions.push(format!("attribute_not_exists({})", attribute)),
+            }
+        }
+
+        conditions.join(" AND ")
+    });
+
+    FriendlyNameUpdateParams {
+        update_expression: update_expression.to_string(),
+        condition_expression,
+        expression_attribute_values,
+    }
+}
+
 /// Update device friendly_name
 ///
-/// Uses UpdateItem to update the friendly_name field.
+/// Uses UpdateItem to set or remove the friendly_name field.
 /// Note: Historical readings preserve the friendly_name value at the time of ingestion,
 /// so changes to the device's friendly_name do not affect previously stored readings.
 ///
@@ -179,41 +303,37 @@ pub async fn update_device_timestamps(
 /// * `table_name` - Name of the devices table
 /// * `hardware_id` - MAC address of the device (partition key)
 /// * `friendly_name` - New friendly name (or None to remove)
+/// * `expected` - Device as last read, to fail the update if it has changed since
 ///
 /// # Returns
 /// * `Ok(())` - Update successful
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - The device no longer matches `expected`
 /// * `Err(DatabaseError)` - DynamoDB error occurred
 pub async fn update_friendly_name(
     client: &DynamoDbClient,
     table_name: &str,
     hardware_id: &str,
     friendly_name: Option<&str>,
+    expected: Option<&Device>,
 ) -> Result<(), DatabaseError> {
-    match friendly_name {
-        Some(name) => {
-            // Set or update friendly_name
-            client
-                .update_item()
-                .table_name(table_name)
-                .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
-                .update_expression("SET friendly_name = :name")
-                .expression_attribute_values(":name", AttributeValue::S(name.to_string()))
-                .send()
-                .await
-                .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
-        }
-        None => {
-            // Remove friendly_name
-            client
-                .update_item()
-                .table_name(table_name)
-                .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
-                .update_expression("REMOVE friendly_name")
-                .send()
-                .await
-                .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
-        }
-    }
+    let params = build_friendly_name_update_params(friendly_name, expected);
+    let values = params.expression_attribute_values;
+
+    client
+        .update_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .update_expression(params.update_expression)
+        .set_condition_expression(params.condition_expression)
+        .set_expression_attribute_values((!values.is_empty()).then_some(values))
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
 
     Ok(())
 }
@@ -275,9 +395,42 @@ fn item_to_device(item: &HashMap<String, AttributeValue>) -> Result<Device, Data
         first_registered_at,
         last_seen_at,
         last_boot_id,
+        first_reading_ms: number_attr(item, "first_reading_ms"),
+        last_reading_ms: number_attr(item, "last_reading_ms"),
+        readings_this_boot: number_attr(item, "readings_this_boot"),
     })
 }
 
+/// Convert a projected device item to a `DeviceListItem`
+fn item_to_device_summary(
+    item: &HashMap<String, AttributeValue>,
+) -> Result<DeviceListItem, DatabaseError> {
+    let string_attr = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_s().ok())
+            .cloned()
+            .ok_or_else(|| DatabaseError::Serialization(format!("Missing {}", name)))
+    };
+
+    Ok(DeviceListItem {
+        hardware_id: string_attr("hardware_id")?,
+        confirmation_id: string_attr("confirmation_id")?,
+        friendly_name: string_attr("friendly_name").ok(),
+        firmware_version: string_attr("firmware_version")?,
+        first_registered_at: string_attr("first_registered_at")?,
+        last_seen_at: string_attr("last_seen_at")?,
+        first_reading_ms: None,
+        last_reading_ms: None,
+    })
+}
+
+/// Read an optional numeric attribute
+fn number_attr(item: &HashMap<String, AttributeValue>, name: &str) -> Option<i64> {
+    item.get(name)
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+}
+
 /// Convert Capabilities struct to DynamoDB AttributeValue (Map)
 fn capabilities_to_attribute_value(capabilities: &Capabilities) -> AttributeValue {
     let mut cap_map = HashMap::new();
@@ -345,6 +498,69 @@ pub struct DeviceListResponse {
     pub page_token: Option<String>,
 }
 
+/// Optional filters for a device listing
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceListFilter {
+    /// Exact firmware version, or a prefix when it ends with `*` (e.g. `1.0.*`)
+    pub firmware_version: Option<String>,
+    /// Only devices seen at or after this RFC3339 timestamp
+    pub min_last_seen: Option<String>,
+}
+
+/// Key condition, filter, and attribute values for a device listing query
+///
+/// Kept separate from the SDK call so the query structure can be verified in tests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevicesQueryParams {
+    pub key_condition_expression: String,
+    pub filter_expression: Option<String>,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the GSI1 key condition and optional filter for a device listing
+///
+/// `min_last_seen` bounds the gsi1sk (last_seen_at) sort key, so it reduces
+/// the items read. `firmware_version` is a filter expression applied after
+/// the key condition and limit, so a page may hold fewer items than `limit`.
+pub fn build_list_devices_query_params(filter: &DeviceListFilter) -> DevicesQueryParams {
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(":pk".to_string(), AttributeValue::S("devices".to_string()));
+
+    let key_condition_expression = match filter.min_last_seen {
+        Some(ref min_last_seen) => {
+            expression_attribute_values.insert(
+                ":min_last_seen".to_string(),
+                AttributeValue::S(min_last_seen.clone()),
+            );
+            "gsi1pk = :pk AND gsi1sk >= :min_last_seen".to_string()
+        }
+        None => "gsi1pk = :pk".to_string(),
+    };
+
+    let filter_expression = filter.firmware_version.as_ref().map(|firmware_version| {
+        match firmware_version.strip_suffix('*') {
+            Some(prefix) => {
+                expression_attribute_values
+                    .insert(":fw".to_string(), AttributeValue::S(prefix.to_string()));
+                "begins_with(firmware_version, :fw)".to_string()
+            }
+            None => {
+                expression_attribute_values.insert(
+                    ":fw".to_string(),
+                    AttributeValue::S(firmware_version.clone()),
+                );
+                "firmware_version = :fw".to_string()
+            }
+        }
+    });
+
+    DevicesQueryParams {
+        key_condition_expression,
+        filter_expression,
+        expression_attribute_values,
+    }
+}
+
 /// List devices with pagination, sorted by last_seen_at descending
 ///
 /// Uses GSI1 (gsi1pk="devices", gsi1sk=last_seen_at) for efficient querying
@@ -355,6 +571,7 @@ pub struct DeviceListResponse {
 /// * `table_name` - Name of the devices table
 /// * `limit` - Maximum number of devices to return (default 50, max 1000)
 /// * `page_token` - Optional pagination pageToken from previous response
+/// * `filter` - Optional firmware_version / min_last_seen filters
 ///
 /// # Returns
 /// * `DeviceListResponse` with devices and optional nextPageToken
@@ -363,6 +580,7 @@ pub async fn list_devices(
     table_name: &str,
     limit: Option<i32>,
     page_token: Option<String>,
+    filter: &DeviceListFilter,
 ) -> Result<DeviceListResponse, DatabaseError> {
     use esp32_backend::shared::cursor::{decode_device_page_token, encode_device_page_token};
 
@@ -378,20 +596,23 @@ pub async fn list_devices(
         None => 50,
     };
 
+    let params = build_list_devices_query_params(filter);
+
     // Build query
     let mut query = client
         .query()
         .table_name(table_name)
         .index_name("gsi1")
-        .key_condition_expression("gsi1pk = :pk")
-        .expression_attribute_values(":pk", AttributeValue::S("devices".to_string()))
+        .key_condition_expression(params.key_condition_expression)
+        .set_filter_expression(params.filter_expression)
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
         .scan_index_forward(false) // Most recent first (descending order)
         .limit(limit);
 
     // Add pageToken if provided
     if let Some(page_token_str) = page_token {
         let page_token = decode_device_page_token(&page_token_str)
-            .map_err(|e| DatabaseError::Serialization(format!("Invalid pageToken: {}", e.message)))?;
+            .map_err(|e| DatabaseError::Serialization(format!("Invalid pageToken: {}", e)))?;
 
         let start_key = page_token_to_exclusive_start_key(&page_token);
         query = query.set_exclusive_start_key(Some(start_key));
@@ -573,6 +794,50 @@ mod tests {
         assert_eq!(device.last_boot_id, "7c9e6679-7425-40de-944b-e07fc1f90ae7");
     }
 
+    #[test]
+    fn test_device_summary_projection_names() {
+        let names: Vec<&str> = DEVICE_SUMMARY_PROJECTION.split(", ").collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "hardware_id",
+                "confirmation_id",
+                "friendly_name",
+                "firmware_version",
+                "first_registered_at",
+                "last_seen_at",
+            ]
+        );
+        assert!(!names.contains(&"capabilities"));
+    }
+
+    #[test]
+    fn test_item_to_device_summary_omits_capabilities() {
+        let mut item = HashMap::new();
+        for (name, value) in [
+            ("hardware_id", "AA:BB:CC:DD:EE:FF"),
+            ("confirmation_id", "550e8400-e29b-41d4-a716-446655440000"),
+            ("firmware_version", "1.0.16"),
+            ("first_registered_at", "2024-01-15T10:30:00Z"),
+            ("last_seen_at", "2024-01-15T14:22:00Z"),
+        ] {
+            item.insert(name.to_string(), AttributeValue::S(value.to_string()));
+        }
+
+        let summary = item_to_device_summary(&item).unwrap();
+        assert_eq!(summary.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(summary.friendly_name, None);
+        assert_eq!(summary.last_seen_at, "2024-01-15T14:22:00Z");
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json.get("capabilities").is_none());
+        assert!(json.get("last_boot_id").is_none());
+
+        item.remove("last_seen_at");
+        assert!(item_to_device_summary(&item).is_err());
+    }
+
     #[test]
     fn test_item_to_device_without_friendly_name() {
         let mut features_map = HashMap::new();
@@ -640,14 +905,14 @@ mod tests {
 
     #[test]
     fn test_cursor_to_exclusive_start_key() {
-        use esp32_backend::shared::cursor::DeviceListCursor;
+        use esp32_backend::shared::cursor::DeviceListPageToken;
 
-        let cursor = DeviceListCursor {
+        let cursor = DeviceListPageToken {
             hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
             gsi1sk: "2024-01-15T14:22:00Z".to_string(),
         };
 
-        let key = cursor_to_exclusive_start_key(&cursor);
+        let key = page_token_to_exclusive_start_key(&cursor);
 
         assert_eq!(key.len(), 3);
         assert_eq!(
@@ -664,6 +929,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_list_devices_query_params_no_filter() {
+        let params = build_list_devices_query_params(&DeviceListFilter::default());
+
+        assert_eq!(params.key_condition_expression, "gsi1pk = :pk");
+        assert_eq!(params.filter_expression, None);
+        assert_eq!(params.expression_attribute_values.len(), 1);
+    }
+
+    #[test]
+    fn test_build_list_devices_query_params_exact_firmware() {
+        let filter = DeviceListFilter {
+            firmware_version: Some("1.0.16".to_string()),
+            min_last_seen: None,
+        };
+        let params = build_list_devices_query_params(&filter);
+
+        assert_eq!(params.key_condition_expression, "gsi1pk = :pk");
+        assert_eq!(
+            params.filter_expression.as_deref(),
+            Some("firmware_version = :fw")
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":fw"),
+            Some(&AttributeValue::S("1.0.16".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_build_list_devices_query_params_firmware_prefix() {
+        let filter = DeviceListFilter {
+            firmware_version: Some("1.0.*".to_string()),
+            min_last_seen: None,
+        };
+        let params = build_list_devices_query_params(&filter);
+
+        assert_eq!(
+            params.filter_expression.as_deref(),
+            Some("begins_with(firmware_version, :fw)")
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":fw"),
+            Some(&AttributeValue::S("1.0.".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_build_list_devices_query_params_min_last_seen() {
+        let filter = DeviceListFilter {
+            firmware_version: Some("1.0.16".to_string()),
+            min_last_seen: Some("2024-01-15T00:00:00+00:00".to_string()),
+        };
+        let params = build_list_devices_query_params(&filter);
+
+        // Bound on the sort key, not a filter
+        assert_eq!(
+            params.key_condition_expression,
+            "gsi1pk = :pk AND gsi1sk >= :min_last_seen"
+        );
+        assert_eq!(
+            params.filter_expression.as_deref(),
+            Some("firmware_version = :fw")
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":min_last_seen"),
+            Some(&AttributeValue::S("2024-01-15T00:00:00+00:00".to_string()))
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":pk"),
+            Some(&AttributeValue::S("devices".to_string()))
+        );
+    }
+
     #[test]
     fn test_rfc3339_timestamp_sortability() {
         // Verify that RFC3339 timestamps sort correctly lexicographically
@@ -693,6 +1031,82 @@ mod tests {
         assert_eq!(descending[3], "2024-01-14T23:59:59Z");
     }
 
+    fn snapshot_device() -> Device {
+        let clock =
+            esp32_backend::shared::time::FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+
+        Device::builder()
+            .hardware_id("AA:BB:CC:DD:EE:FF")
+            .confirmation_id("550e8400-e29b-41d4-a716-446655440000")
+            .friendly_name(Some("greenhouse".to_string()))
+            .firmware_version("1.0.16")
+            .capabilities(Capabilities {
+                sensors: vec!["bme280".to_string()],
+                features: HashMap::new(),
+            })
+            .first_registered_at("2024-01-15T10:30:00Z")
+            .last_seen_at("2024-01-15T14:22:00Z")
+            .last_boot_id("7c9e6679-7425-40de-944b-e07fc1f90ae7")
+            .build(&clock)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_friendly_name_update_without_precondition_is_unconditional() {
+        let params = build_friendly_name_update_params(Some("bench"), None);
+        assert_eq!(params.update_expression, "SET friendly_name = :name");
+        assert!(params.condition_expression.is_none());
+        assert_eq!(params.expression_attribute_values.len(), 1);
+
+        let params = build_friendly_name_update_params(None, None);
+        assert_eq!(params.update_expression, "REMOVE friendly_name");
+        assert!(params.condition_expression.is_none());
+        assert!(params.expression_attribute_values.is_empty());
+    }
+
+    #[test]
+    fn test_friendly_name_update_conditions_on_etag_fields() {
+        let mut device = snapshot_device();
+        device.readings_this_boot = Some(12);
+
+        let params = build_friendly_name_update_params(Some("bench"), Some(&device));
+        let condition = params.condition_expression.unwrap();
+
+        assert!(condition.contains("last_seen_at = :expected_last_seen_at"));
+        assert!(condition.contains("firmware_version = :expected_firmware_version"));
+        assert!(condition.contains("friendly_name = :expected_friendly_name"));
+        assert!(condition.contains("attribute_not_exists(last_reading_ms)"));
+        assert!(condition.contains("readings_this_boot = :expected_readings_this_boot"));
+
+        let values = &params.expression_attribute_values;
+        assert_eq!(values.get(":name").unwrap().as_s().unwrap(), "bench");
+        assert_eq!(
+            values
+                .get(":expected_last_seen_at")
+                .unwrap()
+                .as_s()
+                .unwrap(),
+            "2024-01-15T14:22:00Z"
+        );
+        assert_eq!(
+            values
+                .get(":expected_friendly_name")
+                .unwrap()
+                .as_s()
+                .unwrap(),
+            "greenhouse"
+        );
+        assert_eq!(
+            values
+                .get(":expected_readings_this_boot")
+                .unwrap()
+                .as_n()
+                .unwrap(),
+            "12"
+        );
+        assert!(!values.contains_key(":expected_last_reading_ms"));
+    }
+
     // Note: Integration tests for get_device, create_device, update_device_timestamps, and list_devices
     // require DynamoDB Local and are in the integration test suite
 }
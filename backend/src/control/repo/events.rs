@@ -0,0 +1,370 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::shared::plant_insights::{Event, EventType};
+
+/// Response for events query
+#[derive(Debug, Clone)]
+pub struct EventsQueryResponse {
+    pub events: Vec<Event>,
+    pub next_cursor: Option<String>,
+}
+
+/// Expressions and values for an events query
+///
+/// Kept separate from the SDK call so the query structure can be verified in tests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventsQueryParams {
+    pub key_condition_expression: String,
+    pub filter_expression: Option<String>,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the key condition and optional filter for an events query
+///
+/// The key condition selects the device partition and a start_time_ms range.
+/// The event_type filter is applied after the key condition, so it does not
+/// reduce read capacity, only the returned items.
+pub fn build_events_query_params(
+    hardware_id: &str,
+    from_ms: i64,
+    to_ms: i64,
+    event_type_filter: Option<EventType>,
+) -> EventsQueryParams {
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(
+        ":hw_id".to_string(),
+        AttributeValue::S(hardware_id.to_string()),
+    );
+    expression_attribute_values.insert(
+        ":from_ms".to_string(),
+        AttributeValue::N(from_ms.to_string()),
+    );
+    expression_attribute_values.insert(":to_ms".to_string(), AttributeValue::N(to_ms.to_string()));
+
+    let filter_expression = event_type_filter.map(|event_type| {
+        expression_attribute_values.insert(
+            ":event_type".to_string(),
+            AttributeValue::S(event_type.as_str().to_string()),
+        );
+        "event_type = :event_type".to_string()
+    });
+
+    EventsQueryParams {
+        key_condition_expression:
+            "hardware_id = :hw_id AND start_time_ms BETWEEN :from_ms AND :to_ms".to_string(),
+        filter_expression,
+        expression_attribute_values,
+    }
+}
+
+/// Query detected events for a device within a time range
+///
+/// Uses partition key (hardware_id) and sort key range (start_time_ms) on the
+/// plant events table, with an optional event_type filter expression.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the plant events table
+/// * `hardware_id` - MAC address of the device (partition key)
+/// * `from_ms` - Start of time range (epoch milliseconds, inclusive)
+/// * `to_ms` - End of time range (epoch milliseconds, inclusive)
+/// * `event_type_filter` - Optional event type to filter on
+/// * `limit` - Maximum number of items to evaluate (default 50, max 1000)
+/// * `cursor` - Optional pagination cursor from previous response
+///
+/// # Returns
+/// * `EventsQueryResponse` with events (newest first) and optional next_cursor
+#[allow(clippy::too_many_arguments)]
+pub async fn query_events(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    from_ms: i64,
+    to_ms: i64,
+    event_type_filter: Option<EventType>,
+    limit: Option<i32>,
+    cursor: Option<String>,
+) -> Result<EventsQueryResponse, DatabaseError> {
+    use esp32_backend::shared::cursor::{decode_events_page_token, encode_events_page_token};
+
+    if from_ms < 0 || to_ms < 0 {
+        return Err(DatabaseError::Serialization(
+            "Timestamps must be non-negative".to_string(),
+        ));
+    }
+
+    if from_ms > to_ms {
+        return Err(DatabaseError::Serialization(
+            "from_ms must be less than or equal to to_ms".to_string(),
+        ));
+    }
+
+    // Validate and apply limit (default 50, max 1000)
+    let limit = match limit {
+        Some(l) if l < 1 => {
+            return Err(DatabaseError::Serialization(
+                "Limit must be at least 1".to_string(),
+            ))
+        }
+        Some(l) if l > 1000 => 1000,
+        Some(l) => l,
+        None => 50,
+    };
+
+    let params = build_events_query_params(hardware_id, from_ms, to_ms, event_type_filter);
+
+    let mut query = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression(params.key_condition_expression)
+        .set_filter_expression(params.filter_expression)
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .scan_index_forward(false) // Newest first (descending order)
+        .limit(limit);
+
+    // Add cursor if provided
+    if let Some(cursor_str) = cursor {
+        let cursor = decode_events_page_token(&cursor_str)
+            .map_err(|e| DatabaseError::Serialization(format!("Invalid cursor: {}", e)))?;
+
+        let mut start_key = HashMap::new();
+        start_key.insert(
+            "hardware_id".to_string(),
+            AttributeValue::S(cursor.hardware_id),
+        );
+        start_key.insert(
+            "start_time_ms".to_string(),
+            AttributeValue::N(cursor.start_time_ms.to_string()),
+        );
+        query = query.set_exclusive_start_key(Some(start_key));
+    }
+
+    let result = query
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    let events: Vec<Event> = result
+        .items
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| item_to_event(&item))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Encode next cursor if more results available
+    let next_cursor = result.last_evaluated_key.and_then(|key| {
+        let hardware_id = key.get("hardware_id")?.as_s().ok()?;
+        let start_time_ms = key.get("start_time_ms")?.as_n().ok()?.parse::<i64>().ok()?;
+        encode_events_page_token(hardware_id, start_time_ms).ok()
+    });
+
+    Ok(EventsQueryResponse {
+        events,
+        next_cursor,
+    })
+}
+
+/// Convert DynamoDB item to Event struct
+fn item_to_event(item: &HashMap<String, AttributeValue>) -> Result<Event, DatabaseError> {
+    let hardware_id = item
+        .get("hardware_id")
+        .and_then(|v| v.as_s().ok())
+        .ok_or_else(|| DatabaseError::Serialization("Missing hardware_id".to_string()))?
+        .clone();
+
+    let start_time_ms = get_i64(item, "start_time_ms")?;
+    let end_time_ms = get_i64(item, "end_time_ms")?;
+    let created_at_ms = get_i64(item, "created_at_ms")?;
+
+    let event_type = item
+        .get("event_type")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|s| EventType::parse(s))
+        .ok_or_else(|| DatabaseError::Serialization("Missing or invalid event_type".to_string()))?;
+
+    let sensor_values = item
+        .get("sensor_values")
+        .and_then(|v| v.as_m().ok())
+        .map(|m| {
+            m.iter()
+                .filter_map(|(k, v)| {
+                    let value = v.as_n().ok()?.parse::<f64>().ok()?;
+                    Some((k.clone(), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let detection_metadata = item
+        .get("detection_metadata")
+        .and_then(|v| v.as_m().ok())
+        .map(|m| {
+            m.iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_s().ok()?.clone())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(Event {
+        hardware_id,
+        start_time_ms,
+        end_time_ms,
+        event_type,
+        sensor_values,
+        detection_metadata,
+        created_at_ms,
+    })
+}
+
+/// Read a required numeric attribute as i64
+fn get_i64(item: &HashMap<String, AttributeValue>, field: &str) -> Result<i64, DatabaseError> {
+    item.get(field)
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+        .ok_or_else(|| DatabaseError::Serialization(format!("Missing or invalid {}", field)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_item(event_type: &str) -> HashMap<String, AttributeValue> {
+        let mut item = HashMap::new();
+        item.insert(
+            "hardware_id".to_string(),
+            AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()),
+        );
+        item.insert(
+            "start_time_ms".to_string(),
+            AttributeValue::N("1704067200000".to_string()),
+        );
+        item.insert(
+            "end_time_ms".to_string(),
+            AttributeValue::N("1704067800000".to_string()),
+        );
+        item.insert(
+            "created_at_ms".to_string(),
+            AttributeValue::N("1704067900000".to_string()),
+        );
+        item.insert(
+            "event_type".to_string(),
+            AttributeValue::S(event_type.to_string()),
+        );
+
+        let mut sensor_values = HashMap::new();
+        sensor_values.insert(
+            "soil_moisture_delta".to_string(),
+            AttributeValue::N("18.5".to_string()),
+        );
+        item.insert(
+            "sensor_values".to_string(),
+            AttributeValue::M(sensor_values),
+        );
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "detector".to_string(),
+            AttributeValue::S("moisture_jump".to_string()),
+        );
+        item.insert(
+            "detection_metadata".to_string(),
+            AttributeValue::M(metadata),
+        );
+
+        item
+    }
+
+    #[test]
+    fn test_event_type_rename_round_trip() {
+        let all = [
+            EventType::WateringEvent,
+            EventType::DryingCycle,
+            EventType::TemperatureStress,
+            EventType::HumidityAnomaly,
+            EventType::EnvironmentalChange,
+        ];
+
+        for event_type in all {
+            // Serialized name matches the stored/query name
+            let json = serde_json::to_string(&event_type).unwrap();
+            assert_eq!(json, format!("\"{}\"", event_type.as_str()));
+
+            // Parsing the stored name yields the same variant
+            assert_eq!(EventType::parse(event_type.as_str()), Some(event_type));
+            let decoded: EventType = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, event_type);
+        }
+
+        assert_eq!(EventType::parse("WateringEvent"), None);
+        assert_eq!(EventType::parse("watering_event"), None);
+    }
+
+    #[test]
+    fn test_item_to_event() {
+        let event = item_to_event(&create_test_item("Watering_Event")).unwrap();
+
+        assert_eq!(event.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(event.start_time_ms, 1704067200000);
+        assert_eq!(event.end_time_ms, 1704067800000);
+        assert_eq!(event.created_at_ms, 1704067900000);
+        assert_eq!(event.event_type, EventType::WateringEvent);
+        assert_eq!(event.sensor_values.get("soil_moisture_delta"), Some(&18.5));
+        assert_eq!(
+            event.detection_metadata.get("detector").map(String::as_str),
+            Some("moisture_jump")
+        );
+    }
+
+    #[test]
+    fn test_item_to_event_invalid_event_type() {
+        let result = item_to_event(&create_test_item("Not_An_Event"));
+        assert!(matches!(result, Err(DatabaseError::Serialization(_))));
+    }
+
+    #[test]
+    fn test_build_events_query_params_range_only() {
+        let params = build_events_query_params("AA:BB:CC:DD:EE:FF", 1000, 2000, None);
+
+        assert_eq!(
+            params.key_condition_expression,
+            "hardware_id = :hw_id AND start_time_ms BETWEEN :from_ms AND :to_ms"
+        );
+        assert_eq!(params.filter_expression, None);
+        assert_eq!(params.expression_attribute_values.len(), 3);
+        assert_eq!(
+            params.expression_attribute_values.get(":hw_id"),
+            Some(&AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()))
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":from_ms"),
+            Some(&AttributeValue::N("1000".to_string()))
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":to_ms"),
+            Some(&AttributeValue::N("2000".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_build_events_query_params_with_type_filter() {
+        let params = build_events_query_params(
+            "AA:BB:CC:DD:EE:FF",
+            1000,
+            2000,
+            Some(EventType::DryingCycle),
+        );
+
+        assert_eq!(
+            params.filter_expression.as_deref(),
+            Some("event_type = :event_type")
+        );
+        assert_eq!(params.expression_attribute_values.len(), 4);
+        assert_eq!(
+            params.expression_attribute_values.get(":event_type"),
+            Some(&AttributeValue::S("Drying_Cycle".to_string()))
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::shared::plant_insights::{
+    EventType, InsightRequest, RequestStatus, RequestType,
+};
+
+/// GSI on (status, request_time_ms) used to find pending requests
+const STATUS_INDEX: &str = "StatusIndex";
+
+/// Lifetime of a queued request before DynamoDB TTL removes it (7 days)
+pub const INSIGHT_REQUEST_TTL_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Result of trying to move one request from pending to processing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimOutcome {
+    /// This caller now owns the request
+    Claimed,
+    /// Another worker claimed it first, or it is no longer pending
+    AlreadyClaimed,
+}
+
+/// Parameters for a conditional status transition
+#[derive(Debug, Clone)]
+pub struct StatusTransitionParams {
+    pub update_expression: String,
+    pub condition_expression: String,
+    pub expression_attribute_names: HashMap<String, String>,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the update that moves a request from `from` to `to`
+///
+/// The condition on the current status makes concurrent transitions safe:
+/// only one caller can move a request out of a given status. `status` is a
+/// DynamoDB reserved word, so it is referenced through `#status`.
+pub fn build_status_transition_params(
+    from: RequestStatus,
+    to: RequestStatus,
+) -> StatusTransitionParams {
+    let mut expression_attribute_names = HashMap::new();
+    expression_attribute_names.insert("#status".to_string(), "status".to_string());
+
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(
+        ":from".to_string(),
+        AttributeValue::S(from.as_str().to_string()),
+    );
+    expression_attribute_values.insert(
+        ":to".to_string(),
+        AttributeValue::S(to.as_str().to_string()),
+    );
+
+    StatusTransitionParams {
+        update_expression: "SET #status = :to".to_string(),
+        condition_expression: "#status = :from".to_string(),
+        expression_attribute_names,
+        expression_attribute_values,
+    }
+}
+
+/// Queue a pending insight request
+///
+/// Requests are keyed by hardware_id (partition key) and request_time_ms
+/// (sort key), and expire `INSIGHT_REQUEST_TTL_SECONDS` after `now_ms`.
+///
+/// # Returns
+/// * `Ok(InsightRequest)` - The stored request
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - A request already exists
+///   for this device at `now_ms`
+pub async fn enqueue_request(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    request_type: RequestType,
+    event_type: Option<EventType>,
+    now_ms: i64,
+) -> Result<InsightRequest, DatabaseError> {
+    use aws_sdk_dynamodb::operation::put_item::PutItemError;
+
+    let request = new_pending_request(hardware_id, request_type, event_type, now_ms);
+
+    client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(request_to_item(&request)))
+        .condition_expression("attribute_not_exists(request_time_ms)")
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(PutItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
+
+    Ok(request)
+}
+
+/// Claim up to `limit` of the oldest pending requests
+///
+/// Each request is moved to processing with a conditional update, so a
+/// request seen by two workers is only returned to one of them. Requests
+/// another worker claimed first are skipped, so fewer than `limit` may be
+/// returned.
+pub async fn claim_pending(
+    client: &DynamoDbClient,
+    table_name: &str,
+    limit: i32,
+) -> Result<Vec<InsightRequest>, DatabaseError> {
+    let result = client
+        .query()
+        .table_name(table_name)
+        .index_name(STATUS_INDEX)
+        .key_condition_expression("#status = :pending")
+        .expression_attribute_names("#status", "status")
+        .expression_attribute_values(
+            ":pending",
+            AttributeValue::S(RequestStatus::Pending.as_str().to_string()),
+        )
+        .scan_index_forward(true) // Oldest first
+        .limit(limit)
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    let pending = result
+        .items
+        .unwrap_or_default()
+        .iter()
+        .map(item_to_request)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut claimed = Vec::new();
+    for mut request in pending {
+        let outcome = claim_request(
+            client,
+            table_name,
+            &request.hardware_id,
+            request.request_time_ms,
+        )
+        .await?;
+
+        if outcome == ClaimOutcome::Claimed {
+            request.status = RequestStatus::Processing;
+            claimed.push(request);
+        }
+    }
+
+    Ok(claimed)
+}
+
+/// Move one request from pending to processing
+pub async fn claim_request(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    request_time_ms: i64,
+) -> Result<ClaimOutcome, DatabaseError> {
+    let result = transition_status(
+        client,
+        table_name,
+        hardware_id,
+        request_time_ms,
+        RequestStatus::Pending,
+        RequestStatus::Processing,
+    )
+    .await;
+
+    claim_outcome(result)
+}
+
+/// Mark a claimed request as done
+///
+/// # Returns
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - The request is not processing
+pub async fn mark_done(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    request_time_ms: i64,
+) -> Result<(), DatabaseError> {
+    transition_status(
+        client,
+        table_name,
+        hardware_id,
+        request_time_ms,
+        RequestStatus::Processing,
+        RequestStatus::Done,
+    )
+    .await
+}
+
+/// Mark a claimed request as failed
+///
+/// # Returns
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - The request is not processing
+pub async fn mark_failed(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    request_time_ms: i64,
+) -> Result<(), DatabaseError> {
+    transition_status(
+        client,
+        table_name,
+        hardware_id,
+        request_time_ms,
+        RequestStatus::Processing,
+        RequestStatus::Failed,
+    )
+    .await
+}
+
+/// Conditionally move a request from one status to another
+async fn transition_status(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    request_time_ms: i64,
+    from: RequestStatus,
+    to: RequestStatus,
+) -> Result<(), DatabaseError> {
+    let params = build_status_transition_params(from, to);
+
+    client
+        .update_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .key(
+            "request_time_ms",
+            AttributeValue::N(request_time_ms.to_string()),
+        )
+        .update_expression(params.update_expression)
+        .condition_expression(params.condition_expression)
+        .set_expression_attribute_names(Some(params.expression_attribute_names))
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
+
+    Ok(())
+}
+
+/// Map a pending-to-processing transition result to a claim outcome
+///
+/// A failed condition means the request is no longer pending, which is the
+/// expected result when another worker got there first.
+fn claim_outcome(result: Result<(), DatabaseError>) -> Result<ClaimOutcome, DatabaseError> {
+    match result {
+        Ok(()) => Ok(ClaimOutcome::Claimed),
+        Err(DatabaseError::ConditionalCheckFailed) => Ok(ClaimOutcome::AlreadyClaimed),
+        Err(e) => Err(e),
+    }
+}
+
+/// Build a pending request that expires `INSIGHT_REQUEST_TTL_SECONDS` after `now_ms`
+fn new_pending_request(
+    hardware_id: &str,
+    request_type: RequestType,
+    event_type: Option<EventType>,
+    now_ms: i64,
+) -> InsightRequest {
+    InsightRequest {
+        hardware_id: hardware_id.to_string(),
+        request_time_ms: now_ms,
+        request_type,
+        event_type,
+        status: RequestStatus::Pending,
+        ttl: Some(now_ms / 1000 + INSIGHT_REQUEST_TTL_SECONDS),
+    }
+}
+
+/// Convert an InsightRequest to a DynamoDB item
+fn request_to_item(request: &InsightRequest) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert(
+        "hardware_id".to_string(),
+        AttributeValue::S(request.hardware_id.clone()),
+    );
+    item.insert(
+        "request_time_ms".to_string(),
+        AttributeValue::N(request.request_time_ms.to_string()),
+    );
+    item.insert(
+        "request_type".to_string(),
+        AttributeValue::S(request.request_type.as_str().to_string()),
+    );
+    if let Some(event_type) = request.event_type {
+        item.insert(
+            "event_type".to_string(),
+            AttributeValue::S(event_type.as_str().to_string()),
+        );
+    }
+    item.insert(
+        "status".to_string(),
+        AttributeValue::S(request.status.as_str().to_string()),
+    );
+    if let Some(ttl) = request.ttl {
+        item.insert("ttl".to_string(), AttributeValue::N(ttl.to_string()));
+    }
+    item
+}
+
+/// Convert a DynamoDB item to an InsightRequest
+fn item_to_request(
+    item: &HashMap<String, AttributeValue>,
+) -> Result<InsightRequest, DatabaseError> {
+    let string = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_s().ok())
+            .ok_or_else(|| DatabaseError::Serialization(format!("Missing {}", name)))
+    };
+    let number = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse::<i64>().ok())
+    };
+
+    let request_type = RequestType::parse(string("request_type")?)
+        .ok_or_else(|| DatabaseError::Serialization("Invalid request_type".to_string()))?;
+    let status = RequestStatus::parse(string("status")?)
+        .ok_or_else(|| DatabaseError::Serialization("Invalid status".to_string()))?;
+    let event_type = match item.get("event_type").and_then(|v| v.as_s().ok()) {
+        Some(value) => Some(
+            EventType::parse(value)
+                .ok_or_else(|| DatabaseError::Serialization("Invalid event_type".to_string()))?,
+        ),
+        None => None,
+    };
+
+    Ok(InsightRequest {
+        hardware_id: string("hardware_id")?.clone(),
+        request_time_ms: number("request_time_ms").ok_or_else(|| {
+            DatabaseError::Serialization("Missing or invalid request_time_ms".to_string())
+        })?,
+        request_type,
+        event_type,
+        status,
+        ttl: number("ttl"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_params_require_pending_status() {
+        let params =
+            build_status_transition_params(RequestStatus::Pending, RequestStatus::Processing);
+
+        assert_eq!(params.update_expression, "SET #status = :to");
+        assert_eq!(params.condition_expression, "#status = :from");
+        assert_eq!(
+            params.expression_attribute_names.get("#status").unwrap(),
+            "status"
+        );
+        assert_eq!(
+            params.expression_attribute_values[":from"].as_s().unwrap(),
+            "pending"
+        );
+        assert_eq!(
+            params.expression_attribute_values[":to"].as_s().unwrap(),
+            "processing"
+        );
+    }
+
+    #[test]
+    fn test_finish_params_require_processing_status() {
+        let done = build_status_transition_params(RequestStatus::Processing, RequestStatus::Done);
+        assert_eq!(
+            done.expression_attribute_values[":from"].as_s().unwrap(),
+            "processing"
+        );
+        assert_eq!(
+            done.expression_attribute_values[":to"].as_s().unwrap(),
+            "done"
+        );
+
+        let failed =
+            build_status_transition_params(RequestStatus::Processing, RequestStatus::Failed);
+        assert_eq!(
+            failed.expression_attribute_values[":to"].as_s().unwrap(),
+            "failed"
+        );
+    }
+
+    #[test]
+    fn test_claim_outcome_maps_conditional_failure_to_already_claimed() {
+        assert_eq!(claim_outcome(Ok(())).unwrap(), ClaimOutcome::Claimed);
+        assert_eq!(
+            claim_outcome(Err(DatabaseError::ConditionalCheckFailed)).unwrap(),
+            ClaimOutcome::AlreadyClaimed
+        );
+        assert!(matches!(
+            claim_outcome(Err(DatabaseError::DynamoDb("throttled".to_string()))),
+            Err(DatabaseError::DynamoDb(_))
+        ));
+    }
+
+    #[test]
+    fn test_new_pending_request_ttl_defaults_to_seven_days() {
+        let now_ms = 1_704_067_200_000;
+        let request = new_pending_request(
+            "AA:BB:CC:DD:EE:FF",
+            RequestType::Event,
+            Some(EventType::WateringEvent),
+            now_ms,
+        );
+
+        assert_eq!(request.status, RequestStatus::Pending);
+        assert_eq!(request.request_time_ms, now_ms);
+        assert_eq!(request.ttl, Some(1_704_067_200 + 604_800));
+    }
+
+    #[test]
+    fn test_request_item_round_trip() {
+        let request = new_pending_request(
+            "AA:BB:CC:DD:EE:FF",
+            RequestType::Event,
+            Some(EventType::WateringEvent),
+            1_704_067_200_000,
+        );
+
+        let item = request_to_item(&request);
+        assert_eq!(item["status"].as_s().unwrap(), "pending");
+        assert_eq!(item["request_type"].as_s().unwrap(), "event");
+        assert_eq!(item["event_type"].as_s().unwrap(), "Watering_Event");
+
+        assert_eq!(item_to_request(&item).unwrap(), request);
+    }
+
+    #[test]
+    fn test_item_to_request_rejects_unknown_status() {
+        let mut item = request_to_item(&new_pending_request(
+            "AA:BB:CC:DD:EE:FF",
+            RequestType::Scheduled,
+            None,
+            1_704_067_200_000,
+        ));
+        item.insert(
+            "status".to_string(),
+            AttributeValue::S("Pending".to_string()),
+        );
+
+        assert!(matches!(
+            item_to_request(&item),
+            Err(DatabaseError::Serialization(_))
+        ));
+    }
+}
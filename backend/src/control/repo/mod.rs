@@ -1,3 +1,16 @@
+pub mod aggregates;
 pub mod api_keys;
+pub mod device_status;
 pub mod devices;
+pub mod events;
+// Queue for insight generation workers; no control plane handler calls it yet
+#[allow(dead_code)]
+pub mod insight_requests;
+pub mod notes;
+// Stage markers for the processing pipelines; no control plane handler calls it yet
+#[allow(dead_code)]
+pub mod processed_readings;
 pub mod readings;
+// Write path is used by the status evaluator binary
+#[allow(dead_code)]
+pub mod status_transitions;
@@ -0,0 +1,202 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::shared::domain::Note;
+
+/// Key condition for listing a device's notes within a time range
+const NOTES_KEY_CONDITION: &str =
+    "hardware_id = :hw_id AND timestamp_ms BETWEEN :from_ms AND :to_ms";
+
+/// Store a note for a device
+///
+/// Items are keyed by hardware_id (partition key) and timestamp_ms (sort key),
+/// so a note at the same timestamp as an existing one replaces it.
+pub async fn create_note(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    timestamp_ms: i64,
+    text: &str,
+) -> Result<Note, DatabaseError> {
+    let note = Note {
+        hardware_id: hardware_id.to_string(),
+        timestamp_ms,
+        text: text.to_string(),
+    };
+
+    client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(note_to_item(&note)))
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    Ok(note)
+}
+
+/// List a device's notes within a time range, oldest first
+///
+/// Follows `LastEvaluatedKey` until every note in the range has been read.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the notes table
+/// * `hardware_id` - MAC address of the device (partition key)
+/// * `from_ms` - Start of time range (epoch milliseconds, inclusive)
+/// * `to_ms` - End of time range (epoch milliseconds, inclusive)
+pub async fn list_notes(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    from_ms: i64,
+    to_ms: i64,
+) -> Result<Vec<Note>, DatabaseError> {
+    let mut notes = Vec::new();
+    let mut start_key = None;
+
+    loop {
+        let result = client
+            .query()
+            .table_name(table_name)
+            .key_condition_expression(NOTES_KEY_CONDITION)
+            .set_expression_attribute_values(Some(build_notes_query_values(
+                hardware_id,
+                from_ms,
+                to_ms,
+            )))
+            .set_exclusive_start_key(start_key)
+            .send()
+            .await
+            .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+        for item in result.items.unwrap_or_default() {
+            notes.push(item_to_note(&item)?);
+        }
+
+        match result.last_evaluated_key {
+            Some(key) if !key.is_empty() => start_key = Some(key),
+            _ => return Ok(notes),
+        }
+    }
+}
+
+/// Expression attribute values for `NOTES_KEY_CONDITION`
+fn build_notes_query_values(
+    hardware_id: &str,
+    from_ms: i64,
+    to_ms: i64,
+) -> HashMap<String, AttributeValue> {
+    HashMap::from([
+        (
+            ":hw_id".to_string(),
+            AttributeValue::S(hardware_id.to_string()),
+        ),
+        (
+            ":from_ms".to_string(),
+            AttributeValue::N(from_ms.to_string()),
+        ),
+        (":to_ms".to_string(), AttributeValue::N(to_ms.to_string())),
+    ])
+}
+
+/// Convert a Note to a DynamoDB item
+fn note_to_item(note: &Note) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert(
+        "hardware_id".to_string(),
+        AttributeValue::S(note.hardware_id.clone()),
+    );
+    item.insert(
+        "timestamp_ms".to_string(),
+        AttributeValue::N(note.timestamp_ms.to_string()),
+    );
+    item.insert("text".to_string(), AttributeValue::S(note.text.clone()));
+    item
+}
+
+/// Convert a DynamoDB item to a Note
+fn item_to_note(item: &HashMap<String, AttributeValue>) -> Result<Note, DatabaseError> {
+    let get_s = |name: &str| -> Result<String, DatabaseError> {
+        item.get(name)
+            .and_then(|v| v.as_s().ok())
+            .cloned()
+            .ok_or_else(|| DatabaseError::Serialization(format!("Missing {}", name)))
+    };
+
+    let timestamp_ms = item
+        .get("timestamp_ms")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+        .ok_or_else(|| {
+            DatabaseError::Serialization("Missing or invalid timestamp_ms".to_string())
+        })?;
+
+    Ok(Note {
+        hardware_id: get_s("hardware_id")?,
+        timestamp_ms,
+        text: get_s("text")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_item_round_trip() {
+        let note = Note {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms: 1704067200000,
+            text: "repotted plant here".to_string(),
+        };
+
+        let item = note_to_item(&note);
+        assert_eq!(
+            item.get("timestamp_ms"),
+            Some(&AttributeValue::N("1704067200000".to_string()))
+        );
+
+        assert_eq!(item_to_note(&item).unwrap(), note);
+    }
+
+    #[test]
+    fn test_item_to_note_missing_text() {
+        let mut item = HashMap::new();
+        item.insert(
+            "hardware_id".to_string(),
+            AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()),
+        );
+        item.insert(
+            "timestamp_ms".to_string(),
+            AttributeValue::N("1704067200000".to_string()),
+        );
+
+        assert!(matches!(
+            item_to_note(&item),
+            Err(DatabaseError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_notes_query_values_range() {
+        let values = build_notes_query_values("AA:BB:CC:DD:EE:FF", 1000, 2000);
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            values.get(":hw_id"),
+            Some(&AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()))
+        );
+        assert_eq!(
+            values.get(":from_ms"),
+            Some(&AttributeValue::N("1000".to_string()))
+        );
+        assert_eq!(
+            values.get(":to_ms"),
+            Some(&AttributeValue::N("2000".to_string()))
+        );
+        assert!(NOTES_KEY_CONDITION.contains("timestamp_ms BETWEEN :from_ms AND :to_ms"));
+    }
+}
@@ -0,0 +1,290 @@
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::shared::plant_insights::{ProcessedReading, ProcessingStage};
+use esp32_backend::shared::time::Clock;
+
+/// Lifetime of a processed-reading marker before DynamoDB TTL removes it (30 days)
+pub const PROCESSED_READING_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Result of trying to mark one stage of a reading as processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkStageOutcome {
+    /// The stage was unmarked and is now marked by this caller
+    Marked,
+    /// The stage was already marked; nothing was changed
+    AlreadyMarked,
+}
+
+/// Parameters for a conditional stage marker update
+#[derive(Debug, Clone)]
+pub struct MarkStageParams {
+    pub update_expression: String,
+    pub condition_expression: String,
+    pub expression_attribute_names: HashMap<String, String>,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the update that sets `{stage}_processed_at_ms` to `now_ms`
+///
+/// The condition only lets the marker be written once, so a retried stage
+/// sees `ConditionalCheckFailed` instead of moving the marker. `hardware_id`
+/// and `ttl` are set by whichever stage marks the reading first; `ttl` is a
+/// DynamoDB reserved word, so it is referenced through `#ttl`.
+pub fn build_mark_stage_params(
+    hardware_id: &str,
+    stage: ProcessingStage,
+    now_ms: i64,
+) -> MarkStageParams {
+    let mut expression_attribute_names = HashMap::new();
+    expression_attribute_names.insert(
+        "#stage".to_string(),
+        stage.processed_at_attribute().to_string(),
+    );
+    expression_attribute_names.insert("#ttl".to_string(), "ttl".to_string());
+
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(":now".to_string(), AttributeValue::N(now_ms.to_string()));
+    expression_attribute_values.insert(
+        ":hardware_id".to_string(),
+        AttributeValue::S(hardware_id.to_string()),
+    );
+    expression_attribute_values.insert(
+        ":ttl".to_string(),
+        AttributeValue::N((now_ms / 1000 + PROCESSED_READING_TTL_SECONDS).to_string()),
+    );
+
+    MarkStageParams {
+        update_expression: "SET #stage = :now, \
+                            hardware_id = if_not_exists(hardware_id, :hardware_id), \
+                            #ttl = if_not_exists(#ttl, :ttl)"
+            .to_string(),
+        condition_expression: "attribute_not_exists(#stage)".to_string(),
+        expression_attribute_names,
+        expression_attribute_values,
+    }
+}
+
+/// Get the processing markers for a reading
+///
+/// `reading_id` is `{batch_id}#{timestamp_ms}` (see `Reading::reading_id`).
+///
+/// # Returns
+/// * `Ok(None)` - No stage has processed the reading yet
+pub async fn get_processed(
+    client: &DynamoDbClient,
+    table_name: &str,
+    reading_id: &str,
+) -> Result<Option<ProcessedReading>, DatabaseError> {
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .key("reading_id", AttributeValue::S(reading_id.to_string()))
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result.item.as_ref().map(item_to_processed).transpose()
+}
+
+/// Mark one stage of a reading as processed at the clock's current time
+///
+/// Safe to call again for the same reading and stage: the second call leaves
+/// the original marker in place and returns `AlreadyMarked`, so a stage can
+/// skip work it has already done.
+pub async fn mark_stage(
+    client: &DynamoDbClient,
+    table_name: &str,
+    reading_id: &str,
+    hardware_id: &str,
+    stage: ProcessingStage,
+    clock: &dyn Clock,
+) -> Result<MarkStageOutcome, DatabaseError> {
+    let params = build_mark_stage_params(hardware_id, stage, clock.now_epoch_ms());
+
+    let result = client
+        .update_item()
+        .table_name(table_name)
+        .key("reading_id", AttributeValue::S(reading_id.to_string()))
+        .update_expression(params.update_expression)
+        .condition_expression(params.condition_expression)
+        .set_expression_attribute_names(Some(params.expression_attribute_names))
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|e| match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        });
+
+    mark_outcome(result)
+}
+
+/// Map a stage marker update result to a mark outcome
+///
+/// A failed condition means the stage was already marked, which is the
+/// expected result when a reading is processed twice.
+fn mark_outcome(result: Result<(), DatabaseError>) -> Result<MarkStageOutcome, DatabaseError> {
+    match result {
+        Ok(()) => Ok(MarkStageOutcome::Marked),
+        Err(DatabaseError::ConditionalCheckFailed) => Ok(MarkStageOutcome::AlreadyMarked),
+        Err(e) => Err(e),
+    }
+}
+
+/// Convert a DynamoDB item to a ProcessedReading
+fn item_to_processed(
+    item: &HashMap<String, AttributeValue>,
+) -> Result<ProcessedReading, DatabaseError> {
+    let string = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_s().ok())
+            .cloned()
+            .ok_or_else(|| DatabaseError::Serialization(format!("Missing {}", name)))
+    };
+    let number = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse::<i64>().ok())
+    };
+
+    Ok(ProcessedReading {
+        reading_id: string("reading_id")?,
+        hardware_id: string("hardware_id")?,
+        event_processed_at_ms: number(ProcessingStage::Event.processed_at_attribute()),
+        aggregate_processed_at_ms: number(ProcessingStage::Aggregate.processed_at_attribute()),
+        status_processed_at_ms: number(ProcessingStage::Status.processed_at_attribute()),
+        ttl: number("ttl")
+            .ok_or_else(|| DatabaseError::Serialization("Missing or invalid ttl".to_string()))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::shared::idempotency::generate_reading_id;
+    use esp32_backend::shared::plant_insights::{
+        Reading, ReadingSensorStatus, SensorStatus, SensorValues,
+    };
+
+    #[test]
+    fn test_mark_stage_params_only_write_unset_stage() {
+        let params = build_mark_stage_params(
+            "AA:BB:CC:DD:EE:FF",
+            ProcessingStage::Event,
+            1_704_067_200_000,
+        );
+
+        assert_eq!(params.condition_expression, "attribute_not_exists(#stage)");
+        assert!(params.update_expression.starts_with("SET #stage = :now"));
+        assert_eq!(
+            params.expression_attribute_names["#stage"],
+            "event_processed_at_ms"
+        );
+        assert_eq!(
+            params.expression_attribute_values[":now"].as_n().unwrap(),
+            "1704067200000"
+        );
+    }
+
+    #[test]
+    fn test_mark_stage_params_keep_first_ttl() {
+        let params = build_mark_stage_params(
+            "AA:BB:CC:DD:EE:FF",
+            ProcessingStage::Aggregate,
+            1_704_067_200_000,
+        );
+
+        assert!(params
+            .update_expression
+            .contains("#ttl = if_not_exists(#ttl, :ttl)"));
+        assert_eq!(params.expression_attribute_names["#ttl"], "ttl");
+        assert_eq!(
+            params.expression_attribute_values[":ttl"].as_n().unwrap(),
+            (1_704_067_200 + 2_592_000).to_string()
+        );
+        assert_eq!(
+            params.expression_attribute_names["#stage"],
+            "aggregate_processed_at_ms"
+        );
+    }
+
+    #[test]
+    fn test_second_mark_for_same_stage_is_noop() {
+        assert_eq!(mark_outcome(Ok(())).unwrap(), MarkStageOutcome::Marked);
+        assert_eq!(
+            mark_outcome(Err(DatabaseError::ConditionalCheckFailed)).unwrap(),
+            MarkStageOutcome::AlreadyMarked
+        );
+        assert!(matches!(
+            mark_outcome(Err(DatabaseError::DynamoDb("throttled".to_string()))),
+            Err(DatabaseError::DynamoDb(_))
+        ));
+    }
+
+    #[test]
+    fn test_item_to_processed_reads_stage_markers() {
+        let mut item = HashMap::new();
+        item.insert(
+            "reading_id".to_string(),
+            AttributeValue::S("batch123#1704067200000".to_string()),
+        );
+        item.insert(
+            "hardware_id".to_string(),
+            AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()),
+        );
+        item.insert(
+            "status_processed_at_ms".to_string(),
+            AttributeValue::N("1704067260000".to_string()),
+        );
+        item.insert(
+            "ttl".to_string(),
+            AttributeValue::N("1706659200".to_string()),
+        );
+
+        let processed = item_to_processed(&item).unwrap();
+        assert_eq!(processed.reading_id, "batch123#1704067200000");
+        assert_eq!(processed.status_processed_at_ms, Some(1_704_067_260_000));
+        assert_eq!(processed.event_processed_at_ms, None);
+        assert_eq!(processed.aggregate_processed_at_ms, None);
+        assert_eq!(processed.ttl, 1_706_659_200);
+    }
+
+    #[test]
+    fn test_reading_id_matches_processed_key_format() {
+        let reading = Reading {
+            batch_id: "batch123".to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms: 1_704_067_200_000,
+            ingest_time_ms: 1_704_067_201_000,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: None,
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: Some(45.0),
+            },
+            sensor_status: ReadingSensorStatus {
+                bme280: SensorStatus::Missing,
+                ds18b20: SensorStatus::Missing,
+                soil_moisture: SensorStatus::Ok,
+            },
+            ttl: None,
+        };
+
+        assert_eq!(reading.reading_id(), "batch123#1704067200000");
+        assert_eq!(
+            reading.reading_id(),
+            generate_reading_id(&reading.batch_id, reading.timestamp_ms)
+        );
+    }
+}
@@ -1,9 +1,32 @@
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::operation::query::builders::QueryFluentBuilder;
+use aws_sdk_dynamodb::types::{AttributeValue, KeysAndAttributes, Select};
 use aws_sdk_dynamodb::Client as DynamoDbClient;
 use std::collections::HashMap;
+use std::future::Future;
 
 use crate::error::DatabaseError;
-use esp32_backend::shared::domain::{Reading, SensorStatus, SensorValues};
+use esp32_backend::shared::concurrency::bounded_map;
+use esp32_backend::shared::cursor::{GlobalIngestShardPosition, IngestTimePageToken};
+use esp32_backend::shared::domain::{
+    build_ts_batch, readings_gsi1pk, Reading, SensorStatus, SensorValues, READINGS_GSI1PK_SHARDS,
+};
+use esp32_backend::shared::query_params::SortOrder;
+
+/// Key condition selecting a device partition and a ts_batch range
+const READINGS_KEY_CONDITION: &str =
+    "hardware_id = :hw_id AND ts_batch BETWEEN :from_key AND :to_key";
+
+/// GSI on the readings table keyed by hardware_id and ingest_time_ms
+pub const INGEST_TIME_INDEX: &str = "IngestTimeIndex";
+
+/// Key condition selecting a device's readings ingested at or after `:since`
+const INGEST_TIME_KEY_CONDITION: &str = "hardware_id = :hw_id AND ingest_time_ms >= :since";
+
+/// GSI on the readings table keyed by a sharded gsi1pk and ingest_time_ms
+pub const GLOBAL_INGEST_TIME_INDEX: &str = "GlobalIngestTimeIndex";
+
+/// Key condition selecting every device's readings ingested at or after `:since`
+const GLOBAL_INGEST_TIME_KEY_CONDITION: &str = "gsi1pk = :gsi1pk AND ingest_time_ms >= :since";
 
 /// Response for readings query
 #[derive(Debug, Clone)]
@@ -15,7 +38,10 @@ pub struct ReadingsQueryResponse {
 /// Query readings for a device within a time range
 ///
 /// Uses partition key (hardware_id) and sort key range (ts_batch) for efficient querying.
-/// The ts_batch format is "{timestamp_ms:013}#{batch_id}" with zero-padded 13 digits.
+/// The ts_batch format is "{timestamp_ms:013}#{batch_id}", or
+/// "{timestamp_ms:013}#{seq:05}#{batch_id}" when the device sent a `seq` (see
+/// `build_ts_batch`). Either way the key starts with the zero-padded 13-digit
+/// timestamp, so the range bounds cover both forms.
 ///
 /// # Arguments
 /// * `client` - DynamoDB client
@@ -25,6 +51,10 @@ pub struct ReadingsQueryResponse {
 /// * `to_ms` - End of time range (epoch milliseconds, inclusive)
 /// * `limit` - Maximum number of readings to return (default 50, max 1000)
 /// * `cursor` - Optional pagination cursor from previous response
+/// * `order` - `Desc` for newest first, `Asc` for oldest first
+///
+/// The cursor is the last key read, which DynamoDB resumes after in either
+/// direction; follow-up pages must use the same `order` as the first.
 ///
 /// # Returns
 /// * `ReadingsQueryResponse` with readings and optional next_cursor
@@ -33,6 +63,7 @@ pub struct ReadingsQueryResponse {
 /// * Validates from_ms <= to_ms
 /// * Validates both timestamps are non-negative
 /// * Validates timestamps are within reasonable range (not too far in future)
+#[allow(clippy::too_many_arguments)]
 pub async fn query_readings(
     client: &DynamoDbClient,
     table_name: &str,
@@ -41,36 +72,11 @@ pub async fn query_readings(
     to_ms: i64,
     limit: Option<i32>,
     cursor: Option<String>,
+    order: SortOrder,
 ) -> Result<ReadingsQueryResponse, DatabaseError> {
     use esp32_backend::shared::cursor::{decode_readings_page_token, encode_readings_page_token};
 
-    // Validate timestamp bounds
-    if from_ms < 0 {
-        return Err(DatabaseError::Serialization(
-            "from_ms must be non-negative".to_string(),
-        ));
-    }
-
-    if to_ms < 0 {
-        return Err(DatabaseError::Serialization(
-            "to_ms must be non-negative".to_string(),
-        ));
-    }
-
-    if from_ms > to_ms {
-        return Err(DatabaseError::Serialization(
-            "from_ms must be less than or equal to to_ms".to_string(),
-        ));
-    }
-
-    // Validate timestamps are within reasonable range (not too far in future)
-    // Allow up to 1 year in the future to account for clock skew
-    let max_timestamp = chrono::Utc::now().timestamp_millis() + (365 * 24 * 60 * 60 * 1000);
-    if from_ms > max_timestamp || to_ms > max_timestamp {
-        return Err(DatabaseError::Serialization(
-            "Timestamps are too far in the future".to_string(),
-        ));
-    }
+    validate_query_range(from_ms, to_ms)?;
 
     // Validate and apply limit (default 50, max 1000)
     let limit = match limit {
@@ -84,31 +90,25 @@ pub async fn query_readings(
         None => 50,
     };
 
-    // Build sort key range: "{from_ms:013}#" to "{to_ms:013}#\uffff"
-    // Zero-pad to 13 digits for lexicographic sorting
-    // Append \uffff to to_ms to include all batch_ids at that timestamp
-    let from_key = format!("{:013}#", from_ms);
-    let to_key = format!("{:013}#\u{ffff}", to_ms);
-
-    // Build query
-    let mut query = client
-        .query()
-        .table_name(table_name)
-        .key_condition_expression("hardware_id = :hw_id AND ts_batch BETWEEN :from_key AND :to_key")
-        .expression_attribute_values(":hw_id", AttributeValue::S(hardware_id.to_string()))
-        .expression_attribute_values(":from_key", AttributeValue::S(from_key))
-        .expression_attribute_values(":to_key", AttributeValue::S(to_key))
-        .scan_index_forward(false) // Newest first (descending order)
-        .limit(limit);
-
-    // Add cursor if provided
-    if let Some(cursor_str) = cursor {
-        let cursor = decode_readings_page_token(&cursor_str)
-            .map_err(|e| DatabaseError::Serialization(format!("Invalid cursor: {}", e.message)))?;
-
-        let start_key = cursor_to_exclusive_start_key(&cursor);
-        query = query.set_exclusive_start_key(Some(start_key));
-    }
+    // Resume after the cursor's key if provided
+    let start_key = cursor
+        .map(|cursor_str| {
+            decode_readings_page_token(&cursor_str)
+                .map(|cursor| cursor_to_exclusive_start_key(&cursor))
+                .map_err(|e| DatabaseError::Serialization(format!("Invalid cursor: {}", e)))
+        })
+        .transpose()?;
+
+    let query = build_readings_query(
+        client,
+        table_name,
+        hardware_id,
+        from_ms,
+        to_ms,
+        limit,
+        start_key,
+        order,
+    );
 
     // Execute query
     let result = query
@@ -137,6 +137,569 @@ pub async fn query_readings(
     })
 }
 
+/// Build one page of the readings range query without sending it
+#[allow(clippy::too_many_arguments)]
+fn build_readings_query(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    from_ms: i64,
+    to_ms: i64,
+    limit: i32,
+    start_key: Option<HashMap<String, AttributeValue>>,
+    order: SortOrder,
+) -> QueryFluentBuilder {
+    let (from_key, to_key) = sort_key_range(from_ms, to_ms);
+
+    client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression(READINGS_KEY_CONDITION)
+        .expression_attribute_values(":hw_id", AttributeValue::S(hardware_id.to_string()))
+        .expression_attribute_values(":from_key", AttributeValue::S(from_key))
+        .expression_attribute_values(":to_key", AttributeValue::S(to_key))
+        .scan_index_forward(order.scan_index_forward())
+        .limit(limit)
+        .set_exclusive_start_key(start_key)
+}
+
+/// Response for readings query by ingest time
+#[derive(Debug, Clone)]
+pub struct IngestTimeQueryResponse {
+    pub readings: Vec<Reading>,
+    /// Ingest time of the last reading in the page, for the next `since_ingest`
+    pub last_ingest_time_ms: Option<i64>,
+    pub next_cursor: Option<String>,
+}
+
+/// Query readings for a device by the time they were ingested
+///
+/// Uses the `IngestTimeIndex` GSI (`hardware_id` partition key,
+/// `ingest_time_ms` sort key), so readings backfilled with old timestamps
+/// still appear after the readings ingested before them. The index projects
+/// keys only; the full items are fetched from the table afterwards. Results
+/// are in ascending ingest order.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the device_readings table
+/// * `hardware_id` - MAC address of the device (partition key)
+/// * `since_ingest_ms` - Earliest ingest time to return (epoch milliseconds, inclusive)
+/// * `limit` - Maximum number of readings to return
+/// * `cursor` - Optional ingest-time pagination cursor from previous response
+///
+/// # Returns
+/// * `IngestTimeQueryResponse` with readings, the last ingest time and optional next_cursor
+pub async fn query_by_ingest_time(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    since_ingest_ms: i64,
+    limit: i32,
+    cursor: Option<String>,
+) -> Result<IngestTimeQueryResponse, DatabaseError> {
+    use esp32_backend::shared::cursor::{
+        decode_ingest_time_page_token, encode_ingest_time_page_token,
+    };
+
+    if since_ingest_ms < 0 {
+        return Err(DatabaseError::Serialization(
+            "since_ingest must be non-negative".to_string(),
+        ));
+    }
+
+    let start_key = cursor
+        .map(|cursor_str| {
+            decode_ingest_time_page_token(&cursor_str)
+                .map(|cursor| ingest_time_cursor_to_exclusive_start_key(&cursor))
+                .map_err(|e| DatabaseError::Serialization(format!("Invalid cursor: {}", e)))
+        })
+        .transpose()?;
+
+    let result = build_ingest_time_query(
+        client,
+        table_name,
+        hardware_id,
+        since_ingest_ms,
+        limit,
+        start_key,
+    )
+    .send()
+    .await
+    .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    let keys: Vec<IngestTimePageToken> = result
+        .items
+        .unwrap_or_default()
+        .iter()
+        .filter_map(index_entry)
+        .collect();
+    let last_ingest_time_ms = keys.last().map(|key| key.ingest_time_ms);
+    let readings = batch_get_readings(client, table_name, &keys).await?;
+
+    let next_cursor = result.last_evaluated_key.and_then(|key| {
+        let key = index_entry(&key)?;
+        encode_ingest_time_page_token(&key.hardware_id, &key.ts_batch, key.ingest_time_ms).ok()
+    });
+
+    Ok(IngestTimeQueryResponse {
+        readings,
+        last_ingest_time_ms,
+        next_cursor,
+    })
+}
+
+/// Build one page of the ingest-time query without sending it
+fn build_ingest_time_query(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    since_ingest_ms: i64,
+    limit: i32,
+    start_key: Option<HashMap<String, AttributeValue>>,
+) -> QueryFluentBuilder {
+    client
+        .query()
+        .table_name(table_name)
+        .index_name(INGEST_TIME_INDEX)
+        .key_condition_expression(INGEST_TIME_KEY_CONDITION)
+        .expression_attribute_values(":hw_id", AttributeValue::S(hardware_id.to_string()))
+        .expression_attribute_values(":since", AttributeValue::N(since_ingest_ms.to_string()))
+        .scan_index_forward(true)
+        .limit(limit)
+        .set_exclusive_start_key(start_key)
+}
+
+/// Query readings across every device by the time they were ingested
+///
+/// Uses the `GlobalIngestTimeIndex` GSI (`gsi1pk` partition key, one of
+/// `READINGS_GSI1PK_SHARDS` shards picked from the hardware_id, and
+/// `ingest_time_ms` sort key). Every unfinished shard is queried in
+/// parallel, the pages are merged by ingest time, and the full items are
+/// then fetched from the table, since the index projects keys only. Meant
+/// for support debugging over short windows, not for bulk reads. Readings
+/// stored before the index existed have no `gsi1pk` and are not returned.
+/// Results are in ascending ingest order.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the device_readings table
+/// * `since_ingest_ms` - Earliest ingest time to return (epoch milliseconds, inclusive)
+/// * `limit` - Maximum number of readings to return
+/// * `cursor` - Optional fleet-wide ingest-time pagination cursor from previous response
+///
+/// # Returns
+/// * `IngestTimeQueryResponse` with readings, the last ingest time and optional next_cursor
+pub async fn query_recent_global(
+    client: &DynamoDbClient,
+    table_name: &str,
+    since_ingest_ms: i64,
+    limit: i32,
+    cursor: Option<String>,
+) -> Result<IngestTimeQueryResponse, DatabaseError> {
+    use esp32_backend::shared::cursor::{
+        decode_global_ingest_time_page_token, encode_global_ingest_time_page_token,
+    };
+
+    if since_ingest_ms < 0 {
+        return Err(DatabaseError::Serialization(
+            "since must be non-negative".to_string(),
+        ));
+    }
+
+    let positions = match cursor {
+        None => vec![GlobalIngestShardPosition::Start; READINGS_GSI1PK_SHARDS as usize],
+        Some(cursor_str) => {
+            let cursor = decode_global_ingest_time_page_token(&cursor_str)
+                .map_err(|e| DatabaseError::Serialization(format!("Invalid cursor: {}", e)))?;
+            if cursor.shards.len() != READINGS_GSI1PK_SHARDS as usize {
+                return Err(DatabaseError::Serialization(format!(
+                    "Invalid cursor: expected {} shards, found {}",
+                    READINGS_GSI1PK_SHARDS,
+                    cursor.shards.len()
+                )));
+            }
+            cursor.shards
+        }
+    };
+
+    let pages = bounded_map(
+        positions.iter().enumerate(),
+        positions.len(),
+        |(shard, position)| {
+            query_global_shard(
+                client,
+                table_name,
+                shard as u32,
+                position,
+                since_ingest_ms,
+                limit,
+            )
+        },
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let (keys, positions) = merge_shard_pages(positions, pages, limit.max(0) as usize);
+    let last_ingest_time_ms = keys.last().map(|key| key.ingest_time_ms);
+    let readings = batch_get_readings(client, table_name, &keys).await?;
+
+    let next_cursor = if positions
+        .iter()
+        .all(|position| matches!(position, GlobalIngestShardPosition::Done))
+    {
+        None
+    } else {
+        Some(
+            encode_global_ingest_time_page_token(&positions)
+                .map_err(|e| DatabaseError::Serialization(format!("{}", e)))?,
+        )
+    };
+
+    Ok(IngestTimeQueryResponse {
+        readings,
+        last_ingest_time_ms,
+        next_cursor,
+    })
+}
+
+/// Read one shard's next page of GlobalIngestTimeIndex keys
+///
+/// A shard that is already done isn't queried again.
+async fn query_global_shard(
+    client: &DynamoDbClient,
+    table_name: &str,
+    shard: u32,
+    position: &GlobalIngestShardPosition,
+    since_ingest_ms: i64,
+    limit: i32,
+) -> Result<ShardPage, DatabaseError> {
+    let start_key = match position {
+        GlobalIngestShardPosition::Start => None,
+        GlobalIngestShardPosition::After(after) => {
+            Some(global_ingest_cursor_to_exclusive_start_key(shard, after))
+        }
+        GlobalIngestShardPosition::Done => {
+            return Ok(ShardPage {
+                keys: Vec::new(),
+                exhausted: true,
+            })
+        }
+    };
+
+    let result =
+        build_recent_global_query(client, table_name, shard, since_ingest_ms, limit, start_key)
+            .send()
+            .await
+            .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    Ok(ShardPage {
+        keys: result
+            .items
+            .unwrap_or_default()
+            .iter()
+            .filter_map(index_entry)
+            .collect(),
+        exhausted: result.last_evaluated_key.is_none(),
+    })
+}
+
+/// One shard's page of GlobalIngestTimeIndex keys
+#[derive(Debug)]
+struct ShardPage {
+    keys: Vec<IngestTimePageToken>,
+    /// DynamoDB returned no LastEvaluatedKey, so the shard has nothing more
+    exhausted: bool,
+}
+
+/// Read the table and index key of an ingest-time index item
+fn index_entry(item: &HashMap<String, AttributeValue>) -> Option<IngestTimePageToken> {
+    Some(IngestTimePageToken {
+        hardware_id: item.get("hardware_id")?.as_s().ok()?.clone(),
+        ts_batch: item.get("ts_batch")?.as_s().ok()?.clone(),
+        ingest_time_ms: item.get("ingest_time_ms")?.as_n().ok()?.parse().ok()?,
+    })
+}
+
+/// Merge per-shard pages into one page of at most `limit` keys
+///
+/// Takes the key with the smallest ingest time across the shard heads
+/// (lowest shard first on a tie), so each shard's keys are consumed in the
+/// order DynamoDB returned them and its position can resume after the last
+/// one taken. A shard whose page was fully taken and had nothing after it
+/// is done; a shard none of whose keys were taken keeps its position.
+fn merge_shard_pages(
+    positions: Vec<GlobalIngestShardPosition>,
+    pages: Vec<ShardPage>,
+    limit: usize,
+) -> (Vec<IngestTimePageToken>, Vec<GlobalIngestShardPosition>) {
+    let mut taken_per_shard = vec![0usize; pages.len()];
+    let mut merged = Vec::new();
+
+    while merged.len() < limit {
+        let next = pages
+            .iter()
+            .enumerate()
+            .filter_map(|(shard, page)| {
+                let key = page.keys.get(taken_per_shard[shard])?;
+                Some((key.ingest_time_ms, shard))
+            })
+            .min();
+        let Some((_, shard)) = next else {
+            break;
+        };
+        merged.push(pages[shard].keys[taken_per_shard[shard]].clone());
+        taken_per_shard[shard] += 1;
+    }
+
+    let positions = positions
+        .into_iter()
+        .zip(pages)
+        .zip(taken_per_shard)
+        .map(|((position, page), taken)| {
+            if taken == page.keys.len() && page.exhausted {
+                GlobalIngestShardPosition::Done
+            } else if taken > 0 {
+                GlobalIngestShardPosition::After(page.keys[taken - 1].clone())
+            } else {
+                position
+            }
+        })
+        .collect();
+
+    (merged, positions)
+}
+
+/// Most keys a single BatchGetItem call accepts
+const BATCH_GET_MAX_KEYS: usize = 100;
+
+/// Calls made for one chunk before unprocessed keys become an error
+const BATCH_GET_MAX_ATTEMPTS: usize = 5;
+
+/// Fetch full readings for index keys, in the order of `keys`
+///
+/// Uses BatchGetItem in chunks of `BATCH_GET_MAX_KEYS`, re-requesting keys
+/// DynamoDB leaves unprocessed. Keys whose item is gone (expired by TTL
+/// since the index was read) are skipped.
+async fn batch_get_readings(
+    client: &DynamoDbClient,
+    table_name: &str,
+    keys: &[IngestTimePageToken],
+) -> Result<Vec<Reading>, DatabaseError> {
+    let mut found: HashMap<(String, String), Reading> = HashMap::new();
+
+    for chunk in keys.chunks(BATCH_GET_MAX_KEYS) {
+        let mut pending: Vec<HashMap<String, AttributeValue>> = chunk
+            .iter()
+            .map(|key| {
+                HashMap::from([
+                    (
+                        "hardware_id".to_string(),
+                        AttributeValue::S(key.hardware_id.clone()),
+                    ),
+                    (
+                        "ts_batch".to_string(),
+                        AttributeValue::S(key.ts_batch.clone()),
+                    ),
+                ])
+            })
+            .collect();
+
+        for _ in 0..BATCH_GET_MAX_ATTEMPTS {
+            if pending.is_empty() {
+                break;
+            }
+
+            let request = KeysAndAttributes::builder()
+                .set_keys(Some(pending))
+                .build()
+                .map_err(|e| {
+                    DatabaseError::DynamoDb(format!("Failed to build BatchGetItem keys: {}", e))
+                })?;
+
+            let result = client
+                .batch_get_item()
+                .request_items(table_name, request)
+                .send()
+                .await
+                .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+            let items = result
+                .responses
+                .and_then(|mut responses| responses.remove(table_name))
+                .unwrap_or_default();
+            for item in &items {
+                let ts_batch = item
+                    .get("ts_batch")
+                    .and_then(|v| v.as_s().ok())
+                    .cloned()
+                    .unwrap_or_default();
+                let reading = item_to_reading(item)?;
+                found.insert((reading.hardware_id.clone(), ts_batch), reading);
+            }
+
+            pending = result
+                .unprocessed_keys
+                .and_then(|mut unprocessed| unprocessed.remove(table_name))
+                .map(|unprocessed| unprocessed.keys)
+                .unwrap_or_default();
+        }
+
+        if !pending.is_empty() {
+            return Err(DatabaseError::DynamoDb(format!(
+                "BatchGetItem left {} keys unprocessed",
+                pending.len()
+            )));
+        }
+    }
+
+    Ok(keys
+        .iter()
+        .filter_map(|key| found.remove(&(key.hardware_id.clone(), key.ts_batch.clone())))
+        .collect())
+}
+
+/// Build one shard's page of the fleet-wide ingest-time query without sending it
+fn build_recent_global_query(
+    client: &DynamoDbClient,
+    table_name: &str,
+    shard: u32,
+    since_ingest_ms: i64,
+    limit: i32,
+    start_key: Option<HashMap<String, AttributeValue>>,
+) -> QueryFluentBuilder {
+    client
+        .query()
+        .table_name(table_name)
+        .index_name(GLOBAL_INGEST_TIME_INDEX)
+        .key_condition_expression(GLOBAL_INGEST_TIME_KEY_CONDITION)
+        .expression_attribute_values(":gsi1pk", AttributeValue::S(readings_gsi1pk(shard)))
+        .expression_attribute_values(":since", AttributeValue::N(since_ingest_ms.to_string()))
+        .scan_index_forward(true)
+        .limit(limit)
+        .set_exclusive_start_key(start_key)
+}
+
+/// Count readings for a device within a time range
+///
+/// Runs the same key-condition query as `query_readings` with `Select=COUNT`,
+/// so no items are read back. DynamoDB caps each page at 1 MB of evaluated
+/// data, so pages are followed until exhausted and their counts summed.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the device_readings table
+/// * `hardware_id` - MAC address of the device (partition key)
+/// * `from_ms` - Start of time range (epoch milliseconds, inclusive)
+/// * `to_ms` - End of time range (epoch milliseconds, inclusive)
+///
+/// # Returns
+/// * Total number of readings in the range
+pub async fn count_readings(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    from_ms: i64,
+    to_ms: i64,
+) -> Result<i64, DatabaseError> {
+    validate_query_range(from_ms, to_ms)?;
+
+    let (from_key, to_key) = sort_key_range(from_ms, to_ms);
+
+    sum_page_counts(|start_key| {
+        let query = client
+            .query()
+            .table_name(table_name)
+            .key_condition_expression(READINGS_KEY_CONDITION)
+            .expression_attribute_values(":hw_id", AttributeValue::S(hardware_id.to_string()))
+            .expression_attribute_values(":from_key", AttributeValue::S(from_key.clone()))
+            .expression_attribute_values(":to_key", AttributeValue::S(to_key.clone()))
+            .select(Select::Count)
+            .set_exclusive_start_key(start_key);
+
+        async move {
+            let result = query
+                .send()
+                .await
+                .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+            Ok((result.count, result.last_evaluated_key))
+        }
+    })
+    .await
+}
+
+/// Sum per-page counts, following `LastEvaluatedKey` until there are no more pages
+///
+/// `fetch_page` receives the exclusive start key for the page (None for the
+/// first page) and returns the page count and its last evaluated key.
+async fn sum_page_counts<F, Fut>(mut fetch_page: F) -> Result<i64, DatabaseError>
+where
+    F: FnMut(Option<HashMap<String, AttributeValue>>) -> Fut,
+    Fut: Future<Output = Result<(i32, Option<HashMap<String, AttributeValue>>), DatabaseError>>,
+{
+    let mut total: i64 = 0;
+    let mut start_key = None;
+
+    loop {
+        let (count, last_evaluated_key) = fetch_page(start_key).await?;
+        total += i64::from(count);
+
+        match last_evaluated_key {
+            Some(key) if !key.is_empty() => start_key = Some(key),
+            _ => return Ok(total),
+        }
+    }
+}
+
+/// Validate the time range shared by reading queries
+///
+/// * Validates from_ms <= to_ms
+/// * Validates both timestamps are non-negative
+/// * Validates timestamps are within reasonable range (not too far in future)
+fn validate_query_range(from_ms: i64, to_ms: i64) -> Result<(), DatabaseError> {
+    if from_ms < 0 {
+        return Err(DatabaseError::Serialization(
+            "from_ms must be non-negative".to_string(),
+        ));
+    }
+
+    if to_ms < 0 {
+        return Err(DatabaseError::Serialization(
+            "to_ms must be non-negative".to_string(),
+        ));
+    }
+
+    if from_ms > to_ms {
+        return Err(DatabaseError::Serialization(
+            "from_ms must be less than or equal to to_ms".to_string(),
+        ));
+    }
+
+    // Validate timestamps are within reasonable range (not too far in future)
+    // Allow up to 1 year in the future to account for clock skew
+    let max_timestamp = chrono::Utc::now().timestamp_millis() + (365 * 24 * 60 * 60 * 1000);
+    if from_ms > max_timestamp || to_ms > max_timestamp {
+        return Err(DatabaseError::Serialization(
+            "Timestamps are too far in the future".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Build sort key range: "{from_ms:013}#" to "{to_ms:013}#\uffff"
+///
+/// Zero-pad to 13 digits for lexicographic sorting. Append \uffff to to_ms
+/// to include all batch_ids at that timestamp.
+fn sort_key_range(from_ms: i64, to_ms: i64) -> (String, String) {
+    (
+        format!("{:013}#", from_ms),
+        format!("{:013}#\u{ffff}", to_ms),
+    )
+}
+
 /// Get the latest reading for a device
 ///
 /// Uses partition key (hardware_id) with ScanIndexForward=false and Limit=1
@@ -177,6 +740,100 @@ pub async fn get_latest_reading(
     }
 }
 
+/// Get a single reading by its timestamp and batch_id
+///
+/// Rebuilds the `ts_batch` sort key (see `reading_key`) and reads the item
+/// with GetItem, so no range query is needed.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the device_readings table
+/// * `hardware_id` - MAC address of the device (partition key)
+/// * `timestamp_ms` - Event time of the reading
+/// * `seq` - Sequence the device sent with the reading, if any
+/// * `batch_id` - Batch the reading was ingested in
+///
+/// # Returns
+/// * `Ok(Some(Reading))` - Reading found
+/// * `Ok(None)` - No reading stored under that key
+/// * `Err(DatabaseError)` - DynamoDB error occurred
+pub async fn get_reading(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    timestamp_ms: i64,
+    seq: Option<u32>,
+    batch_id: &str,
+) -> Result<Option<Reading>, DatabaseError> {
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .set_key(Some(reading_key(hardware_id, timestamp_ms, seq, batch_id)))
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result.item.as_ref().map(item_to_reading).transpose()
+}
+
+/// Primary key of a stored reading
+///
+/// The sort key is built exactly as ingestion writes it: the 13-digit
+/// zero-padded timestamp, `#`, then the batch_id (with the 5-digit `seq`
+/// segment in between when the device sent one).
+fn reading_key(
+    hardware_id: &str,
+    timestamp_ms: i64,
+    seq: Option<u32>,
+    batch_id: &str,
+) -> HashMap<String, AttributeValue> {
+    HashMap::from([
+        (
+            "hardware_id".to_string(),
+            AttributeValue::S(hardware_id.to_string()),
+        ),
+        (
+            "ts_batch".to_string(),
+            AttributeValue::S(build_ts_batch(timestamp_ms, seq, batch_id)),
+        ),
+    ])
+}
+
+/// Get the most recent readings for a device, newest first
+///
+/// Reads a single page of at most `limit` items from the device partition
+/// with ScanIndexForward=false; no time range is applied.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the device_readings table
+/// * `hardware_id` - MAC address of the device (partition key)
+/// * `limit` - Maximum number of readings to return
+pub async fn get_recent_readings(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    limit: i32,
+) -> Result<Vec<Reading>, DatabaseError> {
+    let result = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("hardware_id = :hw_id")
+        .expression_attribute_values(":hw_id", AttributeValue::S(hardware_id.to_string()))
+        .scan_index_forward(false) // Newest first
+        .limit(limit)
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result
+        .items
+        .unwrap_or_default()
+        .iter()
+        .map(item_to_reading)
+        .collect()
+}
+
 /// Convert DynamoDB item to Reading struct
 fn item_to_reading(item: &HashMap<String, AttributeValue>) -> Result<Reading, DatabaseError> {
     let batch_id = item
@@ -226,6 +883,33 @@ fn item_to_reading(item: &HashMap<String, AttributeValue>) -> Result<Reading, Da
         .ok_or_else(|| DatabaseError::Serialization("Missing sensor_status".to_string()))
         .and_then(attribute_value_to_sensor_status)?;
 
+    // Readings stored before buffering detection have no flag
+    let was_buffered = item
+        .get("was_buffered")
+        .and_then(|v| v.as_bool().ok())
+        .copied()
+        .unwrap_or(false);
+
+    let quality = item
+        .get("quality")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<f64>().ok());
+
+    let seq = item
+        .get("seq")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<u32>().ok());
+
+    let metadata = item
+        .get("metadata")
+        .and_then(|v| v.as_m().ok())
+        .map(|m| {
+            m.iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_s().ok()?.clone())))
+                .collect()
+        })
+        .unwrap_or_default();
+
     Ok(Reading {
         batch_id,
         hardware_id,
@@ -235,9 +919,25 @@ fn item_to_reading(item: &HashMap<String, AttributeValue>) -> Result<Reading, Da
         friendly_name,
         sensors,
         sensor_status,
+        was_buffered,
+        quality,
+        seq,
+        metadata,
     })
 }
 
+/// Keys of the `sensors` map that have a typed `SensorValues` field
+const TYPED_SENSOR_VALUES: [&str; 5] = [
+    "bme280_temp_c",
+    "ds18b20_temp_c",
+    "humidity_pct",
+    "pressure_hpa",
+    "soil_moisture_pct",
+];
+
+/// Keys of the `sensor_status` map that have a typed `SensorStatus` field
+const TYPED_SENSOR_STATUSES: [&str; 3] = ["bme280", "ds18b20", "soil_moisture"];
+
 /// Convert DynamoDB AttributeValue (Map) to SensorValues struct
 fn attribute_value_to_sensor_values(attr: &AttributeValue) -> Result<SensorValues, DatabaseError> {
     let sensor_map = attr
@@ -270,12 +970,20 @@ fn attribute_value_to_sensor_values(attr: &AttributeValue) -> Result<SensorValue
         .and_then(|v| v.as_n().ok())
         .and_then(|n| n.parse::<f64>().ok());
 
+    // Any other numeric entry is a sensor without a typed field
+    let extra_sensors = sensor_map
+        .iter()
+        .filter(|(name, _)| !TYPED_SENSOR_VALUES.contains(&name.as_str()))
+        .filter_map(|(name, v)| Some((name.clone(), v.as_n().ok()?.parse::<f64>().ok()?)))
+        .collect();
+
     Ok(SensorValues {
         bme280_temp_c,
         ds18b20_temp_c,
         humidity_pct,
         pressure_hpa,
         soil_moisture_pct,
+        extra_sensors,
     })
 }
 
@@ -303,10 +1011,17 @@ fn attribute_value_to_sensor_status(attr: &AttributeValue) -> Result<SensorStatu
         .ok_or_else(|| DatabaseError::Serialization("Missing soil_moisture status".to_string()))?
         .clone();
 
+    let extra_status = status_map
+        .iter()
+        .filter(|(name, _)| !TYPED_SENSOR_STATUSES.contains(&name.as_str()))
+        .filter_map(|(name, v)| Some((name.clone(), v.as_s().ok()?.clone())))
+        .collect();
+
     Ok(SensorStatus {
         bme280,
         ds18b20,
         soil_moisture,
+        extra_status,
     })
 }
 
@@ -326,9 +1041,177 @@ fn cursor_to_exclusive_start_key(
     key
 }
 
+/// Convert an ingest-time cursor to a GSI exclusive start key
+///
+/// A GSI start key carries the table key as well as the index key.
+fn ingest_time_cursor_to_exclusive_start_key(
+    cursor: &esp32_backend::shared::cursor::IngestTimePageToken,
+) -> HashMap<String, AttributeValue> {
+    let mut key =
+        cursor_to_exclusive_start_key(&esp32_backend::shared::cursor::ReadingsPageToken {
+            hardware_id: cursor.hardware_id.clone(),
+            ts_batch: cursor.ts_batch.clone(),
+        });
+    key.insert(
+        "ingest_time_ms".to_string(),
+        AttributeValue::N(cursor.ingest_time_ms.to_string()),
+    );
+    key
+}
+
+/// Convert a shard's fleet-wide ingest-time position to a GSI exclusive start key
+///
+/// The shard's gsi1pk isn't in the cursor; it follows from the shard's
+/// place in the token.
+fn global_ingest_cursor_to_exclusive_start_key(
+    shard: u32,
+    after: &IngestTimePageToken,
+) -> HashMap<String, AttributeValue> {
+    let mut key = ingest_time_cursor_to_exclusive_start_key(after);
+    key.insert(
+        "gsi1pk".to_string(),
+        AttributeValue::S(readings_gsi1pk(shard)),
+    );
+    key
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use esp32_backend::shared::cursor::{decode_readings_page_token, encode_readings_page_token};
+
+    const HW_ID: &str = "AA:BB:CC:DD:EE:FF";
+
+    fn test_client() -> DynamoDbClient {
+        let config = aws_sdk_dynamodb::config::Builder::new()
+            .behavior_version(aws_sdk_dynamodb::config::BehaviorVersion::latest())
+            .region(aws_sdk_dynamodb::config::Region::new("us-east-1"))
+            .build();
+        DynamoDbClient::from_conf(config)
+    }
+
+    /// Evaluate a built query against in-memory sort keys the way DynamoDB
+    /// does: BETWEEN range, ScanIndexForward order, resume after
+    /// ExclusiveStartKey, then Limit. Returns the page and LastEvaluatedKey.
+    fn run_query(
+        sort_keys: &[String],
+        query: &QueryFluentBuilder,
+    ) -> (Vec<String>, Option<String>) {
+        let input = query.as_input();
+        let values = input.get_expression_attribute_values().as_ref().unwrap();
+        let from_key = values[":from_key"].as_s().unwrap();
+        let to_key = values[":to_key"].as_s().unwrap();
+        let forward = input.get_scan_index_forward().unwrap_or(true);
+        let limit = input.get_limit().unwrap() as usize;
+        let start_after = input
+            .get_exclusive_start_key()
+            .as_ref()
+            .map(|key| key["ts_batch"].as_s().unwrap().clone());
+
+        let mut keys: Vec<String> = sort_keys
+            .iter()
+            .filter(|k| k.as_str() >= from_key.as_str() && k.as_str() <= to_key.as_str())
+            .cloned()
+            .collect();
+        keys.sort();
+        if !forward {
+            keys.reverse();
+        }
+        if let Some(start) = start_after {
+            keys.retain(|k| if forward { *k > start } else { *k < start });
+        }
+
+        let has_more = keys.len() > limit;
+        keys.truncate(limit);
+        let last_key = if has_more { keys.last().cloned() } else { None };
+        (keys, last_key)
+    }
+
+    /// Page through every reading in the range, passing each page's cursor to the next
+    fn read_all_pages(sort_keys: &[String], order: SortOrder) -> Vec<String> {
+        let client = test_client();
+        let mut read = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let start_key = cursor
+                .as_deref()
+                .map(|c| cursor_to_exclusive_start_key(&decode_readings_page_token(c).unwrap()));
+            let query =
+                build_readings_query(&client, "readings", HW_ID, 0, 10_000, 2, start_key, order);
+
+            let (page, last_key) = run_query(sort_keys, &query);
+            read.extend(page);
+
+            match last_key {
+                Some(ts_batch) => {
+                    cursor = Some(encode_readings_page_token(HW_ID, &ts_batch).unwrap())
+                }
+                None => break,
+            }
+        }
+
+        read
+    }
+
+    fn sort_keys() -> Vec<String> {
+        [3000, 1000, 5000, 2000, 4000]
+            .iter()
+            .map(|ts| format!("{:013}#batch-{}", ts, ts))
+            .collect()
+    }
+
+    #[test]
+    fn test_build_readings_query_order() {
+        let client = test_client();
+
+        let asc = build_readings_query(&client, "readings", HW_ID, 0, 1, 10, None, SortOrder::Asc);
+        assert_eq!(asc.as_input().get_scan_index_forward(), &Some(true));
+
+        let desc =
+            build_readings_query(&client, "readings", HW_ID, 0, 1, 10, None, SortOrder::Desc);
+        assert_eq!(desc.as_input().get_scan_index_forward(), &Some(false));
+    }
+
+    #[test]
+    fn test_asc_returns_increasing_timestamps() {
+        let client = test_client();
+        let query = build_readings_query(
+            &client,
+            "readings",
+            HW_ID,
+            0,
+            10_000,
+            10,
+            None,
+            SortOrder::Asc,
+        );
+
+        let (page, last_key) = run_query(&sort_keys(), &query);
+
+        let mut expected = sort_keys();
+        expected.sort();
+        assert_eq!(page, expected);
+        assert_eq!(last_key, None);
+    }
+
+    #[test]
+    fn test_asc_pagination_continues_after_cursor() {
+        let mut expected = sort_keys();
+        expected.sort();
+
+        // Three pages of two, in increasing order with nothing skipped or repeated
+        assert_eq!(read_all_pages(&sort_keys(), SortOrder::Asc), expected);
+    }
+
+    #[test]
+    fn test_desc_pagination_continues_after_cursor() {
+        let mut expected = sort_keys();
+        expected.sort();
+        expected.reverse();
+
+        assert_eq!(read_all_pages(&sort_keys(), SortOrder::Desc), expected);
+    }
 
     #[test]
     fn test_attribute_value_to_sensor_values_complete() {
@@ -401,6 +1284,63 @@ mod tests {
         assert_eq!(status.soil_moisture, "error");
     }
 
+    #[test]
+    fn test_item_to_reading_keeps_extra_sensors() {
+        let mut sensor_map = HashMap::new();
+        sensor_map.insert(
+            "soil_moisture_pct".to_string(),
+            AttributeValue::N("41".to_string()),
+        );
+        sensor_map.insert(
+            "sht31_temp_c".to_string(),
+            AttributeValue::N("21.4".to_string()),
+        );
+
+        let mut status_map = HashMap::new();
+        for (name, value) in [
+            ("bme280", "missing"),
+            ("ds18b20", "missing"),
+            ("soil_moisture", "ok"),
+            ("sht31", "ok"),
+        ] {
+            status_map.insert(name.to_string(), AttributeValue::S(value.to_string()));
+        }
+
+        let mut item = HashMap::new();
+        for (name, value) in [
+            ("hardware_id", "AA:BB:CC:DD:EE:FF"),
+            ("batch_id", "batch_1"),
+            ("boot_id", "7c9e6679-7425-40de-944b-e07fc1f90ae7"),
+            ("firmware_version", "1.0.16"),
+        ] {
+            item.insert(name.to_string(), AttributeValue::S(value.to_string()));
+        }
+        item.insert(
+            "timestamp_ms".to_string(),
+            AttributeValue::N("1704067800000".to_string()),
+        );
+        item.insert("sensors".to_string(), AttributeValue::M(sensor_map));
+        item.insert("sensor_status".to_string(), AttributeValue::M(status_map));
+
+        let reading = item_to_reading(&item).unwrap();
+
+        assert_eq!(reading.sensors.soil_moisture_pct, Some(41.0));
+        assert_eq!(reading.sensors.extra_sensors.len(), 1);
+        assert_eq!(
+            reading.sensors.extra_sensors.get("sht31_temp_c"),
+            Some(&21.4)
+        );
+        assert_eq!(reading.sensor_status.extra_status.len(), 1);
+        assert_eq!(
+            reading
+                .sensor_status
+                .extra_status
+                .get("sht31")
+                .map(String::as_str),
+            Some("ok")
+        );
+    }
+
     #[test]
     fn test_attribute_value_to_sensor_status_missing_field() {
         let mut status_map = HashMap::new();
@@ -466,6 +1406,10 @@ mod tests {
         );
         item.insert("sensors".to_string(), AttributeValue::M(sensor_map));
         item.insert("sensor_status".to_string(), AttributeValue::M(status_map));
+        item.insert(
+            "expiration_time".to_string(),
+            AttributeValue::N("1712092200".to_string()),
+        );
 
         let reading = item_to_reading(&item).unwrap();
 
@@ -478,6 +1422,10 @@ mod tests {
         assert_eq!(reading.sensors.bme280_temp_c, Some(22.5));
         assert_eq!(reading.sensors.humidity_pct, Some(45.2));
         assert_eq!(reading.sensor_status.bme280, "ok");
+
+        // The TTL attribute is storage-only and never reaches API responses
+        let json = serde_json::to_string(&reading).unwrap();
+        assert!(!json.contains("expiration_time"));
     }
 
     #[test]
@@ -524,6 +1472,16 @@ mod tests {
         let reading = item_to_reading(&item).unwrap();
 
         assert_eq!(reading.friendly_name, None);
+        assert_eq!(reading.quality, None);
+
+        item.insert("quality".to_string(), AttributeValue::N("0.85".to_string()));
+        let reading = item_to_reading(&item).unwrap();
+        assert_eq!(reading.quality, Some(0.85));
+        assert_eq!(reading.seq, None);
+
+        item.insert("seq".to_string(), AttributeValue::N("12".to_string()));
+        let reading = item_to_reading(&item).unwrap();
+        assert_eq!(reading.seq, Some(12));
     }
 
     #[test]
@@ -546,6 +1504,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reading_key_pads_timestamp() {
+        let key = reading_key("AA:BB:CC:DD:EE:FF", 1704067800000, None, "batch_123");
+
+        assert_eq!(
+            key.get("hardware_id").unwrap().as_s().unwrap(),
+            "AA:BB:CC:DD:EE:FF"
+        );
+        assert_eq!(
+            key.get("ts_batch").unwrap().as_s().unwrap(),
+            "1704067800000#batch_123"
+        );
+
+        // Early timestamps are zero-padded to 13 digits
+        let key = reading_key("AA:BB:CC:DD:EE:FF", 42, None, "batch_123");
+        assert_eq!(
+            key.get("ts_batch").unwrap().as_s().unwrap(),
+            "0000000000042#batch_123"
+        );
+    }
+
+    #[test]
+    fn test_reading_key_includes_seq() {
+        let key = reading_key("AA:BB:CC:DD:EE:FF", 1704067800000, Some(7), "batch_123");
+        assert_eq!(
+            key.get("ts_batch").unwrap().as_s().unwrap(),
+            "1704067800000#00007#batch_123"
+        );
+    }
+
     #[test]
     fn test_cursor_to_exclusive_start_key() {
         use esp32_backend::shared::cursor::ReadingsPageToken;
@@ -568,6 +1556,222 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_ingest_time_query_key_condition() {
+        let client = test_client();
+        let query = build_ingest_time_query(&client, "readings", HW_ID, 1704067800000, 25, None);
+        let input = query.as_input();
+
+        assert_eq!(input.get_index_name().as_deref(), Some(INGEST_TIME_INDEX));
+        assert_eq!(
+            input.get_key_condition_expression().as_deref(),
+            Some("hardware_id = :hw_id AND ingest_time_ms >= :since")
+        );
+        let values = input.get_expression_attribute_values().as_ref().unwrap();
+        assert_eq!(values[":hw_id"].as_s().unwrap(), HW_ID);
+        assert_eq!(values[":since"].as_n().unwrap(), "1704067800000");
+        assert_eq!(input.get_scan_index_forward(), &Some(true));
+        assert_eq!(input.get_limit(), &Some(25));
+        assert_eq!(input.get_exclusive_start_key(), &None);
+    }
+
+    #[test]
+    fn test_ingest_time_cursor_round_trip() {
+        use esp32_backend::shared::cursor::{
+            decode_ingest_time_page_token, encode_ingest_time_page_token,
+        };
+
+        // LastEvaluatedKey of a GSI page: table key plus index key
+        let last_evaluated_key = HashMap::from([
+            (
+                "hardware_id".to_string(),
+                AttributeValue::S(HW_ID.to_string()),
+            ),
+            (
+                "ts_batch".to_string(),
+                AttributeValue::S("0001704060000000#batch-7".to_string()),
+            ),
+            (
+                "ingest_time_ms".to_string(),
+                AttributeValue::N("1704067805000".to_string()),
+            ),
+        ]);
+
+        let cursor = encode_ingest_time_page_token(
+            HW_ID,
+            last_evaluated_key["ts_batch"].as_s().unwrap(),
+            1704067805000,
+        )
+        .unwrap();
+        let start_key = ingest_time_cursor_to_exclusive_start_key(
+            &decode_ingest_time_page_token(&cursor).unwrap(),
+        );
+        assert_eq!(start_key, last_evaluated_key);
+
+        let client = test_client();
+        let query =
+            build_ingest_time_query(&client, "readings", HW_ID, 0, 25, Some(start_key.clone()));
+        assert_eq!(query.as_input().get_exclusive_start_key(), &Some(start_key));
+    }
+
+    #[test]
+    fn test_build_recent_global_query_key_condition() {
+        let client = test_client();
+        let query = build_recent_global_query(&client, "readings", 3, 1704067800000, 100, None);
+        let input = query.as_input();
+
+        assert_eq!(
+            input.get_index_name().as_deref(),
+            Some(GLOBAL_INGEST_TIME_INDEX)
+        );
+        assert_eq!(
+            input.get_key_condition_expression().as_deref(),
+            Some("gsi1pk = :gsi1pk AND ingest_time_ms >= :since")
+        );
+        let values = input.get_expression_attribute_values().as_ref().unwrap();
+        assert_eq!(values[":gsi1pk"].as_s().unwrap(), "readings#3");
+        assert_eq!(values[":since"].as_n().unwrap(), "1704067800000");
+        assert_eq!(input.get_scan_index_forward(), &Some(true));
+        assert_eq!(input.get_limit(), &Some(100));
+        assert_eq!(input.get_exclusive_start_key(), &None);
+    }
+
+    #[test]
+    fn test_global_ingest_cursor_round_trip() {
+        use esp32_backend::shared::cursor::{
+            decode_global_ingest_time_page_token, encode_global_ingest_time_page_token,
+        };
+
+        // LastEvaluatedKey of a GlobalIngestTimeIndex page: table key plus index key
+        let last_evaluated_key = HashMap::from([
+            (
+                "hardware_id".to_string(),
+                AttributeValue::S(HW_ID.to_string()),
+            ),
+            (
+                "ts_batch".to_string(),
+                AttributeValue::S("0001704060000000#batch-7".to_string()),
+            ),
+            (
+                "gsi1pk".to_string(),
+                AttributeValue::S("readings#1".to_string()),
+            ),
+            (
+                "ingest_time_ms".to_string(),
+                AttributeValue::N("1704067805000".to_string()),
+            ),
+        ]);
+
+        let after = index_entry(&last_evaluated_key).unwrap();
+        let cursor = encode_global_ingest_time_page_token(&[
+            GlobalIngestShardPosition::Done,
+            GlobalIngestShardPosition::After(after),
+        ])
+        .unwrap();
+        let decoded = decode_global_ingest_time_page_token(&cursor).unwrap();
+        let GlobalIngestShardPosition::After(after) = &decoded.shards[1] else {
+            panic!("expected shard 1 to resume after a key");
+        };
+        let start_key = global_ingest_cursor_to_exclusive_start_key(1, after);
+        assert_eq!(start_key, last_evaluated_key);
+
+        let client = test_client();
+        let query =
+            build_recent_global_query(&client, "readings", 1, 0, 100, Some(start_key.clone()));
+        assert_eq!(query.as_input().get_exclusive_start_key(), &Some(start_key));
+    }
+
+    fn index_key(hardware_id: &str, ingest_time_ms: i64) -> IngestTimePageToken {
+        IngestTimePageToken {
+            hardware_id: hardware_id.to_string(),
+            ts_batch: format!("{:013}#batch", ingest_time_ms),
+            ingest_time_ms,
+        }
+    }
+
+    fn times(keys: &[IngestTimePageToken]) -> Vec<i64> {
+        keys.iter().map(|key| key.ingest_time_ms).collect()
+    }
+
+    #[test]
+    fn test_merge_shard_pages_orders_by_ingest_time_up_to_limit() {
+        let pages = vec![
+            ShardPage {
+                keys: vec![index_key("A", 10), index_key("A", 40)],
+                exhausted: true,
+            },
+            ShardPage {
+                keys: vec![index_key("B", 20), index_key("B", 30)],
+                exhausted: false,
+            },
+            ShardPage {
+                keys: vec![index_key("C", 50)],
+                exhausted: true,
+            },
+        ];
+        let positions = vec![GlobalIngestShardPosition::Start; 3];
+
+        let (merged, positions) = merge_shard_pages(positions, pages, 3);
+
+        assert_eq!(times(&merged), vec![10, 20, 30]);
+        // Shard 0 still has 40 to return from the same page
+        assert!(
+            matches!(&positions[0], GlobalIngestShardPosition::After(key) if key.ingest_time_ms == 10)
+        );
+        // Shard 1's page was all taken but DynamoDB had more
+        assert!(
+            matches!(&positions[1], GlobalIngestShardPosition::After(key) if key.ingest_time_ms == 30)
+        );
+        // Nothing was taken from shard 2, so it starts over
+        assert!(matches!(positions[2], GlobalIngestShardPosition::Start));
+    }
+
+    #[test]
+    fn test_merge_shard_pages_marks_exhausted_shards_done() {
+        let pages = vec![
+            ShardPage {
+                keys: vec![index_key("A", 10)],
+                exhausted: true,
+            },
+            ShardPage {
+                keys: Vec::new(),
+                exhausted: true,
+            },
+        ];
+        let positions = vec![
+            GlobalIngestShardPosition::Start,
+            GlobalIngestShardPosition::Done,
+        ];
+
+        let (merged, positions) = merge_shard_pages(positions, pages, 25);
+
+        assert_eq!(times(&merged), vec![10]);
+        assert!(positions
+            .iter()
+            .all(|position| matches!(position, GlobalIngestShardPosition::Done)));
+    }
+
+    #[test]
+    fn test_merge_shard_pages_breaks_ties_by_shard() {
+        let pages = vec![
+            ShardPage {
+                keys: vec![index_key("A", 10)],
+                exhausted: true,
+            },
+            ShardPage {
+                keys: vec![index_key("B", 10)],
+                exhausted: true,
+            },
+        ];
+
+        let (merged, positions) =
+            merge_shard_pages(vec![GlobalIngestShardPosition::Start; 2], pages, 1);
+
+        assert_eq!(merged[0].hardware_id, "A");
+        assert!(matches!(positions[0], GlobalIngestShardPosition::Done));
+        assert!(matches!(positions[1], GlobalIngestShardPosition::Start));
+    }
+
     #[test]
     fn test_ts_batch_format_sortability() {
         // Verify that zero-padded timestamps sort correctly lexicographically
@@ -625,6 +1829,69 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sum_page_counts_across_pages() {
+        // 2,500 readings returned as pages of 1,000, 1,000, and 500
+        let total_readings = 2500;
+        let page_size = 1000;
+        let mut calls = 0;
+
+        let count = sum_page_counts(|start_key| {
+            calls += 1;
+            let offset = start_key
+                .and_then(|key| key.get("ts_batch")?.as_s().ok()?.parse::<i32>().ok())
+                .unwrap_or(0);
+            let page_count = (total_readings - offset).min(page_size);
+            let next_offset = offset + page_count;
+
+            let last_evaluated_key = (next_offset < total_readings).then(|| {
+                let mut key = HashMap::new();
+                key.insert(
+                    "ts_batch".to_string(),
+                    AttributeValue::S(next_offset.to_string()),
+                );
+                key
+            });
+
+            async move { Ok((page_count, last_evaluated_key)) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(count, 2500);
+        assert_eq!(calls, 3);
+    }
+
+    #[tokio::test]
+    async fn test_sum_page_counts_empty_range() {
+        let count = sum_page_counts(|_| async { Ok((0, None)) }).await.unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sum_page_counts_propagates_errors() {
+        let result = sum_page_counts(|start_key| async move {
+            match start_key {
+                None => {
+                    let mut key = HashMap::new();
+                    key.insert("ts_batch".to_string(), AttributeValue::S("1".to_string()));
+                    Ok((1000, Some(key)))
+                }
+                Some(_) => Err(DatabaseError::DynamoDb("throttled".to_string())),
+            }
+        })
+        .await;
+
+        assert!(matches!(result, Err(DatabaseError::DynamoDb(_))));
+    }
+
+    #[test]
+    fn test_count_readings_rejects_invalid_range() {
+        assert!(validate_query_range(1000, 500).is_err());
+        assert!(validate_query_range(-1, 500).is_err());
+        assert!(validate_query_range(0, 500).is_ok());
+    }
+
     // Note: Integration tests for query_readings and get_latest_reading
     // require DynamoDB Local and are in the integration test suite
 }
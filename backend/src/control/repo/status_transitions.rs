@@ -0,0 +1,200 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::shared::plant_insights::HealthCategory;
+use esp32_backend::shared::status_transition::{detect_transition, StatusTransition};
+
+/// Append a status transition for a device
+///
+/// Items are keyed by hardware_id (partition key) and at_ms (sort key).
+/// `from` is omitted for a device's first observation.
+pub async fn append_transition(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    transition: &StatusTransition,
+) -> Result<(), DatabaseError> {
+    client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(transition_to_item(hardware_id, transition)))
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    Ok(())
+}
+
+/// Get the most recent status transition for a device
+///
+/// # Returns
+/// * `Ok(Some(StatusTransition))` - Latest transition found
+/// * `Ok(None)` - Device has never been evaluated
+pub async fn get_latest_transition(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+) -> Result<Option<StatusTransition>, DatabaseError> {
+    let mut transitions = list_transitions(client, table_name, hardware_id, 1).await?;
+    Ok(transitions.pop())
+}
+
+/// List status transitions for a device, newest first
+pub async fn list_transitions(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    limit: i32,
+) -> Result<Vec<StatusTransition>, DatabaseError> {
+    let result = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("hardware_id = :hw_id")
+        .expression_attribute_values(":hw_id", AttributeValue::S(hardware_id.to_string()))
+        .scan_index_forward(false) // Newest first
+        .limit(limit)
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result
+        .items
+        .unwrap_or_default()
+        .iter()
+        .map(item_to_transition)
+        .collect()
+}
+
+/// Record a device's current health category
+///
+/// Compares against the latest stored transition and appends a new one only
+/// when the category changed (or the device has no history yet).
+///
+/// # Returns
+/// * The transition that was appended, if any
+pub async fn record_health_category(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    current: HealthCategory,
+    now_ms: i64,
+) -> Result<Option<StatusTransition>, DatabaseError> {
+    let previous = get_latest_transition(client, table_name, hardware_id)
+        .await?
+        .map(|t| t.to);
+
+    let transition = detect_transition(previous, current, now_ms);
+    if let Some(ref transition) = transition {
+        append_transition(client, table_name, hardware_id, transition).await?;
+    }
+
+    Ok(transition)
+}
+
+/// Convert a StatusTransition to a DynamoDB item
+fn transition_to_item(
+    hardware_id: &str,
+    transition: &StatusTransition,
+) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert(
+        "hardware_id".to_string(),
+        AttributeValue::S(hardware_id.to_string()),
+    );
+    item.insert(
+        "at_ms".to_string(),
+        AttributeValue::N(transition.at_ms.to_string()),
+    );
+    item.insert(
+        "to".to_string(),
+        AttributeValue::S(transition.to.as_str().to_string()),
+    );
+    if let Some(from) = transition.from {
+        item.insert(
+            "from".to_string(),
+            AttributeValue::S(from.as_str().to_string()),
+        );
+    }
+    item
+}
+
+/// Convert a DynamoDB item to a StatusTransition
+fn item_to_transition(
+    item: &HashMap<String, AttributeValue>,
+) -> Result<StatusTransition, DatabaseError> {
+    let parse_category = |name: &str| -> Result<Option<HealthCategory>, DatabaseError> {
+        item.get(name)
+            .and_then(|v| v.as_s().ok())
+            .map(|s| {
+                HealthCategory::parse(s).ok_or_else(|| {
+                    DatabaseError::Serialization(format!("Invalid {} category: {}", name, s))
+                })
+            })
+            .transpose()
+    };
+
+    let at_ms = item
+        .get("at_ms")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+        .ok_or_else(|| DatabaseError::Serialization("Missing or invalid at_ms".to_string()))?;
+
+    let to = parse_category("to")?
+        .ok_or_else(|| DatabaseError::Serialization("Missing to".to_string()))?;
+
+    Ok(StatusTransition {
+        from: parse_category("from")?,
+        to,
+        at_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_item_round_trip() {
+        let transition = StatusTransition {
+            from: Some(HealthCategory::Healthy),
+            to: HealthCategory::Missing,
+            at_ms: 1704067200000,
+        };
+
+        let item = transition_to_item("AA:BB:CC:DD:EE:FF", &transition);
+        assert_eq!(
+            item.get("hardware_id").unwrap().as_s().unwrap(),
+            "AA:BB:CC:DD:EE:FF"
+        );
+        assert_eq!(item.get("from").unwrap().as_s().unwrap(), "healthy");
+
+        assert_eq!(item_to_transition(&item).unwrap(), transition);
+    }
+
+    #[test]
+    fn test_first_observation_item_omits_from() {
+        let transition = StatusTransition {
+            from: None,
+            to: HealthCategory::Healthy,
+            at_ms: 1704067200000,
+        };
+
+        let item = transition_to_item("AA:BB:CC:DD:EE:FF", &transition);
+        assert!(!item.contains_key("from"));
+        assert_eq!(item_to_transition(&item).unwrap(), transition);
+    }
+
+    #[test]
+    fn test_item_to_transition_invalid_category() {
+        let mut item = HashMap::new();
+        item.insert("at_ms".to_string(), AttributeValue::N("1".to_string()));
+        item.insert("to".to_string(), AttributeValue::S("unknown".to_string()));
+
+        assert!(matches!(
+            item_to_transition(&item),
+            Err(DatabaseError::Serialization(_))
+        ));
+    }
+}
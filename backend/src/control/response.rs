@@ -0,0 +1,167 @@
+use lambda_http::{Body, Response};
+use serde::Serialize;
+
+use crate::error::ValidationError;
+
+/// Query parameter that requests pretty-printed JSON
+pub const PRETTY_PARAM: &str = "pretty";
+
+/// Query parameter that selects the case of JSON object keys
+pub const CASE_PARAM: &str = "case";
+
+/// Case used for JSON object keys in responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyCase {
+    /// Field names as declared on the response structs (`first_registered_at`)
+    #[default]
+    Snake,
+    /// Field names in camelCase (`firstRegisteredAt`)
+    Camel,
+}
+
+/// Parse the `case` query parameter
+///
+/// Missing means `snake`; values other than `snake` and `camel` are rejected
+/// so a typo doesn't silently fall back to the default.
+pub fn parse_key_case(value: Option<&str>) -> Result<KeyCase, ValidationError> {
+    match value {
+        None | Some("snake") => Ok(KeyCase::Snake),
+        Some("camel") => Ok(KeyCase::Camel),
+        Some(_) => Err(ValidationError::InvalidValue(CASE_PARAM.to_string())),
+    }
+}
+
+/// Whether a request asked for pretty-printed JSON
+///
+/// Only honoured when debug endpoints are enabled, so production responses
+/// stay compact regardless of the query string.
+pub fn wants_pretty(value: Option<&str>, debug_endpoints_enabled: bool) -> bool {
+    debug_endpoints_enabled && value == Some("true")
+}
+
+/// A response body with a camelCase mirror, used for `?case=camel`
+///
+/// The mirror borrows the body's fields into a struct marked
+/// `#[serde(rename_all = "camelCase")]`, so only field names change. Keys of
+/// maps inside the body (sensor names, feature flags) are data and keep the
+/// case they were registered with.
+pub trait CamelCaseMirror {
+    type Camel<'a>: Serialize
+    where
+        Self: 'a;
+
+    fn camel(&self) -> Self::Camel<'_>;
+}
+
+/// Serialize a response body with its field names in the requested case
+pub fn to_json_string<T>(body: &T, case: KeyCase) -> serde_json::Result<String>
+where
+    T: Serialize + CamelCaseMirror,
+{
+    match case {
+        KeyCase::Snake => serde_json::to_string(body),
+        KeyCase::Camel => serde_json::to_string(&body.camel()),
+    }
+}
+
+/// Re-serialize a JSON response body with `serde_json::to_string_pretty`
+///
+/// Responses that are not `application/json` text, or whose body does not
+/// parse, are returned unchanged. Object keys come out in sorted order.
+pub fn format_json_response(mut response: Response<Body>, pretty: bool) -> Response<Body> {
+    if !pretty {
+        return response;
+    }
+
+    let is_json = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let pretty_body = match response.body() {
+        Body::Text(text) => serde_json::from_str::<serde_json::Value>(text)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok()),
+        _ => None,
+    };
+
+    if let Some(body) = pretty_body {
+        *response.body_mut() = Body::from(body);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_response(body: &str) -> Response<Body> {
+        Response::builder()
+            .status(200)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn body_text(response: &Response<Body>) -> String {
+        match response.body() {
+            Body::Text(text) => text.clone(),
+            _ => panic!("Expected text body"),
+        }
+    }
+
+    #[test]
+    fn test_wants_pretty() {
+        assert!(wants_pretty(Some("true"), true));
+        assert!(!wants_pretty(Some("true"), false));
+        assert!(!wants_pretty(Some("1"), true));
+        assert!(!wants_pretty(None, true));
+    }
+
+    #[test]
+    fn test_pretty_flag_indents_output() {
+        let response = format_json_response(json_response(r#"{"count":3,"items":[1,2]}"#), true);
+        let body = body_text(&response);
+
+        assert_eq!(
+            body,
+            "{\n  \"count\": 3,\n  \"items\": [\n    1,\n    2\n  ]\n}"
+        );
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn test_default_is_compact() {
+        let compact = r#"{"count":3,"items":[1,2]}"#;
+        let response = format_json_response(json_response(compact), false);
+
+        assert_eq!(body_text(&response), compact);
+    }
+
+    #[test]
+    fn test_non_json_response_unchanged() {
+        let response = Response::builder().status(304).body(Body::Empty).unwrap();
+
+        let response = format_json_response(response, true);
+        assert!(matches!(response.body(), Body::Empty));
+    }
+
+    #[test]
+    fn test_parse_key_case() {
+        assert_eq!(parse_key_case(None).unwrap(), KeyCase::Snake);
+        assert_eq!(parse_key_case(Some("snake")).unwrap(), KeyCase::Snake);
+        assert_eq!(parse_key_case(Some("camel")).unwrap(), KeyCase::Camel);
+        assert!(matches!(
+            parse_key_case(Some("Camel")),
+            Err(ValidationError::InvalidValue(field)) if field == "case"
+        ));
+    }
+}
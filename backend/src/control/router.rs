@@ -1,14 +1,36 @@
+use esp32_backend::shared::observability::{make_request_span, record_response};
 use lambda_http::{http::Method, Body, Request, RequestExt, Response};
-use tracing::{info, warn};
+use tracing::{info, warn, Instrument};
 
 use super::config::ControlConfig;
 use super::cors;
 use super::error::ApiError;
 use super::handlers;
+use super::response;
 
+/// Route a request inside a span carrying its request_id, route and hardware_id
+///
+/// The span records the response status and error code on the way out.
 pub async fn route_request(
     event: Request,
     config: &ControlConfig,
+) -> Result<Response<Body>, lambda_http::Error> {
+    let path = normalize_path(event.uri().path());
+    let request_id = event.lambda_context().request_id.clone();
+    let route = format!("{} {}", event.method(), route_template(&path));
+    let span = make_request_span(&request_id, &route, hardware_id_from_path(&path));
+
+    let response = dispatch_request(event, config)
+        .instrument(span.clone())
+        .await?;
+    record_response(&span, &response);
+
+    Ok(response)
+}
+
+async fn dispatch_request(
+    event: Request,
+    config: &ControlConfig,
 ) -> Result<Response<Body>, lambda_http::Error> {
     let path = normalize_path(event.uri().path());
     let method = event.method();
@@ -30,6 +52,18 @@ pub async fn route_request(
         return Ok(cors::preflight_response());
     }
 
+    let query_params = event.query_string_parameters();
+    let pretty = response::wants_pretty(
+        query_params.first(response::PRETTY_PARAM),
+        config.debug_endpoints_enabled,
+    );
+    // Handlers that support camelCase read `case` themselves; a bad value is
+    // rejected on every route so a typo never passes silently
+    if let Err(e) = response::parse_key_case(query_params.first(response::CASE_PARAM)) {
+        let response = ApiError::from(e).to_http_response(&request_id);
+        return Ok(cors::add_cors_headers(response));
+    }
+
     let response = match (method, path.as_str()) {
         (&Method::GET, "/health") => {
             info!(request_id = %request_id, "Health check endpoint");
@@ -43,6 +77,20 @@ pub async fn route_request(
                 Err(e) => e.to_http_response(&request_id),
             }
         }
+        (&Method::POST, "/api-keys/verify") => {
+            info!(request_id = %request_id, "Verify API key endpoint");
+            match handlers::api_keys::verify_api_key(event, config).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        (&Method::POST, "/api-keys/revoke-batch") => {
+            info!(request_id = %request_id, "Batch revoke API keys endpoint");
+            match handlers::api_keys::revoke_api_keys_batch(event, config).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
         (&Method::GET, "/api-keys") => {
             info!(request_id = %request_id, "List API keys endpoint");
             match handlers::api_keys::list_api_keys(event, config).await {
@@ -50,6 +98,20 @@ pub async fn route_request(
                 Err(e) => e.to_http_response(&request_id),
             }
         }
+        (&Method::POST, path) if path.starts_with("/api-keys/") && path.ends_with("/rotate") => {
+            info!(request_id = %request_id, path = %path, "Rotate API key endpoint");
+            let key_id = path
+                .trim_start_matches("/api-keys/")
+                .trim_end_matches("/rotate");
+            if key_id.is_empty() || key_id.contains('/') {
+                not_found(&request_id)
+            } else {
+                match handlers::api_keys::rotate_api_key(event, config, key_id).await {
+                    Ok(response) => response,
+                    Err(e) => e.to_http_response(&request_id),
+                }
+            }
+        }
         (&Method::DELETE, path) if path.starts_with("/api-keys/") => {
             info!(request_id = %request_id, path = %path, "Delete API key endpoint");
             let key_id = path.trim_start_matches("/api-keys/");
@@ -59,6 +121,44 @@ pub async fn route_request(
             }
         }
 
+        (&Method::GET, "/admin/readings/recent") => {
+            info!(request_id = %request_id, "Recent fleet readings endpoint");
+            match handlers::recent_readings::list_recent_readings(event, config).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        (&Method::POST, path)
+            if path.starts_with("/admin/devices/") && path.ends_with("/recompute") =>
+        {
+            info!(request_id = %request_id, path = %path, "Recompute aggregates endpoint");
+            let hardware_id = path
+                .trim_start_matches("/admin/devices/")
+                .trim_end_matches("/recompute");
+            if hardware_id.is_empty() || hardware_id.contains('/') {
+                not_found(&request_id)
+            } else {
+                match handlers::aggregate_recompute::recompute_aggregates(
+                    event,
+                    config,
+                    hardware_id,
+                )
+                .await
+                {
+                    Ok(response) => response,
+                    Err(e) => e.to_http_response(&request_id),
+                }
+            }
+        }
+
+        (&Method::GET, "/debug/cursor") => {
+            info!(request_id = %request_id, "Debug cursor preview endpoint");
+            match handlers::debug::preview_cursor(event, config).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+
         (&Method::GET, "/devices") => {
             info!(request_id = %request_id, "List devices endpoint");
             match handlers::devices::list_devices(event, config).await {
@@ -66,6 +166,41 @@ pub async fn route_request(
                 Err(e) => e.to_http_response(&request_id),
             }
         }
+        (&Method::GET, "/fleet/health") => {
+            info!(request_id = %request_id, "Fleet health endpoint");
+            match handlers::fleet_health::get_fleet_health(event, config).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        (&Method::GET, "/fleet/usage") => {
+            info!(request_id = %request_id, "Fleet usage endpoint");
+            match handlers::fleet_usage::get_fleet_usage(event, config).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        (&Method::POST, "/devices/latest") => {
+            info!(request_id = %request_id, "Batch latest readings endpoint");
+            match handlers::latest_readings::get_latest_readings_batch(event, config).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        (&Method::POST, path) if path.starts_with("/devices/") && path.ends_with("/notes") => {
+            info!(request_id = %request_id, path = %path, "Create note endpoint");
+            let hardware_id = path
+                .trim_start_matches("/devices/")
+                .trim_end_matches("/notes");
+            if hardware_id.is_empty() || hardware_id.contains('/') {
+                not_found(&request_id)
+            } else {
+                match handlers::notes::create_note(event, config, hardware_id).await {
+                    Ok(response) => response,
+                    Err(e) => e.to_http_response(&request_id),
+                }
+            }
+        }
         (&Method::GET, path) if path.starts_with("/devices/") => {
             info!(request_id = %request_id, path = %path, "Device detail/readings endpoint");
             route_device_path(event, config, path).await
@@ -82,6 +217,8 @@ pub async fn route_request(
         }
     };
 
+    let response = response::format_json_response(response, pretty);
+
     Ok(cors::add_cors_headers(response))
 }
 
@@ -99,6 +236,36 @@ fn normalize_path(path: &str) -> String {
     path.trim_end_matches('/').to_string()
 }
 
+/// The route template a path matches, e.g. `/devices/{hardware_id}/readings`
+///
+/// Device, batch and key IDs are replaced by their parameter names so every
+/// call to a route shares one `route` value. Other paths are returned as is.
+fn route_template(path: &str) -> String {
+    let mut segments: Vec<&str> = path.split('/').collect();
+    match segments.as_mut_slice() {
+        ["", "devices", hardware_id, rest @ ..] if *hardware_id != "latest" => {
+            *hardware_id = "{hardware_id}";
+            if let ["readings", batch_id] = rest {
+                if !matches!(*batch_id, "export" | "stats") {
+                    *batch_id = "{batch_id}";
+                }
+            }
+        }
+        ["", "admin", "devices", hardware_id, ..] => *hardware_id = "{hardware_id}",
+        ["", "api-keys", key_id, ..] if !matches!(*key_id, "verify" | "revoke-batch") => {
+            *key_id = "{key_id}";
+        }
+        _ => {}
+    }
+    segments.join("/")
+}
+
+/// The hardware_id segment of a `/devices/{hardware_id}/...` path
+fn hardware_id_from_path(path: &str) -> Option<&str> {
+    let hardware_id = path.strip_prefix("/devices/")?.split('/').next()?;
+    (!hardware_id.is_empty() && hardware_id != "latest").then_some(hardware_id)
+}
+
 async fn route_device_path(event: Request, config: &ControlConfig, path: &str) -> Response<Body> {
     let request_id = event.lambda_context().request_id.clone();
     let method = event.method();
@@ -131,6 +298,62 @@ async fn route_device_path(event: Request, config: &ControlConfig, path: &str) -
                 Err(e) => e.to_http_response(&request_id),
             }
         }
+        [hardware_id, "readings", "export"] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, "Reading export endpoint");
+            match handlers::reading_export::export_readings(event, config, hardware_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        [hardware_id, "readings", "stats"] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, "Reading stats endpoint");
+            match handlers::reading_stats::get_reading_stats(event, config, hardware_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        [hardware_id, "readings", batch_id] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, batch_id = %batch_id, "Get reading endpoint");
+            match handlers::readings::get_reading(event, config, hardware_id, batch_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        [hardware_id, "status"] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, "Device status endpoint");
+            match handlers::device_status::get_device_status(event, config, hardware_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        [hardware_id, "status", "history"] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, "Status history endpoint");
+            match handlers::status_history::get_status_history(event, config, hardware_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        [hardware_id, "events"] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, "Query events endpoint");
+            match handlers::events::query_events(event, config, hardware_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        [hardware_id, "notes"] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, "List notes endpoint");
+            match handlers::notes::list_notes(event, config, hardware_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
+        [hardware_id, "boots", "latest-readings"] => {
+            info!(request_id = %request_id, hardware_id = %hardware_id, "Boot latest readings endpoint");
+            match handlers::boots::get_boot_latest_readings(event, config, hardware_id).await {
+                Ok(response) => response,
+                Err(e) => e.to_http_response(&request_id),
+            }
+        }
         [hardware_id, "latest"] => {
             info!(request_id = %request_id, hardware_id = %hardware_id, "Get latest reading endpoint");
             match handlers::readings::get_latest_reading(event, config, hardware_id).await {
@@ -207,6 +430,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_route_template() {
+        assert_eq!(
+            route_template("/devices/AA:BB:CC:DD:EE:FF"),
+            "/devices/{hardware_id}"
+        );
+        assert_eq!(
+            route_template("/devices/AA:BB:CC:DD:EE:FF/readings"),
+            "/devices/{hardware_id}/readings"
+        );
+        assert_eq!(
+            route_template("/devices/AA:BB:CC:DD:EE:FF/readings/stats"),
+            "/devices/{hardware_id}/readings/stats"
+        );
+        assert_eq!(
+            route_template("/devices/AA:BB:CC:DD:EE:FF/readings/batch-42"),
+            "/devices/{hardware_id}/readings/{batch_id}"
+        );
+        assert_eq!(
+            route_template("/admin/devices/AA:BB:CC:DD:EE:FF/recompute"),
+            "/admin/devices/{hardware_id}/recompute"
+        );
+        assert_eq!(
+            route_template("/api-keys/key-1/rotate"),
+            "/api-keys/{key_id}/rotate"
+        );
+        assert_eq!(route_template("/api-keys/verify"), "/api-keys/verify");
+        assert_eq!(route_template("/devices/latest"), "/devices/latest");
+        assert_eq!(route_template("/devices"), "/devices");
+    }
+
+    #[test]
+    fn test_hardware_id_from_path() {
+        assert_eq!(
+            hardware_id_from_path("/devices/AA:BB:CC:DD:EE:FF"),
+            Some("AA:BB:CC:DD:EE:FF")
+        );
+        assert_eq!(
+            hardware_id_from_path("/devices/AA:BB:CC:DD:EE:FF/readings/stats"),
+            Some("AA:BB:CC:DD:EE:FF")
+        );
+        assert_eq!(hardware_id_from_path("/devices/latest"), None);
+        assert_eq!(hardware_id_from_path("/devices"), None);
+        assert_eq!(hardware_id_from_path("/api-keys/abc/rotate"), None);
+    }
+
     #[test]
     fn test_handle_health() {
         let response = handle_health("test-request-id");
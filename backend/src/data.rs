@@ -12,13 +12,37 @@ mod router;
 mod handlers;
 
 use lambda_http::{run, service_fn, Body, Error, Request, RequestExt, Response};
-use tracing::{error, info};
+use tracing::{error, info, Instrument};
 
 use config::Config;
-use esp32_backend::{RandomIdGenerator, SystemClock};
+use esp32_backend::shared::observability::{make_request_span, record_response};
+use esp32_backend::{BestEffortQueue, RandomIdGenerator, SystemClock, DEFAULT_FLUSH_TIMEOUT};
 use router::route_request;
 
-async fn function_handler(event: Request) -> Result<Response<Body>, Error> {
+/// Handle one request inside a span carrying its request_id and route
+///
+/// Data plane paths have no parameters, so the normalized path is the route
+/// template. The device is only known once the handler has read the body,
+/// which is when it records `hardware_id`. The span records the response
+/// status and error code on the way out.
+async fn function_handler(event: Request, config: &Config) -> Result<Response<Body>, Error> {
+    let request_id = event.lambda_context().request_id.clone();
+    let route = format!(
+        "{} {}",
+        event.method(),
+        router::normalize_path(event.uri().path())
+    );
+    let span = make_request_span(&request_id, &route, None);
+
+    let response = handle_request(event, config)
+        .instrument(span.clone())
+        .await?;
+    record_response(&span, &response);
+
+    Ok(response)
+}
+
+async fn handle_request(event: Request, config: &Config) -> Result<Response<Body>, Error> {
     // Extract request ID from Lambda context
     let request_id = event.lambda_context().request_id.clone();
 
@@ -29,7 +53,7 @@ async fn function_handler(event: Request) -> Result<Response<Body>, Error> {
         "Data plane Lambda invoked"
     );
 
-    // For health check, we don't need config
+    // Health checks are answered without touching DynamoDB
     if event.method() == lambda_http::http::Method::GET
         && (event.uri().path() == "/health" || event.uri().path() == "/health/")
     {
@@ -53,28 +77,17 @@ async fn function_handler(event: Request) -> Result<Response<Body>, Error> {
         };
     }
 
-    // Initialize configuration for other endpoints
-    let config = match Config::from_env().await {
-        Ok(config) => config,
-        Err(e) => {
-            error!(
-                request_id = %request_id,
-                error = %e,
-                "Failed to load configuration"
-            );
-            return Ok(
-                error::ApiError::Internal(format!("Configuration error: {}", e))
-                    .to_http_response(&request_id),
-            );
-        }
-    };
-
-    // Initialize Clock and IdGenerator
+    // Initialize Clock, IdGenerator and the best-effort write queue
     let clock = SystemClock::new();
     let id_generator = RandomIdGenerator::new();
+    let writes = BestEffortQueue::default();
 
-    // Route the request and handle any errors
-    match route_request(event, &request_id, &config, &clock, &id_generator).await {
+    // Route the request, then let best-effort writes finish before the
+    // response returns and the execution environment can be frozen
+    let result = route_request(event, &request_id, config, &clock, &id_generator, &writes).await;
+    writes.flush(DEFAULT_FLUSH_TIMEOUT).await;
+
+    match result {
         Ok(response) => {
             info!(
                 request_id = %request_id,
@@ -104,7 +117,14 @@ async fn main() -> Result<(), Error> {
 
     info!("Data plane Lambda starting");
 
-    run(service_fn(function_handler)).await
+    // Load and validate configuration once per cold start
+    let config = Config::from_env().await.map_err(|e| {
+        error!(error = %e, "Failed to load configuration");
+        Error::from(format!("Configuration error: {}", e))
+    })?;
+    let config = &config;
+
+    run(service_fn(move |event| function_handler(event, config))).await
 }
 
 #[cfg(test)]
@@ -130,10 +150,22 @@ mod tests {
         lambda_req.with_lambda_context(context)
     }
 
+    async fn test_config() -> Config {
+        Config::for_test(
+            "http://localhost:8000",
+            "test-devices".to_string(),
+            "test-api-keys".to_string(),
+            "test-processed-batches".to_string(),
+            "test-device-readings".to_string(),
+        )
+        .await
+    }
+
     #[tokio::test]
     async fn test_health_endpoint() {
         let request = create_test_request(Method::GET, "/health");
-        let response = function_handler(request).await.unwrap();
+        let config = test_config().await;
+        let response = function_handler(request, &config).await.unwrap();
 
         assert_eq!(response.status(), 200);
 
@@ -149,7 +181,8 @@ mod tests {
     #[tokio::test]
     async fn test_health_endpoint_with_trailing_slash() {
         let request = create_test_request(Method::GET, "/health/");
-        let response = function_handler(request).await.unwrap();
+        let config = test_config().await;
+        let response = function_handler(request, &config).await.unwrap();
 
         assert_eq!(response.status(), 200);
 
@@ -166,7 +199,8 @@ mod tests {
         // This test will fail if environment variables are not set
         // That's expected - the endpoint requires proper configuration
         let request = create_test_request(Method::POST, "/register");
-        let response = function_handler(request).await.unwrap();
+        let config = test_config().await;
+        let response = function_handler(request, &config).await.unwrap();
 
         // Should either return 500 (config error) or 401 (missing API key)
         // depending on whether environment variables are set
@@ -176,7 +210,8 @@ mod tests {
     #[tokio::test]
     async fn test_data_endpoint_requires_auth() {
         let request = create_test_request(Method::POST, "/data");
-        let response = function_handler(request).await.unwrap();
+        let config = test_config().await;
+        let response = function_handler(request, &config).await.unwrap();
 
         // Should return 500 (config error) or 401 (missing API key)
         // depending on whether environment variables are set
@@ -186,7 +221,8 @@ mod tests {
     #[tokio::test]
     async fn test_unknown_route() {
         let request = create_test_request(Method::GET, "/unknown");
-        let response = function_handler(request).await.unwrap();
+        let config = test_config().await;
+        let response = function_handler(request, &config).await.unwrap();
 
         // Should return 500 (config error) or 404 (not found)
         // depending on whether environment variables are set
@@ -196,7 +232,8 @@ mod tests {
     #[tokio::test]
     async fn test_unknown_method() {
         let request = create_test_request(Method::DELETE, "/register");
-        let response = function_handler(request).await.unwrap();
+        let config = test_config().await;
+        let response = function_handler(request, &config).await.unwrap();
 
         // Should return 500 (config error) or 404 (not found)
         // depending on whether environment variables are set
@@ -207,10 +244,11 @@ mod tests {
     async fn test_trailing_slash_normalization() {
         // Test that /health and /health/ are treated the same
         let request1 = create_test_request(Method::GET, "/health");
-        let response1 = function_handler(request1).await.unwrap();
+        let config = test_config().await;
+        let response1 = function_handler(request1, &config).await.unwrap();
 
         let request2 = create_test_request(Method::GET, "/health/");
-        let response2 = function_handler(request2).await.unwrap();
+        let response2 = function_handler(request2, &config).await.unwrap();
 
         // Both should return the same status (200 OK)
         assert_eq!(response1.status(), response2.status());
@@ -248,4 +286,62 @@ mod tests {
         assert!(body.contains("BATCH_SIZE_EXCEEDED"));
         assert!(body.contains("test-req-456"));
     }
+
+    #[test]
+    fn test_error_handling_multiple_validation_errors() {
+        let error = ApiError::Validation(ValidationError::Multiple(vec![
+            ("hardware_id".to_string(), "bad MAC".to_string()),
+            ("batch_id".to_string(), "empty".to_string()),
+        ]));
+        let response = error.to_http_response("test-req-789");
+
+        assert_eq!(response.status(), 400);
+
+        let body = match response.body() {
+            Body::Text(text) => text.clone(),
+            _ => panic!("Expected text body"),
+        };
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(json["error"], "VALIDATION_FAILED");
+        assert_eq!(
+            json["errors"],
+            serde_json::json!([
+                {"field": "hardware_id", "message": "bad MAC"},
+                {"field": "batch_id", "message": "empty"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_error_handling_rate_limited() {
+        let error = ApiError::RateLimited(60);
+        let response = error.to_http_response("test-req-429");
+
+        assert_eq!(response.status(), 429);
+
+        let body = match response.body() {
+            Body::Text(text) => text.clone(),
+            _ => panic!("Expected text body"),
+        };
+
+        assert!(body.contains("RATE_LIMITED"));
+        assert!(body.contains("60 requests per minute"));
+    }
+
+    #[test]
+    fn test_error_handling_idempotency_conflict() {
+        let error = ApiError::IdempotencyConflict;
+        let response = error.to_http_response("test-req-409");
+
+        assert_eq!(response.status(), 409);
+
+        let body = match response.body() {
+            Body::Text(text) => text.clone(),
+            _ => panic!("Expected text body"),
+        };
+
+        assert!(body.contains("IDEMPOTENCY_CONFLICT"));
+        assert!(body.contains("test-req-409"));
+    }
 }
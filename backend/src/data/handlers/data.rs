@@ -1,8 +1,15 @@
-use lambda_http::{Body, Request, Response};
+use lambda_http::{Body, Request, RequestExt, Response};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::future::Future;
+use tracing::warn;
 
-use crate::error::ApiError;
-use esp32_backend::domain::Reading;
+use crate::config::TimeRegressionMode;
+use crate::error::{ApiError, DatabaseError};
+use esp32_backend::domain::{build_ts_batch, ApiKey, Reading};
+use esp32_backend::plant_insights::BucketType;
+use esp32_backend::shared::concurrency::{bounded_map, DEFAULT_FANOUT_CONCURRENCY};
+use esp32_backend::shared::observability::record_hardware_id;
 
 /// Request payload for POST /data endpoint
 ///
@@ -12,6 +19,11 @@ use esp32_backend::domain::Reading;
 pub struct DataRequest {
     /// Array of sensor readings to ingest
     pub readings: Vec<Reading>,
+
+    /// Optional context applying to every reading in the batch (e.g. gateway id,
+    /// signal strength), stamped onto each stored reading's metadata
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub batch_metadata: HashMap<String, String>,
 }
 
 /// Response payload for POST /data endpoint
@@ -19,6 +31,8 @@ pub struct DataRequest {
 /// Returns two lists of batch IDs:
 /// - acknowledged_batch_ids: Newly processed readings
 /// - duplicate_batch_ids: Previously seen readings (idempotent retries)
+///
+/// plus the readings that failed validation in `rejected_batch_ids`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataResponse {
     /// Batch IDs that were newly processed and stored
@@ -26,6 +40,71 @@ pub struct DataResponse {
 
     /// Batch IDs that were previously processed (duplicates)
     pub duplicate_batch_ids: Vec<String>,
+
+    /// Storage keys of newly stored readings (only with `?return_keys=true`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stored: Option<Vec<StoredReadingKey>>,
+
+    /// Readings that failed validation and were not stored (omitted when there are none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejected_batch_ids: Vec<RejectedReading>,
+
+    /// Non-fatal issues found in the batch (omitted when there are none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<IngestWarning>,
+}
+
+/// Reading left out of a batch because it failed validation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejectedReading {
+    pub batch_id: String,
+    /// Validation error, e.g. `Invalid format for field: hardware_id: ...`
+    pub reason: String,
+}
+
+/// Warning code for a batch that moves backwards in time within a boot
+pub const TIME_REGRESSION_WARNING: &str = "TIME_REGRESSION";
+
+/// Warning code for a sensor repeating one value across the batch
+pub const STUCK_SENSOR_WARNING: &str = "STUCK_SENSOR";
+
+/// Rollup metric counting POST /data requests per API key and minute
+pub const INGEST_REQUESTS_METRIC: &str = "ingest_requests";
+
+/// Status value marking a sensor reading as usable
+const SENSOR_STATUS_OK: &str = "ok";
+
+/// Media type of the compact binary encoding devices may use instead of JSON
+const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Non-fatal issue found while ingesting a batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestWarning {
+    /// Machine-readable warning code (e.g. `TIME_REGRESSION`)
+    pub code: String,
+    pub hardware_id: String,
+    pub message: String,
+}
+
+/// Server-assigned keys of a stored reading
+///
+/// `ts_batch` is the device_readings sort key, which clients can use to
+/// fetch or delete this specific reading later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredReadingKey {
+    pub batch_id: String,
+    pub timestamp_ms: i64,
+    pub ts_batch: String,
+}
+
+impl StoredReadingKey {
+    pub fn from_reading(reading: &Reading) -> Self {
+        Self {
+            batch_id: reading.batch_id.clone(),
+            timestamp_ms: reading.timestamp_ms,
+            ts_batch: build_ts_batch(reading.timestamp_ms, reading.seq, &reading.batch_id),
+        }
+    }
 }
 
 /// Handle POST /data requests for sensor data ingestion
@@ -34,11 +113,36 @@ pub struct DataResponse {
 /// the request body, checks batch size limits (max 100 readings), processes
 /// each reading with idempotency checks, and returns acknowledged and
 /// duplicate batch IDs.
+///
+/// Readings are validated one by one: an invalid reading is listed in
+/// `rejected_batch_ids` with its reason and the rest of the batch is still
+/// stored. Only when no reading is valid does the request fail with the first
+/// reading's validation error. Authentication, body parsing, the batch size
+/// limit and batch-level checks still fail the whole request.
+///
+/// With `?return_keys=true`, the response also lists the storage keys
+/// (`batch_id`, `timestamp_ms`, `ts_batch`) of each newly stored reading.
+///
+/// When `TIME_REGRESSION_MODE` is `warn` or `reject`, a batch whose readings
+/// start before the last stored reading of the same boot is reported in
+/// `warnings` or rejected with 400. When `STUCK_SENSOR_MIN_RUN` is set, a
+/// sensor repeating one value that many times in a row is reported in
+/// `warnings`.
+///
+/// When `DEVICE_PROFILES_TABLE` is set, each device's calibration offsets
+/// (`temp_offset_c`, `humidity_offset_pct`, `soil_moisture_offset_pct`) are
+/// added to its valid readings, clamped to each sensor's range, and only the
+/// corrected values are stored.
+///
+/// When `ROLLUPS_TABLE` is set, each request is counted against its API key's
+/// per-minute limit (`rate_limit_per_minute` on the key, falling back to
+/// `INGEST_RATE_LIMIT_PER_MINUTE`) and rejected with 429 once it is used up.
 pub async fn handle_data(
     event: Request,
     _request_id: &str,
     config: &crate::config::Config,
     clock: &dyn esp32_backend::Clock,
+    writes: &esp32_backend::BestEffortQueue,
 ) -> Result<Response<Body>, ApiError> {
     // Step 1: Extract and validate API key from X-API-Key header
     let api_key = event
@@ -48,14 +152,22 @@ pub async fn handle_data(
         .ok_or(crate::error::AuthError::MissingKey)?;
 
     // Validate API key against DynamoDB
-    crate::auth::validate_api_key(
+    let api_key_record = crate::auth::validate_api_key(
         &config.dynamodb_client,
         &config.api_keys_table,
         api_key,
         clock,
+        config.last_used_throttle_minutes,
+        config.api_key_hash_scheme,
+        writes,
     )
     .await?;
 
+    // Count the request against the key's per-minute limit
+    check_rate_limit(config, &api_key_record, clock).await?;
+
+    let return_keys = parse_return_keys(event.query_string_parameters().first("return_keys"))?;
+
     // Step 2: Parse request body
     let body_bytes = match event.body() {
         lambda_http::Body::Text(text) => text.as_bytes(),
@@ -68,70 +180,124 @@ pub async fn handle_data(
         }
     };
 
-    let request: DataRequest = serde_json::from_slice(body_bytes).map_err(|e| {
-        crate::error::ValidationError::InvalidBody(format!("Failed to parse JSON: {}", e))
-    })?;
+    check_body_size(&event, body_bytes.len(), config.max_body_bytes)?;
+
+    let request = parse_data_request(is_cbor_request(&event), body_bytes)?;
+    if let Some(hardware_id) = batch_hardware_id(&request.readings) {
+        record_hardware_id(hardware_id);
+    }
 
     // Step 3: Enforce batch size limit (100 readings max) after authentication
     if request.readings.len() > 100 {
         return Err(crate::error::ValidationError::BatchSizeExceeded.into());
     }
 
-    // Step 4: Validate each reading
-    for reading in &request.readings {
-        // Validate hardware_id (MAC address format)
-        esp32_backend::validate_mac_address(&reading.hardware_id).map_err(|e| {
-            crate::error::ValidationError::InvalidFormat(format!("hardware_id: {}", e.message))
-        })?;
+    // Step 4: Validate each reading, setting invalid ones aside
+    let (mut readings, failures) = validate_readings(request.readings);
+
+    let mut failures = failures.into_iter();
+    if readings.is_empty() {
+        if let Some((_, e)) = failures.next() {
+            return Err(e.into());
+        }
+    }
+    let mut rejected_batch_ids: Vec<RejectedReading> = failures
+        .map(|(batch_id, e)| RejectedReading {
+            batch_id,
+            reason: e.to_string(),
+        })
+        .collect();
+
+    // Validate batch-level metadata and stamp it onto each reading
+    esp32_backend::validate_batch_metadata(&request.batch_metadata).map_err(|e| {
+        crate::error::ValidationError::InvalidValue(format!("batch_metadata: {}", e.message))
+    })?;
+    esp32_backend::domain::apply_batch_metadata(&mut readings, &request.batch_metadata);
+
+    // Set aside readings sent too soon after the device's previous one
+    let (mut readings, too_frequent) = check_min_reading_interval(config, readings).await?;
+    rejected_batch_ids.extend(too_frequent);
+
+    // Correct sensor values with each device's calibration offsets
+    apply_calibrations(config, &mut readings).await?;
 
-        // Validate timestamp_ms (epoch milliseconds with sane bounds)
-        esp32_backend::validate_epoch_millis(reading.timestamp_ms).map_err(|e| {
-            crate::error::ValidationError::InvalidFormat(format!("timestamp_ms: {}", e.message))
-        })?;
+    // Check that no boot session moves backwards in time
+    let mut warnings = check_time_regression(config, &readings).await?;
 
-        // Validate batch_id (max length 256, safe ASCII charset - treat as opaque)
-        esp32_backend::validate_batch_id(&reading.batch_id).map_err(|e| {
-            crate::error::ValidationError::InvalidFormat(format!("batch_id: {}", e.message))
-        })?;
+    if let Some(min_run) = config.stuck_sensor_min_run {
+        warnings.extend(stuck_sensor_warnings(&readings, min_run));
     }
 
     // Step 5: Process each reading with idempotency checks
-    let mut acknowledged_batch_ids = Vec::new();
-    let mut duplicate_batch_ids = Vec::new();
-
-    for reading in &request.readings {
-        // Call transact_write_reading_if_new_batch for each reading
-        // This uses DynamoDB transactions to atomically check idempotency and write
-        match crate::repo::ingestion::transact_write_reading_if_new_batch(
+    // Uses DynamoDB transactions to atomically check idempotency and write
+    let outcome = write_readings(&readings, |reading| {
+        crate::repo::ingestion::transact_write_reading_if_new_batch(
             &config.dynamodb_client,
             &config.processed_batches_table,
             &config.device_readings_table,
             reading,
             clock,
-            None, // No TTL for now (optional enhancement in task 14.3)
+            config.readings_retention_seconds(),
+            config.allow_reading_overwrite,
         )
-        .await
-        {
-            Ok(true) => {
-                // Transaction succeeded, reading was written
-                acknowledged_batch_ids.push(reading.batch_id.clone());
-            }
-            Ok(false) => {
-                // Duplicate batch_id detected
-                duplicate_batch_ids.push(reading.batch_id.clone());
-            }
-            Err(e) => {
-                // Non-duplicate DynamoDB error occurred
-                // Use fail-fast error handling: return error immediately
-                return Err(crate::error::ApiError::Database(e));
-            }
-        }
+    })
+    .await?;
+
+    let acknowledged_batch_ids = outcome
+        .written
+        .iter()
+        .map(|reading| reading.batch_id.clone())
+        .collect();
+    let stored = return_keys.then(|| {
+        outcome
+            .written
+            .iter()
+            .map(|reading| StoredReadingKey::from_reading(reading))
+            .collect()
+    });
+
+    // Record first/last reading timestamps on each device record
+    for (hardware_id, (first_ms, last_ms)) in reading_bounds(&outcome.written) {
+        let client = config.dynamodb_client.clone();
+        let table_name = config.devices_table.clone();
+        let hardware_id = hardware_id.to_string();
+        writes.submit(async move {
+            let _ = crate::repo::devices::update_reading_bounds(
+                &client,
+                &table_name,
+                &hardware_id,
+                first_ms,
+                last_ms,
+            )
+            .await;
+        });
+    }
+
+    // Count stored readings toward each device's current boot session
+    for ((hardware_id, boot_id), count) in readings_per_boot(&outcome.written) {
+        let client = config.dynamodb_client.clone();
+        let table_name = config.devices_table.clone();
+        let hardware_id = hardware_id.to_string();
+        let boot_id = boot_id.to_string();
+        writes.submit(async move {
+            let _ = crate::repo::devices::increment_boot_readings(
+                &client,
+                &table_name,
+                &hardware_id,
+                &boot_id,
+                count,
+            )
+            .await;
+        });
     }
 
     // Step 6: Return DataResponse with both lists
     let response = DataResponse {
         acknowledged_batch_ids,
-        duplicate_batch_ids,
+        duplicate_batch_ids: outcome.duplicate_batch_ids,
+        stored,
+        rejected_batch_ids,
+        warnings,
     };
 
     let response_body = serde_json::to_string(&response)
@@ -144,6 +310,633 @@ pub async fn handle_data(
         .map_err(|e| ApiError::Internal(format!("Failed to build response: {}", e)))
 }
 
+/// Field problems collected while validating one reading
+///
+/// Each entry keeps the single-error variant to report when it is the only problem.
+#[derive(Default)]
+struct FieldErrors(Vec<(fn(String) -> crate::error::ValidationError, String, String)>);
+
+impl FieldErrors {
+    fn check(
+        &mut self,
+        kind: fn(String) -> crate::error::ValidationError,
+        field: &str,
+        result: Result<(), esp32_backend::ValidationError>,
+    ) {
+        if let Err(e) = result {
+            self.0.push((kind, field.to_string(), e.message));
+        }
+    }
+
+    /// One problem keeps its own variant; several become `ValidationError::Multiple`
+    fn into_result(mut self) -> Result<(), crate::error::ValidationError> {
+        match self.0.len() {
+            0 => Ok(()),
+            1 => {
+                let (kind, field, message) = self.0.remove(0);
+                Err(kind(format!("{}: {}", field, message)))
+            }
+            _ => Err(crate::error::ValidationError::Multiple(
+                self.0
+                    .into_iter()
+                    .map(|(_, field, message)| (field, message))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+/// Check a single reading's fields
+///
+/// Every field is checked so a device sees all of its problems in one response.
+fn validate_reading(reading: &Reading) -> Result<(), crate::error::ValidationError> {
+    use crate::error::ValidationError::{InvalidFormat, InvalidValue};
+
+    let mut errors = FieldErrors::default();
+
+    // Validate hardware_id (MAC address format)
+    errors.check(
+        InvalidFormat,
+        "hardware_id",
+        esp32_backend::validate_mac_address(&reading.hardware_id),
+    );
+
+    // Validate timestamp_ms (epoch milliseconds with sane bounds)
+    errors.check(
+        InvalidFormat,
+        "timestamp_ms",
+        esp32_backend::validate_epoch_millis(reading.timestamp_ms),
+    );
+
+    // Validate boot_id (UUID v4 or v7)
+    errors.check(
+        InvalidFormat,
+        "boot_id",
+        esp32_backend::validate_uuid(&reading.boot_id, esp32_backend::BOOT_ID_UUID_VERSIONS),
+    );
+
+    // Validate batch_id (max length 256, safe ASCII charset - treat as opaque)
+    errors.check(
+        InvalidFormat,
+        "batch_id",
+        esp32_backend::validate_batch_id(&reading.batch_id),
+    );
+
+    // Validate quality score (optional, 0.0 to 1.0)
+    if let Some(quality) = reading.quality {
+        errors.check(
+            InvalidValue,
+            "quality",
+            esp32_backend::validate_quality(quality),
+        );
+    }
+
+    // Validate sequence (optional, must fit the sort key segment)
+    if let Some(seq) = reading.seq {
+        errors.check(InvalidValue, "seq", esp32_backend::validate_seq(seq));
+    }
+
+    // Validate that every sensor value is finite (CBOR can carry NaN and ±Infinity)
+    for (name, value) in sensor_value_fields(&reading.sensors) {
+        errors.check(
+            InvalidValue,
+            &format!("sensors.{}", name),
+            esp32_backend::validate_sensor_value(name, value),
+        );
+    }
+
+    // Validate that each sensor_status agrees with its values
+    if let Err(e) = esp32_backend::validate_status_value_consistency(reading) {
+        errors.0.push((InvalidValue, e.field, e.message));
+    }
+
+    errors.into_result()
+}
+
+/// Every sensor value present in a reading, typed sensors first, then extra
+/// sensors by name
+fn sensor_value_fields(sensors: &esp32_backend::domain::SensorValues) -> Vec<(&str, f64)> {
+    let typed = [
+        ("bme280_temp_c", sensors.bme280_temp_c),
+        ("ds18b20_temp_c", sensors.ds18b20_temp_c),
+        ("humidity_pct", sensors.humidity_pct),
+        ("pressure_hpa", sensors.pressure_hpa),
+        ("soil_moisture_pct", sensors.soil_moisture_pct),
+    ];
+    let mut fields: Vec<(&str, f64)> = typed
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|v| (name, v)))
+        .collect();
+
+    let mut extra: Vec<(&str, f64)> = sensors
+        .extra_sensors
+        .iter()
+        .map(|(name, value)| (name.as_str(), *value))
+        .collect();
+    extra.sort_by(|a, b| a.0.cmp(b.0));
+    fields.extend(extra);
+
+    fields
+}
+
+/// Split a batch into valid readings and `(batch_id, error)` for each invalid one
+///
+/// Both keep the order of the request.
+fn validate_readings(
+    readings: Vec<Reading>,
+) -> (Vec<Reading>, Vec<(String, crate::error::ValidationError)>) {
+    let mut valid = Vec::with_capacity(readings.len());
+    let mut failures = Vec::new();
+
+    for reading in readings {
+        match validate_reading(&reading) {
+            Ok(()) => valid.push(reading),
+            Err(e) => failures.push((reading.batch_id, e)),
+        }
+    }
+
+    (valid, failures)
+}
+
+/// Readings stored by `write_readings` and the batch IDs it skipped as duplicates
+#[derive(Debug)]
+struct WriteOutcome<'a> {
+    written: Vec<&'a Reading>,
+    duplicate_batch_ids: Vec<String>,
+}
+
+/// Write each reading with `write`, which returns false for a duplicate batch
+///
+/// Readings repeated earlier in the request are duplicates without a write. A
+/// database error fails the whole batch; retrying it is safe because writes
+/// are idempotent on `batch_id`.
+async fn write_readings<'a, F, Fut>(
+    readings: &'a [Reading],
+    write: F,
+) -> Result<WriteOutcome<'a>, DatabaseError>
+where
+    F: Fn(&'a Reading) -> Fut,
+    Fut: Future<Output = Result<bool, DatabaseError>>,
+{
+    let mut outcome = WriteOutcome {
+        written: Vec::new(),
+        duplicate_batch_ids: Vec::new(),
+    };
+
+    let repeats = in_request_repeats(readings);
+
+    for (reading, is_repeat) in readings.iter().zip(repeats) {
+        if !is_repeat && write(reading).await? {
+            outcome.written.push(reading);
+        } else {
+            outcome.duplicate_batch_ids.push(reading.batch_id.clone());
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Flag readings whose `batch_id` and `timestamp_ms` repeat an earlier reading
+/// in the same request
+///
+/// Only the first occurrence is written; repeats are reported as duplicates.
+fn in_request_repeats(readings: &[Reading]) -> Vec<bool> {
+    let mut seen = HashSet::new();
+    readings
+        .iter()
+        .map(|reading| !seen.insert((reading.batch_id.as_str(), reading.timestamp_ms)))
+        .collect()
+}
+
+/// Add this request to the key's per-minute counter and reject it over the limit
+///
+/// Does nothing when no rollups table is configured or the key is unlimited.
+/// If the counter cannot be updated the request is let through: a rollups
+/// outage should not stop ingestion.
+async fn check_rate_limit(
+    config: &crate::config::Config,
+    api_key: &ApiKey,
+    clock: &dyn esp32_backend::Clock,
+) -> Result<(), ApiError> {
+    let Some(rollups_table) = &config.rollups_table else {
+        return Ok(());
+    };
+    let Some(limit) = effective_rate_limit(
+        api_key.rate_limit_per_minute,
+        config.ingest_rate_limit_per_minute,
+    ) else {
+        return Ok(());
+    };
+
+    let dimensions = HashMap::from([("key_id".to_string(), api_key.key_id.clone())]);
+    match crate::repo::rollups::increment_rollup(
+        &config.dynamodb_client,
+        rollups_table,
+        BucketType::Minute,
+        clock.now_epoch_ms(),
+        INGEST_REQUESTS_METRIC,
+        &dimensions,
+    )
+    .await
+    {
+        // The counter already includes this request
+        Ok(count) => enforce_rate_limit(count - 1, limit),
+        Err(e) => {
+            warn!(key_id = %api_key.key_id, error = %e, "Rate limit counter update failed");
+            Ok(())
+        }
+    }
+}
+
+/// Per-minute limit for a key: its own limit if set, else the default (0 means unlimited)
+fn effective_rate_limit(key_limit: Option<u32>, default_limit: u32) -> Option<u32> {
+    match key_limit.unwrap_or(default_limit) {
+        0 => None,
+        limit => Some(limit),
+    }
+}
+
+/// Reject a request once the key already made `limit` requests this minute
+fn enforce_rate_limit(previous_requests: i64, limit: u32) -> Result<(), ApiError> {
+    if previous_requests >= i64::from(limit) {
+        return Err(ApiError::RateLimited(limit));
+    }
+    Ok(())
+}
+
+/// Add each device's calibration offsets to its readings before they are stored
+///
+/// Offsets come from the device's profile, read once per hardware_id in the
+/// batch, `DEFAULT_FANOUT_CONCURRENCY` reads at a time. Does no reads when no
+/// device profiles table is configured. A failed read fails the request rather
+/// than storing uncorrected values.
+async fn apply_calibrations(
+    config: &crate::config::Config,
+    readings: &mut [Reading],
+) -> Result<(), ApiError> {
+    let Some(profiles_table) = &config.device_profiles_table else {
+        return Ok(());
+    };
+
+    let hardware_ids: BTreeSet<String> = readings.iter().map(|r| r.hardware_id.clone()).collect();
+    let calibrations = bounded_map(
+        hardware_ids.iter(),
+        DEFAULT_FANOUT_CONCURRENCY,
+        |hardware_id| {
+            crate::repo::profiles::get_calibration(
+                &config.dynamodb_client,
+                profiles_table,
+                hardware_id,
+            )
+        },
+    )
+    .await;
+    for (hardware_id, calibration) in hardware_ids.into_iter().zip(calibrations) {
+        let calibration = calibration?;
+        if calibration.is_zero() {
+            continue;
+        }
+
+        for reading in readings.iter_mut().filter(|r| r.hardware_id == hardware_id) {
+            esp32_backend::domain::apply_calibration(reading, &calibration);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reject readings closer than `min_reading_interval_ms` to their device's previous reading
+///
+/// The device's stored `last_reading_ms` is read once per hardware_id in the
+/// batch, `DEFAULT_FANOUT_CONCURRENCY` reads at a time. Returns the readings to
+/// keep and a `RejectedReading` for each one set aside, both in request order. Does no reads when the check is off.
+async fn check_min_reading_interval(
+    config: &crate::config::Config,
+    readings: Vec<Reading>,
+) -> Result<(Vec<Reading>, Vec<RejectedReading>), ApiError> {
+    let min_interval_ms = config.min_reading_interval_ms;
+    if min_interval_ms == 0 {
+        return Ok((readings, Vec::new()));
+    }
+
+    let mut last_reading_ms = HashMap::new();
+    let hardware_ids: BTreeSet<&str> = readings.iter().map(|r| r.hardware_id.as_str()).collect();
+    let devices = bounded_map(
+        hardware_ids.iter().copied(),
+        DEFAULT_FANOUT_CONCURRENCY,
+        |hardware_id| {
+            crate::repo::devices::get_device(
+                &config.dynamodb_client,
+                &config.devices_table,
+                hardware_id,
+            )
+        },
+    )
+    .await;
+    for (hardware_id, device) in hardware_ids.into_iter().zip(devices) {
+        if let Some(last_ms) = device?.and_then(|d| d.last_reading_ms) {
+            last_reading_ms.insert(hardware_id.to_string(), last_ms);
+        }
+    }
+
+    let flags = too_frequent_readings(&readings, &last_reading_ms, min_interval_ms);
+    let mut kept = Vec::with_capacity(readings.len());
+    let mut rejected = Vec::new();
+    for (reading, too_frequent) in readings.into_iter().zip(flags) {
+        if too_frequent {
+            rejected.push(RejectedReading {
+                batch_id: reading.batch_id,
+                reason: format!(
+                    "timestamp_ms: within {} ms of the device's previous reading",
+                    min_interval_ms
+                ),
+            });
+        } else {
+            kept.push(reading);
+        }
+    }
+
+    Ok((kept, rejected))
+}
+
+/// Flag readings closer than `min_interval_ms` to another reading from their device
+///
+/// Each device's readings are taken in timestamp order and compared with the
+/// last one kept from the batch and with its stored `last_reading_ms`. A
+/// reading at exactly a kept or stored timestamp is left to duplicate
+/// detection, and a device with no stored reading always keeps its first.
+fn too_frequent_readings(
+    readings: &[Reading],
+    last_reading_ms: &HashMap<String, i64>,
+    min_interval_ms: i64,
+) -> Vec<bool> {
+    let mut order: Vec<usize> = (0..readings.len()).collect();
+    order.sort_by_key(|&i| (&readings[i].hardware_id, readings[i].timestamp_ms));
+
+    let mut flags = vec![false; readings.len()];
+    let mut last_kept: HashMap<&str, i64> = HashMap::new();
+    for i in order {
+        let reading = &readings[i];
+        let too_close = |other_ms: i64| {
+            other_ms != reading.timestamp_ms
+                && (reading.timestamp_ms - other_ms).abs() < min_interval_ms
+        };
+
+        let stored = last_reading_ms.get(&reading.hardware_id).copied();
+        let previous = last_kept.get(reading.hardware_id.as_str()).copied();
+        if stored.is_some_and(too_close) || previous.is_some_and(too_close) {
+            flags[i] = true;
+        } else {
+            last_kept.insert(&reading.hardware_id, reading.timestamp_ms);
+        }
+    }
+
+    flags
+}
+
+/// Compare each boot session in the batch against its last stored reading
+///
+/// Returns warnings in `warn` mode and an error on the first regression in
+/// `reject` mode. Does no reads when the check is off.
+async fn check_time_regression(
+    config: &crate::config::Config,
+    readings: &[Reading],
+) -> Result<Vec<IngestWarning>, ApiError> {
+    let mut warnings = Vec::new();
+    if config.time_regression_mode == TimeRegressionMode::Off {
+        return Ok(warnings);
+    }
+
+    for ((hardware_id, boot_id), timestamps) in timestamps_by_boot(readings) {
+        let last_stored_ms = crate::repo::ingestion::get_last_stored_timestamp_for_boot(
+            &config.dynamodb_client,
+            &config.device_readings_table,
+            hardware_id,
+            boot_id,
+        )
+        .await?;
+
+        let Some(last_stored_ms) = last_stored_ms else {
+            continue;
+        };
+        if !is_time_regression(last_stored_ms, &timestamps) {
+            continue;
+        }
+
+        let batch_min_ms = timestamps.iter().copied().min().unwrap_or_default();
+        let message = format!(
+            "boot {}: batch starts at {}, before last stored reading at {}",
+            boot_id, batch_min_ms, last_stored_ms
+        );
+        warn!(hardware_id = %hardware_id, "Time regression: {}", message);
+
+        if config.time_regression_mode == TimeRegressionMode::Reject {
+            return Err(crate::error::ValidationError::InvalidValue(format!(
+                "timestamp_ms: {}",
+                message
+            ))
+            .into());
+        }
+
+        warnings.push(IngestWarning {
+            code: TIME_REGRESSION_WARNING.to_string(),
+            hardware_id: hardware_id.to_string(),
+            message,
+        });
+    }
+
+    Ok(warnings)
+}
+
+/// Oldest and newest timestamp per hardware_id among the written readings
+fn reading_bounds<'a>(readings: &[&'a Reading]) -> BTreeMap<&'a str, (i64, i64)> {
+    let mut bounds: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+    for reading in readings {
+        let ts = reading.timestamp_ms;
+        bounds
+            .entry(reading.hardware_id.as_str())
+            .and_modify(|(first, last)| {
+                *first = (*first).min(ts);
+                *last = (*last).max(ts);
+            })
+            .or_insert((ts, ts));
+    }
+    bounds
+}
+
+/// Number of written readings per (hardware_id, boot_id)
+fn readings_per_boot<'a>(readings: &[&'a Reading]) -> BTreeMap<(&'a str, &'a str), i64> {
+    let mut counts: BTreeMap<(&str, &str), i64> = BTreeMap::new();
+    for reading in readings {
+        *counts
+            .entry((reading.hardware_id.as_str(), reading.boot_id.as_str()))
+            .or_default() += 1;
+    }
+    counts
+}
+
+/// Group reading timestamps by (hardware_id, boot_id)
+fn timestamps_by_boot(readings: &[Reading]) -> BTreeMap<(&str, &str), Vec<i64>> {
+    let mut groups: BTreeMap<(&str, &str), Vec<i64>> = BTreeMap::new();
+    for reading in readings {
+        groups
+            .entry((reading.hardware_id.as_str(), reading.boot_id.as_str()))
+            .or_default()
+            .push(reading.timestamp_ms);
+    }
+    groups
+}
+
+/// Whether a boot's batch regresses behind its last stored reading
+///
+/// A batch containing the last stored timestamp is treated as a retry of a
+/// batch that was already stored, not as a clock reset.
+fn is_time_regression(last_stored_ms: i64, timestamps: &[i64]) -> bool {
+    if timestamps.contains(&last_stored_ms) {
+        return false;
+    }
+
+    match timestamps.iter().copied().min() {
+        Some(batch_min_ms) => {
+            esp32_backend::detect_time_regression(Some(last_stored_ms), batch_min_ms)
+        }
+        None => false,
+    }
+}
+
+/// Flag sensors that report one exact value `min_run` or more times in a row
+///
+/// Readings are checked per device in timestamp order. A value only counts
+/// while its sensor status is `ok`; anything else breaks the run.
+fn stuck_sensor_warnings(readings: &[Reading], min_run: usize) -> Vec<IngestWarning> {
+    type Select = fn(&Reading) -> Option<f64>;
+    type Status = fn(&Reading) -> &str;
+    const SENSORS: [(&str, Select, Status); 5] = [
+        (
+            "bme280_temp_c",
+            |r| r.sensors.bme280_temp_c,
+            |r| &r.sensor_status.bme280,
+        ),
+        (
+            "ds18b20_temp_c",
+            |r| r.sensors.ds18b20_temp_c,
+            |r| &r.sensor_status.ds18b20,
+        ),
+        (
+            "humidity_pct",
+            |r| r.sensors.humidity_pct,
+            |r| &r.sensor_status.bme280,
+        ),
+        (
+            "pressure_hpa",
+            |r| r.sensors.pressure_hpa,
+            |r| &r.sensor_status.bme280,
+        ),
+        (
+            "soil_moisture_pct",
+            |r| r.sensors.soil_moisture_pct,
+            |r| &r.sensor_status.soil_moisture,
+        ),
+    ];
+
+    let mut by_device: BTreeMap<&str, Vec<&Reading>> = BTreeMap::new();
+    for reading in readings {
+        by_device
+            .entry(reading.hardware_id.as_str())
+            .or_default()
+            .push(reading);
+    }
+
+    let mut warnings = Vec::new();
+    for (hardware_id, mut device_readings) in by_device {
+        device_readings.sort_by_key(|r| r.timestamp_ms);
+
+        for (sensor, select, status) in SENSORS {
+            let values: Vec<Option<f64>> = device_readings
+                .iter()
+                .map(|r| select(r).filter(|_| status(r) == SENSOR_STATUS_OK))
+                .collect();
+
+            if esp32_backend::detect_stuck_sensor(&values, min_run) {
+                warnings.push(IngestWarning {
+                    code: STUCK_SENSOR_WARNING.to_string(),
+                    hardware_id: hardware_id.to_string(),
+                    message: format!(
+                        "{} reported the same value in {} or more consecutive readings",
+                        sensor, min_run
+                    ),
+                });
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Reject a body larger than `max_body_bytes` before it is parsed
+///
+/// Checks both the buffered length and the `Content-Length` header.
+fn check_body_size(
+    event: &Request,
+    body_len: usize,
+    max_body_bytes: usize,
+) -> Result<(), ApiError> {
+    let content_length = event
+        .headers()
+        .get("content-length")
+        .and_then(|v| v.to_str().ok());
+
+    esp32_backend::validate_body_size(content_length, body_len, max_body_bytes)
+        .map_err(|e| crate::error::ValidationError::InvalidBody(e.message).into())
+}
+
+/// The device a batch is from, when every reading names the same one
+///
+/// Gateways may forward several devices' readings in one batch; those
+/// requests leave `hardware_id` off the request span.
+fn batch_hardware_id(readings: &[Reading]) -> Option<&str> {
+    let (first, rest) = readings.split_first()?;
+    rest.iter()
+        .all(|r| r.hardware_id == first.hardware_id)
+        .then_some(first.hardware_id.as_str())
+}
+
+/// Whether the request body is CBOR, judged by its `Content-Type` (parameters ignored)
+fn is_cbor_request(event: &Request) -> bool {
+    event
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(CBOR_CONTENT_TYPE))
+}
+
+/// Decode a `DataRequest` from CBOR or JSON; both yield the same request
+fn parse_data_request(cbor: bool, body: &[u8]) -> Result<DataRequest, ApiError> {
+    if cbor {
+        ciborium::de::from_reader(body).map_err(|e| {
+            crate::error::ValidationError::InvalidBody(format!("Failed to parse CBOR: {}", e))
+                .into()
+        })
+    } else {
+        serde_json::from_slice(body).map_err(|e| {
+            crate::error::ValidationError::InvalidBody(format!("Failed to parse JSON: {}", e))
+                .into()
+        })
+    }
+}
+
+/// Parse the optional `return_keys` query parameter (defaults to false)
+fn parse_return_keys(value: Option<&str>) -> Result<bool, ApiError> {
+    match value {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(_) => {
+            Err(crate::error::ValidationError::InvalidValue("return_keys".to_string()).into())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +969,32 @@ mod tests {
         assert_eq!(request.readings.len(), 1);
         assert_eq!(request.readings[0].hardware_id, "AA:BB:CC:DD:EE:FF");
         assert_eq!(request.readings[0].timestamp_ms, 1704067800000);
+        assert_eq!(request.readings[0].quality, None);
+    }
+
+    #[test]
+    fn test_data_request_with_quality() {
+        let json = r#"{
+            "readings": [
+                {
+                    "batch_id": "batch1",
+                    "hardware_id": "AA:BB:CC:DD:EE:FF",
+                    "timestamp_ms": 1704067800000,
+                    "boot_id": "550e8400-e29b-41d4-a716-446655440000",
+                    "firmware_version": "1.0.16",
+                    "sensors": {},
+                    "sensor_status": {
+                        "bme280": "ok",
+                        "ds18b20": "ok",
+                        "soil_moisture": "ok"
+                    },
+                    "quality": 0.92
+                }
+            ]
+        }"#;
+
+        let request: DataRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.readings[0].quality, Some(0.92));
     }
 
     #[test]
@@ -222,6 +1041,9 @@ mod tests {
         let response = DataResponse {
             acknowledged_batch_ids: vec!["batch1".to_string(), "batch2".to_string()],
             duplicate_batch_ids: vec!["batch3".to_string()],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -237,6 +1059,9 @@ mod tests {
         let response = DataResponse {
             acknowledged_batch_ids: vec![],
             duplicate_batch_ids: vec![],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -249,6 +1074,9 @@ mod tests {
         let response = DataResponse {
             acknowledged_batch_ids: vec!["batch1".to_string()],
             duplicate_batch_ids: vec![],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -257,11 +1085,412 @@ mod tests {
         assert_eq!(parsed.duplicate_batch_ids.len(), 0);
     }
 
+    #[test]
+    fn test_stored_keys_match_build_ts_batch() {
+        let json = r#"{
+            "readings": [
+                {
+                    "batch_id": "batch1",
+                    "hardware_id": "AA:BB:CC:DD:EE:FF",
+                    "timestamp_ms": 1704067800000,
+                    "seq": 3,
+                    "boot_id": "550e8400-e29b-41d4-a716-446655440000",
+                    "firmware_version": "1.0.16",
+                    "sensors": {},
+                    "sensor_status": {"bme280": "ok", "ds18b20": "ok", "soil_moisture": "ok"}
+                },
+                {
+                    "batch_id": "batch2",
+                    "hardware_id": "AA:BB:CC:DD:EE:FF",
+                    "timestamp_ms": 42,
+                    "boot_id": "550e8400-e29b-41d4-a716-446655440000",
+                    "firmware_version": "1.0.16",
+                    "sensors": {},
+                    "sensor_status": {"bme280": "ok", "ds18b20": "ok", "soil_moisture": "ok"}
+                }
+            ]
+        }"#;
+        let request: DataRequest = serde_json::from_str(json).unwrap();
+
+        for reading in &request.readings {
+            let key = StoredReadingKey::from_reading(reading);
+            assert_eq!(key.batch_id, reading.batch_id);
+            assert_eq!(key.timestamp_ms, reading.timestamp_ms);
+            assert_eq!(
+                key.ts_batch,
+                build_ts_batch(reading.timestamp_ms, reading.seq, &reading.batch_id)
+            );
+        }
+
+        assert_eq!(
+            StoredReadingKey::from_reading(&request.readings[0]).ts_batch,
+            "1704067800000#00003#batch1"
+        );
+        assert_eq!(
+            StoredReadingKey::from_reading(&request.readings[1]).ts_batch,
+            "0000000000042#batch2"
+        );
+    }
+
+    #[test]
+    fn test_data_response_stored_keys_serialization() {
+        let response = DataResponse {
+            acknowledged_batch_ids: vec!["batch1".to_string()],
+            duplicate_batch_ids: vec![],
+            stored: Some(vec![StoredReadingKey {
+                batch_id: "batch1".to_string(),
+                timestamp_ms: 1704067800000,
+                ts_batch: "1704067800000#batch1".to_string(),
+            }]),
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains(
+            r#""stored":[{"batch_id":"batch1","timestamp_ms":1704067800000,"ts_batch":"1704067800000#batch1"}]"#
+        ));
+
+        // Omitted unless requested
+        let response = DataResponse {
+            stored: None,
+            ..response
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("stored"));
+    }
+
+    #[test]
+    fn test_parse_return_keys() {
+        assert!(!parse_return_keys(None).unwrap());
+        assert!(!parse_return_keys(Some("false")).unwrap());
+        assert!(parse_return_keys(Some("true")).unwrap());
+        assert!(parse_return_keys(Some("1")).is_err());
+    }
+
+    fn body_request(body: &str, content_length: Option<&str>) -> Request {
+        let mut builder = lambda_http::http::Request::builder()
+            .method("POST")
+            .uri("/data");
+        if let Some(len) = content_length {
+            builder = builder.header("content-length", len);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_check_body_size_just_under_and_over_limit() {
+        let max = 1024;
+
+        let under = "x".repeat(max);
+        let event = body_request(&under, Some("1024"));
+        assert!(check_body_size(&event, under.len(), max).is_ok());
+
+        let over = "x".repeat(max + 1);
+        let event = body_request(&over, None);
+        match check_body_size(&event, over.len(), max) {
+            Err(ApiError::Validation(crate::error::ValidationError::InvalidBody(msg))) => {
+                assert!(msg.starts_with("payload too large"));
+            }
+            other => panic!("Expected InvalidBody, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_body_size_spoofed_content_length() {
+        let body = r#"{"readings": []}"#;
+        let event = body_request(body, Some("10485760"));
+
+        assert!(matches!(
+            check_body_size(&event, body.len(), 1024),
+            Err(ApiError::Validation(
+                crate::error::ValidationError::InvalidBody(_)
+            ))
+        ));
+    }
+
+    fn reading_at(hardware_id: &str, boot_id: &str, timestamp_ms: i64) -> Reading {
+        Reading {
+            batch_id: format!("{}_{}_{}", hardware_id, boot_id, timestamp_ms),
+            hardware_id: hardware_id.to_string(),
+            timestamp_ms,
+            boot_id: boot_id.to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: esp32_backend::domain::SensorValues {
+                bme280_temp_c: None,
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: None,
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: esp32_backend::domain::SensorStatus {
+                bme280: "ok".to_string(),
+                ds18b20: "ok".to_string(),
+                soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_in_request_repeats_identical_readings() {
+        let reading = reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 1000);
+        let readings = vec![
+            reading.clone(),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 2000),
+            reading,
+        ];
+
+        // First occurrence is written, the identical copy is a duplicate
+        assert_eq!(in_request_repeats(&readings), vec![false, false, true]);
+    }
+
+    #[test]
+    fn test_in_request_repeats_same_batch_id_different_timestamp() {
+        let mut later = reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 2000);
+        let earlier = reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 1000);
+        later.batch_id = earlier.batch_id.clone();
+
+        assert_eq!(in_request_repeats(&[earlier, later]), vec![false, false]);
+    }
+
+    #[test]
+    fn test_timestamps_by_boot() {
+        let readings = vec![
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 3000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-b", 1000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 2000),
+        ];
+
+        let groups = timestamps_by_boot(&readings);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&("AA:BB:CC:DD:EE:FF", "boot-a")], vec![3000, 2000]);
+        assert_eq!(groups[&("AA:BB:CC:DD:EE:FF", "boot-b")], vec![1000]);
+    }
+
+    #[test]
+    fn test_reading_bounds_per_device() {
+        let readings = [
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 3000),
+            reading_at("11:22:33:44:55:66", "boot-b", 1500),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 1000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-b", 2000),
+        ];
+        let written: Vec<&Reading> = readings.iter().collect();
+
+        let bounds = reading_bounds(&written);
+        assert_eq!(bounds.len(), 2);
+        assert_eq!(bounds["AA:BB:CC:DD:EE:FF"], (1000, 3000));
+        assert_eq!(bounds["11:22:33:44:55:66"], (1500, 1500));
+        assert!(reading_bounds(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_readings_per_boot() {
+        let readings = [
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 1000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 2000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-b", 3000),
+            reading_at("11:22:33:44:55:66", "boot-a", 1000),
+        ];
+        let written: Vec<&Reading> = readings.iter().collect();
+
+        let counts = readings_per_boot(&written);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&("AA:BB:CC:DD:EE:FF", "boot-a")], 2);
+        assert_eq!(counts[&("AA:BB:CC:DD:EE:FF", "boot-b")], 1);
+        assert_eq!(counts[&("11:22:33:44:55:66", "boot-a")], 1);
+    }
+
+    #[test]
+    fn test_is_time_regression() {
+        // Batch starts before the last stored reading for the boot
+        assert!(is_time_regression(5000, &[4000, 6000]));
+        // Normal progression
+        assert!(!is_time_regression(5000, &[6000, 7000]));
+        // Retry of the batch that was last stored
+        assert!(!is_time_regression(5000, &[4000, 5000]));
+    }
+
+    #[test]
+    fn test_effective_rate_limit() {
+        // Key limit wins over the default
+        assert_eq!(effective_rate_limit(Some(600), 120), Some(600));
+        // Keys without a limit use the default
+        assert_eq!(effective_rate_limit(None, 120), Some(120));
+        // 0 means unlimited, whether set on the key or as the default
+        assert_eq!(effective_rate_limit(None, 0), None);
+        assert_eq!(effective_rate_limit(Some(0), 120), None);
+    }
+
+    #[test]
+    fn test_enforce_rate_limit_under_limit_passes() {
+        assert!(enforce_rate_limit(0, 60).is_ok());
+        assert!(enforce_rate_limit(59, 60).is_ok());
+    }
+
+    #[test]
+    fn test_enforce_rate_limit_at_or_over_limit_is_rejected() {
+        for previous_requests in [60, 61, 500] {
+            let err = enforce_rate_limit(previous_requests, 60).unwrap_err();
+            assert!(matches!(err, ApiError::RateLimited(60)));
+            assert_eq!(err.to_http_response("req-1").status(), 429);
+        }
+    }
+
+    #[test]
+    fn test_too_frequent_readings_below_interval_rejected() {
+        let readings = vec![
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 10_000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 10_100),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 10_400),
+        ];
+        let stored = HashMap::from([("AA:BB:CC:DD:EE:FF".to_string(), 9_500)]);
+
+        assert_eq!(
+            too_frequent_readings(&readings, &stored, 1000),
+            vec![true, true, true]
+        );
+    }
+
+    #[test]
+    fn test_too_frequent_readings_exactly_at_interval_accepted() {
+        let readings = vec![
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 11_000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 12_000),
+        ];
+        let stored = HashMap::from([("AA:BB:CC:DD:EE:FF".to_string(), 10_000)]);
+
+        assert_eq!(
+            too_frequent_readings(&readings, &stored, 1000),
+            vec![false, false]
+        );
+    }
+
+    #[test]
+    fn test_too_frequent_readings_just_below_interval_rejected() {
+        let readings = vec![
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 10_999),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 12_000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 12_999),
+        ];
+        let stored = HashMap::from([("AA:BB:CC:DD:EE:FF".to_string(), 10_000)]);
+
+        // 10_999 is 1 ms short of the stored reading; 12_999 is 1 ms short of the kept 12_000
+        assert_eq!(
+            too_frequent_readings(&readings, &stored, 1000),
+            vec![true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_too_frequent_readings_first_reading_accepted() {
+        let readings = vec![
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 10_000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 10_200),
+            reading_at("11:22:33:44:55:66", "boot-a", 10_100),
+        ];
+
+        // No stored reading: each device keeps its first, and devices are independent
+        assert_eq!(
+            too_frequent_readings(&readings, &HashMap::new(), 1000),
+            vec![false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_too_frequent_readings_leaves_repeats_to_dedup() {
+        let readings = vec![
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 10_000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 11_000),
+            reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 11_000),
+        ];
+        let stored = HashMap::from([("AA:BB:CC:DD:EE:FF".to_string(), 11_000)]);
+
+        // A retried batch matching the stored timestamps is not rejected
+        assert_eq!(
+            too_frequent_readings(&readings, &stored, 1000),
+            vec![false, false, false]
+        );
+    }
+
+    #[test]
+    fn test_stuck_sensor_warnings() {
+        let readings: Vec<Reading> = (0..4)
+            .map(|i| {
+                let mut reading = reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 1000 + i);
+                reading.sensors.soil_moisture_pct = Some(38.5);
+                reading.sensors.bme280_temp_c = Some(20.0 + i as f64);
+                reading
+            })
+            .collect();
+
+        let warnings = stuck_sensor_warnings(&readings, 4);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, STUCK_SENSOR_WARNING);
+        assert_eq!(warnings[0].hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert!(warnings[0].message.starts_with("soil_moisture_pct"));
+
+        assert!(stuck_sensor_warnings(&readings, 5).is_empty());
+    }
+
+    #[test]
+    fn test_stuck_sensor_warnings_ignores_error_status() {
+        let readings: Vec<Reading> = (0..4)
+            .map(|i| {
+                let mut reading = reading_at("AA:BB:CC:DD:EE:FF", "boot-a", 1000 + i);
+                reading.sensors.soil_moisture_pct = Some(38.5);
+                if i == 2 {
+                    reading.sensor_status.soil_moisture = "error".to_string();
+                }
+                reading
+            })
+            .collect();
+
+        assert!(stuck_sensor_warnings(&readings, 3).is_empty());
+    }
+
+    #[test]
+    fn test_data_response_warnings_serialization() {
+        let response = DataResponse {
+            acknowledged_batch_ids: vec!["batch1".to_string()],
+            duplicate_batch_ids: vec![],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: vec![IngestWarning {
+                code: TIME_REGRESSION_WARNING.to_string(),
+                hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+                message: "boot boot-a: batch starts at 4000, before last stored reading at 5000"
+                    .to_string(),
+            }],
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"code\":\"TIME_REGRESSION\""));
+
+        // Omitted when empty
+        let response = DataResponse {
+            warnings: Vec::new(),
+            ..response
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("warnings"));
+    }
+
     #[test]
     fn test_data_response_only_duplicates() {
         let response = DataResponse {
             acknowledged_batch_ids: vec![],
             duplicate_batch_ids: vec!["batch1".to_string()],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -298,4 +1527,311 @@ mod tests {
         assert_eq!(request.readings[0].sensors.ds18b20_temp_c, None);
         assert_eq!(request.readings[0].friendly_name, None);
     }
+
+    #[test]
+    fn test_data_request_with_batch_metadata() {
+        let json = r#"{
+            "readings": [
+                {
+                    "batch_id": "batch1",
+                    "hardware_id": "AA:BB:CC:DD:EE:FF",
+                    "timestamp_ms": 1704067800000,
+                    "boot_id": "550e8400-e29b-41d4-a716-446655440000",
+                    "firmware_version": "1.0.16",
+                    "friendly_name": null,
+                    "sensors": {},
+                    "sensor_status": {"bme280": "ok", "ds18b20": "ok", "soil_moisture": "ok"}
+                }
+            ],
+            "batch_metadata": {"gateway_id": "gw-01", "rssi": "-67"}
+        }"#;
+
+        let mut request: DataRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.batch_metadata.len(), 2);
+
+        esp32_backend::domain::apply_batch_metadata(&mut request.readings, &request.batch_metadata);
+        assert_eq!(
+            request.readings[0]
+                .metadata
+                .get("gateway_id")
+                .map(String::as_str),
+            Some("gw-01")
+        );
+    }
+
+    #[test]
+    fn test_data_request_batch_metadata_defaults_empty() {
+        let request: DataRequest = serde_json::from_str(r#"{"readings": []}"#).unwrap();
+        assert!(request.batch_metadata.is_empty());
+    }
+
+    fn valid_reading(batch_id: &str, timestamp_ms: i64) -> Reading {
+        let mut reading = reading_at(
+            "AA:BB:CC:DD:EE:FF",
+            "550e8400-e29b-41d4-a716-446655440000",
+            timestamp_ms,
+        );
+        reading.batch_id = batch_id.to_string();
+        reading.sensors.soil_moisture_pct = Some(41.0);
+        reading.sensor_status.bme280 = "missing".to_string();
+        reading.sensor_status.ds18b20 = "missing".to_string();
+        reading
+    }
+
+    /// Batch of four readings where the second and fourth are invalid
+    fn mixed_batch() -> Vec<Reading> {
+        let mut bad_mac = valid_reading("batch2", 1704067802000);
+        bad_mac.hardware_id = "not-a-mac".to_string();
+        let mut bad_quality = valid_reading("batch4", 1704067804000);
+        bad_quality.quality = Some(1.5);
+
+        vec![
+            valid_reading("batch1", 1704067801000),
+            bad_mac,
+            valid_reading("batch3", 1704067803000),
+            bad_quality,
+        ]
+    }
+
+    #[test]
+    fn test_validate_readings_mixed_batch() {
+        let (valid, failures) = validate_readings(mixed_batch());
+
+        let valid_ids: Vec<&str> = valid.iter().map(|r| r.batch_id.as_str()).collect();
+        assert_eq!(valid_ids, vec!["batch1", "batch3"]);
+
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].0, "batch2");
+        assert!(failures[0].1.to_string().contains("hardware_id"));
+        assert_eq!(failures[1].0, "batch4");
+        assert!(failures[1].1.to_string().contains("quality"));
+    }
+
+    #[test]
+    fn test_validate_reading_reports_every_field_error() {
+        let mut reading = valid_reading("", -1);
+        reading.hardware_id = "not-a-mac".to_string();
+
+        match validate_reading(&reading) {
+            Err(crate::error::ValidationError::Multiple(errors)) => {
+                let fields: Vec<&str> = errors.iter().map(|(field, _)| field.as_str()).collect();
+                assert_eq!(fields, vec!["hardware_id", "timestamp_ms", "batch_id"]);
+                assert!(errors.iter().all(|(_, message)| !message.is_empty()));
+            }
+            other => panic!("Expected ValidationError::Multiple, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_reading_single_error_keeps_variant() {
+        let mut reading = valid_reading("batch1", 1704067800000);
+        reading.hardware_id = "not-a-mac".to_string();
+
+        match validate_reading(&reading) {
+            Err(crate::error::ValidationError::InvalidFormat(msg)) => {
+                assert!(msg.starts_with("hardware_id: "));
+            }
+            other => panic!("Expected ValidationError::InvalidFormat, got {:?}", other),
+        }
+        assert!(validate_reading(&valid_reading("batch1", 1704067800000)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_write_readings_stores_valid_readings_despite_rejected_siblings() {
+        let (valid, _) = validate_readings(mixed_batch());
+        let writes = std::sync::Mutex::new(Vec::new());
+
+        let outcome = write_readings(&valid, |reading| {
+            writes.lock().unwrap().push(reading.batch_id.clone());
+            async { Ok(true) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(*writes.lock().unwrap(), vec!["batch1", "batch3"]);
+        let written: Vec<&str> = outcome
+            .written
+            .iter()
+            .map(|r| r.batch_id.as_str())
+            .collect();
+        assert_eq!(written, vec!["batch1", "batch3"]);
+        assert!(outcome.duplicate_batch_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_readings_duplicates_and_errors() {
+        let first = valid_reading("batch1", 1704067801000);
+        let readings = vec![
+            first.clone(),
+            valid_reading("seen-before", 1704067802000),
+            first,
+        ];
+
+        // Existing batch and in-request repeat are both duplicates
+        let outcome = write_readings(&readings, |reading| {
+            let is_new = reading.batch_id != "seen-before";
+            async move { Ok(is_new) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(outcome.written.len(), 1);
+        assert_eq!(outcome.duplicate_batch_ids, vec!["seen-before", "batch1"]);
+
+        let result = write_readings(&readings, |_| async {
+            Err(DatabaseError::DynamoDb("throttled".to_string()))
+        })
+        .await;
+        assert!(matches!(result, Err(DatabaseError::DynamoDb(_))));
+    }
+
+    #[test]
+    fn test_data_response_rejected_serialization() {
+        let response = DataResponse {
+            acknowledged_batch_ids: vec!["batch1".to_string()],
+            duplicate_batch_ids: vec![],
+            stored: None,
+            rejected_batch_ids: vec![RejectedReading {
+                batch_id: "batch2".to_string(),
+                reason: "Invalid format for field: hardware_id: bad".to_string(),
+            }],
+            warnings: Vec::new(),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains(
+            r#""rejected_batch_ids":[{"batch_id":"batch2","reason":"Invalid format for field: hardware_id: bad"}]"#
+        ));
+
+        // Omitted when nothing was rejected
+        let response = DataResponse {
+            rejected_batch_ids: Vec::new(),
+            ..response
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("rejected_batch_ids"));
+    }
+
+    const CBOR_SAMPLE_JSON: &str = r#"{
+        "readings": [
+            {
+                "batch_id": "batch1",
+                "hardware_id": "AA:BB:CC:DD:EE:FF",
+                "timestamp_ms": 1704067800000,
+                "boot_id": "550e8400-e29b-41d4-a716-446655440000",
+                "firmware_version": "1.0.16",
+                "friendly_name": "test-sensor",
+                "sensors": {"bme280_temp_c": 22.5, "humidity_pct": 45.2, "lux": 310.0},
+                "sensor_status": {"bme280": "ok", "ds18b20": "error", "soil_moisture": "ok"},
+                "quality": 0.92
+            }
+        ],
+        "batch_metadata": {"gateway_id": "gw-01"}
+    }"#;
+
+    #[test]
+    fn test_batch_hardware_id() {
+        let a = "AA:BB:CC:DD:EE:FF";
+        let b = "11:22:33:44:55:66";
+
+        assert_eq!(
+            batch_hardware_id(&[reading_at(a, "boot", 1_000), reading_at(a, "boot", 2_000)]),
+            Some(a)
+        );
+        assert_eq!(
+            batch_hardware_id(&[reading_at(a, "boot", 1_000), reading_at(b, "boot", 2_000)]),
+            None
+        );
+        assert_eq!(batch_hardware_id(&[]), None);
+    }
+
+    fn request_with_content_type(content_type: &str) -> Request {
+        lambda_http::http::Request::builder()
+            .header("content-type", content_type)
+            .body(Body::Empty)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_cbor_request() {
+        for (content_type, expected) in [
+            ("application/cbor", true),
+            ("Application/CBOR; charset=binary", true),
+            ("application/json", false),
+            ("application/cbor-seq", false),
+        ] {
+            let event = request_with_content_type(content_type);
+            assert_eq!(is_cbor_request(&event), expected, "{}", content_type);
+        }
+        assert!(!is_cbor_request(&Request::default()));
+    }
+
+    #[test]
+    fn test_data_request_cbor_round_trip() {
+        let request: DataRequest = serde_json::from_str(CBOR_SAMPLE_JSON).unwrap();
+
+        let mut cbor = Vec::new();
+        ciborium::ser::into_writer(&request, &mut cbor).unwrap();
+        assert!(cbor.len() < CBOR_SAMPLE_JSON.len());
+
+        let decoded: DataRequest = ciborium::de::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&request).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_data_request_cbor_matches_json() {
+        let from_json = parse_data_request(false, CBOR_SAMPLE_JSON.as_bytes()).unwrap();
+
+        let mut cbor = Vec::new();
+        ciborium::ser::into_writer(&from_json, &mut cbor).unwrap();
+        let from_cbor = parse_data_request(true, &cbor).unwrap();
+
+        assert_eq!(from_cbor.readings[0].sensors.extra_sensors["lux"], 310.0);
+        assert_eq!(from_cbor.readings[0].quality, Some(0.92));
+        assert_eq!(from_cbor.batch_metadata["gateway_id"], "gw-01");
+        assert_eq!(
+            serde_json::to_value(&from_cbor).unwrap(),
+            serde_json::to_value(&from_json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cbor_non_finite_sensor_values_rejected() {
+        let mut request: DataRequest = serde_json::from_str(CBOR_SAMPLE_JSON).unwrap();
+        request.readings[0].sensors.bme280_temp_c = Some(f64::NAN);
+        request.readings[0]
+            .sensors
+            .extra_sensors
+            .insert("lux".to_string(), f64::INFINITY);
+
+        let mut cbor = Vec::new();
+        ciborium::ser::into_writer(&request, &mut cbor).unwrap();
+        let request = parse_data_request(true, &cbor).unwrap();
+
+        // Nothing is left to store, and each bad field is reported
+        let (valid, mut failures) = validate_readings(request.readings);
+        assert!(valid.is_empty());
+        assert_eq!(failures.len(), 1);
+
+        let (_, e) = failures.remove(0);
+        match &e {
+            crate::error::ValidationError::Multiple(errors) => {
+                let fields: Vec<&str> = errors.iter().map(|(field, _)| field.as_str()).collect();
+                assert_eq!(fields, vec!["sensors.bme280_temp_c", "sensors.lux"]);
+            }
+            other => panic!("Expected ValidationError::Multiple, got {:?}", other),
+        }
+        assert_eq!(ApiError::from(e).to_http_response("req-1").status(), 400);
+    }
+
+    #[test]
+    fn test_parse_data_request_rejects_malformed_bodies() {
+        let err = parse_data_request(true, CBOR_SAMPLE_JSON.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("Failed to parse CBOR"));
+
+        let err = parse_data_request(false, &[0xa1, 0x00]).unwrap_err();
+        assert!(err.to_string().contains("Failed to parse JSON"));
+    }
 }
@@ -18,8 +18,9 @@ mod tests {
     use crate::config::Config;
     use crate::error::{ApiError, AuthError, ValidationError};
     use esp32_backend::domain::{Reading, SensorStatus, SensorValues};
-    use esp32_backend::FixedClock;
+    use esp32_backend::{BestEffortQueue, FixedClock};
     use lambda_http::{Body, Request};
+    use std::collections::HashMap;
 
     // ============================================================================
     // Test Helpers
@@ -54,18 +55,27 @@ mod tests {
                 humidity_pct: Some(45.2),
                 pressure_hpa: Some(1013.25),
                 soil_moisture_pct: Some(62.3),
+                extra_sensors: HashMap::new(),
             },
             sensor_status: SensorStatus {
                 bme280: "ok".to_string(),
                 ds18b20: "ok".to_string(),
                 soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
             },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
         }
     }
 
     /// Create a test request with the given readings
     fn create_test_request(readings: Vec<Reading>, api_key: Option<&str>) -> Request {
-        let data_request = DataRequest { readings };
+        let data_request = DataRequest {
+            readings,
+            batch_metadata: HashMap::new(),
+        };
         let body_json = serde_json::to_string(&data_request).unwrap();
 
         let mut req = lambda_http::http::Request::builder()
@@ -88,11 +98,12 @@ mod tests {
     async fn test_data_missing_api_key_header() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         let reading = create_test_reading("batch1", 1704067800000);
         let request = create_test_request(vec![reading], None); // No API key
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return authentication error
         assert!(result.is_err());
@@ -115,6 +126,7 @@ mod tests {
     async fn test_data_empty_body() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         let request = lambda_http::http::Request::builder()
             .method("POST")
@@ -124,7 +136,7 @@ mod tests {
             .unwrap()
             .into();
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error for empty body
         assert!(result.is_err());
@@ -140,6 +152,7 @@ mod tests {
     async fn test_data_malformed_json() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         let request = lambda_http::http::Request::builder()
             .method("POST")
@@ -149,7 +162,7 @@ mod tests {
             .unwrap()
             .into();
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error for malformed JSON
         assert!(result.is_err());
@@ -169,6 +182,7 @@ mod tests {
     async fn test_data_batch_size_limit_exceeded() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         // Create 101 readings (exceeds limit of 100)
         let mut readings = Vec::new();
@@ -179,7 +193,7 @@ mod tests {
 
         let request = create_test_request(readings, Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error for batch size exceeded
         assert!(result.is_err());
@@ -195,6 +209,7 @@ mod tests {
     async fn test_data_batch_size_limit_exactly_100() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         // Create exactly 100 readings (at the limit)
         let mut readings = Vec::new();
@@ -207,7 +222,7 @@ mod tests {
 
         // This will fail at API key validation since we don't have a real DynamoDB
         // But it should NOT fail at batch size validation
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should fail at auth, not batch size
         assert!(result.is_err());
@@ -232,13 +247,14 @@ mod tests {
     async fn test_data_invalid_hardware_id() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         let mut reading = create_test_reading("batch1", 1704067800000);
         reading.hardware_id = "invalid-mac".to_string(); // Invalid MAC format
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error for invalid hardware_id
         assert!(result.is_err());
@@ -254,13 +270,14 @@ mod tests {
     async fn test_data_invalid_hardware_id_lowercase() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         let mut reading = create_test_reading("batch1", 1704067800000);
         reading.hardware_id = "aa:bb:cc:dd:ee:ff".to_string(); // Lowercase (invalid)
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error
         assert!(result.is_err());
@@ -276,12 +293,13 @@ mod tests {
     async fn test_data_invalid_timestamp_negative() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         let reading = create_test_reading("batch1", -1); // Negative timestamp
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error for invalid timestamp
         assert!(result.is_err());
@@ -297,13 +315,14 @@ mod tests {
     async fn test_data_invalid_timestamp_too_old() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         // Timestamp before year 2000
         let reading = create_test_reading("batch1", 946684799999);
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error
         assert!(result.is_err());
@@ -319,13 +338,14 @@ mod tests {
     async fn test_data_invalid_timestamp_too_new() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         // Timestamp after year 2100
         let reading = create_test_reading("batch1", 4102444800001);
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error
         assert!(result.is_err());
@@ -341,12 +361,13 @@ mod tests {
     async fn test_data_invalid_batch_id_empty() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         let reading = create_test_reading("", 1704067800000); // Empty batch_id
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error
         assert!(result.is_err());
@@ -362,6 +383,7 @@ mod tests {
     async fn test_data_invalid_batch_id_too_long() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         // Batch ID longer than 256 characters
         let long_batch_id = "a".repeat(257);
@@ -369,7 +391,7 @@ mod tests {
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error
         assert!(result.is_err());
@@ -385,13 +407,14 @@ mod tests {
     async fn test_data_invalid_batch_id_control_characters() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         // Batch ID with control characters
         let reading = create_test_reading("batch\nid", 1704067800000);
 
         let request = create_test_request(vec![reading], Some("test-key-123"));
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should return validation error
         assert!(result.is_err());
@@ -412,6 +435,9 @@ mod tests {
         let response = DataResponse {
             acknowledged_batch_ids: vec!["batch1".to_string(), "batch2".to_string()],
             duplicate_batch_ids: vec!["batch3".to_string()],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         // Verify structure
@@ -427,6 +453,9 @@ mod tests {
         let response = DataResponse {
             acknowledged_batch_ids: vec!["batch1".to_string()],
             duplicate_batch_ids: vec!["batch2".to_string()],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -449,6 +478,9 @@ mod tests {
         let response = DataResponse {
             acknowledged_batch_ids: vec![],
             duplicate_batch_ids: vec![],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -464,6 +496,9 @@ mod tests {
         let response = DataResponse {
             acknowledged_batch_ids: vec!["batch1".to_string(), "batch2".to_string()],
             duplicate_batch_ids: vec!["batch3".to_string(), "batch4".to_string()],
+            stored: None,
+            rejected_batch_ids: Vec::new(),
+            warnings: Vec::new(),
         };
 
         // Convert to sets to check for overlap
@@ -491,6 +526,7 @@ mod tests {
 
         let request = DataRequest {
             readings: readings.clone(),
+            batch_metadata: HashMap::new(),
         };
 
         assert_eq!(request.readings.len(), 3);
@@ -504,6 +540,7 @@ mod tests {
         let reading = create_test_reading("batch1", 1704067800000);
         let request = DataRequest {
             readings: vec![reading],
+            batch_metadata: HashMap::new(),
         };
 
         assert_eq!(request.readings.len(), 1);
@@ -519,6 +556,7 @@ mod tests {
 
         let request = DataRequest {
             readings: readings.clone(),
+            batch_metadata: HashMap::new(),
         };
 
         // Serialize
@@ -562,12 +600,18 @@ mod tests {
                 humidity_pct: None,      // No humidity reading
                 pressure_hpa: None,      // No pressure reading
                 soil_moisture_pct: None, // No soil moisture reading
+                extra_sensors: HashMap::new(),
             },
             sensor_status: SensorStatus {
                 bme280: "ok".to_string(),
                 ds18b20: "error".to_string(),
                 soil_moisture: "error".to_string(),
+                extra_status: HashMap::new(),
             },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
         };
 
         assert!(reading.friendly_name.is_none());
@@ -591,12 +635,18 @@ mod tests {
                 humidity_pct: None,
                 pressure_hpa: None,
                 soil_moisture_pct: None,
+                extra_sensors: HashMap::new(),
             },
             sensor_status: SensorStatus {
                 bme280: "error".to_string(),
                 ds18b20: "error".to_string(),
                 soil_moisture: "error".to_string(),
+                extra_status: HashMap::new(),
             },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
         };
 
         // All sensors should be in error state
@@ -650,7 +700,10 @@ mod tests {
 
     #[test]
     fn test_data_request_empty_readings_array() {
-        let request = DataRequest { readings: vec![] };
+        let request = DataRequest {
+            readings: vec![],
+            batch_metadata: HashMap::new(),
+        };
 
         assert_eq!(request.readings.len(), 0);
 
@@ -668,6 +721,7 @@ mod tests {
     async fn test_validation_order_auth_before_batch_size() {
         let config = create_test_config().await;
         let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let writes = BestEffortQueue::default();
 
         // Create 101 readings (exceeds batch size limit)
         let mut readings = Vec::new();
@@ -681,7 +735,7 @@ mod tests {
         // No API key provided
         let request = create_test_request(readings, None);
 
-        let result = handle_data(request, "test-request-id", &config, &clock).await;
+        let result = handle_data(request, "test-request-id", &config, &clock, &writes).await;
 
         // Should fail at auth first, not batch size validation
         assert!(result.is_err());
@@ -1,12 +1,19 @@
-use lambda_http::{Body, Request, Response};
+use chrono::DateTime;
+use lambda_http::{Body, Request, RequestExt, Response};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use crate::auth::validate_api_key;
-use crate::error::ApiError;
+use crate::config::ApiKeyHashScheme;
+use crate::error::{ApiError, DatabaseError};
 use crate::repo::devices::{create_device, get_device, update_device_timestamps};
+use crate::repo::idempotency::{
+    get_idempotency_record, put_idempotency_record, request_hash, IdempotencyRecord,
+};
+use esp32_backend::shared::observability::record_hardware_id;
 use esp32_backend::{
-    validate_mac_address, validate_uuid_v4, Capabilities, Clock, Device, IdGenerator,
+    validate_capabilities, validate_mac_address, validate_uuid, BestEffortQueue, Capabilities,
+    Clock, Device, IdGenerator, BOOT_ID_UUID_VERSIONS,
 };
 
 /// Request payload for device registration
@@ -52,6 +59,40 @@ pub struct RegisterResponse {
     pub registered_at: String,
 }
 
+/// Response payload for a dry-run registration
+///
+/// Describes what the registration would have done; nothing is written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunResponse {
+    /// "would_create" for an unknown device, "would_update" for an existing one
+    pub status: String,
+
+    /// Hardware ID (MAC address) echoed back from the request
+    pub hardware_id: String,
+
+    /// Existing confirmation_id; absent when the device would be created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_id: Option<String>,
+}
+
+impl DryRunResponse {
+    /// Describe the registration of `hardware_id` given the stored device, if any
+    pub fn for_device(hardware_id: &str, existing: Option<&Device>) -> Self {
+        match existing {
+            Some(device) => Self {
+                status: "would_update".to_string(),
+                hardware_id: hardware_id.to_string(),
+                confirmation_id: Some(device.confirmation_id.clone()),
+            },
+            None => Self {
+                status: "would_create".to_string(),
+                hardware_id: hardware_id.to_string(),
+                confirmation_id: None,
+            },
+        }
+    }
+}
+
 impl RegisterResponse {
     /// Create a new RegisterResponse from a Device record
     ///
@@ -73,26 +114,47 @@ impl RegisterResponse {
 /// It validates the API key, parses the request body, checks if the device
 /// already exists, and either creates a new device or updates an existing one.
 ///
+/// With `?dry_run=true` (or an `X-Dry-Run: true` header) it stops after the
+/// existence check and returns a `DryRunResponse` without writing the device.
+///
+/// With an `Idempotency-Key` header the response is stored under the key in
+/// the processed_batches table. A repeat with the same key and body gets the
+/// stored response back verbatim without re-registering; a repeat with a
+/// different body is rejected with 409.
+///
 /// # Arguments
 /// * `event` - Lambda HTTP request event
 /// * `request_id` - Request ID for logging and error responses
 /// * `dynamodb_client` - DynamoDB client for database operations
 /// * `devices_table` - Name of the devices table
 /// * `api_keys_table` - Name of the API keys table
+/// * `idempotency_table` - Table holding `Idempotency-Key` records (processed_batches)
 /// * `clock` - Clock implementation for timestamp generation
+/// * `last_used_throttle_minutes` - Minimum age of an API key's last_used_at before it is rewritten
+/// * `api_key_hash_scheme` - Scheme older API key hashes are upgraded to
+/// * `allow_unknown_capabilities` - Skip checking capability names against the known set
+/// * `retry_window_seconds` - Same-boot re-registrations within this window skip the update
 /// * `id_generator` - IdGenerator implementation for UUID generation
+/// * `writes` - Queue for best-effort metadata writes
 ///
 /// # Returns
 /// * `Ok(Response)` - HTTP 200 with RegisterResponse on success
 /// * `Err(ApiError)` - Authentication, validation, or database error
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_register(
     event: Request,
     request_id: &str,
     dynamodb_client: &aws_sdk_dynamodb::Client,
     devices_table: &str,
     api_keys_table: &str,
+    idempotency_table: &str,
     clock: &dyn Clock,
+    last_used_throttle_minutes: i64,
+    api_key_hash_scheme: ApiKeyHashScheme,
+    allow_unknown_capabilities: bool,
+    retry_window_seconds: i64,
     id_generator: &dyn IdGenerator,
+    writes: &BestEffortQueue,
 ) -> Result<Response<Body>, ApiError> {
     info!(request_id = %request_id, "Processing registration request");
 
@@ -104,7 +166,24 @@ pub async fn handle_register(
         .ok_or(crate::error::AuthError::MissingKey)?;
 
     info!(request_id = %request_id, "Validating API key");
-    validate_api_key(dynamodb_client, api_keys_table, api_key, clock).await?;
+    validate_api_key(
+        dynamodb_client,
+        api_keys_table,
+        api_key,
+        clock,
+        last_used_throttle_minutes,
+        api_key_hash_scheme,
+        writes,
+    )
+    .await?;
+
+    let dry_run = parse_dry_run(
+        event.query_string_parameters().first("dry_run"),
+        event
+            .headers()
+            .get("x-dry-run")
+            .and_then(|v| v.to_str().ok()),
+    )?;
 
     // Step 2: Parse and validate request body
     let body_bytes = match event.body() {
@@ -117,6 +196,32 @@ pub async fn handle_register(
         }
     };
 
+    // A dry run writes nothing, so there is nothing to replay or remember
+    let idempotency_key = if dry_run {
+        None
+    } else {
+        parse_idempotency_key(
+            event
+                .headers()
+                .get("idempotency-key")
+                .map(|v| v.to_str().unwrap_or_default()),
+        )?
+    };
+    let body_hash = request_hash(body_bytes);
+
+    if let Some(ref key) = idempotency_key {
+        if let Some(record) =
+            get_idempotency_record(dynamodb_client, idempotency_table, key).await?
+        {
+            info!(
+                request_id = %request_id,
+                confirmation_id = %record.confirmation_id,
+                "Repeated Idempotency-Key, replaying stored response"
+            );
+            return replay_response(&record, &body_hash);
+        }
+    }
+
     let request: RegisterRequest = serde_json::from_slice(body_bytes).map_err(|e| {
         warn!(request_id = %request_id, error = %e, "Failed to parse request body");
         ApiError::Validation(crate::error::ValidationError::InvalidFormat(format!(
@@ -132,13 +237,14 @@ pub async fn handle_register(
         firmware_version = %request.firmware_version,
         "Parsed registration request"
     );
+    record_hardware_id(&request.hardware_id);
 
     // Step 3: Validate request fields using shared validators
     validate_mac_address(&request.hardware_id).map_err(|e| {
         ApiError::Validation(crate::error::ValidationError::InvalidFormat(e.to_string()))
     })?;
 
-    validate_uuid_v4(&request.boot_id).map_err(|e| {
+    validate_uuid(&request.boot_id, BOOT_ID_UUID_VERSIONS).map_err(|e| {
         ApiError::Validation(crate::error::ValidationError::InvalidFormat(e.to_string()))
     })?;
 
@@ -149,6 +255,14 @@ pub async fn handle_register(
         ));
     }
 
+    // Reject unknown sensor names and feature keys (e.g. typos) unless allowed
+    if !allow_unknown_capabilities {
+        validate_capabilities(&request.capabilities).map_err(|e| {
+            warn!(request_id = %request_id, error = %e, "Unknown capabilities in registration");
+            ApiError::Validation(crate::error::ValidationError::InvalidFormat(e.to_string()))
+        })?;
+    }
+
     // Step 4: Check if device exists
     info!(
         request_id = %request_id,
@@ -158,7 +272,35 @@ pub async fn handle_register(
 
     let existing_device = get_device(dynamodb_client, devices_table, &request.hardware_id).await?;
 
+    if dry_run {
+        let response = DryRunResponse::for_device(&request.hardware_id, existing_device.as_ref());
+
+        info!(
+            request_id = %request_id,
+            hardware_id = %request.hardware_id,
+            status = %response.status,
+            "Dry-run registration, skipping writes"
+        );
+
+        return json_response(&response);
+    }
+
     let response = match existing_device {
+        // Same boot registered moments ago (connectivity retry) - nothing to update
+        Some(device)
+            if is_same_boot_retry(&device, &request.boot_id, clock, retry_window_seconds) =>
+        {
+            info!(
+                request_id = %request_id,
+                hardware_id = %request.hardware_id,
+                confirmation_id = %device.confirmation_id,
+                "Repeated registration within boot session, skipping update"
+            );
+
+            let last_seen_at = device.last_seen_at.clone();
+            RegisterResponse::from_device(&device, last_seen_at)
+        }
+
         // Device exists - update timestamps and last_boot_id
         Some(device) => {
             info!(
@@ -170,13 +312,14 @@ pub async fn handle_register(
 
             let now = clock.now_rfc3339();
 
-            // Update last_seen_at and last_boot_id
+            // Update last_seen_at and last_boot_id; a new boot resets readings_this_boot
             update_device_timestamps(
                 dynamodb_client,
                 devices_table,
                 &request.hardware_id,
                 &now,
                 &request.boot_id,
+                device.last_boot_id != request.boot_id,
             )
             .await?;
 
@@ -202,38 +345,47 @@ pub async fn handle_register(
                 "Generated confirmation_id"
             );
 
-            let now = clock.now_rfc3339();
-
             // Create new device record
-            let device = Device {
-                hardware_id: request.hardware_id.clone(),
-                confirmation_id: confirmation_id.clone(),
-                friendly_name: request.friendly_name.clone(),
-                firmware_version: request.firmware_version.clone(),
-                capabilities: request.capabilities.clone(),
-                first_registered_at: now.clone(),
-                last_seen_at: now.clone(),
-                last_boot_id: request.boot_id.clone(),
-            };
-
-            // Store device in DynamoDB
-            create_device(dynamodb_client, devices_table, &device).await?;
-
-            info!(
-                request_id = %request_id,
-                hardware_id = %request.hardware_id,
-                confirmation_id = %confirmation_id,
-                "Device created successfully"
-            );
-
-            RegisterResponse::from_device(&device, now)
+            let device = Device::builder()
+                .hardware_id(request.hardware_id.clone())
+                .confirmation_id(confirmation_id.clone())
+                .friendly_name(request.friendly_name.clone())
+                .firmware_version(request.firmware_version.clone())
+                .capabilities(request.capabilities.clone())
+                .last_boot_id(request.boot_id.clone())
+                .build(clock)
+                .map_err(|e| crate::error::ValidationError::MissingField(e.field))?;
+
+            // Store device in DynamoDB, unless another request created it first
+            let created = create_device(dynamodb_client, devices_table, &device).await;
+
+            match create_outcome(created)? {
+                CreateOutcome::Created => {
+                    info!(
+                        request_id = %request_id,
+                        hardware_id = %request.hardware_id,
+                        confirmation_id = %confirmation_id,
+                        "Device created successfully"
+                    );
+
+                    RegisterResponse::from_device(&device, device.first_registered_at.clone())
+                }
+                CreateOutcome::AlreadyExists => {
+                    warn!(
+                        request_id = %request_id,
+                        hardware_id = %request.hardware_id,
+                        "Device created by a concurrent registration, returning existing record"
+                    );
+
+                    let existing =
+                        get_device(dynamodb_client, devices_table, &request.hardware_id).await?;
+                    lost_race_response(existing)?
+                }
+            }
         }
     };
 
     // Step 5: Return RegisterResponse
-    let response_body = serde_json::to_string(&response)
-        .map_err(|e| ApiError::Internal(format!("Failed to serialize response: {}", e)))?;
-
     info!(
         request_id = %request_id,
         hardware_id = %request.hardware_id,
@@ -241,6 +393,40 @@ pub async fn handle_register(
         "Registration completed successfully"
     );
 
+    let Some(key) = idempotency_key else {
+        return json_response(&response);
+    };
+
+    let record = idempotency_record(key, body_hash, &response)?;
+    match put_idempotency_record(dynamodb_client, idempotency_table, &record, clock).await {
+        Ok(()) => {}
+        // A concurrent request with the same key stored its response first
+        Err(DatabaseError::ConditionalCheckFailed) => {
+            if let Some(stored) =
+                get_idempotency_record(dynamodb_client, idempotency_table, &record.key).await?
+            {
+                return replay_response(&stored, &record.request_hash);
+            }
+        }
+        // The device is registered; a retry without the record just registers again
+        Err(e) => {
+            warn!(request_id = %request_id, error = %e, "Failed to store Idempotency-Key record");
+        }
+    }
+
+    body_response(record.response_body)
+}
+
+/// Build an HTTP 200 response with a JSON body
+fn json_response<T: Serialize>(body: &T) -> Result<Response<Body>, ApiError> {
+    let response_body = serde_json::to_string(body)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize response: {}", e)))?;
+
+    body_response(response_body)
+}
+
+/// Build an HTTP 200 response from an already-serialized JSON body
+fn body_response(response_body: String) -> Result<Response<Body>, ApiError> {
     Response::builder()
         .status(200)
         .header("content-type", "application/json")
@@ -248,9 +434,135 @@ pub async fn handle_register(
         .map_err(|e| ApiError::Internal(format!("Failed to build response: {}", e)))
 }
 
+/// Parse the dry-run flag from the `dry_run` query parameter or `X-Dry-Run` header
+///
+/// Either set to "true" enables it; both default to false.
+fn parse_dry_run(query: Option<&str>, header: Option<&str>) -> Result<bool, ApiError> {
+    let parse = |value: Option<&str>| match value {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(_) => Err(ApiError::from(crate::error::ValidationError::InvalidValue(
+            "dry_run".to_string(),
+        ))),
+    };
+
+    Ok(parse(query)? || parse(header)?)
+}
+
+/// Longest `Idempotency-Key` header value accepted
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Parse the optional `Idempotency-Key` header
+///
+/// Keys must be 1 to `MAX_IDEMPOTENCY_KEY_LEN` printable ASCII characters.
+fn parse_idempotency_key(header: Option<&str>) -> Result<Option<String>, ApiError> {
+    match header {
+        None => Ok(None),
+        Some(key)
+            if !key.is_empty()
+                && key.len() <= MAX_IDEMPOTENCY_KEY_LEN
+                && key.bytes().all(|b| (b' '..=b'~').contains(&b)) =>
+        {
+            Ok(Some(key.to_string()))
+        }
+        Some(_) => {
+            Err(crate::error::ValidationError::InvalidValue("Idempotency-Key".to_string()).into())
+        }
+    }
+}
+
+/// Build the record that remembers a registration under its `Idempotency-Key`
+fn idempotency_record(
+    key: String,
+    request_hash: String,
+    response: &RegisterResponse,
+) -> Result<IdempotencyRecord, ApiError> {
+    let response_body = serde_json::to_string(response)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize response: {}", e)))?;
+
+    Ok(IdempotencyRecord {
+        key,
+        request_hash,
+        confirmation_id: response.confirmation_id.clone(),
+        response_body,
+    })
+}
+
+/// Answer a repeated `Idempotency-Key` from its stored record
+///
+/// The same body gets the stored response verbatim; a different body means
+/// the key was reused for another request, which is a conflict.
+fn replay_response(
+    record: &IdempotencyRecord,
+    request_hash: &str,
+) -> Result<Response<Body>, ApiError> {
+    if record.request_hash != request_hash {
+        return Err(ApiError::IdempotencyConflict);
+    }
+
+    body_response(record.response_body.clone())
+}
+
+/// Result of the conditional device create
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CreateOutcome {
+    /// This request created the device record
+    Created,
+    /// A concurrent registration created the record first; nothing was written
+    AlreadyExists,
+}
+
+/// Map a conditional create result to a create outcome
+fn create_outcome(result: Result<(), DatabaseError>) -> Result<CreateOutcome, DatabaseError> {
+    match result {
+        Ok(()) => Ok(CreateOutcome::Created),
+        Err(DatabaseError::ConditionalCheckFailed) => Ok(CreateOutcome::AlreadyExists),
+        Err(e) => Err(e),
+    }
+}
+
+/// Build the response for a registration that lost the create race
+///
+/// Returns the winner's confirmation_id so both callers agree on it. The
+/// record can only be missing here if it was deleted between the failed
+/// create and the read.
+fn lost_race_response(existing: Option<Device>) -> Result<RegisterResponse, ApiError> {
+    let device = existing.ok_or_else(|| {
+        ApiError::Internal("Device missing after conditional create failed".to_string())
+    })?;
+
+    let last_seen_at = device.last_seen_at.clone();
+    Ok(RegisterResponse::from_device(&device, last_seen_at))
+}
+
+/// Whether a registration repeats the device's current boot within the retry window
+///
+/// True when `last_boot_id` matches `boot_id` and `last_seen_at` is less than
+/// `window_seconds` old. A window of 0 (or an unparseable `last_seen_at`)
+/// always returns false, so the update runs.
+fn is_same_boot_retry(
+    device: &Device,
+    boot_id: &str,
+    clock: &dyn Clock,
+    window_seconds: i64,
+) -> bool {
+    if device.last_boot_id != boot_id {
+        return false;
+    }
+
+    let last_seen = match DateTime::parse_from_rfc3339(&device.last_seen_at) {
+        Ok(dt) => dt.timestamp(),
+        Err(_) => return false,
+    };
+
+    let age_seconds = clock.now_epoch_seconds() - last_seen;
+    (0..window_seconds).contains(&age_seconds)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use esp32_backend::FixedClock;
     use std::collections::HashMap;
 
     #[test]
@@ -377,6 +689,9 @@ mod tests {
             first_registered_at: "2024-01-15T10:30:00Z".to_string(),
             last_seen_at: "2024-01-15T14:22:00Z".to_string(),
             last_boot_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
         };
 
         let response = RegisterResponse::from_device(&device, "2024-01-15T14:22:00Z".to_string());
@@ -437,4 +752,205 @@ mod tests {
         assert!(json.get("hardware_id").is_some());
         assert!(json.get("registered_at").is_some());
     }
+
+    fn registered_device(last_boot_id: &str, last_seen_at: &str) -> Device {
+        Device {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            confirmation_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            friendly_name: None,
+            firmware_version: "1.0.16".to_string(),
+            capabilities: Capabilities {
+                sensors: vec!["bme280".to_string()],
+                features: HashMap::new(),
+            },
+            first_registered_at: "2024-01-15T10:00:00Z".to_string(),
+            last_seen_at: last_seen_at.to_string(),
+            last_boot_id: last_boot_id.to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
+        }
+    }
+
+    const BOOT_ID: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+    #[test]
+    fn test_is_same_boot_retry_recent_skips_update() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:30Z").unwrap();
+        let device = registered_device(BOOT_ID, "2024-01-15T10:30:00Z");
+
+        assert!(is_same_boot_retry(&device, BOOT_ID, &clock, 300));
+    }
+
+    #[test]
+    fn test_is_same_boot_retry_stale_updates() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:35:00Z").unwrap();
+        let device = registered_device(BOOT_ID, "2024-01-15T10:30:00Z");
+
+        // Exactly at the window edge counts as stale
+        assert!(!is_same_boot_retry(&device, BOOT_ID, &clock, 300));
+        // A window of 0 disables the short-circuit
+        assert!(!is_same_boot_retry(&device, BOOT_ID, &clock, 0));
+    }
+
+    #[test]
+    fn test_is_same_boot_retry_different_boot_updates() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:30Z").unwrap();
+        let device = registered_device(BOOT_ID, "2024-01-15T10:30:00Z");
+
+        assert!(!is_same_boot_retry(
+            &device,
+            "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+            &clock,
+            300
+        ));
+    }
+
+    #[test]
+    fn test_create_outcome_conditional_failure_is_already_exists() {
+        assert_eq!(create_outcome(Ok(())).unwrap(), CreateOutcome::Created);
+        assert_eq!(
+            create_outcome(Err(DatabaseError::ConditionalCheckFailed)).unwrap(),
+            CreateOutcome::AlreadyExists
+        );
+        assert!(matches!(
+            create_outcome(Err(DatabaseError::DynamoDb("throttled".to_string()))),
+            Err(DatabaseError::DynamoDb(_))
+        ));
+    }
+
+    #[test]
+    fn test_lost_race_response_returns_existing_confirmation_id() {
+        let winner = registered_device(BOOT_ID, "2024-01-15T10:30:00Z");
+
+        let response = lost_race_response(Some(winner.clone())).unwrap();
+
+        assert_eq!(response.confirmation_id, winner.confirmation_id);
+        assert_eq!(response.hardware_id, winner.hardware_id);
+        assert_eq!(response.registered_at, "2024-01-15T10:30:00Z");
+    }
+
+    #[test]
+    fn test_lost_race_response_missing_device() {
+        assert!(matches!(
+            lost_race_response(None),
+            Err(ApiError::Internal(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_dry_run() {
+        assert!(!parse_dry_run(None, None).unwrap());
+        assert!(parse_dry_run(Some("true"), None).unwrap());
+        assert!(parse_dry_run(None, Some("true")).unwrap());
+        assert!(!parse_dry_run(Some("false"), Some("false")).unwrap());
+        assert!(parse_dry_run(Some("yes"), None).is_err());
+        assert!(parse_dry_run(None, Some("1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_idempotency_key() {
+        assert_eq!(parse_idempotency_key(None).unwrap(), None);
+        assert_eq!(
+            parse_idempotency_key(Some("retry-7f3a")).unwrap(),
+            Some("retry-7f3a".to_string())
+        );
+        assert!(parse_idempotency_key(Some(&"k".repeat(255))).is_ok());
+
+        for invalid in ["", "tab\tkey", "caf\u{e9}"] {
+            assert!(matches!(
+                parse_idempotency_key(Some(invalid)),
+                Err(ApiError::Validation(crate::error::ValidationError::InvalidValue(ref field))) if field == "Idempotency-Key"
+            ));
+        }
+        assert!(parse_idempotency_key(Some(&"k".repeat(256))).is_err());
+    }
+
+    fn idempotent_response() -> RegisterResponse {
+        RegisterResponse {
+            status: "registered".to_string(),
+            confirmation_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            registered_at: "2024-01-15T10:30:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_idempotency_first_call_stores_response() {
+        let response = idempotent_response();
+        let record = idempotency_record(
+            "retry-7f3a".to_string(),
+            request_hash(b"{\"hardware_id\":\"AA:BB:CC:DD:EE:FF\"}"),
+            &response,
+        )
+        .unwrap();
+
+        assert_eq!(record.key, "retry-7f3a");
+        assert_eq!(record.confirmation_id, response.confirmation_id);
+        assert_eq!(
+            record.response_body,
+            serde_json::to_string(&response).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_idempotency_repeat_same_body_returns_cached() {
+        let body = b"{\"hardware_id\":\"AA:BB:CC:DD:EE:FF\"}";
+        let record = idempotency_record(
+            "retry-7f3a".to_string(),
+            request_hash(body),
+            &idempotent_response(),
+        )
+        .unwrap();
+
+        let replay = replay_response(&record, &request_hash(body)).unwrap();
+
+        assert_eq!(replay.status(), 200);
+        match replay.body() {
+            Body::Text(text) => assert_eq!(text, &record.response_body),
+            _ => panic!("Expected text body"),
+        }
+    }
+
+    #[test]
+    fn test_idempotency_repeat_different_body_conflicts() {
+        let record = idempotency_record(
+            "retry-7f3a".to_string(),
+            request_hash(b"{\"hardware_id\":\"AA:BB:CC:DD:EE:FF\"}"),
+            &idempotent_response(),
+        )
+        .unwrap();
+
+        let result = replay_response(
+            &record,
+            &request_hash(b"{\"hardware_id\":\"11:22:33:44:55:66\"}"),
+        );
+
+        assert!(matches!(result, Err(ApiError::IdempotencyConflict)));
+    }
+
+    #[test]
+    fn test_dry_run_new_device_would_create() {
+        let response = DryRunResponse::for_device("AA:BB:CC:DD:EE:FF", None);
+
+        assert_eq!(response.status, "would_create");
+        assert_eq!(response.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(response.confirmation_id, None);
+
+        let json: serde_json::Value = serde_json::to_value(&response).unwrap();
+        assert!(json.get("confirmation_id").is_none());
+    }
+
+    #[test]
+    fn test_dry_run_existing_device_would_update() {
+        let existing = registered_device(BOOT_ID, "2024-01-15T10:30:00Z");
+
+        let response = DryRunResponse::for_device("AA:BB:CC:DD:EE:FF", Some(&existing));
+
+        assert_eq!(response.status, "would_update");
+        assert_eq!(
+            response.confirmation_id.as_deref(),
+            Some("7c9e6679-7425-40de-944b-e07fc1f90ae7")
+        );
+    }
 }
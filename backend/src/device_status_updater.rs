@@ -5,6 +5,9 @@ use std::collections::HashMap;
 use std::env;
 use tracing::{error, info, warn};
 
+use esp32_backend::device_status_update::{
+    exceeds_skew_threshold, DEFAULT_SKEW_WARN_THRESHOLD_SEC,
+};
 use esp32_backend::plant_insights::Reading;
 
 #[tokio::main]
@@ -27,6 +30,15 @@ async fn main() -> Result<(), Error> {
         .expect("DEVICE_PROFILES_TABLE environment variable must be set");
     let processed_readings_table = env::var("PROCESSED_READINGS_TABLE")
         .expect("PROCESSED_READINGS_TABLE environment variable must be set");
+    let skew_warn_threshold_sec = match env::var("SKEW_WARN_THRESHOLD_SEC") {
+        Ok(value) => value
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .filter(|sec| *sec >= 0)
+            .expect("SKEW_WARN_THRESHOLD_SEC must be a non-negative integer"),
+        Err(_) => DEFAULT_SKEW_WARN_THRESHOLD_SEC,
+    };
 
     run(service_fn(|event: LambdaEvent<DynamoDbEvent>| {
         function_handler(
@@ -35,6 +47,7 @@ async fn main() -> Result<(), Error> {
             device_status_table.clone(),
             device_profiles_table.clone(),
             processed_readings_table.clone(),
+            skew_warn_threshold_sec,
         )
     }))
     .await
@@ -46,6 +59,7 @@ async fn function_handler(
     device_status_table: String,
     device_profiles_table: String,
     processed_readings_table: String,
+    skew_warn_threshold_sec: i64,
 ) -> Result<(), Error> {
     info!(
         "Processing DynamoDB stream batch with {} records",
@@ -63,6 +77,7 @@ async fn function_handler(
             &device_status_table,
             &device_profiles_table,
             &processed_readings_table,
+            skew_warn_threshold_sec,
         )
         .await
         {
@@ -90,6 +105,7 @@ async fn process_record(
     device_status_table: &str,
     device_profiles_table: &str,
     processed_readings_table: &str,
+    skew_warn_threshold_sec: i64,
 ) -> Result<bool, Error> {
     // Only process INSERT and MODIFY events
     let event_name = record.event_name.as_str();
@@ -126,6 +142,7 @@ async fn process_record(
         device_status_table,
         &reading,
         expected_interval_sec,
+        skew_warn_threshold_sec,
     )
     .await?;
 
@@ -259,6 +276,7 @@ async fn update_device_status(
     device_status_table: &str,
     reading: &Reading,
     expected_interval_sec: i64,
+    skew_warn_threshold_sec: i64,
 ) -> Result<(), Error> {
     let now_ms = chrono::Utc::now().timestamp_millis();
 
@@ -266,12 +284,13 @@ async fn update_device_status(
     let ingest_event_skew_seconds = (reading.ingest_time_ms - reading.timestamp_ms) / 1000;
     let pipeline_lag_seconds = (now_ms - reading.timestamp_ms) / 1000;
 
-    // Log clock skew warning if event_time is more than 5 minutes ahead of ingest_time
-    if reading.timestamp_ms > reading.ingest_time_ms + (5 * 60 * 1000) {
+    // Flag the device clock when event_time drifts past the threshold either way
+    let clock_skew_warning =
+        exceeds_skew_threshold(ingest_event_skew_seconds, skew_warn_threshold_sec);
+    if clock_skew_warning {
         warn!(
-            "Clock skew detected for device {}: event_time is {} ms ahead of ingest_time",
-            reading.hardware_id,
-            reading.timestamp_ms - reading.ingest_time_ms
+            "Clock skew detected for device {}: ingest_time - event_time is {}s (threshold {}s)",
+            reading.hardware_id, ingest_event_skew_seconds, skew_warn_threshold_sec
         );
     }
 
@@ -283,12 +302,13 @@ async fn update_device_status(
 
     // Build update expression for field-owned updates
     // Status Updater owns: last_seen_event_time_ms, last_seen_ingest_time_ms,
-    // ingest_event_skew_seconds, pipeline_lag_seconds, expected_interval_sec,
-    // sensor_status_summary, health_category, updated_at_ms
+    // ingest_event_skew_seconds, clock_skew_warning, pipeline_lag_seconds,
+    // expected_interval_sec, sensor_status_summary, health_category, updated_at_ms
     let update_expression = "SET \
         last_seen_event_time_ms = :event_time, \
         last_seen_ingest_time_ms = :ingest_time, \
         ingest_event_skew_seconds = :skew, \
+        clock_skew_warning = :skew_warning, \
         pipeline_lag_seconds = :lag, \
         expected_interval_sec = :interval, \
         last_processed_event_time_ms = :event_time, \
@@ -309,6 +329,10 @@ async fn update_device_status(
         ":skew".to_string(),
         aws_sdk_dynamodb::types::AttributeValue::N(ingest_event_skew_seconds.to_string()),
     );
+    expression_values.insert(
+        ":skew_warning".to_string(),
+        aws_sdk_dynamodb::types::AttributeValue::Bool(clock_skew_warning),
+    );
     expression_values.insert(
         ":lag".to_string(),
         aws_sdk_dynamodb::types::AttributeValue::N(pipeline_lag_seconds.to_string()),
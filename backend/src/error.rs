@@ -1,7 +1,7 @@
 use lambda_http::{Body, Response};
 use thiserror::Error;
 
-use esp32_backend::shared::error::{error_codes, ErrorResponse};
+use esp32_backend::shared::error::{error_codes, ErrorResponse, FieldError};
 
 /// Main error type for the Data Plane API
 #[derive(Debug, Error)]
@@ -15,6 +15,14 @@ pub enum ApiError {
     #[error("Database error: {0}")]
     Database(#[from] DatabaseError),
 
+    /// The API key made more requests this minute than its limit allows
+    #[error("Rate limit of {0} requests per minute exceeded")]
+    RateLimited(u32),
+
+    /// An `Idempotency-Key` was reused with a different request body
+    #[error("Idempotency key reused with a different request body")]
+    IdempotencyConflict,
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -28,6 +36,9 @@ pub enum AuthError {
     #[error("API key is invalid or not found")]
     InvalidKey,
 
+    #[error("API key is not a 64-character hex string")]
+    MalformedKey,
+
     #[error("API key has been revoked")]
     KeyRevoked,
 
@@ -67,6 +78,23 @@ pub enum ValidationError {
 
     #[error("Invalid request body: {0}")]
     InvalidBody(String),
+
+    /// Several field problems found in one input, as `(field, message)` pairs
+    #[error("{}", describe_field_errors(.0))]
+    Multiple(Vec<(String, String)>),
+}
+
+/// Render `(field, message)` pairs as one line, e.g. for a rejected reading's reason
+fn describe_field_errors(errors: &[(String, String)]) -> String {
+    let details: Vec<String> = errors
+        .iter()
+        .map(|(field, message)| format!("{}: {}", field, message))
+        .collect();
+    format!(
+        "{} fields are invalid: {}",
+        errors.len(),
+        details.join("; ")
+    )
 }
 
 /// Database-specific errors
@@ -91,6 +119,7 @@ pub enum DatabaseError {
 impl ApiError {
     /// Convert error to HTTP response with appropriate status code and error payload
     pub fn to_http_response(&self, request_id: &str) -> Response<Body> {
+        let mut field_errors = Vec::new();
         let (status, error_code, message): (u16, &str, String) = match self {
             ApiError::Auth(AuthError::MissingKey) => (
                 401,
@@ -102,6 +131,11 @@ impl ApiError {
                 error_codes::INVALID_API_KEY,
                 "API key is invalid or not found".to_string(),
             ),
+            ApiError::Auth(AuthError::MalformedKey) => (
+                401,
+                error_codes::MALFORMED_API_KEY,
+                "API key must be a 64-character hex string".to_string(),
+            ),
             ApiError::Auth(AuthError::KeyRevoked) => (
                 401,
                 error_codes::KEY_REVOKED,
@@ -160,6 +194,33 @@ impl ApiError {
             ApiError::Validation(ValidationError::InvalidBody(msg)) => {
                 (400, error_codes::INVALID_FORMAT, msg.clone())
             }
+            ApiError::Validation(ValidationError::Multiple(errors)) => {
+                field_errors = errors
+                    .iter()
+                    .map(|(field, message)| FieldError {
+                        field: field.clone(),
+                        message: message.clone(),
+                    })
+                    .collect();
+                (
+                    400,
+                    error_codes::VALIDATION_FAILED,
+                    format!("{} fields are invalid", errors.len()),
+                )
+            }
+            ApiError::RateLimited(limit) => (
+                429,
+                error_codes::RATE_LIMITED,
+                format!(
+                    "Rate limit of {} requests per minute exceeded for this API key",
+                    limit
+                ),
+            ),
+            ApiError::IdempotencyConflict => (
+                409,
+                error_codes::IDEMPOTENCY_CONFLICT,
+                "Idempotency-Key was already used with a different request body".to_string(),
+            ),
             ApiError::Database(_) => (
                 500,
                 error_codes::DATABASE_ERROR,
@@ -172,7 +233,8 @@ impl ApiError {
             ),
         };
 
-        let error_response = ErrorResponse::new(error_code, &message, request_id);
+        let error_response =
+            ErrorResponse::new(error_code, &message, request_id).with_errors(field_errors);
 
         let body = error_response
             .to_json()
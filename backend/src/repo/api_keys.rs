@@ -117,6 +117,18 @@ fn item_to_api_key(item: &HashMap<String, AttributeValue>) -> Result<ApiKey, Dat
 
     let description = item.get("description").and_then(|v| v.as_s().ok()).cloned();
 
+    // Keys created before scopes were introduced have no scopes attribute
+    let scopes = item
+        .get("scopes")
+        .and_then(|v| v.as_l().ok())
+        .map(|list| list.iter().filter_map(|v| v.as_s().ok().cloned()).collect())
+        .unwrap_or_default();
+
+    let rate_limit_per_minute = item
+        .get("rate_limit_per_minute")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<u32>().ok());
+
     Ok(ApiKey {
         key_id,
         api_key_hash,
@@ -124,6 +136,8 @@ fn item_to_api_key(item: &HashMap<String, AttributeValue>) -> Result<ApiKey, Dat
         last_used_at,
         is_active,
         description,
+        scopes,
+        rate_limit_per_minute,
     })
 }
 
@@ -137,8 +151,11 @@ fn item_to_api_key(item: &HashMap<String, AttributeValue>) -> Result<ApiKey, Dat
 /// * `table_name` - Name of the api_keys table
 /// * `key_id` - UUID v4 for the API key
 /// * `api_key_hash` - SHA-256 hash of the raw API key
+/// * `pepper_version` - Version of the pepper the hash was made with
 /// * `created_at` - RFC3339 timestamp when the key was created
 /// * `description` - Optional description for the API key
+/// * `scopes` - Scopes granted to the key (stored as a list attribute, omitted when empty)
+/// * `rate_limit_per_minute` - Per-key ingest limit (omitted to use the deployment-wide limit)
 ///
 /// # Returns
 /// * `Ok(())` - API key created successfully
@@ -148,8 +165,11 @@ pub async fn create_api_key(
     table_name: &str,
     key_id: &str,
     api_key_hash: &str,
+    pepper_version: u32,
     created_at: &str,
     description: Option<String>,
+    scopes: &[String],
+    rate_limit_per_minute: Option<u32>,
 ) -> Result<(), DatabaseError> {
     let mut item = HashMap::new();
     item.insert("key_id".to_string(), AttributeValue::S(key_id.to_string()));
@@ -157,6 +177,10 @@ pub async fn create_api_key(
         "api_key_hash".to_string(),
         AttributeValue::S(api_key_hash.to_string()),
     );
+    item.insert(
+        "pepper_version".to_string(),
+        AttributeValue::N(pepper_version.to_string()),
+    );
     item.insert(
         "created_at".to_string(),
         AttributeValue::S(created_at.to_string()),
@@ -177,6 +201,25 @@ pub async fn create_api_key(
         item.insert("description".to_string(), AttributeValue::S(desc));
     }
 
+    if !scopes.is_empty() {
+        item.insert(
+            "scopes".to_string(),
+            AttributeValue::L(
+                scopes
+                    .iter()
+                    .map(|s| AttributeValue::S(s.clone()))
+                    .collect(),
+            ),
+        );
+    }
+
+    if let Some(limit) = rate_limit_per_minute {
+        item.insert(
+            "rate_limit_per_minute".to_string(),
+            AttributeValue::N(limit.to_string()),
+        );
+    }
+
     client
         .put_item()
         .table_name(table_name)
@@ -188,16 +231,76 @@ pub async fn create_api_key(
     Ok(())
 }
 
+/// Build the filter expression selecting keys that hold a scope
+///
+/// Returns the expression and its `:scope` attribute value, or `None` when no
+/// scope filter was requested.
+pub fn build_scope_filter(scope: Option<&str>) -> Option<(String, AttributeValue)> {
+    scope.map(|scope| {
+        (
+            "contains(scopes, :scope)".to_string(),
+            AttributeValue::S(scope.to_string()),
+        )
+    })
+}
+
+/// Optional created_at bounds for an API key listing
+///
+/// Both bounds are inclusive RFC3339 timestamps in the same UTC form as the
+/// stored `created_at`, so the sort-key comparison is lexicographically correct.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiKeyCreatedRange {
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+}
+
+/// Build the GSI_list key condition and its attribute values for a listing
+///
+/// The created range bounds the gsi1sk (created_at) sort key, so it reduces the
+/// items read rather than filtering them after the limit is applied.
+pub fn build_list_api_keys_key_condition(
+    range: &ApiKeyCreatedRange,
+) -> (String, HashMap<String, AttributeValue>) {
+    let mut values = HashMap::new();
+    values.insert(":pk".to_string(), AttributeValue::S("api_keys".to_string()));
+
+    let expression = match (&range.created_after, &range.created_before) {
+        (Some(after), Some(before)) => {
+            values.insert(":after".to_string(), AttributeValue::S(after.clone()));
+            values.insert(":before".to_string(), AttributeValue::S(before.clone()));
+            "gsi1pk = :pk AND gsi1sk BETWEEN :after AND :before"
+        }
+        (Some(after), None) => {
+            values.insert(":after".to_string(), AttributeValue::S(after.clone()));
+            "gsi1pk = :pk AND gsi1sk >= :after"
+        }
+        (None, Some(before)) => {
+            values.insert(":before".to_string(), AttributeValue::S(before.clone()));
+            "gsi1pk = :pk AND gsi1sk <= :before"
+        }
+        (None, None) => "gsi1pk = :pk",
+    };
+
+    (expression.to_string(), values)
+}
+
 /// List API keys with pagination
 ///
 /// Queries the GSI_list (pk=gsi1pk="api_keys") sorted by gsi1sk (created_at) descending.
 /// Returns a list of API keys and an optional pageToken for pagination.
 ///
+/// When `scope` is set, a `contains(scopes, :scope)` filter is applied after the
+/// key condition. DynamoDB applies `limit` before filtering, so a filtered page
+/// may hold fewer than `limit` keys (even none) while still returning a pageToken.
+/// The `created` range is a key condition on gsi1sk and does not thin pages.
+///
 /// # Arguments
 /// * `client` - DynamoDB client
 /// * `table_name` - Name of the api_keys table
-/// * `limit` - Maximum number of API keys to return
+/// * `limit` - Maximum number of API keys to evaluate
 /// * `page_token` - Optional base64-encoded pageToken for pagination
+/// * `scope` - Optional scope the returned keys must hold
+/// * `created` - Optional inclusive created_at bounds
 ///
 /// # Returns
 /// * `Ok((Vec<ApiKey>, Option<String>))` - List of API keys and optional next pageToken
@@ -207,16 +310,27 @@ pub async fn list_api_keys(
     table_name: &str,
     limit: i32,
     page_token: Option<String>,
+    scope: Option<&str>,
+    created: &ApiKeyCreatedRange,
 ) -> Result<(Vec<ApiKey>, Option<String>), DatabaseError> {
+    let (key_condition_expression, expression_attribute_values) =
+        build_list_api_keys_key_condition(created);
+
     let mut query = client
         .query()
         .table_name(table_name)
         .index_name("gsi1")
-        .key_condition_expression("gsi1pk = :pk")
-        .expression_attribute_values(":pk", AttributeValue::S("api_keys".to_string()))
+        .key_condition_expression(key_condition_expression)
+        .set_expression_attribute_values(Some(expression_attribute_values))
         .scan_index_forward(false) // Descending order (most recent first)
         .limit(limit);
 
+    if let Some((filter_expression, scope_value)) = build_scope_filter(scope) {
+        query = query
+            .filter_expression(filter_expression)
+            .expression_attribute_values(":scope", scope_value);
+    }
+
     // Handle pagination pageToken
     if let Some(page_token_str) = page_token {
         let page_token = decode_api_key_page_token(&page_token_str)
@@ -258,10 +372,15 @@ pub async fn list_api_keys(
     Ok((api_keys, page_token))
 }
 
+/// Condition on the revoke UpdateItem: only an existing key can be revoked
+pub const REVOKE_KEY_CONDITION: &str = "attribute_exists(key_id)";
+
 /// Revoke an API key by setting is_active to false
 ///
 /// This is used by the Control Plane API to revoke API keys.
 /// The key remains in the database but will be rejected during authentication.
+/// Revoking an already revoked key succeeds; the `REVOKE_KEY_CONDITION`
+/// keeps an unknown key_id from creating a partial record.
 ///
 /// # Arguments
 /// * `client` - DynamoDB client
@@ -270,21 +389,183 @@ pub async fn list_api_keys(
 ///
 /// # Returns
 /// * `Ok(())` - API key revoked successfully
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - No API key exists with this key_id
 /// * `Err(DatabaseError)` - DynamoDB error occurred
 pub async fn revoke_api_key(
     client: &DynamoDbClient,
     table_name: &str,
     key_id: &str,
 ) -> Result<(), DatabaseError> {
+    use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+
     client
         .update_item()
         .table_name(table_name)
         .key("key_id", AttributeValue::S(key_id.to_string()))
         .update_expression("SET is_active = :inactive")
+        .condition_expression(REVOKE_KEY_CONDITION)
         .expression_attribute_values(":inactive", AttributeValue::Bool(false))
         .send()
         .await
-        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+        .map_err(|e| match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
+
+    Ok(())
+}
+
+/// Expressions and values for rotating an API key in place
+///
+/// Kept separate from the SDK call so the update can be verified in tests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotateKeyParams {
+    pub update_expression: String,
+    pub condition_expression: String,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the update that replaces a key's hash and clears last_used_at
+///
+/// The condition makes the update fail for a key_id that does not exist
+/// instead of creating a partial record.
+pub fn build_rotate_key_params(new_api_key_hash: &str, pepper_version: u32) -> RotateKeyParams {
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(
+        ":hash".to_string(),
+        AttributeValue::S(new_api_key_hash.to_string()),
+    );
+    expression_attribute_values.insert(
+        ":pepper_version".to_string(),
+        AttributeValue::N(pepper_version.to_string()),
+    );
+
+    RotateKeyParams {
+        update_expression:
+            "SET api_key_hash = :hash, pepper_version = :pepper_version REMOVE last_used_at"
+                .to_string(),
+        condition_expression: "attribute_exists(key_id)".to_string(),
+        expression_attribute_values,
+    }
+}
+
+/// Rotate an API key by replacing its hash, keeping the same key_id
+///
+/// Used by the Control Plane API when a key is suspected compromised. The old
+/// raw key stops matching immediately. `last_used_at` is cleared since the new
+/// key has not been used yet; `is_active`, scopes, and description are kept.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `table_name` - Name of the api_keys table
+/// * `key_id` - UUID of the API key to rotate
+/// * `new_api_key_hash` - SHA-256 hash of the newly generated raw key
+/// * `pepper_version` - Version of the pepper the new hash was made with
+///
+/// # Returns
+/// * `Ok(())` - API key rotated successfully
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - No API key with this key_id
+/// * `Err(DatabaseError)` - Other DynamoDB error occurred
+pub async fn rotate_key(
+    client: &DynamoDbClient,
+    table_name: &str,
+    key_id: &str,
+    new_api_key_hash: &str,
+    pepper_version: u32,
+) -> Result<(), DatabaseError> {
+    use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+
+    let params = build_rotate_key_params(new_api_key_hash, pepper_version);
+
+    client
+        .update_item()
+        .table_name(table_name)
+        .key("key_id", AttributeValue::S(key_id.to_string()))
+        .update_expression(params.update_expression)
+        .condition_expression(params.condition_expression)
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
+
+    Ok(())
+}
+
+/// Build the update that upgrades a key's stored hash to a newer scheme or pepper
+///
+/// The condition only matches while the record still holds `old_api_key_hash`,
+/// so a re-hash racing a rotation cannot bring back the rotated-out key.
+/// Unlike rotation, `last_used_at` is left alone: the raw key is unchanged.
+pub fn build_rehash_key_params(
+    old_api_key_hash: &str,
+    new_api_key_hash: &str,
+    pepper_version: u32,
+) -> RotateKeyParams {
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(
+        ":old_hash".to_string(),
+        AttributeValue::S(old_api_key_hash.to_string()),
+    );
+    expression_attribute_values.insert(
+        ":hash".to_string(),
+        AttributeValue::S(new_api_key_hash.to_string()),
+    );
+    expression_attribute_values.insert(
+        ":pepper_version".to_string(),
+        AttributeValue::N(pepper_version.to_string()),
+    );
+
+    RotateKeyParams {
+        update_expression: "SET api_key_hash = :hash, pepper_version = :pepper_version".to_string(),
+        condition_expression: "api_key_hash = :old_hash".to_string(),
+        expression_attribute_values,
+    }
+}
+
+/// Replace a key's stored hash with the same key hashed under a newer scheme or pepper
+///
+/// Called best-effort after a successful validation when the stored hash uses
+/// an older scheme than `API_KEY_HASH_SCHEME` or was made with
+/// `API_KEY_PEPPER_PREVIOUS`.
+///
+/// # Returns
+/// * `Ok(())` - Stored hash upgraded
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - The stored hash changed since it was read
+/// * `Err(DatabaseError)` - Other DynamoDB error occurred
+pub async fn update_api_key_hash(
+    client: &DynamoDbClient,
+    table_name: &str,
+    key_id: &str,
+    old_api_key_hash: &str,
+    new_api_key_hash: &str,
+    pepper_version: u32,
+) -> Result<(), DatabaseError> {
+    use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+
+    let params = build_rehash_key_params(old_api_key_hash, new_api_key_hash, pepper_version);
+
+    client
+        .update_item()
+        .table_name(table_name)
+        .key("key_id", AttributeValue::S(key_id.to_string()))
+        .update_expression(params.update_expression)
+        .condition_expression(params.condition_expression)
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
 
     Ok(())
 }
@@ -501,6 +782,130 @@ mod tests {
         assert!(limit <= 100);
     }
 
+    #[test]
+    fn test_build_scope_filter() {
+        assert!(build_scope_filter(None).is_none());
+
+        let (expression, value) = build_scope_filter(Some("data:write")).unwrap();
+        assert_eq!(expression, "contains(scopes, :scope)");
+        assert_eq!(value, AttributeValue::S("data:write".to_string()));
+    }
+
+    #[test]
+    fn test_build_list_api_keys_key_condition_unbounded() {
+        let (expression, values) =
+            build_list_api_keys_key_condition(&ApiKeyCreatedRange::default());
+
+        assert_eq!(expression, "gsi1pk = :pk");
+        assert_eq!(values.len(), 1);
+        assert_eq!(
+            values.get(":pk"),
+            Some(&AttributeValue::S("api_keys".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_build_list_api_keys_key_condition_bounded() {
+        let after = "2024-01-01T00:00:00+00:00".to_string();
+        let before = "2024-02-01T00:00:00+00:00".to_string();
+
+        let (expression, values) = build_list_api_keys_key_condition(&ApiKeyCreatedRange {
+            created_after: Some(after.clone()),
+            created_before: Some(before.clone()),
+        });
+        assert_eq!(
+            expression,
+            "gsi1pk = :pk AND gsi1sk BETWEEN :after AND :before"
+        );
+        assert_eq!(
+            values.get(":after"),
+            Some(&AttributeValue::S(after.clone()))
+        );
+        assert_eq!(
+            values.get(":before"),
+            Some(&AttributeValue::S(before.clone()))
+        );
+
+        let (expression, values) = build_list_api_keys_key_condition(&ApiKeyCreatedRange {
+            created_after: Some(after),
+            created_before: None,
+        });
+        assert_eq!(expression, "gsi1pk = :pk AND gsi1sk >= :after");
+        assert!(!values.contains_key(":before"));
+
+        let (expression, values) = build_list_api_keys_key_condition(&ApiKeyCreatedRange {
+            created_after: None,
+            created_before: Some(before),
+        });
+        assert_eq!(expression, "gsi1pk = :pk AND gsi1sk <= :before");
+        assert!(!values.contains_key(":after"));
+    }
+
+    #[test]
+    fn test_item_to_api_key_with_and_without_scope() {
+        let mut item = HashMap::new();
+        item.insert(
+            "key_id".to_string(),
+            AttributeValue::S("test-key-id".to_string()),
+        );
+        item.insert(
+            "api_key_hash".to_string(),
+            AttributeValue::S("test-hash".to_string()),
+        );
+        item.insert(
+            "created_at".to_string(),
+            AttributeValue::S("2024-01-15T10:30:00Z".to_string()),
+        );
+        item.insert("is_active".to_string(), AttributeValue::Bool(true));
+
+        // Legacy key without a scopes attribute
+        let legacy = item_to_api_key(&item).unwrap();
+        assert!(legacy.scopes.is_empty());
+        assert!(!legacy.has_scope("data:write"));
+
+        item.insert(
+            "scopes".to_string(),
+            AttributeValue::L(vec![
+                AttributeValue::S("data:read".to_string()),
+                AttributeValue::S("data:write".to_string()),
+            ]),
+        );
+        let scoped = item_to_api_key(&item).unwrap();
+        assert_eq!(scoped.scopes, vec!["data:read", "data:write"]);
+        assert!(scoped.has_scope("data:write"));
+        assert!(!scoped.has_scope("admin"));
+    }
+
+    #[test]
+    fn test_item_to_api_key_rate_limit_per_minute() {
+        let mut item = HashMap::new();
+        item.insert(
+            "key_id".to_string(),
+            AttributeValue::S("test-key-id".to_string()),
+        );
+        item.insert(
+            "api_key_hash".to_string(),
+            AttributeValue::S("test-hash".to_string()),
+        );
+        item.insert(
+            "created_at".to_string(),
+            AttributeValue::S("2024-01-15T10:30:00Z".to_string()),
+        );
+        item.insert("is_active".to_string(), AttributeValue::Bool(true));
+
+        // Keys without the attribute fall back to the deployment-wide limit
+        assert_eq!(item_to_api_key(&item).unwrap().rate_limit_per_minute, None);
+
+        item.insert(
+            "rate_limit_per_minute".to_string(),
+            AttributeValue::N("600".to_string()),
+        );
+        assert_eq!(
+            item_to_api_key(&item).unwrap().rate_limit_per_minute,
+            Some(600)
+        );
+    }
+
     #[test]
     fn test_list_api_keys_pagination_page_token() {
         // Verify pageToken encoding/decoding structure
@@ -652,4 +1057,58 @@ mod tests {
         assert!(update_expression.contains("is_active"));
         assert!(update_expression.contains(":inactive"));
     }
+
+    #[test]
+    fn test_build_rotate_key_params_replaces_hash() {
+        let old_hash = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        let new_hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+        let params = build_rotate_key_params(new_hash, 2);
+
+        assert_eq!(
+            params.update_expression,
+            "SET api_key_hash = :hash, pepper_version = :pepper_version REMOVE last_used_at"
+        );
+        assert_eq!(params.condition_expression, "attribute_exists(key_id)");
+        assert_eq!(params.expression_attribute_values.len(), 2);
+        assert_eq!(
+            params.expression_attribute_values.get(":pepper_version"),
+            Some(&AttributeValue::N("2".to_string()))
+        );
+
+        let stored = params
+            .expression_attribute_values
+            .get(":hash")
+            .and_then(|v| v.as_s().ok())
+            .unwrap();
+        assert_eq!(stored, new_hash);
+        assert_ne!(stored, old_hash);
+    }
+
+    #[test]
+    fn test_build_rehash_key_params_requires_old_hash() {
+        let old_hash = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        let new_hash = "argon2$9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+        let params = build_rehash_key_params(old_hash, new_hash, 3);
+
+        assert_eq!(
+            params.update_expression,
+            "SET api_key_hash = :hash, pepper_version = :pepper_version"
+        );
+        assert!(!params.update_expression.contains("last_used_at"));
+        assert_eq!(params.condition_expression, "api_key_hash = :old_hash");
+        assert_eq!(
+            params.expression_attribute_values.get(":old_hash"),
+            Some(&AttributeValue::S(old_hash.to_string()))
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":hash"),
+            Some(&AttributeValue::S(new_hash.to_string()))
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":pepper_version"),
+            Some(&AttributeValue::N("3".to_string()))
+        );
+    }
 }
@@ -41,10 +41,14 @@ pub async fn get_device(
     }
 }
 
+/// Condition on the device PutItem: only create, never overwrite
+pub const CREATE_DEVICE_CONDITION: &str = "attribute_not_exists(hardware_id)";
+
 /// Create a new device record in the devices table
 ///
-/// Uses PutItem to create a new device record with GSI attributes.
-/// Sets gsi1pk="devices" and gsi1sk=last_seen_at for listing devices sorted by activity.
+/// Uses a conditional PutItem (`CREATE_DEVICE_CONDITION`) so that when two
+/// first-time registrations race, the second fails instead of replacing the
+/// first one's confirmation_id.
 ///
 /// # Arguments
 /// * `client` - DynamoDB client
@@ -53,12 +57,36 @@ pub async fn get_device(
 ///
 /// # Returns
 /// * `Ok(())` - Device created successfully
-/// * `Err(DatabaseError)` - DynamoDB error occurred
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - A record for this hardware_id already exists
+/// * `Err(DatabaseError)` - Other DynamoDB error occurred
 pub async fn create_device(
     client: &DynamoDbClient,
     table_name: &str,
     device: &Device,
 ) -> Result<(), DatabaseError> {
+    use aws_sdk_dynamodb::operation::put_item::PutItemError;
+
+    client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(device_to_item(device)))
+        .condition_expression(CREATE_DEVICE_CONDITION)
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(PutItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
+
+    Ok(())
+}
+
+/// Convert a new Device to a DynamoDB item
+///
+/// Sets gsi1pk="devices" and gsi1sk=last_seen_at for listing devices sorted by activity.
+fn device_to_item(device: &Device) -> HashMap<String, AttributeValue> {
     let mut item = HashMap::new();
 
     // Partition key
@@ -107,6 +135,13 @@ pub async fn create_device(
         AttributeValue::S(device.last_boot_id.clone()),
     );
 
+    if let Some(readings_this_boot) = device.readings_this_boot {
+        item.insert(
+            "readings_this_boot".to_string(),
+            AttributeValue::N(readings_this_boot.to_string()),
+        );
+    }
+
     // GSI attributes for listing devices sorted by last_seen_at
     item.insert(
         "gsi1pk".to_string(),
@@ -118,15 +153,19 @@ pub async fn create_device(
         AttributeValue::S(device.last_seen_at.clone()),
     );
 
-    client
-        .put_item()
-        .table_name(table_name)
-        .set_item(Some(item))
-        .send()
-        .await
-        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+    item
+}
 
-    Ok(())
+/// Build the re-registration update for last_seen_at, last_boot_id and gsi1sk
+///
+/// When the device reports a new boot, `readings_this_boot` is reset to 0
+/// so the data plane starts counting the new boot session from scratch.
+pub fn build_device_timestamps_update(boot_changed: bool) -> &'static str {
+    if boot_changed {
+        "SET last_seen_at = :last_seen, last_boot_id = :boot_id, gsi1sk = :gsi1sk, readings_this_boot = :zero"
+    } else {
+        "SET last_seen_at = :last_seen, last_boot_id = :boot_id, gsi1sk = :gsi1sk"
+    }
 }
 
 /// Update device timestamps and last_boot_id
@@ -140,6 +179,7 @@ pub async fn create_device(
 /// * `hardware_id` - MAC address of the device (partition key)
 /// * `last_seen_at` - New last_seen_at timestamp (RFC3339 string)
 /// * `last_boot_id` - New boot_id from the device
+/// * `boot_changed` - Whether `last_boot_id` differs from the stored one (resets `readings_this_boot`)
 ///
 /// # Returns
 /// * `Ok(())` - Update successful
@@ -150,17 +190,23 @@ pub async fn update_device_timestamps(
     hardware_id: &str,
     last_seen_at: &str,
     last_boot_id: &str,
+    boot_changed: bool,
 ) -> Result<(), DatabaseError> {
-    client
+    let mut request = client
         .update_item()
         .table_name(table_name)
         .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
-        .update_expression(
-            "SET last_seen_at = :last_seen, last_boot_id = :boot_id, gsi1sk = :gsi1sk",
-        )
+        .update_expression(build_device_timestamps_update(boot_changed))
         .expression_attribute_values(":last_seen", AttributeValue::S(last_seen_at.to_string()))
         .expression_attribute_values(":boot_id", AttributeValue::S(last_boot_id.to_string()))
-        .expression_attribute_values(":gsi1sk", AttributeValue::S(last_seen_at.to_string()))
+        .expression_attribute_values(":gsi1sk", AttributeValue::S(last_seen_at.to_string()));
+
+    // DynamoDB rejects expression values the expression does not use
+    if boot_changed {
+        request = request.expression_attribute_values(":zero", AttributeValue::N("0".to_string()));
+    }
+
+    request
         .send()
         .await
         .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
@@ -168,6 +214,146 @@ pub async fn update_device_timestamps(
     Ok(())
 }
 
+/// Parameters for the reading-bounds UpdateItem
+#[derive(Debug, Clone)]
+pub struct ReadingBoundsParams {
+    pub update_expression: String,
+    pub condition_expression: String,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the update that records a batch's reading timestamps on a device
+///
+/// `last_reading_ms` is set to `:last` only when it is absent or older, so
+/// a late or replayed batch never moves it backwards. `first_reading_ms` is
+/// only written through `if_not_exists`, so it keeps the value from the
+/// first batch. `attribute_exists(hardware_id)` keeps the update from
+/// creating a record for an unregistered device.
+pub fn build_reading_bounds_params(first_ms: i64, last_ms: i64) -> ReadingBoundsParams {
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(
+        ":first".to_string(),
+        AttributeValue::N(first_ms.to_string()),
+    );
+    expression_attribute_values.insert(":last".to_string(), AttributeValue::N(last_ms.to_string()));
+
+    ReadingBoundsParams {
+        update_expression:
+            "SET first_reading_ms = if_not_exists(first_reading_ms, :first), last_reading_ms = :last"
+                .to_string(),
+        condition_expression:
+            "attribute_exists(hardware_id) AND (attribute_not_exists(last_reading_ms) OR last_reading_ms < :last)"
+                .to_string(),
+        expression_attribute_values,
+    }
+}
+
+/// Record the oldest and newest timestamps of a stored batch on the device
+///
+/// # Arguments
+/// * `first_ms` - Oldest timestamp in the batch (used only for a device's first batch)
+/// * `last_ms` - Newest timestamp in the batch
+///
+/// # Returns
+/// * `Ok(true)` - The bounds were updated
+/// * `Ok(false)` - `last_reading_ms` is already at or past `last_ms`, or the
+///   device is not registered
+/// * `Err(DatabaseError)` - DynamoDB error occurred
+pub async fn update_reading_bounds(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    first_ms: i64,
+    last_ms: i64,
+) -> Result<bool, DatabaseError> {
+    use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+
+    let params = build_reading_bounds_params(first_ms, last_ms);
+
+    let result = client
+        .update_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .update_expression(params.update_expression)
+        .condition_expression(params.condition_expression)
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) => match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => Ok(false),
+            _ => Err(DatabaseError::DynamoDb(format!("{:?}", e))),
+        },
+    }
+}
+
+/// Parameters for the readings-this-boot UpdateItem
+#[derive(Debug, Clone)]
+pub struct BootReadingsParams {
+    pub update_expression: String,
+    pub condition_expression: String,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the update that adds `count` stored readings to the device's boot counter
+///
+/// `ADD` creates the counter at `count` when it is absent. The condition
+/// only counts readings from the boot the device last registered with, so
+/// readings from an earlier boot (or an unregistered device) are ignored.
+pub fn build_boot_readings_params(boot_id: &str, count: i64) -> BootReadingsParams {
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(":count".to_string(), AttributeValue::N(count.to_string()));
+    expression_attribute_values.insert(
+        ":boot_id".to_string(),
+        AttributeValue::S(boot_id.to_string()),
+    );
+
+    BootReadingsParams {
+        update_expression: "ADD readings_this_boot :count".to_string(),
+        condition_expression: "last_boot_id = :boot_id".to_string(),
+        expression_attribute_values,
+    }
+}
+
+/// Count stored readings toward the device's current boot session
+///
+/// # Returns
+/// * `Ok(true)` - The counter was incremented
+/// * `Ok(false)` - `boot_id` is not the device's `last_boot_id`, or the
+///   device is not registered
+/// * `Err(DatabaseError)` - DynamoDB error occurred
+pub async fn increment_boot_readings(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+    boot_id: &str,
+    count: i64,
+) -> Result<bool, DatabaseError> {
+    use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+
+    let params = build_boot_readings_params(boot_id, count);
+
+    let result = client
+        .update_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .update_expression(params.update_expression)
+        .condition_expression(params.condition_expression)
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) => match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => Ok(false),
+            _ => Err(DatabaseError::DynamoDb(format!("{:?}", e))),
+        },
+    }
+}
+
 /// Convert a DynamoDB item to a Device struct
 fn item_to_device(item: &HashMap<String, AttributeValue>) -> Result<Device, DatabaseError> {
     let hardware_id = item
@@ -225,9 +411,19 @@ fn item_to_device(item: &HashMap<String, AttributeValue>) -> Result<Device, Data
         first_registered_at,
         last_seen_at,
         last_boot_id,
+        first_reading_ms: number_attr(item, "first_reading_ms"),
+        last_reading_ms: number_attr(item, "last_reading_ms"),
+        readings_this_boot: number_attr(item, "readings_this_boot"),
     })
 }
 
+/// Read an optional numeric attribute
+fn number_attr(item: &HashMap<String, AttributeValue>, name: &str) -> Option<i64> {
+    item.get(name)
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+}
+
 /// Convert Capabilities struct to DynamoDB AttributeValue (Map)
 fn capabilities_to_attribute_value(capabilities: &Capabilities) -> AttributeValue {
     let mut cap_map = HashMap::new();
@@ -341,7 +537,7 @@ pub async fn list_devices(
     // Add cursor if provided
     if let Some(cursor_str) = cursor {
         let cursor = decode_device_page_token(&cursor_str)
-            .map_err(|e| DatabaseError::Serialization(format!("Invalid cursor: {}", e.message)))?;
+            .map_err(|e| DatabaseError::Serialization(format!("Invalid cursor: {}", e)))?;
 
         let start_key = cursor_to_exclusive_start_key(&cursor);
         query = query.set_exclusive_start_key(Some(start_key));
@@ -398,6 +594,243 @@ fn cursor_to_exclusive_start_key(
 mod tests {
     use super::*;
 
+    /// Apply reading-bounds params to a stored device item the way DynamoDB
+    /// would, returning whether the condition passed
+    fn apply_reading_bounds(
+        item: &mut HashMap<String, AttributeValue>,
+        params: &ReadingBoundsParams,
+    ) -> bool {
+        let value = |name: &str| -> i64 {
+            params.expression_attribute_values[name]
+                .as_n()
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let (first, last) = (value(":first"), value(":last"));
+
+        let stored_last = number_attr(item, "last_reading_ms");
+        if !item.contains_key("hardware_id") || stored_last.is_some_and(|ms| ms >= last) {
+            return false;
+        }
+
+        item.entry("first_reading_ms".to_string())
+            .or_insert_with(|| AttributeValue::N(first.to_string()));
+        item.insert(
+            "last_reading_ms".to_string(),
+            AttributeValue::N(last.to_string()),
+        );
+        true
+    }
+
+    fn registered_item() -> HashMap<String, AttributeValue> {
+        HashMap::from([(
+            "hardware_id".to_string(),
+            AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()),
+        )])
+    }
+
+    #[test]
+    fn test_build_reading_bounds_params() {
+        let params = build_reading_bounds_params(1000, 2000);
+
+        assert_eq!(
+            params.update_expression,
+            "SET first_reading_ms = if_not_exists(first_reading_ms, :first), last_reading_ms = :last"
+        );
+        assert!(params
+            .condition_expression
+            .contains("attribute_not_exists(last_reading_ms) OR last_reading_ms < :last"));
+        assert!(params
+            .condition_expression
+            .starts_with("attribute_exists(hardware_id)"));
+        assert_eq!(
+            params.expression_attribute_values[":first"].as_n().unwrap(),
+            "1000"
+        );
+        assert_eq!(
+            params.expression_attribute_values[":last"].as_n().unwrap(),
+            "2000"
+        );
+    }
+
+    #[test]
+    fn test_reading_bounds_new_device_sets_both() {
+        let mut item = registered_item();
+
+        assert!(apply_reading_bounds(
+            &mut item,
+            &build_reading_bounds_params(1000, 2000)
+        ));
+        assert_eq!(number_attr(&item, "first_reading_ms"), Some(1000));
+        assert_eq!(number_attr(&item, "last_reading_ms"), Some(2000));
+    }
+
+    #[test]
+    fn test_reading_bounds_later_batch_advances_only_last() {
+        let mut item = registered_item();
+        apply_reading_bounds(&mut item, &build_reading_bounds_params(1000, 2000));
+
+        assert!(apply_reading_bounds(
+            &mut item,
+            &build_reading_bounds_params(500, 3000)
+        ));
+        assert_eq!(number_attr(&item, "first_reading_ms"), Some(1000));
+        assert_eq!(number_attr(&item, "last_reading_ms"), Some(3000));
+
+        // An older batch fails the condition and changes nothing
+        assert!(!apply_reading_bounds(
+            &mut item,
+            &build_reading_bounds_params(100, 2500)
+        ));
+        assert_eq!(number_attr(&item, "last_reading_ms"), Some(3000));
+    }
+
+    #[test]
+    fn test_reading_bounds_unregistered_device() {
+        let mut item = HashMap::new();
+
+        assert!(!apply_reading_bounds(
+            &mut item,
+            &build_reading_bounds_params(1000, 2000)
+        ));
+        assert!(item.is_empty());
+    }
+
+    /// Apply boot-readings params to a stored device item the way DynamoDB
+    /// would, returning whether the condition passed
+    fn apply_boot_readings(
+        item: &mut HashMap<String, AttributeValue>,
+        params: &BootReadingsParams,
+    ) -> bool {
+        let boot_id = &params.expression_attribute_values[":boot_id"];
+        if item.get("last_boot_id") != Some(boot_id) {
+            return false;
+        }
+
+        let count: i64 = params.expression_attribute_values[":count"]
+            .as_n()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let total = number_attr(item, "readings_this_boot").unwrap_or(0) + count;
+        item.insert(
+            "readings_this_boot".to_string(),
+            AttributeValue::N(total.to_string()),
+        );
+        true
+    }
+
+    /// Apply a re-registration to a stored device item the way DynamoDB would
+    fn apply_reregistration(item: &mut HashMap<String, AttributeValue>, boot_id: &str) {
+        let boot_changed = item["last_boot_id"].as_s().unwrap() != boot_id;
+        if build_device_timestamps_update(boot_changed).contains("readings_this_boot = :zero") {
+            item.insert(
+                "readings_this_boot".to_string(),
+                AttributeValue::N("0".to_string()),
+            );
+        }
+        item.insert(
+            "last_boot_id".to_string(),
+            AttributeValue::S(boot_id.to_string()),
+        );
+    }
+
+    fn booted_item(boot_id: &str) -> HashMap<String, AttributeValue> {
+        let mut item = registered_item();
+        item.insert(
+            "last_boot_id".to_string(),
+            AttributeValue::S(boot_id.to_string()),
+        );
+        item
+    }
+
+    #[test]
+    fn test_build_boot_readings_params() {
+        let params = build_boot_readings_params("boot-1", 3);
+
+        assert_eq!(params.update_expression, "ADD readings_this_boot :count");
+        assert_eq!(params.condition_expression, "last_boot_id = :boot_id");
+        assert_eq!(
+            params.expression_attribute_values[":count"].as_n().unwrap(),
+            "3"
+        );
+        assert_eq!(
+            params.expression_attribute_values[":boot_id"]
+                .as_s()
+                .unwrap(),
+            "boot-1"
+        );
+    }
+
+    #[test]
+    fn test_boot_readings_accumulate_within_boot() {
+        let mut item = booted_item("boot-1");
+
+        assert!(apply_boot_readings(
+            &mut item,
+            &build_boot_readings_params("boot-1", 3)
+        ));
+        assert!(apply_boot_readings(
+            &mut item,
+            &build_boot_readings_params("boot-1", 2)
+        ));
+        assert_eq!(number_attr(&item, "readings_this_boot"), Some(5));
+
+        // Readings from another boot are not counted
+        assert!(!apply_boot_readings(
+            &mut item,
+            &build_boot_readings_params("boot-0", 4)
+        ));
+        assert_eq!(number_attr(&item, "readings_this_boot"), Some(5));
+    }
+
+    #[test]
+    fn test_boot_readings_reset_on_new_boot() {
+        let mut item = booted_item("boot-1");
+        apply_boot_readings(&mut item, &build_boot_readings_params("boot-1", 5));
+
+        // Re-registering within the same boot keeps the count
+        apply_reregistration(&mut item, "boot-1");
+        assert_eq!(number_attr(&item, "readings_this_boot"), Some(5));
+
+        apply_reregistration(&mut item, "boot-2");
+        assert_eq!(number_attr(&item, "readings_this_boot"), Some(0));
+
+        apply_boot_readings(&mut item, &build_boot_readings_params("boot-2", 1));
+        assert_eq!(number_attr(&item, "readings_this_boot"), Some(1));
+    }
+
+    #[test]
+    fn test_create_device_only_creates() {
+        assert_eq!(CREATE_DEVICE_CONDITION, "attribute_not_exists(hardware_id)");
+
+        let device = Device {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            confirmation_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+            friendly_name: None,
+            firmware_version: "1.0.16".to_string(),
+            capabilities: Capabilities {
+                sensors: vec!["bme280".to_string()],
+                features: HashMap::new(),
+            },
+            first_registered_at: "2024-01-15T10:30:00Z".to_string(),
+            last_seen_at: "2024-01-15T10:30:00Z".to_string(),
+            last_boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
+        };
+
+        // The condition names the partition key the item is written under
+        let item = device_to_item(&device);
+        assert_eq!(item["hardware_id"].as_s().unwrap(), "AA:BB:CC:DD:EE:FF");
+        assert_eq!(item["gsi1pk"].as_s().unwrap(), "devices");
+        assert_eq!(item["gsi1sk"].as_s().unwrap(), "2024-01-15T10:30:00Z");
+        assert!(!item.contains_key("friendly_name"));
+        assert!(!item.contains_key("readings_this_boot"));
+    }
+
     #[test]
     fn test_capabilities_to_attribute_value() {
         let mut features = HashMap::new();
@@ -590,9 +1023,9 @@ mod tests {
 
     #[test]
     fn test_cursor_to_exclusive_start_key() {
-        use esp32_backend::shared::cursor::DeviceListCursor;
+        use esp32_backend::shared::cursor::DeviceListPageToken;
 
-        let cursor = DeviceListCursor {
+        let cursor = DeviceListPageToken {
             hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
             gsi1sk: "2024-01-15T14:22:00Z".to_string(),
         };
@@ -0,0 +1,217 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::Clock;
+
+/// Hours a registration's `Idempotency-Key` is remembered
+pub const REGISTER_IDEMPOTENCY_TTL_HOURS: i64 = 24;
+
+/// Prefix of the processed_batches key holding a registration idempotency record
+///
+/// Batch IDs start with the device's hardware_id, so a prefixed key cannot
+/// collide with a batch.
+const REGISTER_KEY_PREFIX: &str = "register#";
+
+/// Condition on the record PutItem: the first request with a key wins
+pub const CREATE_IDEMPOTENCY_RECORD_CONDITION: &str = "attribute_not_exists(batch_id)";
+
+/// A registration remembered under its `Idempotency-Key`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    /// The client's `Idempotency-Key` header value
+    pub key: String,
+    /// Hex SHA-256 of the request body (see `request_hash`)
+    pub request_hash: String,
+    /// confirmation_id returned by the registration
+    pub confirmation_id: String,
+    /// JSON body returned by the registration, replayed verbatim on repeats
+    pub response_body: String,
+}
+
+/// Hex SHA-256 of a request body, used to spot a key reused with a different body
+pub fn request_hash(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+/// processed_batches partition key for an `Idempotency-Key`
+fn record_key(key: &str) -> String {
+    format!("{}{}", REGISTER_KEY_PREFIX, key)
+}
+
+/// Read the registration stored under an `Idempotency-Key`, if any
+pub async fn get_idempotency_record(
+    client: &DynamoDbClient,
+    table_name: &str,
+    key: &str,
+) -> Result<Option<IdempotencyRecord>, DatabaseError> {
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .key("batch_id", AttributeValue::S(record_key(key)))
+        .consistent_read(true)
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result
+        .item
+        .map(|item| item_to_record(key, &item))
+        .transpose()
+}
+
+/// Store a registration under its `Idempotency-Key`
+///
+/// Uses a conditional PutItem (`CREATE_IDEMPOTENCY_RECORD_CONDITION`) so a
+/// concurrent request with the same key cannot replace the first response.
+/// The record expires `REGISTER_IDEMPOTENCY_TTL_HOURS` after it is written.
+///
+/// # Returns
+/// * `Ok(())` - Record stored
+/// * `Err(DatabaseError::ConditionalCheckFailed)` - A record for this key already exists
+/// * `Err(DatabaseError)` - Other DynamoDB error occurred
+pub async fn put_idempotency_record(
+    client: &DynamoDbClient,
+    table_name: &str,
+    record: &IdempotencyRecord,
+    clock: &dyn Clock,
+) -> Result<(), DatabaseError> {
+    use aws_sdk_dynamodb::operation::put_item::PutItemError;
+
+    client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(record_to_item(record, clock)))
+        .condition_expression(CREATE_IDEMPOTENCY_RECORD_CONDITION)
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(PutItemError::ConditionalCheckFailedException(_)) => {
+                DatabaseError::ConditionalCheckFailed
+            }
+            _ => DatabaseError::DynamoDb(format!("{:?}", e)),
+        })?;
+
+    Ok(())
+}
+
+/// Convert an IdempotencyRecord to a processed_batches item
+///
+/// `received_at` and `expiration_time` match the attributes of batch records,
+/// so the table's TTL expires both.
+fn record_to_item(
+    record: &IdempotencyRecord,
+    clock: &dyn Clock,
+) -> HashMap<String, AttributeValue> {
+    let expiration_time = clock.now_epoch_seconds() + REGISTER_IDEMPOTENCY_TTL_HOURS * 3600;
+
+    let mut item = HashMap::new();
+    item.insert(
+        "batch_id".to_string(),
+        AttributeValue::S(record_key(&record.key)),
+    );
+    item.insert(
+        "request_hash".to_string(),
+        AttributeValue::S(record.request_hash.clone()),
+    );
+    item.insert(
+        "confirmation_id".to_string(),
+        AttributeValue::S(record.confirmation_id.clone()),
+    );
+    item.insert(
+        "response_body".to_string(),
+        AttributeValue::S(record.response_body.clone()),
+    );
+    item.insert(
+        "received_at".to_string(),
+        AttributeValue::S(clock.now_rfc3339()),
+    );
+    item.insert(
+        "expiration_time".to_string(),
+        AttributeValue::N(expiration_time.to_string()),
+    );
+    item
+}
+
+/// Convert a processed_batches item back to an IdempotencyRecord
+fn item_to_record(
+    key: &str,
+    item: &HashMap<String, AttributeValue>,
+) -> Result<IdempotencyRecord, DatabaseError> {
+    let string_attr = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_s().ok())
+            .cloned()
+            .ok_or_else(|| DatabaseError::Serialization(format!("Missing {}", name)))
+    };
+
+    Ok(IdempotencyRecord {
+        key: key.to_string(),
+        request_hash: string_attr("request_hash")?,
+        confirmation_id: string_attr("confirmation_id")?,
+        response_body: string_attr("response_body")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp32_backend::FixedClock;
+
+    fn record() -> IdempotencyRecord {
+        IdempotencyRecord {
+            key: "retry-7f3a".to_string(),
+            request_hash: request_hash(br#"{"hardware_id":"AA:BB:CC:DD:EE:FF"}"#),
+            confirmation_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            response_body: r#"{"status":"registered"}"#.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_request_hash_is_hex_sha256() {
+        let hash = request_hash(b"{}");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, request_hash(b"{}"));
+        assert_ne!(hash, request_hash(b"{ }"));
+    }
+
+    #[test]
+    fn test_record_to_item_stores_key_response_and_ttl() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let item = record_to_item(&record(), &clock);
+
+        assert_eq!(
+            item.get("batch_id").unwrap().as_s().unwrap(),
+            "register#retry-7f3a"
+        );
+        assert_eq!(
+            item.get("confirmation_id").unwrap().as_s().unwrap(),
+            "7c9e6679-7425-40de-944b-e07fc1f90ae7"
+        );
+        assert_eq!(
+            item.get("response_body").unwrap().as_s().unwrap(),
+            r#"{"status":"registered"}"#
+        );
+        assert_eq!(
+            item.get("expiration_time").unwrap().as_n().unwrap(),
+            (clock.now_epoch_seconds() + 24 * 3600).to_string()
+        );
+    }
+
+    #[test]
+    fn test_item_round_trips_to_record() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+        let item = record_to_item(&record(), &clock);
+
+        assert_eq!(item_to_record("retry-7f3a", &item).unwrap(), record());
+
+        let mut incomplete = item;
+        incomplete.remove("response_body");
+        assert!(matches!(
+            item_to_record("retry-7f3a", &incomplete),
+            Err(DatabaseError::Serialization(_))
+        ));
+    }
+}
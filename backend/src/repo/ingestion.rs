@@ -1,9 +1,11 @@
-use aws_sdk_dynamodb::types::{AttributeValue, Put, TransactWriteItem};
+use aws_sdk_dynamodb::types::{AttributeValue, CancellationReason, Put, TransactWriteItem};
 use aws_sdk_dynamodb::Client as DynamoDbClient;
 use std::collections::HashMap;
 
-use esp32_backend::domain::{Reading, SensorStatus, SensorValues};
-use esp32_backend::Clock;
+use esp32_backend::domain::{
+    build_ts_batch, readings_gsi1pk, readings_gsi1pk_shard, Reading, SensorStatus, SensorValues,
+};
+use esp32_backend::{is_buffered, Clock, BUFFERED_THRESHOLD_MS};
 
 use crate::error::DatabaseError;
 
@@ -13,6 +15,9 @@ use crate::error::DatabaseError;
 /// - Err: Other database error occurred
 pub type TransactWriteResult = Result<bool, DatabaseError>;
 
+/// Condition keeping a reading Put from replacing a reading already stored at its key
+pub const READING_NOT_EXISTS_CONDITION: &str = "attribute_not_exists(ts_batch)";
+
 /// Atomically write a reading to both processed_batches and device_readings tables
 /// using DynamoDB transactions.
 ///
@@ -20,7 +25,10 @@ pub type TransactWriteResult = Result<bool, DatabaseError>;
 /// 1. Conditionally writing to processed_batches (fails if batch_id exists)
 /// 2. Writing to device_readings (only if step 1 succeeds)
 ///
-/// Both operations are atomic - either both succeed or both fail.
+/// Both operations are atomic - either both succeed or both fail. Unless
+/// `allow_overwrite` is set, the reading write is also conditional on no
+/// reading being stored at the same `ts_batch`, so a replayed batch cannot
+/// replace a newer reading; that conflict is reported as a duplicate too.
 ///
 /// # Arguments
 /// * `client` - DynamoDB client
@@ -28,11 +36,12 @@ pub type TransactWriteResult = Result<bool, DatabaseError>;
 /// * `readings_table` - Name of the device_readings table
 /// * `reading` - The sensor reading to write
 /// * `clock` - Clock implementation for timestamp generation
-/// * `retention_seconds` - Optional TTL for readings (if None, no TTL is set)
+/// * `retention_seconds` - Seconds after ingestion the reading expires (if None, no TTL is set)
+/// * `allow_overwrite` - Write the reading even if one is already stored at its `ts_batch`
 ///
 /// # Returns
 /// * `Ok(true)` - Transaction succeeded, reading was written
-/// * `Ok(false)` - Duplicate batch_id detected (ConditionalCheckFailedException)
+/// * `Ok(false)` - Duplicate batch_id or existing reading detected (conditional check failed)
 /// * `Err(DatabaseError)` - Other database error occurred
 pub async fn transact_write_reading_if_new_batch(
     client: &DynamoDbClient,
@@ -41,6 +50,7 @@ pub async fn transact_write_reading_if_new_batch(
     reading: &Reading,
     clock: &dyn Clock,
     retention_seconds: Option<i64>,
+    allow_overwrite: bool,
 ) -> TransactWriteResult {
     // Prepare processed_batches record
     let received_at = clock.now_rfc3339();
@@ -61,8 +71,71 @@ pub async fn transact_write_reading_if_new_batch(
         AttributeValue::N(batch_expiration_time.to_string()),
     );
 
+    let reading_item = reading_to_item(reading, clock.now_epoch_seconds(), retention_seconds);
+
+    // Build transaction items
+    let batch_put = Put::builder()
+        .table_name(batches_table)
+        .set_item(Some(batch_item))
+        .condition_expression("attribute_not_exists(batch_id)")
+        .build()
+        .map_err(|e| DatabaseError::DynamoDb(format!("Failed to build batch Put: {}", e)))?;
+
+    let reading_put = build_reading_put(readings_table, reading_item, allow_overwrite)?;
+
+    let transact_items = vec![
+        TransactWriteItem::builder().put(batch_put).build(),
+        TransactWriteItem::builder().put(reading_put).build(),
+    ];
+
+    // Execute transaction
+    let result = client
+        .transact_write_items()
+        .set_transact_items(Some(transact_items))
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => Ok(true), // Transaction succeeded
+        Err(err) => {
+            // Check if it's a conditional check failure (duplicate batch_id)
+            if is_conditional_check_failed(&err) {
+                Ok(false) // Duplicate detected
+            } else {
+                // Other error
+                Err(DatabaseError::from(err))
+            }
+        }
+    }
+}
+
+/// Build the device_readings Put, conditional on the key being free unless `allow_overwrite`
+fn build_reading_put(
+    readings_table: &str,
+    reading_item: HashMap<String, AttributeValue>,
+    allow_overwrite: bool,
+) -> Result<Put, DatabaseError> {
+    let condition = (!allow_overwrite).then(|| READING_NOT_EXISTS_CONDITION.to_string());
+
+    Put::builder()
+        .table_name(readings_table)
+        .set_item(Some(reading_item))
+        .set_condition_expression(condition)
+        .build()
+        .map_err(|e| DatabaseError::DynamoDb(format!("Failed to build reading Put: {}", e)))
+}
+
+/// Build the device_readings item for a reading ingested at `ingest_epoch_seconds`
+///
+/// `expiration_time` (the table's TTL attribute) is only set when
+/// `retention_seconds` is given.
+fn reading_to_item(
+    reading: &Reading,
+    ingest_epoch_seconds: i64,
+    retention_seconds: Option<i64>,
+) -> HashMap<String, AttributeValue> {
     // Prepare device_readings record
-    let ts_batch = format!("{:013}#{}", reading.timestamp_ms, reading.batch_id);
+    let ts_batch = build_ts_batch(reading.timestamp_ms, reading.seq, &reading.batch_id);
 
     let mut reading_item = HashMap::new();
     reading_item.insert(
@@ -106,52 +179,113 @@ pub async fn transact_write_reading_if_new_batch(
         AttributeValue::M(sensor_status_map),
     );
 
-    // Add TTL if retention is specified
+    // Flag readings that were buffered on the device and delivered late
+    let ingest_ms = ingest_epoch_seconds * 1000;
+    reading_item.insert(
+        "was_buffered".to_string(),
+        AttributeValue::Bool(is_buffered(
+            reading.timestamp_ms,
+            ingest_ms,
+            BUFFERED_THRESHOLD_MS,
+        )),
+    );
+
+    // Ingest time is the IngestTimeIndex sort key for incremental sync
+    reading_item.insert(
+        "ingest_time_ms".to_string(),
+        AttributeValue::N(ingest_ms.to_string()),
+    );
+
+    // Device's shard of GlobalIngestTimeIndex, for fleet-wide recent readings
+    reading_item.insert(
+        "gsi1pk".to_string(),
+        AttributeValue::S(readings_gsi1pk(readings_gsi1pk_shard(&reading.hardware_id))),
+    );
+
+    // Add quality score if the device reported one
+    if let Some(quality) = reading.quality {
+        reading_item.insert(
+            "quality".to_string(),
+            AttributeValue::N(quality.to_string()),
+        );
+    }
+
+    // Keep the device sequence so the sort key can be rebuilt on read
+    if let Some(seq) = reading.seq {
+        reading_item.insert("seq".to_string(), AttributeValue::N(seq.to_string()));
+    }
+
+    // Add batch-level metadata stamped onto the reading
+    if !reading.metadata.is_empty() {
+        reading_item.insert(
+            "metadata".to_string(),
+            AttributeValue::M(
+                reading
+                    .metadata
+                    .iter()
+                    .map(|(k, v)| (k.clone(), AttributeValue::S(v.clone())))
+                    .collect(),
+            ),
+        );
+    }
+
+    // Expire the reading a fixed time after ingestion; buffered readings
+    // delivered late still get the full retention period
     if let Some(retention) = retention_seconds {
-        let reading_expiration_time = (reading.timestamp_ms / 1000) + retention;
         reading_item.insert(
             "expiration_time".to_string(),
-            AttributeValue::N(reading_expiration_time.to_string()),
+            AttributeValue::N((ingest_epoch_seconds + retention).to_string()),
         );
     }
 
-    // Build transaction items
-    let batch_put = Put::builder()
-        .table_name(batches_table)
-        .set_item(Some(batch_item))
-        .condition_expression("attribute_not_exists(batch_id)")
-        .build()
-        .map_err(|e| DatabaseError::DynamoDb(format!("Failed to build batch Put: {}", e)))?;
+    reading_item
+}
 
-    let reading_put = Put::builder()
+/// Get the timestamp of the last stored reading for a device's boot session
+///
+/// Reads only the device's newest reading. If that reading belongs to a
+/// different boot (the device has rebooted since, or this is a new boot),
+/// returns `None` so no ordering is enforced.
+///
+/// # Arguments
+/// * `client` - DynamoDB client
+/// * `readings_table` - Name of the device_readings table
+/// * `hardware_id` - MAC address of the device
+/// * `boot_id` - Boot session of the incoming batch
+pub async fn get_last_stored_timestamp_for_boot(
+    client: &DynamoDbClient,
+    readings_table: &str,
+    hardware_id: &str,
+    boot_id: &str,
+) -> Result<Option<i64>, DatabaseError> {
+    let result = client
+        .query()
         .table_name(readings_table)
-        .set_item(Some(reading_item))
-        .build()
-        .map_err(|e| DatabaseError::DynamoDb(format!("Failed to build reading Put: {}", e)))?;
+        .key_condition_expression("hardware_id = :hardware_id")
+        .expression_attribute_values(":hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .projection_expression("timestamp_ms, boot_id")
+        .scan_index_forward(false)
+        .limit(1)
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
 
-    let transact_items = vec![
-        TransactWriteItem::builder().put(batch_put).build(),
-        TransactWriteItem::builder().put(reading_put).build(),
-    ];
+    Ok(result
+        .items()
+        .first()
+        .and_then(|item| timestamp_for_boot(item, boot_id)))
+}
 
-    // Execute transaction
-    let result = client
-        .transact_write_items()
-        .set_transact_items(Some(transact_items))
-        .send()
-        .await;
+/// Extract timestamp_ms from a reading item if it belongs to `boot_id`
+fn timestamp_for_boot(item: &HashMap<String, AttributeValue>, boot_id: &str) -> Option<i64> {
+    match item.get("boot_id") {
+        Some(AttributeValue::S(stored_boot_id)) if stored_boot_id == boot_id => {}
+        _ => return None,
+    }
 
-    match result {
-        Ok(_) => Ok(true), // Transaction succeeded
-        Err(err) => {
-            // Check if it's a conditional check failure (duplicate batch_id)
-            if is_conditional_check_failed(&err) {
-                Ok(false) // Duplicate detected
-            } else {
-                // Other error
-                Err(DatabaseError::from(err))
-            }
-        }
+    match item.get("timestamp_ms") {
+        Some(AttributeValue::N(n)) => n.parse().ok(),
+        _ => None,
     }
 }
 
@@ -190,6 +324,11 @@ fn sensor_values_to_attribute_map(sensors: &SensorValues) -> HashMap<String, Att
         );
     }
 
+    // Sensors without a typed field are stored under their own names
+    for (name, value) in &sensors.extra_sensors {
+        map.insert(name.clone(), AttributeValue::N(value.to_string()));
+    }
+
     map
 }
 
@@ -210,6 +349,10 @@ fn sensor_status_to_attribute_map(status: &SensorStatus) -> HashMap<String, Attr
         AttributeValue::S(status.soil_moisture.clone()),
     );
 
+    for (name, value) in &status.extra_status {
+        map.insert(name.clone(), AttributeValue::S(value.clone()));
+    }
+
     map
 }
 
@@ -223,16 +366,28 @@ fn is_conditional_check_failed(
     use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
 
     match err {
-        SdkError::ServiceError(service_err) => {
-            matches!(
-                service_err.err(),
-                TransactWriteItemsError::TransactionCanceledException(_)
-            )
-        }
+        SdkError::ServiceError(service_err) => match service_err.err() {
+            TransactWriteItemsError::TransactionCanceledException(cancelled) => {
+                is_duplicate_cancellation(cancelled.cancellation_reasons())
+            }
+            _ => false,
+        },
         _ => false,
     }
 }
 
+/// Whether a cancelled transaction failed on one of its conditions
+///
+/// Either the batch_id was already processed or a reading is already stored
+/// at the same key; both mean the reading is a duplicate. A cancellation
+/// without reasons is treated as a duplicate as well.
+fn is_duplicate_cancellation(reasons: &[CancellationReason]) -> bool {
+    reasons.is_empty()
+        || reasons
+            .iter()
+            .any(|reason| reason.code() == Some("ConditionalCheckFailed"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,12 +408,18 @@ mod tests {
                 humidity_pct: Some(45.2),
                 pressure_hpa: Some(1013.25),
                 soil_moisture_pct: Some(62.3),
+                extra_sensors: HashMap::new(),
             },
             sensor_status: SensorStatus {
                 bme280: "ok".to_string(),
                 ds18b20: "ok".to_string(),
                 soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
             },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
         }
     }
 
@@ -270,6 +431,7 @@ mod tests {
             humidity_pct: Some(45.2),
             pressure_hpa: None,
             soil_moisture_pct: Some(62.3),
+            extra_sensors: HashMap::new(),
         };
 
         let map = sensor_values_to_attribute_map(&sensors);
@@ -288,6 +450,7 @@ mod tests {
             bme280: "ok".to_string(),
             ds18b20: "error".to_string(),
             soil_moisture: "ok".to_string(),
+            extra_status: HashMap::new(),
         };
 
         let map = sensor_status_to_attribute_map(&status);
@@ -298,6 +461,46 @@ mod tests {
         assert_eq!(map.get("soil_moisture").unwrap().as_s().unwrap(), "ok");
     }
 
+    #[test]
+    fn test_reading_item_stores_extra_sensors() {
+        let mut reading = create_test_reading();
+        reading
+            .sensors
+            .extra_sensors
+            .insert("sht31_temp_c".to_string(), 21.4);
+        reading
+            .sensor_status
+            .extra_status
+            .insert("sht31".to_string(), "ok".to_string());
+
+        let item = reading_to_item(&reading, 1705316400, None);
+
+        let sensors = item["sensors"].as_m().unwrap();
+        assert_eq!(sensors["sht31_temp_c"].as_n().unwrap(), "21.4");
+        assert_eq!(sensors["bme280_temp_c"].as_n().unwrap(), "22.5");
+        let status = item["sensor_status"].as_m().unwrap();
+        assert_eq!(status["sht31"].as_s().unwrap(), "ok");
+        assert_eq!(status.len(), 4);
+    }
+
+    #[test]
+    fn test_timestamp_for_boot() {
+        let mut item = HashMap::new();
+        item.insert(
+            "boot_id".to_string(),
+            AttributeValue::S("boot-a".to_string()),
+        );
+        item.insert(
+            "timestamp_ms".to_string(),
+            AttributeValue::N("1704067800000".to_string()),
+        );
+
+        assert_eq!(timestamp_for_boot(&item, "boot-a"), Some(1704067800000));
+        // Newest reading is from another boot session
+        assert_eq!(timestamp_for_boot(&item, "boot-b"), None);
+        assert_eq!(timestamp_for_boot(&HashMap::new(), "boot-a"), None);
+    }
+
     #[test]
     fn test_ts_batch_format() {
         let reading = create_test_reading();
@@ -313,17 +516,107 @@ mod tests {
         let clock = FixedClock::from_epoch_seconds(1705316400);
         let retention_seconds = 90 * 24 * 3600; // 90 days
 
-        let reading = create_test_reading();
-        let reading_expiration = (reading.timestamp_ms / 1000) + retention_seconds;
+        let item = reading_to_item(
+            &create_test_reading(),
+            clock.now_epoch_seconds(),
+            Some(retention_seconds),
+        );
 
-        // Verify TTL is calculated from reading timestamp, not current time
-        assert_eq!(reading_expiration, 1704067800 + retention_seconds);
+        // Reading TTL is calculated from ingest time, not the reading timestamp
+        assert_eq!(
+            item.get("expiration_time").unwrap().as_n().unwrap(),
+            &(1705316400 + retention_seconds).to_string()
+        );
+        assert_eq!(
+            item.get("ingest_time_ms").unwrap().as_n().unwrap(),
+            "1705316400000"
+        );
 
         // Verify batch TTL is calculated from current time
         let batch_expiration = clock.now_epoch_seconds() + (30 * 24 * 3600);
         assert_eq!(batch_expiration, 1705316400 + (30 * 24 * 3600));
     }
 
+    #[test]
+    fn test_reading_item_without_retention_has_no_ttl() {
+        let clock = FixedClock::from_epoch_seconds(1705316400);
+
+        let item = reading_to_item(&create_test_reading(), clock.now_epoch_seconds(), None);
+
+        assert!(!item.contains_key("expiration_time"));
+        assert!(item.contains_key("ts_batch"));
+    }
+
+    #[test]
+    fn test_reading_item_in_global_ingest_index() {
+        let item = reading_to_item(&create_test_reading(), 1705316400, None);
+
+        let shard = readings_gsi1pk_shard("AA:BB:CC:DD:EE:FF");
+        assert_eq!(
+            item["gsi1pk"].as_s().unwrap(),
+            &format!("readings#{}", shard)
+        );
+        assert_eq!(item["ingest_time_ms"].as_n().unwrap(), "1705316400000");
+    }
+
+    #[test]
+    fn test_reading_item_sort_key_with_seq() {
+        let item = reading_to_item(&create_test_reading(), 1705316400, None);
+        assert!(!item.contains_key("seq"));
+
+        let mut reading = create_test_reading();
+        reading.seq = Some(4);
+        let item = reading_to_item(&reading, 1705316400, None);
+
+        assert_eq!(
+            item["ts_batch"].as_s().unwrap(),
+            &format!("1704067800000#00004#{}", reading.batch_id)
+        );
+        assert_eq!(item["seq"].as_n().unwrap(), "4");
+    }
+
+    #[test]
+    fn test_reading_put_is_conditional_unless_overwrite_allowed() {
+        let item = reading_to_item(&create_test_reading(), 1705316400, None);
+
+        let put = build_reading_put("device_readings", item.clone(), false).unwrap();
+        assert_eq!(
+            put.condition_expression(),
+            Some(READING_NOT_EXISTS_CONDITION)
+        );
+        assert_eq!(put.table_name(), "device_readings");
+
+        let put = build_reading_put("device_readings", item, true).unwrap();
+        assert_eq!(put.condition_expression(), None);
+    }
+
+    #[test]
+    fn test_conditional_conflict_on_either_item_is_duplicate() {
+        let none = CancellationReason::builder().code("None").build();
+        let conditional = CancellationReason::builder()
+            .code("ConditionalCheckFailed")
+            .build();
+
+        // batch_id already processed
+        assert!(is_duplicate_cancellation(&[
+            conditional.clone(),
+            none.clone()
+        ]));
+        // reading already stored at the same ts_batch
+        assert!(is_duplicate_cancellation(&[none.clone(), conditional]));
+        assert!(is_duplicate_cancellation(&[]));
+    }
+
+    #[test]
+    fn test_other_cancellation_is_not_duplicate() {
+        let none = CancellationReason::builder().code("None").build();
+        let conflict = CancellationReason::builder()
+            .code("TransactionConflict")
+            .build();
+
+        assert!(!is_duplicate_cancellation(&[conflict, none]));
+    }
+
     // Note: Integration tests with actual DynamoDB client are in the integration test suite
     // These unit tests verify the data transformation logic only
 }
@@ -1,3 +1,6 @@
 pub mod api_keys;
 pub mod devices;
+pub mod idempotency;
 pub mod ingestion;
+pub mod profiles;
+pub mod rollups;
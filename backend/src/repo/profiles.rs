@@ -0,0 +1,95 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::plant_insights::SensorCalibration;
+
+/// Read a device's calibration offsets from its profile
+///
+/// Only the offset attributes are fetched. A device without a profile, or a
+/// profile without offsets, gets the zero calibration.
+pub async fn get_calibration(
+    client: &DynamoDbClient,
+    table_name: &str,
+    hardware_id: &str,
+) -> Result<SensorCalibration, DatabaseError> {
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .key("hardware_id", AttributeValue::S(hardware_id.to_string()))
+        .projection_expression("temp_offset_c, humidity_offset_pct, soil_moisture_offset_pct")
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result
+        .item
+        .map(|item| item_to_calibration(&item))
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Convert the offset attributes of a profile item to a SensorCalibration
+///
+/// A missing offset is 0; one that is not a number is a serialization error.
+fn item_to_calibration(
+    item: &HashMap<String, AttributeValue>,
+) -> Result<SensorCalibration, DatabaseError> {
+    let offset = |name: &str| match item.get(name) {
+        None => Ok(0.0),
+        Some(value) => value
+            .as_n()
+            .ok()
+            .and_then(|n| n.parse::<f64>().ok())
+            .filter(|n| n.is_finite())
+            .ok_or_else(|| DatabaseError::Serialization(format!("Invalid {}", name))),
+    };
+
+    Ok(SensorCalibration {
+        temp_offset_c: offset("temp_offset_c")?,
+        humidity_offset_pct: offset("humidity_offset_pct")?,
+        soil_moisture_offset_pct: offset("soil_moisture_offset_pct")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_to_calibration_reads_offsets() {
+        let item = HashMap::from([
+            (
+                "hardware_id".to_string(),
+                AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()),
+            ),
+            (
+                "soil_moisture_offset_pct".to_string(),
+                AttributeValue::N("-5".to_string()),
+            ),
+            (
+                "temp_offset_c".to_string(),
+                AttributeValue::N("0.4".to_string()),
+            ),
+        ]);
+
+        let calibration = item_to_calibration(&item).unwrap();
+        assert_eq!(calibration.soil_moisture_offset_pct, -5.0);
+        assert_eq!(calibration.temp_offset_c, 0.4);
+        assert_eq!(calibration.humidity_offset_pct, 0.0);
+    }
+
+    #[test]
+    fn test_item_to_calibration_rejects_non_numeric_offset() {
+        let item = HashMap::from([(
+            "humidity_offset_pct".to_string(),
+            AttributeValue::S("high".to_string()),
+        )]);
+
+        assert!(matches!(
+            item_to_calibration(&item),
+            Err(DatabaseError::Serialization(_))
+        ));
+    }
+}
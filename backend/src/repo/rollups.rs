@@ -0,0 +1,247 @@
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+
+use crate::error::DatabaseError;
+use esp32_backend::shared::plant_insights::{BucketType, Rollup};
+
+/// Days a minute bucket is kept before DynamoDB TTL expires it
+pub const MINUTE_BUCKET_TTL_DAYS: i64 = 7;
+
+/// Days an hour bucket is kept before DynamoDB TTL expires it
+pub const HOUR_BUCKET_TTL_DAYS: i64 = 90;
+
+/// Start of the bucket containing `timestamp_ms`, in epoch milliseconds
+pub fn bucket_start_ms(bucket_type: BucketType, timestamp_ms: i64) -> i64 {
+    let width_ms = match bucket_type {
+        BucketType::Minute => 60_000,
+        BucketType::Hour => 3_600_000,
+    };
+    timestamp_ms - timestamp_ms.rem_euclid(width_ms)
+}
+
+/// Expressions and values for incrementing a rollup counter
+///
+/// Kept separate from the SDK call so the update can be verified in tests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncrementRollupParams {
+    pub key: HashMap<String, AttributeValue>,
+    pub update_expression: String,
+    pub expression_attribute_names: HashMap<String, String>,
+    pub expression_attribute_values: HashMap<String, AttributeValue>,
+}
+
+/// Build the update that adds one to a rollup counter
+///
+/// Matches the items written by the insights rollup updater: `count` is
+/// incremented atomically and the bucket's descriptive attributes and TTL are
+/// only set when the counter is created.
+pub fn build_increment_rollup_params(
+    bucket_type: BucketType,
+    timestamp_ms: i64,
+    metric_name: &str,
+    dimensions: &HashMap<String, String>,
+) -> IncrementRollupParams {
+    let start_ms = bucket_start_ms(bucket_type, timestamp_ms);
+    let ttl_days = match bucket_type {
+        BucketType::Minute => MINUTE_BUCKET_TTL_DAYS,
+        BucketType::Hour => HOUR_BUCKET_TTL_DAYS,
+    };
+    let ttl = start_ms / 1000 + ttl_days * 24 * 3600;
+
+    let mut key = HashMap::new();
+    key.insert(
+        "bucket_key".to_string(),
+        AttributeValue::S(Rollup::bucket_key(bucket_type, start_ms)),
+    );
+    key.insert(
+        "metric_key".to_string(),
+        AttributeValue::S(Rollup::metric_key(metric_name, dimensions)),
+    );
+
+    // count and ttl are reserved words in DynamoDB expressions
+    let mut expression_attribute_names = HashMap::new();
+    expression_attribute_names.insert("#count".to_string(), "count".to_string());
+    expression_attribute_names.insert("#ttl".to_string(), "ttl".to_string());
+
+    let mut expression_attribute_values = HashMap::new();
+    expression_attribute_values.insert(":zero".to_string(), AttributeValue::N("0".to_string()));
+    expression_attribute_values.insert(":one".to_string(), AttributeValue::N("1".to_string()));
+    expression_attribute_values.insert(
+        ":bucket_start_ms".to_string(),
+        AttributeValue::N(start_ms.to_string()),
+    );
+    expression_attribute_values.insert(
+        ":bucket_type".to_string(),
+        AttributeValue::S(bucket_type.as_str().to_string()),
+    );
+    expression_attribute_values.insert(
+        ":metric_name".to_string(),
+        AttributeValue::S(metric_name.to_string()),
+    );
+    expression_attribute_values.insert(":ttl".to_string(), AttributeValue::N(ttl.to_string()));
+
+    let mut update_expression = "SET #count = if_not_exists(#count, :zero) + :one, \
+         bucket_start_ms = if_not_exists(bucket_start_ms, :bucket_start_ms), \
+         bucket_type = if_not_exists(bucket_type, :bucket_type), \
+         metric_name = if_not_exists(metric_name, :metric_name), \
+         #ttl = if_not_exists(#ttl, :ttl)"
+        .to_string();
+
+    if !dimensions.is_empty() {
+        update_expression.push_str(", dimensions = if_not_exists(dimensions, :dimensions)");
+        expression_attribute_values.insert(
+            ":dimensions".to_string(),
+            AttributeValue::M(
+                dimensions
+                    .iter()
+                    .map(|(k, v)| (k.clone(), AttributeValue::S(v.clone())))
+                    .collect(),
+            ),
+        );
+    }
+
+    IncrementRollupParams {
+        key,
+        update_expression,
+        expression_attribute_names,
+        expression_attribute_values,
+    }
+}
+
+/// Add one to the rollup counter for `metric_name` in the bucket containing `timestamp_ms`
+///
+/// # Returns
+/// * `Ok(count)` - The counter's value after this increment
+/// * `Err(DatabaseError)` - DynamoDB error occurred
+pub async fn increment_rollup(
+    client: &DynamoDbClient,
+    table_name: &str,
+    bucket_type: BucketType,
+    timestamp_ms: i64,
+    metric_name: &str,
+    dimensions: &HashMap<String, String>,
+) -> Result<i64, DatabaseError> {
+    let params = build_increment_rollup_params(bucket_type, timestamp_ms, metric_name, dimensions);
+
+    let result = client
+        .update_item()
+        .table_name(table_name)
+        .set_key(Some(params.key))
+        .update_expression(params.update_expression)
+        .set_expression_attribute_names(Some(params.expression_attribute_names))
+        .set_expression_attribute_values(Some(params.expression_attribute_values))
+        .return_values(ReturnValue::UpdatedNew)
+        .send()
+        .await
+        .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+    result
+        .attributes
+        .as_ref()
+        .and_then(|attrs| attrs.get("count"))
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+        .ok_or_else(|| DatabaseError::Serialization("Missing rollup count".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_dimensions(key_id: &str) -> HashMap<String, String> {
+        HashMap::from([("key_id".to_string(), key_id.to_string())])
+    }
+
+    #[test]
+    fn test_bucket_start_ms_aligns_to_minute_and_hour() {
+        // 2024-01-01T00:01:30.500Z
+        let ts = 1_704_067_290_500;
+        assert_eq!(bucket_start_ms(BucketType::Minute, ts), 1_704_067_260_000);
+        assert_eq!(bucket_start_ms(BucketType::Hour, ts), 1_704_067_200_000);
+        assert_eq!(
+            bucket_start_ms(BucketType::Minute, 1_704_067_260_000),
+            1_704_067_260_000
+        );
+    }
+
+    #[test]
+    fn test_build_increment_rollup_params_adds_one_per_key_and_minute() {
+        let params = build_increment_rollup_params(
+            BucketType::Minute,
+            1_704_067_290_500,
+            "ingest_requests",
+            &key_dimensions("key-1"),
+        );
+
+        assert_eq!(
+            params.key.get("bucket_key").unwrap().as_s().unwrap(),
+            "minute#1704067260000"
+        );
+        assert_eq!(
+            params.key.get("metric_key").unwrap().as_s().unwrap(),
+            "ingest_requests#key_id=key-1"
+        );
+        assert!(params
+            .update_expression
+            .starts_with("SET #count = if_not_exists(#count, :zero) + :one"));
+        assert!(params
+            .update_expression
+            .contains("dimensions = if_not_exists(dimensions, :dimensions)"));
+        assert_eq!(
+            params.expression_attribute_values.get(":one"),
+            Some(&AttributeValue::N("1".to_string()))
+        );
+        assert_eq!(
+            params.expression_attribute_values.get(":ttl"),
+            Some(&AttributeValue::N(
+                (1_704_067_260 + MINUTE_BUCKET_TTL_DAYS * 24 * 3600).to_string()
+            ))
+        );
+        assert_eq!(
+            params.expression_attribute_names.get("#count"),
+            Some(&"count".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_increment_rollup_params_same_minute_shares_counter() {
+        let dims = key_dimensions("key-1");
+        let first = build_increment_rollup_params(
+            BucketType::Minute,
+            1_704_067_260_000,
+            "ingest_requests",
+            &dims,
+        );
+        let last = build_increment_rollup_params(
+            BucketType::Minute,
+            1_704_067_319_999,
+            "ingest_requests",
+            &dims,
+        );
+        let next = build_increment_rollup_params(
+            BucketType::Minute,
+            1_704_067_320_000,
+            "ingest_requests",
+            &dims,
+        );
+
+        assert_eq!(first.key, last.key);
+        assert_ne!(first.key, next.key);
+    }
+
+    #[test]
+    fn test_build_increment_rollup_params_without_dimensions() {
+        let params = build_increment_rollup_params(
+            BucketType::Hour,
+            1_704_067_290_500,
+            "readings_ingested_count",
+            &HashMap::new(),
+        );
+
+        assert!(!params.update_expression.contains("dimensions"));
+        assert!(!params
+            .expression_attribute_values
+            .contains_key(":dimensions"));
+    }
+}
@@ -6,7 +6,7 @@ use crate::config::Config;
 use crate::error::ApiError;
 use crate::handlers::data::handle_data;
 use crate::handlers::register::handle_register;
-use esp32_backend::{Clock, IdGenerator};
+use esp32_backend::{BestEffortQueue, Clock, IdGenerator};
 
 /// Route a health check request (no config needed)
 pub fn route_request_health(request_id: &str) -> Result<Response<Body>, ApiError> {
@@ -24,6 +24,7 @@ pub async fn route_request(
     config: &Config,
     clock: &dyn Clock,
     id_generator: &dyn IdGenerator,
+    writes: &BestEffortQueue,
 ) -> Result<Response<Body>, ApiError> {
     let method = event.method();
     let path = normalize_path(event.uri().path());
@@ -52,8 +53,14 @@ pub async fn route_request(
                 &config.dynamodb_client,
                 &config.devices_table,
                 &config.api_keys_table,
+                &config.processed_batches_table,
                 clock,
+                config.last_used_throttle_minutes,
+                config.api_key_hash_scheme,
+                config.allow_unknown_capabilities,
+                config.register_retry_window_seconds,
                 id_generator,
+                writes,
             )
             .await
         }
@@ -61,7 +68,7 @@ pub async fn route_request(
         // Sensor data ingestion endpoint
         (&Method::POST, "/data") => {
             info!(request_id = %request_id, "Data ingestion endpoint");
-            handle_data(event, request_id, config, clock).await
+            handle_data(event, request_id, config, clock, writes).await
         }
 
         // Unknown route - return 404
@@ -82,7 +89,7 @@ pub async fn route_request(
 /// This ensures that /register and /register/ are treated the same.
 /// It also strips /api/control and /api/data prefixes added by CloudFront routing.
 /// The root path "/" is preserved as-is.
-fn normalize_path(path: &str) -> String {
+pub fn normalize_path(path: &str) -> String {
     if path == "/" {
         return path.to_string();
     }
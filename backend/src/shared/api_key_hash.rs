@@ -0,0 +1,76 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use sha2::{Digest, Sha256};
+
+/// SHA-256(pepper || key), hex-encoded
+///
+/// The pepper is never stored in DynamoDB, so a leaked table alone is not
+/// enough to verify API keys.
+pub fn sha256_digest(pepper: &str, key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(pepper.as_bytes());
+    hasher.update(key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Argon2id(key) with the pepper as secret and SHA-256(pepper) as salt, hex-encoded
+///
+/// Keys are looked up by hash through a GSI, so the hash must be deterministic:
+/// the salt is derived from the pepper rather than generated per key. The data
+/// plane writes these hashes and the control plane looks them up, so both hash
+/// through this function and cannot drift apart.
+///
+/// # Errors
+/// * `argon2::Error` - Argon2 rejected the pepper as a secret
+pub fn argon2_digest(pepper: &str, key: &str) -> Result<String, argon2::Error> {
+    let argon2 = Argon2::new_with_secret(
+        pepper.as_bytes(),
+        Algorithm::Argon2id,
+        Version::V0x13,
+        Params::default(),
+    )?;
+
+    let salt = Sha256::digest(pepper.as_bytes());
+    let mut output = [0u8; 32];
+    argon2.hash_password_into(key.as_bytes(), &salt, &mut output)?;
+
+    Ok(hex::encode(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+
+    #[test]
+    fn test_sha256_digest() {
+        let hash = sha256_digest("test-pepper", KEY);
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, sha256_digest("test-pepper", KEY));
+        assert_ne!(hash, sha256_digest("other-pepper", KEY));
+    }
+
+    #[test]
+    fn test_argon2_digest_deterministic() {
+        let hash1 = argon2_digest("test-pepper-argon2", KEY).unwrap();
+        let hash2 = argon2_digest("test-pepper-argon2", KEY).unwrap();
+
+        assert_eq!(hash1, hash2);
+        assert_eq!(hash1.len(), 64);
+        assert!(hash1.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_argon2_digest_depends_on_key_and_pepper() {
+        let other_key = "0000000000000000000000000000000000000000000000000000000000000000";
+        let hash = argon2_digest("test-pepper-argon2", KEY).unwrap();
+
+        assert_ne!(
+            hash,
+            argon2_digest("test-pepper-argon2", other_key).unwrap()
+        );
+        assert_ne!(hash, argon2_digest("other-pepper-argon2", KEY).unwrap());
+        assert_ne!(hash, sha256_digest("test-pepper-argon2", KEY));
+    }
+}
@@ -0,0 +1,185 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Default number of best-effort writes that may be pending at once
+pub const DEFAULT_BEST_EFFORT_CAPACITY: usize = 16;
+
+/// Default time budget for flushing pending writes before a response returns
+pub const DEFAULT_FLUSH_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Outcome of a flush
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushStats {
+    /// Writes that finished before the deadline
+    pub completed: usize,
+    /// Writes aborted because they exceeded the deadline
+    pub dropped: usize,
+}
+
+/// Bounded queue for best-effort writes (last_used_at, rollup counters, ...)
+///
+/// Writes are spawned immediately so they overlap with the critical path,
+/// then `flush` waits for them (up to a timeout) before the handler returns.
+/// This keeps them from being lost when the Lambda execution environment is
+/// frozen after the response is sent.
+#[derive(Debug)]
+pub struct BestEffortQueue {
+    capacity: usize,
+    pending: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl BestEffortQueue {
+    /// Create a queue that holds at most `capacity` pending writes
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Spawn a best-effort write
+    ///
+    /// Returns false (and discards the write) if the queue is already full.
+    pub fn submit<F>(&self, write: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|handle| !handle.is_finished());
+
+        if pending.len() >= self.capacity {
+            warn!(
+                capacity = self.capacity,
+                "Best-effort write queue full, dropping write"
+            );
+            return false;
+        }
+
+        pending.push(tokio::spawn(write));
+        true
+    }
+
+    /// Number of writes that have been submitted but not yet flushed
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Wait for pending writes to complete, aborting any still running at the deadline
+    pub async fn flush(&self, timeout: Duration) -> FlushStats {
+        let handles: Vec<JoinHandle<()>> = std::mem::take(&mut *self.pending.lock().unwrap());
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut stats = FlushStats::default();
+
+        for mut handle in handles {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(_) => stats.completed += 1,
+                Err(_) => {
+                    handle.abort();
+                    stats.dropped += 1;
+                }
+            }
+        }
+
+        if stats.dropped > 0 {
+            warn!(
+                completed = stats.completed,
+                dropped = stats.dropped,
+                "Best-effort writes exceeded flush timeout"
+            );
+        }
+
+        stats
+    }
+}
+
+impl Default for BestEffortQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_BEST_EFFORT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_flush_drains_writes_within_timeout() {
+        let queue = BestEffortQueue::default();
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..3 {
+            let counter = counter.clone();
+            assert!(queue.submit(async move {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+            }));
+        }
+
+        let stats = queue.flush(Duration::from_secs(1)).await;
+
+        assert_eq!(
+            stats,
+            FlushStats {
+                completed: 3,
+                dropped: 0
+            }
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert_eq!(queue.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_flush_drops_writes_exceeding_timeout() {
+        let queue = BestEffortQueue::default();
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let fast = counter.clone();
+        queue.submit(async move {
+            fast.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let slow = counter.clone();
+        queue.submit(async move {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            slow.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let stats = queue.flush(Duration::from_millis(50)).await;
+
+        assert_eq!(
+            stats,
+            FlushStats {
+                completed: 1,
+                dropped: 1
+            }
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_when_full() {
+        let queue = BestEffortQueue::new(1);
+
+        assert!(queue.submit(tokio::time::sleep(Duration::from_secs(30))));
+        assert!(!queue.submit(async {}));
+        assert_eq!(queue.pending_count(), 1);
+
+        let stats = queue.flush(Duration::from_millis(10)).await;
+        assert_eq!(stats.dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_flush_empty_queue() {
+        let queue = BestEffortQueue::default();
+        assert_eq!(
+            queue.flush(DEFAULT_FLUSH_TIMEOUT).await,
+            FlushStats::default()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use futures::stream::{self, StreamExt};
+use std::future::Future;
+
+/// Limit on the DynamoDB calls one request's fan-out keeps in flight
+///
+/// Keeps a single request from bursting past the table's capacity when it
+/// looks up many devices at once.
+pub const DEFAULT_FANOUT_CONCURRENCY: usize = 10;
+
+/// Run `f` over every item with at most `concurrency` calls in flight
+///
+/// Calls complete in whatever order DynamoDB answers them, but the results
+/// come back in input order, one per item. Errors do not stop the other
+/// calls; the caller decides whether one failure fails the whole request. A
+/// `concurrency` of 0 is treated as 1.
+pub async fn bounded_map<I, T, E, F, Fut>(items: I, concurrency: usize, f: F) -> Vec<Result<T, E>>
+where
+    I: IntoIterator,
+    F: Fn(I::Item) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut indexed: Vec<(usize, Result<T, E>)> = stream::iter(items.into_iter().enumerate())
+        .map(|(index, item)| {
+            let call = f(item);
+            async move { (index, call.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    indexed.sort_unstable_by_key(|(index, _)| *index);
+    indexed.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_bounded_map_preserves_input_order() {
+        // Later items finish first
+        let results = bounded_map(0..5u64, 5, |i| async move {
+            tokio::time::sleep(Duration::from_millis(5 * (5 - i))).await;
+            Ok::<_, String>(i * 10)
+        })
+        .await;
+
+        let values: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, vec![0, 10, 20, 30, 40]);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_map_bounds_in_flight_calls() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let results = bounded_map(0..25, 4, |i| {
+            let in_flight = &in_flight;
+            let peak = &peak;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, String>(i)
+            }
+        })
+        .await;
+
+        assert_eq!(results.len(), 25);
+        assert_eq!(peak.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_map_keeps_errors_in_place() {
+        let results = bounded_map(vec![1, 2, 3], 2, |i| async move {
+            if i == 2 {
+                Err(format!("item {} failed", i))
+            } else {
+                Ok(i)
+            }
+        })
+        .await;
+
+        assert_eq!(
+            results,
+            vec![Ok(1), Err("item 2 failed".to_string()), Ok(3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bounded_map_zero_concurrency_still_runs() {
+        let results = bounded_map(vec!["a", "b"], 0, |s| async move { Ok::<_, ()>(s) }).await;
+
+        assert_eq!(results, vec![Ok("a"), Ok("b")]);
+    }
+}
@@ -1,4 +1,5 @@
 use base64::{engine::general_purpose, Engine as _};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 /// PageToken for device list pagination
@@ -15,6 +16,33 @@ pub struct ReadingsPageToken {
     pub ts_batch: String,
 }
 
+/// PageToken for readings by ingest time (IngestTimeIndex) pagination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestTimePageToken {
+    pub hardware_id: String,
+    pub ts_batch: String,
+    pub ingest_time_ms: i64,
+}
+
+/// Where a fleet-wide listing stands in one shard of the GlobalIngestTimeIndex
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GlobalIngestShardPosition {
+    /// Nothing has been returned from the shard yet
+    Start,
+    /// Resume the shard after this reading
+    After(IngestTimePageToken),
+    /// Every reading in the shard has been returned
+    Done,
+}
+
+/// PageToken for fleet-wide readings by ingest time (GlobalIngestTimeIndex) pagination
+///
+/// Holds one position per `gsi1pk` shard, in shard order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalIngestTimePageToken {
+    pub shards: Vec<GlobalIngestShardPosition>,
+}
+
 /// PageToken for API key list pagination
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiKeyListPageToken {
@@ -22,104 +50,464 @@ pub struct ApiKeyListPageToken {
     pub gsi1sk: String,
 }
 
+/// PageToken for events list pagination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventsPageToken {
+    pub hardware_id: String,
+    pub start_time_ms: i64,
+}
+
+/// PageToken for insights list pagination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsightsPageToken {
+    pub hardware_id: String,
+    pub timestamp_ms: i64,
+}
+
+/// Version of the page token payload layout written by the encoders
+///
+/// Each token is base64 over this byte followed by the payload. Bump it when
+/// a token struct changes in a way older decoders can't read, so stale tokens
+/// are rejected up front instead of failing (or succeeding) mid-query.
+///
+/// v2 payloads are the compact binary layout (see `CompactToken`); v1
+/// payloads were JSON and are still decoded.
+pub const CURSOR_VERSION: u8 = 2;
+
+/// Version byte of a JSON-payload token issued before the compact layout
+const JSON_V1: u8 = 1;
+
+/// First byte of a pre-versioning token, which was bare JSON; read as v1
+const LEGACY_V1_PREFIX: u8 = b'{';
+
 /// PageToken encoding/decoding error
-#[derive(Debug, Clone)]
-pub struct PageTokenError {
-    pub message: String,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorError {
+    /// The token could not be encoded, or is not a well-formed token
+    Invalid(String),
+    /// The token was written with a payload version this build can't read
+    UnsupportedVersion(u8),
 }
 
-impl PageTokenError {
-    pub fn new(message: impl Into<String>) -> Self {
-        Self {
-            message: message.into(),
+impl std::fmt::Display for CursorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CursorError::Invalid(message) => write!(f, "{}", message),
+            CursorError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported pageToken version: {}", version)
+            }
         }
     }
 }
 
-impl std::fmt::Display for PageTokenError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "PageToken error: {}", self.message)
+impl std::error::Error for CursorError {}
+
+/// A page token with a compact binary layout
+///
+/// The payload is a kind byte, so a token for one endpoint can't be decoded
+/// as another's, followed by the fields in declaration order: strings as a
+/// LEB128 length and UTF-8 bytes, integers as 8 big-endian bytes.
+trait CompactToken: Sized {
+    /// Distinguishes token types that share a field layout
+    const KIND: u8;
+
+    fn write_fields(&self, out: &mut Vec<u8>);
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError>;
+}
+
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    let mut len = value.len();
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
     }
+    out.extend_from_slice(value.as_bytes());
 }
 
-impl std::error::Error for PageTokenError {}
+fn write_i64(out: &mut Vec<u8>, value: i64) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
 
-/// Encode device list pageToken to base64 string
-pub fn encode_device_page_token(hardware_id: &str, gsi1sk: &str) -> Result<String, PageTokenError> {
-    let page_token = DeviceListPageToken {
-        hardware_id: hardware_id.to_string(),
-        gsi1sk: gsi1sk.to_string(),
-    };
+fn write_u8(out: &mut Vec<u8>, value: u8) {
+    out.push(value);
+}
+
+/// Reads fields back out of a compact payload
+struct CompactReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> CompactReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CursorError> {
+        if self.bytes.len() < len {
+            return Err(CursorError::Invalid("Truncated pageToken".to_string()));
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn read_str(&mut self) -> Result<String, CursorError> {
+        let mut len = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = self.take(1)?[0];
+            if shift > 28 {
+                return Err(CursorError::Invalid("Invalid pageToken length".to_string()));
+            }
+            len |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|e| CursorError::Invalid(format!("Failed to decode UTF-8: {}", e)))
+    }
 
-    let json = serde_json::to_string(&page_token)
-        .map_err(|e| PageTokenError::new(format!("Failed to serialize pageToken: {}", e)))?;
+    fn read_i64(&mut self) -> Result<i64, CursorError> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().expect("took 8 bytes");
+        Ok(i64::from_be_bytes(bytes))
+    }
 
-    Ok(general_purpose::STANDARD.encode(json.as_bytes()))
+    fn read_u8(&mut self) -> Result<u8, CursorError> {
+        Ok(self.take(1)?[0])
+    }
 }
 
-/// Decode device list pageToken from base64 string
-pub fn decode_device_page_token(page_token: &str) -> Result<DeviceListPageToken, PageTokenError> {
+impl CompactToken for DeviceListPageToken {
+    const KIND: u8 = 1;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_str(out, &self.hardware_id);
+        write_str(out, &self.gsi1sk);
+    }
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError> {
+        Ok(Self {
+            hardware_id: reader.read_str()?,
+            gsi1sk: reader.read_str()?,
+        })
+    }
+}
+
+impl CompactToken for ReadingsPageToken {
+    const KIND: u8 = 2;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_str(out, &self.hardware_id);
+        write_str(out, &self.ts_batch);
+    }
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError> {
+        Ok(Self {
+            hardware_id: reader.read_str()?,
+            ts_batch: reader.read_str()?,
+        })
+    }
+}
+
+impl CompactToken for IngestTimePageToken {
+    const KIND: u8 = 3;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_str(out, &self.hardware_id);
+        write_str(out, &self.ts_batch);
+        write_i64(out, self.ingest_time_ms);
+    }
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError> {
+        Ok(Self {
+            hardware_id: reader.read_str()?,
+            ts_batch: reader.read_str()?,
+            ingest_time_ms: reader.read_i64()?,
+        })
+    }
+}
+
+impl CompactToken for ApiKeyListPageToken {
+    const KIND: u8 = 4;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_str(out, &self.key_id);
+        write_str(out, &self.gsi1sk);
+    }
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError> {
+        Ok(Self {
+            key_id: reader.read_str()?,
+            gsi1sk: reader.read_str()?,
+        })
+    }
+}
+
+impl CompactToken for EventsPageToken {
+    const KIND: u8 = 5;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_str(out, &self.hardware_id);
+        write_i64(out, self.start_time_ms);
+    }
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError> {
+        Ok(Self {
+            hardware_id: reader.read_str()?,
+            start_time_ms: reader.read_i64()?,
+        })
+    }
+}
+
+/// Tags of `GlobalIngestShardPosition` in the compact layout
+const SHARD_START: u8 = 0;
+const SHARD_AFTER: u8 = 1;
+const SHARD_DONE: u8 = 2;
+
+impl CompactToken for GlobalIngestTimePageToken {
+    const KIND: u8 = 6;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_u8(out, self.shards.len() as u8);
+        for position in &self.shards {
+            match position {
+                GlobalIngestShardPosition::Start => write_u8(out, SHARD_START),
+                GlobalIngestShardPosition::After(after) => {
+                    write_u8(out, SHARD_AFTER);
+                    after.write_fields(out);
+                }
+                GlobalIngestShardPosition::Done => write_u8(out, SHARD_DONE),
+            }
+        }
+    }
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError> {
+        let count = reader.read_u8()?;
+        let shards = (0..count)
+            .map(|_| match reader.read_u8()? {
+                SHARD_START => Ok(GlobalIngestShardPosition::Start),
+                SHARD_AFTER => Ok(GlobalIngestShardPosition::After(
+                    IngestTimePageToken::read_fields(reader)?,
+                )),
+                SHARD_DONE => Ok(GlobalIngestShardPosition::Done),
+                _ => Err(CursorError::Invalid(
+                    "Invalid shard position in pageToken".to_string(),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { shards })
+    }
+}
+
+impl CompactToken for InsightsPageToken {
+    const KIND: u8 = 7;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_str(out, &self.hardware_id);
+        write_i64(out, self.timestamp_ms);
+    }
+
+    fn read_fields(reader: &mut CompactReader<'_>) -> Result<Self, CursorError> {
+        Ok(Self {
+            hardware_id: reader.read_str()?,
+            timestamp_ms: reader.read_i64()?,
+        })
+    }
+}
+
+/// Serialize a token compactly and encode it as base64 behind the version byte
+fn encode_versioned<T: CompactToken>(page_token: &T) -> Result<String, CursorError> {
+    let mut bytes = vec![CURSOR_VERSION, T::KIND];
+    page_token.write_fields(&mut bytes);
+
+    Ok(general_purpose::STANDARD.encode(bytes))
+}
+
+/// Read a compact payload, rejecting other token kinds and trailing bytes
+fn decode_compact<T: CompactToken>(payload: &[u8]) -> Result<T, CursorError> {
+    let mut reader = CompactReader { bytes: payload };
+
+    if reader.take(1)?[0] != T::KIND {
+        return Err(CursorError::Invalid(
+            "pageToken is for a different endpoint".to_string(),
+        ));
+    }
+
+    let page_token = T::read_fields(&mut reader)?;
+    if !reader.bytes.is_empty() {
+        return Err(CursorError::Invalid(
+            "Trailing bytes in pageToken".to_string(),
+        ));
+    }
+
+    Ok(page_token)
+}
+
+/// Deserialize a v1 JSON payload
+fn decode_json<T: DeserializeOwned>(json: &[u8]) -> Result<T, CursorError> {
+    let json = std::str::from_utf8(json)
+        .map_err(|e| CursorError::Invalid(format!("Failed to decode UTF-8: {}", e)))?;
+
+    serde_json::from_str(json)
+        .map_err(|e| CursorError::Invalid(format!("Failed to deserialize pageToken: {}", e)))
+}
+
+/// Decode a base64 token, check its version byte and deserialize the payload
+///
+/// v1 tokens (JSON payload) and tokens issued before the version byte
+/// existed, which start directly with the JSON object, are still read, so
+/// pages in flight across a deploy keep working.
+fn decode_versioned<T: CompactToken + DeserializeOwned>(
+    page_token: &str,
+) -> Result<T, CursorError> {
+    let bytes = general_purpose::STANDARD
+        .decode(page_token)
+        .map_err(|e| CursorError::Invalid(format!("Failed to decode base64: {}", e)))?;
+
+    match bytes.split_first() {
+        Some((&CURSOR_VERSION, payload)) => decode_compact(payload),
+        Some((&JSON_V1, payload)) => decode_json(payload),
+        Some((&LEGACY_V1_PREFIX, _)) => decode_json(&bytes),
+        Some((&version, _)) => Err(CursorError::UnsupportedVersion(version)),
+        None => Err(CursorError::Invalid("Empty pageToken".to_string())),
+    }
+}
+
+/// Payload version a token was written with
+///
+/// Only the version byte is read; the payload isn't checked. Tokens issued
+/// before the version byte existed report v1, as `decode_versioned` reads
+/// them.
+pub fn page_token_version(page_token: &str) -> Result<u8, CursorError> {
     let bytes = general_purpose::STANDARD
         .decode(page_token)
-        .map_err(|e| PageTokenError::new(format!("Failed to decode base64: {}", e)))?;
+        .map_err(|e| CursorError::Invalid(format!("Failed to decode base64: {}", e)))?;
 
-    let json = String::from_utf8(bytes)
-        .map_err(|e| PageTokenError::new(format!("Failed to decode UTF-8: {}", e)))?;
+    match bytes.first() {
+        Some(&LEGACY_V1_PREFIX) => Ok(JSON_V1),
+        Some(&version) => Ok(version),
+        None => Err(CursorError::Invalid("Empty pageToken".to_string())),
+    }
+}
+
+/// Encode device list pageToken to base64 string
+pub fn encode_device_page_token(hardware_id: &str, gsi1sk: &str) -> Result<String, CursorError> {
+    encode_versioned(&DeviceListPageToken {
+        hardware_id: hardware_id.to_string(),
+        gsi1sk: gsi1sk.to_string(),
+    })
+}
 
-    serde_json::from_str(&json)
-        .map_err(|e| PageTokenError::new(format!("Failed to deserialize pageToken: {}", e)))
+/// Decode device list pageToken from base64 string
+pub fn decode_device_page_token(page_token: &str) -> Result<DeviceListPageToken, CursorError> {
+    decode_versioned(page_token)
 }
 
 /// Encode readings pageToken to base64 string
-pub fn encode_readings_page_token(hardware_id: &str, ts_batch: &str) -> Result<String, PageTokenError> {
-    let page_token = ReadingsPageToken {
+pub fn encode_readings_page_token(
+    hardware_id: &str,
+    ts_batch: &str,
+) -> Result<String, CursorError> {
+    encode_versioned(&ReadingsPageToken {
         hardware_id: hardware_id.to_string(),
         ts_batch: ts_batch.to_string(),
-    };
+    })
+}
 
-    let json = serde_json::to_string(&page_token)
-        .map_err(|e| PageTokenError::new(format!("Failed to serialize pageToken: {}", e)))?;
+/// Decode readings pageToken from base64 string
+pub fn decode_readings_page_token(page_token: &str) -> Result<ReadingsPageToken, CursorError> {
+    decode_versioned(page_token)
+}
 
-    Ok(general_purpose::STANDARD.encode(json.as_bytes()))
+/// Encode ingest-time readings pageToken to base64 string
+pub fn encode_ingest_time_page_token(
+    hardware_id: &str,
+    ts_batch: &str,
+    ingest_time_ms: i64,
+) -> Result<String, CursorError> {
+    encode_versioned(&IngestTimePageToken {
+        hardware_id: hardware_id.to_string(),
+        ts_batch: ts_batch.to_string(),
+        ingest_time_ms,
+    })
 }
 
-/// Decode readings pageToken from base64 string
-pub fn decode_readings_page_token(page_token: &str) -> Result<ReadingsPageToken, PageTokenError> {
-    let bytes = general_purpose::STANDARD
-        .decode(page_token)
-        .map_err(|e| PageTokenError::new(format!("Failed to decode base64: {}", e)))?;
+/// Decode ingest-time readings pageToken from base64 string
+pub fn decode_ingest_time_page_token(page_token: &str) -> Result<IngestTimePageToken, CursorError> {
+    decode_versioned(page_token)
+}
 
-    let json = String::from_utf8(bytes)
-        .map_err(|e| PageTokenError::new(format!("Failed to decode UTF-8: {}", e)))?;
+/// Encode fleet-wide ingest-time readings pageToken to base64 string
+pub fn encode_global_ingest_time_page_token(
+    shards: &[GlobalIngestShardPosition],
+) -> Result<String, CursorError> {
+    if shards.len() > u8::MAX as usize {
+        return Err(CursorError::Invalid(
+            "Too many shards for pageToken".to_string(),
+        ));
+    }
 
-    serde_json::from_str(&json)
-        .map_err(|e| PageTokenError::new(format!("Failed to deserialize pageToken: {}", e)))
+    encode_versioned(&GlobalIngestTimePageToken {
+        shards: shards.to_vec(),
+    })
+}
+
+/// Decode fleet-wide ingest-time readings pageToken from base64 string
+pub fn decode_global_ingest_time_page_token(
+    page_token: &str,
+) -> Result<GlobalIngestTimePageToken, CursorError> {
+    decode_versioned(page_token)
 }
 
 /// Encode API key list pageToken to base64 string
-pub fn encode_api_key_page_token(key_id: &str, gsi1sk: &str) -> Result<String, PageTokenError> {
-    let page_token = ApiKeyListPageToken {
+pub fn encode_api_key_page_token(key_id: &str, gsi1sk: &str) -> Result<String, CursorError> {
+    encode_versioned(&ApiKeyListPageToken {
         key_id: key_id.to_string(),
         gsi1sk: gsi1sk.to_string(),
-    };
+    })
+}
 
-    let json = serde_json::to_string(&page_token)
-        .map_err(|e| PageTokenError::new(format!("Failed to serialize pageToken: {}", e)))?;
+/// Decode API key list pageToken from base64 string
+pub fn decode_api_key_page_token(page_token: &str) -> Result<ApiKeyListPageToken, CursorError> {
+    decode_versioned(page_token)
+}
 
-    Ok(general_purpose::STANDARD.encode(json.as_bytes()))
+/// Encode events pageToken to base64 string
+pub fn encode_events_page_token(
+    hardware_id: &str,
+    start_time_ms: i64,
+) -> Result<String, CursorError> {
+    encode_versioned(&EventsPageToken {
+        hardware_id: hardware_id.to_string(),
+        start_time_ms,
+    })
 }
 
-/// Decode API key list pageToken from base64 string
-pub fn decode_api_key_page_token(page_token: &str) -> Result<ApiKeyListPageToken, PageTokenError> {
-    let bytes = general_purpose::STANDARD
-        .decode(page_token)
-        .map_err(|e| PageTokenError::new(format!("Failed to decode base64: {}", e)))?;
+/// Decode events pageToken from base64 string
+pub fn decode_events_page_token(page_token: &str) -> Result<EventsPageToken, CursorError> {
+    decode_versioned(page_token)
+}
 
-    let json = String::from_utf8(bytes)
-        .map_err(|e| PageTokenError::new(format!("Failed to decode UTF-8: {}", e)))?;
+/// Encode insights pageToken to base64 string
+pub fn encode_insights_page_token(
+    hardware_id: &str,
+    timestamp_ms: i64,
+) -> Result<String, CursorError> {
+    encode_versioned(&InsightsPageToken {
+        hardware_id: hardware_id.to_string(),
+        timestamp_ms,
+    })
+}
 
-    serde_json::from_str(&json)
-        .map_err(|e| PageTokenError::new(format!("Failed to deserialize pageToken: {}", e)))
+/// Decode insights pageToken from base64 string
+pub fn decode_insights_page_token(page_token: &str) -> Result<InsightsPageToken, CursorError> {
+    decode_versioned(page_token)
 }
 
 #[cfg(test)]
@@ -150,6 +538,74 @@ mod tests {
         assert_eq!(decoded.ts_batch, ts_batch);
     }
 
+    #[test]
+    fn test_encode_decode_ingest_time_page_token() {
+        let hardware_id = "AA:BB:CC:DD:EE:FF";
+        let ts_batch = "1704067800000#batch_id_123";
+        let ingest_time_ms = 1704067805000;
+
+        let encoded = encode_ingest_time_page_token(hardware_id, ts_batch, ingest_time_ms).unwrap();
+        let decoded = decode_ingest_time_page_token(&encoded).unwrap();
+
+        assert_eq!(decoded.hardware_id, hardware_id);
+        assert_eq!(decoded.ts_batch, ts_batch);
+        assert_eq!(decoded.ingest_time_ms, ingest_time_ms);
+    }
+
+    #[test]
+    fn test_global_ingest_time_page_token_round_trip() {
+        let ts_batch = "1704067800000#batch_id_123";
+        let shards = vec![
+            GlobalIngestShardPosition::Start,
+            GlobalIngestShardPosition::After(IngestTimePageToken {
+                hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+                ts_batch: ts_batch.to_string(),
+                ingest_time_ms: 1704067805000,
+            }),
+            GlobalIngestShardPosition::Done,
+        ];
+
+        let encoded = encode_global_ingest_time_page_token(&shards).unwrap();
+        let decoded = decode_global_ingest_time_page_token(&encoded).unwrap();
+
+        assert_eq!(decoded.shards.len(), 3);
+        assert!(matches!(
+            decoded.shards[0],
+            GlobalIngestShardPosition::Start
+        ));
+        match &decoded.shards[1] {
+            GlobalIngestShardPosition::After(after) => {
+                assert_eq!(after.hardware_id, "AA:BB:CC:DD:EE:FF");
+                assert_eq!(after.ts_batch, ts_batch);
+                assert_eq!(after.ingest_time_ms, 1704067805000);
+            }
+            other => panic!("Expected After, got {:?}", other),
+        }
+        assert!(matches!(decoded.shards[2], GlobalIngestShardPosition::Done));
+
+        // It resumes a different index than a per-device ingest-time token
+        assert!(decode_ingest_time_page_token(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_decode_global_ingest_time_page_token_rejects_bad_shard_tag() {
+        let bytes = vec![CURSOR_VERSION, 8, 1, 9];
+        let encoded = general_purpose::STANDARD.encode(bytes);
+
+        assert!(matches!(
+            decode_global_ingest_time_page_token(&encoded),
+            Err(CursorError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_ingest_time_page_token_rejects_readings_token() {
+        // A ts_batch-only readings cursor cannot resume an ingest-time query
+        let readings_token =
+            encode_readings_page_token("AA:BB:CC:DD:EE:FF", "1704067800000#b").unwrap();
+        assert!(decode_ingest_time_page_token(&readings_token).is_err());
+    }
+
     #[test]
     fn test_decode_invalid_page_token() {
         // Invalid base64
@@ -201,4 +657,193 @@ mod tests {
         let wrong_structure = general_purpose::STANDARD.encode(b"{\"wrong\":\"fields\"}");
         assert!(decode_api_key_page_token(&wrong_structure).is_err());
     }
+
+    #[test]
+    fn test_encode_decode_events_page_token() {
+        let hardware_id = "AA:BB:CC:DD:EE:FF";
+        let start_time_ms = 1704067800000;
+
+        let encoded = encode_events_page_token(hardware_id, start_time_ms).unwrap();
+        let decoded = decode_events_page_token(&encoded).unwrap();
+
+        assert_eq!(decoded.hardware_id, hardware_id);
+        assert_eq!(decoded.start_time_ms, start_time_ms);
+    }
+
+    #[test]
+    fn test_encode_decode_insights_page_token() {
+        let hardware_id = "AA:BB:CC:DD:EE:FF";
+        let timestamp_ms = 1704067800000;
+
+        let encoded = encode_insights_page_token(hardware_id, timestamp_ms).unwrap();
+        let decoded = decode_insights_page_token(&encoded).unwrap();
+
+        assert_eq!(decoded.hardware_id, hardware_id);
+        assert_eq!(decoded.timestamp_ms, timestamp_ms);
+    }
+
+    #[test]
+    fn test_decode_invalid_events_and_insights_page_tokens() {
+        // Invalid base64
+        assert!(decode_events_page_token("not-valid-base64!@#").is_err());
+        assert!(decode_insights_page_token("not-valid-base64!@#").is_err());
+
+        // Valid base64 but invalid JSON
+        let invalid_json = general_purpose::STANDARD.encode(b"not json");
+        assert!(decode_events_page_token(&invalid_json).is_err());
+        assert!(decode_insights_page_token(&invalid_json).is_err());
+
+        // Valid JSON but wrong structure
+        let wrong_structure = general_purpose::STANDARD.encode(b"{\"wrong\":\"fields\"}");
+        assert!(decode_events_page_token(&wrong_structure).is_err());
+        assert!(decode_insights_page_token(&wrong_structure).is_err());
+    }
+
+    #[test]
+    fn test_insights_page_token_is_not_an_events_token() {
+        // Same field layout, different endpoint
+        let insights = encode_insights_page_token("AA:BB:CC:DD:EE:FF", 1704067800000).unwrap();
+        assert!(decode_events_page_token(&insights).is_err());
+
+        let events = encode_events_page_token("AA:BB:CC:DD:EE:FF", 1704067800000).unwrap();
+        assert!(decode_insights_page_token(&events).is_err());
+    }
+
+    #[test]
+    fn test_encoded_page_token_carries_version() {
+        let encoded = encode_events_page_token("AA:BB:CC:DD:EE:FF", 1704067800000).unwrap();
+        let bytes = general_purpose::STANDARD.decode(&encoded).unwrap();
+        assert_eq!(bytes[0], CURSOR_VERSION);
+
+        let decoded = decode_events_page_token(&encoded).unwrap();
+        assert_eq!(decoded.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(decoded.start_time_ms, 1704067800000);
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_version() {
+        let mut bytes = vec![0x7f];
+        bytes.extend_from_slice(
+            br#"{"hardware_id":"AA:BB:CC:DD:EE:FF","gsi1sk":"2024-01-15T14:22:00Z"}"#,
+        );
+        let token = general_purpose::STANDARD.encode(bytes);
+
+        assert_eq!(
+            decode_device_page_token(&token).unwrap_err(),
+            CursorError::UnsupportedVersion(0x7f)
+        );
+        assert_eq!(
+            decode_api_key_page_token(&token).unwrap_err(),
+            CursorError::UnsupportedVersion(0x7f)
+        );
+    }
+
+    /// A token as encoded before the compact layout: v1 byte and JSON
+    fn json_v1_token<T: Serialize>(page_token: &T) -> String {
+        let mut bytes = vec![JSON_V1];
+        bytes.extend_from_slice(&serde_json::to_vec(page_token).unwrap());
+        general_purpose::STANDARD.encode(bytes)
+    }
+
+    #[test]
+    fn test_compact_readings_token_is_shorter_than_json() {
+        let hardware_id = "AA:BB:CC:DD:EE:FF";
+        let ts_batch = "1704067800000#AA:BB:CC:DD:EE:FF_7c9e6679_1704067200000_1704067800000";
+
+        let compact = encode_readings_page_token(hardware_id, ts_batch).unwrap();
+        let json = json_v1_token(&ReadingsPageToken {
+            hardware_id: hardware_id.to_string(),
+            ts_batch: ts_batch.to_string(),
+        });
+
+        // 160 base64 characters as JSON, 120 compact
+        assert_eq!(json.len(), 160);
+        assert_eq!(compact.len(), 120);
+
+        let decoded = decode_readings_page_token(&compact).unwrap();
+        assert_eq!(decoded.hardware_id, hardware_id);
+        assert_eq!(decoded.ts_batch, ts_batch);
+    }
+
+    #[test]
+    fn test_decode_json_v1_token() {
+        let token = json_v1_token(&IngestTimePageToken {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            ts_batch: "1704067800000#b".to_string(),
+            ingest_time_ms: 1704067805000,
+        });
+
+        let decoded = decode_ingest_time_page_token(&token).unwrap();
+        assert_eq!(decoded.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(decoded.ts_batch, "1704067800000#b");
+        assert_eq!(decoded.ingest_time_ms, 1704067805000);
+    }
+
+    #[test]
+    fn test_decode_compact_rejects_other_kind_and_bad_payloads() {
+        // Same field layout, different endpoint
+        let device_token = encode_device_page_token("AA:BB:CC:DD:EE:FF", "2024-01-15").unwrap();
+        assert!(decode_readings_page_token(&device_token).is_err());
+
+        let mut bytes = general_purpose::STANDARD
+            .decode(encode_events_page_token("AA:BB:CC:DD:EE:FF", 1704067800000).unwrap())
+            .unwrap();
+
+        // Truncated integer
+        let truncated = general_purpose::STANDARD.encode(&bytes[..bytes.len() - 1]);
+        assert!(decode_events_page_token(&truncated).is_err());
+
+        // Trailing garbage
+        bytes.push(0);
+        let trailing = general_purpose::STANDARD.encode(&bytes);
+        assert!(decode_events_page_token(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_compact_roundtrip_long_and_unicode_strings() {
+        // Lengths past 127 bytes take a multi-byte prefix
+        let ts_batch = format!("1704067800000#{}", "é".repeat(200));
+
+        let encoded = encode_readings_page_token("AA:BB:CC:DD:EE:FF", &ts_batch).unwrap();
+        let decoded = decode_readings_page_token(&encoded).unwrap();
+
+        assert_eq!(decoded.ts_batch, ts_batch);
+    }
+
+    #[test]
+    fn test_decode_legacy_unversioned_token_as_v1() {
+        // Tokens issued before the version byte were bare JSON
+        let legacy = general_purpose::STANDARD
+            .encode(br#"{"hardware_id":"AA:BB:CC:DD:EE:FF","ts_batch":"1704067800000#b"}"#);
+
+        let decoded = decode_readings_page_token(&legacy).unwrap();
+        assert_eq!(decoded.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(decoded.ts_batch, "1704067800000#b");
+    }
+
+    #[test]
+    fn test_page_token_version() {
+        let compact = encode_readings_page_token("AA:BB:CC:DD:EE:FF", "1704067800000#b").unwrap();
+        assert_eq!(page_token_version(&compact).unwrap(), CURSOR_VERSION);
+
+        let json = json_v1_token(&ReadingsPageToken {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            ts_batch: "1704067800000#b".to_string(),
+        });
+        assert_eq!(page_token_version(&json).unwrap(), JSON_V1);
+
+        let legacy = general_purpose::STANDARD.encode(br#"{"hardware_id":"AA:BB:CC:DD:EE:FF"}"#);
+        assert_eq!(page_token_version(&legacy).unwrap(), JSON_V1);
+
+        assert!(page_token_version("").is_err());
+        assert!(page_token_version("not-valid-base64!@#").is_err());
+    }
+
+    #[test]
+    fn test_decode_empty_page_token() {
+        assert!(matches!(
+            decode_device_page_token(""),
+            Err(CursorError::Invalid(_))
+        ));
+    }
 }
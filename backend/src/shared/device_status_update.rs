@@ -1,10 +1,29 @@
-﻿use crate::plant_insights::{DeviceStatus, ErrorRecord};
+use crate::plant_insights::{DeviceStatus, ErrorRecord, Reading};
+
+/// Length of the trailing window used for `coverage_pct_last_hour`
+pub const COVERAGE_WINDOW_MS: i64 = 3_600_000;
+
+/// Default `skew_warn_threshold_sec` (5 minutes)
+pub const DEFAULT_SKEW_WARN_THRESHOLD_SEC: i64 = 300;
+
+/// Whether an ingest/event skew is large enough to flag the device's clock
+///
+/// Skew is ingest time minus event time, so it is negative when the device
+/// clock runs ahead of the server; either direction counts. A skew exactly at
+/// the threshold does not warn.
+pub fn exceeds_skew_threshold(
+    ingest_event_skew_seconds: i64,
+    skew_warn_threshold_sec: i64,
+) -> bool {
+    ingest_event_skew_seconds.abs() > skew_warn_threshold_sec
+}
 
 pub enum DeviceStatusUpdate {
     StatusUpdater {
         last_seen_event_time_ms: i64,
         last_seen_ingest_time_ms: i64,
         ingest_event_skew_seconds: i64,
+        clock_skew_warning: bool,
         pipeline_lag_seconds: i64,
     },
     EventDetector {
@@ -31,11 +50,13 @@ pub fn apply_status_update(status: &mut DeviceStatus, update: DeviceStatusUpdate
             last_seen_event_time_ms,
             last_seen_ingest_time_ms,
             ingest_event_skew_seconds,
+            clock_skew_warning,
             pipeline_lag_seconds,
         } => {
             status.last_seen_event_time_ms = last_seen_event_time_ms;
             status.last_seen_ingest_time_ms = last_seen_ingest_time_ms;
             status.ingest_event_skew_seconds = ingest_event_skew_seconds;
+            status.clock_skew_warning = clock_skew_warning;
             status.pipeline_lag_seconds = pipeline_lag_seconds;
             status.updated_at_ms = now_ms;
         }
@@ -77,6 +98,86 @@ pub fn apply_status_update(status: &mut DeviceStatus, update: DeviceStatusUpdate
     }
 }
 
+/// Recompute the ingest-derived status fields after a new reading arrives
+///
+/// Sets skew (ingest time minus event time) and pipeline lag (now minus event
+/// time) in whole seconds, flags `clock_skew_warning` when the skew exceeds
+/// `skew_warn_threshold_sec` in either direction, advances the last-seen
+/// timestamps, and updates `coverage_pct_last_hour` as observed/expected
+/// readings in the trailing hour for `expected_interval_sec`, capped at 1.0.
+///
+/// Observed readings are not stored individually, so the previous count is
+/// recovered from the existing coverage and aged by the time elapsed since the
+/// last reading, assuming readings were spread evenly across the window. With
+/// a non-positive expected interval the existing coverage is kept.
+///
+/// A device is not penalized for time it was powered off: when a reading
+/// arrives with a new `boot_id`, the boot start is recorded and the expected
+/// count is capped by the uptime span (boot start to the reading) instead of
+/// the full hour, so the count restarts at one of one. When the boot start is
+/// unknown the full window is expected. A late reading from another boot does
+/// not count as a restart.
+pub fn recompute_status(
+    existing: &DeviceStatus,
+    reading: &Reading,
+    now_ms: i64,
+    skew_warn_threshold_sec: i64,
+) -> DeviceStatus {
+    let mut status = existing.clone();
+
+    status.ingest_event_skew_seconds = (reading.ingest_time_ms - reading.timestamp_ms) / 1000;
+    status.clock_skew_warning =
+        exceeds_skew_threshold(status.ingest_event_skew_seconds, skew_warn_threshold_sec);
+    status.pipeline_lag_seconds = (now_ms - reading.timestamp_ms) / 1000;
+
+    let rebooted = existing
+        .current_boot_id
+        .as_deref()
+        .is_some_and(|boot_id| boot_id != reading.boot_id)
+        && reading.timestamp_ms >= existing.last_seen_event_time_ms;
+    if rebooted {
+        status.current_boot_id = Some(reading.boot_id.clone());
+        status.boot_started_at_ms = Some(reading.timestamp_ms);
+    } else if existing.current_boot_id.is_none() {
+        status.current_boot_id = Some(reading.boot_id.clone());
+    }
+
+    if existing.expected_interval_sec > 0 {
+        let interval_ms = existing.expected_interval_sec * 1000;
+        // Readings expected over an uptime span, including the one at its start
+        let expected_over = |span_ms: i64| {
+            (span_ms.max(0) + interval_ms).min(COVERAGE_WINDOW_MS) as f64 / interval_ms as f64
+        };
+
+        status.coverage_pct_last_hour = if rebooted {
+            1.0
+        } else {
+            let uptime_to = |t: i64| {
+                existing
+                    .boot_started_at_ms
+                    .map_or(COVERAGE_WINDOW_MS, |boot_ms| t - boot_ms)
+            };
+            let prev_expected = expected_over(uptime_to(existing.last_seen_event_time_ms));
+            let prev_window_ms = prev_expected * interval_ms as f64;
+            let elapsed_ms = (reading.timestamp_ms - existing.last_seen_event_time_ms)
+                .clamp(0, COVERAGE_WINDOW_MS);
+            let dropped_ms =
+                (elapsed_ms as f64 + prev_window_ms - COVERAGE_WINDOW_MS as f64).max(0.0);
+            let retained = 1.0 - dropped_ms / prev_window_ms;
+            let observed = existing.coverage_pct_last_hour * prev_expected * retained + 1.0;
+            (observed / expected_over(uptime_to(reading.timestamp_ms))).min(1.0)
+        };
+    }
+
+    status.last_seen_event_time_ms = existing.last_seen_event_time_ms.max(reading.timestamp_ms);
+    status.last_seen_ingest_time_ms = existing
+        .last_seen_ingest_time_ms
+        .max(reading.ingest_time_ms);
+    status.updated_at_ms = now_ms;
+
+    status
+}
+
 pub fn truncate_error_message(message: &str) -> String {
     if message.len() <= 256 {
         message.to_string()
@@ -88,7 +189,11 @@ pub fn truncate_error_message(message: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::plant_insights::SensorStatusSummary;
+    use crate::plant_insights::{
+        ReadingSensorStatus, SensorStatus, SensorStatusSummary, SensorValues,
+    };
+
+    const SKEW_WARN: i64 = DEFAULT_SKEW_WARN_THRESHOLD_SEC;
 
     fn create_test_status() -> DeviceStatus {
         DeviceStatus {
@@ -98,8 +203,11 @@ mod tests {
             expected_interval_sec: 300,
             last_processed_event_time_ms: 1000,
             ingest_event_skew_seconds: 0,
+            clock_skew_warning: false,
             pipeline_lag_seconds: 0,
             coverage_pct_last_hour: 1.0,
+            current_boot_id: None,
+            boot_started_at_ms: None,
             sensor_status_summary: SensorStatusSummary::Ok,
             last_event_detected_at_ms: None,
             last_aggregate_computed_at_ms: None,
@@ -118,6 +226,7 @@ mod tests {
             last_seen_event_time_ms: 2000,
             last_seen_ingest_time_ms: 2010,
             ingest_event_skew_seconds: 10,
+            clock_skew_warning: false,
             pipeline_lag_seconds: 5,
         };
         apply_status_update(&mut status, update, 2020);
@@ -173,4 +282,215 @@ mod tests {
         let truncated = truncate_error_message(&message);
         assert_eq!(truncated.len(), 256);
     }
+
+    fn create_test_reading(timestamp_ms: i64, ingest_time_ms: i64) -> Reading {
+        Reading {
+            batch_id: "batch1".to_string(),
+            hardware_id: "device1".to_string(),
+            timestamp_ms,
+            ingest_time_ms,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: Some(22.5),
+                ds18b20_temp_c: Some(21.8),
+                humidity_pct: Some(45.0),
+                pressure_hpa: Some(1013.0),
+                soil_moisture_pct: Some(40.0),
+            },
+            sensor_status: ReadingSensorStatus {
+                bme280: SensorStatus::Ok,
+                ds18b20: SensorStatus::Ok,
+                soil_moisture: SensorStatus::Ok,
+            },
+            quality: None,
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn test_recompute_status_skew() {
+        let status = create_test_status();
+        let reading = create_test_reading(1_000_000, 1_045_500);
+
+        let updated = recompute_status(&status, &reading, 1_050_000, SKEW_WARN);
+        assert_eq!(updated.ingest_event_skew_seconds, 45);
+        assert_eq!(updated.last_seen_event_time_ms, 1_000_000);
+        assert_eq!(updated.last_seen_ingest_time_ms, 1_045_500);
+        assert_eq!(updated.updated_at_ms, 1_050_000);
+    }
+
+    #[test]
+    fn test_recompute_status_lag() {
+        let status = create_test_status();
+        let reading = create_test_reading(1_000_000, 1_002_000);
+
+        let updated = recompute_status(&status, &reading, 1_120_000, SKEW_WARN);
+        assert_eq!(updated.pipeline_lag_seconds, 120);
+        // Unrelated fields are carried over
+        assert_eq!(updated.hardware_id, status.hardware_id);
+        assert_eq!(updated.expected_interval_sec, 300);
+    }
+
+    #[test]
+    fn test_recompute_status_out_of_order_keeps_last_seen() {
+        let mut status = create_test_status();
+        status.last_seen_event_time_ms = 2_000_000;
+        status.last_seen_ingest_time_ms = 2_000_000;
+        let reading = create_test_reading(1_500_000, 1_900_000);
+
+        let updated = recompute_status(&status, &reading, 2_100_000, SKEW_WARN);
+        assert_eq!(updated.last_seen_event_time_ms, 2_000_000);
+        assert_eq!(updated.last_seen_ingest_time_ms, 2_000_000);
+    }
+
+    #[test]
+    fn test_recompute_status_coverage_with_known_interval() {
+        // 300 second interval: 12 readings expected per hour
+        let mut status = create_test_status();
+        let mut t = status.last_seen_event_time_ms;
+
+        // Steady readings keep full coverage
+        for _ in 0..3 {
+            t += 300_000;
+            status = recompute_status(&status, &create_test_reading(t, t), t, SKEW_WARN);
+            assert!((status.coverage_pct_last_hour - 1.0).abs() < 1e-9);
+        }
+
+        // A 30 minute gap loses half the window: 6 retained + 1 new
+        t += 1_800_000;
+        status = recompute_status(&status, &create_test_reading(t, t), t, SKEW_WARN);
+        assert!((status.coverage_pct_last_hour - 7.0 / 12.0).abs() < 1e-9);
+
+        // A gap longer than the window leaves only the new reading
+        t += 7_200_000;
+        status = recompute_status(&status, &create_test_reading(t, t), t, SKEW_WARN);
+        assert!((status.coverage_pct_last_hour - 1.0 / 12.0).abs() < 1e-9);
+
+        // Readings back on schedule raise coverage again
+        let before = status.coverage_pct_last_hour;
+        t += 300_000;
+        status = recompute_status(&status, &create_test_reading(t, t), t, SKEW_WARN);
+        assert!(status.coverage_pct_last_hour > before);
+    }
+
+    #[test]
+    fn test_recompute_status_coverage_up_whole_hour() {
+        // Same boot since long before the window: the full hour is expected
+        let mut status = create_test_status();
+        let mut t = 10_000_000;
+        status.last_seen_event_time_ms = t;
+        status.current_boot_id = Some("7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string());
+        status.boot_started_at_ms = Some(t - 7_200_000);
+
+        t += 300_000;
+        status = recompute_status(&status, &create_test_reading(t, t), t, SKEW_WARN);
+        assert!((status.coverage_pct_last_hour - 1.0).abs() < 1e-9);
+
+        // A 30 minute gap while running still counts as missing readings
+        t += 1_800_000;
+        status = recompute_status(&status, &create_test_reading(t, t), t, SKEW_WARN);
+        assert!((status.coverage_pct_last_hour - 7.0 / 12.0).abs() < 1e-9);
+        assert_eq!(status.boot_started_at_ms, Some(10_000_000 - 7_200_000));
+    }
+
+    #[test]
+    fn test_recompute_status_coverage_scaled_after_reboot() {
+        let mut status = create_test_status();
+        status.current_boot_id = Some("previous-boot".to_string());
+        status.coverage_pct_last_hour = 0.25;
+
+        // Powered off for hours, then booted 20 minutes before the last reading
+        let boot_ms = 20_000_000;
+        status = recompute_status(
+            &status,
+            &create_test_reading(boot_ms, boot_ms),
+            boot_ms,
+            SKEW_WARN,
+        );
+        assert_eq!(status.boot_started_at_ms, Some(boot_ms));
+        assert_eq!(
+            status.current_boot_id.as_deref(),
+            Some("7c9e6679-7425-40de-944b-e07fc1f90ae7")
+        );
+        assert!((status.coverage_pct_last_hour - 1.0).abs() < 1e-9);
+
+        // Readings at +5, +15 and +20 minutes miss the one at +10: 4 of 5
+        let mut uptime = status.clone();
+        for minutes in [5, 15, 20] {
+            let t = boot_ms + minutes * 60_000;
+            uptime = recompute_status(&uptime, &create_test_reading(t, t), t, SKEW_WARN);
+        }
+        assert!((uptime.coverage_pct_last_hour - 4.0 / 5.0).abs() < 1e-9);
+
+        // Every reading since boot is full coverage, not 5 of 12
+        let mut complete = status;
+        for minutes in [5, 10, 15, 20] {
+            let t = boot_ms + minutes * 60_000;
+            complete = recompute_status(&complete, &create_test_reading(t, t), t, SKEW_WARN);
+        }
+        assert!((complete.coverage_pct_last_hour - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recompute_status_coverage_unknown_interval() {
+        let mut status = create_test_status();
+        status.expected_interval_sec = 0;
+        status.coverage_pct_last_hour = 0.5;
+
+        let updated = recompute_status(&status, &create_test_reading(2000, 2000), 2000, SKEW_WARN);
+        assert_eq!(updated.coverage_pct_last_hour, 0.5);
+    }
+
+    #[test]
+    fn test_recompute_status_skew_below_threshold_no_warning() {
+        let status = create_test_status();
+        // 240 seconds behind
+        let reading = create_test_reading(1_000_000, 1_240_000);
+
+        let updated = recompute_status(&status, &reading, 1_240_000, SKEW_WARN);
+        assert_eq!(updated.ingest_event_skew_seconds, 240);
+        assert!(!updated.clock_skew_warning);
+    }
+
+    #[test]
+    fn test_recompute_status_skew_above_threshold_warns() {
+        let status = create_test_status();
+        // 10 minutes behind
+        let reading = create_test_reading(1_000_000, 1_600_000);
+
+        let updated = recompute_status(&status, &reading, 1_600_000, SKEW_WARN);
+        assert_eq!(updated.ingest_event_skew_seconds, 600);
+        assert!(updated.clock_skew_warning);
+
+        // A later reading back within the threshold clears the warning
+        let reading = create_test_reading(1_700_000, 1_710_000);
+        let cleared = recompute_status(&updated, &reading, 1_710_000, SKEW_WARN);
+        assert!(!cleared.clock_skew_warning);
+    }
+
+    #[test]
+    fn test_recompute_status_negative_skew_uses_absolute_value() {
+        let status = create_test_status();
+        // Device clock 10 minutes ahead of the server
+        let reading = create_test_reading(1_600_000, 1_000_000);
+
+        let updated = recompute_status(&status, &reading, 1_000_000, SKEW_WARN);
+        assert_eq!(updated.ingest_event_skew_seconds, -600);
+        assert!(updated.clock_skew_warning);
+
+        // 4 minutes ahead stays below the threshold
+        let reading = create_test_reading(1_240_000, 1_000_000);
+        let updated = recompute_status(&status, &reading, 1_000_000, SKEW_WARN);
+        assert!(!updated.clock_skew_warning);
+    }
+
+    #[test]
+    fn test_exceeds_skew_threshold_boundary() {
+        assert!(!exceeds_skew_threshold(300, 300));
+        assert!(exceeds_skew_threshold(301, 300));
+        assert!(!exceeds_skew_threshold(-300, 300));
+        assert!(exceeds_skew_threshold(-301, 300));
+    }
 }
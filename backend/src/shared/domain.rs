@@ -1,5 +1,13 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::plant_insights::SensorCalibration;
+use crate::sensor_validation::{
+    HUMIDITY_MAX_PCT, HUMIDITY_MIN_PCT, SOIL_MOISTURE_MAX_PCT, SOIL_MOISTURE_MIN_PCT, TEMP_MAX_C,
+    TEMP_MIN_C,
+};
+use crate::time::Clock;
+use crate::validators::ValidationError;
 
 /// Device domain type representing a registered ESP32 device
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,15 +20,35 @@ pub struct Device {
     pub first_registered_at: String,
     pub last_seen_at: String,
     pub last_boot_id: String,
+    /// Epoch ms of the oldest stored reading (set by the first ingested batch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_reading_ms: Option<i64>,
+    /// Epoch ms of the newest stored reading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_reading_ms: Option<i64>,
+    /// Readings stored since the device registered with `last_boot_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readings_this_boot: Option<i64>,
 }
 
 /// Device capabilities including sensors and features
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Capabilities {
     pub sensors: Vec<String>,
+    /// Serialized in sorted key order so the same capabilities always produce the same JSON
+    #[serde(serialize_with = "serialize_sorted")]
     pub features: HashMap<String, bool>,
 }
 
+/// Serialize a map with its keys in ascending order
+fn serialize_sorted<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
 /// Sensor reading from a device
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reading {
@@ -32,6 +60,105 @@ pub struct Reading {
     pub friendly_name: Option<String>,
     pub sensors: SensorValues,
     pub sensor_status: SensorStatus,
+    /// True when the reading arrived well after it was taken (offline buffering).
+    /// Computed at ingest time; any value sent by the device is ignored.
+    #[serde(default)]
+    pub was_buffered: bool,
+    /// Optional device-reported quality score (0.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
+    /// Optional device-assigned sequence (0 to `MAX_READING_SEQ`) that orders
+    /// readings sharing a timestamp_ms; see `build_ts_batch`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u32>,
+    /// Batch-level context (e.g. gateway id) stamped from the request's
+    /// `batch_metadata` at ingest; any value sent per reading is ignored.
+    #[serde(default, skip_deserializing, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+/// Largest `seq` that fits the 5-digit zero-padded sort key segment
+pub const MAX_READING_SEQ: u32 = 99_999;
+
+/// Build the device_readings sort key for a reading
+///
+/// Format is "{timestamp_ms:013}#{batch_id}", or "{timestamp_ms:013}#{seq:05}#{batch_id}"
+/// when the device sent a `seq`. Zero-padding keeps keys in chronological (then
+/// sequence) order when sorted lexicographically.
+pub fn build_ts_batch(timestamp_ms: i64, seq: Option<u32>, batch_id: &str) -> String {
+    match seq {
+        Some(seq) => format!("{:013}#{:05}#{}", timestamp_ms, seq, batch_id),
+        None => format!("{:013}#{}", timestamp_ms, batch_id),
+    }
+}
+
+/// Number of partitions the fleet-wide ingest-time index is spread across
+///
+/// Every reading is written to one of these `gsi1pk` shards so fleet-wide
+/// ingest isn't capped by the write throughput of a single GSI partition.
+/// Changing it moves devices between shards; readings already stored keep
+/// their old shard.
+pub const READINGS_GSI1PK_SHARDS: u32 = 8;
+
+/// Shard of the fleet-wide ingest-time index a device's readings go to
+///
+/// FNV-1a over the hardware_id, so the shard is stable across processes and
+/// releases (unlike `std`'s hasher).
+pub fn readings_gsi1pk_shard(hardware_id: &str) -> u32 {
+    let hash = hardware_id.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    hash % READINGS_GSI1PK_SHARDS
+}
+
+/// `gsi1pk` value of one fleet-wide ingest-time index shard ("readings#{shard}")
+pub fn readings_gsi1pk(shard: u32) -> String {
+    format!("readings#{}", shard)
+}
+
+/// Stamp batch-level metadata onto every reading in the batch
+///
+/// Batch values overwrite any existing entry with the same key.
+pub fn apply_batch_metadata(readings: &mut [Reading], meta: &HashMap<String, String>) {
+    if meta.is_empty() {
+        return;
+    }
+
+    for reading in readings {
+        reading
+            .metadata
+            .extend(meta.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+/// Add a device's calibration offsets to a reading's sensor values
+///
+/// `temp_offset_c` applies to both temperature probes. Each corrected value is
+/// clamped back into its sensor's valid range, so an offset cannot turn a
+/// valid reading into an out-of-range one. Missing values stay missing.
+pub fn apply_calibration(reading: &mut Reading, calibration: &SensorCalibration) {
+    fn correct(value: &mut Option<f64>, offset: f64, min: f64, max: f64) {
+        if let Some(v) = value {
+            *v = (*v + offset).clamp(min, max);
+        }
+    }
+
+    let sensors = &mut reading.sensors;
+    let temp = calibration.temp_offset_c;
+    correct(&mut sensors.bme280_temp_c, temp, TEMP_MIN_C, TEMP_MAX_C);
+    correct(&mut sensors.ds18b20_temp_c, temp, TEMP_MIN_C, TEMP_MAX_C);
+    correct(
+        &mut sensors.humidity_pct,
+        calibration.humidity_offset_pct,
+        HUMIDITY_MIN_PCT,
+        HUMIDITY_MAX_PCT,
+    );
+    correct(
+        &mut sensors.soil_moisture_pct,
+        calibration.soil_moisture_offset_pct,
+        SOIL_MOISTURE_MIN_PCT,
+        SOIL_MOISTURE_MAX_PCT,
+    );
 }
 
 /// Sensor values from various sensors
@@ -47,6 +174,10 @@ pub struct SensorValues {
     pub pressure_hpa: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub soil_moisture_pct: Option<f64>,
+    /// Values from sensors without a typed field (e.g. `sht31_temp_c`),
+    /// flattened so they sit alongside the typed keys
+    #[serde(flatten)]
+    pub extra_sensors: HashMap<String, f64>,
 }
 
 /// Status of each sensor (ok or error)
@@ -55,6 +186,47 @@ pub struct SensorStatus {
     pub bme280: String,
     pub ds18b20: String,
     pub soil_moisture: String,
+    /// Statuses of sensors without a typed field (e.g. `sht31`), flattened
+    #[serde(flatten)]
+    pub extra_status: HashMap<String, String>,
+}
+
+/// Sensor status value that means the sensor read successfully
+pub const SENSOR_STATUS_OK: &str = "ok";
+
+/// Sensor status value that means the sensor failed to read
+pub const SENSOR_STATUS_ERROR: &str = "error";
+
+/// Overall sensor health of a reading, derived from its per-sensor statuses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensorStatusSummary {
+    /// Every sensor reported ok
+    Ok,
+    /// Some, but not all, sensors reported ok
+    Degraded,
+    /// No sensor reported ok
+    Missing,
+}
+
+impl SensorStatus {
+    /// Summarize the typed and extra sensor statuses into one value
+    pub fn summary(&self) -> SensorStatusSummary {
+        let statuses: Vec<&String> = [&self.bme280, &self.ds18b20, &self.soil_moisture]
+            .into_iter()
+            .chain(self.extra_status.values())
+            .collect();
+        let ok_count = statuses
+            .iter()
+            .filter(|status| status.as_str() == SENSOR_STATUS_OK)
+            .count();
+
+        match ok_count {
+            0 => SensorStatusSummary::Missing,
+            n if n == statuses.len() => SensorStatusSummary::Ok,
+            _ => SensorStatusSummary::Degraded,
+        }
+    }
 }
 
 /// API Key domain type
@@ -66,4 +238,516 @@ pub struct ApiKey {
     pub last_used_at: Option<String>,
     pub is_active: bool,
     pub description: Option<String>,
+    /// Scopes granted to the key (e.g. "data:write"); empty for legacy keys
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Ingest requests allowed per minute (0 is unlimited); `None` uses the deployment-wide limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+impl ApiKey {
+    /// Whether the key has been granted the given scope
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Operator note attached to a device at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub hardware_id: String,
+    /// Epoch ms the note refers to (sort key)
+    pub timestamp_ms: i64,
+    pub text: String,
+}
+
+/// Builder for `Device` records
+///
+/// `hardware_id`, `confirmation_id`, `firmware_version` and `capabilities` are
+/// required. Timestamps default to the injected clock's current time, so a
+/// freshly built device has `first_registered_at == last_seen_at`.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceBuilder {
+    hardware_id: Option<String>,
+    confirmation_id: Option<String>,
+    friendly_name: Option<String>,
+    firmware_version: Option<String>,
+    capabilities: Option<Capabilities>,
+    first_registered_at: Option<String>,
+    last_seen_at: Option<String>,
+    last_boot_id: String,
+}
+
+impl DeviceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn hardware_id(mut self, hardware_id: impl Into<String>) -> Self {
+        self.hardware_id = Some(hardware_id.into());
+        self
+    }
+
+    pub fn confirmation_id(mut self, confirmation_id: impl Into<String>) -> Self {
+        self.confirmation_id = Some(confirmation_id.into());
+        self
+    }
+
+    pub fn friendly_name(mut self, friendly_name: Option<String>) -> Self {
+        self.friendly_name = friendly_name;
+        self
+    }
+
+    pub fn firmware_version(mut self, firmware_version: impl Into<String>) -> Self {
+        self.firmware_version = Some(firmware_version.into());
+        self
+    }
+
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    pub fn last_boot_id(mut self, last_boot_id: impl Into<String>) -> Self {
+        self.last_boot_id = last_boot_id.into();
+        self
+    }
+
+    /// Override the registration timestamp (defaults to the clock's current time)
+    pub fn first_registered_at(mut self, first_registered_at: impl Into<String>) -> Self {
+        self.first_registered_at = Some(first_registered_at.into());
+        self
+    }
+
+    /// Override the last-seen timestamp (defaults to the clock's current time)
+    pub fn last_seen_at(mut self, last_seen_at: impl Into<String>) -> Self {
+        self.last_seen_at = Some(last_seen_at.into());
+        self
+    }
+
+    /// Build the device, returning a `ValidationError` naming the first missing required field
+    pub fn build(self, clock: &dyn Clock) -> Result<Device, ValidationError> {
+        let hardware_id = require(self.hardware_id, "hardware_id")?;
+        let confirmation_id = require(self.confirmation_id, "confirmation_id")?;
+        let firmware_version = require(self.firmware_version, "firmware_version")?;
+        let capabilities = require(self.capabilities, "capabilities")?;
+
+        let now = clock.now_rfc3339();
+
+        Ok(Device {
+            hardware_id,
+            confirmation_id,
+            friendly_name: self.friendly_name,
+            firmware_version,
+            capabilities,
+            first_registered_at: self.first_registered_at.unwrap_or_else(|| now.clone()),
+            last_seen_at: self.last_seen_at.unwrap_or(now),
+            last_boot_id: self.last_boot_id,
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: Some(0),
+        })
+    }
+}
+
+impl Device {
+    /// Start building a new device
+    pub fn builder() -> DeviceBuilder {
+        DeviceBuilder::new()
+    }
+}
+
+fn require<T>(value: Option<T>, field: &str) -> Result<T, ValidationError> {
+    value.ok_or_else(|| ValidationError::new(field, "is required"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::FixedClock;
+
+    fn test_capabilities() -> Capabilities {
+        let mut features = HashMap::new();
+        features.insert("tft_display".to_string(), true);
+        Capabilities {
+            sensors: vec!["bme280".to_string()],
+            features,
+        }
+    }
+
+    fn complete_builder() -> DeviceBuilder {
+        Device::builder()
+            .hardware_id("AA:BB:CC:DD:EE:FF")
+            .confirmation_id("550e8400-e29b-41d4-a716-446655440000")
+            .firmware_version("1.0.16")
+            .capabilities(test_capabilities())
+    }
+
+    #[test]
+    fn test_capabilities_features_serialize_sorted() {
+        let features: HashMap<String, bool> = [
+            "tft_display",
+            "offline_buffering",
+            "ota_updates",
+            "battery_monitor",
+            "deep_sleep",
+        ]
+        .iter()
+        .map(|f| (f.to_string(), true))
+        .collect();
+        let capabilities = Capabilities {
+            sensors: vec!["bme280".to_string()],
+            features,
+        };
+
+        let first = serde_json::to_string(&capabilities).unwrap();
+        let second = serde_json::to_string(&capabilities.clone()).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(
+            first,
+            r#"{"sensors":["bme280"],"features":{"battery_monitor":true,"deep_sleep":true,"offline_buffering":true,"ota_updates":true,"tft_display":true}}"#
+        );
+
+        // Round-trips through the unchanged HashMap field
+        let parsed: Capabilities = serde_json::from_str(&first).unwrap();
+        assert_eq!(parsed.features, capabilities.features);
+    }
+
+    #[test]
+    fn test_device_builder_defaults_timestamps_from_clock() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+
+        let device = complete_builder()
+            .last_boot_id("7c9e6679-7425-40de-944b-e07fc1f90ae7")
+            .build(&clock)
+            .unwrap();
+
+        assert_eq!(device.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(device.friendly_name, None);
+        assert_eq!(device.first_registered_at, clock.now_rfc3339());
+        assert_eq!(device.first_registered_at, device.last_seen_at);
+        assert_eq!(device.last_boot_id, "7c9e6679-7425-40de-944b-e07fc1f90ae7");
+    }
+
+    #[test]
+    fn test_device_builder_overrides() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+
+        let device = complete_builder()
+            .friendly_name(Some("greenhouse-1".to_string()))
+            .first_registered_at("2024-01-01T00:00:00Z")
+            .build(&clock)
+            .unwrap();
+
+        assert_eq!(device.friendly_name.as_deref(), Some("greenhouse-1"));
+        assert_eq!(device.first_registered_at, "2024-01-01T00:00:00Z");
+        assert_eq!(device.last_seen_at, clock.now_rfc3339());
+    }
+
+    #[test]
+    fn test_device_builder_missing_required_field() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00Z").unwrap();
+
+        let err = Device::builder()
+            .hardware_id("AA:BB:CC:DD:EE:FF")
+            .firmware_version("1.0.16")
+            .capabilities(test_capabilities())
+            .build(&clock)
+            .unwrap_err();
+        assert_eq!(err.field, "confirmation_id");
+
+        let err = Device::builder()
+            .hardware_id("AA:BB:CC:DD:EE:FF")
+            .confirmation_id("550e8400-e29b-41d4-a716-446655440000")
+            .firmware_version("1.0.16")
+            .build(&clock)
+            .unwrap_err();
+        assert_eq!(err.field, "capabilities");
+
+        let err = DeviceBuilder::new().build(&clock).unwrap_err();
+        assert_eq!(err.field, "hardware_id");
+    }
+
+    fn test_reading(batch_id: &str) -> Reading {
+        Reading {
+            batch_id: batch_id.to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms: 1704067800000,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: Some(22.5),
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: None,
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: SensorStatus {
+                bme280: "ok".to_string(),
+                ds18b20: "ok".to_string(),
+                soil_moisture: "ok".to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn sensor_status(bme280: &str, ds18b20: &str, soil_moisture: &str) -> SensorStatus {
+        SensorStatus {
+            bme280: bme280.to_string(),
+            ds18b20: ds18b20.to_string(),
+            soil_moisture: soil_moisture.to_string(),
+            extra_status: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_sensor_status_summary_all_ok() {
+        assert_eq!(
+            sensor_status("ok", "ok", "ok").summary(),
+            SensorStatusSummary::Ok
+        );
+    }
+
+    #[test]
+    fn test_sensor_status_summary_mixed() {
+        assert_eq!(
+            sensor_status("ok", "error", "ok").summary(),
+            SensorStatusSummary::Degraded
+        );
+        assert_eq!(
+            sensor_status("error", "error", "ok").summary(),
+            SensorStatusSummary::Degraded
+        );
+    }
+
+    #[test]
+    fn test_sensor_status_summary_all_error() {
+        let summary = sensor_status("error", "error", "error").summary();
+        assert_eq!(summary, SensorStatusSummary::Missing);
+        assert_eq!(serde_json::to_string(&summary).unwrap(), "\"missing\"");
+        assert_eq!(
+            serde_json::to_string(&SensorStatusSummary::Degraded).unwrap(),
+            "\"degraded\""
+        );
+    }
+
+    #[test]
+    fn test_sensor_status_summary_counts_extra_status() {
+        let mut status = sensor_status("ok", "ok", "ok");
+        status
+            .extra_status
+            .insert("sht31".to_string(), "error".to_string());
+        assert_eq!(status.summary(), SensorStatusSummary::Degraded);
+
+        status
+            .extra_status
+            .insert("sht31".to_string(), "ok".to_string());
+        assert_eq!(status.summary(), SensorStatusSummary::Ok);
+    }
+
+    #[test]
+    fn test_reading_extra_sensors_round_trip() {
+        let json = r#"{
+            "batch_id": "batch_1",
+            "hardware_id": "AA:BB:CC:DD:EE:FF",
+            "timestamp_ms": 1704067800000,
+            "boot_id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+            "firmware_version": "1.0.16",
+            "friendly_name": null,
+            "sensors": {"soil_moisture_pct": 41.0, "sht31_temp_c": 21.4},
+            "sensor_status": {
+                "bme280": "missing",
+                "ds18b20": "missing",
+                "soil_moisture": "ok",
+                "sht31": "ok"
+            }
+        }"#;
+
+        let reading: Reading = serde_json::from_str(json).unwrap();
+        assert_eq!(reading.sensors.soil_moisture_pct, Some(41.0));
+        assert_eq!(
+            reading.sensors.extra_sensors.get("sht31_temp_c"),
+            Some(&21.4)
+        );
+        assert!(!reading
+            .sensors
+            .extra_sensors
+            .contains_key("soil_moisture_pct"));
+        assert_eq!(
+            reading
+                .sensor_status
+                .extra_status
+                .get("sht31")
+                .map(String::as_str),
+            Some("ok")
+        );
+
+        let value = serde_json::to_value(&reading).unwrap();
+        assert_eq!(value["sensors"]["sht31_temp_c"], 21.4);
+        assert_eq!(value["sensor_status"]["sht31"], "ok");
+
+        let round_trip: Reading = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            round_trip.sensors.extra_sensors,
+            reading.sensors.extra_sensors
+        );
+        assert_eq!(
+            round_trip.sensor_status.extra_status,
+            reading.sensor_status.extra_status
+        );
+    }
+
+    #[test]
+    fn test_readings_gsi1pk_shard_is_stable_and_in_range() {
+        assert_eq!(
+            readings_gsi1pk_shard("AA:BB:CC:DD:EE:FF"),
+            readings_gsi1pk_shard("AA:BB:CC:DD:EE:FF")
+        );
+
+        let shards: std::collections::HashSet<u32> = (0..64)
+            .map(|i| readings_gsi1pk_shard(&format!("AA:BB:CC:DD:EE:{:02X}", i)))
+            .collect();
+        assert!(shards.iter().all(|shard| *shard < READINGS_GSI1PK_SHARDS));
+        // 64 devices spread over more than one shard
+        assert!(shards.len() > 1);
+
+        assert_eq!(readings_gsi1pk(3), "readings#3");
+    }
+
+    #[test]
+    fn test_build_ts_batch() {
+        assert_eq!(
+            build_ts_batch(1704067800000, None, "batch_1"),
+            "1704067800000#batch_1"
+        );
+        assert_eq!(build_ts_batch(42, None, "b"), "0000000000042#b");
+    }
+
+    #[test]
+    fn test_build_ts_batch_with_seq() {
+        assert_eq!(
+            build_ts_batch(1704067800000, Some(7), "batch_1"),
+            "1704067800000#00007#batch_1"
+        );
+        assert_eq!(
+            build_ts_batch(42, Some(MAX_READING_SEQ), "b"),
+            "0000000000042#99999#b"
+        );
+    }
+
+    #[test]
+    fn test_build_ts_batch_seq_orders_within_millisecond() {
+        // Batch ids sort opposite to seq, so only seq can explain the order
+        let mut keys = vec![
+            build_ts_batch(1704067800001, Some(0), "a"),
+            build_ts_batch(1704067800000, Some(10), "a"),
+            build_ts_batch(1704067800000, Some(2), "c"),
+            build_ts_batch(1704067800000, Some(1), "z"),
+        ];
+        keys.sort();
+
+        assert_eq!(
+            keys,
+            vec![
+                "1704067800000#00001#z",
+                "1704067800000#00002#c",
+                "1704067800000#00010#a",
+                "1704067800001#00000#a",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_batch_metadata_stamps_every_reading() {
+        let mut readings = vec![test_reading("batch1"), test_reading("batch2")];
+        readings[1]
+            .metadata
+            .insert("gateway_id".to_string(), "stale".to_string());
+
+        let mut meta = HashMap::new();
+        meta.insert("gateway_id".to_string(), "gw-01".to_string());
+        meta.insert("rssi".to_string(), "-67".to_string());
+
+        apply_batch_metadata(&mut readings, &meta);
+
+        for reading in &readings {
+            assert_eq!(reading.metadata, meta);
+        }
+    }
+
+    #[test]
+    fn test_apply_batch_metadata_empty_is_noop() {
+        let mut readings = vec![test_reading("batch1")];
+        apply_batch_metadata(&mut readings, &HashMap::new());
+
+        assert!(readings[0].metadata.is_empty());
+        let json = serde_json::to_string(&readings[0]).unwrap();
+        assert!(!json.contains("metadata"));
+    }
+
+    #[test]
+    fn test_apply_calibration_shifts_values() {
+        let mut reading = test_reading("batch1");
+        reading.sensors.soil_moisture_pct = Some(45.0);
+        let calibration = SensorCalibration {
+            temp_offset_c: -0.5,
+            humidity_offset_pct: 3.0,
+            soil_moisture_offset_pct: -5.0,
+        };
+
+        apply_calibration(&mut reading, &calibration);
+
+        assert_eq!(reading.sensors.bme280_temp_c, Some(22.0));
+        assert_eq!(reading.sensors.soil_moisture_pct, Some(40.0));
+        // Missing values are not created by an offset
+        assert_eq!(reading.sensors.ds18b20_temp_c, None);
+        assert_eq!(reading.sensors.humidity_pct, None);
+    }
+
+    #[test]
+    fn test_apply_calibration_clamps_to_valid_range() {
+        let mut reading = test_reading("batch1");
+        reading.sensors.bme280_temp_c = Some(84.0);
+        reading.sensors.humidity_pct = Some(98.0);
+        reading.sensors.soil_moisture_pct = Some(2.0);
+        let calibration = SensorCalibration {
+            temp_offset_c: 3.0,
+            humidity_offset_pct: 5.0,
+            soil_moisture_offset_pct: -5.0,
+        };
+
+        apply_calibration(&mut reading, &calibration);
+
+        assert_eq!(reading.sensors.bme280_temp_c, Some(TEMP_MAX_C));
+        assert_eq!(reading.sensors.humidity_pct, Some(HUMIDITY_MAX_PCT));
+        assert_eq!(
+            reading.sensors.soil_moisture_pct,
+            Some(SOIL_MOISTURE_MIN_PCT)
+        );
+    }
+
+    #[test]
+    fn test_reading_metadata_not_deserialized_from_input() {
+        let json = r#"{
+            "batch_id": "batch1",
+            "hardware_id": "AA:BB:CC:DD:EE:FF",
+            "timestamp_ms": 1704067800000,
+            "boot_id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+            "firmware_version": "1.0.16",
+            "friendly_name": null,
+            "sensors": {},
+            "sensor_status": {"bme280": "ok", "ds18b20": "ok", "soil_moisture": "ok"},
+            "metadata": {"gateway_id": "spoofed"}
+        }"#;
+
+        let reading: Reading = serde_json::from_str(json).unwrap();
+        assert!(reading.metadata.is_empty());
+    }
 }
@@ -12,6 +12,17 @@ pub struct ErrorResponse {
 
     /// Request ID for tracing and debugging
     pub request_id: String,
+
+    /// Every field problem, when validation found more than one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+/// One field problem within a multi-error validation response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
 }
 
 impl ErrorResponse {
@@ -25,9 +36,16 @@ impl ErrorResponse {
             error: error.into(),
             message: message.into(),
             request_id: request_id.into(),
+            errors: Vec::new(),
         }
     }
 
+    /// Attach the individual field problems behind this error
+    pub fn with_errors(mut self, errors: Vec<FieldError>) -> Self {
+        self.errors = errors;
+        self
+    }
+
     /// Convert to JSON string
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
@@ -44,6 +62,7 @@ pub mod error_codes {
     // Authentication errors
     pub const MISSING_API_KEY: &str = "MISSING_API_KEY";
     pub const INVALID_API_KEY: &str = "INVALID_API_KEY";
+    pub const MALFORMED_API_KEY: &str = "MALFORMED_API_KEY";
     pub const KEY_REVOKED: &str = "KEY_REVOKED";
     pub const MISSING_TOKEN: &str = "MISSING_TOKEN";
     pub const INVALID_TOKEN: &str = "INVALID_TOKEN";
@@ -58,12 +77,23 @@ pub mod error_codes {
     pub const INVALID_TIMESTAMP: &str = "INVALID_TIMESTAMP";
     pub const INVALID_BATCH_ID: &str = "INVALID_BATCH_ID";
     pub const BATCH_SIZE_EXCEEDED: &str = "BATCH_SIZE_EXCEEDED";
+    pub const VALIDATION_FAILED: &str = "VALIDATION_FAILED";
 
     // Not found errors
     pub const DEVICE_NOT_FOUND: &str = "DEVICE_NOT_FOUND";
     pub const NO_READINGS: &str = "NO_READINGS";
+    pub const READING_NOT_FOUND: &str = "READING_NOT_FOUND";
     pub const API_KEY_NOT_FOUND: &str = "API_KEY_NOT_FOUND";
 
+    // Precondition errors
+    pub const PRECONDITION_FAILED: &str = "PRECONDITION_FAILED";
+
+    // Conflict errors
+    pub const IDEMPOTENCY_CONFLICT: &str = "IDEMPOTENCY_CONFLICT";
+
+    // Rate limiting errors
+    pub const RATE_LIMITED: &str = "RATE_LIMITED";
+
     // Database errors
     pub const DATABASE_ERROR: &str = "DATABASE_ERROR";
 
@@ -107,6 +137,32 @@ mod tests {
         assert_eq!(deserialized.request_id, error.request_id);
     }
 
+    #[test]
+    fn test_error_response_with_errors() {
+        let error = ErrorResponse::new("VALIDATION_FAILED", "2 fields are invalid", "req-789");
+        let json = serde_json::to_value(&error).unwrap();
+        assert!(json.get("errors").is_none());
+
+        let error = error.with_errors(vec![
+            FieldError {
+                field: "hardware_id".to_string(),
+                message: "bad MAC".to_string(),
+            },
+            FieldError {
+                field: "batch_id".to_string(),
+                message: "empty".to_string(),
+            },
+        ]);
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            json["errors"],
+            serde_json::json!([
+                {"field": "hardware_id", "message": "bad MAC"},
+                {"field": "batch_id", "message": "empty"}
+            ])
+        );
+    }
+
     #[test]
     fn test_error_codes_constants() {
         assert_eq!(error_codes::MISSING_API_KEY, "MISSING_API_KEY");
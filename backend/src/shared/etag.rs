@@ -0,0 +1,97 @@
+use sha2::{Digest, Sha256};
+
+/// Number of hex characters of the SHA-256 digest kept in an ETag
+const ETAG_HASH_LEN: usize = 16;
+
+/// Compute a weak ETag (`W/"<hash>"`) from the fields that define a resource's state
+///
+/// Parts are length-prefixed before hashing so that `["ab", "c"]` and
+/// `["a", "bc"]` produce different tags.
+pub fn weak_etag(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part.as_bytes());
+    }
+    let digest = hex::encode(hasher.finalize());
+    format!("W/\"{}\"", &digest[..ETAG_HASH_LEN])
+}
+
+/// Check an `If-None-Match` header value against the current ETag
+///
+/// Uses weak comparison (the `W/` prefix is ignored on both sides), accepts a
+/// comma-separated list of tags, and treats `*` as matching any current tag.
+pub fn if_none_match_matches(if_none_match: &str, etag: &str) -> bool {
+    let current = strip_weak_prefix(etag.trim());
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || strip_weak_prefix(candidate) == current)
+}
+
+/// Check an `If-Match` header value against the current ETag
+///
+/// Resource ETags are weak, so this uses the same weak comparison, list
+/// handling and `*` wildcard as [`if_none_match_matches`].
+pub fn if_match_matches(if_match: &str, etag: &str) -> bool {
+    if_none_match_matches(if_match, etag)
+}
+
+fn strip_weak_prefix(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weak_etag_format() {
+        let etag = weak_etag(&["2024-01-15T10:30:00Z", "1.0.16"]);
+        assert!(etag.starts_with("W/\""));
+        assert!(etag.ends_with('"'));
+        assert_eq!(etag.len(), ETAG_HASH_LEN + 4);
+    }
+
+    #[test]
+    fn test_weak_etag_deterministic() {
+        let a = weak_etag(&["2024-01-15T10:30:00Z", "1.0.16"]);
+        let b = weak_etag(&["2024-01-15T10:30:00Z", "1.0.16"]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_weak_etag_changes_with_parts() {
+        let a = weak_etag(&["2024-01-15T10:30:00Z", "1.0.16"]);
+        let b = weak_etag(&["2024-01-15T10:35:00Z", "1.0.16"]);
+        assert_ne!(a, b);
+
+        // Part boundaries are significant
+        assert_ne!(weak_etag(&["ab", "c"]), weak_etag(&["a", "bc"]));
+    }
+
+    #[test]
+    fn test_if_none_match_matches() {
+        let etag = weak_etag(&["x"]);
+        let strong = etag.trim_start_matches("W/").to_string();
+
+        assert!(if_none_match_matches(&etag, &etag));
+        assert!(if_none_match_matches(&strong, &etag));
+        assert!(if_none_match_matches(
+            &format!("\"other\", {}", etag),
+            &etag
+        ));
+        assert!(if_none_match_matches("*", &etag));
+        assert!(!if_none_match_matches("W/\"other\"", &etag));
+        assert!(!if_none_match_matches("", &etag));
+    }
+
+    #[test]
+    fn test_if_match_matches() {
+        let etag = weak_etag(&["x"]);
+
+        assert!(if_match_matches(&etag, &etag));
+        assert!(if_match_matches("*", &etag));
+        assert!(!if_match_matches(&weak_etag(&["y"]), &etag));
+    }
+}
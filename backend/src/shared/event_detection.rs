@@ -0,0 +1,621 @@
+use std::collections::HashMap;
+
+use crate::plant_insights::{DeviceProfile, Event, EventType, Reading, SensorStats, SensorStatus};
+
+const HOUR_MS: f64 = 60.0 * 60.0 * 1000.0;
+const MINUTE_MS: f64 = 60.0 * 1000.0;
+
+/// Thresholds for `detect_drying_cycles_with`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DryingCycleThresholds {
+    /// Smallest soil moisture drop (percentage points) that counts as a cycle
+    pub min_drop_pct: f64,
+    /// Shortest decline, first to last reading, that counts as a cycle
+    pub min_duration_ms: i64,
+    /// Rise above the lowest value so far that is still treated as noise
+    pub rise_tolerance_pct: f64,
+}
+
+impl Default for DryingCycleThresholds {
+    fn default() -> Self {
+        DryingCycleThresholds {
+            min_drop_pct: 10.0,
+            min_duration_ms: 2 * 60 * 60 * 1000,
+            rise_tolerance_pct: 1.0,
+        }
+    }
+}
+
+/// Detect drying cycles with the default thresholds
+///
+/// See `detect_drying_cycles_with`.
+pub fn detect_drying_cycles(readings: &[Reading], profile: &DeviceProfile) -> Vec<Event> {
+    detect_drying_cycles_with(readings, profile, &DryingCycleThresholds::default())
+}
+
+/// Detect sustained declines in soil moisture
+///
+/// Readings whose soil moisture sensor is not `ok` are skipped; the rest are
+/// walked in timestamp order. A decline continues until a value rises more
+/// than `rise_tolerance_pct` above the lowest value seen in it, and ends at
+/// that lowest value. It becomes an event when it drops at least
+/// `min_drop_pct` over at least `min_duration_ms`.
+///
+/// When the profile has a moisture range (the learned `baseline_moisture_range`,
+/// or its soil type's default), moisture below its `min` does not count
+/// toward the drop, so wobble around the dry baseline is not
+/// reported as drying. Each event's `created_at_ms` is the end of the decline.
+pub fn detect_drying_cycles_with(
+    readings: &[Reading],
+    profile: &DeviceProfile,
+    thresholds: &DryingCycleThresholds,
+) -> Vec<Event> {
+    let mut moisture: Vec<(i64, f64)> = readings
+        .iter()
+        .filter(|r| r.sensor_status.soil_moisture == SensorStatus::Ok)
+        .filter_map(|r| {
+            r.sensors
+                .soil_moisture_pct
+                .filter(|v| v.is_finite())
+                .map(|v| (r.timestamp_ms, v))
+        })
+        .collect();
+    moisture.sort_by_key(|(ts, _)| *ts);
+
+    let floor = profile
+        .effective_moisture_range()
+        .map_or(f64::NEG_INFINITY, |range| range.min);
+
+    let mut events = Vec::new();
+    let mut start = 0;
+    let mut low = 0;
+    for i in 1..moisture.len() {
+        if moisture[i].1 > moisture[low].1 + thresholds.rise_tolerance_pct {
+            events.extend(drying_event(
+                &profile.hardware_id,
+                moisture[start],
+                moisture[low],
+                floor,
+                thresholds,
+            ));
+            start = i;
+            low = i;
+        } else if moisture[i].1 <= moisture[low].1 {
+            low = i;
+        }
+    }
+    if !moisture.is_empty() {
+        events.extend(drying_event(
+            &profile.hardware_id,
+            moisture[start],
+            moisture[low],
+            floor,
+            thresholds,
+        ));
+    }
+
+    events
+}
+
+/// Build a drying event for a decline from `start` to `end` if it qualifies
+fn drying_event(
+    hardware_id: &str,
+    (start_ms, start_pct): (i64, f64),
+    (end_ms, end_pct): (i64, f64),
+    floor: f64,
+    thresholds: &DryingCycleThresholds,
+) -> Option<Event> {
+    let duration_ms = end_ms - start_ms;
+    let drop_pct = start_pct - end_pct.max(floor);
+    if drop_pct < thresholds.min_drop_pct || duration_ms < thresholds.min_duration_ms {
+        return None;
+    }
+
+    let rate_pct_per_hour = (start_pct - end_pct) / (duration_ms as f64 / HOUR_MS);
+
+    Some(Event {
+        hardware_id: hardware_id.to_string(),
+        start_time_ms: start_ms,
+        end_time_ms: end_ms,
+        event_type: EventType::DryingCycle,
+        sensor_values: HashMap::from([("soil_moisture_pct".to_string(), end_pct)]),
+        detection_metadata: HashMap::from([
+            (
+                "start_moisture_pct".to_string(),
+                format!("{:.2}", start_pct),
+            ),
+            ("end_moisture_pct".to_string(), format!("{:.2}", end_pct)),
+            (
+                "drying_rate_pct_per_hour".to_string(),
+                format!("{:.2}", rate_pct_per_hour),
+            ),
+        ]),
+        created_at_ms: end_ms,
+    })
+}
+
+/// Thresholds for `detect_humidity_anomalies_with`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HumidityAnomalyThresholds {
+    /// Number of preceding readings the mean and stddev are computed over
+    pub window_size: usize,
+    /// Fewest preceding readings needed before a value can be flagged
+    pub min_window_size: usize,
+    /// Distance from the window mean, in stddevs, that counts as an anomaly
+    pub z_threshold: f64,
+}
+
+impl Default for HumidityAnomalyThresholds {
+    fn default() -> Self {
+        HumidityAnomalyThresholds {
+            window_size: 12,
+            min_window_size: 3,
+            z_threshold: 3.0,
+        }
+    }
+}
+
+/// Detect humidity anomalies with the default thresholds
+///
+/// See `detect_humidity_anomalies_with`.
+pub fn detect_humidity_anomalies(readings: &[Reading], profile: &DeviceProfile) -> Vec<Event> {
+    detect_humidity_anomalies_with(readings, profile, &HumidityAnomalyThresholds::default())
+}
+
+/// Detect sudden humidity jumps and drops
+///
+/// Readings whose bme280 sensor (the humidity source) is not `ok` are
+/// skipped; the rest are walked in timestamp order. Each value is compared
+/// with the mean and stddev of up to `window_size` values before it, and
+/// flagged when its z-score reaches `z_threshold`. Nothing is flagged until
+/// `min_window_size` values precede it, or while the window's stddev is zero,
+/// since no z-score can be computed.
+pub fn detect_humidity_anomalies_with(
+    readings: &[Reading],
+    profile: &DeviceProfile,
+    thresholds: &HumidityAnomalyThresholds,
+) -> Vec<Event> {
+    let mut humidity: Vec<(i64, f64)> = readings
+        .iter()
+        .filter(|r| r.sensor_status.bme280 == SensorStatus::Ok)
+        .filter_map(|r| {
+            r.sensors
+                .humidity_pct
+                .filter(|v| v.is_finite())
+                .map(|v| (r.timestamp_ms, v))
+        })
+        .collect();
+    humidity.sort_by_key(|(ts, _)| *ts);
+
+    let mut events = Vec::new();
+    for (i, &(timestamp_ms, value)) in humidity.iter().enumerate() {
+        let window = &humidity[i.saturating_sub(thresholds.window_size)..i];
+        if window.len() < thresholds.min_window_size.max(2) {
+            continue;
+        }
+
+        let mut stats = SensorStats::default();
+        for (_, v) in window {
+            stats.record(Some(*v));
+        }
+        if stats.stddev <= 0.0 {
+            continue;
+        }
+
+        let z_score = (value - stats.avg) / stats.stddev;
+        if z_score.abs() < thresholds.z_threshold {
+            continue;
+        }
+
+        events.push(Event {
+            hardware_id: profile.hardware_id.clone(),
+            start_time_ms: timestamp_ms,
+            end_time_ms: timestamp_ms,
+            event_type: EventType::HumidityAnomaly,
+            sensor_values: HashMap::from([("humidity_pct".to_string(), value)]),
+            detection_metadata: HashMap::from([
+                ("humidity_pct".to_string(), format!("{:.2}", value)),
+                ("window_mean_pct".to_string(), format!("{:.2}", stats.avg)),
+                (
+                    "window_stddev_pct".to_string(),
+                    format!("{:.2}", stats.stddev),
+                ),
+                ("z_score".to_string(), format!("{:.2}", z_score)),
+                ("window_size".to_string(), window.len().to_string()),
+            ]),
+            created_at_ms: timestamp_ms,
+        });
+    }
+
+    events
+}
+
+/// Thresholds for `detect_environmental_changes_with`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvironmentalChangeThresholds {
+    /// Longest span, first to last reading, compared in one window
+    pub window_ms: i64,
+    /// Shortest span a window must cover, so two close readings are not a trend
+    pub min_span_ms: i64,
+    /// Temperature change (bme280, degrees C per minute) that counts as rapid
+    pub temp_c_per_minute: f64,
+    /// Pressure change (hPa per minute) that counts as rapid
+    pub pressure_hpa_per_minute: f64,
+}
+
+impl Default for EnvironmentalChangeThresholds {
+    fn default() -> Self {
+        EnvironmentalChangeThresholds {
+            window_ms: 30 * 60 * 1000,
+            min_span_ms: 10 * 60 * 1000,
+            temp_c_per_minute: 0.1,
+            pressure_hpa_per_minute: 0.05,
+        }
+    }
+}
+
+/// Detect environmental changes with the default thresholds
+///
+/// See `detect_environmental_changes_with`.
+pub fn detect_environmental_changes(readings: &[Reading]) -> Vec<Event> {
+    detect_environmental_changes_with(readings, &EnvironmentalChangeThresholds::default())
+}
+
+/// Detect coincident rapid shifts in temperature and pressure, such as a front passing
+///
+/// Readings are walked in timestamp order. Each one starts a window reaching
+/// up to `window_ms` ahead; the window is only considered when it spans at
+/// least `min_span_ms` and every reading in it has an `ok` bme280 with both
+/// a temperature and a pressure value. An event is emitted when the change
+/// from the first to the last reading exceeds both per-minute thresholds,
+/// and the next window starts after it, so one shift yields one event.
+pub fn detect_environmental_changes_with(
+    readings: &[Reading],
+    thresholds: &EnvironmentalChangeThresholds,
+) -> Vec<Event> {
+    let mut sorted: Vec<&Reading> = readings.iter().collect();
+    sorted.sort_by_key(|r| r.timestamp_ms);
+
+    let mut events = Vec::new();
+    let mut start = 0;
+    while start < sorted.len() {
+        let first = sorted[start];
+        let end = sorted[start..]
+            .iter()
+            .rposition(|r| r.timestamp_ms - first.timestamp_ms <= thresholds.window_ms)
+            .map_or(start, |offset| start + offset);
+
+        match environmental_change_event(&sorted[start..=end], thresholds) {
+            Some(event) => {
+                events.push(event);
+                start = end + 1;
+            }
+            None => start += 1,
+        }
+    }
+
+    events
+}
+
+/// Build an environmental change event for one window if both shifts qualify
+fn environmental_change_event(
+    window: &[&Reading],
+    thresholds: &EnvironmentalChangeThresholds,
+) -> Option<Event> {
+    let (first, last) = (window.first()?, window.last()?);
+    let span_ms = last.timestamp_ms - first.timestamp_ms;
+    if span_ms < thresholds.min_span_ms.max(1) {
+        return None;
+    }
+
+    let values: Vec<(f64, f64)> = window
+        .iter()
+        .map(|r| {
+            if r.sensor_status.bme280 != SensorStatus::Ok {
+                return None;
+            }
+            let temp = r.sensors.bme280_temp_c.filter(|v| v.is_finite())?;
+            let pressure = r.sensors.pressure_hpa.filter(|v| v.is_finite())?;
+            Some((temp, pressure))
+        })
+        .collect::<Option<_>>()?;
+
+    let (first_temp, first_pressure) = values[0];
+    let (last_temp, last_pressure) = values[values.len() - 1];
+    let temp_delta = last_temp - first_temp;
+    let pressure_delta = last_pressure - first_pressure;
+
+    let minutes = span_ms as f64 / MINUTE_MS;
+    let temp_rate = temp_delta / minutes;
+    let pressure_rate = pressure_delta / minutes;
+    if temp_rate.abs() < thresholds.temp_c_per_minute
+        || pressure_rate.abs() < thresholds.pressure_hpa_per_minute
+    {
+        return None;
+    }
+
+    Some(Event {
+        hardware_id: first.hardware_id.clone(),
+        start_time_ms: first.timestamp_ms,
+        end_time_ms: last.timestamp_ms,
+        event_type: EventType::EnvironmentalChange,
+        sensor_values: HashMap::from([
+            ("temperature_delta_c".to_string(), temp_delta),
+            ("pressure_delta_hpa".to_string(), pressure_delta),
+        ]),
+        detection_metadata: HashMap::from([
+            (
+                "temperature_rate_c_per_minute".to_string(),
+                format!("{:.3}", temp_rate),
+            ),
+            (
+                "pressure_rate_hpa_per_minute".to_string(),
+                format!("{:.3}", pressure_rate),
+            ),
+            ("window_minutes".to_string(), format!("{:.1}", minutes)),
+        ]),
+        created_at_ms: last.timestamp_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plant_insights::{MoistureRange, ReadingSensorStatus, SensorValues};
+
+    const HOUR: i64 = 60 * 60 * 1000;
+    const QUARTER_HOUR: i64 = HOUR / 4;
+
+    fn reading(timestamp_ms: i64, moisture: f64) -> Reading {
+        Reading {
+            batch_id: format!("batch-{}", timestamp_ms),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms,
+            ingest_time_ms: timestamp_ms,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: None,
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: Some(moisture),
+            },
+            sensor_status: ReadingSensorStatus {
+                bme280: SensorStatus::Ok,
+                ds18b20: SensorStatus::Ok,
+                soil_moisture: SensorStatus::Ok,
+            },
+            quality: None,
+            ttl: None,
+        }
+    }
+
+    fn humidity_reading(timestamp_ms: i64, humidity: f64, status: SensorStatus) -> Reading {
+        let mut reading = reading(timestamp_ms, 40.0);
+        reading.sensors.humidity_pct = Some(humidity);
+        reading.sensor_status.bme280 = status;
+        reading
+    }
+
+    /// Humidity readings every 15 minutes with a healthy bme280
+    fn humidity_series(values: &[f64]) -> Vec<Reading> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| humidity_reading(i as i64 * QUARTER_HOUR, *v, SensorStatus::Ok))
+            .collect()
+    }
+
+    fn environment_reading(timestamp_ms: i64, temp_c: f64, pressure_hpa: f64) -> Reading {
+        let mut reading = reading(timestamp_ms, 40.0);
+        reading.sensors.bme280_temp_c = Some(temp_c);
+        reading.sensors.pressure_hpa = Some(pressure_hpa);
+        reading
+    }
+
+    /// Readings every 5 minutes with the given (temperature, pressure) pairs
+    fn environment_series(values: &[(f64, f64)]) -> Vec<Reading> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, (temp, pressure))| {
+                environment_reading(i as i64 * 5 * 60_000, *temp, *pressure)
+            })
+            .collect()
+    }
+
+    fn profile() -> DeviceProfile {
+        DeviceProfile {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            plant_type: None,
+            soil_type: None,
+            pot_size_liters: None,
+            expected_interval_sec: 900,
+            baseline_moisture_range: None,
+            typical_watering_interval_sec: None,
+            last_watering_events: None,
+            temp_offset_c: None,
+            humidity_offset_pct: None,
+            soil_moisture_offset_pct: None,
+            updated_at_ms: 0,
+        }
+    }
+
+    /// Readings every 15 minutes with the given moisture values
+    fn series(values: &[f64]) -> Vec<Reading> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| reading(i as i64 * QUARTER_HOUR, *v))
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_drying_cycles_clean_slope() {
+        // 60% down to 40% over 4 hours
+        let values: Vec<f64> = (0..=16).map(|i| 60.0 - 1.25 * i as f64).collect();
+
+        let events = detect_drying_cycles(&series(&values), &profile());
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.event_type, EventType::DryingCycle);
+        assert_eq!(event.start_time_ms, 0);
+        assert_eq!(event.end_time_ms, 4 * HOUR);
+        assert_eq!(event.detection_metadata["start_moisture_pct"], "60.00");
+        assert_eq!(event.detection_metadata["end_moisture_pct"], "40.00");
+        assert_eq!(event.detection_metadata["drying_rate_pct_per_hour"], "5.00");
+    }
+
+    #[test]
+    fn test_detect_drying_cycles_noisy_decline() {
+        // Declining overall with small bumps inside the rise tolerance
+        let values = [
+            60.0, 59.0, 59.5, 57.0, 56.2, 56.8, 54.0, 53.0, 53.6, 51.0, 50.5, 49.0, 49.7,
+        ];
+
+        let events = detect_drying_cycles(&series(&values), &profile());
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start_time_ms, 0);
+        assert_eq!(events[0].end_time_ms, 11 * QUARTER_HOUR);
+        assert_eq!(events[0].detection_metadata["end_moisture_pct"], "49.00");
+    }
+
+    #[test]
+    fn test_detect_drying_cycles_rising_curve() {
+        let values: Vec<f64> = (0..=16).map(|i| 30.0 + 2.0 * i as f64).collect();
+
+        assert!(detect_drying_cycles(&series(&values), &profile()).is_empty());
+    }
+
+    #[test]
+    fn test_detect_drying_cycles_short_or_shallow_decline() {
+        // 15% drop in one hour is too fast to count
+        let fast: Vec<f64> = (0..=4).map(|i| 60.0 - 3.75 * i as f64).collect();
+        assert!(detect_drying_cycles(&series(&fast), &profile()).is_empty());
+
+        // 4 hours but only 5% down
+        let shallow: Vec<f64> = (0..=16).map(|i| 50.0 - 0.3125 * i as f64).collect();
+        assert!(detect_drying_cycles(&series(&shallow), &profile()).is_empty());
+    }
+
+    #[test]
+    fn test_detect_drying_cycles_ignores_decline_below_baseline() {
+        // 12% drop over 4 hours, but only 4% of it is above the dry baseline
+        let values: Vec<f64> = (0..=16).map(|i| 24.0 - 0.75 * i as f64).collect();
+        let mut profile = profile();
+        profile.baseline_moisture_range = Some(MoistureRange {
+            min: 20.0,
+            max: 60.0,
+        });
+
+        assert!(detect_drying_cycles(&series(&values), &profile).is_empty());
+    }
+
+    #[test]
+    fn test_detect_humidity_anomalies_clear_spike() {
+        let values = [
+            50.0, 51.0, 50.5, 49.5, 50.0, 51.0, 50.5, 49.5, 50.0, 51.0, 80.0, 50.0,
+        ];
+
+        let events = detect_humidity_anomalies(&humidity_series(&values), &profile());
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.event_type, EventType::HumidityAnomaly);
+        assert_eq!(event.start_time_ms, 10 * QUARTER_HOUR);
+        assert_eq!(event.sensor_values["humidity_pct"], 80.0);
+        assert_eq!(event.detection_metadata["humidity_pct"], "80.00");
+        assert_eq!(event.detection_metadata["window_mean_pct"], "50.30");
+        assert_eq!(event.detection_metadata["window_size"], "10");
+        let z_score: f64 = event.detection_metadata["z_score"].parse().unwrap();
+        assert!(z_score > 3.0);
+    }
+
+    #[test]
+    fn test_detect_humidity_anomalies_stable_humidity() {
+        let values = [
+            50.0, 51.0, 50.5, 49.5, 50.0, 51.0, 50.5, 49.5, 50.0, 51.0, 50.2, 49.8,
+        ];
+
+        assert!(detect_humidity_anomalies(&humidity_series(&values), &profile()).is_empty());
+    }
+
+    #[test]
+    fn test_detect_humidity_anomalies_window_too_small() {
+        // Two prior values are too few for a stddev; a flat window has none
+        assert!(
+            detect_humidity_anomalies(&humidity_series(&[50.0, 51.0, 90.0]), &profile()).is_empty()
+        );
+        assert!(
+            detect_humidity_anomalies(&humidity_series(&[50.0, 50.0, 50.0, 90.0]), &profile())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_detect_humidity_anomalies_skips_unhealthy_bme280() {
+        let mut readings = humidity_series(&[50.0, 51.0, 50.5, 49.5, 50.0, 51.0]);
+        readings.push(humidity_reading(
+            6 * QUARTER_HOUR,
+            95.0,
+            SensorStatus::OutOfRange,
+        ));
+
+        assert!(detect_humidity_anomalies(&readings, &profile()).is_empty());
+    }
+
+    /// Steady conditions, a 30 minute shift, then steady conditions again
+    fn shift(temp_step: f64, pressure_step: f64) -> Vec<(f64, f64)> {
+        (0..=18)
+            .map(|i| {
+                let step = (i as i64 - 6).clamp(0, 6) as f64;
+                (20.0 - temp_step * step, 1013.0 - pressure_step * step)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_environmental_changes_combined_shift() {
+        // -3 C and -1.8 hPa over 30 minutes
+        let events = detect_environmental_changes(&environment_series(&shift(0.5, 0.3)));
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.event_type, EventType::EnvironmentalChange);
+        assert_eq!(event.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(event.start_time_ms, 30 * 60_000);
+        assert_eq!(event.end_time_ms, 60 * 60_000);
+        assert!((event.sensor_values["temperature_delta_c"] + 3.0).abs() < 1e-9);
+        assert!((event.sensor_values["pressure_delta_hpa"] + 1.8).abs() < 1e-9);
+        assert_eq!(
+            event.detection_metadata["temperature_rate_c_per_minute"],
+            "-0.100"
+        );
+        assert_eq!(
+            event.detection_metadata["pressure_rate_hpa_per_minute"],
+            "-0.060"
+        );
+    }
+
+    #[test]
+    fn test_detect_environmental_changes_temperature_only() {
+        assert!(detect_environmental_changes(&environment_series(&shift(0.5, 0.0))).is_empty());
+    }
+
+    #[test]
+    fn test_detect_environmental_changes_pressure_only() {
+        assert!(detect_environmental_changes(&environment_series(&shift(0.0, 0.3))).is_empty());
+    }
+
+    #[test]
+    fn test_detect_environmental_changes_requires_healthy_bme280() {
+        let mut readings = environment_series(&shift(0.5, 0.3));
+        readings[9].sensor_status.bme280 = SensorStatus::Noisy;
+
+        assert!(detect_environmental_changes(&readings).is_empty());
+    }
+}
@@ -1,13 +1,24 @@
 // Declare modules at the root level
+pub mod api_key_hash;
+pub mod best_effort;
+pub mod concurrency;
 pub mod cursor;
 pub mod device_status_update;
 pub mod domain;
 pub mod error;
+pub mod etag;
+pub mod event_detection;
 pub mod id_generator;
 pub mod idempotency;
+pub mod observability;
 pub mod plant_insights;
+pub mod profile_learning;
+pub mod query_params;
 pub mod sensor_validation;
+pub mod status_transition;
 pub mod time;
+pub mod trend;
+pub mod units;
 pub mod validators;
 
 // Test utilities module (available in test and integration test builds)
@@ -16,26 +27,48 @@ pub mod test_utils;
 
 // Re-export everything under a shared namespace for external access
 pub mod shared {
+    pub use super::api_key_hash;
+    pub use super::best_effort;
+    pub use super::concurrency;
     pub use super::cursor;
     pub use super::device_status_update;
     pub use super::domain;
     pub use super::error;
+    pub use super::etag;
+    pub use super::event_detection;
     pub use super::id_generator;
     pub use super::idempotency;
+    pub use super::observability;
     pub use super::plant_insights;
+    pub use super::profile_learning;
+    pub use super::query_params;
     pub use super::sensor_validation;
+    pub use super::status_transition;
     pub use super::time;
+    pub use super::trend;
+    pub use super::units;
     pub use super::validators;
 }
 
 // Also re-export at root for convenience
+pub use api_key_hash::*;
+pub use best_effort::*;
+pub use concurrency::*;
 pub use cursor::*;
 pub use device_status_update::*;
 pub use domain::*;
 pub use error::*;
+pub use etag::*;
+pub use event_detection::*;
 pub use id_generator::*;
 pub use idempotency::*;
+pub use observability::*;
 pub use plant_insights::*;
+pub use profile_learning::*;
+pub use query_params::*;
 pub use sensor_validation::*;
+pub use status_transition::*;
 pub use time::*;
+pub use trend::*;
+pub use units::*;
 pub use validators::*;
@@ -0,0 +1,170 @@
+use lambda_http::{Body, Response};
+use tracing::{field, info_span, Span};
+
+/// Create the span that wraps one API request
+///
+/// Every handler runs inside this span, so its logs and those of the repo
+/// calls it makes carry `request_id`, `route` and, for device routes,
+/// `hardware_id`. `route` is the method and route template (for example
+/// `GET /devices/{hardware_id}/readings`), not the raw path. `status_code`
+/// and `error_code` start empty and are filled in by `record_response` once
+/// the response is built; a handler that learns the device only from the
+/// request body fills `hardware_id` with `record_hardware_id`.
+pub fn make_request_span(request_id: &str, route: &str, hardware_id: Option<&str>) -> Span {
+    let span = info_span!(
+        "request",
+        request_id = %request_id,
+        route = %route,
+        hardware_id = field::Empty,
+        status_code = field::Empty,
+        error_code = field::Empty,
+    );
+
+    if let Some(hardware_id) = hardware_id {
+        span.record("hardware_id", hardware_id);
+    }
+
+    span
+}
+
+/// Record the device a request is for on the current request span
+pub fn record_hardware_id(hardware_id: &str) {
+    Span::current().record("hardware_id", hardware_id);
+}
+
+/// Record a response's status code, and its error code when it failed, on the request span
+pub fn record_response(span: &Span, response: &Response<Body>) {
+    let status = response.status();
+    span.record("status_code", status.as_u16());
+
+    if status.is_client_error() || status.is_server_error() {
+        if let Some(error_code) = response_error_code(response.body()) {
+            span.record("error_code", error_code.as_str());
+        }
+    }
+}
+
+/// The `error` field of a JSON error body, if there is one
+fn response_error_code(body: &Body) -> Option<String> {
+    let bytes: &[u8] = match body {
+        Body::Text(text) => text.as_bytes(),
+        Body::Binary(bytes) => bytes.as_slice(),
+        Body::Empty => return None,
+    };
+
+    serde_json::from_slice::<serde_json::Value>(bytes)
+        .ok()?
+        .get("error")?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    /// Layer that keeps every span field recorded while it is installed
+    #[derive(Clone, Default)]
+    struct FieldCapture(Arc<Mutex<HashMap<String, String>>>);
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+
+        fn record_str(&mut self, field: &field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for FieldCapture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            attrs.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+        }
+
+        fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            values.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+        }
+    }
+
+    fn capture(f: impl FnOnce()) -> HashMap<String, String> {
+        let layer = FieldCapture::default();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+        tracing::subscriber::with_default(subscriber, f);
+        let fields = layer.0.lock().unwrap();
+        fields.clone()
+    }
+
+    fn response(status: u16, body: &str) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_request_span_records_request_fields() {
+        let fields = capture(|| {
+            let span = make_request_span(
+                "req-123",
+                "GET /devices/{hardware_id}/readings",
+                Some("AA:BB:CC:DD:EE:FF"),
+            );
+            record_response(&span, &response(200, r#"{"readings":[]}"#));
+        });
+
+        assert_eq!(fields["request_id"], "req-123");
+        assert_eq!(fields["route"], "GET /devices/{hardware_id}/readings");
+        assert_eq!(fields["hardware_id"], "AA:BB:CC:DD:EE:FF");
+        assert_eq!(fields["status_code"], "200");
+        assert!(!fields.contains_key("error_code"));
+    }
+
+    #[test]
+    fn test_request_span_records_error_code() {
+        let fields = capture(|| {
+            let span = make_request_span("req-456", "GET /devices", None);
+            record_response(
+                &span,
+                &response(
+                    401,
+                    r#"{"error":"INVALID_TOKEN","message":"Bearer token is invalid","request_id":"req-456"}"#,
+                ),
+            );
+        });
+
+        assert_eq!(fields["status_code"], "401");
+        assert_eq!(fields["error_code"], "INVALID_TOKEN");
+        assert!(!fields.contains_key("hardware_id"));
+    }
+
+    #[test]
+    fn test_record_hardware_id_fills_current_span() {
+        let fields = capture(|| {
+            let span = make_request_span("req-789", "POST /data", None);
+            span.in_scope(|| record_hardware_id("AA:BB:CC:DD:EE:FF"));
+        });
+
+        assert_eq!(fields["hardware_id"], "AA:BB:CC:DD:EE:FF");
+    }
+
+    #[test]
+    fn test_response_error_code_non_json_body() {
+        assert_eq!(response_error_code(&Body::Empty), None);
+        assert_eq!(response_error_code(&Body::from("not json")), None);
+        assert_eq!(
+            response_error_code(&Body::from(r#"{"error":"NOT_FOUND"}"#)),
+            Some("NOT_FOUND".to_string())
+        );
+    }
+}
@@ -17,6 +17,9 @@ pub struct Reading {
     pub friendly_name: Option<String>,
     pub sensors: SensorValues,
     pub sensor_status: ReadingSensorStatus,
+    /// Device-reported quality score (0.0 to 1.0), weighting the reading in aggregates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ttl: Option<i64>,
 }
@@ -108,6 +111,18 @@ impl EventType {
             EventType::EnvironmentalChange => "Environmental_Change",
         }
     }
+
+    /// Parse an event type from its serialized name (e.g. "Watering_Event")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Watering_Event" => Some(EventType::WateringEvent),
+            "Drying_Cycle" => Some(EventType::DryingCycle),
+            "Temperature_Stress" => Some(EventType::TemperatureStress),
+            "Humidity_Anomaly" => Some(EventType::HumidityAnomaly),
+            "Environmental_Change" => Some(EventType::EnvironmentalChange),
+            _ => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -153,10 +168,38 @@ impl WindowType {
             WindowType::Weekly => "weekly",
         }
     }
+
+    /// Length of one window in milliseconds
+    pub fn duration_ms(&self) -> i64 {
+        match self {
+            WindowType::Hourly => 3_600_000,
+            WindowType::Daily => 86_400_000,
+            WindowType::Weekly => 604_800_000,
+        }
+    }
+
+    /// Window containing `timestamp_ms` as `(start_ms, end_ms)`, end exclusive
+    ///
+    /// Windows are aligned in UTC: hours on the hour, days at midnight, and
+    /// weeks at Monday 00:00.
+    pub fn window_bounds(&self, timestamp_ms: i64) -> (i64, i64) {
+        let duration = self.duration_ms();
+        // The epoch fell on a Thursday; shift so weeks start on Monday
+        let offset = match self {
+            WindowType::Weekly => EPOCH_TO_MONDAY_MS,
+            WindowType::Hourly | WindowType::Daily => 0,
+        };
+
+        let start = (timestamp_ms + offset).div_euclid(duration) * duration - offset;
+        (start, start + duration)
+    }
 }
 
+/// Milliseconds from Monday 1969-12-29 00:00 UTC to the epoch
+const EPOCH_TO_MONDAY_MS: i64 = 3 * 86_400_000;
+
 /// Sensor statistics with accumulators for incremental updates
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct SensorStats {
     pub min: f64,
     pub max: f64,
@@ -167,6 +210,125 @@ pub struct SensorStats {
     // Accumulators for incremental computation
     pub sum: f64,
     pub sumsq: f64,
+    /// Total quality weight of the valid values; `None` on stats built before
+    /// values were weighted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight_sum: Option<f64>,
+    /// Sum of each valid value times its quality weight
+    #[serde(default)]
+    pub weighted_sum: f64,
+}
+
+impl SensorStats {
+    /// Feed one sample into the accumulators at `DEFAULT_QUALITY_WEIGHT`
+    pub fn record(&mut self, value: Option<f64>) {
+        self.record_weighted(value, DEFAULT_QUALITY_WEIGHT);
+    }
+
+    /// Feed one sample into the accumulators, weighting it by `weight`
+    ///
+    /// Every call counts toward `total_count`; only finite values count toward
+    /// `valid_count` and the derived min/max/avg/stddev. `avg` is the mean
+    /// weighted by each value's weight (see `weighted_mean`); it falls back to
+    /// the plain mean while no value carries weight. `stddev` is the
+    /// unweighted population stddev, matching the aggregator.
+    pub fn record_weighted(&mut self, value: Option<f64>, weight: f64) {
+        self.total_count += 1;
+
+        // Values recorded before weighting existed count at the default weight
+        if self.weight_sum.is_none() {
+            self.weight_sum = Some(self.valid_count as f64 * DEFAULT_QUALITY_WEIGHT);
+            self.weighted_sum = self.sum * DEFAULT_QUALITY_WEIGHT;
+        }
+
+        let value = match value {
+            Some(v) if v.is_finite() => v,
+            _ => return,
+        };
+
+        if self.valid_count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.valid_count += 1;
+        self.sum += value;
+        self.sumsq += value * value;
+
+        let mut weight_sum = self.weight_sum.unwrap_or_default();
+        if weight.is_finite() && weight > 0.0 {
+            weight_sum += weight;
+            self.weighted_sum += value * weight;
+        }
+        self.weight_sum = Some(weight_sum);
+
+        let n = self.valid_count as f64;
+        let mean = self.sum / n;
+        self.avg = if weight_sum > 0.0 {
+            self.weighted_sum / weight_sum
+        } else {
+            mean
+        };
+        self.stddev = (self.sumsq / n - mean * mean).max(0.0).sqrt();
+    }
+}
+
+/// Percentiles of an ascending-sorted slice using linear interpolation
+///
+/// Each entry of `ps` is a percentile in 0..=100 (clamped) and yields one
+/// value, interpolating between the two nearest ranks. Returns an empty vec
+/// when `sorted` is empty.
+pub fn compute_percentiles(sorted: &[f64], ps: &[f64]) -> Vec<f64> {
+    if sorted.is_empty() {
+        return Vec::new();
+    }
+
+    let last = (sorted.len() - 1) as f64;
+    ps.iter()
+        .map(|p| {
+            let rank = p.clamp(0.0, 100.0) / 100.0 * last;
+            let lo = rank.floor() as usize;
+            let hi = rank.ceil() as usize;
+            sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+        })
+        .collect()
+}
+
+/// Weight applied to readings that carry no quality score
+pub const DEFAULT_QUALITY_WEIGHT: f64 = 1.0;
+
+/// Aggregate weight of a reading with the given quality score
+///
+/// Scores are clamped to 0.0..=1.0; a missing or non-finite score gets
+/// `DEFAULT_QUALITY_WEIGHT`.
+pub fn quality_weight(quality: Option<f64>) -> f64 {
+    quality
+        .filter(|q| q.is_finite())
+        .map(|q| q.clamp(0.0, 1.0))
+        .unwrap_or(DEFAULT_QUALITY_WEIGHT)
+}
+
+/// Mean of `(value, weight)` pairs, weighting each value by its weight
+///
+/// Used to weight aggregate values by reading quality. Pairs with a
+/// non-finite value or a non-positive/non-finite weight are skipped.
+/// Returns `None` when no pair contributes any weight.
+pub fn weighted_mean(values: &[(f64, f64)]) -> Option<f64> {
+    let (weighted_sum, total_weight) = values
+        .iter()
+        .filter(|(value, weight)| value.is_finite() && weight.is_finite() && *weight > 0.0)
+        .fold((0.0, 0.0), |(sum, total), (value, weight)| {
+            (sum + value * weight, total + weight)
+        });
+
+    if total_weight > 0.0 {
+        Some(weighted_sum / total_weight)
+    } else {
+        None
+    }
 }
 
 // ============================================================================
@@ -206,6 +368,16 @@ pub enum ConfidenceLevel {
     High,
 }
 
+impl ConfidenceLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfidenceLevel::Low => "low",
+            ConfidenceLevel::Medium => "medium",
+            ConfidenceLevel::High => "high",
+        }
+    }
+}
+
 /// Trend classification
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -215,6 +387,16 @@ pub enum Trend {
     Stable,
 }
 
+impl Trend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Trend::Improving => "improving",
+            Trend::Declining => "declining",
+            Trend::Stable => "stable",
+        }
+    }
+}
+
 /// Urgency level for recommendations
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -224,6 +406,16 @@ pub enum Urgency {
     High,
 }
 
+impl Urgency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Urgency::Low => "low",
+            Urgency::Medium => "medium",
+            Urgency::High => "high",
+        }
+    }
+}
+
 /// Evidence references for insight generation
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Evidence {
@@ -253,9 +445,57 @@ pub struct DeviceProfile {
     pub typical_watering_interval_sec: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_watering_events: Option<Vec<i64>>,
+    /// Added to both temperature probes at ingestion (°C)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_offset_c: Option<f64>,
+    /// Added to humidity_pct at ingestion (percentage points)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub humidity_offset_pct: Option<f64>,
+    /// Added to soil_moisture_pct at ingestion (percentage points)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soil_moisture_offset_pct: Option<f64>,
     pub updated_at_ms: i64,
 }
 
+impl DeviceProfile {
+    /// Moisture range to compare readings against
+    ///
+    /// The learned `baseline_moisture_range` wins; without one, the soil
+    /// type's default range is used, and `None` means neither is known.
+    pub fn effective_moisture_range(&self) -> Option<MoistureRange> {
+        self.baseline_moisture_range
+            .clone()
+            .or_else(|| self.soil_type.map(|soil| soil.default_moisture_range()))
+    }
+
+    /// Calibration offsets to apply at ingestion, with unset offsets as 0
+    pub fn calibration(&self) -> SensorCalibration {
+        SensorCalibration {
+            temp_offset_c: self.temp_offset_c.unwrap_or(0.0),
+            humidity_offset_pct: self.humidity_offset_pct.unwrap_or(0.0),
+            soil_moisture_offset_pct: self.soil_moisture_offset_pct.unwrap_or(0.0),
+        }
+    }
+}
+
+/// Per-device offsets added to sensor values at ingestion, for probes with a
+/// known bias (e.g. a soil moisture probe reading 5% high has an offset of -5)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SensorCalibration {
+    pub temp_offset_c: f64,
+    pub humidity_offset_pct: f64,
+    pub soil_moisture_offset_pct: f64,
+}
+
+impl SensorCalibration {
+    /// Whether every offset is 0, so applying it changes nothing
+    pub fn is_zero(&self) -> bool {
+        self.temp_offset_c == 0.0
+            && self.humidity_offset_pct == 0.0
+            && self.soil_moisture_offset_pct == 0.0
+    }
+}
+
 /// Soil type enum
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -267,6 +507,34 @@ pub enum SoilType {
     Hydroponic,
 }
 
+impl SoilType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SoilType::PottingMix => "potting_mix",
+            SoilType::CocoCoir => "coco_coir",
+            SoilType::Peat => "peat",
+            SoilType::Soil => "soil",
+            SoilType::Hydroponic => "hydroponic",
+        }
+    }
+
+    /// Typical soil moisture range (percent) for this medium, used until a
+    /// baseline has been learned from readings
+    ///
+    /// Coco coir drains fast and sits drier than potting mix, peat holds
+    /// water longer, and a hydroponic medium stays near saturation.
+    pub fn default_moisture_range(&self) -> MoistureRange {
+        let (min, max) = match self {
+            SoilType::PottingMix => (30.0, 60.0),
+            SoilType::CocoCoir => (25.0, 50.0),
+            SoilType::Peat => (40.0, 70.0),
+            SoilType::Soil => (20.0, 45.0),
+            SoilType::Hydroponic => (80.0, 100.0),
+        };
+        MoistureRange { min, max }
+    }
+}
+
 /// Moisture range for baseline learning
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MoistureRange {
@@ -287,8 +555,18 @@ pub struct DeviceStatus {
     pub expected_interval_sec: i64,
     pub last_processed_event_time_ms: i64,
     pub ingest_event_skew_seconds: i64,
+    /// True when `ingest_event_skew_seconds` exceeded the skew warning threshold
+    #[serde(default)]
+    pub clock_skew_warning: bool,
     pub pipeline_lag_seconds: i64,
     pub coverage_pct_last_hour: f64,
+    /// boot_id of the most recent reading, used to spot restarts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_boot_id: Option<String>,
+    /// Event time of the first reading seen from `current_boot_id`; None when
+    /// the boot was already running when tracking started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boot_started_at_ms: Option<i64>,
     pub sensor_status_summary: SensorStatusSummary,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_event_detected_at_ms: Option<i64>,
@@ -313,6 +591,16 @@ pub enum SensorStatusSummary {
     Missing,
 }
 
+impl SensorStatusSummary {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SensorStatusSummary::Ok => "ok",
+            SensorStatusSummary::Degraded => "degraded",
+            SensorStatusSummary::Missing => "missing",
+        }
+    }
+}
+
 /// Health category derived from device status
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -323,6 +611,27 @@ pub enum HealthCategory {
     Failing,
 }
 
+impl HealthCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthCategory::Healthy => "healthy",
+            HealthCategory::Stale => "stale",
+            HealthCategory::Missing => "missing",
+            HealthCategory::Failing => "failing",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "healthy" => Some(HealthCategory::Healthy),
+            "stale" => Some(HealthCategory::Stale),
+            "missing" => Some(HealthCategory::Missing),
+            "failing" => Some(HealthCategory::Failing),
+            _ => None,
+        }
+    }
+}
+
 /// Error record for device status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ErrorRecord {
@@ -392,6 +701,23 @@ pub enum RequestType {
     Event,
 }
 
+impl RequestType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequestType::Scheduled => "scheduled",
+            RequestType::Event => "event",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "scheduled" => Some(RequestType::Scheduled),
+            "event" => Some(RequestType::Event),
+            _ => None,
+        }
+    }
+}
+
 /// Request status for insight generation
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -402,6 +728,27 @@ pub enum RequestStatus {
     Failed,
 }
 
+impl RequestStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequestStatus::Pending => "pending",
+            RequestStatus::Processing => "processing",
+            RequestStatus::Done => "done",
+            RequestStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(RequestStatus::Pending),
+            "processing" => Some(RequestStatus::Processing),
+            "done" => Some(RequestStatus::Done),
+            "failed" => Some(RequestStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
 // ============================================================================
 // Processed Readings Index Models
 // ============================================================================
@@ -420,6 +767,74 @@ pub struct ProcessedReading {
     pub ttl: i64,
 }
 
+/// Pipeline stage that marks a reading as processed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessingStage {
+    Event,
+    Aggregate,
+    Status,
+}
+
+impl ProcessingStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessingStage::Event => "event",
+            ProcessingStage::Aggregate => "aggregate",
+            ProcessingStage::Status => "status",
+        }
+    }
+
+    /// Attribute holding this stage's marker, `{stage}_processed_at_ms`
+    pub fn processed_at_attribute(&self) -> &'static str {
+        match self {
+            ProcessingStage::Event => "event_processed_at_ms",
+            ProcessingStage::Aggregate => "aggregate_processed_at_ms",
+            ProcessingStage::Status => "status_processed_at_ms",
+        }
+    }
+}
+
+// ============================================================================
+// String Conversions
+// ============================================================================
+
+/// `Display` and `AsRef<str>` through `as_str`, so log fields and rollup
+/// dimensions use the same names as the serialized form
+macro_rules! impl_str_conversions {
+    ($($enum_type:ty),* $(,)?) => {
+        $(
+            impl std::fmt::Display for $enum_type {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl AsRef<str> for $enum_type {
+                fn as_ref(&self) -> &str {
+                    self.as_str()
+                }
+            }
+        )*
+    };
+}
+
+impl_str_conversions!(
+    SensorStatus,
+    EventType,
+    WindowType,
+    ConfidenceLevel,
+    Trend,
+    Urgency,
+    SoilType,
+    SensorStatusSummary,
+    HealthCategory,
+    BucketType,
+    RequestType,
+    RequestStatus,
+    ProcessingStage,
+);
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -436,6 +851,72 @@ impl Aggregate {
     pub fn device_window_key(hardware_id: &str, window_type: WindowType) -> String {
         format!("{}#{}", hardware_id, window_type.as_str())
     }
+
+    /// Whether a late reading should reopen this window
+    ///
+    /// True when the reading's event time falls inside the window and the
+    /// window ended less than `lateness_grace_ms` before `now_ms`. Readings
+    /// arriving after the grace period are left for a recompute.
+    pub fn should_reopen(
+        &self,
+        new_reading_event_time_ms: i64,
+        now_ms: i64,
+        lateness_grace_ms: i64,
+    ) -> bool {
+        let in_window = new_reading_event_time_ms >= self.window_start_ms
+            && new_reading_event_time_ms < self.window_end_ms;
+        in_window && now_ms < self.window_end_ms.saturating_add(lateness_grace_ms)
+    }
+
+    /// Merge a late reading's sensor values into the existing stats
+    ///
+    /// Values are counted only when their sensor status is ok, as in a full
+    /// aggregation, and weighted by the reading's quality score.
+    /// `computed_at_ms` moves up to the reading's ingest time,
+    /// and the window is marked incomplete until `refresh_completeness`
+    /// sees the grace period has passed.
+    pub fn incorporate(&mut self, reading: &Reading) {
+        let bme280_ok = reading.sensor_status.bme280 == SensorStatus::Ok;
+        let soil_moisture_ok = reading.sensor_status.soil_moisture == SensorStatus::Ok;
+
+        let sensors = [
+            (
+                &mut self.temperature_stats,
+                reading.sensors.bme280_temp_c,
+                bme280_ok,
+            ),
+            (
+                &mut self.humidity_stats,
+                reading.sensors.humidity_pct,
+                bme280_ok,
+            ),
+            (
+                &mut self.pressure_stats,
+                reading.sensors.pressure_hpa,
+                bme280_ok,
+            ),
+            (
+                &mut self.soil_moisture_stats,
+                reading.sensors.soil_moisture_pct,
+                soil_moisture_ok,
+            ),
+        ];
+        let weight = quality_weight(reading.quality);
+        for (stats, value, ok) in sensors {
+            stats
+                .get_or_insert_with(SensorStats::default)
+                .record_weighted(value.filter(|_| ok), weight);
+        }
+
+        self.computed_at_ms = self.computed_at_ms.max(reading.ingest_time_ms);
+        self.is_complete = false;
+    }
+
+    /// Mark the window complete once it ended at least `lateness_grace_ms`
+    /// before `now_ms`, and incomplete while late data may still arrive
+    pub fn refresh_completeness(&mut self, now_ms: i64, lateness_grace_ms: i64) {
+        self.is_complete = now_ms >= self.window_end_ms.saturating_add(lateness_grace_ms);
+    }
 }
 
 impl Rollup {
@@ -461,26 +942,91 @@ impl Rollup {
     }
 }
 
+/// Expected reporting intervals a device may go unseen and still be healthy
+pub const HEALTHY_INTERVALS: i64 = 4;
+
+/// Expected reporting intervals a device may go unseen before it is missing
+pub const STALE_INTERVALS: i64 = 12;
+
+/// Cut-offs `DeviceStatus::health_category` sorts devices by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthThresholds {
+    /// Longest time since last ingest that still counts as healthy
+    pub healthy_max_sec: i64,
+    /// Longest time since last ingest that counts as stale rather than missing
+    pub stale_max_sec: i64,
+    /// How long after an error the device counts as failing
+    pub failing_error_window_sec: i64,
+}
+
+impl Default for HealthThresholds {
+    /// Healthy up to 2 hours, stale up to 6, failing for 24 hours after an error
+    fn default() -> Self {
+        Self {
+            healthy_max_sec: 2 * 3600,
+            stale_max_sec: 6 * 3600,
+            failing_error_window_sec: 24 * 3600,
+        }
+    }
+}
+
+impl HealthThresholds {
+    /// Thresholds scaled to a device's reporting interval
+    ///
+    /// A device is healthy for `HEALTHY_INTERVALS` missed intervals and stale
+    /// up to `STALE_INTERVALS`, so a device reporting every 5 minutes goes
+    /// stale after 20 minutes instead of 2 hours. The failing window does not
+    /// depend on the interval. A non-positive interval falls back to the
+    /// defaults.
+    pub fn from_expected_interval(expected_interval_sec: i64) -> Self {
+        if expected_interval_sec <= 0 {
+            return Self::default();
+        }
+
+        Self {
+            healthy_max_sec: HEALTHY_INTERVALS * expected_interval_sec,
+            stale_max_sec: STALE_INTERVALS * expected_interval_sec,
+            ..Self::default()
+        }
+    }
+}
+
 impl DeviceStatus {
     /// Derive health category from last_seen_ingest_time_ms
-    pub fn health_category(&self, now_ms: i64) -> HealthCategory {
-        let hours_since_seen = (now_ms - self.last_seen_ingest_time_ms) / (1000 * 3600);
-
-        if self.last_error_at_ms.is_some() {
-            let hours_since_error = (now_ms - self.last_error_at_ms.unwrap()) / (1000 * 3600);
-            if hours_since_error < 24 {
+    ///
+    /// An error within `failing_error_window_sec` takes precedence; otherwise
+    /// the time since the last ingest is compared against the healthy and
+    /// stale maximums (both inclusive).
+    pub fn health_category(&self, now_ms: i64, thresholds: &HealthThresholds) -> HealthCategory {
+        if let Some(error_at_ms) = self.last_error_at_ms {
+            if (now_ms - error_at_ms) / 1000 < thresholds.failing_error_window_sec {
                 return HealthCategory::Failing;
             }
         }
 
-        if hours_since_seen <= 2 {
+        let sec_since_seen = (now_ms - self.last_seen_ingest_time_ms) / 1000;
+        if sec_since_seen <= thresholds.healthy_max_sec {
             HealthCategory::Healthy
-        } else if hours_since_seen <= 6 {
+        } else if sec_since_seen <= thresholds.stale_max_sec {
             HealthCategory::Stale
         } else {
             HealthCategory::Missing
         }
     }
+
+    /// Record an error as the newest entry of `last_errors`
+    ///
+    /// The message is truncated to 256 characters and the record is placed
+    /// first; only the `max_keep` most recent records are kept.
+    /// `last_error_at_ms` and `last_error_code` are taken from the record.
+    pub fn push_error(&mut self, mut record: ErrorRecord, max_keep: usize) {
+        record.error_message =
+            crate::device_status_update::truncate_error_message(&record.error_message);
+        self.last_error_at_ms = Some(record.timestamp_ms);
+        self.last_error_code = Some(record.error_code.clone());
+        self.last_errors.insert(0, record);
+        self.last_errors.truncate(max_keep);
+    }
 }
 
 #[cfg(test)]
@@ -509,6 +1055,7 @@ mod tests {
                 ds18b20: SensorStatus::Missing,
                 soil_moisture: SensorStatus::Ok,
             },
+            quality: None,
             ttl: None,
         };
 
@@ -568,8 +1115,11 @@ mod tests {
             expected_interval_sec: 300,
             last_processed_event_time_ms: 1000,
             ingest_event_skew_seconds: 0,
+            clock_skew_warning: false,
             pipeline_lag_seconds: 0,
             coverage_pct_last_hour: 1.0,
+            current_boot_id: None,
+            boot_started_at_ms: None,
             sensor_status_summary: SensorStatusSummary::Ok,
             last_event_detected_at_ms: None,
             last_aggregate_computed_at_ms: None,
@@ -582,7 +1132,10 @@ mod tests {
 
         // Within 2 hours
         let now_ms = 1000 + (1 * 3600 * 1000);
-        assert_eq!(status.health_category(now_ms), HealthCategory::Healthy);
+        assert_eq!(
+            status.health_category(now_ms, &HealthThresholds::default()),
+            HealthCategory::Healthy
+        );
     }
 
     #[test]
@@ -594,8 +1147,11 @@ mod tests {
             expected_interval_sec: 300,
             last_processed_event_time_ms: 1000,
             ingest_event_skew_seconds: 0,
+            clock_skew_warning: false,
             pipeline_lag_seconds: 0,
             coverage_pct_last_hour: 1.0,
+            current_boot_id: None,
+            boot_started_at_ms: None,
             sensor_status_summary: SensorStatusSummary::Ok,
             last_event_detected_at_ms: None,
             last_aggregate_computed_at_ms: None,
@@ -608,7 +1164,10 @@ mod tests {
 
         // Between 2 and 6 hours
         let now_ms = 1000 + (4 * 3600 * 1000);
-        assert_eq!(status.health_category(now_ms), HealthCategory::Stale);
+        assert_eq!(
+            status.health_category(now_ms, &HealthThresholds::default()),
+            HealthCategory::Stale
+        );
     }
 
     #[test]
@@ -620,8 +1179,11 @@ mod tests {
             expected_interval_sec: 300,
             last_processed_event_time_ms: 1000,
             ingest_event_skew_seconds: 0,
+            clock_skew_warning: false,
             pipeline_lag_seconds: 0,
             coverage_pct_last_hour: 1.0,
+            current_boot_id: None,
+            boot_started_at_ms: None,
             sensor_status_summary: SensorStatusSummary::Ok,
             last_event_detected_at_ms: None,
             last_aggregate_computed_at_ms: None,
@@ -634,7 +1196,10 @@ mod tests {
 
         // More than 6 hours
         let now_ms = 1000 + (7 * 3600 * 1000);
-        assert_eq!(status.health_category(now_ms), HealthCategory::Missing);
+        assert_eq!(
+            status.health_category(now_ms, &HealthThresholds::default()),
+            HealthCategory::Missing
+        );
     }
 
     #[test]
@@ -646,8 +1211,11 @@ mod tests {
             expected_interval_sec: 300,
             last_processed_event_time_ms: 1000,
             ingest_event_skew_seconds: 0,
+            clock_skew_warning: false,
             pipeline_lag_seconds: 0,
             coverage_pct_last_hour: 1.0,
+            current_boot_id: None,
+            boot_started_at_ms: None,
             sensor_status_summary: SensorStatusSummary::Ok,
             last_event_detected_at_ms: None,
             last_aggregate_computed_at_ms: None,
@@ -660,6 +1228,590 @@ mod tests {
 
         // Error within 24 hours
         let now_ms = 1000 + (2 * 3600 * 1000);
-        assert_eq!(status.health_category(now_ms), HealthCategory::Failing);
+        assert_eq!(
+            status.health_category(now_ms, &HealthThresholds::default()),
+            HealthCategory::Failing
+        );
+    }
+
+    fn status_seen_at(last_seen_ingest_time_ms: i64, expected_interval_sec: i64) -> DeviceStatus {
+        DeviceStatus {
+            hardware_id: "device1".to_string(),
+            last_seen_event_time_ms: last_seen_ingest_time_ms,
+            last_seen_ingest_time_ms,
+            expected_interval_sec,
+            last_processed_event_time_ms: last_seen_ingest_time_ms,
+            ingest_event_skew_seconds: 0,
+            clock_skew_warning: false,
+            pipeline_lag_seconds: 0,
+            coverage_pct_last_hour: 1.0,
+            current_boot_id: None,
+            boot_started_at_ms: None,
+            sensor_status_summary: SensorStatusSummary::Ok,
+            last_event_detected_at_ms: None,
+            last_aggregate_computed_at_ms: None,
+            last_insight_generated_at_ms: None,
+            last_error_at_ms: None,
+            last_error_code: None,
+            last_errors: vec![],
+            updated_at_ms: last_seen_ingest_time_ms,
+        }
+    }
+
+    fn error_record(timestamp_ms: i64, message: &str) -> ErrorRecord {
+        ErrorRecord {
+            timestamp_ms,
+            error_code: format!("ERROR_{}", timestamp_ms),
+            error_message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_push_error_truncates_message_to_256_chars() {
+        let mut status = status_seen_at(1000, 300);
+
+        status.push_error(error_record(2000, &"x".repeat(300)), 10);
+        assert_eq!(status.last_errors[0].error_message.chars().count(), 256);
+
+        status.push_error(error_record(3000, &"y".repeat(256)), 10);
+        assert_eq!(status.last_errors[0].error_message, "y".repeat(256));
+    }
+
+    #[test]
+    fn test_push_error_newest_first_and_updates_last_error() {
+        let mut status = status_seen_at(1000, 300);
+
+        status.push_error(error_record(2000, "first"), 10);
+        status.push_error(error_record(3000, "second"), 10);
+
+        assert_eq!(status.last_errors[0].error_message, "second");
+        assert_eq!(status.last_errors[1].error_message, "first");
+        assert_eq!(status.last_error_at_ms, Some(3000));
+        assert_eq!(status.last_error_code.as_deref(), Some("ERROR_3000"));
+    }
+
+    #[test]
+    fn test_push_error_keeps_most_recent_max_keep() {
+        let mut status = status_seen_at(1000, 300);
+
+        for i in 0..8 {
+            status.push_error(error_record(2000 + i, "boom"), 5);
+        }
+
+        let kept: Vec<i64> = status.last_errors.iter().map(|e| e.timestamp_ms).collect();
+        assert_eq!(kept, vec![2007, 2006, 2005, 2004, 2003]);
+    }
+
+    #[test]
+    fn test_health_category_short_interval_goes_stale_sooner() {
+        let status = status_seen_at(1000, 300);
+        let thresholds = HealthThresholds::from_expected_interval(status.expected_interval_sec);
+        let minute_ms = 60 * 1000;
+
+        assert_eq!(thresholds.healthy_max_sec, 20 * 60);
+        assert_eq!(
+            status.health_category(1000 + 19 * minute_ms, &thresholds),
+            HealthCategory::Healthy
+        );
+        assert_eq!(
+            status.health_category(1000 + 21 * minute_ms, &thresholds),
+            HealthCategory::Stale
+        );
+        assert_eq!(
+            status.health_category(1000 + 61 * minute_ms, &thresholds),
+            HealthCategory::Missing
+        );
+    }
+
+    #[test]
+    fn test_health_category_default_thresholds_match_hours() {
+        let status = status_seen_at(1000, 300);
+        let thresholds = HealthThresholds::default();
+        let hour_ms = 3600 * 1000;
+
+        assert_eq!(
+            status.health_category(1000 + 2 * hour_ms, &thresholds),
+            HealthCategory::Healthy
+        );
+        assert_eq!(
+            status.health_category(1000 + 2 * hour_ms + 60_000, &thresholds),
+            HealthCategory::Stale
+        );
+        assert_eq!(
+            status.health_category(1000 + 6 * hour_ms, &thresholds),
+            HealthCategory::Stale
+        );
+        assert_eq!(
+            status.health_category(1000 + 6 * hour_ms + 60_000, &thresholds),
+            HealthCategory::Missing
+        );
+
+        // A non-positive interval keeps the defaults
+        assert_eq!(HealthThresholds::from_expected_interval(0), thresholds);
+    }
+
+    #[test]
+    fn test_health_category_as_str_round_trip() {
+        for category in [
+            HealthCategory::Healthy,
+            HealthCategory::Stale,
+            HealthCategory::Missing,
+            HealthCategory::Failing,
+        ] {
+            assert_eq!(HealthCategory::parse(category.as_str()), Some(category));
+            assert_eq!(
+                serde_json::to_string(&category).unwrap(),
+                format!("\"{}\"", category.as_str())
+            );
+        }
+        assert_eq!(HealthCategory::parse("Healthy"), None);
+    }
+
+    #[test]
+    fn test_weighted_mean_equal_weights_matches_unweighted() {
+        let values = [(10.0, 1.0), (20.0, 1.0), (30.0, 1.0)];
+        assert_eq!(weighted_mean(&values), Some(20.0));
+    }
+
+    #[test]
+    fn test_weighted_mean_favors_high_quality() {
+        // Unweighted mean is 20.0; the low-quality outlier pulls it up
+        let values = [(10.0, 1.0), (10.0, 1.0), (40.0, 0.1)];
+        let unweighted = values.iter().map(|(v, _)| v).sum::<f64>() / values.len() as f64;
+        let weighted = weighted_mean(&values).unwrap();
+
+        assert_eq!(unweighted, 20.0);
+        assert!((weighted - 11.4285714).abs() < 1e-6);
+        assert!(weighted < unweighted);
+    }
+
+    #[test]
+    fn test_weighted_mean_skips_zero_and_invalid_weights() {
+        let values = [(10.0, 0.0), (20.0, 0.5), (f64::NAN, 1.0), (99.0, f64::NAN)];
+        assert_eq!(weighted_mean(&values), Some(20.0));
+
+        assert_eq!(weighted_mean(&[]), None);
+        assert_eq!(weighted_mean(&[(10.0, 0.0)]), None);
+    }
+
+    #[test]
+    fn test_sensor_stats_record() {
+        let mut stats = SensorStats::default();
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            stats.record(Some(value));
+        }
+        stats.record(None);
+        stats.record(Some(f64::NAN));
+
+        assert_eq!(stats.total_count, 10);
+        assert_eq!(stats.valid_count, 8);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 9.0);
+        assert_eq!(stats.avg, 5.0);
+        assert!((stats.stddev - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_percentiles_known_dataset() {
+        let sorted: Vec<f64> = (1..=10).map(|v| v as f64).collect();
+        let result = compute_percentiles(&sorted, &[0.0, 50.0, 95.0, 100.0]);
+
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0], 1.0);
+        assert!((result[1] - 5.5).abs() < 1e-9);
+        assert!((result[2] - 9.55).abs() < 1e-9);
+        assert_eq!(result[3], 10.0);
+
+        // Single value: every percentile is that value
+        assert_eq!(
+            compute_percentiles(&[42.0], &[50.0, 95.0]),
+            vec![42.0, 42.0]
+        );
+    }
+
+    #[test]
+    fn test_request_status_lowercase_serialization() {
+        for status in [
+            RequestStatus::Pending,
+            RequestStatus::Processing,
+            RequestStatus::Done,
+            RequestStatus::Failed,
+        ] {
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(json, format!("\"{}\"", status.as_str()));
+            assert_eq!(RequestStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(
+            serde_json::to_string(&RequestStatus::Pending).unwrap(),
+            "\"pending\""
+        );
+        assert_eq!(RequestStatus::parse("Pending"), None);
+    }
+
+    #[test]
+    fn test_request_type_as_str_round_trip() {
+        for request_type in [RequestType::Scheduled, RequestType::Event] {
+            let json = serde_json::to_string(&request_type).unwrap();
+            assert_eq!(json, format!("\"{}\"", request_type.as_str()));
+            assert_eq!(
+                RequestType::parse(request_type.as_str()),
+                Some(request_type)
+            );
+        }
+    }
+
+    #[test]
+    fn test_window_type_duration_ms() {
+        assert_eq!(WindowType::Hourly.duration_ms(), 3_600_000);
+        assert_eq!(WindowType::Daily.duration_ms(), 86_400_000);
+        assert_eq!(WindowType::Weekly.duration_ms(), 604_800_000);
+    }
+
+    #[test]
+    fn test_window_bounds_known_timestamp() {
+        // Wednesday 2024-01-03 14:25:36.789 UTC
+        let ts = 1_704_291_936_789;
+
+        // 14:00 to 15:00
+        assert_eq!(
+            WindowType::Hourly.window_bounds(ts),
+            (1_704_290_400_000, 1_704_294_000_000)
+        );
+        // 2024-01-03 00:00 to 2024-01-04 00:00
+        assert_eq!(
+            WindowType::Daily.window_bounds(ts),
+            (1_704_240_000_000, 1_704_326_400_000)
+        );
+        // Monday 2024-01-01 00:00 to Monday 2024-01-08 00:00
+        assert_eq!(
+            WindowType::Weekly.window_bounds(ts),
+            (1_704_067_200_000, 1_704_672_000_000)
+        );
+    }
+
+    #[test]
+    fn test_window_bounds_weekly_monday_edge() {
+        let monday = 1_704_067_200_000; // 2024-01-01 00:00 UTC
+        let week = WindowType::Weekly.duration_ms();
+
+        // Monday midnight starts its own week
+        assert_eq!(
+            WindowType::Weekly.window_bounds(monday),
+            (monday, monday + week)
+        );
+        // One millisecond earlier is the last moment of the previous week (Sunday)
+        assert_eq!(
+            WindowType::Weekly.window_bounds(monday - 1),
+            (monday - week, monday)
+        );
+        // Epoch (a Thursday) falls in the week starting Monday 1969-12-29
+        assert_eq!(
+            WindowType::Weekly.window_bounds(0),
+            (-EPOCH_TO_MONDAY_MS, week - EPOCH_TO_MONDAY_MS)
+        );
+    }
+
+    /// Assert `as_str`, `Display` and `AsRef` all match the serde name
+    fn assert_str_matches_serde<T>(values: &[T])
+    where
+        T: Serialize + std::fmt::Display + AsRef<str>,
+    {
+        for value in values {
+            let serialized = serde_json::to_value(value).unwrap();
+            assert_eq!(serialized.as_str(), Some(value.as_ref()));
+            assert_eq!(value.to_string(), value.as_ref());
+        }
+    }
+
+    #[test]
+    fn test_enum_as_str_matches_serialized_form() {
+        use SensorStatus as S;
+        assert_str_matches_serde(&[S::Ok, S::Missing, S::Stale, S::OutOfRange, S::Noisy]);
+        assert_str_matches_serde(&[
+            EventType::WateringEvent,
+            EventType::DryingCycle,
+            EventType::TemperatureStress,
+            EventType::HumidityAnomaly,
+            EventType::EnvironmentalChange,
+        ]);
+        assert_str_matches_serde(&[WindowType::Hourly, WindowType::Daily, WindowType::Weekly]);
+        assert_str_matches_serde(&[
+            ConfidenceLevel::Low,
+            ConfidenceLevel::Medium,
+            ConfidenceLevel::High,
+        ]);
+        assert_str_matches_serde(&[Trend::Improving, Trend::Declining, Trend::Stable]);
+        assert_str_matches_serde(&[Urgency::Low, Urgency::Medium, Urgency::High]);
+        assert_str_matches_serde(&[
+            SoilType::PottingMix,
+            SoilType::CocoCoir,
+            SoilType::Peat,
+            SoilType::Soil,
+            SoilType::Hydroponic,
+        ]);
+        assert_str_matches_serde(&[
+            SensorStatusSummary::Ok,
+            SensorStatusSummary::Degraded,
+            SensorStatusSummary::Missing,
+        ]);
+        assert_str_matches_serde(&[
+            HealthCategory::Healthy,
+            HealthCategory::Stale,
+            HealthCategory::Missing,
+            HealthCategory::Failing,
+        ]);
+        assert_str_matches_serde(&[BucketType::Minute, BucketType::Hour]);
+        assert_str_matches_serde(&[RequestType::Scheduled, RequestType::Event]);
+        assert_str_matches_serde(&[
+            RequestStatus::Pending,
+            RequestStatus::Processing,
+            RequestStatus::Done,
+            RequestStatus::Failed,
+        ]);
+        assert_str_matches_serde(&[
+            ProcessingStage::Event,
+            ProcessingStage::Aggregate,
+            ProcessingStage::Status,
+        ]);
+    }
+
+    #[test]
+    fn test_enum_display_for_log_fields() {
+        assert_eq!(format!("{}", EventType::WateringEvent), "Watering_Event");
+        assert_eq!(format!("soil={}", SoilType::PottingMix), "soil=potting_mix");
+    }
+
+    fn profile_with(soil_type: Option<SoilType>, learned: Option<MoistureRange>) -> DeviceProfile {
+        DeviceProfile {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            plant_type: None,
+            soil_type,
+            pot_size_liters: None,
+            expected_interval_sec: 300,
+            baseline_moisture_range: learned,
+            typical_watering_interval_sec: None,
+            last_watering_events: None,
+            temp_offset_c: None,
+            humidity_offset_pct: None,
+            soil_moisture_offset_pct: None,
+            updated_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_soil_type_default_moisture_range_is_plausible() {
+        for soil in [
+            SoilType::PottingMix,
+            SoilType::CocoCoir,
+            SoilType::Peat,
+            SoilType::Soil,
+            SoilType::Hydroponic,
+        ] {
+            let range = soil.default_moisture_range();
+            assert!(range.min < range.max, "{} range is inverted", soil);
+            assert!(
+                range.min >= 0.0 && range.max <= 100.0,
+                "{} out of bounds",
+                soil
+            );
+        }
+
+        // Coco coir drains faster than potting mix; hydroponics stays wettest
+        assert!(
+            SoilType::CocoCoir.default_moisture_range().min
+                < SoilType::PottingMix.default_moisture_range().min
+        );
+        assert!(SoilType::Hydroponic.default_moisture_range().min >= 80.0);
+    }
+
+    #[test]
+    fn test_effective_moisture_range_falls_back_to_soil_type() {
+        let fallback = profile_with(Some(SoilType::CocoCoir), None);
+        assert_eq!(
+            fallback.effective_moisture_range(),
+            Some(SoilType::CocoCoir.default_moisture_range())
+        );
+
+        let learned = MoistureRange {
+            min: 33.0,
+            max: 58.0,
+        };
+        let profile = profile_with(Some(SoilType::CocoCoir), Some(learned.clone()));
+        assert_eq!(profile.effective_moisture_range(), Some(learned));
+
+        assert_eq!(profile_with(None, None).effective_moisture_range(), None);
+    }
+
+    const HOUR_MS: i64 = 3_600_000;
+    const GRACE_MS: i64 = 15 * 60_000;
+
+    fn hourly_aggregate(readings: &[Reading]) -> Aggregate {
+        let mut aggregate = Aggregate {
+            device_window: Aggregate::device_window_key("AA:BB:CC:DD:EE:FF", WindowType::Hourly),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            window_type: WindowType::Hourly,
+            window_start_ms: 1704067200000,
+            window_end_ms: 1704067200000 + HOUR_MS,
+            temperature_stats: None,
+            humidity_stats: None,
+            pressure_stats: None,
+            soil_moisture_stats: None,
+            computed_at_ms: 1704067200000 + HOUR_MS,
+            is_complete: true,
+            ttl: None,
+        };
+        for reading in readings {
+            aggregate.incorporate(reading);
+        }
+        aggregate.computed_at_ms = 1704067200000 + HOUR_MS;
+        aggregate.is_complete = true;
+        aggregate
+    }
+
+    fn window_reading(timestamp_ms: i64, temp: f64, soil_moisture: f64) -> Reading {
+        Reading {
+            batch_id: format!("batch-{}", timestamp_ms),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms,
+            ingest_time_ms: timestamp_ms,
+            boot_id: "boot1".to_string(),
+            firmware_version: "1.0.0".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: Some(temp),
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: Some(soil_moisture),
+            },
+            sensor_status: ReadingSensorStatus {
+                bme280: SensorStatus::Ok,
+                ds18b20: SensorStatus::Missing,
+                soil_moisture: SensorStatus::Ok,
+            },
+            quality: None,
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn test_incorporate_weights_low_quality_outlier_less() {
+        let start = 1704067200000;
+        let mut aggregate = hourly_aggregate(&[
+            window_reading(start + 60_000, 20.0, 40.0),
+            window_reading(start + 120_000, 20.0, 40.0),
+        ]);
+
+        let mut outlier = window_reading(start + 180_000, 40.0, 40.0);
+        outlier.quality = Some(0.1);
+        aggregate.incorporate(&outlier);
+
+        let temperature = aggregate.temperature_stats.as_ref().unwrap();
+        // (20 + 20 + 0.1 * 40) / 2.1, against 26.67 unweighted
+        assert!((temperature.avg - 44.0 / 2.1).abs() < 1e-9);
+        assert_eq!(temperature.max, 40.0);
+        assert_eq!(temperature.valid_count, 3);
+    }
+
+    #[test]
+    fn test_sensor_stats_without_weights_count_at_default_weight() {
+        // Stats stored before weighting carry sum and count but no weight_sum
+        let mut stats: SensorStats = serde_json::from_str(
+            r#"{"min":20.0,"max":22.0,"avg":21.0,"stddev":1.0,"valid_count":2,"total_count":2,"sum":42.0,"sumsq":884.0}"#,
+        )
+        .unwrap();
+        assert_eq!(stats.weight_sum, None);
+
+        stats.record_weighted(Some(24.0), 1.0);
+        assert!((stats.avg - 22.0).abs() < 1e-9);
+        assert_eq!(stats.weight_sum, Some(3.0));
+    }
+
+    #[test]
+    fn test_quality_weight() {
+        assert_eq!(quality_weight(None), DEFAULT_QUALITY_WEIGHT);
+        assert_eq!(quality_weight(Some(0.25)), 0.25);
+        assert_eq!(quality_weight(Some(1.5)), 1.0);
+        assert_eq!(quality_weight(Some(-0.5)), 0.0);
+        assert_eq!(quality_weight(Some(f64::NAN)), DEFAULT_QUALITY_WEIGHT);
+    }
+
+    #[test]
+    fn test_incorporate_late_reading_into_existing_aggregate() {
+        let start = 1704067200000;
+        let mut aggregate = hourly_aggregate(&[
+            window_reading(start + 60_000, 20.0, 40.0),
+            window_reading(start + 120_000, 22.0, 42.0),
+        ]);
+
+        // Arrives two hours after it was taken, with a failed soil sensor
+        let mut late = window_reading(start + 1_800_000, 24.0, 99.0);
+        late.ingest_time_ms = start + 3 * HOUR_MS;
+        late.sensor_status.soil_moisture = SensorStatus::OutOfRange;
+        aggregate.incorporate(&late);
+
+        let temperature = aggregate.temperature_stats.as_ref().unwrap();
+        assert_eq!(temperature.valid_count, 3);
+        assert_eq!(temperature.total_count, 3);
+        assert_eq!(temperature.min, 20.0);
+        assert_eq!(temperature.max, 24.0);
+        assert!((temperature.avg - 22.0).abs() < 1e-9);
+
+        let soil_moisture = aggregate.soil_moisture_stats.as_ref().unwrap();
+        assert_eq!(soil_moisture.valid_count, 2);
+        assert_eq!(soil_moisture.total_count, 3);
+        assert_eq!(soil_moisture.max, 42.0);
+
+        let humidity = aggregate.humidity_stats.as_ref().unwrap();
+        assert_eq!(humidity.valid_count, 0);
+        assert_eq!(humidity.total_count, 3);
+
+        assert_eq!(aggregate.computed_at_ms, start + 3 * HOUR_MS);
+        assert!(!aggregate.is_complete);
+    }
+
+    #[test]
+    fn test_should_reopen_near_grace_boundary() {
+        let aggregate = hourly_aggregate(&[]);
+        let inside = aggregate.window_start_ms + 1_800_000;
+        let end = aggregate.window_end_ms;
+
+        assert!(aggregate.should_reopen(inside, end, GRACE_MS));
+        assert!(aggregate.should_reopen(inside, end + GRACE_MS - 1, GRACE_MS));
+        assert!(!aggregate.should_reopen(inside, end + GRACE_MS, GRACE_MS));
+
+        // Readings outside the window never reopen it
+        assert!(!aggregate.should_reopen(end, end, GRACE_MS));
+        assert!(!aggregate.should_reopen(aggregate.window_start_ms - 1, end, GRACE_MS));
+        assert!(aggregate.should_reopen(aggregate.window_start_ms, end, GRACE_MS));
+    }
+
+    #[test]
+    fn test_refresh_completeness_waits_for_grace_period() {
+        let mut aggregate = hourly_aggregate(&[]);
+        let end = aggregate.window_end_ms;
+
+        aggregate.refresh_completeness(end + GRACE_MS - 1, GRACE_MS);
+        assert!(!aggregate.is_complete);
+
+        aggregate.refresh_completeness(end + GRACE_MS, GRACE_MS);
+        assert!(aggregate.is_complete);
+    }
+
+    #[test]
+    fn test_compute_percentiles_empty() {
+        assert!(compute_percentiles(&[], &[50.0, 95.0]).is_empty());
+    }
+
+    #[test]
+    fn test_profile_calibration_defaults_unset_offsets_to_zero() {
+        let mut profile = profile_with(None, None);
+        assert!(profile.calibration().is_zero());
+
+        profile.soil_moisture_offset_pct = Some(-5.0);
+        let calibration = profile.calibration();
+        assert_eq!(calibration.soil_moisture_offset_pct, -5.0);
+        assert_eq!(calibration.temp_offset_c, 0.0);
+        assert!(!calibration.is_zero());
     }
 }
@@ -0,0 +1,212 @@
+use crate::plant_insights::{
+    compute_percentiles, DeviceProfile, MoistureRange, Reading, SensorStatus,
+};
+
+/// Minimum span of moisture data (first to last reading) before the baseline is learned
+pub const MIN_BASELINE_SPAN_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Percentiles used for the baseline moisture range, so spikes and dropouts
+/// do not stretch it
+pub const BASELINE_MOISTURE_PERCENTILES: [f64; 2] = [5.0, 95.0];
+
+/// Learn the moisture baseline and watering interval for a device profile
+///
+/// `baseline_moisture_range` becomes the 5th to 95th percentile of soil
+/// moisture values whose sensor reported `ok`. `typical_watering_interval_sec`
+/// becomes the median gap between the timestamps in `last_watering_events`
+/// (needs at least two events; otherwise it is left as is).
+///
+/// Nothing is updated unless the usable moisture readings span at least
+/// `MIN_BASELINE_SPAN_MS`, so a short burst of data cannot replace a learned
+/// baseline.
+pub fn update_baseline(profile: &mut DeviceProfile, recent_readings: &[Reading]) {
+    let moisture: Vec<(i64, f64)> = recent_readings
+        .iter()
+        .filter(|r| r.sensor_status.soil_moisture == SensorStatus::Ok)
+        .filter_map(|r| {
+            r.sensors
+                .soil_moisture_pct
+                .filter(|v| v.is_finite())
+                .map(|v| (r.timestamp_ms, v))
+        })
+        .collect();
+
+    let first_ms = moisture.iter().map(|(ts, _)| *ts).min();
+    let last_ms = moisture.iter().map(|(ts, _)| *ts).max();
+    match (first_ms, last_ms) {
+        (Some(first), Some(last)) if last - first >= MIN_BASELINE_SPAN_MS => {}
+        _ => return,
+    }
+
+    let mut values: Vec<f64> = moisture.into_iter().map(|(_, v)| v).collect();
+    values.sort_by(|a, b| a.total_cmp(b));
+    let range = compute_percentiles(&values, &BASELINE_MOISTURE_PERCENTILES);
+    profile.baseline_moisture_range = Some(MoistureRange {
+        min: range[0],
+        max: range[1],
+    });
+
+    if let Some(interval_sec) = profile
+        .last_watering_events
+        .as_deref()
+        .and_then(median_interval_sec)
+    {
+        profile.typical_watering_interval_sec = Some(interval_sec);
+    }
+}
+
+/// Median gap between event timestamps (epoch ms), in whole seconds
+///
+/// Timestamps may be in any order; `None` with fewer than two events.
+pub fn median_interval_sec(event_times_ms: &[i64]) -> Option<i64> {
+    let mut times = event_times_ms.to_vec();
+    times.sort_unstable();
+
+    let mut gaps: Vec<f64> = times.windows(2).map(|w| (w[1] - w[0]) as f64).collect();
+    if gaps.is_empty() {
+        return None;
+    }
+    gaps.sort_by(|a, b| a.total_cmp(b));
+
+    let median_ms = compute_percentiles(&gaps, &[50.0])[0];
+    Some((median_ms / 1000.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plant_insights::{ReadingSensorStatus, SensorValues};
+
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+    const DAY_MS: i64 = 24 * HOUR_MS;
+
+    fn reading(timestamp_ms: i64, moisture: f64, status: SensorStatus) -> Reading {
+        Reading {
+            batch_id: format!("batch-{}", timestamp_ms),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms,
+            ingest_time_ms: timestamp_ms,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: SensorValues {
+                bme280_temp_c: None,
+                ds18b20_temp_c: None,
+                humidity_pct: None,
+                pressure_hpa: None,
+                soil_moisture_pct: Some(moisture),
+            },
+            sensor_status: ReadingSensorStatus {
+                bme280: SensorStatus::Ok,
+                ds18b20: SensorStatus::Ok,
+                soil_moisture: status,
+            },
+            quality: None,
+            ttl: None,
+        }
+    }
+
+    fn profile() -> DeviceProfile {
+        DeviceProfile {
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            plant_type: None,
+            soil_type: None,
+            pot_size_liters: None,
+            expected_interval_sec: 300,
+            baseline_moisture_range: None,
+            typical_watering_interval_sec: None,
+            last_watering_events: None,
+            temp_offset_c: None,
+            humidity_offset_pct: None,
+            soil_moisture_offset_pct: None,
+            updated_at_ms: 0,
+        }
+    }
+
+    /// Drying curve from 70% down to 30% over two days, then watered back to
+    /// 70%, with a single dropout and a single spike
+    fn drying_curve() -> Vec<Reading> {
+        let mut readings: Vec<Reading> = (0..=96)
+            .map(|i| {
+                let hour_of_cycle = (i % 48) as f64;
+                let moisture = 70.0 - 40.0 * hour_of_cycle / 47.0;
+                reading(i * HOUR_MS, moisture, SensorStatus::Ok)
+            })
+            .collect();
+        readings.push(reading(10 * HOUR_MS + 1, 0.0, SensorStatus::Ok));
+        readings.push(reading(20 * HOUR_MS + 1, 100.0, SensorStatus::Ok));
+        readings
+    }
+
+    #[test]
+    fn test_update_baseline_uses_percentile_range() {
+        let readings = drying_curve();
+        let mut expected: Vec<f64> = readings
+            .iter()
+            .filter_map(|r| r.sensors.soil_moisture_pct)
+            .collect();
+        expected.sort_by(|a, b| a.total_cmp(b));
+        let bounds = compute_percentiles(&expected, &[5.0, 95.0]);
+
+        let mut profile = profile();
+        update_baseline(&mut profile, &readings);
+
+        let range = profile.baseline_moisture_range.unwrap();
+        assert!((range.min - bounds[0]).abs() < 1e-9);
+        assert!((range.max - bounds[1]).abs() < 1e-9);
+
+        // The dropout and spike are outside the learned range
+        assert!(range.min > 30.0 && range.min < 35.0);
+        assert!(range.max > 65.0 && range.max < 70.0);
+    }
+
+    #[test]
+    fn test_update_baseline_median_watering_interval() {
+        let mut profile = profile();
+        profile.last_watering_events = Some(vec![5 * DAY_MS, 0, 2 * DAY_MS, 7 * DAY_MS]);
+
+        update_baseline(&mut profile, &drying_curve());
+
+        // Gaps of 2, 3, and 2 days
+        assert_eq!(profile.typical_watering_interval_sec, Some(2 * 86_400));
+    }
+
+    #[test]
+    fn test_update_baseline_requires_a_day_of_data() {
+        let readings: Vec<Reading> = (0..24)
+            .map(|i| reading(i * HOUR_MS, 50.0, SensorStatus::Ok))
+            .collect();
+
+        let mut profile = profile();
+        profile.last_watering_events = Some(vec![0, DAY_MS]);
+        update_baseline(&mut profile, &readings);
+
+        assert_eq!(profile.baseline_moisture_range, None);
+        assert_eq!(profile.typical_watering_interval_sec, None);
+    }
+
+    #[test]
+    fn test_update_baseline_ignores_unhealthy_sensor_values() {
+        let mut readings = vec![
+            reading(0, 40.0, SensorStatus::Ok),
+            reading(DAY_MS, 60.0, SensorStatus::Ok),
+        ];
+        readings.push(reading(2 * DAY_MS, 5.0, SensorStatus::OutOfRange));
+
+        let mut profile = profile();
+        update_baseline(&mut profile, &readings);
+
+        let range = profile.baseline_moisture_range.unwrap();
+        assert!((range.min - 41.0).abs() < 1e-9);
+        assert!((range.max - 59.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_median_interval_sec() {
+        assert_eq!(median_interval_sec(&[]), None);
+        assert_eq!(median_interval_sec(&[1000]), None);
+        assert_eq!(median_interval_sec(&[0, 3_600_000]), Some(3600));
+        // Even number of gaps averages the middle two
+        assert_eq!(median_interval_sec(&[0, 1000, 4000, 9000, 10_000]), Some(2));
+    }
+}
@@ -0,0 +1,259 @@
+use lambda_http::aws_lambda_events::query_map::QueryMap;
+use std::str::FromStr;
+
+use crate::time::parse_timestamp;
+use crate::validators::parse_and_clamp_limit;
+
+/// Error parsing a query string parameter; carries the parameter name
+///
+/// Handlers convert this into their own validation error, so a missing
+/// parameter is always `MissingField` and an unparseable one `InvalidFormat`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryParamError {
+    /// A required parameter is absent
+    Missing(String),
+    /// A parameter is present but cannot be parsed
+    InvalidFormat(String),
+}
+
+impl std::fmt::Display for QueryParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryParamError::Missing(name) => write!(f, "Required field missing: {}", name),
+            QueryParamError::InvalidFormat(name) => {
+                write!(f, "Invalid format for field: {}", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryParamError {}
+
+/// Result ordering for time-ordered list queries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Oldest first
+    Asc,
+    /// Newest first
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    /// Value for DynamoDB's `ScanIndexForward` (true reads the sort key ascending)
+    pub fn scan_index_forward(&self) -> bool {
+        matches!(self, SortOrder::Asc)
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Parse a required parameter
+pub fn parse_required<T: FromStr>(params: &QueryMap, name: &str) -> Result<T, QueryParamError> {
+    parse_optional(params, name)?.ok_or_else(|| QueryParamError::Missing(name.to_string()))
+}
+
+/// Parse an optional parameter; absent yields `None`
+pub fn parse_optional<T: FromStr>(
+    params: &QueryMap,
+    name: &str,
+) -> Result<Option<T>, QueryParamError> {
+    params
+        .first(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| QueryParamError::InvalidFormat(name.to_string()))
+        })
+        .transpose()
+}
+
+/// Parse a required i64 parameter (e.g. `from`/`to` epoch milliseconds)
+pub fn parse_required_i64(params: &QueryMap, name: &str) -> Result<i64, QueryParamError> {
+    parse_required(params, name)
+}
+
+/// Parse a required timestamp parameter given as epoch milliseconds or RFC3339
+///
+/// See `parse_timestamp`; the value comes back as epoch milliseconds.
+pub fn parse_required_timestamp(params: &QueryMap, name: &str) -> Result<i64, QueryParamError> {
+    let value = params
+        .first(name)
+        .ok_or_else(|| QueryParamError::Missing(name.to_string()))?;
+
+    parse_timestamp(value).map_err(|_| QueryParamError::InvalidFormat(name.to_string()))
+}
+
+/// Parse an optional timestamp parameter given as epoch milliseconds or RFC3339
+pub fn parse_optional_timestamp(
+    params: &QueryMap,
+    name: &str,
+) -> Result<Option<i64>, QueryParamError> {
+    params
+        .first(name)
+        .map(|value| {
+            parse_timestamp(value).map_err(|_| QueryParamError::InvalidFormat(name.to_string()))
+        })
+        .transpose()
+}
+
+/// Parse an optional i32 parameter
+pub fn parse_optional_i32(params: &QueryMap, name: &str) -> Result<Option<i32>, QueryParamError> {
+    parse_optional(params, name)
+}
+
+/// Parse an optional string parameter (e.g. `cursor`)
+pub fn parse_optional_string(params: &QueryMap, name: &str) -> Option<String> {
+    params.first(name).map(|value| value.to_string())
+}
+
+/// Parse the optional `order` parameter (`asc` or `desc`, default `desc`)
+pub fn parse_sort_order(params: &QueryMap) -> Result<SortOrder, QueryParamError> {
+    Ok(parse_optional(params, "order")?.unwrap_or_default())
+}
+
+/// Parse the `limit` parameter, clamped to 1 through `max`
+///
+/// Absent or non-numeric values use `default`, matching `parse_and_clamp_limit`.
+pub fn parse_clamped_limit(params: &QueryMap, default: i32, max: i32) -> i32 {
+    parse_and_clamp_limit(params.first("limit"), default, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn params(pairs: &[(&str, &str)]) -> QueryMap {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<String, String>>()
+            .into()
+    }
+
+    #[test]
+    fn test_parse_required_i64() {
+        let query = params(&[("from", "1704067200000")]);
+        assert_eq!(parse_required_i64(&query, "from"), Ok(1704067200000));
+    }
+
+    #[test]
+    fn test_parse_required_i64_missing() {
+        let query = params(&[("from", "1704067200000")]);
+        assert_eq!(
+            parse_required_i64(&query, "to"),
+            Err(QueryParamError::Missing("to".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_required_i64_non_numeric() {
+        for value in ["yesterday", "", "1.5", "12abc"] {
+            let query = params(&[("from", value)]);
+            assert_eq!(
+                parse_required_i64(&query, "from"),
+                Err(QueryParamError::InvalidFormat("from".to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_required_timestamp_either_form() {
+        let query = params(&[("from", "1705312800000"), ("to", "2024-01-15T10:00:00Z")]);
+        assert_eq!(parse_required_timestamp(&query, "from"), Ok(1705312800000));
+        assert_eq!(parse_required_timestamp(&query, "to"), Ok(1705312800000));
+        assert_eq!(
+            parse_required_timestamp(&query, "since"),
+            Err(QueryParamError::Missing("since".to_string()))
+        );
+        assert_eq!(
+            parse_required_timestamp(&params(&[("to", "2024-01-15")]), "to"),
+            Err(QueryParamError::InvalidFormat("to".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_optional_timestamp() {
+        assert_eq!(parse_optional_timestamp(&params(&[]), "since"), Ok(None));
+        assert_eq!(
+            parse_optional_timestamp(&params(&[("since", "2024-01-15T10:00:00Z")]), "since"),
+            Ok(Some(1705312800000))
+        );
+        assert_eq!(
+            parse_optional_timestamp(&params(&[("since", "5m")]), "since"),
+            Err(QueryParamError::InvalidFormat("since".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_optional_i32() {
+        assert_eq!(parse_optional_i32(&params(&[]), "limit"), Ok(None));
+        assert_eq!(
+            parse_optional_i32(&params(&[("limit", "-5")]), "limit"),
+            Ok(Some(-5))
+        );
+        assert_eq!(
+            parse_optional_i32(&params(&[("limit", "ten")]), "limit"),
+            Err(QueryParamError::InvalidFormat("limit".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_optional_string() {
+        let query = params(&[("cursor", "abc")]);
+        assert_eq!(
+            parse_optional_string(&query, "cursor").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(parse_optional_string(&query, "missing"), None);
+    }
+
+    #[test]
+    fn test_parse_sort_order() {
+        assert_eq!(parse_sort_order(&params(&[])), Ok(SortOrder::Desc));
+        assert_eq!(
+            parse_sort_order(&params(&[("order", "asc")])),
+            Ok(SortOrder::Asc)
+        );
+        assert_eq!(
+            parse_sort_order(&params(&[("order", "DESC")])),
+            Ok(SortOrder::Desc)
+        );
+        assert_eq!(
+            parse_sort_order(&params(&[("order", "oldest")])),
+            Err(QueryParamError::InvalidFormat("order".to_string()))
+        );
+
+        assert!(SortOrder::Asc.scan_index_forward());
+        assert!(!SortOrder::Desc.scan_index_forward());
+    }
+
+    fn limit(pairs: &[(&str, &str)]) -> i32 {
+        parse_clamped_limit(&params(pairs), 50, 100)
+    }
+
+    #[test]
+    fn test_parse_clamped_limit() {
+        assert_eq!(limit(&[]), 50);
+        assert_eq!(limit(&[("limit", "20")]), 20);
+        assert_eq!(limit(&[("limit", "500")]), 100);
+        assert_eq!(limit(&[("limit", "abc")]), 50);
+    }
+
+    #[test]
+    fn test_parse_clamped_limit_negative_clamps_to_minimum() {
+        assert_eq!(limit(&[("limit", "-10")]), 1);
+        assert_eq!(limit(&[("limit", "0")]), 1);
+    }
+}
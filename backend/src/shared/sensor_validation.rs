@@ -1,4 +1,4 @@
-﻿use crate::plant_insights::{SensorStatus, SensorValues};
+﻿use crate::plant_insights::{Reading, ReadingSensorStatus, SensorStatus, SensorValues};
 
 pub const TEMP_MIN_C: f64 = -40.0;
 pub const TEMP_MAX_C: f64 = 85.0;
@@ -9,6 +9,10 @@ pub const PRESSURE_MAX_HPA: f64 = 1100.0;
 pub const SOIL_MOISTURE_MIN_PCT: f64 = 0.0;
 pub const SOIL_MOISTURE_MAX_PCT: f64 = 100.0;
 
+/// A sensor is stale when the gap since the previous reading exceeds this
+/// multiple of the device's expected reporting interval
+pub const STALE_INTERVAL_MULTIPLIER: i64 = 2;
+
 pub fn validate_temperature(temp_c: Option<f64>) -> SensorStatus {
     match temp_c {
         None => SensorStatus::Missing,
@@ -50,6 +54,30 @@ pub fn is_stale(recent_values: &[f64]) -> bool {
     last_6.iter().all(|&v| (v - first).abs() < 0.001)
 }
 
+/// Detect a sensor stuck at one value
+/// Returns true if `values` contains a run of at least `min_run` consecutive,
+/// exactly equal readings; a `None` breaks the run
+pub fn detect_stuck_sensor(values: &[Option<f64>], min_run: usize) -> bool {
+    if min_run == 0 {
+        return false;
+    }
+
+    let mut run = 0;
+    let mut previous: Option<f64> = None;
+    for value in values {
+        run = match (*value, previous) {
+            (Some(v), Some(p)) if v == p => run + 1,
+            (Some(_), _) => 1,
+            (None, _) => 0,
+        };
+        if run >= min_run {
+            return true;
+        }
+        previous = *value;
+    }
+    false
+}
+
 pub fn is_noisy(prev_value: f64, current_value: f64) -> bool {
     if prev_value == 0.0 {
         return false;
@@ -70,6 +98,39 @@ pub fn validate_sensor_values(sensors: &SensorValues) -> (SensorStatus, SensorSt
     (bme280_status, ds18b20_status, soil_moisture_status)
 }
 
+/// Classify each sensor of a reading as Ok, Missing, or Stale
+///
+/// A sensor is `Missing` when its value is absent from `current`, `Stale` when
+/// the gap since `previous` exceeds `STALE_INTERVAL_MULTIPLIER` times
+/// `expected_interval_sec`, and `Ok` otherwise. Without a previous reading or a
+/// positive expected interval, staleness cannot be judged and present values
+/// are `Ok`.
+pub fn classify_status(
+    current: &Reading,
+    previous: Option<&Reading>,
+    expected_interval_sec: i64,
+) -> ReadingSensorStatus {
+    let stale = match previous {
+        Some(prev) if expected_interval_sec > 0 => {
+            let gap_ms = current.timestamp_ms - prev.timestamp_ms;
+            gap_ms > STALE_INTERVAL_MULTIPLIER * expected_interval_sec * 1000
+        }
+        _ => false,
+    };
+
+    let classify = |value: Option<f64>| match value {
+        None => SensorStatus::Missing,
+        Some(_) if stale => SensorStatus::Stale,
+        Some(_) => SensorStatus::Ok,
+    };
+
+    ReadingSensorStatus {
+        bme280: classify(current.sensors.bme280_temp_c),
+        ds18b20: classify(current.sensors.ds18b20_temp_c),
+        soil_moisture: classify(current.sensors.soil_moisture_pct),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,6 +148,27 @@ mod tests {
         assert_eq!(validate_temperature(Some(86.0)), SensorStatus::OutOfRange);
     }
 
+    #[test]
+    fn test_detect_stuck_sensor_stuck_run() {
+        let values = vec![Some(41.0), Some(38.5), Some(38.5), Some(38.5), Some(38.5)];
+        assert!(detect_stuck_sensor(&values, 4));
+        assert!(!detect_stuck_sensor(&values, 5));
+    }
+
+    #[test]
+    fn test_detect_stuck_sensor_varying_series() {
+        let values = vec![Some(38.5), Some(38.6), Some(38.5), Some(38.7), Some(38.5)];
+        assert!(!detect_stuck_sensor(&values, 2));
+    }
+
+    #[test]
+    fn test_detect_stuck_sensor_run_broken_by_none() {
+        let values = vec![Some(22.0), Some(22.0), None, Some(22.0), Some(22.0)];
+        assert!(!detect_stuck_sensor(&values, 3));
+        assert!(detect_stuck_sensor(&values, 2));
+        assert!(!detect_stuck_sensor(&[None, None, None], 2));
+    }
+
     #[test]
     fn test_is_stale_identical_readings() {
         let values = vec![25.0, 25.0, 25.0, 25.0, 25.0, 25.0];
@@ -119,4 +201,82 @@ mod tests {
         assert_eq!(ds18b20, SensorStatus::Ok);
         assert_eq!(soil, SensorStatus::Ok);
     }
+
+    fn reading_at(timestamp_ms: i64, sensors: SensorValues) -> Reading {
+        Reading {
+            batch_id: "batch1".to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms,
+            ingest_time_ms: timestamp_ms,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors,
+            sensor_status: ReadingSensorStatus {
+                bme280: SensorStatus::Ok,
+                ds18b20: SensorStatus::Ok,
+                soil_moisture: SensorStatus::Ok,
+            },
+            quality: None,
+            ttl: None,
+        }
+    }
+
+    fn all_sensors() -> SensorValues {
+        SensorValues {
+            bme280_temp_c: Some(22.5),
+            ds18b20_temp_c: Some(21.8),
+            humidity_pct: Some(45.0),
+            pressure_hpa: Some(1013.0),
+            soil_moisture_pct: Some(40.0),
+        }
+    }
+
+    #[test]
+    fn test_classify_status_fresh_reading_ok() {
+        let previous = reading_at(1_000_000, all_sensors());
+        // 5 minutes later with a 5 minute expected interval
+        let current = reading_at(1_000_000 + 300_000, all_sensors());
+
+        let status = classify_status(&current, Some(&previous), 300);
+        assert_eq!(status.bme280, SensorStatus::Ok);
+        assert_eq!(status.ds18b20, SensorStatus::Ok);
+        assert_eq!(status.soil_moisture, SensorStatus::Ok);
+
+        // Exactly 2x the interval is not yet stale
+        let current = reading_at(1_000_000 + 600_000, all_sensors());
+        let status = classify_status(&current, Some(&previous), 300);
+        assert_eq!(status.bme280, SensorStatus::Ok);
+
+        // No previous reading to compare against
+        let status = classify_status(&current, None, 300);
+        assert_eq!(status.bme280, SensorStatus::Ok);
+    }
+
+    #[test]
+    fn test_classify_status_large_gap_stale() {
+        let previous = reading_at(1_000_000, all_sensors());
+        // 11 minutes later with a 5 minute expected interval
+        let current = reading_at(1_000_000 + 660_000, all_sensors());
+
+        let status = classify_status(&current, Some(&previous), 300);
+        assert_eq!(status.bme280, SensorStatus::Stale);
+        assert_eq!(status.ds18b20, SensorStatus::Stale);
+        assert_eq!(status.soil_moisture, SensorStatus::Stale);
+    }
+
+    #[test]
+    fn test_classify_status_absent_value_missing() {
+        let previous = reading_at(1_000_000, all_sensors());
+        let mut sensors = all_sensors();
+        sensors.ds18b20_temp_c = None;
+        sensors.soil_moisture_pct = None;
+
+        // Missing takes precedence over Stale
+        let current = reading_at(1_000_000 + 660_000, sensors);
+        let status = classify_status(&current, Some(&previous), 300);
+        assert_eq!(status.bme280, SensorStatus::Stale);
+        assert_eq!(status.ds18b20, SensorStatus::Missing);
+        assert_eq!(status.soil_moisture, SensorStatus::Missing);
+    }
 }
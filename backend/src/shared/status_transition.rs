@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::plant_insights::HealthCategory;
+
+/// Milliseconds per hour, used by the health category thresholds
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// A change in a device's health category
+///
+/// `from` is `None` for the first observation of a device, so its history
+/// starts with the category it was first seen in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StatusTransition {
+    pub from: Option<HealthCategory>,
+    pub to: HealthCategory,
+    pub at_ms: i64,
+}
+
+/// Detect a health category change between two evaluations
+///
+/// Returns `None` when the category is unchanged. A device with no previous
+/// category produces a transition with `from: None`.
+pub fn detect_transition(
+    prev: Option<HealthCategory>,
+    curr: HealthCategory,
+    at_ms: i64,
+) -> Option<StatusTransition> {
+    if prev == Some(curr) {
+        return None;
+    }
+
+    Some(StatusTransition {
+        from: prev,
+        to: curr,
+        at_ms,
+    })
+}
+
+/// Derive a health category from the device status timestamps
+///
+/// Mirrors `derive_health_category` in the insights pipeline:
+/// - failing: an error within the last 24 hours (takes precedence)
+/// - healthy: ingested within the last 2 hours
+/// - stale: ingested between 2 and 6 hours ago
+/// - missing: ingested more than 6 hours ago, or never
+pub fn derive_health_category(
+    last_seen_ingest_time_ms: Option<i64>,
+    last_error_at_ms: Option<i64>,
+    now_ms: i64,
+) -> HealthCategory {
+    if let Some(error_at_ms) = last_error_at_ms {
+        if (now_ms - error_at_ms) as f64 / MS_PER_HOUR <= 24.0 {
+            return HealthCategory::Failing;
+        }
+    }
+
+    let Some(last_seen_ms) = last_seen_ingest_time_ms else {
+        return HealthCategory::Missing;
+    };
+
+    let hours_since_seen = (now_ms - last_seen_ms) as f64 / MS_PER_HOUR;
+    if hours_since_seen <= 2.0 {
+        HealthCategory::Healthy
+    } else if hours_since_seen <= 6.0 {
+        HealthCategory::Stale
+    } else {
+        HealthCategory::Missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW_MS: i64 = 1_704_067_200_000;
+    const HOUR_MS: i64 = 3_600_000;
+
+    #[test]
+    fn test_detect_transition_no_change() {
+        assert_eq!(
+            detect_transition(
+                Some(HealthCategory::Healthy),
+                HealthCategory::Healthy,
+                NOW_MS
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_detect_transition_category_change() {
+        let transition = detect_transition(
+            Some(HealthCategory::Healthy),
+            HealthCategory::Missing,
+            NOW_MS,
+        )
+        .unwrap();
+
+        assert_eq!(transition.from, Some(HealthCategory::Healthy));
+        assert_eq!(transition.to, HealthCategory::Missing);
+        assert_eq!(transition.at_ms, NOW_MS);
+    }
+
+    #[test]
+    fn test_detect_transition_first_observation() {
+        let transition = detect_transition(None, HealthCategory::Healthy, NOW_MS).unwrap();
+
+        assert_eq!(transition.from, None);
+        assert_eq!(transition.to, HealthCategory::Healthy);
+
+        let json = serde_json::to_string(&transition).unwrap();
+        assert_eq!(
+            json,
+            r#"{"from":null,"to":"healthy","at_ms":1704067200000}"#
+        );
+    }
+
+    #[test]
+    fn test_derive_health_category() {
+        assert_eq!(
+            derive_health_category(Some(NOW_MS - HOUR_MS), None, NOW_MS),
+            HealthCategory::Healthy
+        );
+        assert_eq!(
+            derive_health_category(Some(NOW_MS - 2 * HOUR_MS), None, NOW_MS),
+            HealthCategory::Healthy
+        );
+        assert_eq!(
+            derive_health_category(Some(NOW_MS - 3 * HOUR_MS), None, NOW_MS),
+            HealthCategory::Stale
+        );
+        assert_eq!(
+            derive_health_category(Some(NOW_MS - 7 * HOUR_MS), None, NOW_MS),
+            HealthCategory::Missing
+        );
+        assert_eq!(
+            derive_health_category(None, None, NOW_MS),
+            HealthCategory::Missing
+        );
+
+        // Recent error takes precedence over recent ingest
+        assert_eq!(
+            derive_health_category(Some(NOW_MS), Some(NOW_MS - 24 * HOUR_MS), NOW_MS),
+            HealthCategory::Failing
+        );
+        assert_eq!(
+            derive_health_category(Some(NOW_MS), Some(NOW_MS - 25 * HOUR_MS), NOW_MS),
+            HealthCategory::Healthy
+        );
+    }
+}
@@ -1,4 +1,20 @@
 use chrono::{DateTime, Datelike, Timelike, Utc};
+use thiserror::Error;
+
+use crate::validators::ValidationError;
+
+/// Errors from building a clock
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ClockError {
+    /// The input was not a valid RFC3339 timestamp
+    #[error("Malformed RFC3339 timestamp {input:?}: {source}")]
+    MalformedTimestamp {
+        input: String,
+        #[source]
+        source: chrono::ParseError,
+    },
+}
 
 /// Clock trait for abstracting time operations
 /// Provides methods for getting current time in different formats
@@ -10,6 +26,10 @@ pub trait Clock: Send + Sync {
     /// Get current time as epoch seconds (for TTL calculations)
     /// Returns seconds since Unix epoch (1970-01-01 00:00:00 UTC)
     fn now_epoch_seconds(&self) -> i64;
+
+    /// Get current time as epoch milliseconds (for event and ingest times)
+    /// Returns milliseconds since Unix epoch (1970-01-01 00:00:00 UTC)
+    fn now_epoch_ms(&self) -> i64;
 }
 
 /// Production implementation of Clock using system time
@@ -30,6 +50,10 @@ impl Clock for SystemClock {
     fn now_epoch_seconds(&self) -> i64 {
         Utc::now().timestamp()
     }
+
+    fn now_epoch_ms(&self) -> i64 {
+        Utc::now().timestamp_millis()
+    }
 }
 
 /// Test implementation of Clock with fixed/controllable time
@@ -46,8 +70,15 @@ impl FixedClock {
     }
 
     /// Create a FixedClock from RFC3339 string
-    pub fn from_rfc3339(timestamp_str: &str) -> Result<Self, chrono::ParseError> {
-        let timestamp = DateTime::parse_from_rfc3339(timestamp_str)?.with_timezone(&Utc);
+    ///
+    /// Returns `ClockError::MalformedTimestamp` when the string does not parse.
+    pub fn from_rfc3339(timestamp_str: &str) -> Result<Self, ClockError> {
+        let timestamp = DateTime::parse_from_rfc3339(timestamp_str)
+            .map_err(|source| ClockError::MalformedTimestamp {
+                input: timestamp_str.to_string(),
+                source,
+            })?
+            .with_timezone(&Utc);
         Ok(Self { timestamp })
     }
 
@@ -76,6 +107,10 @@ impl Clock for FixedClock {
     fn now_epoch_seconds(&self) -> i64 {
         self.timestamp.timestamp()
     }
+
+    fn now_epoch_ms(&self) -> i64 {
+        self.timestamp.timestamp_millis()
+    }
 }
 
 #[cfg(test)]
@@ -120,6 +155,38 @@ mod tests {
         assert!(epoch > 1705314000 && epoch < 1705318000); // Within reasonable range
     }
 
+    #[test]
+    fn test_fixed_clock_epoch_ms_agrees_with_rfc3339() {
+        let clock = FixedClock::from_rfc3339("2024-01-15T10:30:00.250Z").unwrap();
+
+        let parsed = DateTime::parse_from_rfc3339(&clock.now_rfc3339()).unwrap();
+        assert_eq!(clock.now_epoch_ms(), parsed.timestamp_millis());
+        assert_eq!(clock.now_epoch_ms(), 1705314600250);
+        assert_eq!(clock.now_epoch_ms() / 1000, clock.now_epoch_seconds());
+    }
+
+    #[test]
+    fn test_fixed_clock_from_rfc3339_malformed() {
+        for input in ["", "2024-01-15", "2024-01-15T10:30:00", "yesterday"] {
+            let err = FixedClock::from_rfc3339(input).expect_err(input);
+            assert!(
+                matches!(&err, ClockError::MalformedTimestamp { input: i, .. } if i == input),
+                "{:?}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_system_clock_now_epoch_ms() {
+        let clock = SystemClock::new();
+        let before = clock.now_epoch_seconds();
+        let now_ms = clock.now_epoch_ms();
+
+        assert!(now_ms / 1000 >= before);
+        assert!(now_ms / 1000 <= clock.now_epoch_seconds());
+    }
+
     #[test]
     fn test_fixed_clock_from_epoch_seconds() {
         let clock = FixedClock::from_epoch_seconds(1705316400);
@@ -185,6 +252,33 @@ mod tests {
     }
 }
 
+/// Parse a timestamp given as epoch milliseconds or RFC3339 into epoch milliseconds
+///
+/// All-digit input is epoch milliseconds (`1705312800000`); anything else
+/// must be a full RFC3339 timestamp with an offset (`2024-01-15T10:00:00Z`).
+/// Dates without a time or offset, signed numbers and blank input are
+/// rejected rather than guessed at.
+pub fn parse_timestamp(input: &str) -> Result<i64, ValidationError> {
+    if input.is_empty() {
+        return Err(ValidationError::new("timestamp", "is required"));
+    }
+
+    if input.bytes().all(|b| b.is_ascii_digit()) {
+        return input
+            .parse::<i64>()
+            .map_err(|_| ValidationError::new("timestamp", "Epoch milliseconds out of range"));
+    }
+
+    DateTime::parse_from_rfc3339(input)
+        .map(|dt| dt.timestamp_millis())
+        .map_err(|_| {
+            ValidationError::new(
+                "timestamp",
+                "Must be epoch milliseconds or an RFC3339 timestamp",
+            )
+        })
+}
+
 // ============================================================================
 // Time Window Utilities for Plant Insights
 // ============================================================================
@@ -246,6 +340,22 @@ pub fn has_clock_skew(event_time_ms: i64, ingest_time_ms: i64) -> bool {
     skew_ms > (5 * 60 * 1000) // 5 minutes in milliseconds
 }
 
+/// Default age after which an ingested reading is considered buffered (10 minutes)
+pub const BUFFERED_THRESHOLD_MS: i64 = 10 * 60 * 1000;
+
+/// Detect readings that were buffered on the device and sent late
+/// Returns true if timestamp_ms is older than ingest_ms - threshold_ms
+pub fn is_buffered(timestamp_ms: i64, ingest_ms: i64, threshold_ms: i64) -> bool {
+    timestamp_ms < ingest_ms - threshold_ms
+}
+
+/// Detect a batch that moves backwards in time within a boot session
+/// Returns true if the earliest reading in the batch is before the last stored
+/// reading for the same boot (e.g. the device clock was reset mid-session)
+pub fn detect_time_regression(last_stored_ms: Option<i64>, batch_min_ms: i64) -> bool {
+    matches!(last_stored_ms, Some(last) if batch_min_ms < last)
+}
+
 /// Calculate clock skew in seconds
 pub fn calculate_skew_seconds(event_time_ms: i64, ingest_time_ms: i64) -> i64 {
     (ingest_time_ms - event_time_ms) / 1000
@@ -255,6 +365,55 @@ pub fn calculate_skew_seconds(event_time_ms: i64, ingest_time_ms: i64) -> i64 {
 mod time_window_tests {
     use super::*;
 
+    #[test]
+    fn test_parse_timestamp_epoch_ms() {
+        assert_eq!(parse_timestamp("1705312800000").unwrap(), 1705312800000);
+        assert_eq!(parse_timestamp("0").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_parse_timestamp_rfc3339() {
+        assert_eq!(
+            parse_timestamp("2024-01-15T10:00:00Z").unwrap(),
+            1705312800000
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-15T10:00:00.123Z").unwrap(),
+            1705312800123
+        );
+        // Offsets are converted to UTC
+        assert_eq!(
+            parse_timestamp("2024-01-15T12:00:00+02:00").unwrap(),
+            1705312800000
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_forms_agree() {
+        assert_eq!(
+            parse_timestamp("2024-01-15T10:00:00Z").unwrap(),
+            parse_timestamp("1705312800000").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_rejects_malformed() {
+        for input in [
+            "",
+            " 1705312800000",
+            "-1705312800000",
+            "+1705312800000",
+            "1705312800000.5",
+            "2024-01-15",
+            "2024-01-15T10:00:00",
+            "2024-01-15 10:00",
+            "99999999999999999999",
+            "yesterday",
+        ] {
+            assert!(parse_timestamp(input).is_err(), "accepted {:?}", input);
+        }
+    }
+
     #[test]
     fn test_align_to_minute() {
         // 2024-01-15 10:30:45.123 -> 2024-01-15 10:30:00.000
@@ -373,4 +532,48 @@ mod time_window_tests {
 
         assert_eq!(calculate_skew_seconds(event_time_ms, ingest_time_ms), -10);
     }
+
+    #[test]
+    fn test_is_buffered_fresh_reading() {
+        let ingest_ms = 1705316400000;
+        let timestamp_ms = ingest_ms - 30_000; // 30 seconds old
+
+        assert!(!is_buffered(timestamp_ms, ingest_ms, BUFFERED_THRESHOLD_MS));
+    }
+
+    #[test]
+    fn test_is_buffered_old_reading() {
+        let ingest_ms = 1705316400000;
+        let timestamp_ms = ingest_ms - 2 * 3600 * 1000; // 2 hours old
+
+        assert!(is_buffered(timestamp_ms, ingest_ms, BUFFERED_THRESHOLD_MS));
+    }
+
+    #[test]
+    fn test_detect_time_regression_earlier_batch() {
+        let last_stored_ms = 1705316400000;
+
+        assert!(detect_time_regression(
+            Some(last_stored_ms),
+            last_stored_ms - 1
+        ));
+        assert!(detect_time_regression(Some(last_stored_ms), 1000));
+    }
+
+    #[test]
+    fn test_detect_time_regression_normal_progression() {
+        let last_stored_ms = 1705316400000;
+
+        assert!(!detect_time_regression(
+            Some(last_stored_ms),
+            last_stored_ms + 60_000
+        ));
+        // A retried batch starting at the same instant is not a regression
+        assert!(!detect_time_regression(
+            Some(last_stored_ms),
+            last_stored_ms
+        ));
+        // Nothing stored yet for this boot
+        assert!(!detect_time_regression(None, 1000));
+    }
 }
@@ -0,0 +1,111 @@
+use crate::plant_insights::{SensorStats, Trend};
+
+/// Relative change in average below which two windows count as `Stable` (2%)
+pub const TREND_DEADBAND_FRACTION: f64 = 0.02;
+
+/// Classify the change from one aggregate window to the next
+///
+/// Compares the windows' averages. A change smaller than
+/// `TREND_DEADBAND_FRACTION` of the previous average is `Stable`; otherwise
+/// the direction decides: with `improving_when_higher` (e.g. soil moisture) a
+/// rise is `Improving`, without it (e.g. temperature stress) a fall is.
+/// Windows without valid samples have no meaningful average, so they are
+/// `Stable`.
+pub fn compute_trend(
+    previous: &SensorStats,
+    current: &SensorStats,
+    improving_when_higher: bool,
+) -> Trend {
+    if previous.valid_count == 0 || current.valid_count == 0 {
+        return Trend::Stable;
+    }
+
+    let change = current.avg - previous.avg;
+    let within_deadband = if previous.avg == 0.0 {
+        change == 0.0
+    } else {
+        (change / previous.avg).abs() < TREND_DEADBAND_FRACTION
+    };
+
+    if within_deadband {
+        Trend::Stable
+    } else if (change > 0.0) == improving_when_higher {
+        Trend::Improving
+    } else {
+        Trend::Declining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(avg: f64) -> SensorStats {
+        let mut stats = SensorStats::default();
+        stats.record(Some(avg));
+        stats
+    }
+
+    #[test]
+    fn test_compute_trend_clear_improvement() {
+        assert_eq!(
+            compute_trend(&stats(30.0), &stats(40.0), true),
+            Trend::Improving
+        );
+    }
+
+    #[test]
+    fn test_compute_trend_clear_decline() {
+        assert_eq!(
+            compute_trend(&stats(40.0), &stats(30.0), true),
+            Trend::Declining
+        );
+    }
+
+    #[test]
+    fn test_compute_trend_within_deadband_is_stable() {
+        // 1.5% up and down
+        assert_eq!(
+            compute_trend(&stats(40.0), &stats(40.6), true),
+            Trend::Stable
+        );
+        assert_eq!(
+            compute_trend(&stats(40.0), &stats(39.4), true),
+            Trend::Stable
+        );
+        // Just past the band
+        assert_eq!(
+            compute_trend(&stats(40.0), &stats(41.0), true),
+            Trend::Improving
+        );
+    }
+
+    #[test]
+    fn test_compute_trend_honors_direction() {
+        // Temperature falling out of a stress range is an improvement
+        assert_eq!(
+            compute_trend(&stats(35.0), &stats(28.0), false),
+            Trend::Improving
+        );
+        assert_eq!(
+            compute_trend(&stats(28.0), &stats(35.0), false),
+            Trend::Declining
+        );
+    }
+
+    #[test]
+    fn test_compute_trend_without_samples_is_stable() {
+        let empty = SensorStats::default();
+        assert_eq!(compute_trend(&empty, &stats(40.0), true), Trend::Stable);
+        assert_eq!(compute_trend(&stats(40.0), &empty, true), Trend::Stable);
+    }
+
+    #[test]
+    fn test_compute_trend_from_zero_average() {
+        assert_eq!(compute_trend(&stats(0.0), &stats(0.0), true), Trend::Stable);
+        assert_eq!(
+            compute_trend(&stats(0.0), &stats(5.0), true),
+            Trend::Improving
+        );
+    }
+}
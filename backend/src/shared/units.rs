@@ -0,0 +1,244 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::domain::SensorValues;
+
+/// Inches of mercury per hectopascal
+pub const INHG_PER_HPA: f64 = 0.02953;
+
+/// Unit system requested for sensor values in API responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitSystem {
+    /// Celsius and hectopascals (as stored)
+    #[default]
+    Metric,
+    /// Fahrenheit and inches of mercury
+    Imperial,
+}
+
+impl UnitSystem {
+    /// Parse the `units` query parameter value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "metric" => Some(UnitSystem::Metric),
+            "imperial" => Some(UnitSystem::Imperial),
+            _ => None,
+        }
+    }
+}
+
+/// Units applied to the sensor values in a response
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitsMetadata {
+    pub temperature: &'static str,
+    pub pressure: &'static str,
+    pub humidity: &'static str,
+    pub soil_moisture: &'static str,
+}
+
+impl UnitsMetadata {
+    pub fn for_system(system: UnitSystem) -> Self {
+        let (temperature, pressure) = match system {
+            UnitSystem::Metric => ("C", "hPa"),
+            UnitSystem::Imperial => ("F", "inHg"),
+        };
+
+        Self {
+            temperature,
+            pressure,
+            humidity: "%",
+            soil_moisture: "%",
+        }
+    }
+}
+
+/// Convert degrees Celsius to degrees Fahrenheit
+pub fn celsius_to_fahrenheit(celsius: f64) -> f64 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// Convert hectopascals to inches of mercury, rounded to 2 decimals
+pub fn hpa_to_inhg(hpa: f64) -> f64 {
+    (hpa * INHG_PER_HPA * 100.0).round() / 100.0
+}
+
+/// Sensor values as returned by the API
+///
+/// Temperatures and pressure are serialized under the key for their unit
+/// (`bme280_temp_c`/`bme280_temp_f`, `ds18b20_temp_c`/`ds18b20_temp_f`,
+/// `pressure_hpa`/`pressure_inhg`), so only one key of each pair is present.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResponseSensorValues {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bme280_temp_c: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bme280_temp_f: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ds18b20_temp_c: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ds18b20_temp_f: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub humidity_pct: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure_hpa: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure_inhg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soil_moisture_pct: Option<f64>,
+    /// Sensors without a typed field, returned as stored (never converted)
+    #[serde(flatten)]
+    pub extra_sensors: HashMap<String, f64>,
+}
+
+impl From<SensorValues> for ResponseSensorValues {
+    /// Stored (metric) values, unconverted
+    fn from(values: SensorValues) -> Self {
+        Self {
+            bme280_temp_c: values.bme280_temp_c,
+            bme280_temp_f: None,
+            ds18b20_temp_c: values.ds18b20_temp_c,
+            ds18b20_temp_f: None,
+            humidity_pct: values.humidity_pct,
+            pressure_hpa: values.pressure_hpa,
+            pressure_inhg: None,
+            soil_moisture_pct: values.soil_moisture_pct,
+            extra_sensors: values.extra_sensors,
+        }
+    }
+}
+
+/// Convert stored (metric) sensor values to the requested unit system
+///
+/// Humidity and soil moisture are percentages and are never converted.
+/// Absent values stay absent.
+pub fn convert_sensor_values(values: &SensorValues, system: UnitSystem) -> ResponseSensorValues {
+    match system {
+        UnitSystem::Metric => values.clone().into(),
+        UnitSystem::Imperial => ResponseSensorValues {
+            bme280_temp_c: None,
+            bme280_temp_f: values.bme280_temp_c.map(celsius_to_fahrenheit),
+            ds18b20_temp_c: None,
+            ds18b20_temp_f: values.ds18b20_temp_c.map(celsius_to_fahrenheit),
+            humidity_pct: values.humidity_pct,
+            pressure_hpa: None,
+            pressure_inhg: values.pressure_hpa.map(hpa_to_inhg),
+            soil_moisture_pct: values.soil_moisture_pct,
+            extra_sensors: values.extra_sensors.clone(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_values() -> SensorValues {
+        SensorValues {
+            bme280_temp_c: Some(22.5),
+            ds18b20_temp_c: Some(-40.0),
+            humidity_pct: Some(45.2),
+            pressure_hpa: Some(1013.25),
+            soil_moisture_pct: None,
+            extra_sensors: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_celsius_to_fahrenheit() {
+        assert_eq!(celsius_to_fahrenheit(0.0), 32.0);
+        assert_eq!(celsius_to_fahrenheit(100.0), 212.0);
+        assert_eq!(celsius_to_fahrenheit(-40.0), -40.0);
+        assert!((celsius_to_fahrenheit(22.5) - 72.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hpa_to_inhg() {
+        assert_eq!(hpa_to_inhg(1013.25), 29.92);
+        assert_eq!(hpa_to_inhg(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_convert_sensor_values_metric_passthrough() {
+        let values = sample_values();
+        let converted = convert_sensor_values(&values, UnitSystem::Metric);
+
+        assert_eq!(converted.bme280_temp_c, values.bme280_temp_c);
+        assert_eq!(converted.bme280_temp_f, None);
+        assert_eq!(converted.ds18b20_temp_c, values.ds18b20_temp_c);
+        assert_eq!(converted.ds18b20_temp_f, None);
+        assert_eq!(converted.humidity_pct, values.humidity_pct);
+        assert_eq!(converted.pressure_hpa, values.pressure_hpa);
+        assert_eq!(converted.pressure_inhg, None);
+        assert_eq!(converted.soil_moisture_pct, values.soil_moisture_pct);
+    }
+
+    #[test]
+    fn test_convert_sensor_values_imperial() {
+        let converted = convert_sensor_values(&sample_values(), UnitSystem::Imperial);
+
+        assert!((converted.bme280_temp_f.unwrap() - 72.5).abs() < 1e-9);
+        assert_eq!(converted.bme280_temp_c, None);
+        assert_eq!(converted.ds18b20_temp_f, Some(-40.0));
+        assert_eq!(converted.ds18b20_temp_c, None);
+        assert_eq!(converted.humidity_pct, Some(45.2));
+        assert_eq!(converted.pressure_hpa, None);
+        assert_eq!(converted.pressure_inhg, Some(29.92));
+        assert_eq!(converted.soil_moisture_pct, None);
+
+        let json = serde_json::to_string(&converted).unwrap();
+        assert!(json.contains("\"pressure_inhg\":29.92"));
+        assert!(json.contains("\"ds18b20_temp_f\":-40.0"));
+        assert!(!json.contains("pressure_hpa"));
+        assert!(!json.contains("_temp_c"));
+    }
+
+    #[test]
+    fn test_convert_sensor_values_imperial_absent_pressure() {
+        let values = SensorValues {
+            pressure_hpa: None,
+            ..sample_values()
+        };
+        let converted = convert_sensor_values(&values, UnitSystem::Imperial);
+
+        assert_eq!(converted.pressure_inhg, None);
+        let json = serde_json::to_string(&converted).unwrap();
+        assert!(!json.contains("pressure"));
+    }
+
+    #[test]
+    fn test_convert_sensor_values_keeps_extra_sensors() {
+        let mut values = sample_values();
+        values
+            .extra_sensors
+            .insert("sht31_temp_c".to_string(), 21.4);
+
+        for system in [UnitSystem::Metric, UnitSystem::Imperial] {
+            let converted = convert_sensor_values(&values, system);
+            assert_eq!(converted.extra_sensors.get("sht31_temp_c"), Some(&21.4));
+
+            let json = serde_json::to_value(&converted).unwrap();
+            assert_eq!(json["sht31_temp_c"], 21.4);
+        }
+    }
+
+    #[test]
+    fn test_unit_system_parse_and_default() {
+        assert_eq!(UnitSystem::default(), UnitSystem::Metric);
+        assert_eq!(UnitSystem::parse("metric"), Some(UnitSystem::Metric));
+        assert_eq!(UnitSystem::parse("imperial"), Some(UnitSystem::Imperial));
+        assert_eq!(UnitSystem::parse("Imperial"), None);
+        assert_eq!(UnitSystem::parse("kelvin"), None);
+    }
+
+    #[test]
+    fn test_units_metadata_reflects_system() {
+        let metric = UnitsMetadata::for_system(UnitSystem::Metric);
+        assert_eq!(metric.temperature, "C");
+        assert_eq!(metric.pressure, "hPa");
+
+        let imperial = UnitsMetadata::for_system(UnitSystem::Imperial);
+        assert_eq!(imperial.temperature, "F");
+        assert_eq!(imperial.pressure, "inHg");
+        assert_eq!(imperial.humidity, "%");
+    }
+}
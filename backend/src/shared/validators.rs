@@ -1,6 +1,11 @@
 use regex::Regex;
+use std::collections::HashMap;
 use std::sync::OnceLock;
 
+use crate::domain::{
+    Capabilities, Reading, MAX_READING_SEQ, SENSOR_STATUS_ERROR, SENSOR_STATUS_OK,
+};
+
 /// Validation error type
 #[derive(Debug, Clone)]
 pub struct ValidationError {
@@ -29,6 +34,72 @@ impl std::fmt::Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+/// Default page size for list endpoints when `limit` is absent or unparseable
+pub const DEFAULT_PAGE_LIMIT: i32 = 50;
+
+/// Maximum page size for GET /devices
+pub const MAX_DEVICES_PAGE_LIMIT: i32 = 100;
+
+/// Maximum page size for GET /api-keys
+pub const MAX_API_KEYS_PAGE_LIMIT: i32 = 100;
+
+/// Maximum page size for GET /devices/{hardware_id}/readings
+pub const MAX_READINGS_PAGE_LIMIT: i32 = 1000;
+
+/// Maximum page size for GET /devices/{hardware_id}/events
+pub const MAX_EVENTS_PAGE_LIMIT: i32 = 1000;
+
+/// Maximum page size for GET /devices/{hardware_id}/status/history
+pub const MAX_STATUS_HISTORY_PAGE_LIMIT: i32 = 100;
+
+/// Maximum devices per page for GET /fleet/usage (each device costs a count query)
+pub const MAX_FLEET_USAGE_PAGE_LIMIT: i32 = 50;
+
+/// Default device cap for GET /fleet/health (each device costs a status lookup)
+pub const DEFAULT_FLEET_HEALTH_MAX_DEVICES: i32 = 500;
+
+/// Maximum device cap for GET /fleet/health
+pub const MAX_FLEET_HEALTH_MAX_DEVICES: i32 = 2000;
+
+/// Default number of boots for GET /devices/{hardware_id}/boots/latest-readings
+pub const DEFAULT_BOOTS_LIMIT: i32 = 10;
+
+/// Maximum number of boots for GET /devices/{hardware_id}/boots/latest-readings
+pub const MAX_BOOTS_LIMIT: i32 = 50;
+
+/// Maximum number of distinct hardware IDs in a POST /devices/latest request
+pub const MAX_LATEST_BATCH_HARDWARE_IDS: usize = 50;
+
+/// Maximum number of distinct key IDs in a POST /api-keys/revoke-batch request
+pub const MAX_REVOKE_BATCH_KEY_IDS: usize = 100;
+
+/// Maximum number of entries in a request's batch_metadata
+pub const MAX_BATCH_METADATA_KEYS: usize = 10;
+
+/// Maximum length of a batch_metadata value in characters
+pub const MAX_BATCH_METADATA_VALUE_LEN: usize = 128;
+
+/// Maximum length of a device note's text in characters
+pub const MAX_NOTE_TEXT_LEN: usize = 1000;
+
+/// Maximum length of an API key description in characters, after trimming
+pub const MAX_API_KEY_DESCRIPTION_LEN: usize = 256;
+
+/// Random bytes in an issued API key
+pub const API_KEY_BYTES: usize = 32;
+
+/// Length of an issued API key: `API_KEY_BYTES`, hex-encoded
+pub const API_KEY_HEX_LEN: usize = API_KEY_BYTES * 2;
+
+/// Sensor names a device may report in `capabilities.sensors`
+pub const KNOWN_SENSORS: &[&str] = &["bme280", "ds18b20", "soil_moisture"];
+
+/// Feature keys a device may report in `capabilities.features`
+pub const KNOWN_FEATURES: &[&str] = &["tft_display", "offline_buffering"];
+
+/// Default maximum request body size in bytes (256 KB)
+pub const DEFAULT_MAX_BODY_BYTES: usize = 256 * 1024;
+
 /// Validate MAC address format (XX:XX:XX:XX:XX:XX with uppercase hex)
 pub fn validate_mac_address(mac: &str) -> Result<(), ValidationError> {
     static MAC_REGEX: OnceLock<Regex> = OnceLock::new();
@@ -47,18 +118,23 @@ pub fn validate_mac_address(mac: &str) -> Result<(), ValidationError> {
     }
 }
 
-/// Validate UUID v4 format
-pub fn validate_uuid_v4(uuid_str: &str) -> Result<(), ValidationError> {
+/// UUID versions accepted for device boot_ids (random v4 and time-ordered v7)
+pub const BOOT_ID_UUID_VERSIONS: &[usize] = &[4, 7];
+
+/// Validate UUID format and that its version is one of `allowed_versions`
+pub fn validate_uuid(uuid_str: &str, allowed_versions: &[usize]) -> Result<(), ValidationError> {
     match uuid::Uuid::parse_str(uuid_str) {
         Ok(uuid) => {
-            // Check version is 4
-            if uuid.get_version_num() != 4 {
+            let version = uuid.get_version_num();
+            if !allowed_versions.contains(&version) {
+                let allowed = allowed_versions
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" or ");
                 return Err(ValidationError::new(
                     "uuid",
-                    format!(
-                        "UUID must be version 4, got version {}",
-                        uuid.get_version_num()
-                    ),
+                    format!("UUID must be version {}, got version {}", allowed, version),
                 ));
             }
             Ok(())
@@ -67,6 +143,11 @@ pub fn validate_uuid_v4(uuid_str: &str) -> Result<(), ValidationError> {
     }
 }
 
+/// Validate UUID v4 format
+pub fn validate_uuid_v4(uuid_str: &str) -> Result<(), ValidationError> {
+    validate_uuid(uuid_str, &[4])
+}
+
 /// Validate RFC3339 timestamp string for metadata
 pub fn validate_rfc3339_timestamp(timestamp: &str) -> Result<(), ValidationError> {
     match chrono::DateTime::parse_from_rfc3339(timestamp) {
@@ -142,6 +223,180 @@ pub fn validate_batch_id(batch_id: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Whether a raw API key has the shape of an issued key (`API_KEY_HEX_LEN` hex characters)
+///
+/// Keys are issued in lowercase hex, but either case is accepted.
+pub fn is_api_key_format(key: &str) -> bool {
+    key.len() == API_KEY_HEX_LEN && key.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Validate a device-reported reading quality score (0.0 to 1.0 inclusive)
+pub fn validate_quality(quality: f64) -> Result<(), ValidationError> {
+    if !(0.0..=1.0).contains(&quality) {
+        return Err(ValidationError::new(
+            "quality",
+            format!("Quality {} must be between 0 and 1", quality),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate that a sensor value is a finite number
+///
+/// JSON cannot carry NaN or ±Infinity but CBOR can, and DynamoDB rejects them
+/// as numbers, so they are refused before anything is written.
+pub fn validate_sensor_value(field: &str, value: f64) -> Result<(), ValidationError> {
+    if !value.is_finite() {
+        return Err(ValidationError::new(
+            field,
+            format!("Sensor value {} must be a finite number", value),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate a device-assigned reading sequence (0 to `MAX_READING_SEQ` inclusive)
+pub fn validate_seq(seq: u32) -> Result<(), ValidationError> {
+    if seq > MAX_READING_SEQ {
+        return Err(ValidationError::new(
+            "seq",
+            format!("Seq {} must be at most {}", seq, MAX_READING_SEQ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate batch-level metadata (at most 10 keys, values at most 128 chars)
+pub fn validate_batch_metadata(meta: &HashMap<String, String>) -> Result<(), ValidationError> {
+    if meta.len() > MAX_BATCH_METADATA_KEYS {
+        return Err(ValidationError::new(
+            "batch_metadata",
+            format!(
+                "batch_metadata has {} keys, maximum is {}",
+                meta.len(),
+                MAX_BATCH_METADATA_KEYS
+            ),
+        ));
+    }
+
+    if let Some((key, _)) = meta
+        .iter()
+        .find(|(_, v)| v.chars().count() > MAX_BATCH_METADATA_VALUE_LEN)
+    {
+        return Err(ValidationError::new(
+            "batch_metadata",
+            format!(
+                "Value for '{}' exceeds maximum length of {} characters",
+                key, MAX_BATCH_METADATA_VALUE_LEN
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate that each sensor's status agrees with which of its values are present
+///
+/// A status of `"ok"` requires the sensor's primary value, and a status of
+/// `"error"` forbids all of its values. BME280 covers `bme280_temp_c`,
+/// `humidity_pct` and `pressure_hpa`, but only the temperature is required
+/// when it is ok: firmware reports the status from the temperature read and
+/// nulls humidity or pressure on their own when those reads fail.
+pub fn validate_status_value_consistency(reading: &Reading) -> Result<(), ValidationError> {
+    let sensors = &reading.sensors;
+    let status = &reading.sensor_status;
+
+    check_sensor_values(
+        "bme280",
+        &status.bme280,
+        &[
+            ("bme280_temp_c", sensors.bme280_temp_c),
+            ("humidity_pct", sensors.humidity_pct),
+            ("pressure_hpa", sensors.pressure_hpa),
+        ],
+    )?;
+    check_sensor_values(
+        "ds18b20",
+        &status.ds18b20,
+        &[("ds18b20_temp_c", sensors.ds18b20_temp_c)],
+    )?;
+    check_sensor_values(
+        "soil_moisture",
+        &status.soil_moisture,
+        &[("soil_moisture_pct", sensors.soil_moisture_pct)],
+    )
+}
+
+/// Check one sensor's status against its values; `values[0]` is the primary value
+fn check_sensor_values(
+    sensor: &str,
+    status: &str,
+    values: &[(&str, Option<f64>)],
+) -> Result<(), ValidationError> {
+    let field = format!("sensor_status.{}", sensor);
+
+    if status == SENSOR_STATUS_OK {
+        if let Some((name, None)) = values.first() {
+            return Err(ValidationError::new(
+                field,
+                format!("{} is \"ok\" but {} is missing", sensor, name),
+            ));
+        }
+    }
+
+    if status == SENSOR_STATUS_ERROR {
+        if let Some((name, _)) = values.iter().find(|(_, value)| value.is_some()) {
+            return Err(ValidationError::new(
+                field,
+                format!("{} is \"error\" but {} has a value", sensor, name),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a request body against a size limit before it is parsed
+///
+/// Rejects the body when either its buffered length or the declared
+/// `Content-Length` exceeds `max_body_bytes`. The buffered length is always
+/// checked, so an understated header cannot slip a large body through; a
+/// header claiming more than the limit is rejected even if the body is
+/// smaller. An unparseable `Content-Length` is ignored.
+pub fn validate_body_size(
+    content_length: Option<&str>,
+    body_len: usize,
+    max_body_bytes: usize,
+) -> Result<(), ValidationError> {
+    let declared_len = content_length.and_then(|v| v.trim().parse::<u64>().ok());
+    let too_large =
+        body_len > max_body_bytes || declared_len.is_some_and(|len| len > max_body_bytes as u64);
+
+    if too_large {
+        return Err(ValidationError::new(
+            "body",
+            format!("payload too large (limit {} bytes)", max_body_bytes),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Resolve a page size: `default` when absent, otherwise clamped to 1..=max
+pub fn clamp_limit(raw: Option<i32>, default: i32, max: i32) -> i32 {
+    raw.unwrap_or(default).min(max).max(1)
+}
+
+/// Parse a `limit` query parameter and clamp it with `clamp_limit`
+///
+/// Values that are not integers fall back to `default`.
+pub fn parse_and_clamp_limit(raw: Option<&str>, default: i32, max: i32) -> i32 {
+    clamp_limit(raw.and_then(|s| s.trim().parse().ok()), default, max)
+}
+
 /// Validate friendly_name format
 /// Friendly name is optional, max 64 chars, safe ASCII only
 pub fn validate_friendly_name(friendly_name: &str) -> Result<(), ValidationError> {
@@ -177,6 +432,70 @@ pub fn validate_friendly_name(friendly_name: &str) -> Result<(), ValidationError
     Ok(())
 }
 
+/// Validate the text of a device note
+/// Must be non-empty and at most `MAX_NOTE_TEXT_LEN` characters
+pub fn validate_note_text(text: &str) -> Result<(), ValidationError> {
+    if text.trim().is_empty() {
+        return Err(ValidationError::new("text", "Note text cannot be empty"));
+    }
+
+    let len = text.chars().count();
+    if len > MAX_NOTE_TEXT_LEN {
+        return Err(ValidationError::new(
+            "text",
+            format!(
+                "Note text length {} exceeds maximum of {} characters",
+                len, MAX_NOTE_TEXT_LEN
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate capability names against `KNOWN_SENSORS` and `KNOWN_FEATURES`
+/// Catches typos such as `tft_dispaly` before they are stored on the device
+pub fn validate_capabilities(capabilities: &Capabilities) -> Result<(), ValidationError> {
+    let unknown_sensors: Vec<&str> = capabilities
+        .sensors
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !KNOWN_SENSORS.contains(name))
+        .collect();
+
+    if !unknown_sensors.is_empty() {
+        return Err(ValidationError::new(
+            "capabilities.sensors",
+            format!(
+                "Unknown sensor(s): {} (expected one of: {})",
+                unknown_sensors.join(", "),
+                KNOWN_SENSORS.join(", ")
+            ),
+        ));
+    }
+
+    let mut unknown_features: Vec<&str> = capabilities
+        .features
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !KNOWN_FEATURES.contains(key))
+        .collect();
+    unknown_features.sort_unstable();
+
+    if !unknown_features.is_empty() {
+        return Err(ValidationError::new(
+            "capabilities.features",
+            format!(
+                "Unknown feature(s): {} (expected one of: {})",
+                unknown_features.join(", "),
+                KNOWN_FEATURES.join(", ")
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,6 +526,36 @@ mod tests {
         assert!(validate_uuid_v4("not-a-uuid").is_err());
         assert!(validate_uuid_v4("550e8400-e29b-11d4-a716-446655440000").is_err()); // UUID v1
         assert!(validate_uuid_v4("").is_err());
+
+        // Strict v4 still rejects v7
+        let err = validate_uuid_v4("018f3e5c-7b2a-7cde-8f01-23456789abcd").unwrap_err();
+        assert_eq!(err.message, "UUID must be version 4, got version 7");
+    }
+
+    #[test]
+    fn test_validate_uuid_boot_id_versions() {
+        // UUID v7 (time-ordered)
+        assert!(validate_uuid(
+            "018f3e5c-7b2a-7cde-8f01-23456789abcd",
+            BOOT_ID_UUID_VERSIONS
+        )
+        .is_ok());
+        // UUID v4
+        assert!(validate_uuid(
+            "550e8400-e29b-41d4-a716-446655440000",
+            BOOT_ID_UUID_VERSIONS
+        )
+        .is_ok());
+
+        // UUID v1 is not in the default allow-list
+        let err = validate_uuid(
+            "550e8400-e29b-11d4-a716-446655440000",
+            BOOT_ID_UUID_VERSIONS,
+        )
+        .unwrap_err();
+        assert_eq!(err.message, "UUID must be version 4 or 7, got version 1");
+
+        assert!(validate_uuid("not-a-uuid", BOOT_ID_UUID_VERSIONS).is_err());
     }
 
     #[test]
@@ -255,6 +604,100 @@ mod tests {
         assert!(validate_batch_id("batch\x00id").is_err()); // null byte
     }
 
+    #[test]
+    fn test_is_api_key_format() {
+        let key = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        assert!(is_api_key_format(key));
+        assert!(is_api_key_format(&key.to_uppercase()));
+
+        assert!(!is_api_key_format(""));
+        assert!(!is_api_key_format(&key[..API_KEY_HEX_LEN - 1]));
+        assert!(!is_api_key_format(&format!("{}0", key)));
+        let non_hex = format!("{}g", &key[..API_KEY_HEX_LEN - 1]);
+        assert!(!is_api_key_format(&non_hex));
+    }
+
+    #[test]
+    fn test_validate_quality() {
+        assert!(validate_quality(0.0).is_ok());
+        assert!(validate_quality(0.5).is_ok());
+        assert!(validate_quality(1.0).is_ok());
+
+        assert!(validate_quality(-0.01).is_err());
+        assert!(validate_quality(1.01).is_err());
+        assert!(validate_quality(f64::NAN).is_err());
+        assert!(validate_quality(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_validate_sensor_value() {
+        assert!(validate_sensor_value("humidity_pct", 45.2).is_ok());
+        assert!(validate_sensor_value("humidity_pct", -40.0).is_ok());
+
+        for invalid in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = validate_sensor_value("humidity_pct", invalid).unwrap_err();
+            assert_eq!(err.field, "humidity_pct");
+        }
+    }
+
+    #[test]
+    fn test_validate_seq() {
+        assert!(validate_seq(0).is_ok());
+        assert!(validate_seq(MAX_READING_SEQ).is_ok());
+
+        assert!(validate_seq(MAX_READING_SEQ + 1).is_err());
+        assert!(validate_seq(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_validate_note_text() {
+        assert!(validate_note_text("repotted plant here").is_ok());
+        assert!(validate_note_text(&"a".repeat(MAX_NOTE_TEXT_LEN)).is_ok());
+        // Length is counted in characters, not bytes
+        assert!(validate_note_text(&"é".repeat(MAX_NOTE_TEXT_LEN)).is_ok());
+
+        assert!(validate_note_text("").is_err());
+        assert!(validate_note_text("   ").is_err());
+        let err = validate_note_text(&"a".repeat(MAX_NOTE_TEXT_LEN + 1)).unwrap_err();
+        assert_eq!(err.field, "text");
+        assert!(err.message.contains("1001"));
+    }
+
+    fn capabilities(sensors: &[&str], features: &[&str]) -> Capabilities {
+        Capabilities {
+            sensors: sensors.iter().map(|s| s.to_string()).collect(),
+            features: features.iter().map(|f| (f.to_string(), true)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_capabilities_known() {
+        let caps = capabilities(KNOWN_SENSORS, KNOWN_FEATURES);
+        assert!(validate_capabilities(&caps).is_ok());
+        assert!(validate_capabilities(&capabilities(&["bme280"], &[])).is_ok());
+    }
+
+    #[test]
+    fn test_validate_capabilities_unknown_sensor() {
+        let err = validate_capabilities(&capabilities(&["bme280", "bme680"], &["tft_display"]))
+            .unwrap_err();
+
+        assert_eq!(err.field, "capabilities.sensors");
+        assert!(err.message.starts_with("Unknown sensor(s): bme680 ("));
+    }
+
+    #[test]
+    fn test_validate_capabilities_unknown_feature() {
+        let err = validate_capabilities(&capabilities(
+            &["bme280"],
+            &["tft_dispaly", "offline_buffering"],
+        ))
+        .unwrap_err();
+
+        assert_eq!(err.field, "capabilities.features");
+        assert!(err.message.starts_with("Unknown feature(s): tft_dispaly ("));
+    }
+
     #[test]
     fn test_validate_friendly_name() {
         // Valid friendly names
@@ -271,4 +714,231 @@ mod tests {
         assert!(validate_friendly_name("device\tname").is_err()); // tab
         assert!(validate_friendly_name("device\x00name").is_err()); // null byte
     }
+
+    #[test]
+    fn test_clamp_limit_absent_uses_default() {
+        assert_eq!(
+            clamp_limit(None, DEFAULT_PAGE_LIMIT, MAX_DEVICES_PAGE_LIMIT),
+            50
+        );
+        assert_eq!(
+            parse_and_clamp_limit(None, DEFAULT_PAGE_LIMIT, MAX_READINGS_PAGE_LIMIT),
+            50
+        );
+    }
+
+    #[test]
+    fn test_clamp_limit_in_range() {
+        assert_eq!(clamp_limit(Some(1), 50, 100), 1);
+        assert_eq!(clamp_limit(Some(25), 50, 100), 25);
+        assert_eq!(clamp_limit(Some(100), 50, 100), 100);
+        assert_eq!(parse_and_clamp_limit(Some("750"), 50, 1000), 750);
+    }
+
+    #[test]
+    fn test_clamp_limit_below_min() {
+        assert_eq!(clamp_limit(Some(0), 50, 100), 1);
+        assert_eq!(clamp_limit(Some(-5), 50, 100), 1);
+        assert_eq!(parse_and_clamp_limit(Some("-10"), 50, 1000), 1);
+    }
+
+    #[test]
+    fn test_clamp_limit_above_max() {
+        assert_eq!(clamp_limit(Some(150), 50, MAX_DEVICES_PAGE_LIMIT), 100);
+        assert_eq!(clamp_limit(Some(150), 50, MAX_API_KEYS_PAGE_LIMIT), 100);
+        assert_eq!(clamp_limit(Some(5000), 50, MAX_READINGS_PAGE_LIMIT), 1000);
+        assert_eq!(
+            parse_and_clamp_limit(Some("5000"), 50, MAX_EVENTS_PAGE_LIMIT),
+            1000
+        );
+    }
+
+    #[test]
+    fn test_parse_and_clamp_limit_unparseable_uses_default() {
+        assert_eq!(parse_and_clamp_limit(Some("abc"), 50, 100), 50);
+        assert_eq!(parse_and_clamp_limit(Some(""), 50, 100), 50);
+        assert_eq!(parse_and_clamp_limit(Some("1.5"), 50, 100), 50);
+    }
+
+    #[test]
+    fn test_validate_batch_metadata_within_limits() {
+        assert!(validate_batch_metadata(&HashMap::new()).is_ok());
+
+        let meta: HashMap<String, String> = (0..MAX_BATCH_METADATA_KEYS)
+            .map(|i| {
+                (
+                    format!("key{}", i),
+                    "x".repeat(MAX_BATCH_METADATA_VALUE_LEN),
+                )
+            })
+            .collect();
+        assert!(validate_batch_metadata(&meta).is_ok());
+    }
+
+    #[test]
+    fn test_validate_batch_metadata_too_many_keys() {
+        let meta: HashMap<String, String> = (0..=MAX_BATCH_METADATA_KEYS)
+            .map(|i| (format!("key{}", i), "value".to_string()))
+            .collect();
+
+        let err = validate_batch_metadata(&meta).unwrap_err();
+        assert_eq!(err.field, "batch_metadata");
+        assert!(err.message.contains("11 keys"));
+    }
+
+    #[test]
+    fn test_validate_batch_metadata_value_too_long() {
+        let mut meta = HashMap::new();
+        meta.insert("gateway_id".to_string(), "gw-01".to_string());
+        meta.insert(
+            "note".to_string(),
+            "x".repeat(MAX_BATCH_METADATA_VALUE_LEN + 1),
+        );
+
+        let err = validate_batch_metadata(&meta).unwrap_err();
+        assert!(err.message.contains("'note'"));
+    }
+
+    #[test]
+    fn test_validate_body_size_just_under_and_over_limit() {
+        assert!(validate_body_size(None, 1024, 1024).is_ok());
+        assert!(validate_body_size(Some("1024"), 1024, 1024).is_ok());
+
+        let err = validate_body_size(None, 1025, 1024).unwrap_err();
+        assert_eq!(err.field, "body");
+        assert!(err.message.starts_with("payload too large"));
+    }
+
+    #[test]
+    fn test_validate_body_size_content_length() {
+        // Declared length over the limit is rejected even for a small body
+        assert!(validate_body_size(Some("5000000"), 10, 1024).is_err());
+        // Understated header does not hide an oversized body
+        assert!(validate_body_size(Some("10"), 2048, 1024).is_err());
+        // Unparseable header falls back to the buffered length
+        assert!(validate_body_size(Some("lots"), 10, 1024).is_ok());
+    }
+
+    fn consistency_reading(
+        bme280: &str,
+        bme280_temp_c: Option<f64>,
+        humidity_pct: Option<f64>,
+        soil_moisture: &str,
+        soil_moisture_pct: Option<f64>,
+    ) -> Reading {
+        Reading {
+            batch_id: "batch1".to_string(),
+            hardware_id: "AA:BB:CC:DD:EE:FF".to_string(),
+            timestamp_ms: 1704067800000,
+            boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            firmware_version: "1.0.16".to_string(),
+            friendly_name: None,
+            sensors: crate::domain::SensorValues {
+                bme280_temp_c,
+                ds18b20_temp_c: None,
+                humidity_pct,
+                pressure_hpa: None,
+                soil_moisture_pct,
+                extra_sensors: HashMap::new(),
+            },
+            sensor_status: crate::domain::SensorStatus {
+                bme280: bme280.to_string(),
+                ds18b20: "unavailable".to_string(),
+                soil_moisture: soil_moisture.to_string(),
+                extra_status: HashMap::new(),
+            },
+            was_buffered: false,
+            quality: None,
+            seq: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_status_value_consistency_ok_without_value() {
+        let err = validate_status_value_consistency(&consistency_reading(
+            "ok",
+            Some(22.5),
+            Some(45.0),
+            "ok",
+            None,
+        ))
+        .unwrap_err();
+        assert_eq!(err.field, "sensor_status.soil_moisture");
+        assert!(err.message.contains("soil_moisture_pct is missing"));
+
+        // BME280 ok requires its temperature even when humidity is present
+        let err = validate_status_value_consistency(&consistency_reading(
+            "ok",
+            None,
+            Some(45.0),
+            "ok",
+            Some(40.0),
+        ))
+        .unwrap_err();
+        assert_eq!(err.field, "sensor_status.bme280");
+        assert!(err.message.contains("bme280_temp_c is missing"));
+    }
+
+    #[test]
+    fn test_status_value_consistency_error_with_value() {
+        let err = validate_status_value_consistency(&consistency_reading(
+            "ok",
+            Some(22.5),
+            None,
+            "error",
+            Some(40.0),
+        ))
+        .unwrap_err();
+        assert_eq!(err.field, "sensor_status.soil_moisture");
+
+        // Any BME280 value conflicts with a BME280 error, not just temperature
+        let err = validate_status_value_consistency(&consistency_reading(
+            "error",
+            None,
+            Some(45.0),
+            "ok",
+            Some(40.0),
+        ))
+        .unwrap_err();
+        assert_eq!(err.field, "sensor_status.bme280");
+        assert!(err.message.contains("humidity_pct has a value"));
+    }
+
+    #[test]
+    fn test_status_value_consistency_accepts_consistent_readings() {
+        // Every sensor ok with values
+        assert!(validate_status_value_consistency(&consistency_reading(
+            "ok",
+            Some(22.5),
+            Some(45.0),
+            "ok",
+            Some(40.0),
+        ))
+        .is_ok());
+
+        // BME280 ok with humidity and pressure missing
+        assert!(validate_status_value_consistency(&consistency_reading(
+            "ok",
+            Some(22.5),
+            None,
+            "ok",
+            Some(40.0),
+        ))
+        .is_ok());
+
+        // Errors without values, and other statuses with or without values
+        assert!(validate_status_value_consistency(&consistency_reading(
+            "error", None, None, "error", None,
+        ))
+        .is_ok());
+        assert!(validate_status_value_consistency(&consistency_reading(
+            "unavailable",
+            Some(22.5),
+            None,
+            "unavailable",
+            None,
+        ))
+        .is_ok());
+    }
 }
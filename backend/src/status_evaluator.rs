@@ -0,0 +1,223 @@
+// Status evaluator binary entry point
+//
+// Invoked on a schedule. Derives each device's health category from the
+// device status table and appends a status transition whenever it changed.
+
+#[allow(dead_code)]
+#[path = "control/error.rs"]
+mod error;
+
+mod repo {
+    #[path = "../control/repo/status_transitions.rs"]
+    pub mod status_transitions;
+}
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use std::collections::HashMap;
+use std::env;
+use tracing::{error, info};
+
+use error::DatabaseError;
+use esp32_backend::shared::plant_insights::HealthCategory;
+use esp32_backend::shared::status_transition::derive_health_category;
+
+/// Health-relevant fields of a device status item
+#[derive(Debug, PartialEq)]
+struct DeviceHealthFields {
+    hardware_id: String,
+    last_seen_ingest_time_ms: Option<i64>,
+    last_error_at_ms: Option<i64>,
+}
+
+impl DeviceHealthFields {
+    fn health_category(&self, now_ms: i64) -> HealthCategory {
+        derive_health_category(self.last_seen_ingest_time_ms, self.last_error_at_ms, now_ms)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .with_target(false)
+        .without_time()
+        .init();
+
+    let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .load()
+        .await;
+    let dynamodb_client = DynamoDbClient::new(&config);
+
+    let device_status_table = env::var("DEVICE_STATUS_TABLE")
+        .expect("DEVICE_STATUS_TABLE environment variable must be set");
+    let status_transitions_table = env::var("STATUS_TRANSITIONS_TABLE")
+        .expect("STATUS_TRANSITIONS_TABLE environment variable must be set");
+
+    run(service_fn(|event: LambdaEvent<serde_json::Value>| {
+        function_handler(
+            event,
+            dynamodb_client.clone(),
+            device_status_table.clone(),
+            status_transitions_table.clone(),
+        )
+    }))
+    .await
+}
+
+async fn function_handler(
+    _event: LambdaEvent<serde_json::Value>,
+    dynamodb_client: DynamoDbClient,
+    device_status_table: String,
+    status_transitions_table: String,
+) -> Result<(), Error> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let devices = scan_device_health(&dynamodb_client, &device_status_table).await?;
+
+    let mut transition_count = 0;
+    let mut error_count = 0;
+
+    // One device failing to record must not block the rest
+    for device in &devices {
+        let category = device.health_category(now_ms);
+        match repo::status_transitions::record_health_category(
+            &dynamodb_client,
+            &status_transitions_table,
+            &device.hardware_id,
+            category,
+            now_ms,
+        )
+        .await
+        {
+            Ok(Some(transition)) => {
+                transition_count += 1;
+                info!(
+                    hardware_id = %device.hardware_id,
+                    from = ?transition.from,
+                    to = ?transition.to,
+                    "Recorded status transition"
+                );
+            }
+            Ok(None) => {}
+            Err(e) => {
+                error_count += 1;
+                error!(
+                    hardware_id = %device.hardware_id,
+                    error = %e,
+                    "Failed to record status transition"
+                );
+            }
+        }
+    }
+
+    info!(
+        devices = devices.len(),
+        transitions = transition_count,
+        errors = error_count,
+        "Status evaluation complete"
+    );
+
+    Ok(())
+}
+
+/// Read the health-relevant fields of every device status item
+async fn scan_device_health(
+    client: &DynamoDbClient,
+    table_name: &str,
+) -> Result<Vec<DeviceHealthFields>, DatabaseError> {
+    let mut devices = Vec::new();
+    let mut start_key = None;
+
+    loop {
+        let result = client
+            .scan()
+            .table_name(table_name)
+            .projection_expression("hardware_id, last_seen_ingest_time_ms, last_error_at_ms")
+            .set_exclusive_start_key(start_key)
+            .send()
+            .await
+            .map_err(|e| DatabaseError::DynamoDb(format!("{:?}", e)))?;
+
+        for item in result.items.unwrap_or_default() {
+            devices.push(item_to_health_fields(&item)?);
+        }
+
+        match result.last_evaluated_key {
+            Some(key) if !key.is_empty() => start_key = Some(key),
+            _ => return Ok(devices),
+        }
+    }
+}
+
+/// Convert a device status item to its health-relevant fields
+fn item_to_health_fields(
+    item: &HashMap<String, AttributeValue>,
+) -> Result<DeviceHealthFields, DatabaseError> {
+    let number = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse::<i64>().ok())
+    };
+
+    let hardware_id = item
+        .get("hardware_id")
+        .and_then(|v| v.as_s().ok())
+        .ok_or_else(|| DatabaseError::Serialization("Missing hardware_id".to_string()))?
+        .clone();
+
+    Ok(DeviceHealthFields {
+        hardware_id,
+        last_seen_ingest_time_ms: number("last_seen_ingest_time_ms"),
+        last_error_at_ms: number("last_error_at_ms"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_to_health_fields() {
+        let mut item = HashMap::new();
+        item.insert(
+            "hardware_id".to_string(),
+            AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()),
+        );
+        item.insert(
+            "last_seen_ingest_time_ms".to_string(),
+            AttributeValue::N("1704067200000".to_string()),
+        );
+
+        let fields = item_to_health_fields(&item).unwrap();
+        assert_eq!(fields.hardware_id, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(fields.last_seen_ingest_time_ms, Some(1704067200000));
+        assert_eq!(fields.last_error_at_ms, None);
+
+        // Seen an hour ago, no errors
+        assert_eq!(
+            fields.health_category(1704067200000 + 3_600_000),
+            HealthCategory::Healthy
+        );
+    }
+
+    #[test]
+    fn test_item_without_ingest_time_is_missing() {
+        let mut item = HashMap::new();
+        item.insert(
+            "hardware_id".to_string(),
+            AttributeValue::S("AA:BB:CC:DD:EE:FF".to_string()),
+        );
+
+        let fields = item_to_health_fields(&item).unwrap();
+        assert_eq!(
+            fields.health_category(1704067200000),
+            HealthCategory::Missing
+        );
+    }
+
+    #[test]
+    fn test_item_without_hardware_id_is_rejected() {
+        assert!(item_to_health_fields(&HashMap::new()).is_err());
+    }
+}
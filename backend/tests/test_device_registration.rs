@@ -312,6 +312,9 @@ mod tests {
             first_registered_at: now.clone(),
             last_seen_at: now.clone(),
             last_boot_id: boot_id.to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
         };
 
         // Verify device fields
@@ -353,6 +356,9 @@ mod tests {
             first_registered_at: "2024-01-15T08:00:00Z".to_string(),
             last_seen_at: "2024-01-15T09:00:00Z".to_string(),
             last_boot_id: original_boot_id.to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
         };
 
         // Simulate device existing
@@ -402,6 +408,9 @@ mod tests {
             first_registered_at: initial_clock.now_rfc3339(),
             last_seen_at: initial_clock.now_rfc3339(),
             last_boot_id: original_boot_id.to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
         };
 
         // Simulate re-registration with new timestamp and boot_id
@@ -657,6 +666,8 @@ mod tests {
             last_used_at: Some("2024-01-15T10:20:00Z".to_string()),
             is_active: false, // Revoked
             description: Some("Revoked test key".to_string()),
+            scopes: vec![],
+            rate_limit_per_minute: None,
         };
 
         // Verify key is not active
@@ -676,6 +687,8 @@ mod tests {
             last_used_at: Some("2024-01-15T10:20:00Z".to_string()),
             is_active: true, // Active
             description: Some("Active test key".to_string()),
+            scopes: vec![],
+            rate_limit_per_minute: None,
         };
 
         // Verify key is active
@@ -736,6 +749,9 @@ mod tests {
             first_registered_at: now.clone(),
             last_seen_at: now.clone(),
             last_boot_id: boot_id.to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
         };
 
         // Step 7: Verify device record
@@ -793,6 +809,9 @@ mod tests {
             first_registered_at: initial_clock.now_rfc3339(),
             last_seen_at: initial_clock.now_rfc3339(),
             last_boot_id: "7c9e6679-7425-40de-944b-e07fc1f90ae7".to_string(),
+            first_reading_ms: None,
+            last_reading_ms: None,
+            readings_this_boot: None,
         });
 
         assert!(existing_device.is_some());